@@ -0,0 +1,390 @@
+//! Admin API, to manage the tools of a running server.
+//!
+//! Served under `/admin` (`/admin/<name>` for the servers of a mounts file)
+//! when an admin token is configured, to clients presenting it as a bearer
+//! token:
+//!
+//! - `GET /admin/tools`: the tools and whether they are enabled
+//! - `POST /admin/tools/{name}/enable` and `POST /admin/tools/{name}/disable`
+//! - `POST /admin/reload`: load the spec again and regenerate the tools
+//! - `GET /admin/stats`: statistics of the tool registry
+//!
+//! Disabled tools are hidden from every session and refused like unknown
+//! tools. Reloaded tools serve new sessions, and every request in stateless
+//! mode; sessions already started keep the tools they started with.
+
+use crate::configuration::Configuration;
+use actix_web::{HttpResponse, web};
+use rmcp::model::LoggingLevel;
+use rmcp_openapi::{DisabledTools, Error, HttpClient, InFlightCalls, Server};
+use serde_json::json;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+use tracing::{info, warn};
+use url::Url;
+
+/// The server sessions are created from, replaced when the spec is reloaded
+#[derive(Clone)]
+pub struct LiveServer {
+    server: Arc<RwLock<Server>>,
+}
+
+impl LiveServer {
+    pub fn new(server: Server) -> Self {
+        Self {
+            server: Arc::new(RwLock::new(server)),
+        }
+    }
+
+    /// Server for a new MCP session
+    pub fn for_session(&self) -> Server {
+        self.read().for_session()
+    }
+
+    /// Number of tools last loaded
+    pub fn tool_count(&self) -> usize {
+        self.read().tool_count()
+    }
+
+    /// Base URL of the upstream API
+    pub fn base_url(&self) -> Url {
+        self.read().base_url.clone()
+    }
+
+    /// HTTP client the tools last loaded send their requests with
+    pub fn upstream_client(&self) -> Result<HttpClient, Error> {
+        self.read().upstream_client()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Server> {
+        self.server.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn replace(&self, server: Server) {
+        *self.server.write().unwrap_or_else(|e| e.into_inner()) = server;
+    }
+}
+
+/// What the admin API manages
+pub struct Admin {
+    /// Configuration the server is reloaded from
+    config: Configuration,
+    live: LiveServer,
+    disabled: DisabledTools,
+    in_flight: InFlightCalls,
+}
+
+impl Admin {
+    /// Manage `live`, which was created from `config` and disables the
+    /// `disabled` tools, counting the tool calls of reloaded servers in
+    /// `in_flight`
+    pub fn new(
+        config: Configuration,
+        live: LiveServer,
+        disabled: DisabledTools,
+        in_flight: InFlightCalls,
+    ) -> Self {
+        Self {
+            config,
+            live,
+            disabled,
+            in_flight,
+        }
+    }
+
+    /// Load the spec again and serve the tools generated from it, keeping
+    /// the current tools if that fails
+    async fn reload(&self) -> Result<usize, Error> {
+        let mut server = self.config.clone().try_into_server().await?;
+        server.in_flight = self.in_flight.clone();
+        // Clients subscribed to log messages stay subscribed
+        server.client_log = self.live.read().client_log.clone();
+        server.disabled_tools = self.disabled.clone();
+        server.load_openapi_spec()?;
+        server.validate_registry()?;
+        let tool_count = server.tool_count();
+        self.live.replace(server);
+        Ok(tool_count)
+    }
+}
+
+/// Register the admin endpoints
+pub fn configure(config: &mut web::ServiceConfig) {
+    config
+        .route("/tools", web::get().to(tools))
+        .route("/tools/{name}/enable", web::post().to(enable))
+        .route("/tools/{name}/disable", web::post().to(disable))
+        .route("/reload", web::post().to(reload))
+        .route("/stats", web::get().to(stats));
+}
+
+async fn tools(admin: web::Data<Admin>) -> HttpResponse {
+    let server = admin.live.read();
+    let mut tools: Vec<_> = server
+        .tool_collection
+        .iter()
+        .map(|tool| {
+            json!({
+                "name": tool.metadata.name,
+                "method": tool.metadata.method,
+                "path": tool.metadata.path,
+                "tags": tool.metadata.tags,
+                "enabled": !admin.disabled.contains(&tool.metadata.name),
+            })
+        })
+        .collect();
+    tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    HttpResponse::Ok().json(json!({ "tools": tools }))
+}
+
+async fn enable(admin: web::Data<Admin>, name: web::Path<String>) -> HttpResponse {
+    set_enabled(&admin, &name, true)
+}
+
+async fn disable(admin: web::Data<Admin>, name: web::Path<String>) -> HttpResponse {
+    set_enabled(&admin, &name, false)
+}
+
+fn set_enabled(admin: &Admin, name: &str, enabled: bool) -> HttpResponse {
+    let known = {
+        let server = admin.live.read();
+        server.has_tool(name)
+            || server
+                .custom_tools
+                .iter()
+                .any(|custom| custom.name() == name)
+    };
+    if !known {
+        return HttpResponse::NotFound().json(json!({
+            "error": format!("Unknown tool '{name}'"),
+        }));
+    }
+    admin.disabled.set(name, !enabled);
+    info!(
+        tool_name = %name,
+        enabled = enabled,
+        "Tool availability changed through the admin API"
+    );
+    HttpResponse::Ok().json(json!({ "name": name, "enabled": enabled }))
+}
+
+async fn reload(admin: web::Data<Admin>) -> HttpResponse {
+    let client_log = admin.live.read().client_log.clone();
+    match admin.reload().await {
+        Ok(tool_count) => {
+            info!(
+                tool_count = tool_count,
+                "Reloaded the OpenAPI specification through the admin API"
+            );
+            if let Some(log) = client_log {
+                log.log(
+                    LoggingLevel::Info,
+                    "admin",
+                    json!({
+                        "message": "Reloaded the OpenAPI specification",
+                        "tool_count": tool_count,
+                    }),
+                );
+            }
+            HttpResponse::Ok().json(json!({ "status": "reloaded", "tool_count": tool_count }))
+        }
+        Err(e) => {
+            warn!(
+                error = %e,
+                "Unable to reload the OpenAPI specification, keeping the current tools"
+            );
+            if let Some(log) = client_log {
+                log.log(
+                    LoggingLevel::Error,
+                    "admin",
+                    json!({
+                        "message": "Unable to reload the OpenAPI specification, keeping the current tools",
+                        "error": e.to_string(),
+                    }),
+                );
+            }
+            HttpResponse::InternalServerError().json(json!({ "error": e.to_string() }))
+        }
+    }
+}
+
+async fn stats(admin: web::Data<Admin>) -> HttpResponse {
+    let server = admin.live.read();
+    let disabled: Vec<_> = admin
+        .disabled
+        .names()
+        .into_iter()
+        .filter(|name| server.has_tool(name))
+        .collect();
+    HttpResponse::Ok().json(json!({
+        "tool_count": server.tool_count(),
+        "enabled_tool_count": server.tool_count() - disabled.len(),
+        "disabled_tools": disabled,
+        "in_flight": admin.in_flight.count(),
+        "stats": server.get_tool_stats(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use crate::endpoint_auth::{EndpointAuth, require_authentication};
+    use actix_web::dev::ServiceResponse;
+    use actix_web::http::StatusCode;
+    use actix_web::http::header::AUTHORIZATION;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test};
+    use clap::Parser;
+    use serde_json::Value;
+
+    const TOKEN: &str = "admin-s3cret";
+
+    fn spec(paths: &[&str]) -> Value {
+        let paths: serde_json::Map<_, _> = paths
+            .iter()
+            .map(|path| {
+                let operation_id = path.trim_start_matches('/');
+                (
+                    path.to_string(),
+                    json!({ "get": {
+                        "operationId": operation_id,
+                        "responses": { "200": { "description": "OK" } }
+                    }}),
+                )
+            })
+            .collect();
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "paths": paths
+        })
+    }
+
+    fn authorized(request: test::TestRequest) -> test::TestRequest {
+        request.insert_header((AUTHORIZATION, format!("Bearer {TOKEN}")))
+    }
+
+    async fn read(response: ServiceResponse) -> (StatusCode, Value) {
+        let status = response.status();
+        (status, test::read_body_json(response).await)
+    }
+
+    #[actix_web::test]
+    async fn tools_are_disabled_and_reloaded() {
+        let spec_path = std::env::temp_dir().join(format!(
+            "rmcp-openapi-admin-spec-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&spec_path, spec(&["/pets"]).to_string()).unwrap();
+        let cli = Cli::try_parse_from([
+            "rmcp-openapi-server",
+            spec_path.to_str().unwrap(),
+            "--base-url",
+            "https://api.example.com",
+        ])
+        .unwrap();
+        let config = Configuration::from_cli(cli).unwrap();
+
+        let disabled = DisabledTools::default();
+        let mut server = config.clone().try_into_server().await.unwrap();
+        server.disabled_tools = disabled.clone();
+        server.load_openapi_spec().unwrap();
+        let live = LiveServer::new(server);
+        let admin = Admin::new(
+            config,
+            live.clone(),
+            disabled.clone(),
+            InFlightCalls::default(),
+        );
+
+        let app = test::init_service(
+            App::new().service(
+                web::scope("/admin")
+                    .app_data(web::Data::new(admin))
+                    .app_data(web::Data::new(EndpointAuth {
+                        token: Some(TOKEN.to_string()),
+                        jwt: None,
+                    }))
+                    .wrap(from_fn(require_authentication))
+                    .configure(configure),
+            ),
+        )
+        .await;
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::get().uri("/admin/tools").to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let (status, _) = read(
+            test::call_service(
+                &app,
+                authorized(test::TestRequest::post().uri("/admin/tools/pets/disable")).to_request(),
+            )
+            .await,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(disabled.contains("pets"));
+        let (_, body) = read(
+            test::call_service(
+                &app,
+                authorized(test::TestRequest::get().uri("/admin/tools")).to_request(),
+            )
+            .await,
+        )
+        .await;
+        assert_eq!(body["tools"][0]["name"], "pets");
+        assert_eq!(body["tools"][0]["enabled"], false);
+
+        let (status, _) = read(
+            test::call_service(
+                &app,
+                authorized(test::TestRequest::post().uri("/admin/tools/owners/disable"))
+                    .to_request(),
+            )
+            .await,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        std::fs::write(&spec_path, spec(&["/pets", "/owners"]).to_string()).unwrap();
+        let (status, body) = read(
+            test::call_service(
+                &app,
+                authorized(test::TestRequest::post().uri("/admin/reload")).to_request(),
+            )
+            .await,
+        )
+        .await;
+        std::fs::remove_file(&spec_path).unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["tool_count"], 2);
+        assert_eq!(live.for_session().tool_count(), 2);
+
+        let (_, body) = read(
+            test::call_service(
+                &app,
+                authorized(test::TestRequest::get().uri("/admin/stats")).to_request(),
+            )
+            .await,
+        )
+        .await;
+        assert_eq!(body["tool_count"], 2);
+        assert_eq!(body["enabled_tool_count"], 1);
+        assert_eq!(body["disabled_tools"], json!(["pets"]));
+
+        let (status, _) = read(
+            test::call_service(
+                &app,
+                authorized(test::TestRequest::post().uri("/admin/reload")).to_request(),
+            )
+            .await,
+        )
+        .await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(live.for_session().tool_count(), 2);
+    }
+}
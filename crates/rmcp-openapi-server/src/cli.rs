@@ -1,114 +1,1291 @@
+use crate::config_file;
 use crate::spec_loader::SpecLocation;
-use clap::Parser;
-use rmcp_openapi::AuthorizationMode;
+use clap::{ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand};
+use rmcp_openapi::{
+    ApiKey, ApiKeySource, AuthorizationBehavior, AuthorizationMode, AuthorizationRule,
+    AuthorizationTemplate, BaseUrlRoute, ClientAuthMethod, ContentEncoding, DuplicateNameStrategy,
+    Error, HmacAlgorithm, HostOverride, HtmlConversion, JsonPath, NullValuePolicy,
+    OutputSchemaMode, RateLimit, RateLimitMode, RedirectMode, SecretResolver, SignatureComponent,
+    SignatureEncoding,
+};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use url::Url;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 #[command(name = "rmcp-openapi-server")]
+#[command(args_override_self = true)]
 #[command(about = "OpenAPI MCP Server - Expose OpenAPI endpoints as MCP tools")]
+#[command(
+    after_help = "Secret options (passwords, client secrets, tokens and API keys) also accept 'env:VAR' or 'file:PATH' to read the secret from an environment variable or a file."
+)]
 pub struct Cli {
     /// `OpenAPI` specification URL or file path
-    pub spec: SpecLocation,
+    #[arg(env = "RMCP_SPEC", required_unless_present = "mounts")]
+    pub spec: Option<SpecLocation>,
 
-    /// Base URL to override the one in the `OpenAPI` spec
-    #[arg(long)]
-    pub base_url: String,
+    /// Base URL to override the one in the `OpenAPI` spec. Unix domain socket
+    /// targets are accepted as `unix:///path/to.sock` or, to keep a base path,
+    /// `http+unix://%2Fpath%2Fto.sock/v1`.
+    #[arg(long, env = "RMCP_BASE_URL", required_unless_present = "mounts")]
+    pub base_url: Option<String>,
+
+    #[arg(
+        long = "base-url-route",
+        env = "RMCP_BASE_URL_ROUTES",
+        value_delimiter = ',',
+        help = "Base URL for the tools of a tag or path prefix as 'tag:TAG=URL' or 'path:PREFIX=URL' (e.g. 'tag:store=https://store.internal'), overriding --base-url; the first matching route wins"
+    )]
+    pub base_url_routes: Vec<BaseUrlRoute>,
+
+    /// JSON file of OpenAPI servers to mount under `/mcp/<name>`, each given
+    /// as the arguments it would be started with, instead of a single server
+    /// at `/mcp`
+    #[arg(long, env = "RMCP_MOUNTS", conflicts_with_all = ["spec", "base_url"])]
+    pub mounts: Option<PathBuf>,
+
+    /// TOML, YAML or JSON file of options, named as on the command line.
+    /// Command line flags and environment variables override its values.
+    #[arg(long, env = "RMCP_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// URL path to serve the MCP server under, the servers of a mounts file
+    /// being served under `<path>/<name>`
+    #[arg(long, env = "RMCP_PATH", default_value = "/mcp")]
+    pub path: String,
 
     /// Port to bind the MCP server to
-    #[arg(long, short = 'p', default_value = "8080")]
+    #[arg(long, short = 'p', env = "RMCP_PORT", default_value = "8080")]
     pub port: u16,
 
-    /// Address to bind the MCP server to
-    #[arg(long, default_value = "127.0.0.1")]
-    pub bind_address: String,
+    /// Address to bind the MCP server to, or `unix:<path>` for a Unix socket
+    #[arg(
+        long,
+        visible_alias = "bind",
+        env = "RMCP_BIND_ADDRESS",
+        default_value = "127.0.0.1"
+    )]
+    pub bind_address: String,
+
+    /// HTTP headers to add to all requests (format: "name: value")
+    #[arg(long = "header", env = "RMCP_HEADERS", action = clap::ArgAction::Append, help = "HTTP headers to add to all requests in 'name: value' format, the value taking the placeholders {uuid}, {session_id} and {tool} (can be used multiple times)")]
+    pub headers: Vec<String>,
+
+    /// Filter operations by tags (comma-separated)
+    #[arg(
+        long,
+        env = "RMCP_TAGS",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Only include operations with these tags (comma-separated, normalized to kebab-case)"
+    )]
+    pub tags: Option<Vec<String>>,
+
+    /// Filter operations by HTTP methods (comma-separated)
+    #[arg(
+        long,
+        env = "RMCP_METHODS",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Only include operations with these HTTP methods (comma-separated: GET,POST,PUT,DELETE,PATCH,HEAD,OPTIONS,TRACE)"
+    )]
+    pub methods: Option<Vec<reqwest::Method>>,
+
+    #[arg(
+        long,
+        env = "RMCP_READ_ONLY",
+        default_value_t = false,
+        help = "Register only GET and HEAD operations and refuse any other upstream request, for exploratory sessions that must not change data"
+    )]
+    pub read_only: bool,
+
+    /// Filter operations by OperationId
+    #[arg(
+        long,
+        env = "RMCP_OPERATIONIDS_INCLUDE",
+        num_args(1..),
+        value_delimiter = ',',
+        conflicts_with = "operationids_exclude",
+        help = "Only include operations with these OperationIds (comma-separated)"
+    )]
+    pub operationids_include: Option<Vec<String>>,
+
+    /// Filter operations by OperationId
+    #[arg(
+        long,
+        env = "RMCP_OPERATIONIDS_EXCLUDE",
+        num_args(1..),
+        value_delimiter = ',',
+        conflicts_with = "operationids_include",
+        help = "Exclude operations with these OperationIds (comma-separated)"
+    )]
+    pub operationids_exclude: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        env = "RMCP_PARAMETER_POLICY",
+        help = "JSON file of constraints added to tool parameters on top of the spec, e.g. a fixed project_id or a lower per_page maximum; enforced on calls and shown in tool schemas"
+    )]
+    pub parameter_policy: Option<PathBuf>,
+
+    /// Authorization mode for handling Authorization headers
+    #[arg(
+        long,
+        env = "RMCP_AUTHORIZATION_MODE",
+        default_value = "compliant",
+        help = "How to handle Authorization headers: compliant (MCP spec, no passthrough), passthrough-warn (pass with warnings), passthrough-silent (pass silently)"
+    )]
+    pub authorization_mode: AuthorizationMode,
+
+    #[arg(
+        long = "authorization-rule",
+        env = "RMCP_AUTHORIZATION_RULES",
+        hide_env_values = true,
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Authorization behavior for some tools, overriding --authorization-mode: 'tool:NAME=BEHAVIOR' or 'tag:TAG=BEHAVIOR', where BEHAVIOR is an authorization mode or 'token:VALUE' to send that bearer token instead (comma-separated, first match wins)"
+    )]
+    pub authorization_rules: Vec<AuthorizationRule>,
+
+    #[arg(
+        long = "authorization-header",
+        env = "RMCP_AUTHORIZATION_HEADERS",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Send forwarded client authorization as this header instead of Authorization: 'Name: template', with {header}, {scheme} and {token} placeholders, e.g. 'X-Api-Key: {token}' (comma-separated)"
+    )]
+    pub authorization_templates: Vec<AuthorizationTemplate>,
+
+    #[arg(
+        long,
+        env = "RMCP_TOKEN_EXCHANGE_URL",
+        help = "Exchange forwarded client tokens at this security token service endpoint (RFC 8693) before sending them upstream"
+    )]
+    pub token_exchange_url: Option<Url>,
+
+    #[arg(
+        long,
+        env = "RMCP_TOKEN_EXCHANGE_AUDIENCE",
+        requires = "token_exchange_url",
+        help = "Audience of the tokens requested from the security token service"
+    )]
+    pub token_exchange_audience: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_TOKEN_EXCHANGE_RESOURCE",
+        requires = "token_exchange_url",
+        help = "Resource URI of the tokens requested from the security token service"
+    )]
+    pub token_exchange_resource: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_TOKEN_EXCHANGE_SCOPES",
+        requires = "token_exchange_url",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Scopes of the tokens requested from the security token service (comma-separated)"
+    )]
+    pub token_exchange_scopes: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_TOKEN_EXCHANGE_CLIENT_ID",
+        requires_all = ["token_exchange_url", "token_exchange_client_secret"],
+        help = "Client ID authenticating this server to the security token service"
+    )]
+    pub token_exchange_client_id: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_TOKEN_EXCHANGE_CLIENT_SECRET",
+        hide_env_values = true,
+        requires = "token_exchange_client_id",
+        help = "Client secret authenticating this server to the security token service"
+    )]
+    pub token_exchange_client_secret: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_CHECK_SCOPES",
+        help = "Reject calls whose forwarded JWT lacks the OAuth2 scopes the operation requires, read from its scope or scp claim"
+    )]
+    pub check_scopes: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_INTROSPECTION_URL",
+        conflicts_with = "check_scopes",
+        help = "Check forwarded tokens against the operation's required scopes using this OAuth2 token introspection endpoint (RFC 7662)"
+    )]
+    pub introspection_url: Option<Url>,
+
+    #[arg(
+        long,
+        env = "RMCP_INTROSPECTION_CLIENT_ID",
+        requires_all = ["introspection_url", "introspection_client_secret"],
+        help = "Client ID authenticating this server to the introspection endpoint"
+    )]
+    pub introspection_client_id: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_INTROSPECTION_CLIENT_SECRET",
+        hide_env_values = true,
+        requires = "introspection_client_id",
+        help = "Client secret authenticating this server to the introspection endpoint"
+    )]
+    pub introspection_client_secret: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_JWKS_URL",
+        help = "Require MCP clients to present a JWT bearer token signed by a key of this JSON Web Key Set"
+    )]
+    pub jwks_url: Option<Url>,
+
+    #[arg(
+        long,
+        env = "RMCP_JWT_ISSUER",
+        requires = "jwks_url",
+        help = "Issuer (iss claim) required in MCP client tokens"
+    )]
+    pub jwt_issuer: Option<String>,
+
+    #[arg(
+        long = "jwt-audience",
+        env = "RMCP_JWT_AUDIENCES",
+        requires = "jwks_url",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Audience (aud claim) accepted in MCP client tokens (comma-separated, any one suffices)"
+    )]
+    pub jwt_audiences: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_MCP_AUTH_TOKEN",
+        hide_env_values = true,
+        help = "Require MCP clients to present this bearer token"
+    )]
+    pub mcp_auth_token: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_MCP_AUTH_TOKEN_FILE",
+        conflicts_with = "mcp_auth_token",
+        help = "Require MCP clients to present the bearer token stored in this file"
+    )]
+    pub mcp_auth_token_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "RMCP_ADMIN_TOKEN",
+        hide_env_values = true,
+        help = "Serve the admin API, to enable and disable tools and reload the spec, to clients presenting this bearer token"
+    )]
+    pub admin_token: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_SKIP_TOOL_DESCRIPTIONS",
+        default_value_t = false,
+        help = "Exclude the tool descriptions from the generated MCP schema"
+    )]
+    pub skip_tool_descriptions: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_SKIP_PARAMETER_DESCRIPTIONS",
+        default_value_t = false,
+        help = "Exclude the parameter descriptions from the generated MCP schema"
+    )]
+    pub skip_parameter_descriptions: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_STATEFUL",
+        default_value_t = false,
+        help = "Enable stateful session mode for MCP transport (required for Claude Code)"
+    )]
+    pub stateful: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_SHUTDOWN_TIMEOUT_SECS",
+        default_value_t = 30,
+        help = "On SIGTERM or Ctrl-C, seconds to wait for tool calls in progress to finish before exiting"
+    )]
+    pub shutdown_timeout_secs: u64,
+
+    #[arg(
+        long,
+        env = "RMCP_SESSION_CONFIGURATION",
+        default_value_t = false,
+        requires = "stateful",
+        help = "Let each MCP session set its own upstream bearer token, base URL and tool tags, through its initialize request or the configure_session tool"
+    )]
+    pub session_configuration: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_TOOLS_PAGE_SIZE",
+        help = "Maximum number of tools per tools/list page, clients following the returned cursor for the next ones (default: all tools in one page)"
+    )]
+    pub tools_page_size: Option<NonZeroUsize>,
+
+    #[arg(
+        long,
+        env = "RMCP_SPEC_RESOURCES",
+        default_value_t = false,
+        help = "Expose the OpenAPI spec, the operations of each tag and the component schemas as MCP resources"
+    )]
+    pub spec_resources: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_TOOL_PROMPTS",
+        default_value_t = false,
+        help = "Offer an MCP prompt per tool, describing its workflow and asking for its parameters"
+    )]
+    pub tool_prompts: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_TOOL_SEARCH",
+        default_value_t = false,
+        help = "Offer a search_tools tool finding tools by name, description or tag, for APIs with many operations"
+    )]
+    pub tool_search: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_COMPACT_TOOL_LISTINGS",
+        default_value_t = false,
+        help = "List tools by name and one-line description only, offering a describe_tool tool returning the parameters of each on demand"
+    )]
+    pub compact_tool_listings: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_BATCH_CALLS",
+        default_value_t = false,
+        help = "Offer a batch_call tool calling several tools in order, feeding the results of earlier calls into later ones"
+    )]
+    pub batch_calls: bool,
+
+    #[arg(
+        long = "tool-alias",
+        env = "RMCP_TOOL_ALIASES",
+        value_delimiter = ',',
+        help = "Extra name of a tool, listed alongside it, as 'alias=tool' (e.g. 'list_mrs=get_projects_id_merge_requests'); repeat for several aliases"
+    )]
+    pub tool_aliases: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_PROGRESS_THRESHOLD_SECS",
+        help = "Send progress notifications, to clients asking for them, for tool calls running longer than this many seconds"
+    )]
+    pub progress_threshold_secs: Option<u64>,
+
+    #[arg(
+        long,
+        env = "RMCP_QUEUE_WORKERS",
+        help = "Maximum tool calls executing at the same time, the next ones waiting in a bounded queue (default: unlimited)"
+    )]
+    pub queue_workers: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_QUEUE_DEPTH",
+        requires = "queue_workers",
+        help = "Maximum tool calls waiting for a worker; further calls fail with a 'server-busy' error (default: 100)"
+    )]
+    pub queue_depth: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_QUEUE_TIMEOUT_SECS",
+        requires = "queue_workers",
+        help = "Seconds a tool call waits for a worker before failing with a 'server-busy' error (default: 30)"
+    )]
+    pub queue_timeout_secs: Option<u64>,
+
+    #[arg(
+        long,
+        env = "RMCP_CLIENT_LOGGING",
+        help = "Stream security events, upstream errors and spec reloads to MCP clients subscribing with logging/setLevel"
+    )]
+    pub client_logging: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_CORS_ORIGINS",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Origins allowed to call the server from a browser (comma-separated, '*' for any)"
+    )]
+    pub cors_origins: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        env = "RMCP_CORS_HEADERS",
+        num_args(1..),
+        value_delimiter = ',',
+        requires = "cors_origins",
+        help = "Request headers allowed from browsers (comma-separated, default: the headers of the MCP streamable HTTP transport)"
+    )]
+    pub cors_headers: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        env = "RMCP_CORS_METHODS",
+        num_args(1..),
+        value_delimiter = ',',
+        requires = "cors_origins",
+        help = "Methods allowed from browsers (comma-separated, default: GET,POST,DELETE)"
+    )]
+    pub cors_methods: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        env = "RMCP_RESPONSE_HEADERS",
+        help = "Add the upstream response headers, redacted by --redact-headers, to the structured content of tool results"
+    )]
+    pub response_headers: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_FOLLOW_PAGES",
+        value_name = "MAX_PAGES",
+        help = "Follow the next pages of GET listings (Link headers, cursors, page numbers) up to this many pages, merging their items"
+    )]
+    pub follow_pages: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_RESPONSE_FILTER",
+        help = "Give every tool a 'response_filter' parameter, JSONPath expressions selecting the values of the response body to return"
+    )]
+    pub response_filter: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_DRY_RUNS",
+        help = "Give every tool a 'dry_run' parameter returning the request the call would send, with credentials redacted, instead of sending it"
+    )]
+    pub dry_runs: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_TRANSFORMER_PIPELINES",
+        help = "JSON file of response transformer pipelines (truncate, redact, project, format) applied to the tools they match by name or tag"
+    )]
+    pub transformer_pipelines: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "RMCP_MARKDOWN_TABLES",
+        help = "Render responses listing flat objects as Markdown tables in the text content of tool results, keeping the JSON in the structured content"
+    )]
+    pub markdown_tables: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_MARKDOWN_TABLE_ROWS",
+        requires = "markdown_tables",
+        help = "Maximum rows of Markdown tables (default: 50)"
+    )]
+    pub markdown_table_rows: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_HTML_TO",
+        value_name = "FORMAT",
+        help = "Convert text/html response bodies before returning them: text (strip tags) or markdown"
+    )]
+    pub html_to: Option<HtmlConversion>,
+
+    #[arg(
+        long,
+        env = "RMCP_BINARY_RESOURCES",
+        help = "Keep binary responses other than images (PDF, zip...) in memory and return resource links to them, read with resources/read, instead of their content"
+    )]
+    pub binary_resources: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_BINARY_RESOURCES_MAX_BYTES",
+        requires = "binary_resources",
+        help = "Total size of the binary responses kept, the oldest being dropped first (default: 67108864)"
+    )]
+    pub binary_resources_max_bytes: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_BINARY_RESOURCES_TTL_SECS",
+        requires = "binary_resources",
+        help = "Seconds a kept binary response can be read (default: 900)"
+    )]
+    pub binary_resources_ttl_secs: Option<u64>,
+
+    #[arg(
+        long,
+        env = "RMCP_RESPONSE_BUDGET_BYTES",
+        value_name = "BYTES",
+        help = "Largest JSON body returned by a tool call; larger ones keep the first items of their arrays and come with a summary of what was cut"
+    )]
+    pub response_budget_bytes: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_RESPONSE_BUDGET_PREVIEW_ITEMS",
+        requires = "response_budget_bytes",
+        help = "Items of each array kept in bodies cut to the response budget (default: 3)"
+    )]
+    pub response_budget_preview_items: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_TYPED_ERRORS",
+        help = "Read 4xx and 5xx response bodies with the spec's error response schemas, adding their code and message as a typed 'error' to tool results"
+    )]
+    pub typed_errors: bool,
+
+    #[arg(
+        long = "success-status",
+        env = "RMCP_SUCCESS_STATUSES",
+        value_delimiter = ',',
+        help = "Upstream status outside 2xx returned as a successful tool result by every tool (e.g. '404'); repeat for several statuses"
+    )]
+    pub success_statuses: Vec<u16>,
+
+    #[arg(
+        long = "tool-success-status",
+        env = "RMCP_TOOL_SUCCESS_STATUSES",
+        value_delimiter = ',',
+        help = "Upstream status outside 2xx returned as a successful result by a single tool, as 'tool=<status>' (e.g. 'searchPets=404'); repeat for several statuses"
+    )]
+    pub tool_success_statuses: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_OUTPUT_SCHEMAS",
+        default_value = "strict",
+        help = "Tool output schemas: strict (forbid undeclared top-level fields), lenient (allow them), unwrapped (the response body schema, results holding the body alone) or disabled (unstructured results only)"
+    )]
+    pub output_schemas: OutputSchemaMode,
+
+    #[arg(
+        long = "no-output-schema",
+        env = "RMCP_NO_OUTPUT_SCHEMA",
+        value_delimiter = ',',
+        value_name = "TOOL",
+        help = "Advertise no output schema for this tool, which then returns unstructured results only; repeat for several tools"
+    )]
+    pub no_output_schema: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_DUPLICATE_TOOL_NAMES",
+        help = "When operations produce the same tool name: error (refuse the spec), suffix (rename the later tools after their method and a hash of their path) or last-wins (keep the last operation)"
+    )]
+    pub duplicate_tool_names: Option<DuplicateNameStrategy>,
+
+    #[arg(
+        long,
+        visible_alias = "insecure-skip-tls-verify",
+        env = "RMCP_INSECURE",
+        default_value_t = false,
+        help = "Disable TLS certificate verification for all outbound HTTPS requests (mirrors curl --insecure). DANGEROUS: only use in trusted environments."
+    )]
+    pub insecure: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_STRIP_NON_STANDARD_BODIES",
+        default_value_t = false,
+        help = "Never send request bodies on GET, HEAD, DELETE, OPTIONS or TRACE requests, even when the spec declares one (for proxies that reject them)"
+    )]
+    pub strip_non_standard_bodies: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_NULL_QUERY_VALUES",
+        default_value = "literal",
+        help = "How null query parameter values are sent: omit (leave the parameter out), empty (send 'name='), literal (send 'name=null')"
+    )]
+    pub null_query_values: NullValuePolicy,
+
+    #[arg(
+        long,
+        env = "RMCP_MAX_RETRIES",
+        default_value_t = 0,
+        help = "Retry failed upstream requests (429, 5xx, connection errors) up to this many times with exponential backoff (0 disables retries)"
+    )]
+    pub max_retries: u32,
+
+    #[arg(
+        long,
+        env = "RMCP_RETRY_INITIAL_BACKOFF_MS",
+        default_value_t = 200,
+        help = "Delay before the first retry in milliseconds, doubled for each further retry"
+    )]
+    pub retry_initial_backoff_ms: u64,
+
+    #[arg(
+        long,
+        env = "RMCP_RETRY_MAX_BACKOFF_MS",
+        default_value_t = 10_000,
+        help = "Upper bound for the retry delay in milliseconds; longer Retry-After values are not waited for"
+    )]
+    pub retry_max_backoff_ms: u64,
+
+    #[arg(
+        long,
+        env = "RMCP_RETRY_NON_IDEMPOTENT",
+        default_value_t = false,
+        help = "Also retry POST and PATCH requests, which may not be idempotent"
+    )]
+    pub retry_non_idempotent: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_CIRCUIT_BREAKER_THRESHOLD",
+        default_value_t = 0,
+        help = "Fail fast for an upstream host after this many consecutive failures (connection errors, timeouts, 5xx); 0 disables the circuit breaker"
+    )]
+    pub circuit_breaker_threshold: u32,
+
+    #[arg(
+        long,
+        env = "RMCP_CIRCUIT_BREAKER_OPEN_SECS",
+        default_value_t = 30,
+        help = "Seconds an open circuit waits before letting a probe request through"
+    )]
+    pub circuit_breaker_open_secs: u64,
+
+    #[arg(
+        long,
+        env = "RMCP_RATE_LIMIT",
+        help = "Maximum upstream request rate shared by all tools, as '<requests>/<period>' with period s, m or h (e.g. '10/s')"
+    )]
+    pub rate_limit: Option<RateLimit>,
+
+    #[arg(
+        long = "tool-rate-limit",
+        env = "RMCP_TOOL_RATE_LIMITS",
+        value_delimiter = ',',
+        help = "Rate limit for a single tool as 'tool=<requests>/<period>' (e.g. 'searchPets=1/s'); applied on top of --rate-limit"
+    )]
+    pub tool_rate_limits: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_RATE_LIMIT_MODE",
+        default_value = "queue",
+        help = "What to do when a rate limit is exhausted: queue (wait for a token) or reject (fail the call immediately)"
+    )]
+    pub rate_limit_mode: RateLimitMode,
+
+    #[arg(
+        long,
+        env = "RMCP_MAX_CONCURRENT_REQUESTS",
+        help = "Maximum upstream requests in flight across all sessions; further calls fail with a 'throttled' error (default: unlimited)"
+    )]
+    pub max_concurrent_requests: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_MAX_CONCURRENT_REQUESTS_PER_SESSION",
+        help = "Maximum upstream requests in flight for a single MCP session; further calls fail with a 'throttled' error (default: unlimited)"
+    )]
+    pub max_concurrent_requests_per_session: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_PROXY",
+        help = "Proxy URL for upstream requests and spec downloads (e.g. 'http://proxy.corp:3128'); without it HTTP_PROXY/HTTPS_PROXY are honoured"
+    )]
+    pub proxy: Option<Url>,
+
+    #[arg(
+        long,
+        env = "RMCP_NO_PROXY",
+        requires = "proxy",
+        help = "Comma-separated hosts, domains and CIDR ranges that bypass --proxy (defaults to NO_PROXY)"
+    )]
+    pub no_proxy: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_PROXY_USERNAME",
+        requires = "proxy",
+        help = "Username for proxy basic authentication"
+    )]
+    pub proxy_username: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_PROXY_PASSWORD",
+        requires = "proxy_username",
+        hide_env_values = true,
+        help = "Password for proxy basic authentication"
+    )]
+    pub proxy_password: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_CLIENT_CERT",
+        requires = "client_key",
+        help = "PEM client certificate presented to upstream APIs that require mutual TLS"
+    )]
+    pub client_cert: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "RMCP_CLIENT_KEY",
+        requires = "client_cert",
+        help = "PEM private key for --client-cert"
+    )]
+    pub client_key: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "RMCP_CA_CERT",
+        help = "PEM bundle of extra root certificates to trust for upstream requests and spec downloads (for private PKI)"
+    )]
+    pub ca_cert: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "RMCP_POOL_MAX_IDLE_PER_HOST",
+        help = "Maximum idle connections kept open per upstream host (default: unlimited)"
+    )]
+    pub pool_max_idle_per_host: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_POOL_IDLE_TIMEOUT_SECS",
+        help = "Seconds an idle upstream connection is kept in the pool (default: 90)"
+    )]
+    pub pool_idle_timeout_secs: Option<u64>,
+
+    #[arg(
+        long,
+        env = "RMCP_CONNECT_TIMEOUT_MS",
+        help = "Timeout in milliseconds for establishing an upstream connection, TLS handshake included (default: bounded only by the request timeout)"
+    )]
+    pub connect_timeout_ms: Option<u64>,
+
+    #[arg(
+        long,
+        env = "RMCP_TCP_KEEPALIVE_SECS",
+        help = "Interval in seconds between TCP keepalive probes on upstream connections (default: 15)"
+    )]
+    pub tcp_keepalive_secs: Option<u64>,
+
+    #[arg(
+        long,
+        env = "RMCP_RESOLVE",
+        value_delimiter = ',',
+        help = "Connect to a fixed address for an upstream host as 'host:port:addr' (e.g. 'api.example.com:443:10.0.0.5'), like curl's --resolve; repeat for several addresses"
+    )]
+    pub resolve: Vec<HostOverride>,
+
+    #[arg(
+        long = "tool-timeout",
+        env = "RMCP_TOOL_TIMEOUTS",
+        value_delimiter = ',',
+        help = "Request timeout for a single tool as 'tool=<seconds>' (e.g. 'exportReport=120'); callers can only shorten it"
+    )]
+    pub tool_timeouts: Vec<String>,
+
+    #[arg(
+        long = "tag-timeout",
+        env = "RMCP_TAG_TIMEOUTS",
+        value_delimiter = ',',
+        help = "Request timeout for all tools of an OpenAPI tag as 'tag=<seconds>' (e.g. 'reports=60'); --tool-timeout takes precedence"
+    )]
+    pub tag_timeouts: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_MAX_TIMEOUT_SECS",
+        help = "Hard upper bound in seconds for every upstream request, whatever timeout_seconds the caller passes"
+    )]
+    pub max_timeout_secs: Option<u64>,
 
-    /// HTTP headers to add to all requests (format: "name: value")
-    #[arg(long = "header", action = clap::ArgAction::Append, help = "HTTP headers to add to all requests in 'name: value' format (can be used multiple times)")]
-    pub headers: Vec<String>,
+    #[arg(
+        long,
+        env = "RMCP_CACHE_TTL_SECS",
+        help = "Cache successful GET responses in memory for this many seconds unless Cache-Control says otherwise (default: caching disabled)"
+    )]
+    pub cache_ttl_secs: Option<u64>,
+
+    #[arg(
+        long,
+        env = "RMCP_CACHE_MAX_TTL_SECS",
+        requires = "cache_ttl_secs",
+        help = "Upper bound in seconds for cache lifetimes, including upstream Cache-Control max-age"
+    )]
+    pub cache_max_ttl_secs: Option<u64>,
+
+    #[arg(
+        long,
+        env = "RMCP_CACHE_MAX_ENTRIES",
+        default_value_t = 1000,
+        help = "Maximum number of cached responses kept in memory"
+    )]
+    pub cache_max_entries: usize,
+
+    #[arg(
+        long,
+        env = "RMCP_CACHE_IGNORE_CACHE_CONTROL",
+        default_value_t = false,
+        help = "Cache responses for --cache-ttl-secs regardless of upstream Cache-Control headers"
+    )]
+    pub cache_ignore_cache_control: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_NO_DECOMPRESS_RESPONSES",
+        default_value_t = false,
+        help = "Do not advertise gzip, deflate and brotli support to the upstream API or decode compressed responses"
+    )]
+    pub no_decompress_responses: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_COMPRESS_REQUESTS",
+        help = "Compress JSON request bodies with this coding: gzip, deflate or br (default: uncompressed)"
+    )]
+    pub compress_requests: Option<ContentEncoding>,
+
+    #[arg(
+        long,
+        env = "RMCP_COMPRESS_MIN_BYTES",
+        default_value_t = 1024,
+        requires = "compress_requests",
+        help = "Only compress request bodies of at least this many bytes"
+    )]
+    pub compress_min_bytes: usize,
+
+    #[arg(
+        long,
+        env = "RMCP_MAX_RESPONSE_BYTES",
+        help = "Truncate upstream response bodies larger than this many bytes (default: unlimited)"
+    )]
+    pub max_response_bytes: Option<usize>,
+
+    #[arg(
+        long,
+        env = "RMCP_REDIRECTS",
+        default_value = "follow",
+        help = "Redirect handling for upstream requests: none, follow (up to 10) or a maximum number of redirects"
+    )]
+    pub redirects: RedirectMode,
+
+    #[arg(
+        long,
+        env = "RMCP_FORWARD_CREDENTIALS_ON_REDIRECT",
+        default_value_t = false,
+        help = "Keep Authorization and Cookie headers when a redirect points to another host (by default they are dropped)"
+    )]
+    pub forward_credentials_on_redirect: bool,
 
-    /// Filter operations by tags (comma-separated)
     #[arg(
         long,
+        env = "RMCP_ALLOWED_HOSTS",
         num_args(1..),
         value_delimiter = ',',
-        help = "Only include operations with these tags (comma-separated, normalized to kebab-case)"
+        help = "Only send upstream requests, follow redirects and fetch OAuth2 tokens on these hosts, besides the base URL's; '*.example.com' allows subdomains (comma-separated)"
     )]
-    pub tags: Option<Vec<String>>,
+    pub allowed_hosts: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_HMAC_SECRET",
+        hide_env_values = true,
+        help = "Sign upstream requests with an HMAC keyed with this shared secret (default: unsigned)"
+    )]
+    pub hmac_secret: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_HMAC_HEADER",
+        default_value = "X-Signature",
+        help = "Header carrying the HMAC signature"
+    )]
+    pub hmac_header: String,
+
+    #[arg(
+        long,
+        env = "RMCP_HMAC_ALGORITHM",
+        default_value = "sha256",
+        help = "HMAC hash function: sha256, sha384 or sha512"
+    )]
+    pub hmac_algorithm: HmacAlgorithm,
+
+    #[arg(
+        long,
+        env = "RMCP_HMAC_ENCODING",
+        default_value = "hex",
+        help = "Encoding of the HMAC signature: hex or base64"
+    )]
+    pub hmac_encoding: SignatureEncoding,
+
+    #[arg(
+        long,
+        env = "RMCP_HMAC_PREFIX",
+        default_value = "",
+        help = "Text put before the encoded signature, e.g. 'sha256='"
+    )]
+    pub hmac_prefix: String,
 
-    /// Filter operations by HTTP methods (comma-separated)
     #[arg(
         long,
+        env = "RMCP_HMAC_COMPONENTS",
         num_args(1..),
         value_delimiter = ',',
-        help = "Only include operations with these HTTP methods (comma-separated: GET,POST,PUT,DELETE,PATCH,HEAD,OPTIONS,TRACE)"
+        default_value = "method,path,query,timestamp,body",
+        help = "Request parts covered by the signature, in order (comma-separated: method, path, query, timestamp, body, header:<name>)"
     )]
-    pub methods: Option<Vec<reqwest::Method>>,
+    pub hmac_components: Vec<SignatureComponent>,
+
+    #[arg(
+        long,
+        env = "RMCP_HMAC_SEPARATOR",
+        default_value = "\\n",
+        help = "Separator between signed components; '\\n' stands for a newline"
+    )]
+    pub hmac_separator: String,
 
-    /// Filter operations by OperationId
     #[arg(
         long,
+        env = "RMCP_HMAC_TIMESTAMP_HEADER",
+        default_value = "X-Timestamp",
+        help = "Header carrying the Unix timestamp when the signature covers it"
+    )]
+    pub hmac_timestamp_header: String,
+
+    #[arg(
+        long,
+        env = "RMCP_IDEMPOTENCY_KEYS",
+        default_value_t = false,
+        help = "Attach a fresh UUID idempotency key to each POST call unless the caller supplies one; keyed calls are also retried"
+    )]
+    pub idempotency_keys: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_IDEMPOTENCY_HEADER",
+        default_value = "Idempotency-Key",
+        help = "Header carrying the idempotency key"
+    )]
+    pub idempotency_header: String,
+
+    #[arg(
+        long,
+        env = "RMCP_IDEMPOTENCY_METHODS",
         num_args(1..),
         value_delimiter = ',',
-        conflicts_with = "operationids_exclude",
-        help = "Only include operations with these OperationIds (comma-separated)"
+        default_value = "POST",
+        help = "HTTP methods that get an idempotency key (comma-separated)"
     )]
-    pub operationids_include: Option<Vec<String>>,
+    pub idempotency_methods: Vec<reqwest::Method>,
+
+    #[arg(
+        long,
+        env = "RMCP_CONFIRM_DESTRUCTIVE",
+        default_value_t = false,
+        help = "Answer the first call of a DELETE, PUT or PATCH tool with a one-time confirmation token; only a repeated call carrying the token executes"
+    )]
+    pub confirm_destructive: bool,
 
-    /// Filter operations by OperationId
     #[arg(
         long,
+        env = "RMCP_CONFIRM_METHODS",
         num_args(1..),
         value_delimiter = ',',
-        conflicts_with = "operationids_include",
-        help = "Exclude operations with these OperationIds (comma-separated)"
+        default_value = "DELETE,PUT,PATCH",
+        help = "HTTP methods whose calls need confirmation (comma-separated)"
     )]
-    pub operationids_exclude: Option<Vec<String>>,
+    pub confirm_methods: Vec<reqwest::Method>,
 
-    /// Authorization mode for handling Authorization headers
     #[arg(
         long,
-        env = "RMCP_AUTHORIZATION_MODE",
-        default_value = "compliant",
-        help = "How to handle Authorization headers: compliant (MCP spec, no passthrough), passthrough-warn (pass with warnings), passthrough-silent (pass silently)"
+        env = "RMCP_CONFIRMATION_TTL_SECS",
+        default_value_t = 300,
+        help = "Seconds a confirmation token stays valid"
     )]
-    pub authorization_mode: AuthorizationMode,
+    pub confirmation_ttl_secs: u64,
 
     #[arg(
         long,
-        env = "RMCP_SKIP_TOOL_DESCRIPTIONS",
-        default_value_t = false,
-        help = "Exclude the tool descriptions from the generated MCP schema"
+        env = "RMCP_AUDIT_LOG",
+        conflicts_with = "audit_log_tracing",
+        help = "Append a JSON line per upstream request (method, URL, status, duration, session, redacted headers) to this file"
     )]
-    pub skip_tool_descriptions: bool,
+    pub audit_log: Option<PathBuf>,
 
     #[arg(
         long,
-        env = "RMCP_SKIP_PARAMETER_DESCRIPTIONS",
+        env = "RMCP_AUDIT_LOG_TRACING",
         default_value_t = false,
-        help = "Exclude the parameter descriptions from the generated MCP schema"
+        help = "Emit a log event per upstream request on the 'rmcp_openapi::audit' target instead of writing a file"
     )]
-    pub skip_parameter_descriptions: bool,
+    pub audit_log_tracing: bool,
 
     #[arg(
         long,
-        env = "RMCP_STATEFUL",
-        default_value_t = false,
-        help = "Enable stateful session mode for MCP transport (required for Claude Code)"
+        env = "RMCP_AUDIT_REDACT_HEADERS",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Extra header names redacted in the audit log, on top of Authorization, Proxy-Authorization, Cookie, X-Api-Key and Api-Key (comma-separated)"
     )]
-    pub stateful: bool,
+    pub audit_redact_headers: Vec<String>,
 
     #[arg(
         long,
-        env = "RMCP_INSECURE",
+        env = "RMCP_SECURITY_EVENTS_FILE",
+        help = "Append a JSON line per security event (credentials used, calls denied by the server or the upstream) to this file"
+    )]
+    pub security_events_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "RMCP_SECURITY_WEBHOOK",
+        help = "POST each security event as JSON to this URL, e.g. a SIEM collector"
+    )]
+    pub security_webhook: Option<Url>,
+
+    #[arg(
+        long = "security-webhook-header",
+        env = "RMCP_SECURITY_WEBHOOK_HEADERS",
+        hide_env_values = true,
+        requires = "security_webhook",
+        help = "Header sent with security event webhook requests in 'name: value' format (can be used multiple times)"
+    )]
+    pub security_webhook_headers: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_OTLP_ENDPOINT",
+        help = "Export tool call spans to this OTLP/gRPC collector, e.g. http://collector:4317 (requires the opentelemetry feature)"
+    )]
+    pub otlp_endpoint: Option<Url>,
+
+    #[arg(
+        long,
+        env = "RMCP_REDACT_HEADERS",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "Header names whose values are hidden in error results, logs and the audit log (comma-separated)"
+    )]
+    pub redact_headers: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_REDACT_BODY_PATHS",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "JSONPath locations hidden in request and response bodies of error results and in logs, e.g. '$.user.password' or '$..token' (comma-separated)"
+    )]
+    pub redact_body_paths: Vec<JsonPath>,
+
+    #[arg(
+        long,
+        env = "RMCP_RECORD",
+        conflicts_with = "replay",
+        help = "Record every upstream response to this cassette file (JSON), replacing its contents"
+    )]
+    pub record: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "RMCP_REPLAY",
+        help = "Answer tool calls from this cassette file instead of the upstream API; unmatched calls fail"
+    )]
+    pub replay: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "RMCP_MOCK",
         default_value_t = false,
-        help = "Disable TLS certificate verification for all outbound HTTPS requests (mirrors curl --insecure). DANGEROUS: only use in trusted environments."
+        conflicts_with_all = ["record", "replay"],
+        help = "Never contact the upstream API; answer tool calls with responses generated from the spec's response schemas and examples"
     )]
-    pub insecure: bool,
+    pub mock: bool,
+
+    #[arg(
+        long,
+        env = "RMCP_OAUTH2_CLIENT_ID",
+        requires = "oauth2_client_secret",
+        help = "Authenticate upstream requests with OAuth2 access tokens obtained with the client-credentials grant as this client"
+    )]
+    pub oauth2_client_id: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_OAUTH2_CLIENT_SECRET",
+        hide_env_values = true,
+        requires = "oauth2_client_id",
+        help = "OAuth2 client secret"
+    )]
+    pub oauth2_client_secret: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_OAUTH2_TOKEN_URL",
+        help = "OAuth2 token endpoint (default: tokenUrl of the spec's clientCredentials flow)"
+    )]
+    pub oauth2_token_url: Option<Url>,
+
+    #[arg(
+        long,
+        env = "RMCP_OAUTH2_SCOPES",
+        num_args(1..),
+        value_delimiter = ',',
+        help = "OAuth2 scopes to request (comma-separated, default: all scopes of the spec's clientCredentials flow)"
+    )]
+    pub oauth2_scopes: Vec<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_OAUTH2_SCHEME",
+        help = "Security scheme of the spec providing the token URL and scopes (default: the first one with a clientCredentials flow)"
+    )]
+    pub oauth2_scheme: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_OAUTH2_CLIENT_AUTH",
+        default_value = "basic",
+        help = "How the client authenticates to the token endpoint: basic (HTTP Basic) or post (form body)"
+    )]
+    pub oauth2_client_auth: ClientAuthMethod,
+
+    #[arg(
+        long = "api-key",
+        env = "RMCP_API_KEYS",
+        hide_env_values = true,
+        num_args(1..),
+        value_delimiter = ',',
+        help = "API key for an apiKey security scheme of the spec, sent where the scheme says (header, query or cookie): 'scheme=env:VAR', 'scheme=file:PATH' or 'scheme=value' (comma-separated)"
+    )]
+    pub api_keys: Vec<ApiKey>,
+
+    #[arg(
+        long,
+        env = "RMCP_BASIC_AUTH_USERNAME",
+        requires = "basic_auth_password",
+        help = "Authenticate upstream requests with HTTP Basic auth as this user"
+    )]
+    pub basic_auth_username: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_BASIC_AUTH_PASSWORD",
+        hide_env_values = true,
+        requires = "basic_auth_username",
+        help = "HTTP Basic auth password"
+    )]
+    pub basic_auth_password: Option<String>,
+
+    #[arg(
+        long,
+        env = "RMCP_BASIC_AUTH_SCHEME",
+        requires = "basic_auth_username",
+        help = "HTTP Basic security scheme of the spec the credentials are for, checked when the server starts"
+    )]
+    pub basic_auth_scheme: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// What to do with the generated tools instead of serving them
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Write the generated tools, with their schemas and annotations, as JSON
+    /// and exit, e.g. to diff two versions of a spec
+    ExportTools {
+        /// File to write the tools to
+        #[arg(long, short = 'o')]
+        output: PathBuf,
+    },
+    /// Compare the tools of the spec with those of a new version of it,
+    /// generated with the same options, and report the changes
+    Diff {
+        /// New version of the `OpenAPI` specification, as a URL or file path
+        new_spec: SpecLocation,
+        /// Exit with an error when a change breaks existing calls
+        #[arg(long)]
+        fail_on_breaking: bool,
+    },
 }
 
 impl Cli {
-    pub fn parse_args() -> Self {
-        Self::parse()
+    /// Parse the command line, with the options of its `--config` file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration file cannot be read
+    pub fn parse_args() -> Result<Self, Error> {
+        let args = config_file::with_config_file(std::env::args_os().collect(), |name| {
+            std::env::var_os(name).is_some()
+        })?;
+        let matches = Self::command_with_env().get_matches_from(args);
+        Ok(Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit()))
+    }
+
+    /// The command line interface, reading the options without an environment
+    /// variable of their own from `RMCP_<OPTION>`, such as `RMCP_BASE_URL`
+    pub fn command_with_env() -> clap::Command {
+        Self::command().mut_args(|arg| {
+            let builtin = matches!(
+                arg.get_action(),
+                ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+            );
+            if builtin || arg.get_env().is_some() {
+                return arg;
+            }
+            let name = arg.get_long().unwrap_or(arg.get_id().as_str());
+            let env = format!("RMCP_{}", name.replace('-', "_").to_uppercase());
+            arg.env(env)
+        })
+    }
+
+    /// Replace secret references in credential options with the secrets they
+    /// name
+    pub async fn resolve_secrets(mut self, resolver: &SecretResolver) -> Result<Self, Error> {
+        for secret in [
+            &mut self.token_exchange_client_secret,
+            &mut self.introspection_client_secret,
+            &mut self.mcp_auth_token,
+            &mut self.admin_token,
+            &mut self.proxy_password,
+            &mut self.hmac_secret,
+            &mut self.oauth2_client_secret,
+            &mut self.basic_auth_password,
+        ] {
+            resolver.resolve_in_place(secret).await?;
+        }
+        for api_key in &mut self.api_keys {
+            if let ApiKeySource::Value(value) = &mut api_key.source {
+                *value = resolver.resolve(value).await?;
+            }
+        }
+        for rule in &mut self.authorization_rules {
+            if let AuthorizationBehavior::ServiceToken(token) = &mut rule.behavior {
+                *token = token.resolve(resolver).await?;
+            }
+        }
+        Ok(self)
     }
 }
 
@@ -116,6 +1293,80 @@ impl Cli {
 mod tests {
     use super::*;
     use clap::Parser;
+    use std::path::Path;
+
+    #[actix_web::test]
+    async fn secret_references_are_resolved() {
+        let path =
+            std::env::temp_dir().join(format!("rmcp-openapi-cli-secret-{}", std::process::id()));
+        std::fs::write(&path, "s3cret\n").unwrap();
+        let reference = format!("file:{}", path.display());
+        let cli = Cli::try_parse_from([
+            "rmcp-openapi-server",
+            "https://example.com/spec.json",
+            "--base-url",
+            "https://api.example.com",
+            "--basic-auth-username",
+            "agent",
+            "--basic-auth-password",
+            &reference,
+            "--api-key",
+            &format!("petstore_auth={reference}"),
+        ])
+        .unwrap()
+        .resolve_secrets(&SecretResolver::default())
+        .await;
+        std::fs::remove_file(&path).unwrap();
+
+        let cli = cli.unwrap();
+        assert_eq!(cli.basic_auth_password.as_deref(), Some("s3cret"));
+        assert_eq!(
+            cli.api_keys[0].source,
+            ApiKeySource::Value("s3cret".to_string())
+        );
+        assert_eq!(cli.basic_auth_username.as_deref(), Some("agent"));
+    }
+
+    #[test]
+    fn export_tools_subcommand_parsed() {
+        let cli = Cli::try_parse_from([
+            "rmcp-openapi-server",
+            "https://example.com/spec.json",
+            "--base-url",
+            "https://api.example.com",
+            "export-tools",
+            "--output",
+            "tools.json",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.spec.unwrap().to_string(),
+            "https://example.com/spec.json"
+        );
+        assert!(matches!(
+            cli.command,
+            Some(Command::ExportTools { output }) if output.as_path() == Path::new("tools.json")
+        ));
+    }
+
+    #[test]
+    fn diff_subcommand_parsed() {
+        let cli = Cli::try_parse_from([
+            "rmcp-openapi-server",
+            "petstore-v1.json",
+            "--base-url",
+            "https://api.example.com",
+            "diff",
+            "petstore-v2.json",
+            "--fail-on-breaking",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Diff { new_spec: SpecLocation::File(path), fail_on_breaking: true })
+                if path.as_path() == Path::new("petstore-v2.json")
+        ));
+    }
 
     #[test]
     fn insecure_flag_present_sets_true() {
@@ -130,6 +1381,19 @@ mod tests {
         assert!(cli.insecure);
     }
 
+    #[test]
+    fn insecure_skip_tls_verify_alias_sets_insecure() {
+        let cli = Cli::try_parse_from([
+            "rmcp-openapi-server",
+            "https://example.com/spec.json",
+            "--base-url",
+            "https://api.example.com",
+            "--insecure-skip-tls-verify",
+        ])
+        .unwrap();
+        assert!(cli.insecure);
+    }
+
     #[test]
     fn insecure_flag_absent_defaults_false() {
         // clap reads `RMCP_INSECURE` when the flag is absent; an ambient
@@ -177,4 +1441,27 @@ mod tests {
         }
         assert!(cli.insecure);
     }
+
+    #[test]
+    fn every_option_has_an_env_var() {
+        let command = Cli::command_with_env();
+        let env = |id: &str| {
+            command
+                .get_arguments()
+                .find(|arg| arg.get_id().as_str() == id)
+                .and_then(|arg| arg.get_env())
+                .map(|env| env.to_string_lossy().into_owned())
+        };
+        assert_eq!(env("spec").as_deref(), Some("RMCP_SPEC"));
+        assert_eq!(env("base_url").as_deref(), Some("RMCP_BASE_URL"));
+        assert_eq!(env("headers").as_deref(), Some("RMCP_HEADERS"));
+        assert_eq!(env("path").as_deref(), Some("RMCP_PATH"));
+        assert_eq!(env("help"), None);
+        assert!(
+            command
+                .get_arguments()
+                .filter(|arg| !matches!(arg.get_action(), ArgAction::Help | ArgAction::Version))
+                .all(|arg| arg.get_env().is_some())
+        );
+    }
 }
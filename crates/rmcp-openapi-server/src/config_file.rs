@@ -0,0 +1,222 @@
+//! `--config` file, giving command line options from a TOML, YAML or JSON file.
+//!
+//! The file is a table of options named as on the command line, without the
+//! leading dashes, the `OpenAPI` spec being given as `spec`:
+//!
+//! ```toml
+//! spec = "https://petstore.swagger.io/v2/swagger.json"
+//! base-url = "https://petstore.swagger.io/v2"
+//! header = ["X-Team: pets", "X-Env: staging"]
+//! read-only = true
+//! connect-timeout-ms = 2000
+//! ```
+//!
+//! Options are layered: command line flags win over environment variables,
+//! which win over the file, which wins over the defaults. Lists given both in
+//! the file and on the command line add up.
+
+use crate::cli::Cli;
+use clap::ArgAction;
+use clap::parser::ValueSource;
+use rmcp_openapi::Error;
+use serde_json::{Map, Value};
+use std::ffi::{OsStr, OsString};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+/// `args` with the options of the `--config` file, if any, inserted before
+/// those of the command line
+///
+/// Options of the file having an environment variable for which `env_is_set`
+/// holds are left out, the variable giving their value.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or has options the command
+/// line doesn't
+pub fn with_config_file(
+    args: Vec<OsString>,
+    env_is_set: impl Fn(&OsStr) -> bool,
+) -> Result<Vec<OsString>, Error> {
+    // Invalid or incomplete command lines are reported by the final parse
+    let Ok(matches) = Cli::command_with_env()
+        .ignore_errors(true)
+        .try_get_matches_from(&args)
+    else {
+        return Ok(args);
+    };
+    let Some(path) = matches.get_one::<PathBuf>("config") else {
+        return Ok(args);
+    };
+    let content = std::fs::read_to_string(path)?;
+    let spec_given = matches.value_source("spec") == Some(ValueSource::CommandLine);
+    let file_args = options_to_args(parse(path, &content)?, spec_given, env_is_set)?;
+
+    let mut args = args.into_iter();
+    Ok(args
+        .next()
+        .into_iter()
+        .chain(file_args)
+        .chain(args)
+        .collect())
+}
+
+/// Options of the file at `path`, read as TOML, YAML or JSON after its
+/// extension
+fn parse(path: &Path, content: &str) -> Result<Map<String, Value>, Error> {
+    let invalid = |e: &dyn Display| {
+        Error::Validation(format!(
+            "Invalid configuration file '{}': {e}",
+            path.display()
+        ))
+    };
+    let value: Value = match path.extension().and_then(OsStr::to_str) {
+        Some("toml") => toml::from_str(content).map_err(|e| invalid(&e))?,
+        Some("yaml" | "yml") => serde_norway::from_str(content).map_err(|e| invalid(&e))?,
+        Some("json") => serde_json::from_str(content).map_err(|e| invalid(&e))?,
+        _ => {
+            return Err(invalid(&"expected a .toml, .yaml, .yml or .json extension"));
+        }
+    };
+    match value {
+        Value::Object(options) => Ok(options),
+        _ => Err(invalid(&"expected a table of options")),
+    }
+}
+
+/// Command line arguments giving `options`, the spec first
+fn options_to_args(
+    options: Map<String, Value>,
+    spec_given: bool,
+    env_is_set: impl Fn(&OsStr) -> bool,
+) -> Result<Vec<OsString>, Error> {
+    let command = Cli::command_with_env();
+    let mut args = Vec::new();
+    for (name, value) in options {
+        let invalid = |reason: &str| {
+            Error::Validation(format!(
+                "Invalid option '{name}' in configuration file: {reason}"
+            ))
+        };
+        let Some(arg) = command.get_arguments().find(|arg| {
+            arg.get_long() == Some(name.as_str())
+                || (arg.is_positional() && arg.get_id().as_str() == name)
+        }) else {
+            return Err(invalid("no such option"));
+        };
+        if name == "config" {
+            return Err(invalid("configuration files cannot include others"));
+        }
+        if arg.get_env().is_some_and(&env_is_set) {
+            continue;
+        }
+        if arg.is_positional() {
+            if !spec_given {
+                let Value::String(spec) = value else {
+                    return Err(invalid("expected a string"));
+                };
+                args.insert(0, OsString::from(spec));
+            }
+            continue;
+        }
+
+        let values = match value {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+        for value in values {
+            let value = match value {
+                Value::Bool(true) if matches!(arg.get_action(), ArgAction::SetTrue) => {
+                    args.push(format!("--{name}").into());
+                    continue;
+                }
+                Value::Bool(false) if matches!(arg.get_action(), ArgAction::SetTrue) => continue,
+                Value::String(value) => value,
+                Value::Number(value) => value.to_string(),
+                Value::Bool(value) => value.to_string(),
+                _ => {
+                    return Err(invalid(
+                        "expected a string, number, boolean or list of them",
+                    ));
+                }
+            };
+            args.push(format!("--{name}={value}").into());
+        }
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    const CONFIG: &str = r#"
+spec = "https://example.com/spec.json"
+base-url = "https://api.example.com"
+header = ["X-Team: pets", "X-Env: staging"]
+read-only = true
+path = "/tools"
+"#;
+
+    fn cli(args: &[&str], env_is_set: impl Fn(&OsStr) -> bool) -> Cli {
+        let options = parse(Path::new("server.toml"), CONFIG).unwrap();
+        let args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        let spec_given = args.len() > 1 && !args[1].to_string_lossy().starts_with('-');
+        let file_args = options_to_args(options, spec_given, env_is_set).unwrap();
+        Cli::try_parse_from(
+            args[..1]
+                .iter()
+                .cloned()
+                .chain(file_args)
+                .chain(args[1..].iter().cloned()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn command_line_overrides_file() {
+        let cli = cli(
+            &[
+                "rmcp-openapi-server",
+                "--base-url",
+                "https://override.example.com",
+                "--header",
+                "X-Trace: 1",
+            ],
+            |_| false,
+        );
+        assert_eq!(
+            cli.spec.unwrap().to_string(),
+            "https://example.com/spec.json"
+        );
+        assert_eq!(
+            cli.base_url.as_deref(),
+            Some("https://override.example.com")
+        );
+        assert_eq!(
+            cli.headers,
+            ["X-Team: pets", "X-Env: staging", "X-Trace: 1"]
+        );
+        assert!(cli.read_only);
+        assert_eq!(cli.path, "/tools");
+    }
+
+    #[test]
+    fn environment_overrides_file() {
+        let cli = cli(&["rmcp-openapi-server", "other.json"], |name| {
+            name == "RMCP_PATH"
+        });
+        assert_eq!(cli.spec.unwrap().to_string(), "other.json");
+        // Left to the environment variable, unset in tests
+        assert_eq!(cli.path, "/mcp");
+    }
+
+    #[test]
+    fn unknown_options_are_refused() {
+        let options = parse(Path::new("server.yaml"), "base-urll: https://x\n").unwrap();
+        let error = options_to_args(options, false, |_| false).unwrap_err();
+        assert!(error.to_string().contains("'base-urll'"));
+        assert!(parse(Path::new("server.ini"), "").is_err());
+    }
+}
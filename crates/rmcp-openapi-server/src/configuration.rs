@@ -1,76 +1,221 @@
 use crate::cli::Cli;
+use crate::cors::CorsConfig;
 use crate::spec_loader::SpecLocation;
 use bon::Builder;
 use reqwest::header::HeaderMap;
 use rmcp_openapi::{
-    AuthorizationMode, CliError, Error, Server,
+    ApiKey, AuditConfig, AuditLog, AuditSink, AuthorizationMode, AuthorizationRule,
+    AuthorizationTemplate, BaseUrlRoute, BasicAuth, BlobStore, BlobStoreConfig, BudgetConfig,
+    CacheConfig, CallQueue, Cassette, CassetteConfig, CassetteMode, CircuitBreakerConfig, CliError,
+    ClientCertificate, ClientCredentialsConfig, ClientLog, CompressionConfig, ConcurrencyLimits,
+    ConfirmationConfig, ConnectionConfig, DuplicateNameStrategy, Error, FileEventSink, HmacSigner,
+    HostAllowlist, HtmlConversion, IdempotencyConfig, IntrospectionConfig, JwtValidationConfig,
+    NullValuePolicy, OutputSchemaPolicy, PaginationConfig, ParameterPolicy, PipelineConfig,
+    ProxyConfig, QueueConfig, RateLimit, RateLimitConfig, RedactionRules, RedirectPolicy,
+    RequestSigner, RetryPolicy, ScopeSource, SecurityEventSink, Server, StatusPolicy, TableConfig,
+    TimeoutPolicy, TokenExchangeConfig, WebhookEventSink,
     spec::{Filter, Filters},
 };
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
 #[derive(Debug, Clone, Builder)]
 pub struct Configuration {
-    pub spec_location: SpecLocation,
-    pub base_url: Url,
+    pub spec_location: Option<SpecLocation>,
+    pub base_url: Option<Url>,
+    /// Base URLs of the tools of some tags or paths
+    #[builder(default)]
+    pub base_url_routes: Vec<BaseUrlRoute>,
+    /// File of the servers to mount instead of this one
+    pub mounts: Option<PathBuf>,
+    /// URL path of the MCP endpoint, without trailing slash
+    pub path: String,
     pub port: u16,
+    /// Address to bind to, or `unix:<path>` for a Unix socket
     pub bind_address: String,
     pub default_headers: HeaderMap,
     pub filters: Option<Filters>,
+    pub parameter_policy: Option<ParameterPolicy>,
     pub authorization_mode: AuthorizationMode,
     #[builder(default)]
+    pub authorization_rules: Vec<AuthorizationRule>,
+    #[builder(default)]
+    pub authorization_templates: Vec<AuthorizationTemplate>,
+    pub token_exchange: Option<TokenExchangeConfig>,
+    pub scope_check: Option<ScopeSource>,
+    /// Validation of the tokens MCP clients present to the endpoint
+    pub jwt_validation: Option<JwtValidationConfig>,
+    /// Bearer token MCP clients must present to the endpoint
+    pub mcp_auth_token: Option<String>,
+    /// Bearer token admin API clients must present, serving the admin API
+    pub admin_token: Option<String>,
+    #[builder(default)]
     pub skip_tool_descriptions: bool,
     #[builder(default)]
     pub skip_parameter_descriptions: bool,
     #[builder(default)]
     pub stateful: bool,
+    /// How long shutdown waits for tool calls in progress
+    #[builder(default = Duration::from_secs(30))]
+    pub shutdown_timeout: Duration,
+    /// Let sessions choose their own token, base URL and tags
+    #[builder(default)]
+    pub session_configuration: bool,
+    /// Maximum number of tools per `tools/list` page
+    pub tools_page_size: Option<NonZeroUsize>,
+    /// Expose the spec as MCP resources
+    #[builder(default)]
+    pub spec_resources: bool,
+    /// Offer an MCP prompt per tool
+    #[builder(default)]
+    pub tool_prompts: bool,
+    /// Offer a tool searching the other tools
+    #[builder(default)]
+    pub tool_search: bool,
+    /// List tools compactly, described on demand
+    #[builder(default)]
+    pub compact_tool_listings: bool,
+    /// Offer a tool chaining calls to the other tools
+    #[builder(default)]
+    pub batch_calls: bool,
+    /// Extra names of tools, as alias to tool name
+    #[builder(default)]
+    pub tool_aliases: BTreeMap<String, String>,
+    /// How long tool calls run before clients are notified of their progress
+    pub progress_threshold: Option<Duration>,
+    /// Queue bounding the tool calls executing at the same time
+    pub call_queue: Option<QueueConfig>,
+    /// Stream operational messages to MCP clients
+    #[builder(default)]
+    pub client_logging: bool,
+    /// CORS headers for browser-based clients, applying to every mount
+    pub cors: Option<CorsConfig>,
+    /// Add response headers to structured tool results
+    #[builder(default)]
+    pub response_headers: bool,
+    /// Following of the next pages of listings
+    pub pagination: Option<PaginationConfig>,
+    /// Let tool calls filter the values of responses
+    #[builder(default)]
+    pub response_filter: bool,
+    /// Let tool calls return their request instead of sending it
+    #[builder(default)]
+    pub dry_runs: bool,
+    /// Response transformer pipelines, by tool or tag
+    pub transformer_pipelines: Option<PipelineConfig>,
+    /// Markdown rendering of tabular responses
+    pub markdown_tables: Option<TableConfig>,
+    /// Conversion of HTML responses
+    pub html_conversion: Option<HtmlConversion>,
+    /// Binary responses kept as resources
+    pub binary_resources: Option<BlobStoreConfig>,
+    /// Size of the bodies returned by tool calls
+    pub response_budget: Option<BudgetConfig>,
+    /// Read upstream errors with the spec's error schemas
+    #[builder(default)]
+    pub typed_errors: bool,
+    /// Statuses outside `2xx` returned as successful results
+    pub status_policy: Option<StatusPolicy>,
+    /// Strictness of output schemas, and tools without one
+    pub output_schema_policy: Option<OutputSchemaPolicy>,
+    /// Resolution of tool names shared by several operations
+    pub duplicate_tool_names: Option<DuplicateNameStrategy>,
     #[builder(default)]
     pub insecure: bool,
+    #[builder(default)]
+    pub strip_non_standard_bodies: bool,
+    #[builder(default)]
+    pub null_value_policy: NullValuePolicy,
+    pub retry_policy: Option<RetryPolicy>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub concurrency: Option<ConcurrencyLimits>,
+    pub proxy: Option<ProxyConfig>,
+    pub client_certificate: Option<ClientCertificate>,
+    pub ca_bundle: Option<PathBuf>,
+    pub connection: Option<ConnectionConfig>,
+    pub timeout_policy: Option<TimeoutPolicy>,
+    pub response_cache: Option<CacheConfig>,
+    pub compression: Option<CompressionConfig>,
+    pub max_response_bytes: Option<usize>,
+    pub redirect_policy: Option<RedirectPolicy>,
+    pub allowed_hosts: Option<HostAllowlist>,
+    pub hmac_signer: Option<HmacSigner>,
+    pub idempotency: Option<IdempotencyConfig>,
+    pub confirmation: Option<ConfirmationConfig>,
+    pub audit: Option<AuditConfig>,
+    /// File security events are appended to
+    pub security_events_file: Option<PathBuf>,
+    /// URL security events are posted to
+    pub security_webhook: Option<Url>,
+    /// Headers sent with security event webhook requests
+    #[builder(default)]
+    pub security_webhook_headers: HeaderMap,
+    /// OTLP/gRPC collector tool call spans are exported to
+    pub otlp_endpoint: Option<Url>,
+    pub redaction: Option<RedactionRules>,
+    pub cassette: Option<CassetteConfig>,
+    #[builder(default)]
+    pub mock: bool,
+    #[builder(default)]
+    pub read_only: bool,
+    pub client_credentials: Option<ClientCredentialsConfig>,
+    #[builder(default)]
+    pub api_keys: Vec<ApiKey>,
+    pub basic_auth: Option<BasicAuth>,
 }
 
 impl Configuration {
     pub fn from_cli(cli: Cli) -> Result<Self, Error> {
-        // Parse base URL - now required by CLI
-        let base_url = Url::parse(&cli.base_url)
+        // Parse base URL - required by CLI unless servers are mounted from a file
+        let base_url = cli
+            .base_url
+            .as_deref()
+            .map(Url::parse)
+            .transpose()
             .map_err(|e| Error::InvalidUrl(format!("Invalid base URL: {e}")))?;
 
-        // Parse headers from CLI format "name: value"
-        let mut default_headers = HeaderMap::new();
-        for header_str in cli.headers {
-            if let Some((key, value)) = header_str.split_once(':') {
-                let key = key.trim();
-                let value = value.trim();
-
-                if key.is_empty() {
-                    return Err(Error::Cli(CliError::InvalidHeaderFormat {
-                        header: header_str,
-                    }));
-                }
-
-                // Validate header name using reqwest/http
-                let header_name =
-                    http::header::HeaderName::from_bytes(key.as_bytes()).map_err(|e| {
-                        Error::Cli(CliError::InvalidHeaderName {
-                            header: header_str.clone(),
-                            source: e,
-                        })
-                    })?;
-
-                // Validate header value using reqwest/http
-                let header_value = http::header::HeaderValue::from_str(value).map_err(|e| {
-                    Error::Cli(CliError::InvalidHeaderValue {
-                        header: header_str.clone(),
-                        source: e,
-                    })
-                })?;
+        let path = cli.path.trim_end_matches('/');
+        if !path.starts_with('/') {
+            return Err(Error::Validation(format!(
+                "Invalid path '{}': expected an absolute URL path such as '/mcp'",
+                cli.path
+            )));
+        }
+        let path = path.to_string();
 
-                default_headers.insert(header_name, header_value);
-            } else {
-                return Err(Error::Cli(CliError::InvalidHeaderFormat {
-                    header: header_str,
-                }));
+        if let Some(path) = cli.bind_address.strip_prefix(UNIX_SOCKET_PREFIX) {
+            if path.is_empty() {
+                return Err(Error::Validation(format!(
+                    "Missing Unix socket path in '{UNIX_SOCKET_PREFIX}<path>'"
+                )));
+            }
+            if !cfg!(unix) {
+                return Err(Error::Validation(
+                    "Unix sockets are not supported on this platform".to_string(),
+                ));
             }
         }
 
+        let default_headers = parse_headers(cli.headers)?;
+
+        let parameter_policy = cli
+            .parameter_policy
+            .as_deref()
+            .map(ParameterPolicy::from_file)
+            .transpose()?;
+
+        let transformer_pipelines = cli
+            .transformer_pipelines
+            .as_deref()
+            .map(PipelineConfig::from_file)
+            .transpose()?;
+
         let filters = {
             let mut f = Filters::builder().build();
 
@@ -95,27 +240,515 @@ impl Configuration {
             }
         };
 
+        let retry_policy = (cli.max_retries > 0).then(|| {
+            RetryPolicy::builder()
+                .max_retries(cli.max_retries)
+                .initial_backoff(Duration::from_millis(cli.retry_initial_backoff_ms))
+                .max_backoff(Duration::from_millis(cli.retry_max_backoff_ms))
+                .retry_non_idempotent(cli.retry_non_idempotent)
+                .build()
+        });
+
+        let circuit_breaker = (cli.circuit_breaker_threshold > 0).then(|| {
+            CircuitBreakerConfig::builder()
+                .failure_threshold(cli.circuit_breaker_threshold)
+                .open_duration(Duration::from_secs(cli.circuit_breaker_open_secs))
+                .build()
+        });
+
+        let mut per_tool_rate_limits = HashMap::new();
+        for value in cli.tool_rate_limits {
+            let (tool, limit) = value.split_once('=').ok_or_else(|| {
+                Error::Cli(CliError::InvalidToolRateLimit {
+                    value: value.clone(),
+                    reason: "expected 'tool=<requests>/<period>'".to_string(),
+                })
+            })?;
+            let limit: RateLimit = limit.parse().map_err(|reason| {
+                Error::Cli(CliError::InvalidToolRateLimit {
+                    value: value.clone(),
+                    reason,
+                })
+            })?;
+            per_tool_rate_limits.insert(tool.trim().to_string(), limit);
+        }
+
+        let rate_limit =
+            (cli.rate_limit.is_some() || !per_tool_rate_limits.is_empty()).then(|| {
+                RateLimitConfig::builder()
+                    .maybe_global(cli.rate_limit)
+                    .per_tool(per_tool_rate_limits)
+                    .mode(cli.rate_limit_mode)
+                    .build()
+            });
+
+        let concurrency = (cli.max_concurrent_requests.is_some()
+            || cli.max_concurrent_requests_per_session.is_some())
+        .then(|| {
+            ConcurrencyLimits::builder()
+                .maybe_global(cli.max_concurrent_requests)
+                .maybe_per_session(cli.max_concurrent_requests_per_session)
+                .build()
+        });
+
+        let call_queue = cli.queue_workers.map(|workers| {
+            QueueConfig::builder()
+                .workers(workers)
+                .maybe_depth(cli.queue_depth)
+                .maybe_timeout(cli.queue_timeout_secs.map(Duration::from_secs))
+                .build()
+        });
+
+        let cors = cli.cors_origins.map(|origins| {
+            CorsConfig::builder()
+                .origins(origins)
+                .maybe_headers(cli.cors_headers)
+                .maybe_methods(cli.cors_methods)
+                .build()
+        });
+
+        let proxy = cli.proxy.map(|url| {
+            ProxyConfig::builder()
+                .url(url)
+                .maybe_no_proxy(cli.no_proxy)
+                .maybe_username(cli.proxy_username)
+                .maybe_password(cli.proxy_password)
+                .build()
+        });
+
+        let client_certificate =
+            cli.client_cert
+                .zip(cli.client_key)
+                .map(|(cert_path, key_path)| {
+                    ClientCertificate::builder()
+                        .cert_path(cert_path)
+                        .key_path(key_path)
+                        .build()
+                });
+
+        let connection = ConnectionConfig::builder()
+            .maybe_pool_max_idle_per_host(cli.pool_max_idle_per_host)
+            .maybe_pool_idle_timeout(cli.pool_idle_timeout_secs.map(Duration::from_secs))
+            .maybe_connect_timeout(cli.connect_timeout_ms.map(Duration::from_millis))
+            .maybe_tcp_keepalive(cli.tcp_keepalive_secs.map(Duration::from_secs))
+            .resolve(cli.resolve)
+            .build();
+        let connection = (connection != ConnectionConfig::default()).then_some(connection);
+
+        let timeout_policy = TimeoutPolicy::builder()
+            .per_tool(parse_timeouts(cli.tool_timeouts)?)
+            .per_tag(parse_timeouts(cli.tag_timeouts)?)
+            .maybe_max(cli.max_timeout_secs.map(Duration::from_secs))
+            .build();
+        let timeout_policy = (timeout_policy != TimeoutPolicy::default()).then_some(timeout_policy);
+
+        let status_policy = StatusPolicy::builder()
+            .success(cli.success_statuses.into_iter().collect())
+            .per_tool(parse_success_statuses(cli.tool_success_statuses)?)
+            .build();
+        let status_policy = (status_policy != StatusPolicy::default()).then_some(status_policy);
+
+        let output_schema_policy = OutputSchemaPolicy::builder()
+            .mode(cli.output_schemas)
+            .disabled_tools(cli.no_output_schema.into_iter().collect())
+            .build();
+        let output_schema_policy =
+            (output_schema_policy != OutputSchemaPolicy::default()).then_some(output_schema_policy);
+
+        let response_cache = cli.cache_ttl_secs.filter(|ttl| *ttl > 0).map(|ttl| {
+            CacheConfig::builder()
+                .default_ttl(Duration::from_secs(ttl))
+                .maybe_max_ttl(cli.cache_max_ttl_secs.map(Duration::from_secs))
+                .max_entries(cli.cache_max_entries)
+                .honor_cache_control(!cli.cache_ignore_cache_control)
+                .build()
+        });
+
+        let compression =
+            (!cli.no_decompress_responses || cli.compress_requests.is_some()).then(|| {
+                CompressionConfig::builder()
+                    .decompress_responses(!cli.no_decompress_responses)
+                    .maybe_request_encoding(cli.compress_requests)
+                    .min_request_size(cli.compress_min_bytes)
+                    .build()
+            });
+
+        let redirect_policy = RedirectPolicy::builder()
+            .mode(cli.redirects)
+            .forward_credentials_cross_origin(cli.forward_credentials_on_redirect)
+            .build();
+        let redirect_policy =
+            (redirect_policy != RedirectPolicy::default()).then_some(redirect_policy);
+        let allowed_hosts =
+            (!cli.allowed_hosts.is_empty()).then(|| HostAllowlist::new(&cli.allowed_hosts));
+
+        let hmac_signer = cli.hmac_secret.map(|secret| {
+            HmacSigner::builder()
+                .secret(secret.into_bytes())
+                .algorithm(cli.hmac_algorithm)
+                .header(cli.hmac_header)
+                .prefix(cli.hmac_prefix)
+                .encoding(cli.hmac_encoding)
+                .components(cli.hmac_components)
+                .separator(cli.hmac_separator.replace("\\n", "\n"))
+                .timestamp_header(cli.hmac_timestamp_header)
+                .build()
+        });
+
+        let idempotency = cli.idempotency_keys.then(|| {
+            IdempotencyConfig::builder()
+                .header(cli.idempotency_header)
+                .methods(cli.idempotency_methods)
+                .build()
+        });
+
+        let confirmation = cli.confirm_destructive.then(|| {
+            ConfirmationConfig::builder()
+                .methods(cli.confirm_methods)
+                .token_ttl(Duration::from_secs(cli.confirmation_ttl_secs))
+                .build()
+        });
+
+        let audit_sink = match cli.audit_log {
+            Some(path) => Some(AuditSink::File(path)),
+            None => cli.audit_log_tracing.then_some(AuditSink::Tracing),
+        };
+        let audit = audit_sink.map(|sink| {
+            AuditConfig::builder()
+                .sink(sink)
+                .redact_headers(cli.audit_redact_headers)
+                .build()
+        });
+
+        let security_webhook_headers = parse_headers(cli.security_webhook_headers)?;
+
+        let redaction =
+            (!cli.redact_headers.is_empty() || !cli.redact_body_paths.is_empty()).then(|| {
+                RedactionRules::builder()
+                    .headers(cli.redact_headers)
+                    .body_paths(cli.redact_body_paths)
+                    .build()
+            });
+
+        let client_credentials =
+            cli.oauth2_client_id
+                .zip(cli.oauth2_client_secret)
+                .map(|(client_id, client_secret)| {
+                    ClientCredentialsConfig::builder()
+                        .client_id(client_id)
+                        .client_secret(client_secret)
+                        .maybe_token_url(cli.oauth2_token_url)
+                        .scopes(cli.oauth2_scopes)
+                        .maybe_scheme(cli.oauth2_scheme)
+                        .client_auth(cli.oauth2_client_auth)
+                        .build()
+                });
+
+        let token_exchange = cli.token_exchange_url.map(|token_url| {
+            TokenExchangeConfig::builder()
+                .token_url(token_url)
+                .maybe_client_id(cli.token_exchange_client_id)
+                .maybe_client_secret(cli.token_exchange_client_secret)
+                .maybe_audience(cli.token_exchange_audience)
+                .maybe_resource(cli.token_exchange_resource)
+                .scopes(cli.token_exchange_scopes)
+                .build()
+        });
+
+        let scope_check = match cli.introspection_url {
+            Some(url) => Some(ScopeSource::Introspection(
+                IntrospectionConfig::builder()
+                    .url(url)
+                    .maybe_client_id(cli.introspection_client_id)
+                    .maybe_client_secret(cli.introspection_client_secret)
+                    .build(),
+            )),
+            None if cli.check_scopes => Some(ScopeSource::JwtClaims),
+            None => None,
+        };
+
+        let jwt_validation = cli.jwks_url.map(|jwks_url| {
+            JwtValidationConfig::builder()
+                .jwks_url(jwks_url)
+                .maybe_issuer(cli.jwt_issuer)
+                .audiences(cli.jwt_audiences)
+                .build()
+        });
+
+        let mcp_auth_token = match cli.mcp_auth_token_file {
+            Some(path) => Some(
+                std::fs::read_to_string(&path)
+                    .map_err(|e| {
+                        Error::Auth(format!(
+                            "Failed to read MCP auth token from '{}': {e}",
+                            path.display()
+                        ))
+                    })?
+                    .trim()
+                    .to_string(),
+            ),
+            None => cli.mcp_auth_token,
+        };
+        if mcp_auth_token
+            .as_ref()
+            .is_some_and(|token| token.is_empty())
+        {
+            return Err(Error::Auth("MCP auth token is empty".to_string()));
+        }
+        if cli
+            .admin_token
+            .as_ref()
+            .is_some_and(|token| token.is_empty())
+        {
+            return Err(Error::Auth("Admin token is empty".to_string()));
+        }
+
+        let basic_auth =
+            cli.basic_auth_username
+                .zip(cli.basic_auth_password)
+                .map(|(username, password)| {
+                    BasicAuth::builder()
+                        .username(username)
+                        .password(password)
+                        .maybe_scheme(cli.basic_auth_scheme)
+                        .build()
+                });
+
+        let cassette = match (cli.record, cli.replay) {
+            (Some(path), _) => Some((path, CassetteMode::Record)),
+            (None, Some(path)) => Some((path, CassetteMode::Replay)),
+            (None, None) => None,
+        }
+        .map(|(path, mode)| CassetteConfig::builder().path(path).mode(mode).build());
+
         Ok(Configuration {
             spec_location: cli.spec,
+            mounts: cli.mounts,
+            path,
             base_url,
+            base_url_routes: cli.base_url_routes,
             port: cli.port,
             bind_address: cli.bind_address,
             default_headers,
             filters,
+            parameter_policy,
             authorization_mode: cli.authorization_mode,
+            authorization_rules: cli.authorization_rules,
+            authorization_templates: cli.authorization_templates,
+            token_exchange,
+            scope_check,
+            jwt_validation,
+            mcp_auth_token,
+            admin_token: cli.admin_token,
             skip_tool_descriptions: cli.skip_tool_descriptions,
             skip_parameter_descriptions: cli.skip_parameter_descriptions,
             stateful: cli.stateful,
+            shutdown_timeout: Duration::from_secs(cli.shutdown_timeout_secs),
+            session_configuration: cli.session_configuration,
+            tools_page_size: cli.tools_page_size,
+            spec_resources: cli.spec_resources,
+            tool_prompts: cli.tool_prompts,
+            tool_search: cli.tool_search,
+            compact_tool_listings: cli.compact_tool_listings,
+            batch_calls: cli.batch_calls,
+            tool_aliases: parse_tool_aliases(cli.tool_aliases)?,
+            progress_threshold: cli.progress_threshold_secs.map(Duration::from_secs),
+            call_queue,
+            client_logging: cli.client_logging,
+            cors,
+            response_headers: cli.response_headers,
+            pagination: cli
+                .follow_pages
+                .map(|max_pages| PaginationConfig::builder().max_pages(max_pages).build()),
+            response_filter: cli.response_filter,
+            dry_runs: cli.dry_runs,
+            transformer_pipelines,
+            markdown_tables: cli.markdown_tables.then(|| {
+                TableConfig::builder()
+                    .maybe_max_rows(cli.markdown_table_rows)
+                    .build()
+            }),
+            html_conversion: cli.html_to,
+            binary_resources: cli.binary_resources.then(|| {
+                BlobStoreConfig::builder()
+                    .maybe_max_bytes(cli.binary_resources_max_bytes)
+                    .maybe_ttl(cli.binary_resources_ttl_secs.map(Duration::from_secs))
+                    .build()
+            }),
+            response_budget: cli.response_budget_bytes.map(|max_bytes| {
+                BudgetConfig::builder()
+                    .max_bytes(max_bytes)
+                    .maybe_preview_items(cli.response_budget_preview_items)
+                    .build()
+            }),
+            typed_errors: cli.typed_errors,
+            status_policy,
+            output_schema_policy,
+            duplicate_tool_names: cli.duplicate_tool_names,
             insecure: cli.insecure,
+            strip_non_standard_bodies: cli.strip_non_standard_bodies,
+            null_value_policy: cli.null_query_values,
+            retry_policy,
+            circuit_breaker,
+            rate_limit,
+            concurrency,
+            proxy,
+            client_certificate,
+            ca_bundle: cli.ca_cert,
+            connection,
+            timeout_policy,
+            response_cache,
+            compression,
+            max_response_bytes: cli.max_response_bytes,
+            redirect_policy,
+            allowed_hosts,
+            hmac_signer,
+            idempotency,
+            confirmation,
+            audit,
+            security_events_file: cli.security_events_file,
+            security_webhook: cli.security_webhook,
+            security_webhook_headers,
+            otlp_endpoint: cli.otlp_endpoint,
+            redaction,
+            cassette,
+            mock: cli.mock,
+            read_only: cli.read_only,
+            client_credentials,
+            api_keys: cli.api_keys,
+            basic_auth,
+        })
+    }
+}
+
+/// Parse `name=<seconds>` timeout entries
+fn parse_timeouts(values: Vec<String>) -> Result<HashMap<String, Duration>, Error> {
+    values
+        .into_iter()
+        .map(|value| {
+            let invalid = |reason: &str| {
+                Error::Cli(CliError::InvalidTimeout {
+                    value: value.clone(),
+                    reason: reason.to_string(),
+                })
+            };
+            let (name, seconds) = value
+                .split_once('=')
+                .ok_or_else(|| invalid("expected 'name=<seconds>'"))?;
+            let seconds: u64 = seconds
+                .trim()
+                .parse()
+                .map_err(|_| invalid("seconds must be a positive integer"))?;
+            if seconds == 0 {
+                return Err(invalid("seconds must be a positive integer"));
+            }
+            Ok((name.trim().to_string(), Duration::from_secs(seconds)))
         })
+        .collect()
+}
+
+/// Parse 'tool=<status>' entries, several entries for a tool adding up
+fn parse_success_statuses(values: Vec<String>) -> Result<HashMap<String, BTreeSet<u16>>, Error> {
+    let mut per_tool: HashMap<String, BTreeSet<u16>> = HashMap::new();
+    for value in values {
+        let invalid = |reason: &str| {
+            Error::Cli(CliError::InvalidSuccessStatus {
+                value: value.clone(),
+                reason: reason.to_string(),
+            })
+        };
+        let (tool, status) = value
+            .split_once('=')
+            .ok_or_else(|| invalid("expected 'tool=<status>'"))?;
+        let status: u16 = status
+            .trim()
+            .parse()
+            .ok()
+            .filter(|status| (100..600).contains(status))
+            .ok_or_else(|| invalid("status must be an HTTP status code"))?;
+        per_tool
+            .entry(tool.trim().to_string())
+            .or_default()
+            .insert(status);
+    }
+    Ok(per_tool)
+}
+
+/// Parse 'alias=tool' entries
+fn parse_tool_aliases(values: Vec<String>) -> Result<BTreeMap<String, String>, Error> {
+    let mut aliases = BTreeMap::new();
+    for value in values {
+        let invalid = |reason: &str| {
+            Error::Cli(CliError::InvalidToolAlias {
+                value: value.clone(),
+                reason: reason.to_string(),
+            })
+        };
+        let (alias, tool) = value
+            .split_once('=')
+            .map(|(alias, tool)| (alias.trim(), tool.trim()))
+            .filter(|(alias, tool)| !alias.is_empty() && !tool.is_empty())
+            .ok_or_else(|| invalid("expected 'alias=tool'"))?;
+        if alias == tool {
+            return Err(invalid("an alias must differ from its tool"));
+        }
+        if aliases
+            .insert(alias.to_string(), tool.to_string())
+            .is_some_and(|other| other != tool)
+        {
+            return Err(invalid("the alias already names another tool"));
+        }
+    }
+    Ok(aliases)
+}
+
+/// Prefix of bind addresses naming a Unix socket
+const UNIX_SOCKET_PREFIX: &str = "unix:";
+
+/// Where the server listens for connections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Listener {
+    /// TCP address, as `host:port`
+    Tcp(String),
+    /// Path of a Unix socket
+    Unix(PathBuf),
+}
+
+impl fmt::Display for Listener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Listener::Tcp(address) => write!(f, "http://{address}"),
+            Listener::Unix(path) => write!(f, "{UNIX_SOCKET_PREFIX}{}", path.display()),
+        }
     }
 }
 
 impl Configuration {
+    /// Where the server listens: `bind_address` and `port`, or the Unix socket
+    /// `bind_address` names
+    pub fn listener(&self) -> Listener {
+        match self.bind_address.strip_prefix(UNIX_SOCKET_PREFIX) {
+            Some(path) => Listener::Unix(PathBuf::from(path)),
+            None => Listener::Tcp(format!("{}:{}", self.bind_address, self.port)),
+        }
+    }
+
     /// Convert Configuration to Server by loading the OpenAPI spec
     pub async fn try_into_server(self) -> Result<Server, Error> {
+        let (Some(spec_location), Some(base_url)) = (self.spec_location, self.base_url) else {
+            return Err(Error::Validation(
+                "An OpenAPI spec and a base URL are required".to_string(),
+            ));
+        };
+
         // Load OpenAPI specification from the spec location
-        let openapi_spec = self.spec_location.load_json(self.insecure).await?;
+        let openapi_spec = spec_location
+            .load_json(
+                self.insecure,
+                self.ca_bundle.as_deref(),
+                self.proxy.as_ref(),
+            )
+            .await?;
 
         let headers = if self.default_headers.is_empty() {
             None
@@ -123,40 +756,155 @@ impl Configuration {
             Some(self.default_headers)
         };
 
-        let mut server = Server::new(
-            openapi_spec,
-            self.base_url,
-            headers,
-            self.filters,
-            self.skip_tool_descriptions,
-            self.skip_parameter_descriptions,
-            self.insecure,
-        );
+        let mut security_event_sinks: Vec<Arc<dyn SecurityEventSink>> = Vec::new();
+        if let Some(path) = &self.security_events_file {
+            security_event_sinks.push(Arc::new(FileEventSink::new(path)?));
+        }
+        if let Some(url) = self.security_webhook {
+            security_event_sinks.push(Arc::new(WebhookEventSink::new(
+                url,
+                self.security_webhook_headers,
+            )?));
+        }
 
-        // Set the authorization mode
-        server.set_authorization_mode(self.authorization_mode);
+        let server = Server::builder()
+            .openapi_spec(openapi_spec)
+            .base_url(base_url)
+            .base_url_routes(self.base_url_routes)
+            .maybe_default_headers(headers)
+            .maybe_filters(self.filters)
+            .maybe_parameter_policy(self.parameter_policy)
+            .skip_tool_descriptions(self.skip_tool_descriptions)
+            .skip_parameter_descriptions(self.skip_parameter_descriptions)
+            .insecure(self.insecure)
+            .strip_non_standard_bodies(self.strip_non_standard_bodies)
+            .null_value_policy(self.null_value_policy)
+            .maybe_retry_policy(self.retry_policy)
+            .maybe_circuit_breaker(self.circuit_breaker)
+            .maybe_rate_limit(self.rate_limit)
+            .maybe_concurrency(self.concurrency)
+            .maybe_proxy(self.proxy)
+            .maybe_client_certificate(self.client_certificate)
+            .maybe_ca_bundle(self.ca_bundle)
+            .maybe_connection(self.connection)
+            .maybe_timeout_policy(self.timeout_policy)
+            .maybe_response_cache(self.response_cache)
+            .maybe_compression(self.compression)
+            .maybe_max_response_bytes(self.max_response_bytes)
+            .maybe_redirect_policy(self.redirect_policy)
+            .maybe_allowed_hosts(self.allowed_hosts)
+            .maybe_request_signer(
+                self.hmac_signer
+                    .map(|signer| Arc::new(signer) as Arc<dyn RequestSigner>),
+            )
+            .maybe_idempotency(self.idempotency)
+            .maybe_confirmation(self.confirmation)
+            .maybe_audit_log(self.audit.map(AuditLog::new).transpose()?.map(Arc::new))
+            .security_event_sinks(security_event_sinks)
+            .maybe_redaction(self.redaction)
+            .maybe_cassette(self.cassette.map(Cassette::open).transpose()?.map(Arc::new))
+            .mock_responses(self.mock)
+            .read_only(self.read_only)
+            .session_configuration(self.session_configuration)
+            .maybe_tools_page_size(self.tools_page_size)
+            .spec_resources(self.spec_resources)
+            .tool_prompts(self.tool_prompts)
+            .tool_search(self.tool_search)
+            .compact_tool_listings(self.compact_tool_listings)
+            .batch_calls(self.batch_calls)
+            .tool_aliases(self.tool_aliases)
+            .maybe_progress_threshold(self.progress_threshold)
+            .maybe_call_queue(self.call_queue.map(CallQueue::new))
+            .maybe_client_log(self.client_logging.then(ClientLog::default))
+            .response_headers(self.response_headers)
+            .maybe_pagination(self.pagination)
+            .response_filter(self.response_filter)
+            .dry_run(self.dry_runs)
+            .maybe_transformer_pipelines(self.transformer_pipelines)
+            .maybe_markdown_tables(self.markdown_tables)
+            .maybe_html_conversion(self.html_conversion)
+            .maybe_blob_store(self.binary_resources.map(BlobStore::new))
+            .maybe_response_budget(self.response_budget)
+            .typed_errors(self.typed_errors)
+            .maybe_status_policy(self.status_policy)
+            .maybe_output_schema_policy(self.output_schema_policy)
+            .maybe_duplicate_tool_names(self.duplicate_tool_names)
+            .maybe_client_credentials(self.client_credentials)
+            .api_keys(self.api_keys)
+            .maybe_basic_auth(self.basic_auth)
+            .authorization_mode(self.authorization_mode)
+            .authorization_rules(self.authorization_rules)
+            .authorization_templates(self.authorization_templates)
+            .maybe_token_exchange(self.token_exchange)
+            .maybe_scope_check(self.scope_check)
+            .build();
 
-        // Set binary metadata
-        server.name = Some(env!("CARGO_PKG_NAME").to_string());
-        server.version = Some(env!("CARGO_PKG_VERSION").to_string());
-        server.instructions = Some(env!("CARGO_PKG_DESCRIPTION").to_string());
+        // Server info and instructions describe the API, from the spec's info
 
         Ok(server)
     }
 }
 
+/// Parse headers from CLI format "name: value"
+fn parse_headers(headers: Vec<String>) -> Result<HeaderMap, Error> {
+    let mut map = HeaderMap::new();
+    for header_str in headers {
+        if let Some((key, value)) = header_str.split_once(':') {
+            let key = key.trim();
+            let value = value.trim();
+
+            if key.is_empty() {
+                return Err(Error::Cli(CliError::InvalidHeaderFormat {
+                    header: header_str,
+                }));
+            }
+
+            // Validate header name using reqwest/http
+            let header_name =
+                http::header::HeaderName::from_bytes(key.as_bytes()).map_err(|e| {
+                    Error::Cli(CliError::InvalidHeaderName {
+                        header: header_str.clone(),
+                        source: e,
+                    })
+                })?;
+
+            // Validate header value using reqwest/http
+            let header_value = http::header::HeaderValue::from_str(value).map_err(|e| {
+                Error::Cli(CliError::InvalidHeaderValue {
+                    header: header_str.clone(),
+                    source: e,
+                })
+            })?;
+
+            map.insert(header_name, header_value);
+        } else {
+            return Err(Error::Cli(CliError::InvalidHeaderFormat {
+                header: header_str,
+            }));
+        }
+    }
+    Ok(map)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::cli::Cli;
     use crate::spec_loader::SpecLocation;
+    use rmcp_openapi::{
+        ApiKeySource, ClientAuthMethod, ContentEncoding, HmacAlgorithm, OutputSchemaMode,
+        RateLimitMode, RedirectMode, SignatureComponent, SignatureEncoding, ToolSelector,
+    };
     use url::Url;
 
     #[test]
     fn test_header_parsing_valid_formats() {
         let cli = Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec![
@@ -169,11 +917,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         };
 
         let config = Configuration::from_cli(cli).unwrap();
@@ -212,8 +1105,11 @@ mod tests {
     #[test]
     fn test_header_parsing_with_spaces() {
         let cli = Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec![
@@ -224,11 +1120,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         };
 
         let config = Configuration::from_cli(cli).unwrap();
@@ -253,8 +1294,11 @@ mod tests {
     #[test]
     fn test_header_parsing_invalid_format_no_equals() {
         let cli = Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec!["InvalidHeaderNoEquals".to_string()],
@@ -262,11 +1306,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         };
 
         let result = Configuration::from_cli(cli);
@@ -280,8 +1469,11 @@ mod tests {
     #[test]
     fn test_header_parsing_invalid_format_empty_key() {
         let cli = Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec![": value".to_string()],
@@ -289,11 +1481,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         };
 
         let result = Configuration::from_cli(cli);
@@ -307,8 +1644,11 @@ mod tests {
     #[test]
     fn test_header_parsing_empty_value_allowed() {
         let cli = Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec!["X-Empty-Header:".to_string()],
@@ -316,11 +1656,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         };
 
         let config = Configuration::from_cli(cli).unwrap();
@@ -338,8 +1823,11 @@ mod tests {
     #[test]
     fn test_no_headers() {
         let cli = Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec![],
@@ -347,11 +1835,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         };
 
         let config = Configuration::from_cli(cli).unwrap();
@@ -361,8 +1994,11 @@ mod tests {
     #[test]
     fn test_header_validation_invalid_header_name() {
         let cli = Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec!["Invalid Header Name: value".to_string()],
@@ -370,11 +2006,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         };
 
         let result = Configuration::from_cli(cli);
@@ -388,8 +2169,11 @@ mod tests {
     #[test]
     fn test_header_validation_invalid_header_value() {
         let cli = Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec!["Valid-Header: invalid\x00value".to_string()],
@@ -397,11 +2181,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         };
 
         let result = Configuration::from_cli(cli);
@@ -414,8 +2343,11 @@ mod tests {
 
     fn minimal_cli() -> Cli {
         Cli {
-            spec: SpecLocation::Url(Url::parse("https://example.com/spec.json").unwrap()),
-            base_url: "https://api.example.com".to_string(),
+            spec: Some(SpecLocation::Url(
+                Url::parse("https://example.com/spec.json").unwrap(),
+            )),
+            base_url: Some("https://api.example.com".to_string()),
+            base_url_routes: vec![],
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             headers: vec![],
@@ -423,11 +2355,156 @@ mod tests {
             methods: None,
             operationids_include: None,
             operationids_exclude: None,
+            mounts: None,
+            path: "/mcp".to_string(),
+            parameter_policy: None,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: vec![],
+            authorization_templates: vec![],
+            token_exchange_url: None,
+            token_exchange_audience: None,
+            token_exchange_resource: None,
+            token_exchange_scopes: vec![],
+            token_exchange_client_id: None,
+            token_exchange_client_secret: None,
+            check_scopes: false,
+            introspection_url: None,
+            introspection_client_id: None,
+            introspection_client_secret: None,
+            jwks_url: None,
+            jwt_issuer: None,
+            jwt_audiences: vec![],
+            mcp_auth_token: None,
+            mcp_auth_token_file: None,
+            admin_token: None,
             skip_tool_descriptions: false,
             skip_parameter_descriptions: false,
             stateful: false,
+            shutdown_timeout_secs: 30,
+            session_configuration: false,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: vec![],
+            progress_threshold_secs: None,
+            queue_workers: None,
+            queue_depth: None,
+            queue_timeout_secs: None,
+            client_logging: false,
+            cors_origins: None,
+            cors_headers: None,
+            cors_methods: None,
+            response_headers: false,
+            follow_pages: None,
+            response_filter: false,
+            dry_runs: false,
+            transformer_pipelines: None,
+            markdown_tables: false,
+            markdown_table_rows: None,
+            html_to: None,
+            binary_resources: false,
+            binary_resources_max_bytes: None,
+            binary_resources_ttl_secs: None,
+            response_budget_bytes: None,
+            response_budget_preview_items: None,
+            typed_errors: false,
+            success_statuses: vec![],
+            tool_success_statuses: vec![],
+            output_schemas: OutputSchemaMode::Strict,
+            no_output_schema: vec![],
+            duplicate_tool_names: None,
             insecure: false,
+            strip_non_standard_bodies: false,
+            null_query_values: NullValuePolicy::default(),
+            max_retries: 0,
+            retry_initial_backoff_ms: 200,
+            retry_max_backoff_ms: 10_000,
+            retry_non_idempotent: false,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_open_secs: 30,
+            rate_limit: None,
+            tool_rate_limits: vec![],
+            rate_limit_mode: RateLimitMode::default(),
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_session: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            connect_timeout_ms: None,
+            tcp_keepalive_secs: None,
+            resolve: vec![],
+            tool_timeouts: vec![],
+            tag_timeouts: vec![],
+            max_timeout_secs: None,
+            cache_ttl_secs: None,
+            cache_max_ttl_secs: None,
+            cache_max_entries: 1000,
+            cache_ignore_cache_control: false,
+            no_decompress_responses: false,
+            compress_requests: None,
+            compress_min_bytes: 1024,
+            max_response_bytes: None,
+            redirects: RedirectMode::Follow,
+            forward_credentials_on_redirect: false,
+            allowed_hosts: vec![],
+            hmac_secret: None,
+            hmac_header: "X-Signature".to_string(),
+            hmac_algorithm: HmacAlgorithm::Sha256,
+            hmac_encoding: SignatureEncoding::Hex,
+            hmac_prefix: String::new(),
+            hmac_components: vec![
+                SignatureComponent::Method,
+                SignatureComponent::Path,
+                SignatureComponent::Query,
+                SignatureComponent::Timestamp,
+                SignatureComponent::Body,
+            ],
+            hmac_separator: "\\n".to_string(),
+            hmac_timestamp_header: "X-Timestamp".to_string(),
+            idempotency_keys: false,
+            idempotency_header: "Idempotency-Key".to_string(),
+            idempotency_methods: vec![reqwest::Method::POST],
+            confirm_destructive: false,
+            confirm_methods: vec![
+                reqwest::Method::DELETE,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+            ],
+            confirmation_ttl_secs: 300,
+            audit_log: None,
+            audit_log_tracing: false,
+            audit_redact_headers: vec![],
+            security_events_file: None,
+            security_webhook: None,
+            security_webhook_headers: vec![],
+            otlp_endpoint: None,
+            redact_headers: vec![],
+            redact_body_paths: vec![],
+            record: None,
+            replay: None,
+            mock: false,
+            read_only: false,
+            oauth2_client_id: None,
+            oauth2_client_secret: None,
+            oauth2_token_url: None,
+            oauth2_scopes: vec![],
+            oauth2_scheme: None,
+            oauth2_client_auth: ClientAuthMethod::Basic,
+            api_keys: vec![],
+            basic_auth_username: None,
+            basic_auth_password: None,
+            basic_auth_scheme: None,
+            config: None,
+            command: None,
         }
     }
 
@@ -445,4 +2522,1120 @@ mod tests {
         let config = Configuration::from_cli(cli).unwrap();
         assert!(!config.insecure);
     }
+
+    #[test]
+    fn retry_policy_disabled_by_default() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.retry_policy.is_none());
+    }
+
+    #[test]
+    fn retry_flags_mapped_to_policy() {
+        let mut cli = minimal_cli();
+        cli.max_retries = 5;
+        cli.retry_initial_backoff_ms = 50;
+        cli.retry_max_backoff_ms = 2_000;
+        cli.retry_non_idempotent = true;
+        let policy = Configuration::from_cli(cli).unwrap().retry_policy.unwrap();
+        assert_eq!(policy.max_retries, 5);
+        assert_eq!(policy.initial_backoff, Duration::from_millis(50));
+        assert_eq!(policy.max_backoff, Duration::from_secs(2));
+        assert!(policy.retry_non_idempotent);
+    }
+
+    #[test]
+    fn circuit_breaker_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.circuit_breaker.is_none());
+
+        let mut cli = minimal_cli();
+        cli.circuit_breaker_threshold = 4;
+        cli.circuit_breaker_open_secs = 10;
+        let breaker = Configuration::from_cli(cli)
+            .unwrap()
+            .circuit_breaker
+            .unwrap();
+        assert_eq!(breaker.failure_threshold, 4);
+        assert_eq!(breaker.open_duration, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn rate_limit_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.rate_limit.is_none());
+
+        let mut cli = minimal_cli();
+        cli.rate_limit = Some("10/s".parse().unwrap());
+        cli.tool_rate_limits = vec!["searchPets=1/m".to_string()];
+        cli.rate_limit_mode = RateLimitMode::Reject;
+        let rate_limit = Configuration::from_cli(cli).unwrap().rate_limit.unwrap();
+        assert_eq!(rate_limit.global.unwrap().requests, 10);
+        assert_eq!(
+            rate_limit.per_tool["searchPets"].per,
+            Duration::from_secs(60)
+        );
+        assert_eq!(rate_limit.mode, RateLimitMode::Reject);
+    }
+
+    #[test]
+    fn concurrency_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.concurrency.is_none());
+
+        let mut cli = minimal_cli();
+        cli.max_concurrent_requests = Some(32);
+        cli.max_concurrent_requests_per_session = Some(4);
+        let concurrency = Configuration::from_cli(cli).unwrap().concurrency.unwrap();
+        assert_eq!(concurrency.global, Some(32));
+        assert_eq!(concurrency.per_session, Some(4));
+    }
+
+    #[test]
+    fn invalid_tool_rate_limit_rejected() {
+        let mut cli = minimal_cli();
+        cli.tool_rate_limits = vec!["searchPets".to_string()];
+        assert!(matches!(
+            Configuration::from_cli(cli),
+            Err(Error::Cli(CliError::InvalidToolRateLimit { .. }))
+        ));
+    }
+
+    #[test]
+    fn proxy_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.proxy.is_none());
+
+        let mut cli = minimal_cli();
+        cli.proxy = Some(Url::parse("http://proxy.corp:3128").unwrap());
+        cli.no_proxy = Some("localhost,.internal".to_string());
+        cli.proxy_username = Some("user".to_string());
+        cli.proxy_password = Some("secret".to_string());
+        let proxy = Configuration::from_cli(cli).unwrap().proxy.unwrap();
+        assert_eq!(proxy.url.as_str(), "http://proxy.corp:3128/");
+        assert_eq!(proxy.no_proxy.as_deref(), Some("localhost,.internal"));
+        assert_eq!(proxy.username.as_deref(), Some("user"));
+        assert_eq!(proxy.password.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn client_certificate_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.client_certificate.is_none());
+
+        let mut cli = minimal_cli();
+        cli.client_cert = Some("certs/client.pem".into());
+        cli.client_key = Some("certs/client.key".into());
+        let certificate = Configuration::from_cli(cli)
+            .unwrap()
+            .client_certificate
+            .unwrap();
+        assert_eq!(certificate.cert_path.to_str(), Some("certs/client.pem"));
+        assert_eq!(certificate.key_path.to_str(), Some("certs/client.key"));
+    }
+
+    #[test]
+    fn ca_cert_flag_mapped() {
+        let mut cli = minimal_cli();
+        cli.ca_cert = Some("certs/ca.pem".into());
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.ca_bundle, Some(PathBuf::from("certs/ca.pem")));
+    }
+
+    #[test]
+    fn connection_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.connection.is_none());
+
+        let mut cli = minimal_cli();
+        cli.pool_max_idle_per_host = Some(16);
+        cli.pool_idle_timeout_secs = Some(60);
+        cli.connect_timeout_ms = Some(1_500);
+        cli.tcp_keepalive_secs = Some(30);
+        let connection = Configuration::from_cli(cli).unwrap().connection.unwrap();
+        assert_eq!(connection.pool_max_idle_per_host, Some(16));
+        assert_eq!(connection.pool_idle_timeout, Some(Duration::from_secs(60)));
+        assert_eq!(
+            connection.connect_timeout,
+            Some(Duration::from_millis(1_500))
+        );
+        assert_eq!(connection.tcp_keepalive, Some(Duration::from_secs(30)));
+
+        let mut cli = minimal_cli();
+        cli.resolve = vec!["api.example.com:443:10.0.0.5".parse().unwrap()];
+        let connection = Configuration::from_cli(cli).unwrap().connection.unwrap();
+        assert_eq!(connection.resolve[0].host, "api.example.com");
+    }
+
+    #[test]
+    fn timeout_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.timeout_policy.is_none());
+
+        let mut cli = minimal_cli();
+        cli.tool_timeouts = vec!["exportReport=120".to_string()];
+        cli.tag_timeouts = vec!["reports=60".to_string()];
+        cli.max_timeout_secs = Some(300);
+        let policy = Configuration::from_cli(cli)
+            .unwrap()
+            .timeout_policy
+            .unwrap();
+        assert_eq!(policy.per_tool["exportReport"], Duration::from_secs(120));
+        assert_eq!(policy.per_tag["reports"], Duration::from_secs(60));
+        assert_eq!(policy.max, Some(Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn invalid_timeout_rejected() {
+        for value in ["exportReport", "exportReport=soon", "exportReport=0"] {
+            let mut cli = minimal_cli();
+            cli.tool_timeouts = vec![value.to_string()];
+            assert!(matches!(
+                Configuration::from_cli(cli),
+                Err(Error::Cli(CliError::InvalidTimeout { .. }))
+            ));
+        }
+    }
+
+    #[test]
+    fn cache_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.response_cache.is_none());
+
+        let mut cli = minimal_cli();
+        cli.cache_ttl_secs = Some(120);
+        cli.cache_max_ttl_secs = Some(600);
+        cli.cache_max_entries = 50;
+        cli.cache_ignore_cache_control = true;
+        let cache = Configuration::from_cli(cli)
+            .unwrap()
+            .response_cache
+            .unwrap();
+        assert_eq!(cache.default_ttl, Duration::from_secs(120));
+        assert_eq!(cache.max_ttl, Some(Duration::from_secs(600)));
+        assert_eq!(cache.max_entries, 50);
+        assert!(!cache.honor_cache_control);
+    }
+
+    #[test]
+    fn compression_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        let compression = config.compression.unwrap();
+        assert!(compression.decompress_responses);
+        assert_eq!(compression.request_encoding, None);
+
+        let mut cli = minimal_cli();
+        cli.compress_requests = Some(ContentEncoding::Brotli);
+        cli.compress_min_bytes = 4096;
+        let compression = Configuration::from_cli(cli).unwrap().compression.unwrap();
+        assert_eq!(compression.request_encoding, Some(ContentEncoding::Brotli));
+        assert_eq!(compression.min_request_size, 4096);
+
+        let mut cli = minimal_cli();
+        cli.no_decompress_responses = true;
+        assert!(Configuration::from_cli(cli).unwrap().compression.is_none());
+    }
+
+    #[test]
+    fn max_response_bytes_mapped() {
+        let mut cli = minimal_cli();
+        cli.max_response_bytes = Some(65536);
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.max_response_bytes, Some(65536));
+    }
+
+    #[test]
+    fn redirect_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.redirect_policy.is_none());
+
+        let mut cli = minimal_cli();
+        cli.redirects = RedirectMode::Limited(3);
+        cli.forward_credentials_on_redirect = true;
+        let policy = Configuration::from_cli(cli)
+            .unwrap()
+            .redirect_policy
+            .unwrap();
+        assert_eq!(policy.mode, RedirectMode::Limited(3));
+        assert!(policy.forward_credentials_cross_origin);
+    }
+
+    #[test]
+    fn allowed_hosts_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.allowed_hosts.is_none());
+
+        let mut cli = minimal_cli();
+        cli.allowed_hosts = vec!["*.example.com".to_string()];
+        let allowlist = Configuration::from_cli(cli).unwrap().allowed_hosts.unwrap();
+        assert!(allowlist.allows("eu.example.com"));
+        assert!(!allowlist.allows("example.net"));
+    }
+
+    #[test]
+    fn hmac_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.hmac_signer.is_none());
+
+        let mut cli = minimal_cli();
+        cli.hmac_secret = Some("s3cret".to_string());
+        cli.hmac_header = "X-Hub-Signature-256".to_string();
+        cli.hmac_prefix = "sha256=".to_string();
+        cli.hmac_components = vec![SignatureComponent::Body];
+        let signer = Configuration::from_cli(cli).unwrap().hmac_signer.unwrap();
+        assert_eq!(signer.secret, b"s3cret");
+        assert_eq!(signer.header, "X-Hub-Signature-256");
+        assert_eq!(signer.prefix, "sha256=");
+        assert_eq!(signer.components, vec![SignatureComponent::Body]);
+        assert_eq!(signer.separator, "\n");
+    }
+
+    #[test]
+    fn parameter_policy_loaded() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .parameter_policy
+                .is_none()
+        );
+
+        let path =
+            std::env::temp_dir().join(format!("rmcp-openapi-policy-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"constraints": [{"parameter": "per_page", "schema": {"maximum": 100}}]}"#,
+        )
+        .unwrap();
+        let mut cli = minimal_cli();
+        cli.parameter_policy = Some(path.clone());
+        let config = Configuration::from_cli(cli);
+        std::fs::remove_file(&path).unwrap();
+
+        let policy = config.unwrap().parameter_policy.unwrap();
+        assert_eq!(policy.constraints.len(), 1);
+        assert_eq!(policy.constraints[0].parameter, "per_page");
+    }
+
+    #[test]
+    fn unix_socket_base_url_accepted() {
+        let mut cli = minimal_cli();
+        cli.base_url = Some("unix:///var/run/docker.sock".to_string());
+        let config = Configuration::from_cli(cli).unwrap();
+        let base_url = config.base_url.unwrap();
+        assert_eq!(base_url.scheme(), "unix");
+        assert_eq!(base_url.path(), "/var/run/docker.sock");
+    }
+
+    #[test]
+    fn idempotency_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.idempotency.is_none());
+
+        let mut cli = minimal_cli();
+        cli.idempotency_keys = true;
+        cli.idempotency_header = "X-Request-Key".to_string();
+        cli.idempotency_methods = vec![reqwest::Method::POST, reqwest::Method::PATCH];
+        let idempotency = Configuration::from_cli(cli).unwrap().idempotency.unwrap();
+        assert_eq!(idempotency.header, "X-Request-Key");
+        assert_eq!(
+            idempotency.methods,
+            vec![reqwest::Method::POST, reqwest::Method::PATCH]
+        );
+    }
+
+    #[test]
+    fn confirmation_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.confirmation.is_none());
+
+        let mut cli = minimal_cli();
+        cli.confirm_destructive = true;
+        cli.confirm_methods = vec![reqwest::Method::DELETE];
+        cli.confirmation_ttl_secs = 60;
+        let confirmation = Configuration::from_cli(cli).unwrap().confirmation.unwrap();
+        assert_eq!(confirmation.methods, vec![reqwest::Method::DELETE]);
+        assert_eq!(confirmation.token_ttl, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn security_event_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.security_events_file.is_none());
+        assert!(config.security_webhook.is_none());
+
+        let mut cli = minimal_cli();
+        cli.security_events_file = Some(PathBuf::from("/var/log/rmcp-security.jsonl"));
+        cli.security_webhook = Some(Url::parse("https://siem.example.com/events").unwrap());
+        cli.security_webhook_headers = vec!["Authorization: Splunk s3cret".to_string()];
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(
+            config.security_events_file,
+            Some(PathBuf::from("/var/log/rmcp-security.jsonl"))
+        );
+        assert_eq!(
+            config.security_webhook.unwrap().as_str(),
+            "https://siem.example.com/events"
+        );
+        assert_eq!(
+            config
+                .security_webhook_headers
+                .get("authorization")
+                .unwrap(),
+            "Splunk s3cret"
+        );
+
+        let mut cli = minimal_cli();
+        cli.security_webhook_headers = vec!["no-colon".to_string()];
+        assert!(Configuration::from_cli(cli).is_err());
+    }
+
+    #[test]
+    fn shutdown_timeout_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert_eq!(config.shutdown_timeout, Duration::from_secs(30));
+
+        let mut cli = minimal_cli();
+        cli.shutdown_timeout_secs = 5;
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.shutdown_timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn session_configuration_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(!config.session_configuration);
+
+        let mut cli = minimal_cli();
+        cli.stateful = true;
+        cli.session_configuration = true;
+        let config = Configuration::from_cli(cli).unwrap();
+        assert!(config.session_configuration);
+    }
+
+    #[test]
+    fn tools_page_size_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.tools_page_size.is_none());
+
+        let mut cli = minimal_cli();
+        cli.tools_page_size = NonZeroUsize::new(100);
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.tools_page_size, NonZeroUsize::new(100));
+    }
+
+    #[test]
+    fn spec_resources_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(!config.spec_resources);
+
+        let mut cli = minimal_cli();
+        cli.spec_resources = true;
+        let config = Configuration::from_cli(cli).unwrap();
+        assert!(config.spec_resources);
+    }
+
+    #[test]
+    fn tool_prompts_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(!config.tool_prompts);
+
+        let mut cli = minimal_cli();
+        cli.tool_prompts = true;
+        let config = Configuration::from_cli(cli).unwrap();
+        assert!(config.tool_prompts);
+    }
+
+    #[test]
+    fn tool_search_mapped() {
+        assert!(!Configuration::from_cli(minimal_cli()).unwrap().tool_search);
+
+        let mut cli = minimal_cli();
+        cli.tool_search = true;
+        assert!(Configuration::from_cli(cli).unwrap().tool_search);
+    }
+
+    #[test]
+    fn compact_tool_listings_mapped() {
+        assert!(
+            !Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .compact_tool_listings
+        );
+
+        let mut cli = minimal_cli();
+        cli.compact_tool_listings = true;
+        assert!(Configuration::from_cli(cli).unwrap().compact_tool_listings);
+    }
+
+    #[test]
+    fn batch_calls_mapped() {
+        assert!(!Configuration::from_cli(minimal_cli()).unwrap().batch_calls);
+
+        let mut cli = minimal_cli();
+        cli.batch_calls = true;
+        assert!(Configuration::from_cli(cli).unwrap().batch_calls);
+    }
+
+    #[test]
+    fn progress_threshold_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.progress_threshold.is_none());
+
+        let mut cli = minimal_cli();
+        cli.progress_threshold_secs = Some(5);
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.progress_threshold, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn call_queue_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.call_queue.is_none());
+
+        let mut cli = minimal_cli();
+        cli.queue_workers = Some(8);
+        cli.queue_timeout_secs = Some(5);
+        let queue = Configuration::from_cli(cli).unwrap().call_queue.unwrap();
+        assert_eq!(queue.workers, 8);
+        assert_eq!(queue.depth, 100);
+        assert_eq!(queue.timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn client_logging_mapped() {
+        assert!(
+            !Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .client_logging
+        );
+
+        let mut cli = minimal_cli();
+        cli.client_logging = true;
+        assert!(Configuration::from_cli(cli).unwrap().client_logging);
+    }
+
+    #[test]
+    fn response_headers_mapped() {
+        assert!(
+            !Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .response_headers
+        );
+
+        let mut cli = minimal_cli();
+        cli.response_headers = true;
+        assert!(Configuration::from_cli(cli).unwrap().response_headers);
+    }
+
+    #[test]
+    fn pagination_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .pagination
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.follow_pages = Some(5);
+        let pagination = Configuration::from_cli(cli).unwrap().pagination.unwrap();
+        assert_eq!(pagination.max_pages, 5);
+    }
+
+    #[test]
+    fn response_filter_mapped() {
+        assert!(
+            !Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .response_filter
+        );
+
+        let mut cli = minimal_cli();
+        cli.response_filter = true;
+        assert!(Configuration::from_cli(cli).unwrap().response_filter);
+    }
+
+    #[test]
+    fn dry_runs_mapped() {
+        assert!(!Configuration::from_cli(minimal_cli()).unwrap().dry_runs);
+
+        let mut cli = minimal_cli();
+        cli.dry_runs = true;
+        assert!(Configuration::from_cli(cli).unwrap().dry_runs);
+    }
+
+    #[test]
+    fn transformer_pipelines_loaded() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .transformer_pipelines
+                .is_none()
+        );
+
+        let path = std::env::temp_dir().join(format!(
+            "rmcp-openapi-pipelines-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            r#"{"pipelines": [{"tag": "users", "steps": [{"redact": ["$..email"]}, {"format": "compact"}]}]}"#,
+        )
+        .unwrap();
+        let mut cli = minimal_cli();
+        cli.transformer_pipelines = Some(path.clone());
+        let config = Configuration::from_cli(cli);
+        std::fs::remove_file(&path).unwrap();
+
+        let pipelines = config.unwrap().transformer_pipelines.unwrap();
+        assert_eq!(pipelines.pipelines.len(), 1);
+        assert_eq!(pipelines.pipelines[0].steps.len(), 2);
+    }
+
+    #[test]
+    fn markdown_tables_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .markdown_tables
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.markdown_tables = true;
+        let tables = Configuration::from_cli(cli)
+            .unwrap()
+            .markdown_tables
+            .unwrap();
+        assert_eq!(tables.max_rows, 50);
+    }
+
+    #[test]
+    fn html_conversion_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .html_conversion
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.html_to = Some(HtmlConversion::Markdown);
+        assert_eq!(
+            Configuration::from_cli(cli).unwrap().html_conversion,
+            Some(HtmlConversion::Markdown)
+        );
+    }
+
+    #[test]
+    fn binary_resources_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .binary_resources
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.binary_resources = true;
+        cli.binary_resources_ttl_secs = Some(60);
+        let config = Configuration::from_cli(cli)
+            .unwrap()
+            .binary_resources
+            .unwrap();
+        assert_eq!(config.max_bytes, 64 * 1024 * 1024);
+        assert_eq!(config.ttl, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn response_budget_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .response_budget
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.response_budget_bytes = Some(4096);
+        assert_eq!(
+            Configuration::from_cli(cli).unwrap().response_budget,
+            Some(BudgetConfig::builder().max_bytes(4096).build())
+        );
+    }
+
+    #[test]
+    fn typed_errors_mapped() {
+        assert!(!Configuration::from_cli(minimal_cli()).unwrap().typed_errors);
+
+        let mut cli = minimal_cli();
+        cli.typed_errors = true;
+        assert!(Configuration::from_cli(cli).unwrap().typed_errors);
+    }
+
+    #[test]
+    fn success_statuses_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .status_policy
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.success_statuses = vec![409];
+        cli.tool_success_statuses =
+            vec!["searchPets=404".to_string(), "searchPets=410".to_string()];
+        let policy = Configuration::from_cli(cli).unwrap().status_policy.unwrap();
+        assert_eq!(policy.success, BTreeSet::from([409]));
+        assert_eq!(policy.per_tool["searchPets"], BTreeSet::from([404, 410]));
+
+        for value in ["searchPets", "searchPets=missing", "searchPets=99"] {
+            let mut cli = minimal_cli();
+            cli.tool_success_statuses = vec![value.to_string()];
+            assert!(matches!(
+                Configuration::from_cli(cli),
+                Err(Error::Cli(CliError::InvalidSuccessStatus { .. }))
+            ));
+        }
+    }
+
+    #[test]
+    fn tool_aliases_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .tool_aliases
+                .is_empty()
+        );
+
+        let mut cli = minimal_cli();
+        cli.tool_aliases = vec!["list_mrs = get_projects_id_merge_requests".to_string()];
+        assert_eq!(
+            Configuration::from_cli(cli).unwrap().tool_aliases,
+            BTreeMap::from([(
+                "list_mrs".to_string(),
+                "get_projects_id_merge_requests".to_string()
+            )])
+        );
+
+        for values in [
+            vec!["list_mrs"],
+            vec!["=getPetById"],
+            vec!["getPetById=getPetById"],
+            vec!["pet=getPetById", "pet=addPet"],
+        ] {
+            let mut cli = minimal_cli();
+            cli.tool_aliases = values.into_iter().map(String::from).collect();
+            assert!(matches!(
+                Configuration::from_cli(cli),
+                Err(Error::Cli(CliError::InvalidToolAlias { .. }))
+            ));
+        }
+    }
+
+    #[test]
+    fn output_schemas_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .output_schema_policy
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.output_schemas = OutputSchemaMode::Lenient;
+        cli.no_output_schema = vec!["searchPets".to_string()];
+        let policy = Configuration::from_cli(cli)
+            .unwrap()
+            .output_schema_policy
+            .unwrap();
+        assert_eq!(policy.mode, OutputSchemaMode::Lenient);
+        assert!(policy.disabled_tools.contains("searchPets"));
+    }
+
+    #[test]
+    fn duplicate_tool_names_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .duplicate_tool_names
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.duplicate_tool_names = Some(DuplicateNameStrategy::Suffix);
+        assert_eq!(
+            Configuration::from_cli(cli).unwrap().duplicate_tool_names,
+            Some(DuplicateNameStrategy::Suffix)
+        );
+    }
+
+    #[test]
+    fn path_mapped() {
+        assert_eq!(Configuration::from_cli(minimal_cli()).unwrap().path, "/mcp");
+
+        let mut cli = minimal_cli();
+        cli.path = "/v1/mcp/".to_string();
+        assert_eq!(Configuration::from_cli(cli).unwrap().path, "/v1/mcp");
+
+        for path in ["/", "v1/mcp"] {
+            let mut cli = minimal_cli();
+            cli.path = path.to_string();
+            assert!(Configuration::from_cli(cli).is_err(), "{path}");
+        }
+    }
+
+    #[test]
+    fn listener_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert_eq!(
+            config.listener(),
+            Listener::Tcp(format!("{}:{}", config.bind_address, config.port))
+        );
+
+        let mut cli = minimal_cli();
+        cli.bind_address = "unix:/run/rmcp-openapi.sock".to_string();
+        let listener = Configuration::from_cli(cli).unwrap().listener();
+        assert_eq!(
+            listener,
+            Listener::Unix(PathBuf::from("/run/rmcp-openapi.sock"))
+        );
+        assert_eq!(listener.to_string(), "unix:/run/rmcp-openapi.sock");
+
+        let mut cli = minimal_cli();
+        cli.bind_address = "unix:".to_string();
+        assert!(Configuration::from_cli(cli).is_err());
+    }
+
+    #[test]
+    fn cors_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .cors
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.cors_origins = Some(vec!["https://app.example.com".to_string()]);
+        cli.cors_methods = Some(vec!["POST".to_string()]);
+        let cors = Configuration::from_cli(cli).unwrap().cors.unwrap();
+        assert_eq!(cors.origins, vec!["https://app.example.com"]);
+        assert_eq!(cors.methods, vec!["POST"]);
+        assert!(cors.headers.contains(&"mcp-session-id".to_string()));
+    }
+
+    #[actix_web::test]
+    async fn mounts_mapped() {
+        let mut cli = minimal_cli();
+        cli.spec = None;
+        cli.base_url = None;
+        cli.mounts = Some(PathBuf::from("mounts.json"));
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.mounts, Some(PathBuf::from("mounts.json")));
+        assert!(config.base_url.is_none());
+        // The top-level configuration only holds process-wide options
+        assert!(config.try_into_server().await.is_err());
+    }
+
+    #[test]
+    fn otlp_endpoint_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.otlp_endpoint.is_none());
+
+        let mut cli = minimal_cli();
+        cli.otlp_endpoint = Some(Url::parse("http://collector:4317/").unwrap());
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(
+            config.otlp_endpoint.unwrap().as_str(),
+            "http://collector:4317/"
+        );
+    }
+
+    #[test]
+    fn audit_log_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.audit.is_none());
+
+        let mut cli = minimal_cli();
+        cli.audit_log_tracing = true;
+        let audit = Configuration::from_cli(cli).unwrap().audit.unwrap();
+        assert_eq!(audit.sink, AuditSink::Tracing);
+
+        let mut cli = minimal_cli();
+        cli.audit_log = Some(PathBuf::from("/var/log/rmcp-audit.jsonl"));
+        cli.audit_redact_headers = vec!["X-Tenant-Token".to_string()];
+        let audit = Configuration::from_cli(cli).unwrap().audit.unwrap();
+        assert_eq!(
+            audit.sink,
+            AuditSink::File(PathBuf::from("/var/log/rmcp-audit.jsonl"))
+        );
+        assert_eq!(audit.redact_headers, vec!["X-Tenant-Token".to_string()]);
+    }
+
+    #[test]
+    fn redaction_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.redaction.is_none());
+
+        let mut cli = minimal_cli();
+        cli.redact_headers = vec!["X-Session".to_string()];
+        cli.redact_body_paths = vec!["$..password".parse().unwrap()];
+        let redaction = Configuration::from_cli(cli).unwrap().redaction.unwrap();
+        assert!(redaction.redacts_header("x-session"));
+        assert_eq!(redaction.body_paths[0].to_string(), "$..password");
+    }
+
+    #[test]
+    fn cassette_flags_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.cassette.is_none());
+
+        let mut cli = minimal_cli();
+        cli.record = Some(PathBuf::from("cassettes/petstore.json"));
+        let cassette = Configuration::from_cli(cli).unwrap().cassette.unwrap();
+        assert_eq!(cassette.mode, CassetteMode::Record);
+        assert_eq!(cassette.path, PathBuf::from("cassettes/petstore.json"));
+
+        let mut cli = minimal_cli();
+        cli.replay = Some(PathBuf::from("cassettes/petstore.json"));
+        let cassette = Configuration::from_cli(cli).unwrap().cassette.unwrap();
+        assert_eq!(cassette.mode, CassetteMode::Replay);
+    }
+
+    #[test]
+    fn null_query_values_mapped() {
+        let mut cli = minimal_cli();
+        cli.null_query_values = NullValuePolicy::Omit;
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.null_value_policy, NullValuePolicy::Omit);
+    }
+
+    #[test]
+    fn strip_non_standard_bodies_flag_mapped() {
+        let mut cli = minimal_cli();
+        cli.strip_non_standard_bodies = true;
+        let config = Configuration::from_cli(cli).unwrap();
+        assert!(config.strip_non_standard_bodies);
+    }
+
+    #[test]
+    fn oauth2_flags_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .client_credentials
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.oauth2_client_id = Some("agent".to_string());
+        cli.oauth2_client_secret = Some("s3cret".to_string());
+        cli.oauth2_scopes = vec!["pets:read".to_string()];
+        cli.oauth2_client_auth = ClientAuthMethod::Post;
+        let config = Configuration::from_cli(cli)
+            .unwrap()
+            .client_credentials
+            .unwrap();
+        assert_eq!(config.client_id, "agent");
+        assert_eq!(config.client_secret, "s3cret");
+        assert_eq!(config.scopes, vec!["pets:read"]);
+        assert_eq!(config.client_auth, ClientAuthMethod::Post);
+        assert!(config.token_url.is_none());
+    }
+
+    #[test]
+    fn api_keys_mapped() {
+        let mut cli = minimal_cli();
+        cli.api_keys = vec![
+            "petstore_auth=env:PETSTORE_KEY".parse().unwrap(),
+            "partner=abc".parse().unwrap(),
+        ];
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.api_keys.len(), 2);
+        assert_eq!(config.api_keys[0].scheme, "petstore_auth");
+        assert_eq!(
+            config.api_keys[0].source,
+            ApiKeySource::Env("PETSTORE_KEY".to_string())
+        );
+    }
+
+    #[test]
+    fn authorization_rules_mapped() {
+        let mut cli = minimal_cli();
+        cli.authorization_rules = vec![
+            "tag:admin=token:s3cret".parse().unwrap(),
+            "tool:listPets=compliant".parse().unwrap(),
+        ];
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.authorization_rules.len(), 2);
+        assert_eq!(
+            config.authorization_rules[0].selector,
+            ToolSelector::Tag("admin".to_string())
+        );
+    }
+
+    #[test]
+    fn base_url_routes_mapped() {
+        let mut cli = minimal_cli();
+        cli.base_url_routes = vec!["tag:store=https://store.internal".parse().unwrap()];
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.base_url_routes.len(), 1);
+        assert_eq!(
+            config.base_url_routes[0].base_url.as_str(),
+            "https://store.internal/"
+        );
+    }
+
+    #[test]
+    fn authorization_templates_mapped() {
+        let mut cli = minimal_cli();
+        cli.authorization_templates = vec!["X-Api-Key: {token}".parse().unwrap()];
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.authorization_templates.len(), 1);
+        assert_eq!(config.authorization_templates[0].name, "x-api-key");
+    }
+
+    #[test]
+    fn token_exchange_flags_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .token_exchange
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.token_exchange_url = Some(Url::parse("https://sts.example.com/token").unwrap());
+        cli.token_exchange_audience = Some("pets-api".to_string());
+        cli.token_exchange_scopes = vec!["pets:read".to_string()];
+        let config = Configuration::from_cli(cli)
+            .unwrap()
+            .token_exchange
+            .unwrap();
+        assert_eq!(config.token_url.as_str(), "https://sts.example.com/token");
+        assert_eq!(config.audience.as_deref(), Some("pets-api"));
+        assert_eq!(config.scopes, vec!["pets:read"]);
+        assert!(config.client_id.is_none());
+    }
+
+    #[test]
+    fn scope_check_flags_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .scope_check
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.check_scopes = true;
+        assert_eq!(
+            Configuration::from_cli(cli).unwrap().scope_check,
+            Some(ScopeSource::JwtClaims)
+        );
+
+        let mut cli = minimal_cli();
+        cli.introspection_url = Some(Url::parse("https://idp.example.com/introspect").unwrap());
+        let Some(ScopeSource::Introspection(config)) =
+            Configuration::from_cli(cli).unwrap().scope_check
+        else {
+            panic!("expected introspection");
+        };
+        assert_eq!(config.url.as_str(), "https://idp.example.com/introspect");
+        assert!(config.client_id.is_none());
+    }
+
+    #[test]
+    fn jwt_validation_flags_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .jwt_validation
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.jwks_url = Some(Url::parse("https://idp.example.com/jwks.json").unwrap());
+        cli.jwt_issuer = Some("https://idp.example.com".to_string());
+        cli.jwt_audiences = vec!["mcp-server".to_string()];
+        let config = Configuration::from_cli(cli)
+            .unwrap()
+            .jwt_validation
+            .unwrap();
+        assert_eq!(
+            config.jwks_url.as_str(),
+            "https://idp.example.com/jwks.json"
+        );
+        assert_eq!(config.issuer.as_deref(), Some("https://idp.example.com"));
+        assert_eq!(config.audiences, vec!["mcp-server"]);
+    }
+
+    #[test]
+    fn mcp_auth_token_read_from_file() {
+        let path =
+            std::env::temp_dir().join(format!("rmcp-openapi-mcp-token-{}", std::process::id()));
+        std::fs::write(&path, "s3cret\n").unwrap();
+        let mut cli = minimal_cli();
+        cli.mcp_auth_token_file = Some(path.clone());
+        let config = Configuration::from_cli(cli);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(config.unwrap().mcp_auth_token.as_deref(), Some("s3cret"));
+
+        let mut cli = minimal_cli();
+        cli.mcp_auth_token = Some(String::new());
+        assert!(matches!(Configuration::from_cli(cli), Err(Error::Auth(_))));
+    }
+
+    #[test]
+    fn admin_token_mapped() {
+        let config = Configuration::from_cli(minimal_cli()).unwrap();
+        assert!(config.admin_token.is_none());
+
+        let mut cli = minimal_cli();
+        cli.admin_token = Some("admin-s3cret".to_string());
+        let config = Configuration::from_cli(cli).unwrap();
+        assert_eq!(config.admin_token.as_deref(), Some("admin-s3cret"));
+
+        let mut cli = minimal_cli();
+        cli.admin_token = Some(String::new());
+        assert!(matches!(Configuration::from_cli(cli), Err(Error::Auth(_))));
+    }
+
+    #[test]
+    fn basic_auth_flags_mapped() {
+        assert!(
+            Configuration::from_cli(minimal_cli())
+                .unwrap()
+                .basic_auth
+                .is_none()
+        );
+
+        let mut cli = minimal_cli();
+        cli.basic_auth_username = Some("agent".to_string());
+        cli.basic_auth_password = Some("s3cret".to_string());
+        cli.basic_auth_scheme = Some("basic".to_string());
+        let basic_auth = Configuration::from_cli(cli).unwrap().basic_auth.unwrap();
+        assert_eq!(basic_auth.username, "agent");
+        assert_eq!(basic_auth.password, "s3cret");
+        assert_eq!(basic_auth.scheme.as_deref(), Some("basic"));
+    }
+
+    #[test]
+    fn mock_flag_mapped() {
+        assert!(!Configuration::from_cli(minimal_cli()).unwrap().mock);
+
+        let mut cli = minimal_cli();
+        cli.mock = true;
+        assert!(Configuration::from_cli(cli).unwrap().mock);
+    }
+
+    #[test]
+    fn read_only_flag_mapped() {
+        assert!(!Configuration::from_cli(minimal_cli()).unwrap().read_only);
+
+        let mut cli = minimal_cli();
+        cli.read_only = true;
+        assert!(Configuration::from_cli(cli).unwrap().read_only);
+    }
 }
@@ -0,0 +1,193 @@
+//! CORS headers for browser-based MCP clients.
+//!
+//! Browsers only let a page call the MCP endpoint from another origin when
+//! the server allows that origin. With allowed origins configured, preflight
+//! `OPTIONS` requests from them are answered before authentication, and the
+//! responses to their requests allow the origin and expose the MCP session
+//! header. Requests from other origins get no CORS header, so browsers keep
+//! refusing them; clients outside a browser are unaffected.
+
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::Method;
+use actix_web::http::header::{
+    ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+    ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_METHOD,
+    HeaderMap, HeaderName, HeaderValue, ORIGIN, VARY,
+};
+use actix_web::middleware::Next;
+use actix_web::{HttpResponse, web};
+use bon::Builder;
+use rmcp::transport::common::http_header::HEADER_SESSION_ID;
+use tracing::debug;
+
+/// How long browsers may cache the answer to a preflight request, in seconds
+const PREFLIGHT_MAX_AGE: &str = "86400";
+
+/// Headers MCP clients send over the streamable HTTP transport
+const DEFAULT_HEADERS: [&str; 6] = [
+    "authorization",
+    "content-type",
+    "accept",
+    "last-event-id",
+    "mcp-session-id",
+    "mcp-protocol-version",
+];
+
+/// Methods of the streamable HTTP transport
+const DEFAULT_METHODS: [&str; 3] = ["GET", "POST", "DELETE"];
+
+/// Origins allowed to call the server from a browser, and what they may send
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct CorsConfig {
+    /// Allowed origins, such as `https://app.example.com`, or `*` for any
+    pub origins: Vec<String>,
+    /// Request headers allowed
+    #[builder(default = DEFAULT_HEADERS.map(String::from).to_vec())]
+    pub headers: Vec<String>,
+    /// Methods allowed
+    #[builder(default = DEFAULT_METHODS.map(String::from).to_vec())]
+    pub methods: Vec<String>,
+}
+
+impl CorsConfig {
+    fn allows(&self, origin: &str) -> bool {
+        self.origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(origin))
+    }
+}
+
+/// Middleware answering the preflight requests of allowed origins and adding
+/// CORS headers to the responses to their requests
+pub async fn apply_cors(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let cors = req.app_data::<web::Data<CorsConfig>>().cloned();
+    let origin = req
+        .headers()
+        .get(ORIGIN)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let (Some(cors), Some(origin)) = (cors, origin) else {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    };
+    if !cors.allows(&origin) {
+        debug!(origin = %origin, "Not allowing cross-origin request");
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    }
+
+    if req.method() == Method::OPTIONS && req.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+    {
+        let mut response = HttpResponse::NoContent().finish();
+        let headers = response.headers_mut();
+        allow_origin(headers, &origin);
+        insert(
+            headers,
+            ACCESS_CONTROL_ALLOW_METHODS,
+            &cors.methods.join(", "),
+        );
+        insert(
+            headers,
+            ACCESS_CONTROL_ALLOW_HEADERS,
+            &cors.headers.join(", "),
+        );
+        insert(headers, ACCESS_CONTROL_MAX_AGE, PREFLIGHT_MAX_AGE);
+        return Ok(req.into_response(response));
+    }
+
+    let mut response = next.call(req).await?.map_into_boxed_body();
+    let headers = response.headers_mut();
+    allow_origin(headers, &origin);
+    insert(headers, ACCESS_CONTROL_EXPOSE_HEADERS, HEADER_SESSION_ID);
+    Ok(response)
+}
+
+fn allow_origin(headers: &mut HeaderMap, origin: &str) {
+    insert(headers, ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    headers.append(VARY, HeaderValue::from_static("origin"));
+}
+
+fn insert(headers: &mut HeaderMap, name: HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        headers.insert(name, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test};
+
+    #[actix_web::test]
+    async fn allowed_origins_get_cors_headers() {
+        let cors = CorsConfig::builder()
+            .origins(vec!["https://app.example.com".to_string()])
+            .build();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(cors))
+                .wrap(from_fn(apply_cors))
+                .route("/mcp", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::default()
+                .method(Method::OPTIONS)
+                .uri("/mcp")
+                .insert_header((ORIGIN, "https://app.example.com"))
+                .insert_header((ACCESS_CONTROL_REQUEST_METHOD, "POST"))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://app.example.com"
+        );
+        assert_eq!(
+            response
+                .headers()
+                .get(ACCESS_CONTROL_ALLOW_METHODS)
+                .unwrap(),
+            "GET, POST, DELETE"
+        );
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::post()
+                .uri("/mcp")
+                .insert_header((ORIGIN, "https://app.example.com"))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get(ACCESS_CONTROL_EXPOSE_HEADERS)
+                .unwrap(),
+            HEADER_SESSION_ID
+        );
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::post()
+                .uri("/mcp")
+                .insert_header((ORIGIN, "https://evil.example.com"))
+                .to_request(),
+        )
+        .await;
+        assert!(
+            response
+                .headers()
+                .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none()
+        );
+    }
+}
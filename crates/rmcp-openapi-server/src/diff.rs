@@ -0,0 +1,357 @@
+//! `diff` subcommand, comparing the tools of two versions of a spec.
+//!
+//! Both tool sets are generated with the options of the command line, so the
+//! report tells what clients of the server would see change. Changes break
+//! existing calls when a tool disappears, or when one of its parameters
+//! disappears, becomes required, changes type or loses allowed values.
+
+use crate::configuration::Configuration;
+use crate::spec_loader::SpecLocation;
+use rmcp_openapi::{Error, ToolMetadata};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use tracing::info;
+
+/// A change of a tool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub description: String,
+    /// Whether calls made to the old tool may fail with the new one
+    pub breaking: bool,
+}
+
+impl Change {
+    fn breaking(description: String) -> Self {
+        Self {
+            description,
+            breaking: true,
+        }
+    }
+
+    fn compatible(description: String) -> Self {
+        Self {
+            description,
+            breaking: false,
+        }
+    }
+}
+
+/// Differences between two tool sets, by tool name
+#[derive(Debug, Default)]
+pub struct ToolSetDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: BTreeMap<String, Vec<Change>>,
+}
+
+impl ToolSetDiff {
+    /// Compare the `old` tools with the `new` ones
+    pub fn between(old: &[ToolMetadata], new: &[ToolMetadata]) -> Self {
+        let old: BTreeMap<&str, &ToolMetadata> =
+            old.iter().map(|tool| (tool.name.as_str(), tool)).collect();
+        let new: BTreeMap<&str, &ToolMetadata> =
+            new.iter().map(|tool| (tool.name.as_str(), tool)).collect();
+
+        let mut diff = Self::default();
+        for (name, old_tool) in &old {
+            match new.get(name) {
+                None => diff.removed.push(name.to_string()),
+                Some(new_tool) => {
+                    let changes = tool_changes(old_tool, new_tool);
+                    if !changes.is_empty() {
+                        diff.changed.insert(name.to_string(), changes);
+                    }
+                }
+            }
+        }
+        diff.added = new
+            .keys()
+            .filter(|name| !old.contains_key(*name))
+            .map(ToString::to_string)
+            .collect();
+        diff
+    }
+
+    /// Number of changes breaking existing calls, removed tools included
+    pub fn breaking_count(&self) -> usize {
+        self.removed.len()
+            + self
+                .changed
+                .values()
+                .flatten()
+                .filter(|change| change.breaking)
+                .count()
+    }
+}
+
+impl fmt::Display for ToolSetDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty() {
+            return writeln!(f, "No tool changes");
+        }
+        for name in &self.added {
+            writeln!(f, "+ {name}")?;
+        }
+        for name in &self.removed {
+            writeln!(f, "- {name} [breaking]")?;
+        }
+        for (name, changes) in &self.changed {
+            writeln!(f, "~ {name}")?;
+            for change in changes {
+                let marker = if change.breaking { " [breaking]" } else { "" };
+                writeln!(f, "    {}{marker}", change.description)?;
+            }
+        }
+        writeln!(
+            f,
+            "{} added, {} removed, {} changed, {} breaking",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len(),
+            self.breaking_count()
+        )
+    }
+}
+
+/// Changes from `old` to `new`, of the same name
+fn tool_changes(old: &ToolMetadata, new: &ToolMetadata) -> Vec<Change> {
+    let mut changes = Vec::new();
+    if !old.method.eq_ignore_ascii_case(&new.method) || old.path != new.path {
+        changes.push(Change::compatible(format!(
+            "endpoint changed from {} {} to {} {}",
+            old.method.to_uppercase(),
+            old.path,
+            new.method.to_uppercase(),
+            new.path
+        )));
+    }
+    changes.extend(parameter_changes(&old.parameters, &new.parameters));
+    if old.description != new.description || old.title != new.title {
+        changes.push(Change::compatible("description changed".to_string()));
+    }
+    if old.output_schema != new.output_schema {
+        changes.push(Change::compatible("output schema changed".to_string()));
+    }
+    changes
+}
+
+/// Changes of the parameters of a tool, from the `old` to the `new` schema
+fn parameter_changes(old: &Value, new: &Value) -> Vec<Change> {
+    let properties = |schema: &Value| {
+        schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let required = |schema: &Value, name: &str| {
+        schema
+            .get("required")
+            .and_then(Value::as_array)
+            .is_some_and(|required| required.iter().any(|r| r == name))
+    };
+    let (old_properties, new_properties) = (properties(old), properties(new));
+
+    let mut changes = Vec::new();
+    for (name, old_schema) in &old_properties {
+        let Some(new_schema) = new_properties.get(name) else {
+            changes.push(Change::breaking(format!("parameter '{name}' removed")));
+            continue;
+        };
+        if !required(old, name) && required(new, name) {
+            changes.push(Change::breaking(format!(
+                "parameter '{name}' became required"
+            )));
+        } else if required(old, name) && !required(new, name) {
+            changes.push(Change::compatible(format!(
+                "parameter '{name}' became optional"
+            )));
+        }
+        if old_schema.get("type") != new_schema.get("type") {
+            changes.push(Change::breaking(format!(
+                "parameter '{name}' changed type from {} to {}",
+                type_name(old_schema),
+                type_name(new_schema)
+            )));
+        }
+        if let (Some(old_values), Some(new_values)) = (
+            old_schema.get("enum").and_then(Value::as_array),
+            new_schema.get("enum").and_then(Value::as_array),
+        ) {
+            let dropped: Vec<String> = old_values
+                .iter()
+                .filter(|value| !new_values.contains(value))
+                .map(Value::to_string)
+                .collect();
+            if !dropped.is_empty() {
+                changes.push(Change::breaking(format!(
+                    "parameter '{name}' no longer accepts {}",
+                    dropped.join(", ")
+                )));
+            }
+        }
+    }
+    for name in new_properties.keys() {
+        if !old_properties.contains_key(name) {
+            changes.push(if required(new, name) {
+                Change::breaking(format!("required parameter '{name}' added"))
+            } else {
+                Change::compatible(format!("parameter '{name}' added"))
+            });
+        }
+    }
+    changes
+}
+
+/// JSON schema type of `schema`, for reports
+fn type_name(schema: &Value) -> String {
+    schema
+        .get("type")
+        .map_or_else(|| "any".to_string(), Value::to_string)
+}
+
+/// Compare the tools of `config` with those of `new_spec`, generated with the
+/// same options, and print the changes
+///
+/// # Errors
+///
+/// Returns an error if either spec cannot be loaded, or if some changes are
+/// breaking and `fail_on_breaking` is set
+pub async fn diff_specs(
+    config: Configuration,
+    new_spec: SpecLocation,
+    fail_on_breaking: bool,
+) -> Result<(), Error> {
+    if config.mounts.is_some() {
+        return Err(Error::Validation(
+            "diff compares the tools of a single server, not of a mounts file".to_string(),
+        ));
+    }
+    let mut new_config = config.clone();
+    new_config.spec_location = Some(new_spec);
+    let old = tools_of(config).await?;
+    let new = tools_of(new_config).await?;
+
+    let diff = ToolSetDiff::between(&old, &new);
+    print!("{diff}");
+    info!(
+        added = diff.added.len(),
+        removed = diff.removed.len(),
+        changed = diff.changed.len(),
+        breaking = diff.breaking_count(),
+        "Compared the tools of the specs"
+    );
+    if fail_on_breaking && diff.breaking_count() > 0 {
+        return Err(Error::Validation(format!(
+            "{} breaking tool changes",
+            diff.breaking_count()
+        )));
+    }
+    Ok(())
+}
+
+/// Tools generated from `config`
+async fn tools_of(config: Configuration) -> Result<Vec<ToolMetadata>, Error> {
+    let mut server = config.try_into_server().await?;
+    server.load_openapi_spec()?;
+    Ok(server
+        .tool_collection
+        .iter()
+        .map(|tool| tool.metadata.clone())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn tool(name: &str, parameters: Value) -> ToolMetadata {
+        ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            parameters,
+            output_schema: None,
+            method: "GET".to_string(),
+            path: format!("/{name}"),
+            tags: vec![],
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn tool_sets_are_compared() {
+        let old = [
+            tool("listPets", json!({ "properties": {} })),
+            tool("deletePet", json!({ "properties": {} })),
+            tool(
+                "findPets",
+                json!({
+                    "properties": {
+                        "status": { "type": "string", "enum": ["available", "sold"] },
+                        "limit": { "type": "integer" },
+                        "tag": { "type": "string" }
+                    },
+                    "required": ["status"]
+                }),
+            ),
+        ];
+        let new = [
+            tool("listPets", json!({ "properties": {} })),
+            tool("addPet", json!({ "properties": {} })),
+            tool(
+                "findPets",
+                json!({
+                    "properties": {
+                        "status": { "type": "string", "enum": ["available"] },
+                        "limit": { "type": "string" },
+                        "owner": { "type": "string" }
+                    },
+                    "required": ["status", "owner"]
+                }),
+            ),
+        ];
+
+        let diff = ToolSetDiff::between(&old, &new);
+        assert_eq!(diff.added, ["addPet"]);
+        assert_eq!(diff.removed, ["deletePet"]);
+        let changes: Vec<&str> = diff.changed["findPets"]
+            .iter()
+            .map(|change| change.description.as_str())
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "parameter 'status' no longer accepts \"sold\"",
+                "parameter 'limit' changed type from \"integer\" to \"string\"",
+                "parameter 'tag' removed",
+                "required parameter 'owner' added",
+            ]
+        );
+        assert_eq!(diff.breaking_count(), 5);
+        assert!(
+            diff.to_string()
+                .ends_with("1 added, 1 removed, 1 changed, 5 breaking\n")
+        );
+    }
+
+    #[test]
+    fn optional_parameters_are_compatible() {
+        let old = [tool("listPets", json!({ "properties": {} }))];
+        let new = [tool(
+            "listPets",
+            json!({ "properties": { "limit": { "type": "integer" } } }),
+        )];
+
+        let diff = ToolSetDiff::between(&old, &new);
+        assert_eq!(diff.breaking_count(), 0);
+        assert_eq!(
+            diff.changed["listPets"],
+            [Change::compatible("parameter 'limit' added".to_string())]
+        );
+    }
+}
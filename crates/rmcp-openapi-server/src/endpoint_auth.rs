@@ -0,0 +1,207 @@
+//! Authentication of MCP clients to the `/mcp` endpoint.
+//!
+//! Clients present either a static token shared with the server or a JWT
+//! validated against a key set; either is accepted when both are configured.
+//! Without any configured, the endpoint is open to anyone who can reach it.
+
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use actix_web::middleware::Next;
+use actix_web::{HttpResponse, web};
+use rmcp_openapi::{Error, JwtValidator};
+use std::fmt;
+use tracing::{debug, warn};
+
+/// How MCP clients authenticate to the endpoint
+#[derive(Default)]
+pub struct EndpointAuth {
+    /// Bearer token clients must present
+    pub token: Option<String>,
+    /// Validator of JWT bearer tokens
+    pub jwt: Option<JwtValidator>,
+}
+
+impl EndpointAuth {
+    fn is_enabled(&self) -> bool {
+        self.token.is_some() || self.jwt.is_some()
+    }
+
+    fn accepts_static(&self, token: &str) -> bool {
+        self.token
+            .as_deref()
+            .is_some_and(|expected| constant_time_eq(expected.as_bytes(), token.as_bytes()))
+    }
+}
+
+impl fmt::Debug for EndpointAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointAuth")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("jwt", &self.jwt)
+            .finish()
+    }
+}
+
+/// Middleware rejecting requests the configured [`EndpointAuth`] does not
+/// accept with `401 Unauthorized`, before they reach the MCP service
+pub async fn require_authentication(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let Some(auth) = req
+        .app_data::<web::Data<EndpointAuth>>()
+        .filter(|auth| auth.is_enabled())
+        .cloned()
+    else {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    };
+
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim().to_string());
+    let Some(token) = token else {
+        debug!("Rejecting MCP request without a bearer token");
+        return Ok(req.into_response(unauthorized("Bearer")));
+    };
+
+    if auth.accepts_static(&token) {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    }
+    let Some(validator) = &auth.jwt else {
+        debug!("Rejecting MCP request with an invalid token");
+        return Ok(req.into_response(unauthorized(r#"Bearer error="invalid_token""#)));
+    };
+
+    match validator.validate(&token).await {
+        Ok(claims) => {
+            debug!(subject = ?claims.get("sub"), "MCP client authenticated");
+            Ok(next.call(req).await?.map_into_boxed_body())
+        }
+        Err(Error::Auth(reason)) => {
+            debug!(reason = %reason, "Rejecting MCP request with an invalid token");
+            Ok(req.into_response(unauthorized(r#"Bearer error="invalid_token""#)))
+        }
+        Err(e) => {
+            warn!(error = %e, "Unable to validate MCP client token");
+            Ok(req.into_response(HttpResponse::ServiceUnavailable().finish()))
+        }
+    }
+}
+
+/// Compare without exiting at the first difference, so response times do not
+/// reveal how much of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn unauthorized(challenge: &'static str) -> HttpResponse {
+    HttpResponse::Unauthorized()
+        .insert_header((WWW_AUTHENTICATE, challenge))
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test};
+    use rmcp_openapi::JwtValidationConfig;
+    use url::Url;
+
+    async fn call(auth: EndpointAuth, authorization: Option<&str>) -> (StatusCode, Option<String>) {
+        let app = test::init_service(
+            App::new().app_data(web::Data::new(auth)).service(
+                web::scope("/mcp")
+                    .wrap(from_fn(require_authentication))
+                    .route("", web::post().to(HttpResponse::Ok)),
+            ),
+        )
+        .await;
+        let mut request = test::TestRequest::post().uri("/mcp");
+        if let Some(authorization) = authorization {
+            request = request.insert_header((AUTHORIZATION, authorization));
+        }
+        let response = test::call_service(&app, request.to_request()).await;
+        let challenge = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .map(|value| value.to_str().unwrap().to_string());
+        (response.status(), challenge)
+    }
+
+    fn jwt_auth() -> EndpointAuth {
+        let config = JwtValidationConfig::builder()
+            // Never fetched: the requests below fail before a key is needed
+            .jwks_url(Url::parse("http://127.0.0.1:9/jwks.json").unwrap())
+            .build();
+        EndpointAuth {
+            jwt: Some(JwtValidator::new(config).unwrap()),
+            ..EndpointAuth::default()
+        }
+    }
+
+    fn token_auth() -> EndpointAuth {
+        EndpointAuth {
+            token: Some("s3cret".to_string()),
+            ..EndpointAuth::default()
+        }
+    }
+
+    #[actix_web::test]
+    async fn open_without_configuration() {
+        let (status, _) = call(EndpointAuth::default(), None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn missing_token_is_challenged() {
+        let (status, challenge) = call(jwt_auth(), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(challenge.as_deref(), Some("Bearer"));
+
+        let (status, _) = call(jwt_auth(), Some("Basic dXNlcjpwYXNz")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn static_token_is_required() {
+        let (status, _) = call(token_auth(), Some("Bearer s3cret")).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, challenge) = call(token_auth(), Some("Bearer s3cre")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            challenge.as_deref(),
+            Some(r#"Bearer error="invalid_token""#)
+        );
+
+        let (status, _) = call(token_auth(), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn static_token_accepted_alongside_jwt() {
+        let auth = EndpointAuth {
+            token: Some("s3cret".to_string()),
+            ..jwt_auth()
+        };
+        let (status, _) = call(auth, Some("Bearer s3cret")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn malformed_token_is_rejected() {
+        let (status, challenge) = call(jwt_auth(), Some("Bearer not-a-jwt")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            challenge.as_deref(),
+            Some(r#"Bearer error="invalid_token""#)
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! `export-tools` subcommand, writing the generated tools to a JSON file.
+//!
+//! The tools are generated as the server would serve them, with every option
+//! applied, and written sorted by name, each as its metadata plus the
+//! annotations clients see:
+//!
+//! ```json
+//! {
+//!   "tools": [
+//!     { "name": "getPetById", "parameters": { ... }, "method": "GET", "path": "/pet/{petId}", "annotations": { ... } }
+//!   ]
+//! }
+//! ```
+//!
+//! Exports of two versions of a spec diff cleanly, and serve for offline
+//! review or documentation.
+
+use crate::configuration::Configuration;
+use rmcp::model::Tool as McpTool;
+use rmcp_openapi::{Error, Server};
+use serde_json::{Value, json};
+use std::path::Path;
+use tracing::info;
+
+/// Generate the tools of `config` and write them to `output`
+pub async fn export_tools(config: Configuration, output: &Path) -> Result<(), Error> {
+    if config.mounts.is_some() {
+        return Err(Error::Validation(
+            "export-tools exports the tools of a single server, not of a mounts file".to_string(),
+        ));
+    }
+    let mut server = config.try_into_server().await?;
+    server.load_openapi_spec()?;
+    let json = serde_json::to_string_pretty(&tools_json(&server)?)?;
+
+    std::fs::write(output, json + "\n")?;
+    info!(
+        tool_count = server.tool_count(),
+        output = %output.display(),
+        "Exported the generated tools"
+    );
+    Ok(())
+}
+
+/// The tools of `server`, sorted by name
+fn tools_json(server: &Server) -> Result<Value, Error> {
+    let mut tools: Vec<_> = server.tool_collection.iter().collect();
+    tools.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+    let tools = tools
+        .into_iter()
+        .map(|tool| {
+            let mut exported = serde_json::to_value(&tool.metadata)?;
+            exported["annotations"] = serde_json::to_value(McpTool::from(tool).annotations)?;
+            Ok(exported)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(json!({ "tools": tools }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    #[actix_web::test]
+    async fn tools_are_exported_sorted() {
+        let spec = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "tags": ["pet"],
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "post": {
+                        "operationId": "addPet",
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                }
+            }
+        });
+        let spec_path = std::env::temp_dir().join(format!(
+            "rmcp-openapi-export-spec-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&spec_path, spec.to_string()).unwrap();
+        let cli = Cli::try_parse_from([
+            "rmcp-openapi-server",
+            spec_path.to_str().unwrap(),
+            "--base-url",
+            "https://api.example.com",
+        ])
+        .unwrap();
+        let mut server = Configuration::from_cli(cli)
+            .unwrap()
+            .try_into_server()
+            .await
+            .unwrap();
+        server.load_openapi_spec().unwrap();
+        std::fs::remove_file(&spec_path).unwrap();
+
+        let exported = tools_json(&server).unwrap();
+        let tools = exported["tools"].as_array().unwrap();
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0]["name"], "addPet");
+        assert_eq!(tools[1]["name"], "listPets");
+        assert_eq!(tools[1]["tags"], json!(["pet"]));
+        assert_eq!(tools[1]["annotations"]["readOnlyHint"], true);
+        assert!(tools[1]["parameters"].is_object());
+    }
+}
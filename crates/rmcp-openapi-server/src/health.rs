@@ -0,0 +1,257 @@
+//! Probe endpoints for orchestrators such as Kubernetes.
+//!
+//! `/healthz` (also served as `/health`) answers as long as the process
+//! serves requests; `/readyz` additionally checks the upstream API of every
+//! mounted server can be reached and the process is not shutting down, so
+//! traffic is only routed to instances able to execute tools;
+//! `/version` reports the running build. None of them require
+//! authentication nor expose more than the tool count and served paths.
+
+use crate::admin::LiveServer;
+use actix_web::{HttpResponse, web};
+use rmcp_openapi::InFlightCalls;
+use serde_json::json;
+use std::time::Duration;
+use tracing::debug;
+
+/// How long the readiness probe waits for the upstream API
+const UPSTREAM_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// What the probe endpoints report on
+pub struct Health {
+    /// Servers mounted in the process
+    pub mounts: Vec<MountHealth>,
+    /// Tool calls in progress, closed once shutdown begins
+    pub in_flight: InFlightCalls,
+}
+
+/// What the probe endpoints report on a mounted server
+pub struct MountHealth {
+    /// Path the server is served under
+    pub path: String,
+    /// The server, replaced when the spec is reloaded
+    pub live: LiveServer,
+}
+
+impl Health {
+    /// Total number of tools of the mounted servers
+    pub fn tool_count(&self) -> usize {
+        self.mounts
+            .iter()
+            .map(|mount| mount.live.tool_count())
+            .sum()
+    }
+
+    /// URL paths the mounted servers are served under
+    pub fn paths(&self) -> Vec<&str> {
+        self.mounts
+            .iter()
+            .map(|mount| mount.path.as_str())
+            .collect()
+    }
+}
+
+impl MountHealth {
+    /// Whether the upstream API answers at all; any HTTP status counts, as
+    /// the base URL itself is rarely an operation of the API
+    ///
+    /// The probe is sent with the HTTP client of the tools, so that it goes
+    /// through the same proxy, certificates, host overrides and Unix socket.
+    async fn upstream_reachable(&self) -> bool {
+        let probe = match self.live.upstream_client() {
+            Ok(client) => client.probe_base_url(UPSTREAM_PROBE_TIMEOUT).await,
+            Err(e) => Err(e),
+        };
+        match probe {
+            Ok(_) => true,
+            Err(e) => {
+                debug!(error = %e, base_url = %self.live.base_url(), "Upstream API unreachable");
+                false
+            }
+        }
+    }
+}
+
+/// Register the probe endpoints
+pub fn configure(config: &mut web::ServiceConfig) {
+    config
+        .route("/health", web::get().to(healthz))
+        .route("/healthz", web::get().to(healthz))
+        .route("/readyz", web::get().to(readyz))
+        .route("/version", web::get().to(version));
+}
+
+async fn healthz(health: web::Data<Health>) -> HttpResponse {
+    HttpResponse::Ok().json(json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+        "spec_loaded": true,
+        "tool_count": health.tool_count(),
+        "paths": health.paths(),
+    }))
+}
+
+async fn readyz(health: web::Data<Health>) -> HttpResponse {
+    if health.in_flight.is_closed() {
+        return HttpResponse::ServiceUnavailable().json(json!({
+            "status": "shutting_down",
+            "spec_loaded": true,
+            "tool_count": health.tool_count(),
+        }));
+    }
+    let mut ready = true;
+    let mut upstreams = Vec::with_capacity(health.mounts.len());
+    for mount in &health.mounts {
+        let reachable = mount.upstream_reachable().await;
+        ready &= reachable;
+        upstreams.push(json!({
+            "mount": mount.path,
+            "url": mount.live.base_url().as_str(),
+            "reachable": reachable,
+        }));
+    }
+    let body = json!({
+        "status": if ready { "ready" } else { "not_ready" },
+        "spec_loaded": true,
+        "tool_count": health.tool_count(),
+        "upstreams": upstreams,
+    });
+    if ready {
+        HttpResponse::Ok().json(body)
+    } else {
+        HttpResponse::ServiceUnavailable().json(body)
+    }
+}
+
+async fn version() -> HttpResponse {
+    HttpResponse::Ok().json(json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test};
+    use rmcp_openapi::Server;
+    use serde_json::Value;
+    use url::Url;
+
+    /// Server for the API at `base_url` with a tool per path of `paths`
+    fn server(base_url: &str, paths: &[&str]) -> Server {
+        let paths: serde_json::Map<_, _> = paths
+            .iter()
+            .map(|path| {
+                let operation_id = path.trim_start_matches('/');
+                (
+                    path.to_string(),
+                    json!({ "get": {
+                        "operationId": operation_id,
+                        "responses": { "200": { "description": "OK" } }
+                    }}),
+                )
+            })
+            .collect();
+        let mut server = Server::builder()
+            .openapi_spec(json!({
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1.0.0" },
+                "paths": paths
+            }))
+            .base_url(Url::parse(base_url).unwrap())
+            .build();
+        server.load_openapi_spec().unwrap();
+        server
+    }
+
+    async fn get(base_url: &str, uri: &str) -> (StatusCode, Value) {
+        get_with(base_url, uri, InFlightCalls::default()).await
+    }
+
+    async fn get_with(base_url: &str, uri: &str, in_flight: InFlightCalls) -> (StatusCode, Value) {
+        let live = LiveServer::new(server(base_url, &["/pets", "/owners", "/vets"]));
+        get_live(live, uri, in_flight).await
+    }
+
+    async fn get_live(
+        live: LiveServer,
+        uri: &str,
+        in_flight: InFlightCalls,
+    ) -> (StatusCode, Value) {
+        let mount = MountHealth {
+            path: "/mcp".to_string(),
+            live,
+        };
+        let health = Health {
+            mounts: vec![mount],
+            in_flight,
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(health))
+                .configure(configure),
+        )
+        .await;
+        let response =
+            test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        let status = response.status();
+        (status, test::read_body_json(response).await)
+    }
+
+    #[actix_web::test]
+    async fn liveness_reports_tools_and_version() {
+        for uri in ["/health", "/healthz"] {
+            let (status, body) = get("http://127.0.0.1:9", uri).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["tool_count"], 3);
+            assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+            assert_eq!(body["paths"], json!(["/mcp"]));
+        }
+
+        let (status, body) = get("http://127.0.0.1:9", "/version").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["name"], "rmcp-openapi-server");
+    }
+
+    #[actix_web::test]
+    async fn tool_count_follows_reloads() {
+        let live = LiveServer::new(server("http://127.0.0.1:9", &["/pets", "/owners"]));
+        live.replace(server("http://127.0.0.1:9", &["/pets"]));
+
+        let (status, body) = get_live(live, "/healthz", InFlightCalls::default()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["tool_count"], 1);
+    }
+
+    #[actix_web::test]
+    async fn readiness_follows_upstream() {
+        let mut upstream = mockito::Server::new_async().await;
+        let probe = upstream
+            .mock("HEAD", "/api/")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let (status, body) = get(&format!("{}/api", upstream.url()), "/readyz").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["upstreams"][0]["reachable"], true);
+        assert_eq!(body["upstreams"][0]["mount"], "/mcp");
+        probe.assert_async().await;
+
+        let (status, body) = get("http://127.0.0.1:9", "/readyz").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "not_ready");
+    }
+
+    #[actix_web::test]
+    async fn not_ready_while_shutting_down() {
+        let in_flight = InFlightCalls::default();
+        in_flight.close();
+        let (status, body) = get_with("http://127.0.0.1:9", "/readyz", in_flight).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "shutting_down");
+    }
+}
@@ -1,16 +1,43 @@
+mod admin;
 mod cli;
+mod config_file;
 mod configuration;
+mod cors;
+mod diff;
+mod endpoint_auth;
+mod export;
+mod health;
+mod mounts;
+mod shutdown;
 mod spec_loader;
+#[cfg(feature = "opentelemetry")]
+mod telemetry;
 
 use std::{process, sync::Arc};
 
+use actix_web::middleware::from_fn;
 use actix_web::{App, HttpServer, web};
-use cli::Cli;
-use configuration::Configuration;
+use admin::{Admin, LiveServer};
+use cli::{Cli, Command};
+use configuration::{Configuration, Listener};
+use endpoint_auth::{EndpointAuth, require_authentication};
+use health::{Health, MountHealth};
+use mounts::Mount;
+#[cfg(feature = "opentelemetry")]
+use opentelemetry::trace::TracerProvider;
+#[cfg(feature = "opentelemetry")]
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use rmcp::transport::common::http_header::HEADER_SESSION_ID;
 use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
 use rmcp_actix_web::transport::StreamableHttpService;
-use rmcp_openapi::Error;
+#[cfg(feature = "opentelemetry")]
+use rmcp_openapi::TraceContext;
+#[cfg(feature = "opentelemetry")]
+use rmcp_openapi::telemetry::{TRACEPARENT, TRACESTATE};
+use rmcp_openapi::{Error, InFlightCalls, JwtValidator, SecretResolver, Server, SessionId};
 use tracing::{debug, error, info, info_span, warn};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 #[actix_web::main]
 async fn main() {
@@ -22,29 +49,234 @@ async fn main() {
 
 async fn run() -> Result<(), Error> {
     // Parse command line arguments
-    let cli = Cli::parse_args();
+    let resolver = SecretResolver::default();
+    let mut cli = Cli::parse_args()?.resolve_secrets(&resolver).await?;
+    let command = cli.command.take();
     let config = Configuration::from_cli(cli)?;
 
-    // Set up structured logging
+    // Set up structured logging, and span export if a collector is configured
+    #[cfg(feature = "opentelemetry")]
+    let tracer_provider = config
+        .otlp_endpoint
+        .as_ref()
+        .map(telemetry::tracer_provider)
+        .transpose()?;
+    #[cfg(feature = "opentelemetry")]
+    setup_logging(tracer_provider.as_ref());
+    #[cfg(not(feature = "opentelemetry"))]
     setup_logging();
+    match &config.otlp_endpoint {
+        #[cfg(feature = "opentelemetry")]
+        Some(endpoint) => info!(otlp_endpoint = %endpoint, "Exporting tool call spans"),
+        #[cfg(not(feature = "opentelemetry"))]
+        Some(_) => {
+            return Err(Error::Validation(
+                "--otlp-endpoint requires a build with the opentelemetry feature".to_string(),
+            ));
+        }
+        None => {}
+    }
 
-    // Surface the TLS bypass before any outbound request can fire.
-    log_insecure_warning(config.insecure);
+    match command {
+        Some(Command::ExportTools { output }) => {
+            return export::export_tools(config, &output).await;
+        }
+        Some(Command::Diff {
+            new_spec,
+            fail_on_breaking,
+        }) => return diff::diff_specs(config, new_spec, fail_on_breaking).await,
+        None => {}
+    }
 
     // Extract values needed after server creation
-    let bind_address = config.bind_address.clone();
-    let port = config.port;
-    let stateful = config.stateful;
+    let listener = config.listener();
+    let shutdown_timeout = config.shutdown_timeout;
+    let cors = config.cors.clone().map(web::Data::new);
 
     let span = info_span!(
         "server_initialization",
-        bind_address = %bind_address,
-        port = port,
+        listener = %listener,
     );
     let _enter = span.enter();
 
+    // Serve the servers of the mounts file, or the one configured on the
+    // command line
+    let mounts = match config.mounts.clone() {
+        Some(mounts_file) => {
+            let mounts = mounts::load(&mounts_file, &config.path, &resolver).await?;
+            info!(
+                mounts_file = %mounts_file.display(),
+                mount_count = mounts.len(),
+                "Mounting servers from mounts file"
+            );
+            mounts
+        }
+        None => vec![Mount {
+            path: config.path.clone(),
+            admin_path: "/admin".to_string(),
+            config,
+        }],
+    };
+
+    // Tool calls of every server are drained on shutdown
+    let in_flight = InFlightCalls::default();
+    let mut endpoints = Vec::with_capacity(mounts.len());
+    let mut mount_health = Vec::with_capacity(mounts.len());
+    for mount in mounts {
+        let (endpoint, health) = start_mount(mount, &in_flight).await?;
+        endpoints.push(endpoint);
+        mount_health.push(health);
+    }
+
+    let health = web::Data::new(Health {
+        mounts: mount_health,
+        in_flight: in_flight.clone(),
+    });
+
+    info!(
+        listener = %listener,
+        "OpenAPI MCP Server starting"
+    );
+
+    let draining = web::Data::new(in_flight.clone());
+    let paths: Vec<_> = endpoints
+        .iter()
+        .map(|endpoint| endpoint.path.clone())
+        .collect();
+
+    let http_server = HttpServer::new(move || {
+        let mut app = App::new()
+            // Answers preflight requests before any authentication
+            .wrap(from_fn(cors::apply_cors))
+            .app_data(health.clone())
+            .app_data(draining.clone())
+            // Probes stay outside the authenticated scope
+            .configure(health::configure);
+        if let Some(cors) = &cors {
+            app = app.app_data(cors.clone());
+        }
+        // Mount MCP services at custom paths
+        for endpoint in &endpoints {
+            if let Some(admin) = &endpoint.admin {
+                app = app.service(
+                    web::scope(&admin.path)
+                        .app_data(admin.admin.clone())
+                        .app_data(admin.auth.clone())
+                        .wrap(from_fn(require_authentication))
+                        .configure(admin::configure),
+                );
+            }
+            app = app.service(
+                web::scope(&endpoint.path)
+                    .app_data(endpoint.auth.clone())
+                    .wrap(from_fn(require_authentication))
+                    .wrap(from_fn(shutdown::refuse_new_sessions))
+                    .service(endpoint.service.clone().scope()),
+            );
+        }
+        app
+    })
+    // Signals are handled by `shutdown`, which drains tool calls before stopping
+    .disable_signals()
+    .shutdown_timeout(shutdown_timeout.as_secs());
+    let http_server = match &listener {
+        Listener::Tcp(address) => http_server.bind(address)?,
+        #[cfg(unix)]
+        Listener::Unix(path) => {
+            remove_stale_socket(path)?;
+            http_server.bind_uds(path)?
+        }
+        #[cfg(not(unix))]
+        Listener::Unix(_) => unreachable!("Unix sockets are rejected on this platform"),
+    }
+    .run();
+    actix_web::rt::spawn(shutdown::on_signal(
+        http_server.handle(),
+        in_flight,
+        shutdown_timeout,
+    ));
+
+    for path in paths {
+        let connection_url = match &listener {
+            Listener::Tcp(_) => format!("{listener}{path}"),
+            Listener::Unix(_) => format!("http://localhost{path} via {listener}"),
+        };
+        info!(
+            connection_url = %connection_url,
+            "Server ready for MCP client connections"
+        );
+    }
+
+    http_server.await?;
+
+    info!("Server stopped");
+
+    // Flush the spans still batched, off the runtime the exporter sends them on
+    #[cfg(feature = "opentelemetry")]
+    if let Some(provider) = tracer_provider
+        && let Ok(Err(e)) = tokio::task::spawn_blocking(move || provider.shutdown()).await
+    {
+        warn!(error = %e, "Unable to export the remaining spans");
+    }
+
+    Ok(())
+}
+
+/// Remove the socket a previous run left at `path`, which would prevent
+/// binding to it
+#[cfg(unix)]
+fn remove_stale_socket(path: &std::path::Path) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+/// An MCP service and the authentication its clients need
+#[derive(Clone)]
+struct Endpoint {
+    path: String,
+    auth: web::Data<EndpointAuth>,
+    service: StreamableHttpService<Server, LocalSessionManager>,
+    admin: Option<AdminEndpoint>,
+}
+
+/// The admin API of an MCP service
+#[derive(Clone)]
+struct AdminEndpoint {
+    path: String,
+    admin: web::Data<Admin>,
+    auth: web::Data<EndpointAuth>,
+}
+
+/// Load the OpenAPI server of `mount`, counting its tool calls in `in_flight`
+async fn start_mount(
+    mount: Mount,
+    in_flight: &InFlightCalls,
+) -> Result<(Endpoint, MountHealth), Error> {
+    let Mount {
+        path,
+        admin_path,
+        config,
+    } = mount;
+    let span = info_span!("mount", path = %path);
+    let _enter = span.enter();
+
+    // Surface the TLS bypass before any outbound request can fire.
+    log_insecure_warning(config.insecure);
+
+    let stateful = config.stateful;
+    let jwt_validation = config.jwt_validation.clone();
+    let mcp_auth_token = config.mcp_auth_token.clone();
+    let admin_token = config.admin_token.clone();
+    // Kept to reload the spec through the admin API
+    let admin_config = admin_token.as_ref().map(|_| config.clone());
+
     // Create server from configuration by loading OpenAPI spec
     let mut server = config.try_into_server().await?;
+    server.in_flight = in_flight.clone();
+    let disabled = server.disabled_tools.clone();
 
     // Parse OpenAPI specification and generate tools
     server.load_openapi_spec()?;
@@ -58,6 +290,12 @@ async fn run() -> Result<(), Error> {
         authorization_mode = ?server.authorization_mode(),
         "Authorization mode configured"
     );
+    if !server.authorization_rules.is_empty() {
+        info!(
+            authorization_rules = ?server.authorization_rules,
+            "Per-tool authorization rules configured"
+        );
+    }
 
     // Log security warning if in passthrough mode
     #[cfg(feature = "authorization-token-passthrough")]
@@ -89,56 +327,111 @@ async fn run() -> Result<(), Error> {
     // Validate the registry
     server.validate_registry()?;
 
-    let bind_addr = format!("{}:{}", bind_address, port);
-    info!(
-        bind_address = %bind_addr,
-        "OpenAPI MCP Server starting"
-    );
+    let auth = web::Data::new(EndpointAuth {
+        token: mcp_auth_token,
+        jwt: jwt_validation.map(JwtValidator::new).transpose()?,
+    });
+    if auth.token.is_some() {
+        info!("MCP clients must present the configured bearer token");
+    }
+    if let Some(validator) = &auth.jwt {
+        info!(
+            jwks_url = %validator.config().jwks_url,
+            "MCP clients must present a valid JWT"
+        );
+    }
+
+    let live = LiveServer::new(server);
+    let health = MountHealth {
+        path: path.clone(),
+        live: live.clone(),
+    };
+    let admin = admin_token.zip(admin_config).map(|(token, config)| {
+        info!(admin_path = %admin_path, "Admin API enabled");
+        AdminEndpoint {
+            path: admin_path,
+            admin: web::Data::new(Admin::new(
+                config,
+                live.clone(),
+                disabled,
+                in_flight.clone(),
+            )),
+            auth: web::Data::new(EndpointAuth {
+                token: Some(token),
+                jwt: None,
+            }),
+        }
+    });
 
     let service = StreamableHttpService::builder()
-        .service_factory(Arc::new(move || Ok(server.clone())))
+        // Each session gets its own settings, and the tools last loaded
+        .service_factory(Arc::new(move || Ok(live.for_session())))
         .session_manager(LocalSessionManager::default().into())
         .stateful_mode(stateful)
+        .on_request_fn(|request, extensions| {
+            // Attribute upstream calls to the MCP session in the audit log
+            if let Some(session_id) = request
+                .headers()
+                .get(HEADER_SESSION_ID)
+                .and_then(|value| value.to_str().ok())
+            {
+                extensions.insert(SessionId(session_id.to_string()));
+            }
+            // Continue the client's trace in tool call spans
+            #[cfg(feature = "opentelemetry")]
+            let tracestate = request
+                .headers()
+                .get(TRACESTATE)
+                .and_then(|value| value.to_str().ok());
+            #[cfg(feature = "opentelemetry")]
+            if let Some(trace_context) = request
+                .headers()
+                .get(TRACEPARENT)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|traceparent| TraceContext::new(traceparent, tracestate))
+            {
+                extensions.insert(trace_context);
+            }
+        })
         .build();
 
-    let http_server = HttpServer::new(move || {
-        App::new()
-            // Mount MCP services at custom paths
-            .service(web::scope("/mcp").service(service.clone().scope()))
-    })
-    .bind(bind_addr.clone())?
-    .run();
-
-    info!(
-        connection_url = %format!("http://{bind_addr}/mcp"),
-        "Server ready for MCP client connections"
-    );
-
-    http_server.await?;
-
-    info!("Shutdown signal received, stopping server");
-
-    Ok(())
+    Ok((
+        Endpoint {
+            path,
+            auth,
+            service,
+            admin,
+        },
+        health,
+    ))
 }
 
-fn setup_logging() {
+fn setup_logging(#[cfg(feature = "opentelemetry")] tracer_provider: Option<&SdkTracerProvider>) {
     // Initialize tracing subscriber for structured logging using RMCP_OPENAPI_LOG
     let env_filter = tracing_subscriber::EnvFilter::try_from_env("RMCP_OPENAPI_LOG")
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
 
-    tracing_subscriber::fmt()
-        .with_env_filter(env_filter)
-        .with_target(true) // Include the target (module path) in logs
-        .init();
+    let registry = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(tracing_subscriber::fmt::layer().with_target(true)); // Include the target (module path) in logs
+
+    // Turn spans into OpenTelemetry spans, for export to the collector
+    #[cfg(feature = "opentelemetry")]
+    let registry = registry.with(tracer_provider.map(|provider| {
+        tracing_opentelemetry::layer().with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+    }));
+
+    registry.init();
 }
 
 /// Emit a single `WARN` log line when TLS verification has been disabled
-/// via `--insecure` / `RMCP_INSECURE`, so operators see the bypass at
-/// startup. No-op when `insecure` is false.
+/// via `--insecure` / `--insecure-skip-tls-verify` / `RMCP_INSECURE`, so
+/// operators see the bypass at startup. No-op when `insecure` is false.
 fn log_insecure_warning(insecure: bool) {
     if insecure {
         warn!(
-            "⚠️  TLS certificate verification is DISABLED (--insecure / RMCP_INSECURE). \
+            "⚠️  TLS certificate verification is DISABLED (--insecure / \
+             --insecure-skip-tls-verify / RMCP_INSECURE). \
              All outbound HTTPS requests will accept invalid, self-signed, or \
              hostname-mismatched certificates. DO NOT USE IN PRODUCTION."
         );
@@ -0,0 +1,162 @@
+//! Several OpenAPI servers in one process.
+//!
+//! A mounts file names the servers to run and gives each the command line
+//! arguments it would be started with on its own, so every server has its own
+//! spec, base URL, credentials and endpoint authentication:
+//!
+//! ```json
+//! {
+//!   "mounts": {
+//!     "github": ["github.json", "--base-url", "https://api.github.com"],
+//!     "gitlab": [
+//!       "https://gitlab.com/api/v4/openapi.json",
+//!       "--base-url", "https://gitlab.com/api/v4",
+//!       "--mcp-auth-token", "env:GITLAB_MCP_TOKEN"
+//!     ]
+//!   }
+//! }
+//! ```
+//!
+//! Each server is served under `<path>/<name>` (`/mcp/<name>` by default), its
+//! admin API under `/admin/<name>`. Process-wide options (`--path`, `--port`,
+//! `--bind-address`, `--otlp-endpoint`, `--shutdown-timeout-secs`, `--cors-*`)
+//! are taken from the command line and ignored in mounts, while `RMCP_*`
+//! environment variables apply to every mount.
+
+use crate::cli::Cli;
+use crate::configuration::Configuration;
+use clap::Parser;
+use rmcp_openapi::{Error, SecretResolver};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MountsFile {
+    mounts: BTreeMap<String, Vec<String>>,
+}
+
+/// An OpenAPI server and the URL paths it is served under
+#[derive(Debug)]
+pub struct Mount {
+    pub path: String,
+    /// Path of its admin API, when enabled
+    pub admin_path: String,
+    pub config: Configuration,
+}
+
+/// Read the servers to mount under `base_path` from the file at `path`,
+/// reading the secrets their options refer to through `resolver`
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed, names no server or
+/// a server with characters other than ASCII letters, digits, `-` and `_`, or
+/// gives a server invalid arguments
+pub async fn load(
+    path: &Path,
+    base_path: &str,
+    resolver: &SecretResolver,
+) -> Result<Vec<Mount>, Error> {
+    let content = std::fs::read_to_string(path)?;
+    let file: MountsFile = serde_json::from_str(&content)
+        .map_err(|e| Error::Validation(format!("Invalid mounts file '{}': {e}", path.display())))?;
+    if file.mounts.is_empty() {
+        return Err(Error::Validation(format!(
+            "Mounts file '{}' names no server",
+            path.display()
+        )));
+    }
+
+    let mut mounts = Vec::with_capacity(file.mounts.len());
+    for (name, args) in file.mounts {
+        mounts.push(Mount {
+            path: format!("{base_path}/{name}"),
+            admin_path: format!("/admin/{name}"),
+            config: mount_configuration(&name, args, resolver).await?,
+        });
+    }
+    Ok(mounts)
+}
+
+async fn mount_configuration(
+    name: &str,
+    args: Vec<String>,
+    resolver: &SecretResolver,
+) -> Result<Configuration, Error> {
+    let valid_name = !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    if !valid_name {
+        return Err(Error::Validation(format!(
+            "Invalid mount name '{name}': use ASCII letters, digits, '-' and '_'"
+        )));
+    }
+
+    let cli = Cli::try_parse_from(std::iter::once(env!("CARGO_PKG_NAME").to_string()).chain(args))
+        .map_err(|e| Error::Validation(format!("Invalid arguments for mount '{name}': {e}")))?;
+    if cli.mounts.is_some() {
+        return Err(Error::Validation(format!(
+            "Mount '{name}' cannot mount other servers"
+        )));
+    }
+    Configuration::from_cli(cli.resolve_secrets(resolver).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn load_json(content: &str) -> Result<Vec<Mount>, Error> {
+        static FILES: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "rmcp-openapi-mounts-{}-{}.json",
+            std::process::id(),
+            FILES.fetch_add(1, Ordering::SeqCst)
+        ));
+        std::fs::write(&path, content).unwrap();
+        let mounts = load(&path, "/mcp", &SecretResolver::default()).await;
+        std::fs::remove_file(&path).unwrap();
+        mounts
+    }
+
+    #[actix_web::test]
+    async fn mounts_have_their_own_configuration() {
+        let mounts = load_json(
+            r#"{
+                "mounts": {
+                    "gitlab": ["gitlab.json", "--base-url", "https://gitlab.com/api/v4", "--read-only"],
+                    "github": ["github.json", "--base-url", "https://api.github.com"]
+                }
+            }"#,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mounts.len(), 2);
+        assert_eq!(mounts[0].path, "/mcp/github");
+        assert_eq!(
+            mounts[0].config.base_url.as_ref().unwrap().as_str(),
+            "https://api.github.com/"
+        );
+        assert!(!mounts[0].config.read_only);
+        assert_eq!(mounts[1].path, "/mcp/gitlab");
+        assert!(mounts[1].config.read_only);
+    }
+
+    #[actix_web::test]
+    async fn invalid_mounts_are_rejected() {
+        for content in [
+            r#"{ "mounts": {} }"#,
+            r#"{ "mounts": { "git/hub": ["github.json", "--base-url", "https://api.github.com"] } }"#,
+            r#"{ "mounts": { "github": ["github.json"] } }"#,
+            r#"{ "mounts": { "github": ["--mounts", "other.json"] } }"#,
+            r#"{ "servers": {} }"#,
+        ] {
+            assert!(load_json(content).await.is_err(), "{content}");
+        }
+    }
+}
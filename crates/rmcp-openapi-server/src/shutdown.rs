@@ -0,0 +1,121 @@
+//! Graceful shutdown of the server.
+//!
+//! On SIGTERM or Ctrl-C, the server stops starting sessions and tool calls,
+//! waits for the tool calls in progress to finish, up to a timeout, and then
+//! stops the HTTP server. Requests of existing sessions are still served
+//! meanwhile, so clients receive the results of the calls they made.
+
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServerHandle, ServiceRequest, ServiceResponse};
+use actix_web::http::header::RETRY_AFTER;
+use actix_web::middleware::Next;
+use actix_web::{HttpResponse, web};
+use rmcp::transport::common::http_header::HEADER_SESSION_ID;
+use rmcp_openapi::InFlightCalls;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Stop `server` once a shutdown signal is received, after draining
+/// `in_flight` for at most `timeout`
+pub async fn on_signal(server: ServerHandle, in_flight: InFlightCalls, timeout: Duration) {
+    signal().await;
+    in_flight.close();
+    info!(
+        in_flight = in_flight.count(),
+        timeout_secs = timeout.as_secs(),
+        "Shutdown signal received, waiting for tool calls in progress"
+    );
+    if tokio::time::timeout(timeout, in_flight.drained())
+        .await
+        .is_err()
+    {
+        warn!(
+            in_flight = in_flight.count(),
+            "Tool calls still in progress at shutdown timeout, abandoning them"
+        );
+    }
+    server.stop(true).await;
+}
+
+/// Wait for SIGTERM or Ctrl-C
+async fn signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = terminate.recv() => {}
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+                return;
+            }
+            Err(e) => warn!(error = %e, "Unable to listen for SIGTERM"),
+        }
+    }
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        warn!(error = %e, "Unable to listen for Ctrl-C");
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Middleware refusing requests that would start a session with
+/// `503 Service Unavailable` once shutdown has begun
+pub async fn refuse_new_sessions(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let draining = req
+        .app_data::<web::Data<InFlightCalls>>()
+        .is_some_and(|in_flight| in_flight.is_closed());
+    if draining && !req.headers().contains_key(HEADER_SESSION_ID) {
+        debug!("Refusing new MCP session while shutting down");
+        return Ok(req.into_response(
+            HttpResponse::ServiceUnavailable()
+                .insert_header((RETRY_AFTER, "1"))
+                .finish(),
+        ));
+    }
+    Ok(next.call(req).await?.map_into_boxed_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test};
+
+    async fn status(in_flight: &InFlightCalls, session_id: Option<&str>) -> StatusCode {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(in_flight.clone()))
+                .service(
+                    web::scope("/mcp")
+                        .wrap(from_fn(refuse_new_sessions))
+                        .route("", web::post().to(HttpResponse::Ok)),
+                ),
+        )
+        .await;
+        let mut request = test::TestRequest::post().uri("/mcp");
+        if let Some(session_id) = session_id {
+            request = request.insert_header((HEADER_SESSION_ID, session_id));
+        }
+        test::call_service(&app, request.to_request())
+            .await
+            .status()
+    }
+
+    #[actix_web::test]
+    async fn sessions_are_refused_once_draining() {
+        let in_flight = InFlightCalls::default();
+        assert_eq!(status(&in_flight, None).await, StatusCode::OK);
+
+        in_flight.close();
+        assert_eq!(
+            status(&in_flight, None).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(status(&in_flight, Some("abc")).await, StatusCode::OK);
+    }
+}
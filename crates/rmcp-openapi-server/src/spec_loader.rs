@@ -1,7 +1,7 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use rmcp_openapi::Error;
+use rmcp_openapi::{Error, ProxyConfig, config::load_ca_bundle};
 use serde_json::Value;
 use url::Url;
 
@@ -40,13 +40,19 @@ impl SpecLocation {
     ///
     /// When loading from a URL, `insecure` is forwarded to
     /// [`load_from_url`] and disables TLS verification for that fetch.
-    /// The flag is ignored for file-based specs.
+    /// `ca_bundle` adds trusted root certificates for that fetch.
+    /// These options and `proxy` are ignored for file-based specs.
     ///
     /// # Errors
     ///
     /// Returns an error if the file cannot be read or the URL cannot be
     /// fetched, or if the resulting body is not valid JSON.
-    pub async fn load_json(&self, insecure: bool) -> Result<Value, Error> {
+    pub async fn load_json(
+        &self,
+        insecure: bool,
+        ca_bundle: Option<&Path>,
+        proxy: Option<&ProxyConfig>,
+    ) -> Result<Value, Error> {
         match self {
             SpecLocation::File(path) => {
                 load_from_file(
@@ -56,7 +62,7 @@ impl SpecLocation {
                 )
                 .await
             }
-            SpecLocation::Url(url) => load_from_url(url, insecure).await,
+            SpecLocation::Url(url) => load_from_url(url, insecure, ca_bundle, proxy).await,
         }
     }
 }
@@ -73,18 +79,37 @@ pub async fn load_from_file(path: &str) -> Result<Value, Error> {
 /// When `insecure` is `true`, the underlying `reqwest::Client` accepts
 /// invalid and hostname-mismatched TLS certificates, mirroring
 /// `curl --insecure`. Otherwise, default TLS verification applies.
+/// Root certificates from `ca_bundle` are trusted in addition to the
+/// built-in ones. The spec is fetched through `proxy` when one is given.
 ///
 /// # Errors
 ///
-/// Returns an error if the HTTP request fails, the response cannot be
-/// read, or the body is not valid JSON.
-pub async fn load_from_url(url: &Url, insecure: bool) -> Result<Value, Error> {
+/// Returns an error if the CA bundle or proxy is invalid, the HTTP request fails, the
+/// response cannot be read, or the body is not valid JSON.
+pub async fn load_from_url(
+    url: &Url,
+    insecure: bool,
+    ca_bundle: Option<&Path>,
+    proxy: Option<&ProxyConfig>,
+) -> Result<Value, Error> {
     let mut builder = reqwest::Client::builder();
     if insecure {
         builder = builder
             .danger_accept_invalid_certs(true)
             .danger_accept_invalid_hostnames(true);
     }
+    if let Some(path) = ca_bundle {
+        for certificate in load_ca_bundle(path)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(
+            proxy
+                .to_reqwest()
+                .map_err(|e| Error::InvalidUrl(format!("Invalid proxy '{}': {e}", proxy.url)))?,
+        );
+    }
     let client = builder
         .build()
         .map_err(|e| Error::Http(format!("Failed to build HTTP client: {e}")))?;
@@ -110,7 +135,7 @@ mod tests {
             .await;
 
         let url: Url = format!("{}/spec.json", server.url()).parse().unwrap();
-        let spec = load_from_url(&url, true).await.unwrap();
+        let spec = load_from_url(&url, true, None, None).await.unwrap();
         assert!(spec.is_object());
         assert_eq!(spec["openapi"], "3.0.0");
 
@@ -129,9 +154,36 @@ mod tests {
             .await;
 
         let url: Url = format!("{}/spec.json", server.url()).parse().unwrap();
-        let spec = load_from_url(&url, false).await.unwrap();
+        let spec = load_from_url(&url, false, None, None).await.unwrap();
         assert!(spec.is_object());
 
         mock.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn load_from_url_goes_through_proxy() {
+        let mut proxy_server = mockito::Server::new_async().await;
+        let mock = proxy_server
+            .mock("GET", mockito::Matcher::Any)
+            .match_header("proxy-authorization", "Basic dXNlcjpzZWNyZXQ=")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"openapi":"3.0.0","info":{"title":"t","version":"1"},"paths":{}}"#)
+            .create_async()
+            .await;
+
+        let proxy = ProxyConfig::builder()
+            .url(proxy_server.url().parse().unwrap())
+            .no_proxy(String::new())
+            .username("user".to_string())
+            .password("secret".to_string())
+            .build();
+        let url: Url = "http://spec.example.invalid/spec.json".parse().unwrap();
+        let spec = load_from_url(&url, false, None, Some(&proxy))
+            .await
+            .unwrap();
+        assert_eq!(spec["openapi"], "3.0.0");
+
+        mock.assert_async().await;
+    }
 }
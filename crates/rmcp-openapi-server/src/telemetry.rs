@@ -0,0 +1,28 @@
+//! Export of tool call spans to an OpenTelemetry collector.
+//!
+//! Spans are sent over OTLP/gRPC, batched on a background thread. The
+//! exporter's connection runs on the server's Tokio runtime, so the provider
+//! must be built and shut down from within it. The service name defaults to
+//! the binary name and can be overridden with `OTEL_SERVICE_NAME`.
+
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use rmcp_openapi::Error;
+use url::Url;
+
+/// Provider of tracers exporting to the OTLP/gRPC collector at `endpoint`,
+/// such as `http://collector:4317`
+pub fn tracer_provider(endpoint: &Url) -> Result<SdkTracerProvider, Error> {
+    let exporter = SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint.as_str())
+        .build()
+        .map_err(|e| Error::Http(format!("Unable to export spans to {endpoint}: {e}")))?;
+    let service_name =
+        std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| env!("CARGO_PKG_NAME").to_string());
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build())
+}
@@ -0,0 +1,227 @@
+//! Audit log of outbound upstream requests.
+//!
+//! With an [`AuditLog`] configured, every upstream call made on behalf of a tool
+//! produces one [`AuditRecord`]: the method, URL, status (or transport
+//! error), duration, the MCP session that triggered it and the request headers.
+//! Records are written either as JSON lines to a file or as `tracing` events on
+//! the [`AUDIT_TARGET`] target, so compliance reviews can reconstruct what an
+//! agent actually did against the upstream API.
+//!
+//! Credentials never reach the log: the usual credential headers, those listed
+//! in [`AuditConfig::redact_headers`], and values marked sensitive such as the
+//! credentials the server attaches itself, are replaced with `<redacted>`. Calls served
+//! from the response cache do not contact the upstream and are not recorded.
+
+use bon::Builder;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+use crate::error::Error;
+use crate::redaction::REDACTED;
+
+/// `tracing` target of audit events written by [`AuditSink::Tracing`]
+pub const AUDIT_TARGET: &str = "rmcp_openapi::audit";
+
+/// Where audit records are written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditSink {
+    /// Emit one `info` event per record on the [`AUDIT_TARGET`] target
+    Tracing,
+    /// Append one JSON object per line to the file, creating it if needed
+    File(PathBuf),
+}
+
+/// How upstream requests are audited
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct AuditConfig {
+    /// Destination of the records
+    pub sink: AuditSink,
+    /// Header names whose values are replaced with `<redacted>`
+    /// (case-insensitive), on top of [`DEFAULT_REDACTED_HEADERS`]
+    #[builder(default)]
+    pub redact_headers: Vec<String>,
+}
+
+/// Headers carrying credentials in common authentication schemes, always
+/// redacted
+pub const DEFAULT_REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+    "api-key",
+];
+
+/// One upstream request as written to the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditRecord {
+    /// When the request was sent, in milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+    /// MCP session the call was made in, when the transport provides one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Name of the tool that issued the request
+    pub tool: String,
+    /// HTTP method, upper case
+    pub method: String,
+    /// Request URL, including the query string
+    pub url: String,
+    /// Response status, absent when the request failed before a response arrived
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Transport error, when no response was received
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Time until the response headers arrived, including retries and redirects
+    pub duration_ms: u64,
+    /// Request headers, with credentials redacted
+    pub request_headers: BTreeMap<String, String>,
+}
+
+/// Writes [`AuditRecord`]s to the configured [`AuditSink`]
+#[derive(Debug)]
+pub struct AuditLog {
+    config: AuditConfig,
+    file: Option<Mutex<File>>,
+}
+
+impl AuditLog {
+    /// Open the audit log described by `config`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the audit file cannot be opened for appending
+    pub fn new(config: AuditConfig) -> Result<Self, Error> {
+        let file = match &config.sink {
+            AuditSink::Tracing => None,
+            AuditSink::File(path) => Some(Mutex::new(
+                OpenOptions::new().create(true).append(true).open(path)?,
+            )),
+        };
+        Ok(Self { config, file })
+    }
+
+    /// Configuration this log was opened with
+    #[must_use]
+    pub fn config(&self) -> &AuditConfig {
+        &self.config
+    }
+
+    /// Copy `headers` into a sorted map, redacting credentials and values marked
+    /// sensitive
+    pub(crate) fn redact_headers(&self, headers: &HeaderMap) -> BTreeMap<String, String> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if value.is_sensitive()
+                    || DEFAULT_REDACTED_HEADERS
+                        .iter()
+                        .copied()
+                        .chain(self.config.redact_headers.iter().map(String::as_str))
+                        .any(|redacted| redacted.eq_ignore_ascii_case(name.as_str()))
+                {
+                    REDACTED.to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                (name.as_str().to_string(), value)
+            })
+            .collect()
+    }
+
+    /// Write a record; failures are logged and never fail the tool call
+    pub(crate) fn record(&self, record: &AuditRecord) {
+        let line = match serde_json::to_string(record) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize audit record");
+                return;
+            }
+        };
+
+        match &self.file {
+            None => info!(
+                target: AUDIT_TARGET,
+                session_id = record.session_id.as_deref(),
+                tool = %record.tool,
+                method = %record.method,
+                url = %record.url,
+                status = record.status,
+                error = record.error.as_deref(),
+                duration_ms = record.duration_ms,
+                record = %line,
+                "Upstream request"
+            ),
+            Some(file) => {
+                let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+                if let Err(e) = writeln!(file, "{line}").and_then(|()| file.flush()) {
+                    warn!(error = %e, "Failed to write audit record");
+                }
+            }
+        }
+    }
+}
+
+/// Milliseconds since the Unix epoch for `time`
+pub(crate) fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_millis()
+        .try_into()
+        .unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn credentials_are_redacted() {
+        let log = AuditLog::new(AuditConfig::builder().sink(AuditSink::Tracing).build()).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("Authorization", HeaderValue::from_static("Bearer secret"));
+        headers.insert("X-Api-Key", HeaderValue::from_static("key"));
+        headers.insert("Accept", HeaderValue::from_static("application/json"));
+
+        let redacted = log.redact_headers(&headers);
+        assert_eq!(redacted["authorization"], "<redacted>");
+        assert_eq!(redacted["x-api-key"], "<redacted>");
+        assert_eq!(redacted["accept"], "application/json");
+    }
+
+    #[test]
+    fn custom_redacted_headers_extend_defaults() {
+        let config = AuditConfig::builder()
+            .sink(AuditSink::Tracing)
+            .redact_headers(vec!["X-Tenant".to_string()])
+            .build();
+        let log = AuditLog::new(config).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant", HeaderValue::from_static("acme"));
+        headers.insert("authorization", HeaderValue::from_static("Bearer secret"));
+
+        let redacted = log.redact_headers(&headers);
+        assert_eq!(redacted["x-tenant"], "<redacted>");
+        assert_eq!(redacted["authorization"], "<redacted>");
+    }
+
+    #[test]
+    fn sensitive_values_are_redacted() {
+        let log = AuditLog::new(AuditConfig::builder().sink(AuditSink::Tracing).build()).unwrap();
+        let mut value = HeaderValue::from_static("secret");
+        value.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-partner-token", value);
+
+        let redacted = log.redact_headers(&headers);
+        assert_eq!(redacted["x-partner-token"], "<redacted>");
+    }
+}
@@ -0,0 +1,322 @@
+//! API keys for the spec's `apiKey` security schemes.
+//!
+//! Each key is bound to a security scheme by name, as in
+//! `petstore_auth=env:PETSTORE_KEY`. The scheme tells where the key goes: the
+//! header, query parameter or cookie named in the spec. Keys are read once,
+//! when the server is built, and attached to every upstream request that does
+//! not already carry that header, parameter or cookie.
+
+use reqwest::Request;
+use reqwest::header::{self, HeaderName, HeaderValue};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::redaction::REDACTED;
+
+/// Where the value of an API key comes from
+#[derive(Clone, PartialEq, Eq)]
+pub enum ApiKeySource {
+    /// Environment variable, written `env:NAME`
+    Env(String),
+    /// The key itself
+    Value(String),
+}
+
+impl fmt::Debug for ApiKeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiKeySource::Env(name) => f.debug_tuple("Env").field(name).finish(),
+            ApiKeySource::Value(_) => f.debug_tuple("Value").field(&REDACTED).finish(),
+        }
+    }
+}
+
+/// API key bound to a security scheme, written `scheme=env:NAME` or `scheme=value`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    /// Name of the `apiKey` security scheme in the spec
+    pub scheme: String,
+    pub source: ApiKeySource,
+}
+
+impl FromStr for ApiKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (scheme, source) = s
+            .split_once('=')
+            .filter(|(scheme, source)| !scheme.is_empty() && !source.is_empty())
+            .ok_or_else(|| {
+                format!("Invalid API key '{s}': expected 'scheme=env:NAME' or 'scheme=value'")
+            })?;
+        let source = match source.strip_prefix("env:") {
+            Some(name) if !name.is_empty() => ApiKeySource::Env(name.to_string()),
+            Some(_) => return Err(format!("Invalid API key '{s}': missing variable name")),
+            None => ApiKeySource::Value(source.to_string()),
+        };
+        Ok(Self {
+            scheme: scheme.to_string(),
+            source,
+        })
+    }
+}
+
+/// Where an API key is sent, from the scheme's `in`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+    Header,
+    Query,
+    Cookie,
+}
+
+/// API key ready to be attached to requests
+#[derive(Clone)]
+pub struct ResolvedApiKey {
+    pub scheme: String,
+    /// Header, query parameter or cookie name
+    pub name: String,
+    pub location: ApiKeyLocation,
+    value: String,
+}
+
+impl fmt::Debug for ResolvedApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedApiKey")
+            .field("scheme", &self.scheme)
+            .field("name", &self.name)
+            .field("location", &self.location)
+            .field("value", &REDACTED)
+            .finish()
+    }
+}
+
+impl ApiKey {
+    /// Look up the scheme in the spec and read the key
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scheme is missing from the spec or is not an
+    /// `apiKey` scheme, or if the environment variable is not set
+    pub fn resolve(&self, spec: &Value) -> Result<ResolvedApiKey, Error> {
+        let scheme = spec
+            .pointer("/components/securitySchemes")
+            .and_then(|schemes| schemes.get(&self.scheme))
+            .ok_or_else(|| {
+                Error::Auth(format!(
+                    "Security scheme '{}' not found in the spec",
+                    self.scheme
+                ))
+            })?;
+        let not_api_key = || {
+            Error::Auth(format!(
+                "Security scheme '{}' is not an apiKey scheme",
+                self.scheme
+            ))
+        };
+        if scheme.get("type").and_then(Value::as_str) != Some("apiKey") {
+            return Err(not_api_key());
+        }
+        let name = scheme
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(not_api_key)?;
+        let location = match scheme.get("in").and_then(Value::as_str) {
+            Some("header") => ApiKeyLocation::Header,
+            Some("query") => ApiKeyLocation::Query,
+            Some("cookie") => ApiKeyLocation::Cookie,
+            _ => return Err(not_api_key()),
+        };
+
+        let value = match &self.source {
+            ApiKeySource::Env(variable) => std::env::var(variable).map_err(|e| {
+                Error::Auth(format!(
+                    "API key for '{}' could not be read from ${variable}: {e}",
+                    self.scheme
+                ))
+            })?,
+            ApiKeySource::Value(value) => value.clone(),
+        };
+        if location == ApiKeyLocation::Header {
+            HeaderName::from_str(name).map_err(|e| {
+                Error::Auth(format!(
+                    "Invalid header name '{name}' in security scheme '{}': {e}",
+                    self.scheme
+                ))
+            })?;
+            HeaderValue::from_str(&value).map_err(|e| {
+                Error::Auth(format!(
+                    "API key for '{}' is not a valid header value: {e}",
+                    self.scheme
+                ))
+            })?;
+        }
+
+        Ok(ResolvedApiKey {
+            scheme: self.scheme.clone(),
+            name: name.to_string(),
+            location,
+            value,
+        })
+    }
+}
+
+/// Header names of the `apiKey` security schemes of `spec` sent in a header
+pub(crate) fn header_names(spec: &Value) -> Vec<HeaderName> {
+    spec.pointer("/components/securitySchemes")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|schemes| schemes.values())
+        .filter(|scheme| {
+            scheme.get("type").and_then(Value::as_str) == Some("apiKey")
+                && scheme.get("in").and_then(Value::as_str) == Some("header")
+        })
+        .filter_map(|scheme| scheme.get("name")?.as_str()?.parse().ok())
+        .collect()
+}
+
+impl ResolvedApiKey {
+    /// Add the key to `request` unless it already carries one under that name
+    pub(crate) fn apply(&self, request: &mut Request) {
+        match self.location {
+            ApiKeyLocation::Header => {
+                // Both were validated when the key was resolved
+                if let (Ok(name), Ok(mut value)) = (
+                    HeaderName::from_str(&self.name),
+                    HeaderValue::from_str(&self.value),
+                ) && !request.headers().contains_key(&name)
+                {
+                    value.set_sensitive(true);
+                    request.headers_mut().insert(name, value);
+                }
+            }
+            ApiKeyLocation::Query => {
+                if !request
+                    .url()
+                    .query_pairs()
+                    .any(|(name, _)| name == self.name)
+                {
+                    request
+                        .url_mut()
+                        .query_pairs_mut()
+                        .append_pair(&self.name, &self.value);
+                }
+            }
+            ApiKeyLocation::Cookie => {
+                let existing = request
+                    .headers()
+                    .get(header::COOKIE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let present = existing.as_deref().is_some_and(|cookies| {
+                    cookies
+                        .split(';')
+                        .any(|cookie| cookie.trim().split('=').next() == Some(self.name.as_str()))
+                });
+                if present {
+                    return;
+                }
+                let cookie = format!("{}={}", self.name, self.value);
+                let cookies = match existing {
+                    Some(existing) if !existing.is_empty() => format!("{existing}; {cookie}"),
+                    _ => cookie,
+                };
+                if let Ok(mut value) = HeaderValue::from_str(&cookies) {
+                    value.set_sensitive(true);
+                    request.headers_mut().insert(header::COOKIE, value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "components": {
+                "securitySchemes": {
+                    "header_key": { "type": "apiKey", "name": "X-API-Key", "in": "header" },
+                    "query_key": { "type": "apiKey", "name": "api_key", "in": "query" },
+                    "cookie_key": { "type": "apiKey", "name": "session", "in": "cookie" },
+                    "basic": { "type": "http", "scheme": "basic" }
+                }
+            }
+        })
+    }
+
+    fn request(url: &str) -> Request {
+        Request::new(Method::GET, url.parse().unwrap())
+    }
+
+    fn resolved(s: &str) -> ResolvedApiKey {
+        s.parse::<ApiKey>().unwrap().resolve(&spec()).unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(
+            "petstore_auth=env:PETSTORE_KEY".parse::<ApiKey>().unwrap(),
+            ApiKey {
+                scheme: "petstore_auth".to_string(),
+                source: ApiKeySource::Env("PETSTORE_KEY".to_string()),
+            }
+        );
+        assert_eq!(
+            "petstore_auth=abc=def".parse::<ApiKey>().unwrap().source,
+            ApiKeySource::Value("abc=def".to_string())
+        );
+        for invalid in [
+            "petstore_auth",
+            "=abc",
+            "petstore_auth=",
+            "petstore_auth=env:",
+        ] {
+            assert!(invalid.parse::<ApiKey>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn keys_go_where_the_scheme_says() {
+        let mut req = request("https://api.example.com/pets?limit=1");
+        resolved("header_key=k1").apply(&mut req);
+        resolved("query_key=k2").apply(&mut req);
+        resolved("cookie_key=k3").apply(&mut req);
+
+        assert_eq!(req.headers()["x-api-key"], "k1");
+        assert_eq!(req.url().query(), Some("limit=1&api_key=k2"));
+        assert_eq!(req.headers()[header::COOKIE], "session=k3");
+    }
+
+    #[test]
+    fn existing_values_are_kept() {
+        let mut req = request("https://api.example.com/pets?api_key=mine");
+        req.headers_mut()
+            .insert("x-api-key", HeaderValue::from_static("mine"));
+        req.headers_mut()
+            .insert(header::COOKIE, HeaderValue::from_static("theme=dark"));
+        resolved("header_key=k1").apply(&mut req);
+        resolved("query_key=k2").apply(&mut req);
+        resolved("cookie_key=k3").apply(&mut req);
+
+        assert_eq!(req.headers()["x-api-key"], "mine");
+        assert_eq!(req.url().query(), Some("api_key=mine"));
+        assert_eq!(req.headers()[header::COOKIE], "theme=dark; session=k3");
+    }
+
+    #[test]
+    fn unusable_schemes_are_rejected() {
+        for key in ["missing=k", "basic=k"] {
+            assert!(key.parse::<ApiKey>().unwrap().resolve(&spec()).is_err());
+        }
+        let unset = "header_key=env:RMCP_OPENAPI_TEST_UNSET_API_KEY"
+            .parse::<ApiKey>()
+            .unwrap();
+        assert!(unset.resolve(&spec()).is_err());
+    }
+}
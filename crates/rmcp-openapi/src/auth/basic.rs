@@ -0,0 +1,173 @@
+//! HTTP Basic authentication (RFC 7617) for `type: http, scheme: basic`
+//! security schemes.
+//!
+//! The username and password are sent base64-encoded in the `Authorization`
+//! header of every upstream request that does not already carry one. The
+//! header is marked sensitive and the password never appears in `Debug`
+//! output; audit logs redact `Authorization` by default.
+
+use base64::prelude::*;
+use bon::Builder;
+use reqwest::Request;
+use reqwest::header::{self, HeaderValue};
+use serde_json::Value;
+use std::fmt;
+
+use crate::error::Error;
+use crate::redaction::REDACTED;
+
+/// Username and password for HTTP Basic authentication
+#[derive(Clone, PartialEq, Eq, Builder)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: String,
+    /// `http` security scheme of the spec these credentials are for, checked
+    /// to use the `basic` scheme when set
+    pub scheme: Option<String>,
+}
+
+impl fmt::Debug for BasicAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BasicAuth")
+            .field("username", &self.username)
+            .field("password", &REDACTED)
+            .field("scheme", &self.scheme)
+            .finish()
+    }
+}
+
+impl BasicAuth {
+    /// Check the credentials, and the security scheme against the spec
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the username contains a colon, if the credentials
+    /// cannot be sent in a header, or if the scheme is missing from the spec or
+    /// is not an HTTP Basic scheme
+    pub fn validate(&self, spec: &Value) -> Result<(), Error> {
+        if self.username.contains(':') {
+            return Err(Error::Auth(
+                "Basic auth usernames cannot contain ':'".to_string(),
+            ));
+        }
+        if self
+            .username
+            .chars()
+            .chain(self.password.chars())
+            .any(char::is_control)
+        {
+            return Err(Error::Auth(
+                "Basic auth credentials cannot contain control characters".to_string(),
+            ));
+        }
+
+        if let Some(name) = &self.scheme {
+            let scheme = spec
+                .pointer("/components/securitySchemes")
+                .and_then(|schemes| schemes.get(name))
+                .ok_or_else(|| {
+                    Error::Auth(format!("Security scheme '{name}' not found in the spec"))
+                })?;
+            let is_basic = scheme.get("type").and_then(Value::as_str) == Some("http")
+                && scheme
+                    .get("scheme")
+                    .and_then(Value::as_str)
+                    .is_some_and(|s| s.eq_ignore_ascii_case("basic"));
+            if !is_basic {
+                return Err(Error::Auth(format!(
+                    "Security scheme '{name}' is not an HTTP Basic scheme"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// `Authorization` header value
+    fn header_value(&self) -> Option<HeaderValue> {
+        let encoded = BASE64_STANDARD.encode(format!("{}:{}", self.username, self.password));
+        let mut value = HeaderValue::from_str(&format!("Basic {encoded}")).ok()?;
+        value.set_sensitive(true);
+        Some(value)
+    }
+
+    /// Authorize `request` unless it already carries an `Authorization` header
+    pub(crate) fn apply(&self, request: &mut Request) {
+        if !request.headers().contains_key(header::AUTHORIZATION)
+            && let Some(value) = self.header_value()
+        {
+            request.headers_mut().insert(header::AUTHORIZATION, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+    use serde_json::json;
+
+    fn credentials(username: &str, password: &str) -> BasicAuth {
+        BasicAuth::builder()
+            .username(username.to_string())
+            .password(password.to_string())
+            .build()
+    }
+
+    #[test]
+    fn header_is_base64_encoded() {
+        let mut request = Request::new(Method::GET, "https://api.example.com".parse().unwrap());
+        // RFC 7617, section 2
+        credentials("Aladdin", "open sesame").apply(&mut request);
+        assert_eq!(
+            request.headers()[header::AUTHORIZATION],
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+        );
+        assert!(request.headers()[header::AUTHORIZATION].is_sensitive());
+
+        // UTF-8 credentials are encoded as is
+        let mut request = Request::new(Method::GET, "https://api.example.com".parse().unwrap());
+        credentials("test", "123£").apply(&mut request);
+        assert_eq!(
+            request.headers()[header::AUTHORIZATION],
+            "Basic dGVzdDoxMjPCow=="
+        );
+    }
+
+    #[test]
+    fn existing_authorization_is_kept() {
+        let mut request = Request::new(Method::GET, "https://api.example.com".parse().unwrap());
+        request
+            .headers_mut()
+            .insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer t"));
+        credentials("user", "pass").apply(&mut request);
+        assert_eq!(request.headers()[header::AUTHORIZATION], "Bearer t");
+    }
+
+    #[test]
+    fn password_is_not_debug_printed() {
+        let debug = format!("{:?}", credentials("user", "hunter2"));
+        assert!(!debug.contains("hunter2"), "{debug}");
+    }
+
+    #[test]
+    fn validation() {
+        let spec = json!({
+            "components": {
+                "securitySchemes": {
+                    "basic": { "type": "http", "scheme": "Basic" },
+                    "bearer": { "type": "http", "scheme": "bearer" }
+                }
+            }
+        });
+        let with_scheme = |scheme: &str| BasicAuth {
+            scheme: Some(scheme.to_string()),
+            ..credentials("user", "pass")
+        };
+        assert!(credentials("user", "pass").validate(&json!({})).is_ok());
+        assert!(with_scheme("basic").validate(&spec).is_ok());
+        assert!(with_scheme("bearer").validate(&spec).is_err());
+        assert!(with_scheme("missing").validate(&spec).is_err());
+        assert!(credentials("us:er", "pass").validate(&spec).is_err());
+        assert!(credentials("user", "pa\nss").validate(&spec).is_err());
+    }
+}
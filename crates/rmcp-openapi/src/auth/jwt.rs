@@ -0,0 +1,196 @@
+//! Validation of JWT bearer tokens presented to the MCP endpoint.
+//!
+//! Unlike the rest of this module, [`JwtValidator`] guards the server itself
+//! rather than its upstream requests: MCP clients must present a token signed
+//! by one of the keys published at a JWKS URL, and optionally issued by a given
+//! issuer for a given audience. The key set is fetched on first use, reused for
+//! [`JWKS_CACHE_TTL`] and fetched again early when a token names a key it does
+//! not contain, so that key rotations are picked up.
+//!
+//! Only asymmetric algorithms are accepted, since a shared secret cannot be
+//! published in a key set.
+
+use bon::Builder;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use reqwest::{Client, header};
+use serde_json::{Map, Value};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+use url::Url;
+
+use crate::error::Error;
+
+/// How long a fetched key set is used before it is fetched again
+pub const JWKS_CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// Minimum time between fetches triggered by unknown key IDs
+const JWKS_MIN_REFRESH: Duration = Duration::from_secs(30);
+
+/// Time allowed for a key set request
+const JWKS_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JWT validation settings
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct JwtValidationConfig {
+    /// URL of the JSON Web Key Set tokens are verified against
+    pub jwks_url: Url,
+    /// Expected `iss` claim; any issuer when not set
+    pub issuer: Option<String>,
+    /// Accepted `aud` claims, any one of which suffices; not checked when
+    /// empty
+    #[builder(default)]
+    pub audiences: Vec<String>,
+    /// Clock skew tolerated on `exp` and `nbf`
+    #[builder(default = Duration::from_secs(60))]
+    pub leeway: Duration,
+}
+
+struct CachedKeys {
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+/// Validates JWT bearer tokens against a published key set
+pub struct JwtValidator {
+    config: JwtValidationConfig,
+    client: Client,
+    keys: Mutex<Option<CachedKeys>>,
+}
+
+impl std::fmt::Debug for JwtValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtValidator")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JwtValidator {
+    /// Create a validator; the key set is fetched on first use
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created
+    pub fn new(config: JwtValidationConfig) -> Result<Self, Error> {
+        let client = Client::builder().timeout(JWKS_REQUEST_TIMEOUT).build()?;
+        Ok(Self {
+            config,
+            client,
+            keys: Mutex::new(None),
+        })
+    }
+
+    /// Configuration this validator was created with
+    #[must_use]
+    pub fn config(&self) -> &JwtValidationConfig {
+        &self.config
+    }
+
+    /// Verify `token` and return its claims
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Auth`] if the token is malformed, signed by an unknown
+    /// key, expired or issued by or for someone else, and [`Error::Http`] if
+    /// the key set cannot be fetched
+    pub async fn validate(&self, token: &str) -> Result<Map<String, Value>, Error> {
+        let header = jsonwebtoken::decode_header(token)
+            .map_err(|e| Error::Auth(format!("Malformed token: {e}")))?;
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            return Err(Error::Auth(format!(
+                "Token algorithm {:?} is not accepted",
+                header.alg
+            )));
+        }
+
+        let key = self.decoding_key(header.kid.as_deref()).await?;
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = self.config.leeway.as_secs();
+        if let Some(issuer) = &self.config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        if self.config.audiences.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&self.config.audiences);
+        }
+
+        jsonwebtoken::decode::<Map<String, Value>>(token, &key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| Error::Auth(format!("Invalid token: {e}")))
+    }
+
+    /// Key for `kid`, or the only key of the set when the token names none
+    async fn decoding_key(&self, kid: Option<&str>) -> Result<DecodingKey, Error> {
+        let find = |keys: &JwkSet| match kid {
+            Some(kid) => keys.find(kid).cloned(),
+            None if keys.keys.len() == 1 => keys.keys.first().cloned(),
+            None => None,
+        };
+
+        let refetch = {
+            let cached = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+            match cached.as_ref() {
+                Some(cached) if cached.fetched_at.elapsed() < JWKS_CACHE_TTL => {
+                    if let Some(jwk) = find(&cached.keys) {
+                        return DecodingKey::from_jwk(&jwk)
+                            .map_err(|e| Error::Auth(format!("Unusable signing key: {e}")));
+                    }
+                    cached.fetched_at.elapsed() >= JWKS_MIN_REFRESH
+                }
+                _ => true,
+            }
+        };
+        if !refetch {
+            return Err(Error::Auth(unknown_key(kid)));
+        }
+
+        let keys = self.fetch_keys().await?;
+        let jwk = find(&keys);
+        *self.keys.lock().unwrap_or_else(|e| e.into_inner()) = Some(CachedKeys {
+            keys,
+            fetched_at: Instant::now(),
+        });
+        let jwk = jwk.ok_or_else(|| Error::Auth(unknown_key(kid)))?;
+        DecodingKey::from_jwk(&jwk).map_err(|e| Error::Auth(format!("Unusable signing key: {e}")))
+    }
+
+    async fn fetch_keys(&self) -> Result<JwkSet, Error> {
+        debug!(jwks_url = %self.config.jwks_url, "Fetching JSON Web Key Set");
+        let response = self
+            .client
+            .get(self.config.jwks_url.clone())
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(|e| {
+                Error::Http(format!(
+                    "Key set request to {} failed: {e}",
+                    self.config.jwks_url
+                ))
+            })?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Http(format!(
+                "Key set endpoint {} returned {status}",
+                self.config.jwks_url
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| Error::Http(format!("Invalid key set: {e}")))
+    }
+}
+
+fn unknown_key(kid: Option<&str>) -> String {
+    match kid {
+        Some(kid) => format!("Token signed with unknown key '{kid}'"),
+        None => "Token names no key and the key set holds several".to_string(),
+    }
+}
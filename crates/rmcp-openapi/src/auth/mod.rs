@@ -0,0 +1,22 @@
+//! Credentials attached to upstream requests.
+//!
+//! Unlike authorization passthrough, where the MCP client's token is forwarded
+//! as is, the credentials in this module belong to the server itself and are
+//! obtained and attached without the MCP client being involved. The exception
+//! are [`token_exchange`], which trades a forwarded client token for one meant
+//! for the upstream, and [`scopes`], which checks the scopes it was granted.
+//! [`jwt`] authenticates MCP clients to the server itself.
+
+pub mod api_key;
+pub mod basic;
+pub mod jwt;
+pub mod oauth2;
+pub mod scopes;
+pub mod token_exchange;
+
+pub use api_key::{ApiKey, ApiKeyLocation, ApiKeySource, ResolvedApiKey};
+pub use basic::BasicAuth;
+pub use jwt::{JwtValidationConfig, JwtValidator};
+pub use oauth2::{ClientAuthMethod, ClientCredentials, ClientCredentialsConfig};
+pub use scopes::{IntrospectionConfig, ScopeChecker, ScopeSource};
+pub use token_exchange::{TokenExchange, TokenExchangeConfig};
@@ -0,0 +1,502 @@
+//! OAuth2 client-credentials grant (RFC 6749, section 4.4).
+//!
+//! [`ClientCredentials`] obtains an access token from the authorization
+//! server's token endpoint, caches it until shortly before it expires and
+//! attaches it to upstream requests as a bearer token. The token URL and scopes
+//! may be configured explicitly or taken from the `clientCredentials` flow of
+//! an `oauth2` security scheme in the spec.
+//!
+//! Requests that already carry an `Authorization` header (passthrough or a
+//! configured default header) are left untouched.
+//!
+//! Expired tokens are renewed with the refresh token when the authorization
+//! server issued one, and by running the grant again otherwise. A `401` from
+//! the upstream to a request carrying one of these tokens expires it early: the
+//! request is sent once more with a renewed token, and only a second `401`
+//! fails the call with an authentication error.
+
+use bon::Builder;
+use reqwest::header::{self, HeaderValue};
+use reqwest::{Client, Request, RequestBuilder, StatusCode};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+use url::Url;
+
+use crate::error::{Error, ToolCallError, ToolCallExecutionError};
+use crate::redaction::REDACTED;
+
+/// Tokens are renewed this long before they expire
+pub(super) const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// Time allowed for a token request
+pub(super) const TOKEN_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How the client authenticates to the token endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClientAuthMethod {
+    /// HTTP Basic authentication (`client_secret_basic`)
+    #[default]
+    Basic,
+    /// Credentials in the form body (`client_secret_post`)
+    Post,
+}
+
+impl FromStr for ClientAuthMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "basic" => Ok(ClientAuthMethod::Basic),
+            "post" => Ok(ClientAuthMethod::Post),
+            _ => Err(format!(
+                "Invalid client authentication method: '{s}'. Valid values: basic, post"
+            )),
+        }
+    }
+}
+
+/// Client-credentials grant settings
+#[derive(Clone, PartialEq, Eq, Builder)]
+pub struct ClientCredentialsConfig {
+    /// Token endpoint; taken from the spec when not set
+    pub token_url: Option<Url>,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Requested scopes; all scopes of the spec's flow when empty
+    #[builder(default)]
+    pub scopes: Vec<String>,
+    /// Security scheme providing the token URL and scopes; the first one with
+    /// a `clientCredentials` flow when not set
+    pub scheme: Option<String>,
+    #[builder(default)]
+    pub client_auth: ClientAuthMethod,
+}
+
+impl fmt::Debug for ClientCredentialsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientCredentialsConfig")
+            .field("token_url", &self.token_url)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &REDACTED)
+            .field("scopes", &self.scopes)
+            .field("scheme", &self.scheme)
+            .field("client_auth", &self.client_auth)
+            .finish()
+    }
+}
+
+impl ClientCredentialsConfig {
+    /// Fill in the token URL and scopes missing from the configuration using
+    /// the spec's security schemes
+    ///
+    /// Relative token URLs are resolved against `base_url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configured scheme is not an `oauth2` scheme with
+    /// a `clientCredentials` flow, or if no token URL can be determined
+    pub fn resolve(mut self, spec: &Value, base_url: &Url) -> Result<Self, Error> {
+        if self.token_url.is_some() && !self.scopes.is_empty() {
+            return Ok(self);
+        }
+
+        let schemes = spec
+            .pointer("/components/securitySchemes")
+            .and_then(Value::as_object);
+        fn flow(scheme: &Value) -> Option<&Value> {
+            scheme
+                .get("type")
+                .filter(|t| *t == "oauth2")
+                .and(scheme.pointer("/flows/clientCredentials"))
+        }
+        let found = match &self.scheme {
+            Some(name) => {
+                let scheme = schemes.and_then(|s| s.get(name)).ok_or_else(|| {
+                    Error::Auth(format!("Security scheme '{name}' not found in the spec"))
+                })?;
+                let flow = flow(scheme).ok_or_else(|| {
+                    Error::Auth(format!(
+                        "Security scheme '{name}' has no OAuth2 clientCredentials flow"
+                    ))
+                })?;
+                Some((name.clone(), flow))
+            }
+            None => schemes.and_then(|schemes| {
+                schemes
+                    .iter()
+                    .find_map(|(name, scheme)| Some((name.clone(), flow(scheme)?)))
+            }),
+        };
+
+        if let Some((name, flow)) = found {
+            if self.token_url.is_none()
+                && let Some(token_url) = flow.get("tokenUrl").and_then(Value::as_str)
+            {
+                self.token_url = Some(base_url.join(token_url).map_err(|e| {
+                    Error::Auth(format!(
+                        "Invalid token URL '{token_url}' in security scheme '{name}': {e}"
+                    ))
+                })?);
+            }
+            if self.scopes.is_empty()
+                && let Some(scopes) = flow.get("scopes").and_then(Value::as_object)
+            {
+                self.scopes = scopes.keys().cloned().collect();
+            }
+            self.scheme = Some(name);
+        }
+
+        if self.token_url.is_none() {
+            return Err(Error::Auth(
+                "No OAuth2 token URL configured and none found in the spec's security schemes"
+                    .to_string(),
+            ));
+        }
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+/// RFC 6749 error response of the token endpoint
+#[derive(Debug, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Grant used to obtain an access token
+enum Grant<'a> {
+    ClientCredentials,
+    RefreshToken(&'a str),
+}
+
+#[derive(Clone)]
+struct AccessToken {
+    value: String,
+    expires_at: Option<Instant>,
+    refresh_token: Option<String>,
+}
+
+impl fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("value", &REDACTED)
+            .field("expires_at", &self.expires_at)
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_ref().map(|_| REDACTED),
+            )
+            .finish()
+    }
+}
+
+impl AccessToken {
+    fn is_usable(&self) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| Instant::now() + EXPIRY_MARGIN < expires_at)
+    }
+}
+
+/// Access tokens obtained with the client-credentials grant, shared by every
+/// tool of a server
+#[derive(Debug)]
+pub struct ClientCredentials {
+    config: ClientCredentialsConfig,
+    token_url: Url,
+    token: Mutex<Option<AccessToken>>,
+}
+
+impl ClientCredentials {
+    /// Create a token source from a configuration with a token URL
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration has no token URL; see
+    /// [`ClientCredentialsConfig::resolve`]
+    pub fn new(config: ClientCredentialsConfig) -> Result<Self, Error> {
+        let token_url = config
+            .token_url
+            .clone()
+            .ok_or_else(|| Error::Auth("No OAuth2 token URL configured".to_string()))?;
+        Ok(Self {
+            config,
+            token_url,
+            token: Mutex::new(None),
+        })
+    }
+
+    /// Configuration this token source was created with
+    #[must_use]
+    pub fn config(&self) -> &ClientCredentialsConfig {
+        &self.config
+    }
+
+    /// Name reported in authentication errors
+    pub(crate) fn scheme_name(&self) -> &str {
+        self.config.scheme.as_deref().unwrap_or("oauth2")
+    }
+
+    /// Current access token, renewing it when none is cached or the cached one
+    /// is about to expire
+    pub(crate) async fn access_token(&self, client: &Client) -> Result<String, ToolCallError> {
+        let refresh_token = match &*self.token.lock().unwrap_or_else(|e| e.into_inner()) {
+            Some(token) if token.is_usable() => return Ok(token.value.clone()),
+            Some(token) => token.refresh_token.clone(),
+            None => None,
+        };
+
+        let result = match &refresh_token {
+            Some(refresh_token) => {
+                match self
+                    .request_token(client, Grant::RefreshToken(refresh_token))
+                    .await
+                {
+                    Ok(token) => Ok(token),
+                    Err(reason) => {
+                        debug!(%reason, "Refreshing the OAuth2 access token failed");
+                        self.request_token(client, Grant::ClientCredentials).await
+                    }
+                }
+            }
+            None => self.request_token(client, Grant::ClientCredentials).await,
+        };
+        let mut token = result.map_err(|reason| self.authentication_failed(reason))?;
+
+        // The refresh token stays valid when a refresh response does not replace it
+        if token.refresh_token.is_none() {
+            token.refresh_token = refresh_token;
+        }
+        let value = token.value.clone();
+        *self.token.lock().unwrap_or_else(|e| e.into_inner()) = Some(token);
+        Ok(value)
+    }
+
+    /// Mark `rejected` as expired so the next call renews it
+    ///
+    /// Does nothing when another call already replaced it.
+    pub(crate) fn expire(&self, rejected: &str) {
+        if let Some(token) = self
+            .token
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_mut()
+            .filter(|token| token.value == rejected)
+        {
+            token.expires_at = Some(Instant::now());
+        }
+    }
+
+    /// Set `token` as the bearer token of `request`
+    pub(crate) fn authorize(
+        &self,
+        request: &mut Request,
+        token: &str,
+    ) -> Result<(), ToolCallError> {
+        let mut value = HeaderValue::from_str(&format!("Bearer {token}")).map_err(|e| {
+            self.authentication_failed(format!("Access token is not a valid header value: {e}"))
+        })?;
+        value.set_sensitive(true);
+        request.headers_mut().insert(header::AUTHORIZATION, value);
+        Ok(())
+    }
+
+    pub(crate) fn authentication_failed(&self, reason: String) -> ToolCallError {
+        ToolCallError::Execution(ToolCallExecutionError::AuthenticationFailed {
+            scheme: self.scheme_name().to_string(),
+            reason,
+        })
+    }
+
+    async fn request_token(
+        &self,
+        client: &Client,
+        grant: Grant<'_>,
+    ) -> Result<AccessToken, String> {
+        let mut form = match grant {
+            Grant::ClientCredentials => {
+                debug!(token_url = %self.token_url, "Requesting OAuth2 access token");
+                let mut form = vec![("grant_type", "client_credentials".to_string())];
+                if !self.config.scopes.is_empty() {
+                    form.push(("scope", self.config.scopes.join(" ")));
+                }
+                form
+            }
+            Grant::RefreshToken(refresh_token) => {
+                debug!(token_url = %self.token_url, "Refreshing OAuth2 access token");
+                vec![
+                    ("grant_type", "refresh_token".to_string()),
+                    ("refresh_token", refresh_token.to_string()),
+                ]
+            }
+        };
+        let mut request = client
+            .post(self.token_url.clone())
+            .timeout(TOKEN_REQUEST_TIMEOUT)
+            .header(header::ACCEPT, "application/json");
+        request = authenticate_client(
+            request,
+            &mut form,
+            self.config.client_auth,
+            &self.config.client_id,
+            &self.config.client_secret,
+        );
+
+        let response = request
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| format!("Token request to {} failed: {e}", self.token_url))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read token response: {e}"))?;
+
+        if !status.is_success() {
+            return Err(token_error(status, &body));
+        }
+
+        let token: TokenResponse =
+            serde_json::from_str(&body).map_err(|e| format!("Invalid token response: {e}"))?;
+        Ok(AccessToken {
+            value: token.access_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| Instant::now() + Duration::from_secs(seconds)),
+            refresh_token: token.refresh_token,
+        })
+    }
+}
+
+/// Authenticate a token request as `client_id`, with HTTP Basic or in the form
+pub(super) fn authenticate_client(
+    request: RequestBuilder,
+    form: &mut Vec<(&str, String)>,
+    method: ClientAuthMethod,
+    client_id: &str,
+    client_secret: &str,
+) -> RequestBuilder {
+    match method {
+        ClientAuthMethod::Basic => request.basic_auth(
+            form_urlencode(client_id),
+            Some(form_urlencode(client_secret)),
+        ),
+        ClientAuthMethod::Post => {
+            form.push(("client_id", client_id.to_string()));
+            form.push(("client_secret", client_secret.to_string()));
+            request
+        }
+    }
+}
+
+/// Describe an error response of a token endpoint
+pub(super) fn token_error(status: StatusCode, body: &str) -> String {
+    match serde_json::from_str::<TokenErrorResponse>(body) {
+        Ok(TokenErrorResponse {
+            error,
+            error_description: Some(description),
+        }) => format!("Token endpoint returned {status}: {error} ({description})"),
+        Ok(TokenErrorResponse { error, .. }) => {
+            format!("Token endpoint returned {status}: {error}")
+        }
+        Err(_) => format!("Token endpoint returned {status}"),
+    }
+}
+
+/// Client credentials are form-encoded before Basic encoding (RFC 6749, section 2.3.1)
+fn form_urlencode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "components": {
+                "securitySchemes": {
+                    "api_key": { "type": "apiKey", "name": "key", "in": "header" },
+                    "service": {
+                        "type": "oauth2",
+                        "flows": {
+                            "clientCredentials": {
+                                "tokenUrl": "/oauth/token",
+                                "scopes": { "pets:read": "Read pets", "pets:write": "Write pets" }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    fn config() -> ClientCredentialsConfig {
+        ClientCredentialsConfig::builder()
+            .client_id("id".to_string())
+            .client_secret("secret".to_string())
+            .build()
+    }
+
+    #[test]
+    fn token_url_and_scopes_come_from_the_spec() {
+        let base_url = Url::parse("https://api.example.com/v1/").unwrap();
+        let resolved = config().resolve(&spec(), &base_url).unwrap();
+        assert_eq!(
+            resolved.token_url.unwrap().as_str(),
+            "https://api.example.com/oauth/token"
+        );
+        assert_eq!(resolved.scopes, vec!["pets:read", "pets:write"]);
+        assert_eq!(resolved.scheme.as_deref(), Some("service"));
+    }
+
+    #[test]
+    fn configured_values_take_precedence() {
+        let base_url = Url::parse("https://api.example.com/").unwrap();
+        let mut config = config();
+        config.token_url = Some(Url::parse("https://idp.example.com/token").unwrap());
+        config.scopes = vec!["pets:read".to_string()];
+        let resolved = config.resolve(&spec(), &base_url).unwrap();
+        assert_eq!(
+            resolved.token_url.unwrap().as_str(),
+            "https://idp.example.com/token"
+        );
+        assert_eq!(resolved.scopes, vec!["pets:read"]);
+    }
+
+    #[test]
+    fn unusable_schemes_are_rejected() {
+        let base_url = Url::parse("https://api.example.com/").unwrap();
+        let with_scheme = |scheme: &str| {
+            let mut config = config();
+            config.scheme = Some(scheme.to_string());
+            config
+        };
+        assert!(with_scheme("api_key").resolve(&spec(), &base_url).is_err());
+        assert!(with_scheme("missing").resolve(&spec(), &base_url).is_err());
+        // No token URL anywhere
+        assert!(config().resolve(&json!({}), &base_url).is_err());
+    }
+
+    #[test]
+    fn client_auth_method_parsing() {
+        assert_eq!("basic".parse(), Ok(ClientAuthMethod::Basic));
+        assert_eq!("post".parse(), Ok(ClientAuthMethod::Post));
+        assert!("jwt".parse::<ClientAuthMethod>().is_err());
+    }
+}
@@ -0,0 +1,342 @@
+//! Scope checks of forwarded client tokens against operation requirements.
+//!
+//! The scopes granted to a client token are read from its JWT claims or asked
+//! from an OAuth2 token introspection endpoint (RFC 7662). A call is let
+//! through when the token holds every scope of at least one of the operation's
+//! security requirements; otherwise it fails naming the scopes missing. Tokens
+//! whose scopes cannot be determined, such as opaque tokens without
+//! introspection, are not checked.
+//!
+//! Only forwarded tokens are checked, so the checks are compiled in with the
+//! `authorization-token-passthrough` feature alone.
+#![cfg_attr(not(feature = "authorization-token-passthrough"), allow(dead_code))]
+
+use base64::prelude::*;
+use bon::Builder;
+use reqwest::{Client, header};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+use url::Url;
+
+use super::oauth2::{ClientAuthMethod, TOKEN_REQUEST_TIMEOUT, authenticate_client};
+use crate::error::{ToolCallError, ToolCallExecutionError};
+use crate::redaction::REDACTED;
+use crate::tool::{SecurityRequirement, ToolMetadata};
+
+/// How long introspection results are reused, at most
+const INTROSPECTION_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Scheme name reported in authentication errors
+const SCHEME: &str = "introspection";
+
+/// Token introspection endpoint settings
+#[derive(Clone, PartialEq, Eq, Builder)]
+pub struct IntrospectionConfig {
+    pub url: Url,
+    /// Client authenticating this server to the endpoint
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    #[builder(default)]
+    pub client_auth: ClientAuthMethod,
+}
+
+impl fmt::Debug for IntrospectionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntrospectionConfig")
+            .field("url", &self.url)
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| REDACTED),
+            )
+            .field("client_auth", &self.client_auth)
+            .finish()
+    }
+}
+
+/// Where the scopes of client tokens come from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScopeSource {
+    /// The `scope` or `scp` claim of JWT tokens, read without verifying them
+    JwtClaims,
+    /// An OAuth2 token introspection endpoint
+    Introspection(IntrospectionConfig),
+}
+
+#[derive(Debug, Deserialize)]
+struct IntrospectionResponse {
+    active: bool,
+    #[serde(default)]
+    scope: Option<String>,
+    #[serde(default)]
+    exp: Option<u64>,
+}
+
+struct Introspected {
+    scopes: HashSet<String>,
+    valid_until: Instant,
+}
+
+/// Checks client token scopes, shared by every tool of a server
+pub struct ScopeChecker {
+    source: ScopeSource,
+    /// Introspected scopes by token
+    introspected: Mutex<HashMap<String, Introspected>>,
+}
+
+impl fmt::Debug for ScopeChecker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopeChecker")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ScopeChecker {
+    pub fn new(source: ScopeSource) -> Self {
+        Self {
+            source,
+            introspected: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fail unless `token` holds the scopes of one of the tool's requirements
+    pub(crate) async fn check(
+        &self,
+        client: &Client,
+        tool_metadata: &ToolMetadata,
+        token: &str,
+    ) -> Result<(), ToolCallError> {
+        let Some(requirements) = tool_metadata.security.as_deref() else {
+            return Ok(());
+        };
+        if requirements.iter().all(|r| r.values().all(Vec::is_empty)) {
+            return Ok(());
+        }
+        let Some(granted) = self.scopes(client, token).await? else {
+            debug!(
+                tool = %tool_metadata.name,
+                "Token scopes unknown, skipping scope check"
+            );
+            return Ok(());
+        };
+
+        match missing_scopes(requirements, &granted) {
+            None => Ok(()),
+            Some(missing) => Err(ToolCallError::Execution(
+                ToolCallExecutionError::InsufficientScope {
+                    tool: tool_metadata.name.clone(),
+                    missing,
+                },
+            )),
+        }
+    }
+
+    /// Scopes granted to `token`, `None` when they cannot be determined
+    async fn scopes(
+        &self,
+        client: &Client,
+        token: &str,
+    ) -> Result<Option<HashSet<String>>, ToolCallError> {
+        match &self.source {
+            ScopeSource::JwtClaims => Ok(jwt_scopes(token)),
+            ScopeSource::Introspection(config) => {
+                if let Some(introspected) = self
+                    .introspected
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get(token)
+                    .filter(|introspected| Instant::now() < introspected.valid_until)
+                {
+                    return Ok(Some(introspected.scopes.clone()));
+                }
+
+                let response = introspect(client, config, token).await.map_err(|reason| {
+                    ToolCallError::Execution(ToolCallExecutionError::AuthenticationFailed {
+                        scheme: SCHEME.to_string(),
+                        reason,
+                    })
+                })?;
+                if !response.active {
+                    return Err(ToolCallError::Execution(
+                        ToolCallExecutionError::AuthenticationFailed {
+                            scheme: SCHEME.to_string(),
+                            reason: "Token is not active".to_string(),
+                        },
+                    ));
+                }
+                let Some(scope) = response.scope else {
+                    return Ok(None);
+                };
+
+                let scopes: HashSet<String> = scope.split_whitespace().map(String::from).collect();
+                let mut valid_until = Instant::now() + INTROSPECTION_CACHE_TTL;
+                if let Some(remaining) = response.exp.and_then(|exp| {
+                    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+                    Duration::from_secs(exp).checked_sub(now)
+                }) {
+                    valid_until = valid_until.min(Instant::now() + remaining);
+                }
+                let mut introspected = self.introspected.lock().unwrap_or_else(|e| e.into_inner());
+                introspected.retain(|_, entry| Instant::now() < entry.valid_until);
+                introspected.insert(
+                    token.to_string(),
+                    Introspected {
+                        scopes: scopes.clone(),
+                        valid_until,
+                    },
+                );
+                Ok(Some(scopes))
+            }
+        }
+    }
+}
+
+/// Scopes missing for the requirement closest to being met, by scheme, or
+/// `None` when one is met
+fn missing_scopes(
+    requirements: &[SecurityRequirement],
+    granted: &HashSet<String>,
+) -> Option<SecurityRequirement> {
+    let mut closest: Option<SecurityRequirement> = None;
+    for requirement in requirements {
+        let missing: SecurityRequirement = requirement
+            .iter()
+            .map(|(scheme, scopes)| {
+                let missing: Vec<String> = scopes
+                    .iter()
+                    .filter(|scope| !granted.contains(*scope))
+                    .cloned()
+                    .collect();
+                (scheme.clone(), missing)
+            })
+            .filter(|(_, missing)| !missing.is_empty())
+            .collect();
+        if missing.is_empty() {
+            return None;
+        }
+        let count = |r: &SecurityRequirement| r.values().map(Vec::len).sum::<usize>();
+        if closest.as_ref().is_none_or(|c| count(&missing) < count(c)) {
+            closest = Some(missing);
+        }
+    }
+    closest
+}
+
+/// Scopes in the `scope` (space-separated) or `scp` (list) claim of a JWT
+fn jwt_scopes(token: &str) -> Option<HashSet<String>> {
+    let payload = token.split('.').nth(1)?;
+    let claims: Value =
+        serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+    match claims.get("scope").or_else(|| claims.get("scp"))? {
+        Value::String(scopes) => Some(scopes.split_whitespace().map(String::from).collect()),
+        Value::Array(scopes) => Some(
+            scopes
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+async fn introspect(
+    client: &Client,
+    config: &IntrospectionConfig,
+    token: &str,
+) -> Result<IntrospectionResponse, String> {
+    debug!(url = %config.url, "Introspecting client token");
+    let mut form = vec![
+        ("token", token.to_string()),
+        ("token_type_hint", "access_token".to_string()),
+    ];
+    let mut request = client
+        .post(config.url.clone())
+        .timeout(TOKEN_REQUEST_TIMEOUT)
+        .header(header::ACCEPT, "application/json");
+    if let Some(client_id) = &config.client_id {
+        request = authenticate_client(
+            request,
+            &mut form,
+            config.client_auth,
+            client_id,
+            config.client_secret.as_deref().unwrap_or_default(),
+        );
+    }
+
+    let response = request
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| format!("Introspection request to {} failed: {e}", config.url))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Introspection endpoint returned {status}"));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid introspection response: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn jwt(claims: Value) -> String {
+        format!(
+            "{}.{}.signature",
+            BASE64_URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256"}"#),
+            BASE64_URL_SAFE_NO_PAD.encode(claims.to_string())
+        )
+    }
+
+    fn requirement(scheme: &str, scopes: &[&str]) -> SecurityRequirement {
+        SecurityRequirement::from([(
+            scheme.to_string(),
+            scopes.iter().map(|s| s.to_string()).collect(),
+        )])
+    }
+
+    fn granted(scopes: &[&str]) -> HashSet<String> {
+        scopes.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn jwt_scope_claims() {
+        assert_eq!(
+            jwt_scopes(&jwt(json!({ "scope": "pets:read pets:write" }))),
+            Some(granted(&["pets:read", "pets:write"]))
+        );
+        assert_eq!(
+            jwt_scopes(&jwt(json!({ "scp": ["pets:read"] }))),
+            Some(granted(&["pets:read"]))
+        );
+        assert_eq!(jwt_scopes(&jwt(json!({ "sub": "agent" }))), None);
+        assert_eq!(jwt_scopes("opaque-token"), None);
+    }
+
+    #[test]
+    fn any_satisfied_requirement_is_enough() {
+        let requirements = [
+            requirement("oauth", &["pets:read", "pets:write"]),
+            requirement("admin", &["admin"]),
+        ];
+        assert_eq!(missing_scopes(&requirements, &granted(&["admin"])), None);
+        assert_eq!(
+            missing_scopes(&requirements, &granted(&["pets:read"])),
+            Some(requirement("oauth", &["pets:write"]))
+        );
+        assert_eq!(
+            missing_scopes(&requirements, &granted(&[])),
+            Some(requirement("admin", &["admin"]))
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! OAuth 2.0 token exchange (RFC 8693) for forwarded client tokens.
+//!
+//! With authorization passthrough, the MCP client's token was issued for this
+//! server rather than the upstream. [`TokenExchange`] trades it at a security
+//! token service (STS) for a token issued for the upstream's audience, which is
+//! forwarded instead. Exchanged tokens are cached per client token until shortly
+//! before they expire; tokens issued without a lifetime are exchanged on every
+//! call.
+
+use bon::Builder;
+use reqwest::{Client, header};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+use url::Url;
+
+use super::oauth2::{
+    ClientAuthMethod, EXPIRY_MARGIN, TOKEN_REQUEST_TIMEOUT, authenticate_client, token_error,
+};
+use crate::error::{ToolCallError, ToolCallExecutionError};
+use crate::redaction::REDACTED;
+
+const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+/// Scheme name reported in authentication errors
+const SCHEME: &str = "token-exchange";
+
+/// Token exchange settings
+#[derive(Clone, PartialEq, Eq, Builder)]
+pub struct TokenExchangeConfig {
+    /// Token endpoint of the security token service
+    pub token_url: Url,
+    /// Client authenticating this server to the STS; none when it accepts
+    /// unauthenticated exchanges
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    #[builder(default)]
+    pub client_auth: ClientAuthMethod,
+    /// Logical name of the upstream the token is requested for
+    pub audience: Option<String>,
+    /// URI of the upstream the token is requested for
+    pub resource: Option<String>,
+    #[builder(default)]
+    pub scopes: Vec<String>,
+    /// Token type to request; the STS decides when not set
+    pub requested_token_type: Option<String>,
+}
+
+impl fmt::Debug for TokenExchangeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenExchangeConfig")
+            .field("token_url", &self.token_url)
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| REDACTED),
+            )
+            .field("client_auth", &self.client_auth)
+            .field("audience", &self.audience)
+            .field("resource", &self.resource)
+            .field("scopes", &self.scopes)
+            .field("requested_token_type", &self.requested_token_type)
+            .finish()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExchangeResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+struct ExchangedToken {
+    value: String,
+    expires_at: Instant,
+}
+
+impl ExchangedToken {
+    fn is_usable(&self) -> bool {
+        Instant::now() + EXPIRY_MARGIN < self.expires_at
+    }
+}
+
+/// Exchanges client tokens at a security token service, shared by every tool
+/// of a server
+pub struct TokenExchange {
+    config: TokenExchangeConfig,
+    /// Exchanged tokens by client token
+    tokens: Mutex<HashMap<String, ExchangedToken>>,
+}
+
+impl fmt::Debug for TokenExchange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenExchange")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TokenExchange {
+    pub fn new(config: TokenExchangeConfig) -> Self {
+        Self {
+            config,
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Configuration this exchange was created with
+    #[must_use]
+    pub fn config(&self) -> &TokenExchangeConfig {
+        &self.config
+    }
+
+    /// Token for the upstream in exchange for the client's `subject_token`
+    pub(crate) async fn exchange(
+        &self,
+        client: &Client,
+        subject_token: &str,
+    ) -> Result<String, ToolCallError> {
+        if let Some(token) = self
+            .tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(subject_token)
+            .filter(|token| token.is_usable())
+        {
+            return Ok(token.value.clone());
+        }
+
+        let response = self
+            .request_token(client, subject_token)
+            .await
+            .map_err(|reason| {
+                ToolCallError::Execution(ToolCallExecutionError::AuthenticationFailed {
+                    scheme: SCHEME.to_string(),
+                    reason,
+                })
+            })?;
+
+        if let Some(seconds) = response.expires_in {
+            let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+            tokens.retain(|_, token| token.is_usable());
+            tokens.insert(
+                subject_token.to_string(),
+                ExchangedToken {
+                    value: response.access_token.clone(),
+                    expires_at: Instant::now() + Duration::from_secs(seconds),
+                },
+            );
+        }
+        Ok(response.access_token)
+    }
+
+    async fn request_token(
+        &self,
+        client: &Client,
+        subject_token: &str,
+    ) -> Result<ExchangeResponse, String> {
+        debug!(token_url = %self.config.token_url, "Exchanging client token");
+        let mut form = vec![
+            ("grant_type", GRANT_TYPE.to_string()),
+            ("subject_token", subject_token.to_string()),
+            ("subject_token_type", ACCESS_TOKEN_TYPE.to_string()),
+        ];
+        if let Some(audience) = &self.config.audience {
+            form.push(("audience", audience.clone()));
+        }
+        if let Some(resource) = &self.config.resource {
+            form.push(("resource", resource.clone()));
+        }
+        if !self.config.scopes.is_empty() {
+            form.push(("scope", self.config.scopes.join(" ")));
+        }
+        if let Some(token_type) = &self.config.requested_token_type {
+            form.push(("requested_token_type", token_type.clone()));
+        }
+
+        let mut request = client
+            .post(self.config.token_url.clone())
+            .timeout(TOKEN_REQUEST_TIMEOUT)
+            .header(header::ACCEPT, "application/json");
+        if let Some(client_id) = &self.config.client_id {
+            request = authenticate_client(
+                request,
+                &mut form,
+                self.config.client_auth,
+                client_id,
+                self.config.client_secret.as_deref().unwrap_or_default(),
+            );
+        }
+
+        let response = request
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| format!("Token request to {} failed: {e}", self.config.token_url))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read token response: {e}"))?;
+
+        if !status.is_success() {
+            return Err(token_error(status, &body));
+        }
+        serde_json::from_str(&body).map_err(|e| format!("Invalid token response: {e}"))
+    }
+}
@@ -0,0 +1,314 @@
+//! Chained tool calls executed server-side.
+//!
+//! Create-then-update flows take a round trip through the model per call.
+//! With batch calls enabled, the server offers a [`BATCH_CALL_TOOL`] tool
+//! taking an ordered list of steps and calling them one after the other:
+//!
+//! ```json
+//! {
+//!   "steps": [
+//!     { "tool": "addPet", "arguments": { "request_body": { "name": "Rex" } } },
+//!     { "tool": "getPetById", "arguments": { "petId": "{{steps.0.body.id}}" } }
+//!   ]
+//! }
+//! ```
+//!
+//! Strings of the arguments may refer to the results of earlier steps with
+//! `{{steps.N.path}}`, `N` being the index of the step and `path` the dotted
+//! fields, or array indexes, of its structured content, or of its text parsed
+//! as JSON. A string made of a single reference takes the referred value as
+//! is; references within text are replaced by the value, as text. Batches
+//! stop at the first failing step unless `continue_on_error` is set, and
+//! return the result of every step run. Each step is a call of its own: the
+//! filters, authorization and queue of other calls apply.
+
+use rmcp::model::{CallToolResult, Content, ErrorData, RawContent, Tool as McpTool};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::future::Future;
+use std::sync::Arc;
+
+/// Name of the tool calling other tools in turn
+pub const BATCH_CALL_TOOL: &str = "batch_call";
+
+/// Most steps a batch may run
+const MAX_STEPS: usize = 20;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchArguments {
+    steps: Vec<Step>,
+    #[serde(default)]
+    continue_on_error: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Step {
+    tool: String,
+    #[serde(default)]
+    arguments: Map<String, Value>,
+}
+
+/// The [`BATCH_CALL_TOOL`] tool, as listed to clients
+pub(crate) fn batch_call_tool() -> McpTool {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "steps": {
+                "type": "array",
+                "minItems": 1,
+                "maxItems": MAX_STEPS,
+                "description": "Tool calls to make in order. Strings of the arguments may refer to the result of an earlier step with {{steps.N.path}}, N being its index and path the dotted fields of its result",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "tool": { "type": "string", "description": "Name of the tool to call" },
+                        "arguments": { "type": "object", "description": "Arguments of the tool" }
+                    },
+                    "required": ["tool"],
+                    "additionalProperties": false
+                }
+            },
+            "continue_on_error": {
+                "type": "boolean",
+                "description": "Run the next steps after a failing one (default: false)"
+            }
+        },
+        "required": ["steps"],
+        "additionalProperties": false
+    });
+    let Value::Object(schema) = schema else {
+        unreachable!("schema is an object");
+    };
+    McpTool::new_with_raw(
+        BATCH_CALL_TOOL,
+        Some(
+            "Call several tools in order, feeding the results of earlier calls into later ones"
+                .into(),
+        ),
+        Arc::new(schema),
+    )
+}
+
+/// Answer a call to the [`BATCH_CALL_TOOL`] tool, making each step with
+/// `call_step`
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid; failing steps are reported
+/// in the result
+pub(crate) async fn call<F, Fut>(
+    arguments: Option<Map<String, Value>>,
+    mut call_step: F,
+) -> Result<CallToolResult, ErrorData>
+where
+    F: FnMut(String, Map<String, Value>) -> Fut,
+    Fut: Future<Output = Result<CallToolResult, ErrorData>>,
+{
+    let arguments: BatchArguments =
+        serde_json::from_value(Value::Object(arguments.unwrap_or_default())).map_err(|e| {
+            ErrorData::invalid_params(format!("Invalid batch arguments: {e}"), None)
+        })?;
+    if arguments.steps.is_empty() || arguments.steps.len() > MAX_STEPS {
+        return Err(ErrorData::invalid_params(
+            format!("A batch takes 1 to {MAX_STEPS} steps"),
+            None,
+        ));
+    }
+    if arguments
+        .steps
+        .iter()
+        .any(|step| step.tool == BATCH_CALL_TOOL)
+    {
+        return Err(ErrorData::invalid_params(
+            format!("Batches may not call {BATCH_CALL_TOOL}"),
+            None,
+        ));
+    }
+
+    let mut outputs: Vec<Value> = Vec::with_capacity(arguments.steps.len());
+    let mut results = Vec::with_capacity(arguments.steps.len());
+    let mut failed = false;
+    for step in arguments.steps {
+        let resolved = resolve(Value::Object(step.arguments), &outputs);
+        let called = match resolved {
+            Ok(Value::Object(step_arguments)) => call_step(step.tool.clone(), step_arguments)
+                .await
+                .map_err(|e| e.message.to_string()),
+            Ok(_) => unreachable!("resolving keeps the object"),
+            Err(reason) => Err(reason),
+        };
+        let (output, report) = match called {
+            Ok(result) => {
+                let output = output(&result);
+                let is_error = result.is_error == Some(true);
+                let report = json!({ "tool": step.tool, "is_error": is_error, "result": output });
+                (if is_error { Value::Null } else { output }, report)
+            }
+            Err(error) => (
+                Value::Null,
+                json!({ "tool": step.tool, "is_error": true, "error": error }),
+            ),
+        };
+        failed |= report["is_error"] == Value::Bool(true);
+        outputs.push(output);
+        results.push(report);
+        if failed && !arguments.continue_on_error {
+            break;
+        }
+    }
+
+    let structured = json!({ "results": results });
+    let content = vec![Content::text(structured.to_string())];
+    let mut result = if failed {
+        CallToolResult::error(content)
+    } else {
+        CallToolResult::success(content)
+    };
+    result.structured_content = Some(structured);
+    Ok(result)
+}
+
+/// What later steps may refer to of `result`
+fn output(result: &CallToolResult) -> Value {
+    if let Some(structured) = &result.structured_content {
+        return structured.clone();
+    }
+    let text: String = result
+        .content
+        .iter()
+        .filter_map(|content| match &content.raw {
+            RawContent::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect();
+    serde_json::from_str(&text).unwrap_or(Value::String(text))
+}
+
+/// Replace the references to earlier steps in the strings of `value`
+fn resolve(value: Value, outputs: &[Value]) -> Result<Value, String> {
+    match value {
+        Value::String(text) => resolve_text(&text, outputs),
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| resolve(item, outputs))
+            .collect::<Result<_, _>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .into_iter()
+            .map(|(name, field)| Ok((name, resolve(field, outputs)?)))
+            .collect::<Result<_, _>>()
+            .map(Value::Object),
+        other => Ok(other),
+    }
+}
+
+fn resolve_text(text: &str, outputs: &[Value]) -> Result<Value, String> {
+    // A single reference keeps the type of the value it refers to
+    if let Some(reference) = text
+        .strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+        && !reference.contains("{{")
+    {
+        return lookup(reference, outputs);
+    }
+
+    let mut resolved = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        resolved.push_str(&rest[..start]);
+        match lookup(&rest[start + 2..start + end], outputs)? {
+            Value::String(value) => resolved.push_str(&value),
+            value => resolved.push_str(&value.to_string()),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    resolved.push_str(rest);
+    Ok(Value::String(resolved))
+}
+
+/// Value at `reference`, as `steps.N.path`, in the outputs of earlier steps
+fn lookup(reference: &str, outputs: &[Value]) -> Result<Value, String> {
+    let mut segments = reference.trim().split('.');
+    let index = match (segments.next(), segments.next()) {
+        (Some("steps"), Some(index)) => index.parse::<usize>().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| format!("Invalid reference '{{{{{reference}}}}}': expected steps.N.path"))?;
+    let mut value = outputs
+        .get(index)
+        .filter(|output| !output.is_null())
+        .ok_or_else(|| {
+            format!("Reference '{{{{{reference}}}}}' is to no earlier successful step")
+        })?;
+    for segment in segments {
+        value = match value {
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            Value::Object(fields) => fields.get(segment),
+            _ => None,
+        }
+        .ok_or_else(|| format!("Reference '{{{{{reference}}}}}' matches no value"))?;
+    }
+    Ok(value.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_references() {
+        let outputs = [json!({ "status": 201, "body": { "id": 7, "tags": ["a", "b"] } })];
+        let arguments = json!({
+            "petId": "{{steps.0.body.id}}",
+            "path": "/pets/{{ steps.0.body.id }}/{{steps.0.body.tags.1}}",
+            "literal": 3
+        });
+        assert_eq!(
+            resolve(arguments, &outputs).unwrap(),
+            json!({ "petId": 7, "path": "/pets/7/b", "literal": 3 })
+        );
+
+        assert!(resolve(json!("{{steps.1.body}}"), &outputs).is_err());
+        assert!(resolve(json!("{{steps.0.missing}}"), &outputs).is_err());
+        assert!(resolve(json!("{{body.id}}"), &outputs).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_stops_at_first_failure() {
+        let arguments = json!({
+            "steps": [
+                { "tool": "addPet", "arguments": { "name": "Rex" } },
+                { "tool": "getPetById", "arguments": { "petId": "{{steps.0.id}}" } },
+                { "tool": "deletePet", "arguments": { "petId": "{{steps.0.id}}" } }
+            ]
+        });
+        let Value::Object(arguments) = arguments else {
+            unreachable!()
+        };
+        let mut calls = Vec::new();
+        let result = call(Some(arguments), |tool, arguments| {
+            calls.push((tool.clone(), Value::Object(arguments)));
+            async move {
+                Ok(match tool.as_str() {
+                    "addPet" => CallToolResult::success(vec![Content::text(r#"{"id":7}"#)]),
+                    _ => CallToolResult::error(vec![Content::text("Not found")]),
+                })
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1].1, json!({ "petId": 7 }));
+        let results = &result.structured_content.unwrap()["results"];
+        assert_eq!(results[0]["result"], json!({ "id": 7 }));
+        assert_eq!(results[1]["is_error"], true);
+        assert_eq!(results.as_array().unwrap().len(), 2);
+    }
+}
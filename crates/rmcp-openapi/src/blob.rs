@@ -0,0 +1,183 @@
+//! Binary responses, kept as MCP resources.
+//!
+//! Images are returned inline as image content, but other binary bodies,
+//! such as PDF documents or archives, are of no use to an agent as base64
+//! text. With a [`BlobStore`], a successful binary response is kept in memory
+//! and the tool result holds a resource link instead, giving its URI,
+//! `openapi://responses/{id}`, content type and size. Clients read the payload
+//! with `resources/read` while it is kept: the store drops the oldest
+//! payloads beyond `max_bytes`, and any payload after `ttl`.
+//!
+//! The store is shared by the clones of a server, but each payload belongs to
+//! the MCP session whose call returned it: other sessions neither list nor
+//! read it, even knowing its URI.
+
+use bon::Builder;
+use rmcp::model::{AnnotateAble, RawResource, Resource};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// URI prefix of stored payloads
+pub const BLOB_RESOURCE_PREFIX: &str = "openapi://responses/";
+
+/// Size and lifetime of the payloads kept
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct BlobStoreConfig {
+    /// Total size of the payloads kept, in bytes; larger payloads are not kept
+    #[builder(default = 64 * 1024 * 1024)]
+    pub max_bytes: usize,
+    /// How long a payload can be read
+    #[builder(default = Duration::from_secs(15 * 60))]
+    pub ttl: Duration,
+}
+
+/// A binary payload, readable as a resource
+#[derive(Debug, Clone)]
+pub(crate) struct Blob {
+    pub(crate) uri: String,
+    pub(crate) content_type: String,
+    pub(crate) bytes: Arc<[u8]>,
+    /// Session of the call that returned the payload
+    session_id: Option<String>,
+    stored_at: Instant,
+}
+
+/// Binary payloads of tool results, shared by the clones of a server
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    config: BlobStoreConfig,
+    blobs: Arc<Mutex<VecDeque<Blob>>>,
+}
+
+impl BlobStore {
+    pub fn new(config: BlobStoreConfig) -> Self {
+        Self {
+            config,
+            blobs: Arc::default(),
+        }
+    }
+
+    /// Keep `bytes` for `session_id`, returning the resource they can be read
+    /// as, or `None` when they are larger than the whole store
+    pub(crate) fn insert(
+        &self,
+        bytes: &[u8],
+        content_type: &str,
+        session_id: Option<&str>,
+    ) -> Option<Resource> {
+        if bytes.len() > self.config.max_bytes {
+            return None;
+        }
+        let blob = Blob {
+            uri: format!("{BLOB_RESOURCE_PREFIX}{}", Uuid::new_v4()),
+            content_type: content_type.to_string(),
+            bytes: Arc::from(bytes),
+            session_id: session_id.map(str::to_string),
+            stored_at: Instant::now(),
+        };
+        let resource = resource(&blob);
+
+        let mut blobs = self.blobs.lock().unwrap_or_else(|e| e.into_inner());
+        self.expire(&mut blobs);
+        let mut total: usize = blobs.iter().map(|blob| blob.bytes.len()).sum();
+        while total + bytes.len() > self.config.max_bytes
+            && let Some(oldest) = blobs.pop_front()
+        {
+            total -= oldest.bytes.len();
+        }
+        blobs.push_back(blob);
+        Some(resource)
+    }
+
+    /// Payload of `session_id` at `uri`, if still kept
+    pub(crate) fn get(&self, uri: &str, session_id: Option<&str>) -> Option<Blob> {
+        let mut blobs = self.blobs.lock().unwrap_or_else(|e| e.into_inner());
+        self.expire(&mut blobs);
+        blobs
+            .iter()
+            .find(|blob| blob.uri == uri && blob.session_id.as_deref() == session_id)
+            .cloned()
+    }
+
+    /// Resources of the payloads of `session_id` kept
+    pub(crate) fn list(&self, session_id: Option<&str>) -> Vec<Resource> {
+        let mut blobs = self.blobs.lock().unwrap_or_else(|e| e.into_inner());
+        self.expire(&mut blobs);
+        blobs
+            .iter()
+            .filter(|blob| blob.session_id.as_deref() == session_id)
+            .map(resource)
+            .collect()
+    }
+
+    fn expire(&self, blobs: &mut VecDeque<Blob>) {
+        while blobs
+            .front()
+            .is_some_and(|blob| blob.stored_at.elapsed() >= self.config.ttl)
+        {
+            blobs.pop_front();
+        }
+    }
+}
+
+fn resource(blob: &Blob) -> Resource {
+    let id = blob.uri.trim_start_matches(BLOB_RESOURCE_PREFIX);
+    let mut resource = RawResource::new(blob.uri.clone(), format!("response-{id}"));
+    resource.description = Some(format!(
+        "Binary response body ({}, {} bytes)",
+        blob.content_type,
+        blob.bytes.len()
+    ));
+    resource.mime_type = Some(blob.content_type.clone());
+    resource.size = u32::try_from(blob.bytes.len()).ok();
+    resource.no_annotation()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oldest_payloads_are_dropped() {
+        let store = BlobStore::new(BlobStoreConfig::builder().max_bytes(10).build());
+        let first = store.insert(&[1; 6], "application/pdf", None).unwrap();
+        assert_eq!(first.mime_type.as_deref(), Some("application/pdf"));
+        assert_eq!(first.size, Some(6));
+        assert!(first.uri.starts_with(BLOB_RESOURCE_PREFIX));
+        assert_eq!(&*store.get(&first.uri, None).unwrap().bytes, &[1u8; 6]);
+
+        let second = store.insert(&[2; 4], "application/zip", None).unwrap();
+        assert!(store.get(&first.uri, None).is_some());
+        let third = store.insert(&[3; 5], "application/zip", None).unwrap();
+        assert!(store.get(&first.uri, None).is_none());
+        assert!(store.get(&second.uri, None).is_some());
+        assert_eq!(store.list(None).len(), 2);
+        assert_eq!(store.list(None)[1].uri, third.uri);
+
+        assert!(store.insert(&[4; 11], "application/zip", None).is_none());
+    }
+
+    #[test]
+    fn test_payloads_expire() {
+        let store = BlobStore::new(BlobStoreConfig::builder().ttl(Duration::ZERO).build());
+        let blob = store.insert(b"%PDF", "application/pdf", None).unwrap();
+        assert!(store.get(&blob.uri, None).is_none());
+        assert!(store.list(None).is_empty());
+    }
+
+    #[test]
+    fn test_payloads_belong_to_their_session() {
+        let store = BlobStore::new(BlobStoreConfig::builder().build());
+        let blob = store.insert(b"%PDF", "application/pdf", Some("a")).unwrap();
+        let shared = store.clone();
+
+        assert_eq!(shared.list(Some("a")).len(), 1);
+        assert!(shared.get(&blob.uri, Some("a")).is_some());
+        assert!(shared.list(Some("b")).is_empty());
+        assert!(shared.get(&blob.uri, Some("b")).is_none());
+        assert!(shared.list(None).is_empty());
+        assert!(shared.get(&blob.uri, None).is_none());
+    }
+}
@@ -0,0 +1,146 @@
+//! Budget on the size of the response bodies returned to agents.
+//!
+//! A JSON body larger than `max_bytes` once serialized is returned cut
+//! instead of filling the agent's context: arrays, at any depth, keep their
+//! first `preview_items` items, fewer if that is still too large, and the body
+//! is dropped when even empty arrays do not fit. A `summary` then tells what
+//! was cut:
+//!
+//! ```json
+//! {
+//!   "original_bytes": 48213,
+//!   "budget_bytes": 4096,
+//!   "type": "object",
+//!   "keys": ["items", "total"],
+//!   "arrays": { "items": 250 },
+//!   "items_shown": 3
+//! }
+//! ```
+//!
+//! `length` replaces `keys` and `arrays` for a body that is an array, and
+//! `items_shown` is `null` when the body was dropped.
+
+use bon::Builder;
+use serde_json::{Map, Value, json};
+
+/// Size of the bodies returned to agents
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct BudgetConfig {
+    /// Largest serialized body returned whole, in bytes
+    pub max_bytes: usize,
+    /// Items of each array kept in cut bodies
+    #[builder(default = 3)]
+    pub preview_items: usize,
+}
+
+/// Declare the `summary` field of structured results in an output schema
+pub(crate) fn extend_output_schema(schema: &mut Value) {
+    if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+        properties.insert(
+            "summary".to_string(),
+            json!({
+                "type": "object",
+                "description": "Shape of the response body, present when it exceeded the response budget and was cut"
+            }),
+        );
+    }
+}
+
+/// `body`, cut to fit `config` along with the summary of what was cut, or
+/// unchanged without summary when it fits
+pub(crate) fn fit(body: Value, config: &BudgetConfig) -> (Value, Option<Value>) {
+    let size = serialized_len(&body);
+    if size <= config.max_bytes {
+        return (body, None);
+    }
+
+    let mut summary = json!({
+        "original_bytes": size,
+        "budget_bytes": config.max_bytes,
+    });
+    match &body {
+        Value::Array(items) => {
+            summary["type"] = json!("array");
+            summary["length"] = json!(items.len());
+        }
+        Value::Object(object) => {
+            summary["type"] = json!("object");
+            summary["keys"] = json!(object.keys().collect::<Vec<_>>());
+            let arrays: Map<String, Value> = object
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), json!(value.as_array()?.len()))))
+                .collect();
+            if !arrays.is_empty() {
+                summary["arrays"] = Value::Object(arrays);
+            }
+        }
+        Value::String(_) => summary["type"] = json!("string"),
+        _ => summary["type"] = json!("value"),
+    }
+
+    for items in (0..=config.preview_items).rev() {
+        let preview = preview(&body, items);
+        if serialized_len(&preview) <= config.max_bytes {
+            summary["items_shown"] = json!(items);
+            return (preview, Some(summary));
+        }
+    }
+    summary["items_shown"] = Value::Null;
+    (Value::Null, Some(summary))
+}
+
+/// `value` with its arrays, at any depth, cut to `items` items
+fn preview(value: &Value, items: usize) -> Value {
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .take(items)
+            .map(|value| preview(value, items))
+            .collect(),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, value)| (key.clone(), preview(value, items)))
+            .collect(),
+        value => value.clone(),
+    }
+}
+
+fn serialized_len(value: &Value) -> usize {
+    serde_json::to_vec(value).map_or(0, |bytes| bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bodies_over_budget_are_cut_and_summarized() {
+        let config = BudgetConfig::builder()
+            .max_bytes(120)
+            .preview_items(2)
+            .build();
+        let small = json!({ "items": [1, 2, 3] });
+        assert_eq!(fit(small.clone(), &config), (small, None));
+
+        let items: Vec<_> = (0..50)
+            .map(|id| json!({ "id": id, "tags": ["a", "b", "c"] }))
+            .collect();
+        let (body, summary) = fit(json!({ "items": items, "total": 50 }), &config);
+        assert_eq!(
+            body,
+            json!({
+                "items": [{ "id": 0, "tags": ["a", "b"] }, { "id": 1, "tags": ["a", "b"] }],
+                "total": 50
+            })
+        );
+        let summary = summary.unwrap();
+        assert_eq!(summary["type"], "object");
+        assert_eq!(summary["keys"], json!(["items", "total"]));
+        assert_eq!(summary["arrays"], json!({ "items": 50 }));
+        assert_eq!(summary["items_shown"], 2);
+
+        let (body, summary) = fit(json!("x".repeat(200)), &config);
+        assert_eq!(body, Value::Null);
+        assert_eq!(summary.unwrap()["items_shown"], Value::Null);
+    }
+}
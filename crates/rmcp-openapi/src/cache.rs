@@ -0,0 +1,316 @@
+//! Response caching for idempotent `GET` tool calls.
+//!
+//! Agents often fetch the same resource several times in one conversation. With
+//! a [`CacheConfig`] set, successful `GET` responses are stored under a key made
+//! of the method, the final URL and the request headers, and served from the
+//! cache until they expire. Upstream `Cache-Control` headers are honoured:
+//! `no-store` responses are never cached and `max-age` sets the lifetime.
+//!
+//! Expired entries with an `ETag` are revalidated with `If-None-Match`; on
+//! `304 Not Modified` the cached body is served and its lifetime renewed.
+//! [`HttpResponse::cache_status`] tells whether a response was a cache hit, a
+//! revalidated entry or a miss.
+//!
+//! The store is pluggable through the [`ResponseCache`] trait; [`InMemoryCache`]
+//! is used when no other store is given.
+
+use bon::Builder;
+use reqwest::Request;
+use reqwest::header::HeaderName;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::http_client::HttpResponse;
+
+/// How upstream responses are cached
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct CacheConfig {
+    /// Lifetime of responses without a `Cache-Control: max-age`
+    #[builder(default = Duration::from_secs(60))]
+    pub default_ttl: Duration,
+    /// Upper bound for any lifetime, including upstream `max-age` values
+    pub max_ttl: Option<Duration>,
+    /// Maximum number of entries kept by the default in-memory store
+    #[builder(default = 1000)]
+    pub max_entries: usize,
+    /// Follow upstream `Cache-Control` directives (`no-store`, `no-cache`, `max-age`)
+    #[builder(default = true)]
+    pub honor_cache_control: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl CacheConfig {
+    /// Lifetime of a response according to its headers, or `None` if it must not be stored
+    pub(crate) fn ttl_for(&self, response: &HttpResponse) -> Option<Duration> {
+        let mut ttl = self.default_ttl;
+        if self.honor_cache_control
+            && let Some(cache_control) = response.headers.get("cache-control")
+        {
+            for directive in cache_control.split(',').map(str::trim) {
+                let directive = directive.to_ascii_lowercase();
+                if directive == "no-store" {
+                    return None;
+                }
+                if directive == "no-cache" {
+                    ttl = Duration::ZERO;
+                } else if let Some(seconds) = directive.strip_prefix("max-age=")
+                    && let Ok(seconds) = seconds.trim_matches('"').parse()
+                {
+                    ttl = Duration::from_secs(seconds);
+                }
+            }
+        }
+        let ttl = self.max_ttl.map_or(ttl, |max| ttl.min(max));
+        // An immediately stale entry is only useful with a validator
+        (!ttl.is_zero() || response.headers.contains_key("etag")).then_some(ttl)
+    }
+}
+
+/// How the cache was involved in producing a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheStatus {
+    /// Fetched from the upstream; stored if cacheable
+    Miss,
+    /// Served from the cache without contacting the upstream
+    Hit,
+    /// Stale entry confirmed by the upstream with `304 Not Modified`
+    Revalidated,
+}
+
+impl CacheStatus {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Miss => "miss",
+            CacheStatus::Hit => "hit",
+            CacheStatus::Revalidated => "revalidated",
+        }
+    }
+}
+
+/// A stored response and when it stops being fresh
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub response: HttpResponse,
+    pub expires_at: SystemTime,
+}
+
+impl CachedResponse {
+    /// Whether the entry can still be served without contacting the upstream
+    #[must_use]
+    pub fn is_fresh(&self) -> bool {
+        SystemTime::now() < self.expires_at
+    }
+}
+
+/// Storage backend for cached responses
+///
+/// Implementations must be safe to share between tools and concurrent calls.
+pub trait ResponseCache: Send + Sync {
+    /// Look up an entry; it may have expired
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Store or replace an entry
+    fn insert(&self, key: String, entry: CachedResponse);
+}
+
+/// Bounded in-memory [`ResponseCache`]
+///
+/// When full, expired entries without an `ETag` are dropped first, then the
+/// entry closest to expiry.
+#[derive(Debug)]
+pub struct InMemoryCache {
+    max_entries: usize,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl InMemoryCache {
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            entries: Mutex::default(),
+        }
+    }
+}
+
+impl ResponseCache for InMemoryCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(key).cloned()
+    }
+
+    fn insert(&self, key: String, entry: CachedResponse) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let now = SystemTime::now();
+            entries.retain(|_, cached| {
+                cached.expires_at > now || cached.response.headers.contains_key("etag")
+            });
+            if entries.len() >= self.max_entries
+                && let Some(oldest) = entries
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.expires_at)
+                    .map(|(key, _)| key.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, entry);
+    }
+}
+
+/// Cache key for a request: method, URL and a digest of the request headers
+/// other than `ignored`, which take a new value on every request
+///
+/// Headers are hashed rather than stored so credentials never end up in keys.
+pub(crate) fn cache_key(request: &Request, ignored: &[HeaderName]) -> String {
+    let mut headers: Vec<(&str, &[u8])> = request
+        .headers()
+        .iter()
+        .filter(|(name, _)| !ignored.contains(name))
+        .map(|(name, value)| (name.as_str(), value.as_bytes()))
+        .collect();
+    headers.sort_unstable();
+    let mut hasher = DefaultHasher::new();
+    headers.hash(&mut hasher);
+    format!(
+        "{} {} {:016x}",
+        request.method(),
+        request.url(),
+        hasher.finish()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(headers: &[(&str, &str)]) -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            status_text: "OK".to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            content_type: None,
+            body: "{}".to_string(),
+            body_bytes: None,
+            is_success: true,
+            request_method: "GET".to_string(),
+            request_url: "http://example.com/pets".to_string(),
+            request_body: String::new(),
+            cache_status: None,
+            truncated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_ttl_honours_cache_control() {
+        let config = CacheConfig::builder()
+            .max_ttl(Duration::from_secs(300))
+            .build();
+
+        assert_eq!(
+            config.ttl_for(&response(&[])),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            config.ttl_for(&response(&[("cache-control", "public, max-age=120")])),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            config.ttl_for(&response(&[("cache-control", "max-age=86400")])),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            config.ttl_for(&response(&[("cache-control", "no-store")])),
+            None
+        );
+        // no-cache is only worth storing with a validator
+        assert_eq!(
+            config.ttl_for(&response(&[("cache-control", "no-cache")])),
+            None
+        );
+        assert_eq!(
+            config.ttl_for(&response(&[
+                ("cache-control", "no-cache"),
+                ("etag", "\"v1\"")
+            ])),
+            Some(Duration::ZERO)
+        );
+
+        let ignoring = CacheConfig::builder().honor_cache_control(false).build();
+        assert_eq!(
+            ignoring.ttl_for(&response(&[("cache-control", "no-store")])),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn test_in_memory_cache_evicts_when_full() {
+        let cache = InMemoryCache::new(2);
+        let now = SystemTime::now();
+        let entry = |secs| CachedResponse {
+            response: response(&[]),
+            expires_at: now + Duration::from_secs(secs),
+        };
+
+        cache.insert("a".to_string(), entry(10));
+        cache.insert("b".to_string(), entry(20));
+        cache.insert("c".to_string(), entry(30));
+
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_some());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_headers() {
+        let client = reqwest::Client::new();
+        let request = |auth: &str| {
+            client
+                .get("http://example.com/pets?limit=1")
+                .header("authorization", auth)
+                .build()
+                .unwrap()
+        };
+
+        let key = cache_key(&request("Bearer a"), &[]);
+        assert!(key.starts_with("GET http://example.com/pets?limit=1 "));
+        assert!(!key.contains("Bearer"));
+        assert_eq!(key, cache_key(&request("Bearer a"), &[]));
+        assert_ne!(key, cache_key(&request("Bearer b"), &[]));
+    }
+
+    #[test]
+    fn test_cache_key_leaves_out_ignored_headers() {
+        let client = reqwest::Client::new();
+        let request = |id: &str| {
+            client
+                .get("http://example.com/pets")
+                .header("x-request-id", id)
+                .build()
+                .unwrap()
+        };
+
+        let ignored = [HeaderName::from_static("x-request-id")];
+        assert_eq!(
+            cache_key(&request("a"), &ignored),
+            cache_key(&request("b"), &ignored)
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Record-and-replay of upstream HTTP traffic.
+//!
+//! In [`CassetteMode::Record`] every upstream response is written to a
+//! cassette file along with the request that produced it. In
+//! [`CassetteMode::Replay`] the cassette answers tool calls instead of the
+//! upstream: nothing is sent over the network, which makes agent development
+//! deterministic and lets demos run offline.
+//!
+//! Requests are matched on method, URL (query string included) and body.
+//! Identical requests recorded several times are replayed in recording order;
+//! once they are used up the last response is served again. A request with no
+//! recorded match fails with a network error.
+//!
+//! Responses are stored after redaction (see [`crate::redaction`]), and request
+//! headers are not stored at all, so credentials do not end up in cassettes.
+
+use base64::prelude::*;
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+use tracing::warn;
+
+use crate::error::Error;
+use crate::http_client::HttpResponse;
+
+/// Whether a cassette is being written or played back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests upstream and store every response, replacing the file
+    Record,
+    /// Answer requests from the file without contacting the upstream
+    Replay,
+}
+
+impl FromStr for CassetteMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "record" => Ok(CassetteMode::Record),
+            "replay" => Ok(CassetteMode::Replay),
+            _ => Err(format!(
+                "Invalid cassette mode: '{s}'. Valid values: record, replay"
+            )),
+        }
+    }
+}
+
+/// Cassette file and mode
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct CassetteConfig {
+    /// JSON file holding the recorded interactions
+    pub path: PathBuf,
+    pub mode: CassetteMode,
+}
+
+/// Request side of a recorded interaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+}
+
+/// Response side of a recorded interaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    status_text: String,
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    body: String,
+    /// Raw bytes of binary bodies, base64-encoded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_base64: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    truncated_at: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    /// Whether each interaction has been replayed
+    played: Vec<bool>,
+}
+
+/// Recorded upstream interactions, shared by every tool of a server
+#[derive(Debug)]
+pub struct Cassette {
+    config: CassetteConfig,
+    state: Mutex<CassetteState>,
+}
+
+impl Cassette {
+    /// Open the cassette described by `config`
+    ///
+    /// In record mode the file is created (or emptied) right away; in replay
+    /// mode it is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written (record) or read and
+    /// parsed (replay)
+    pub fn open(config: CassetteConfig) -> Result<Self, Error> {
+        let interactions = match config.mode {
+            CassetteMode::Record => {
+                write_cassette(&config.path, &[])?;
+                Vec::new()
+            }
+            CassetteMode::Replay => {
+                let content = std::fs::read_to_string(&config.path)?;
+                serde_json::from_str::<CassetteFile>(&content)?.interactions
+            }
+        };
+        Ok(Self {
+            state: Mutex::new(CassetteState {
+                played: vec![false; interactions.len()],
+                interactions,
+            }),
+            config,
+        })
+    }
+
+    /// Configuration this cassette was opened with
+    #[must_use]
+    pub fn config(&self) -> &CassetteConfig {
+        &self.config
+    }
+
+    /// Whether requests are answered from the cassette
+    #[must_use]
+    pub fn is_replaying(&self) -> bool {
+        self.config.mode == CassetteMode::Replay
+    }
+
+    /// Recorded response for a request, `None` when nothing matches
+    pub(crate) fn replay(&self, method: &str, url: &str, body: &str) -> Option<HttpResponse> {
+        let request = recorded_request(method, url, body);
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let CassetteState {
+            interactions,
+            played,
+        } = &mut *state;
+
+        let index = (0..interactions.len())
+            .find(|&index| !played[index] && interactions[index].request == request)
+            .or_else(|| {
+                interactions
+                    .iter()
+                    .rposition(|interaction| interaction.request == request)
+            })?;
+        played[index] = true;
+
+        let recorded = &interactions[index].response;
+        Some(HttpResponse {
+            status_code: recorded.status,
+            status_text: recorded.status_text.clone(),
+            headers: recorded.headers.clone().into_iter().collect(),
+            content_type: recorded.content_type.clone(),
+            body: recorded.body.clone(),
+            body_bytes: recorded
+                .body_base64
+                .as_ref()
+                .and_then(|data| BASE64_STANDARD.decode(data).ok()),
+            is_success: (200..300).contains(&recorded.status),
+            request_method: method.to_string(),
+            request_url: url.to_string(),
+            request_body: body.to_string(),
+            cache_status: None,
+            truncated_at: recorded.truncated_at,
+        })
+    }
+
+    /// Store a response received from the upstream; failures are logged
+    pub(crate) fn record(&self, method: &str, url: &str, body: &str, response: &HttpResponse) {
+        let interaction = Interaction {
+            request: recorded_request(method, url, body),
+            response: RecordedResponse {
+                status: response.status_code,
+                status_text: response.status_text.clone(),
+                headers: response.headers.clone().into_iter().collect(),
+                content_type: response.content_type.clone(),
+                body: response.body.clone(),
+                body_base64: response
+                    .body_bytes
+                    .as_ref()
+                    .map(|bytes| BASE64_STANDARD.encode(bytes)),
+                truncated_at: response.truncated_at,
+            },
+        };
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.interactions.push(interaction);
+        state.played.push(false);
+        if let Err(e) = write_cassette(&self.config.path, &state.interactions) {
+            warn!(
+                path = %self.config.path.display(),
+                error = %e,
+                "Failed to write cassette"
+            );
+        }
+    }
+}
+
+fn recorded_request(method: &str, url: &str, body: &str) -> RecordedRequest {
+    RecordedRequest {
+        method: method.to_uppercase(),
+        url: url.to_string(),
+        body: (!body.is_empty()).then(|| body.to_string()),
+    }
+}
+
+fn write_cassette(path: &Path, interactions: &[Interaction]) -> Result<(), Error> {
+    let file = CassetteFile {
+        interactions: interactions.to_vec(),
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn response(status: u16, body: &str) -> HttpResponse {
+        HttpResponse {
+            status_code: status,
+            status_text: "OK".to_string(),
+            headers: HashMap::from([("content-type".to_string(), "application/json".to_string())]),
+            content_type: Some("application/json".to_string()),
+            body: body.to_string(),
+            body_bytes: None,
+            is_success: (200..300).contains(&status),
+            request_method: String::new(),
+            request_url: String::new(),
+            request_body: String::new(),
+            cache_status: None,
+            truncated_at: None,
+        }
+    }
+
+    #[test]
+    fn recorded_interactions_replay_in_order() {
+        let path = std::env::temp_dir().join(format!(
+            "rmcp-openapi-{}-cassette-unit.json",
+            std::process::id()
+        ));
+        let config = |mode| {
+            CassetteConfig::builder()
+                .path(path.clone())
+                .mode(mode)
+                .build()
+        };
+
+        let recorder = Cassette::open(config(CassetteMode::Record)).unwrap();
+        recorder.record("get", "http://api/pets", "", &response(200, "[1]"));
+        recorder.record("GET", "http://api/pets", "", &response(200, "[1,2]"));
+        recorder.record(
+            "POST",
+            "http://api/pets",
+            r#"{"a":1}"#,
+            &response(201, "{}"),
+        );
+
+        let player = Cassette::open(config(CassetteMode::Replay)).unwrap();
+        assert_eq!(
+            player.replay("GET", "http://api/pets", "").unwrap().body,
+            "[1]"
+        );
+        assert_eq!(
+            player.replay("GET", "http://api/pets", "").unwrap().body,
+            "[1,2]"
+        );
+        // Used up: the last recording is served again
+        assert_eq!(
+            player.replay("GET", "http://api/pets", "").unwrap().body,
+            "[1,2]"
+        );
+
+        let created = player
+            .replay("POST", "http://api/pets", r#"{"a":1}"#)
+            .unwrap();
+        assert_eq!(created.status_code, 201);
+        assert!(player.replay("POST", "http://api/pets", "{}").is_none());
+        assert!(player.replay("GET", "http://api/other", "").is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,195 @@
+//! Per-host circuit breaker for upstream HTTP requests.
+//!
+//! When an upstream host keeps failing (connection errors, timeouts or `5xx`
+//! responses), hammering it with further tool calls only slows agents down.
+//! After a configurable number of consecutive failures the circuit for that host
+//! opens and calls fail fast with
+//! [`ToolCallExecutionError::UpstreamUnavailable`](crate::error::ToolCallExecutionError::UpstreamUnavailable).
+//! Once the open period elapses a single probe request is let through; its
+//! outcome closes the circuit again or re-opens it.
+
+use bon::Builder;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// When the circuit for an upstream host opens and for how long
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    #[builder(default = 5)]
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe request is allowed
+    #[builder(default = Duration::from_secs(30))]
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CircuitState {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A probe is in flight; other calls fail fast until it reports back or
+    /// the deadline passes (e.g. the probing call was cancelled)
+    HalfOpen {
+        probe_deadline: Instant,
+    },
+}
+
+/// Shared circuit state for every host an `HttpClient` talks to
+///
+/// Clones share the same state, so all tools using clones of one client see the
+/// same circuits.
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    hosts: Arc<Mutex<HashMap<String, CircuitState>>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            hosts: Arc::default(),
+        }
+    }
+
+    /// Check whether a request to `host` may be sent
+    ///
+    /// Returns the time left until the circuit may close when it is open.
+    pub(crate) fn check(&self, host: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let state = hosts
+            .entry(host.to_string())
+            .or_insert(CircuitState::Closed { failures: 0 });
+
+        match *state {
+            CircuitState::Closed { .. } => Ok(()),
+            CircuitState::Open { until }
+            | CircuitState::HalfOpen {
+                probe_deadline: until,
+            } if now < until => Err(until - now),
+            CircuitState::Open { .. } | CircuitState::HalfOpen { .. } => {
+                *state = CircuitState::HalfOpen {
+                    probe_deadline: now + self.config.open_duration,
+                };
+                Ok(())
+            }
+        }
+    }
+
+    /// Record a successful request, closing the circuit for `host`
+    pub(crate) fn record_success(&self, host: &str) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts.insert(host.to_string(), CircuitState::Closed { failures: 0 });
+    }
+
+    /// Record a failed request, opening the circuit once the threshold is reached
+    ///
+    /// Returns `true` if this failure opened the circuit.
+    pub(crate) fn record_failure(&self, host: &str) -> bool {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let state = hosts
+            .entry(host.to_string())
+            .or_insert(CircuitState::Closed { failures: 0 });
+
+        let failures = match *state {
+            CircuitState::Closed { failures } => failures + 1,
+            // A failed probe re-opens the circuit straight away
+            CircuitState::HalfOpen { .. } => self.config.failure_threshold,
+            CircuitState::Open { .. } => return false,
+        };
+
+        if failures >= self.config.failure_threshold {
+            *state = CircuitState::Open {
+                until: Instant::now() + self.config.open_duration,
+            };
+            true
+        } else {
+            *state = CircuitState::Closed { failures };
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(open_duration: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(
+            CircuitBreakerConfig::builder()
+                .failure_threshold(3)
+                .open_duration(open_duration)
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        assert!(!breaker.record_failure("api.example.com:443"));
+        assert!(!breaker.record_failure("api.example.com:443"));
+        assert!(breaker.check("api.example.com:443").is_ok());
+        assert!(breaker.record_failure("api.example.com:443"));
+
+        let remaining = breaker.check("api.example.com:443").unwrap_err();
+        assert!(remaining > Duration::from_secs(59));
+
+        // Other hosts are unaffected
+        assert!(breaker.check("other.example.com:443").is_ok());
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        breaker.record_failure("host");
+        breaker.record_failure("host");
+        breaker.record_success("host");
+        breaker.record_failure("host");
+        breaker.record_failure("host");
+        assert!(breaker.check("host").is_ok());
+    }
+
+    #[test]
+    fn test_half_open_allows_single_probe() {
+        let breaker = breaker(Duration::ZERO);
+        for _ in 0..3 {
+            breaker.record_failure("host");
+        }
+
+        // Open period elapsed: one probe goes through
+        assert!(breaker.check("host").is_ok());
+
+        // Failed probe re-opens the circuit
+        assert!(breaker.record_failure("host"));
+
+        // Successful probe closes it
+        assert!(breaker.check("host").is_ok());
+        breaker.record_success("host");
+        assert!(breaker.check("host").is_ok());
+        assert!(!breaker.record_failure("host"));
+    }
+
+    #[test]
+    fn test_clones_share_state() {
+        let breaker = breaker(Duration::from_secs(60));
+        let clone = breaker.clone();
+        for _ in 0..3 {
+            clone.record_failure("host");
+        }
+        assert!(breaker.check("host").is_err());
+    }
+}
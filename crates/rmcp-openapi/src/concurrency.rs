@@ -0,0 +1,152 @@
+//! Limits on upstream requests in flight at the same time.
+//!
+//! Agents tend to fire tool calls in bursts. A global limit protects the
+//! upstream API and the server itself, and a per-session limit keeps one MCP
+//! session from starving the others. A call arriving while a limit is
+//! saturated is not queued: it fails straight away with
+//! [`ToolCallExecutionError::Throttled`](crate::error::ToolCallExecutionError::Throttled)
+//! so the agent can back off.
+//!
+//! A call holds its slot from just before the request is sent until the
+//! response body has been read, retries included. Calls made outside an MCP
+//! session only count against the global limit.
+
+use bon::Builder;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Maximum numbers of upstream requests in flight
+#[derive(Debug, Clone, Default, PartialEq, Eq, Builder)]
+pub struct ConcurrencyLimits {
+    /// Limit shared by every session
+    pub global: Option<usize>,
+    /// Limit for each MCP session, applied on top of `global`
+    pub per_session: Option<usize>,
+}
+
+/// Error returned when a call is rejected because a limit is saturated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Throttled {
+    /// `"global"` or `"session"`
+    pub(crate) scope: &'static str,
+    pub(crate) limit: usize,
+}
+
+#[derive(Debug, Default)]
+struct InFlight {
+    global: usize,
+    sessions: HashMap<String, usize>,
+}
+
+/// Shared in-flight counters; clones of one `HttpClient` share the same counters
+#[derive(Debug, Clone)]
+pub(crate) struct ConcurrencyLimiter {
+    limits: ConcurrencyLimits,
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+impl ConcurrencyLimiter {
+    pub(crate) fn new(limits: ConcurrencyLimits) -> Self {
+        Self {
+            limits,
+            in_flight: Arc::default(),
+        }
+    }
+
+    /// Take a slot for a call made in `session_id`, released when the guard drops
+    pub(crate) fn try_acquire(&self, session_id: Option<&str>) -> Result<InFlightGuard, Throttled> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(limit) = self.limits.global
+            && in_flight.global >= limit
+        {
+            return Err(Throttled {
+                scope: "global",
+                limit,
+            });
+        }
+
+        let session_id = session_id.filter(|_| self.limits.per_session.is_some());
+        if let (Some(limit), Some(session_id)) = (self.limits.per_session, session_id)
+            && in_flight.sessions.get(session_id).copied().unwrap_or(0) >= limit
+        {
+            return Err(Throttled {
+                scope: "session",
+                limit,
+            });
+        }
+
+        in_flight.global += 1;
+        if let Some(session_id) = session_id {
+            *in_flight
+                .sessions
+                .entry(session_id.to_string())
+                .or_insert(0) += 1;
+        }
+
+        Ok(InFlightGuard {
+            in_flight: self.in_flight.clone(),
+            session_id: session_id.map(str::to_string),
+        })
+    }
+}
+
+/// Slot held by a call until it completes
+#[derive(Debug)]
+pub(crate) struct InFlightGuard {
+    in_flight: Arc<Mutex<InFlight>>,
+    session_id: Option<String>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        in_flight.global -= 1;
+        if let Some(session_id) = &self.session_id
+            && let Some(count) = in_flight.sessions.get_mut(session_id)
+        {
+            *count -= 1;
+            if *count == 0 {
+                in_flight.sessions.remove(session_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_limit_rejects_until_a_slot_is_released() {
+        let limiter = ConcurrencyLimiter::new(ConcurrencyLimits::builder().global(2).build());
+
+        let first = limiter.try_acquire(None).unwrap();
+        let _second = limiter.try_acquire(Some("a")).unwrap();
+        assert_eq!(
+            limiter.try_acquire(Some("b")).unwrap_err(),
+            Throttled {
+                scope: "global",
+                limit: 2
+            }
+        );
+
+        drop(first);
+        assert!(limiter.try_acquire(Some("b")).is_ok());
+    }
+
+    #[test]
+    fn test_session_limit_is_per_session() {
+        let limiter = ConcurrencyLimiter::new(ConcurrencyLimits::builder().per_session(1).build());
+
+        let first = limiter.try_acquire(Some("a")).unwrap();
+        assert_eq!(limiter.try_acquire(Some("a")).unwrap_err().scope, "session");
+        let _other = limiter.try_acquire(Some("b")).unwrap();
+        // Calls outside a session are not limited per session
+        let _anonymous = limiter.try_acquire(None).unwrap();
+
+        drop(first);
+        assert!(limiter.try_acquire(Some("a")).is_ok());
+        assert!(!limiter.in_flight.lock().unwrap().sessions.contains_key("a"));
+    }
+}
@@ -1,6 +1,11 @@
 use rmcp_actix_web::transport::AuthorizationHeader;
+use std::fmt;
 use std::str::FromStr;
 
+use crate::error::Error;
+use crate::redaction::REDACTED;
+use crate::secrets::SecretResolver;
+
 /// Authorization handling for MCP server operations
 ///
 /// This enum combines the authorization mode with the actual header value,
@@ -18,10 +23,45 @@ pub enum Authorization {
     /// Forward authorization silently (requires feature flag)
     #[cfg(feature = "authorization-token-passthrough")]
     PassthroughSilent(Option<AuthorizationHeader>),
+
+    /// Send the server's own token instead of the client's
+    ServiceToken(ServiceToken),
+}
+
+/// Bearer token configured on the server, redacted in `Debug` output
+#[derive(Clone, PartialEq, Eq)]
+pub struct ServiceToken(String);
+
+impl ServiceToken {
+    /// Token sent as `Bearer <token>`
+    pub fn new(token: &str) -> Self {
+        Self(format!("Bearer {token}"))
+    }
+
+    /// `Authorization` header value
+    pub(crate) fn header_value(&self) -> &str {
+        &self.0
+    }
+
+    /// Token read through `resolver` when it is a secret reference
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the referenced secret cannot be read
+    pub async fn resolve(&self, resolver: &SecretResolver) -> Result<Self, Error> {
+        let token = self.0.strip_prefix("Bearer ").unwrap_or(&self.0);
+        Ok(Self::new(&resolver.resolve(token).await?))
+    }
+}
+
+impl fmt::Debug for ServiceToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ServiceToken").field(&REDACTED).finish()
+    }
 }
 
 /// Simple mode enum for conversion (matches CLI AuthorizationMode)
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum AuthorizationMode {
     #[default]
     Compliant,
@@ -73,4 +113,162 @@ impl Authorization {
             AuthorizationMode::PassthroughSilent => Authorization::PassthroughSilent(header),
         }
     }
+
+    /// Create Authorization for a tool from the first rule matching it, falling
+    /// back to `mode`
+    pub fn for_tool(
+        mode: AuthorizationMode,
+        rules: &[AuthorizationRule],
+        tool_name: &str,
+        tags: &[String],
+        header: Option<AuthorizationHeader>,
+    ) -> Self {
+        let behavior = rules
+            .iter()
+            .find(|rule| rule.matches(tool_name, tags))
+            .map(|rule| &rule.behavior);
+        match behavior {
+            Some(AuthorizationBehavior::Mode(rule_mode)) => Self::from_mode(*rule_mode, header),
+            Some(AuthorizationBehavior::ServiceToken(token)) => {
+                Authorization::ServiceToken(token.clone())
+            }
+            None => Self::from_mode(mode, header),
+        }
+    }
+}
+
+/// Tools an [`AuthorizationRule`] applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolSelector {
+    /// Tool with this name
+    Tool(String),
+    /// Tools whose operation has this tag
+    Tag(String),
+}
+
+/// How the tools matched by an [`AuthorizationRule`] are authorized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthorizationBehavior {
+    /// Handle the client's Authorization header as in this mode
+    Mode(AuthorizationMode),
+    /// Send this token instead, written `token:VALUE`
+    ServiceToken(ServiceToken),
+}
+
+/// Authorization behavior for some tools, written `tool:NAME=BEHAVIOR` or
+/// `tag:TAG=BEHAVIOR`, where the behavior is an authorization mode or
+/// `token:VALUE`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationRule {
+    pub selector: ToolSelector,
+    pub behavior: AuthorizationBehavior,
+}
+
+impl AuthorizationRule {
+    /// Whether the rule applies to the tool `tool_name` with operation `tags`
+    pub fn matches(&self, tool_name: &str, tags: &[String]) -> bool {
+        match &self.selector {
+            ToolSelector::Tool(name) => name == tool_name,
+            ToolSelector::Tag(tag) => tags.iter().any(|t| t == tag),
+        }
+    }
+}
+
+impl FromStr for AuthorizationRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid authorization rule '{s}': expected 'tool:NAME=BEHAVIOR' or 'tag:TAG=BEHAVIOR'"
+            )
+        };
+        let (selector, behavior) = s.split_once('=').ok_or_else(invalid)?;
+        let selector = match selector.split_once(':') {
+            Some(("tool", name)) if !name.is_empty() => ToolSelector::Tool(name.to_string()),
+            Some(("tag", tag)) if !tag.is_empty() => ToolSelector::Tag(tag.to_string()),
+            _ => return Err(invalid()),
+        };
+        let behavior = match behavior.strip_prefix("token:") {
+            Some("") => return Err(format!("Invalid authorization rule '{s}': missing token")),
+            Some(token) => AuthorizationBehavior::ServiceToken(ServiceToken::new(token)),
+            None => AuthorizationBehavior::Mode(behavior.parse()?),
+        };
+        Ok(Self { selector, behavior })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(rules: &[&str]) -> Vec<AuthorizationRule> {
+        rules.iter().map(|rule| rule.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn rule_parsing() {
+        assert_eq!(
+            "tag:admin=token:s3cret"
+                .parse::<AuthorizationRule>()
+                .unwrap(),
+            AuthorizationRule {
+                selector: ToolSelector::Tag("admin".to_string()),
+                behavior: AuthorizationBehavior::ServiceToken(ServiceToken::new("s3cret")),
+            }
+        );
+        assert_eq!(
+            "tool:listPets=compliant"
+                .parse::<AuthorizationRule>()
+                .unwrap()
+                .selector,
+            ToolSelector::Tool("listPets".to_string())
+        );
+        for invalid in [
+            "listPets=compliant",
+            "tool:=compliant",
+            "group:pets=compliant",
+            "tool:listPets",
+            "tool:listPets=token:",
+            "tool:listPets=forward",
+        ] {
+            assert!(invalid.parse::<AuthorizationRule>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn service_token_is_not_debug_printed() {
+        let rule = "tag:admin=token:s3cret"
+            .parse::<AuthorizationRule>()
+            .unwrap();
+        let debug = format!("{rule:?}");
+        assert!(!debug.contains("s3cret"), "{debug}");
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = rules(&[
+            "tool:deletePet=token:delete",
+            "tag:pets=token:pets",
+            "tag:admin=compliant",
+        ]);
+        let tags = vec!["pets".to_string(), "admin".to_string()];
+        let for_tool = |name: &str, tags: &[String]| {
+            Authorization::for_tool(AuthorizationMode::Compliant, &rules, name, tags, None)
+        };
+
+        assert!(matches!(
+            for_tool("deletePet", &tags),
+            Authorization::ServiceToken(token) if token.header_value() == "Bearer delete"
+        ));
+        assert!(matches!(
+            for_tool("listPets", &tags),
+            Authorization::ServiceToken(token) if token.header_value() == "Bearer pets"
+        ));
+        assert!(matches!(
+            for_tool("listUsers", &["admin".to_string()]),
+            Authorization::None
+        ));
+        assert!(matches!(for_tool("listUsers", &[]), Authorization::None));
+    }
 }
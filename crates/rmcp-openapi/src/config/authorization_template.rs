@@ -0,0 +1,132 @@
+//! Upstream headers built from the MCP client's `Authorization` header.
+//!
+//! With authorization passthrough, the client's header is forwarded as is.
+//! Templates translate it for upstreams expecting credentials elsewhere, as in
+//! `X-Api-Key: {token}`. Placeholders:
+//!
+//! - `{header}`: the whole header value
+//! - `{scheme}`: the authentication scheme, such as `Bearer`
+//! - `{token}`: the credentials after the scheme
+
+use reqwest::header::{HeaderName, HeaderValue};
+use std::str::FromStr;
+
+const PLACEHOLDERS: [&str; 3] = ["{header}", "{scheme}", "{token}"];
+
+/// Upstream header rendered from the client's authorization, written `Name: template`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationTemplate {
+    pub name: HeaderName,
+    pub template: String,
+}
+
+impl FromStr for AuthorizationTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, template) = s
+            .split_once(':')
+            .map(|(name, template)| (name.trim(), template.trim()))
+            .filter(|(name, template)| !name.is_empty() && !template.is_empty())
+            .ok_or_else(|| {
+                format!("Invalid authorization template '{s}': expected 'Name: template'")
+            })?;
+        let name = HeaderName::from_str(name)
+            .map_err(|e| format!("Invalid header name '{name}' in '{s}': {e}"))?;
+
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let placeholder = rest[start..]
+                .find('}')
+                .map(|end| &rest[start..=start + end])
+                .filter(|placeholder| PLACEHOLDERS.contains(placeholder))
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid authorization template '{s}': placeholders are {}",
+                        PLACEHOLDERS.join(", ")
+                    )
+                })?;
+            rest = &rest[start + placeholder.len()..];
+        }
+
+        Ok(Self {
+            name,
+            template: template.to_string(),
+        })
+    }
+}
+
+impl AuthorizationTemplate {
+    /// Render the header value from the client's `Authorization` header
+    ///
+    /// Returns `None` if the result is not a valid header value.
+    pub fn render(&self, authorization: &str) -> Option<HeaderValue> {
+        let authorization = authorization.trim();
+        let (scheme, token) = authorization
+            .split_once(' ')
+            .map_or(("", authorization), |(scheme, token)| {
+                (scheme, token.trim_start())
+            });
+        let value = self
+            .template
+            .replace("{header}", authorization)
+            .replace("{scheme}", scheme)
+            .replace("{token}", token);
+        let mut value = HeaderValue::from_str(&value).ok()?;
+        value.set_sensitive(true);
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, authorization: &str) -> HeaderValue {
+        template
+            .parse::<AuthorizationTemplate>()
+            .unwrap()
+            .render(authorization)
+            .unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let template = "X-Api-Key: {token}"
+            .parse::<AuthorizationTemplate>()
+            .unwrap();
+        assert_eq!(template.name, "x-api-key");
+        assert_eq!(template.template, "{token}");
+
+        for invalid in [
+            "X-Api-Key",
+            "X-Api-Key:",
+            ": {token}",
+            "X Api Key: {token}",
+            "X-Api-Key: {password}",
+            "X-Api-Key: {token",
+        ] {
+            assert!(
+                invalid.parse::<AuthorizationTemplate>().is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(render("X-Api-Key: {token}", "Bearer abc"), "abc");
+        assert_eq!(render("X-Scheme: {scheme}", "Bearer abc"), "Bearer");
+        assert_eq!(
+            render("X-Forwarded-Authorization: {header}", "Bearer abc"),
+            "Bearer abc"
+        );
+        assert_eq!(
+            render("Authorization: Token {token}", "Bearer abc"),
+            "Token abc"
+        );
+        // Headers without a scheme are all token
+        assert_eq!(render("X-Api-Key: {token}", "abc"), "abc");
+        assert!(render("X-Api-Key: {token}", "Bearer abc").is_sensitive());
+    }
+}
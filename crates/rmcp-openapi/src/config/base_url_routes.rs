@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+use url::Url;
+
+use crate::normalize_tag;
+use crate::tool::ToolMetadata;
+
+/// Tools sent to a [`BaseUrlRoute`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteSelector {
+    /// Tools whose operation has this tag, compared in kebab-case
+    Tag(String),
+    /// Tools whose path is this prefix or under it, such as `/pets` for
+    /// `/pets/{id}`
+    PathPrefix(String),
+}
+
+/// Base URL for a group of tools, so that one spec fronting several services
+/// sends each group to its host. Written `tag:TAG=URL` or `path:PREFIX=URL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseUrlRoute {
+    pub selector: RouteSelector,
+    pub base_url: Url,
+}
+
+impl BaseUrlRoute {
+    /// Whether the route applies to `tool`
+    pub fn matches(&self, tool: &ToolMetadata) -> bool {
+        match &self.selector {
+            RouteSelector::Tag(tag) => tool
+                .tags
+                .iter()
+                .any(|t| normalize_tag(t) == normalize_tag(tag)),
+            RouteSelector::PathPrefix(prefix) => {
+                let prefix = prefix.trim_end_matches('/');
+                tool.path
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            }
+        }
+    }
+
+    /// Base URL of `tool`, from the first of `routes` matching it
+    pub fn base_url_for<'a>(routes: &'a [BaseUrlRoute], tool: &ToolMetadata) -> Option<&'a Url> {
+        routes
+            .iter()
+            .find(|route| route.matches(tool))
+            .map(|route| &route.base_url)
+    }
+}
+
+impl FromStr for BaseUrlRoute {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid base URL route '{s}': expected 'tag:TAG=URL' or 'path:PREFIX=URL'");
+        let (selector, base_url) = s.split_once('=').ok_or_else(invalid)?;
+        let selector = match selector.split_once(':') {
+            Some(("tag", tag)) if !tag.is_empty() => RouteSelector::Tag(tag.to_string()),
+            Some(("path", prefix)) if prefix.starts_with('/') => {
+                RouteSelector::PathPrefix(prefix.to_string())
+            }
+            _ => return Err(invalid()),
+        };
+        let base_url =
+            Url::parse(base_url).map_err(|e| format!("Invalid base URL route '{s}': {e}"))?;
+        Ok(Self { selector, base_url })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn tool(path: &str, tags: &[&str]) -> ToolMetadata {
+        ToolMetadata {
+            name: "tool".to_string(),
+            title: None,
+            description: None,
+            parameters: json!({}),
+            output_schema: None,
+            method: "get".to_string(),
+            path: path.to_string(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_first_matching_route_wins() {
+        let routes: Vec<BaseUrlRoute> = [
+            "tag:PetStore=https://pets.internal/v1",
+            "path:/store/=https://store.internal",
+            "path:/pets=https://legacy.internal",
+        ]
+        .iter()
+        .map(|route| route.parse().unwrap())
+        .collect();
+        let base_url = |path: &str, tags: &[&str]| {
+            BaseUrlRoute::base_url_for(&routes, &tool(path, tags)).map(Url::as_str)
+        };
+
+        assert_eq!(
+            base_url("/pets/{id}", &["pet-store"]),
+            Some("https://pets.internal/v1")
+        );
+        assert_eq!(
+            base_url("/pets/{id}", &[]),
+            Some("https://legacy.internal/")
+        );
+        assert_eq!(base_url("/store", &[]), Some("https://store.internal/"));
+        assert_eq!(
+            base_url("/store/order", &[]),
+            Some("https://store.internal/")
+        );
+        assert_eq!(base_url("/petsitters", &[]), None);
+    }
+
+    #[test]
+    fn test_route_parsing() {
+        let route: BaseUrlRoute = "tag:admin=https://admin.internal/api?v=2".parse().unwrap();
+        assert_eq!(route.selector, RouteSelector::Tag("admin".to_string()));
+        assert_eq!(route.base_url.as_str(), "https://admin.internal/api?v=2");
+        for invalid in [
+            "admin=https://admin.internal",
+            "tag:=https://admin.internal",
+            "path:store=https://store.internal",
+            "tag:admin",
+            "tag:admin=not a url",
+        ] {
+            assert!(invalid.parse::<BaseUrlRoute>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use bon::Builder;
+use flate2::Compression;
+use flate2::read::{DeflateDecoder, GzDecoder};
+use flate2::write::{DeflateEncoder, GzEncoder};
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+
+/// Content codings understood for upstream request and response bodies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+    Brotli,
+}
+
+impl ContentEncoding {
+    /// Token used in `Content-Encoding` and `Accept-Encoding` headers
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+            ContentEncoding::Brotli => "br",
+        }
+    }
+
+    /// Parse a `Content-Encoding` header value, `None` for identity or unknown codings
+    pub(crate) fn from_header(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(ContentEncoding::Gzip),
+            "deflate" => Some(ContentEncoding::Deflate),
+            "br" => Some(ContentEncoding::Brotli),
+            _ => None,
+        }
+    }
+
+    /// Compress `data` with this coding
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoder fails
+    pub fn encode(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            ContentEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            ContentEncoding::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            ContentEncoding::Brotli => {
+                let mut encoded = Vec::new();
+                let mut encoder = brotli::CompressorWriter::new(&mut encoded, 4096, 5, 22);
+                encoder.write_all(data)?;
+                drop(encoder);
+                Ok(encoded)
+            }
+        }
+    }
+
+    /// Decompress `data` encoded with this coding
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not valid for this coding
+    pub fn decode(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut decoded = Vec::new();
+        self.decoder(data).read_to_end(&mut decoded)?;
+        Ok(decoded)
+    }
+
+    /// Decompress at most `limit` bytes of `data`, telling whether output was cut
+    ///
+    /// `data` may itself be the start of a longer stream when `complete` is
+    /// `false`; whatever decodes from it is returned.
+    pub(crate) fn decode_prefix(
+        self,
+        data: &[u8],
+        limit: Option<usize>,
+        complete: bool,
+    ) -> io::Result<(Vec<u8>, bool)> {
+        let mut decoded = Vec::new();
+        let cap = limit.map_or(u64::MAX, |limit| limit as u64 + 1);
+        if let Err(e) = self.decoder(data).take(cap).read_to_end(&mut decoded)
+            && complete
+        {
+            return Err(e);
+        }
+        let mut truncated = !complete;
+        if let Some(limit) = limit
+            && decoded.len() > limit
+        {
+            decoded.truncate(limit);
+            truncated = true;
+        }
+        Ok((decoded, truncated))
+    }
+
+    fn decoder(self, data: &[u8]) -> Box<dyn Read + '_> {
+        match self {
+            ContentEncoding::Gzip => Box::new(GzDecoder::new(data)),
+            ContentEncoding::Deflate => Box::new(DeflateDecoder::new(data)),
+            ContentEncoding::Brotli => Box::new(brotli::Decompressor::new(data, 4096)),
+        }
+    }
+}
+
+impl FromStr for ContentEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gzip" => Ok(ContentEncoding::Gzip),
+            "deflate" => Ok(ContentEncoding::Deflate),
+            "br" | "brotli" => Ok(ContentEncoding::Brotli),
+            _ => Err(format!(
+                "Invalid content encoding: '{s}'. Valid values: gzip, deflate, br"
+            )),
+        }
+    }
+}
+
+/// Compression of upstream traffic
+///
+/// Response decompression advertises gzip, deflate and brotli in
+/// `Accept-Encoding` and decodes bodies transparently. Request compression
+/// only applies to JSON bodies of at least `min_request_size` bytes, since
+/// small payloads rarely shrink enough to be worth it.
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct CompressionConfig {
+    /// Accept compressed responses and decode them
+    #[builder(default = true)]
+    pub decompress_responses: bool,
+    /// Coding used for request bodies, uncompressed when unset
+    pub request_encoding: Option<ContentEncoding>,
+    /// Smallest JSON body, in bytes, that gets compressed
+    #[builder(default = 1024)]
+    pub min_request_size: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let data = br#"{"name":"doggie","tags":["a","b","c"]}"#.repeat(20);
+        for encoding in [
+            ContentEncoding::Gzip,
+            ContentEncoding::Deflate,
+            ContentEncoding::Brotli,
+        ] {
+            let encoded = encoding.encode(&data).unwrap();
+            assert!(encoded.len() < data.len(), "{encoding:?} did not shrink");
+            assert_eq!(encoding.decode(&encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_decode_prefix() {
+        let data = b"0123456789".repeat(100);
+        let encoded = ContentEncoding::Gzip.encode(&data).unwrap();
+
+        let (decoded, truncated) = ContentEncoding::Gzip
+            .decode_prefix(&encoded, Some(100), true)
+            .unwrap();
+        assert_eq!(decoded, &data[..100]);
+        assert!(truncated);
+
+        let (decoded, truncated) = ContentEncoding::Gzip
+            .decode_prefix(&encoded, None, true)
+            .unwrap();
+        assert_eq!(decoded, data);
+        assert!(!truncated);
+
+        // A stream cut short still yields what decodes
+        let (decoded, truncated) = ContentEncoding::Gzip
+            .decode_prefix(&encoded[..encoded.len() - 10], None, false)
+            .unwrap();
+        assert!(data.starts_with(&decoded));
+        assert!(truncated);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("br".parse(), Ok(ContentEncoding::Brotli));
+        assert_eq!("brotli".parse(), Ok(ContentEncoding::Brotli));
+        assert!("zstd".parse::<ContentEncoding>().is_err());
+        assert_eq!(
+            ContentEncoding::from_header(" GZIP "),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(ContentEncoding::from_header("identity"), None);
+    }
+}
@@ -0,0 +1,115 @@
+use bon::Builder;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Connection pool and socket settings for the upstream HTTP client
+///
+/// Unset fields keep the `reqwest` defaults: unlimited idle connections per
+/// host, a 90 second idle timeout, no separate connect timeout, a 15 second
+/// TCP keepalive and system DNS resolution.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Builder)]
+pub struct ConnectionConfig {
+    /// Maximum idle connections kept open per upstream host
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long an idle pooled connection is kept before it is closed
+    pub pool_idle_timeout: Option<Duration>,
+    /// Timeout for establishing a connection, TLS handshake included
+    pub connect_timeout: Option<Duration>,
+    /// Interval of TCP keepalive probes on open connections
+    pub tcp_keepalive: Option<Duration>,
+    /// Addresses used instead of DNS answers for specific hosts
+    #[builder(default)]
+    pub resolve: Vec<HostOverride>,
+}
+
+/// Fixed address for an upstream host, in the style of curl's `--resolve`
+///
+/// Written `host:port:addr`, with IPv6 addresses in brackets
+/// (`api.example.com:443:[2001:db8::1]`). TLS still verifies the certificate
+/// against `host`. DNS has no notion of ports, so the override applies to
+/// every port of the host and connections go to the port in the request URL.
+/// Several overrides for the same host are tried in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostOverride {
+    /// Host name as it appears in request URLs
+    pub host: String,
+    /// Port the override was written for
+    pub port: u16,
+    /// Address connected to instead of the DNS answer
+    pub addr: IpAddr,
+}
+
+impl HostOverride {
+    /// Socket address handed to the resolver
+    #[must_use]
+    pub fn socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.addr, self.port)
+    }
+}
+
+impl FromStr for HostOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid host override: '{s}'. Expected host:port:addr");
+
+        let mut parts = s.splitn(3, ':');
+        let (Some(host), Some(port), Some(addr)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        let port = port.parse().map_err(|_| invalid())?;
+        let addr = addr
+            .strip_prefix('[')
+            .and_then(|a| a.strip_suffix(']'))
+            .unwrap_or(addr)
+            .parse()
+            .map_err(|_| invalid())?;
+
+        Ok(Self {
+            host: host.to_ascii_lowercase(),
+            port,
+            addr,
+        })
+    }
+}
+
+impl fmt::Display for HostOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.addr {
+            IpAddr::V4(addr) => write!(f, "{}:{}:{addr}", self.host, self.port),
+            IpAddr::V6(addr) => write!(f, "{}:{}:[{addr}]", self.host, self.port),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_curl_style_overrides() {
+        let v4: HostOverride = "API.example.com:443:10.0.0.5".parse().unwrap();
+        assert_eq!(v4.host, "api.example.com");
+        assert_eq!(v4.socket_addr(), "10.0.0.5:443".parse().unwrap());
+        assert_eq!(v4.to_string(), "api.example.com:443:10.0.0.5");
+
+        let v6: HostOverride = "staging.internal:8080:[::1]".parse().unwrap();
+        assert_eq!(v6.socket_addr(), "[::1]:8080".parse().unwrap());
+        assert_eq!(v6.to_string(), "staging.internal:8080:[::1]");
+
+        for invalid in [
+            "api.example.com:10.0.0.5",
+            ":443:10.0.0.5",
+            "host:https:10.0.0.5",
+            "host:443:not-an-ip",
+        ] {
+            assert!(invalid.parse::<HostOverride>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::tool::ToolMetadata;
+
+/// What to do when several operations produce the same tool name
+///
+/// Tool names come from operation IDs, which specs don't always keep unique,
+/// and from method and path otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateNameStrategy {
+    /// Refuse the spec
+    #[default]
+    Error,
+    /// Rename the later tools after their method and a hash of their path,
+    /// as `getPet_get_1a2b3c4d`
+    Suffix,
+    /// Keep the last operation only
+    LastWins,
+}
+
+impl FromStr for DuplicateNameStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(DuplicateNameStrategy::Error),
+            "suffix" => Ok(DuplicateNameStrategy::Suffix),
+            "last-wins" => Ok(DuplicateNameStrategy::LastWins),
+            _ => Err(format!(
+                "Invalid duplicate name strategy: '{s}'. Valid values: error, suffix, last-wins"
+            )),
+        }
+    }
+}
+
+impl DuplicateNameStrategy {
+    /// Resolve the duplicate names of `tools`, in spec order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two tools share a name and the strategy is
+    /// [`DuplicateNameStrategy::Error`]
+    pub(crate) fn apply(self, tools: Vec<ToolMetadata>) -> Result<Vec<ToolMetadata>, Error> {
+        let mut resolved: Vec<ToolMetadata> = Vec::with_capacity(tools.len());
+        for mut tool in tools {
+            if let Some(index) = resolved.iter().position(|other| other.name == tool.name) {
+                match self {
+                    DuplicateNameStrategy::Error => {
+                        return Err(duplicate_name_error(&resolved[index], &tool));
+                    }
+                    DuplicateNameStrategy::Suffix => tool.name = suffixed_name(&tool),
+                    DuplicateNameStrategy::LastWins => {
+                        resolved.remove(index);
+                    }
+                }
+            }
+            resolved.push(tool);
+        }
+        Ok(resolved)
+    }
+}
+
+/// Error for `tool`, named as `existing` is
+pub(crate) fn duplicate_name_error(existing: &ToolMetadata, tool: &ToolMetadata) -> Error {
+    Error::ToolGeneration(format!(
+        "Tool '{}' already exists: {} {} and {} {} have the same name",
+        tool.name,
+        existing.method.to_uppercase(),
+        existing.path,
+        tool.method.to_uppercase(),
+        tool.path
+    ))
+}
+
+/// Name of `tool` suffixed with its method and a hash of its path, stable
+/// across reloads and releases
+pub(crate) fn suffixed_name(tool: &ToolMetadata) -> String {
+    // 32-bit FNV-1a
+    let hash = tool.path.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    format!("{}_{}_{hash:08x}", tool.name, tool.method.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn tool(name: &str, method: &str, path: &str) -> ToolMetadata {
+        ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            parameters: json!({}),
+            output_schema: None,
+            method: method.to_string(),
+            path: path.to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    fn names(tools: &[ToolMetadata]) -> Vec<(&str, &str)> {
+        tools
+            .iter()
+            .map(|tool| (tool.name.as_str(), tool.path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_duplicate_name_strategies() {
+        let tools = || {
+            vec![
+                tool("getPet", "get", "/pets/{id}"),
+                tool("listPets", "get", "/pets"),
+                tool("getPet", "get", "/v2/pets/{id}"),
+            ]
+        };
+
+        let error = DuplicateNameStrategy::Error.apply(tools()).unwrap_err();
+        assert!(error.to_string().contains("GET /v2/pets/{id}"));
+
+        let suffixed = DuplicateNameStrategy::Suffix.apply(tools()).unwrap();
+        assert_eq!(suffixed[0].name, "getPet");
+        assert!(suffixed[2].name.starts_with("getPet_get_"));
+        assert_eq!(suffixed[2].name, suffixed_name(&tools()[2]));
+        assert_ne!(suffixed_name(&tools()[0]), suffixed_name(&tools()[2]));
+
+        let last = DuplicateNameStrategy::LastWins.apply(tools()).unwrap();
+        assert_eq!(
+            names(&last),
+            [("listPets", "/pets"), ("getPet", "/v2/pets/{id}")]
+        );
+    }
+}
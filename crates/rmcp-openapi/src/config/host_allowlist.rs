@@ -0,0 +1,93 @@
+use url::Url;
+
+/// Hosts upstream requests may be sent to
+///
+/// Guards against a spec or argument steering requests, redirects or
+/// credentials to another host (SSRF). Entries are host names or IP
+/// addresses, matched case-insensitively; `*.example.com` matches every
+/// subdomain of `example.com` but not `example.com` itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostAllowlist {
+    patterns: Vec<String>,
+}
+
+impl HostAllowlist {
+    /// Allowlist of `patterns`
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::default().with_hosts(patterns)
+    }
+
+    /// Also allow `patterns`
+    #[must_use]
+    pub fn with_hosts<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.patterns.extend(
+            patterns
+                .into_iter()
+                .map(|p| normalize(p.as_ref()))
+                .filter(|p| !p.is_empty()),
+        );
+        self
+    }
+
+    /// Whether requests to `host` are allowed
+    pub fn allows(&self, host: &str) -> bool {
+        let host = normalize(host);
+        self.patterns
+            .iter()
+            .any(|pattern| match pattern.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.len() > 1 && sub.ends_with('.')),
+                None => *pattern == host,
+            })
+    }
+
+    /// Whether requests to `url` are allowed
+    pub fn allows_url(&self, url: &Url) -> bool {
+        url.host_str().is_some_and(|host| self.allows(host))
+    }
+}
+
+fn normalize(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_hosts() {
+        let allowlist = HostAllowlist::new(["api.example.com", "10.0.0.5"]);
+        assert!(allowlist.allows("api.example.com"));
+        assert!(allowlist.allows("API.Example.com."));
+        assert!(allowlist.allows("10.0.0.5"));
+        assert!(!allowlist.allows("example.com"));
+        assert!(!allowlist.allows("api.example.com.evil.net"));
+        assert!(!allowlist.allows("169.254.169.254"));
+    }
+
+    #[test]
+    fn test_wildcard_matches_subdomains_only() {
+        let allowlist = HostAllowlist::new(["*.example.com"]);
+        assert!(allowlist.allows("api.example.com"));
+        assert!(allowlist.allows("eu.api.example.com"));
+        assert!(!allowlist.allows("example.com"));
+        assert!(!allowlist.allows("evilexample.com"));
+    }
+
+    #[test]
+    fn test_urls_without_host_are_refused() {
+        let allowlist = HostAllowlist::new(["api.example.com"]);
+        assert!(allowlist.allows_url(&Url::parse("https://api.example.com:8443/pets").unwrap()));
+        assert!(!allowlist.allows_url(&Url::parse("file:///etc/passwd").unwrap()));
+    }
+}
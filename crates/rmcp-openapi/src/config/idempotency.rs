@@ -0,0 +1,35 @@
+use bon::Builder;
+use reqwest::Method;
+
+/// Automatic idempotency keys for non-idempotent requests
+///
+/// Each tool call of a matching method gets a fresh UUID v4 in `header`, kept
+/// for every retry of that call, so an API that supports idempotency keys
+/// never creates the same resource twice. A key supplied by the caller, as a
+/// header parameter declared in the spec or as a default header, is sent as
+/// is. Because keyed requests are safe to repeat, the retry policy retries
+/// them even when it does not retry `POST` requests otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct IdempotencyConfig {
+    /// Header carrying the key
+    #[builder(into, default = "Idempotency-Key")]
+    pub header: String,
+    /// HTTP methods that get a key
+    #[builder(default = vec![Method::POST])]
+    pub methods: Vec<Method>,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl IdempotencyConfig {
+    /// Whether calls using `method` get a key
+    pub(crate) fn applies_to(&self, method: &str) -> bool {
+        self.methods
+            .iter()
+            .any(|m| m.as_str().eq_ignore_ascii_case(method))
+    }
+}
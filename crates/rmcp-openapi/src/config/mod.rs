@@ -1,3 +1,34 @@
 pub mod authorization;
+pub mod authorization_template;
+pub mod base_url_routes;
+pub mod compression;
+pub mod connection;
+pub mod duplicate_names;
+pub mod host_allowlist;
+pub mod idempotency;
+pub mod null_values;
+pub mod output_schema;
+pub mod proxy;
+pub mod redirect;
+pub mod status_policy;
+pub mod timeouts;
+pub mod tls;
 
-pub use authorization::{Authorization, AuthorizationMode};
+pub use authorization::{
+    Authorization, AuthorizationBehavior, AuthorizationMode, AuthorizationRule, ServiceToken,
+    ToolSelector,
+};
+pub use authorization_template::AuthorizationTemplate;
+pub use base_url_routes::{BaseUrlRoute, RouteSelector};
+pub use compression::{CompressionConfig, ContentEncoding};
+pub use connection::{ConnectionConfig, HostOverride};
+pub use duplicate_names::DuplicateNameStrategy;
+pub use host_allowlist::HostAllowlist;
+pub use idempotency::IdempotencyConfig;
+pub use null_values::NullValuePolicy;
+pub use output_schema::{OutputSchemaMode, OutputSchemaPolicy};
+pub use proxy::ProxyConfig;
+pub use redirect::{RedirectMode, RedirectPolicy};
+pub use status_policy::StatusPolicy;
+pub use timeouts::TimeoutPolicy;
+pub use tls::{ClientCertificate, load_ca_bundle};
@@ -0,0 +1,32 @@
+use std::str::FromStr;
+
+/// How `null` query parameter values are sent to the upstream API
+///
+/// A `null` only reaches the HTTP client when the parameter schema allows it.
+/// Upstream APIs disagree on what an empty or `null` query parameter means, so
+/// the encoding is chosen per server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullValuePolicy {
+    /// Leave the parameter out of the query string, as if it were absent
+    Omit,
+    /// Send the parameter with an empty value (`?name=`)
+    Empty,
+    /// Send the literal string `null` (`?name=null`)
+    #[default]
+    Literal,
+}
+
+impl FromStr for NullValuePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "omit" => Ok(NullValuePolicy::Omit),
+            "empty" => Ok(NullValuePolicy::Empty),
+            "literal" => Ok(NullValuePolicy::Literal),
+            _ => Err(format!(
+                "Invalid null value policy: '{s}'. Valid values: omit, empty, literal"
+            )),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use bon::Builder;
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use crate::tool::ToolMetadata;
+
+/// How tool output schemas constrain structured results
+///
+/// Output schemas wrap the response body with `status`, `body` and the fields
+/// enabled features add, and forbid any other top-level field. Clients that
+/// validate strictly then reject results carrying fields a middleware or a
+/// newer server adds; lenient schemas allow them. Unwrapped schemas are the
+/// success schemas of the spec, the structured content of successful results
+/// being the response body itself, for clients expecting the API's own shapes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputSchemaMode {
+    /// Forbid top-level fields the schema does not declare
+    #[default]
+    Strict,
+    /// Allow top-level fields the schema does not declare
+    Lenient,
+    /// Advertise the success schema of the response body, returned as is;
+    /// error results carry no structured content
+    Unwrapped,
+    /// Advertise no output schema, tools returning unstructured results only
+    Disabled,
+}
+
+impl FromStr for OutputSchemaMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(OutputSchemaMode::Strict),
+            "lenient" => Ok(OutputSchemaMode::Lenient),
+            "unwrapped" => Ok(OutputSchemaMode::Unwrapped),
+            "disabled" => Ok(OutputSchemaMode::Disabled),
+            _ => Err(format!(
+                "Invalid output schema mode: '{s}'. Valid values: strict, lenient, unwrapped, disabled"
+            )),
+        }
+    }
+}
+
+/// Output schemas advertised by tools
+#[derive(Debug, Clone, Default, PartialEq, Eq, Builder)]
+pub struct OutputSchemaPolicy {
+    #[builder(default)]
+    pub mode: OutputSchemaMode,
+    /// Tools advertising no output schema whatever the mode, by name
+    #[builder(default)]
+    pub disabled_tools: BTreeSet<String>,
+}
+
+impl OutputSchemaPolicy {
+    /// Apply the policy to the output schema of `metadata`
+    pub(crate) fn apply(&self, metadata: &mut ToolMetadata) {
+        if self.mode == OutputSchemaMode::Disabled || self.disabled_tools.contains(&metadata.name) {
+            metadata.output_schema = None;
+        } else if self.mode == OutputSchemaMode::Lenient
+            && let Some(Value::Object(schema)) = &mut metadata.output_schema
+            && schema.get("additionalProperties") == Some(&Value::Bool(false))
+        {
+            schema.remove("additionalProperties");
+        } else if self.mode == OutputSchemaMode::Unwrapped {
+            // Outputs must be objects: other bodies are returned unstructured
+            metadata.output_schema = metadata.output_schema.as_ref().and_then(success_schema);
+        }
+    }
+}
+
+/// Success schema of the body of a wrapped output schema, if an object
+fn success_schema(schema: &Value) -> Option<Value> {
+    let body = schema.pointer("/properties/body")?;
+    let success = match body.get("oneOf").and_then(Value::as_array) {
+        Some(variants) => variants.first()?,
+        None => body,
+    };
+    (success.get("type") == Some(&json!("object"))).then(|| success.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn tool(name: &str) -> ToolMetadata {
+        ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            parameters: json!({}),
+            output_schema: Some(json!({
+                "type": "object",
+                "additionalProperties": false,
+                "properties": { "status": { "type": "integer" } }
+            })),
+            method: "get".to_string(),
+            path: "/".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_output_schema_modes() {
+        let mut strict = tool("getPet");
+        OutputSchemaPolicy::default().apply(&mut strict);
+        assert_eq!(strict.output_schema, tool("getPet").output_schema);
+
+        let mut lenient = tool("getPet");
+        OutputSchemaPolicy::builder()
+            .mode(OutputSchemaMode::Lenient)
+            .build()
+            .apply(&mut lenient);
+        let schema = lenient.output_schema.unwrap();
+        assert!(schema.get("additionalProperties").is_none());
+        assert_eq!(schema["properties"]["status"]["type"], "integer");
+
+        let policy = OutputSchemaPolicy::builder()
+            .disabled_tools(BTreeSet::from(["searchPets".to_string()]))
+            .build();
+        let mut disabled = tool("searchPets");
+        policy.apply(&mut disabled);
+        assert!(disabled.output_schema.is_none());
+        let mut other = tool("getPet");
+        policy.apply(&mut other);
+        assert!(other.output_schema.is_some());
+    }
+
+    #[test]
+    fn test_unwrapped_output_schemas() {
+        let policy = OutputSchemaPolicy::builder()
+            .mode(OutputSchemaMode::Unwrapped)
+            .build();
+        let pet = json!({ "type": "object", "properties": { "id": { "type": "integer" } } });
+        let mut object = tool("getPet");
+        object.output_schema = Some(json!({
+            "type": "object",
+            "properties": {
+                "status": { "type": "integer" },
+                "body": { "oneOf": [pet.clone(), { "type": "object" }] }
+            }
+        }));
+        policy.apply(&mut object);
+        assert_eq!(object.output_schema, Some(pet));
+
+        let mut array = tool("listPets");
+        array.output_schema = Some(json!({
+            "type": "object",
+            "properties": { "body": { "oneOf": [{ "type": "array" }, { "type": "object" }] } }
+        }));
+        policy.apply(&mut array);
+        assert!(array.output_schema.is_none());
+    }
+}
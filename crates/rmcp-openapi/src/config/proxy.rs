@@ -0,0 +1,39 @@
+use bon::Builder;
+use reqwest::{NoProxy, Proxy};
+use url::Url;
+
+/// Outbound proxy for requests to the upstream API
+///
+/// Without an explicit proxy the client still honours the `HTTP_PROXY`,
+/// `HTTPS_PROXY` and `NO_PROXY` environment variables.
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct ProxyConfig {
+    /// Proxy URL for both HTTP and HTTPS traffic, e.g. `http://proxy.corp:3128`
+    pub url: Url,
+    /// Comma-separated hosts, domains and CIDR ranges to reach directly
+    /// (same syntax as `NO_PROXY`). Falls back to the `NO_PROXY` environment variable.
+    pub no_proxy: Option<String>,
+    /// Username for proxy basic authentication
+    pub username: Option<String>,
+    /// Password for proxy basic authentication
+    pub password: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Build the `reqwest` proxy
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proxy URL is not usable as a proxy
+    pub fn to_reqwest(&self) -> Result<Proxy, reqwest::Error> {
+        let mut proxy = Proxy::all(self.url.as_str())?;
+        if let Some(username) = &self.username {
+            proxy = proxy.basic_auth(username, self.password.as_deref().unwrap_or_default());
+        }
+        let no_proxy = match &self.no_proxy {
+            Some(no_proxy) => NoProxy::from_string(no_proxy),
+            None => NoProxy::from_env(),
+        };
+        Ok(proxy.no_proxy(no_proxy))
+    }
+}
@@ -0,0 +1,123 @@
+use bon::Builder;
+use std::str::FromStr;
+use tracing::warn;
+
+use super::HostAllowlist;
+
+/// How many redirects the upstream client follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RedirectMode {
+    /// Return 3xx responses to the caller as they are
+    None,
+    /// Follow at most this many redirects
+    Limited(usize),
+    /// Follow up to 10 redirects, the `reqwest` default
+    #[default]
+    Follow,
+}
+
+impl RedirectMode {
+    /// Maximum number of redirects followed for one call
+    #[must_use]
+    pub fn max_redirects(&self) -> usize {
+        match self {
+            RedirectMode::None => 0,
+            RedirectMode::Limited(max) => *max,
+            RedirectMode::Follow => 10,
+        }
+    }
+}
+
+impl FromStr for RedirectMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(RedirectMode::None),
+            "follow" => Ok(RedirectMode::Follow),
+            _ => s.parse().map(RedirectMode::Limited).map_err(|_| {
+                format!(
+                    "Invalid redirect mode: '{s}'. Valid values: none, follow, or a maximum number of redirects"
+                )
+            }),
+        }
+    }
+}
+
+/// Redirect handling for upstream requests
+///
+/// By default `Authorization`, `Cookie` and `Proxy-Authorization` headers are
+/// dropped when a redirect leaves the original scheme, host and port, so
+/// tokens never reach a third party. APIs that redirect to a trusted
+/// signed-URL host on another origin can opt into forwarding them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Builder)]
+pub struct RedirectPolicy {
+    #[builder(default)]
+    pub mode: RedirectMode,
+    /// Keep credential headers on redirects to another origin
+    #[builder(default)]
+    pub forward_credentials_cross_origin: bool,
+}
+
+impl RedirectPolicy {
+    /// Whether redirects must be followed by hand rather than by `reqwest`,
+    /// which always strips credentials across origins
+    pub(crate) fn follows_manually(&self) -> bool {
+        self.forward_credentials_cross_origin && self.mode.max_redirects() > 0
+    }
+
+    /// Policy for `reqwest`, which stops at redirects to hosts `allowlist`
+    /// does not allow and returns them as they are
+    pub(crate) fn to_reqwest(self, allowlist: Option<&HostAllowlist>) -> reqwest::redirect::Policy {
+        if self.follows_manually() {
+            return reqwest::redirect::Policy::none();
+        }
+        match (self.mode, allowlist) {
+            (RedirectMode::None, _) => reqwest::redirect::Policy::none(),
+            (mode, Some(allowlist)) => {
+                let max = mode.max_redirects();
+                let allowlist = allowlist.clone();
+                reqwest::redirect::Policy::custom(move |attempt| {
+                    if attempt.previous().len() > max {
+                        attempt.error("too many redirects")
+                    } else if !allowlist.allows_url(attempt.url()) {
+                        warn!(location = %attempt.url(), "Not following redirect to a host outside the allowlist");
+                        attempt.stop()
+                    } else {
+                        attempt.follow()
+                    }
+                })
+            }
+            (RedirectMode::Limited(max), None) => reqwest::redirect::Policy::limited(max),
+            (RedirectMode::Follow, None) => reqwest::redirect::Policy::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_redirect_mode() {
+        assert_eq!("none".parse(), Ok(RedirectMode::None));
+        assert_eq!("follow".parse(), Ok(RedirectMode::Follow));
+        assert_eq!("3".parse(), Ok(RedirectMode::Limited(3)));
+        assert!("always".parse::<RedirectMode>().is_err());
+        assert_eq!(RedirectMode::Follow.max_redirects(), 10);
+    }
+
+    #[test]
+    fn test_follows_manually_only_when_forwarding() {
+        assert!(!RedirectPolicy::default().follows_manually());
+        let forwarding = RedirectPolicy::builder()
+            .forward_credentials_cross_origin(true)
+            .build();
+        assert!(forwarding.follows_manually());
+        let none = RedirectPolicy::builder()
+            .mode(RedirectMode::None)
+            .forward_credentials_cross_origin(true)
+            .build();
+        assert!(!none.follows_manually());
+    }
+}
@@ -0,0 +1,72 @@
+use bon::Builder;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::tool::ToolMetadata;
+
+/// Upstream statuses returned as successful tool results
+///
+/// Any status outside `2xx` makes the tool result an error by default. Some
+/// APIs answer with `404` when a search finds nothing, which agents should
+/// read as an empty answer rather than a failure: listing `404` here returns
+/// such responses as successful results, with their status and body.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Builder)]
+pub struct StatusPolicy {
+    /// Statuses successful for every tool
+    #[builder(default)]
+    pub success: BTreeSet<u16>,
+    /// Statuses successful for individual tools, keyed by tool name
+    #[builder(default)]
+    pub per_tool: HashMap<String, BTreeSet<u16>>,
+}
+
+impl StatusPolicy {
+    /// Whether a `status` response to a call to `tool` is a successful result
+    #[must_use]
+    pub fn is_success(&self, tool: &ToolMetadata, status: u16) -> bool {
+        (200..300).contains(&status)
+            || self.success.contains(&status)
+            || self
+                .per_tool
+                .get(&tool.name)
+                .is_some_and(|statuses| statuses.contains(&status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool(name: &str) -> ToolMetadata {
+        ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            parameters: json!({}),
+            output_schema: None,
+            method: "get".to_string(),
+            path: "/".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_configured_statuses_are_successful() {
+        let policy = StatusPolicy::builder()
+            .success(BTreeSet::from([409]))
+            .per_tool(HashMap::from([(
+                "searchPets".to_string(),
+                BTreeSet::from([404]),
+            )]))
+            .build();
+
+        assert!(policy.is_success(&tool("getPet"), 204));
+        assert!(policy.is_success(&tool("getPet"), 409));
+        assert!(!policy.is_success(&tool("getPet"), 404));
+        assert!(policy.is_success(&tool("searchPets"), 404));
+        assert!(!policy.is_success(&tool("searchPets"), 500));
+        assert!(!StatusPolicy::default().is_success(&tool("searchPets"), 404));
+    }
+}
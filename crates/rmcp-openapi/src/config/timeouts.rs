@@ -0,0 +1,164 @@
+use bon::Builder;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::normalize_tag;
+use crate::tool::ToolMetadata;
+
+/// Server-side limits on how long upstream requests may take
+///
+/// Callers can still pass `timeout_seconds`, but only to shorten the timeout:
+/// a configured per-tool or per-tag timeout, and the global `max`, always win.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Builder)]
+pub struct TimeoutPolicy {
+    /// Timeouts for individual tools, keyed by tool name
+    #[builder(default)]
+    pub per_tool: HashMap<String, Duration>,
+    /// Timeouts for every tool of an `OpenAPI` tag. Tags are compared in kebab-case;
+    /// a tool with several configured tags gets the shortest timeout.
+    #[builder(default)]
+    pub per_tag: HashMap<String, Duration>,
+    /// Hard upper bound for every request
+    pub max: Option<Duration>,
+}
+
+impl TimeoutPolicy {
+    /// Timeout configured for a tool, by name first and then by tag
+    fn configured_for(&self, tool: &ToolMetadata) -> Option<Duration> {
+        self.per_tool.get(&tool.name).copied().or_else(|| {
+            tool.tags
+                .iter()
+                .filter_map(|tag| {
+                    self.per_tag.iter().find_map(|(configured, timeout)| {
+                        (normalize_tag(configured) == normalize_tag(tag)).then_some(*timeout)
+                    })
+                })
+                .min()
+        })
+    }
+
+    /// Effective timeout for a call to `tool`
+    ///
+    /// `requested` is the caller-provided timeout, if any; `default` is used
+    /// when neither the caller nor the policy sets one.
+    #[must_use]
+    pub fn timeout_for(
+        &self,
+        tool: &ToolMetadata,
+        requested: Option<Duration>,
+        default: Duration,
+    ) -> Duration {
+        let configured = self.configured_for(tool);
+        let timeout = requested.or(configured).unwrap_or(default);
+        [Some(timeout), configured, self.max]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool(name: &str, tags: &[&str]) -> ToolMetadata {
+        ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            parameters: json!({}),
+            output_schema: None,
+            method: "get".to_string(),
+            path: "/".to_string(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    const DEFAULT: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn test_default_policy_keeps_caller_timeout() {
+        let policy = TimeoutPolicy::default();
+        let tool = tool("getPet", &[]);
+        assert_eq!(policy.timeout_for(&tool, None, DEFAULT), DEFAULT);
+        assert_eq!(
+            policy.timeout_for(&tool, Some(Duration::from_secs(120)), DEFAULT),
+            Duration::from_secs(120)
+        );
+    }
+
+    #[test]
+    fn test_configured_timeout_caps_caller_timeout() {
+        let policy = TimeoutPolicy::builder()
+            .per_tool(HashMap::from([(
+                "exportReport".to_string(),
+                Duration::from_secs(5),
+            )]))
+            .build();
+        let tool = tool("exportReport", &[]);
+        assert_eq!(
+            policy.timeout_for(&tool, None, DEFAULT),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            policy.timeout_for(&tool, Some(Duration::from_secs(600)), DEFAULT),
+            Duration::from_secs(5)
+        );
+        // Shorter caller timeouts are still honoured
+        assert_eq!(
+            policy.timeout_for(&tool, Some(Duration::from_secs(2)), DEFAULT),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_tag_timeouts_and_tool_precedence() {
+        let policy = TimeoutPolicy::builder()
+            .per_tool(HashMap::from([(
+                "fastReport".to_string(),
+                Duration::from_secs(60),
+            )]))
+            .per_tag(HashMap::from([
+                ("Reports".to_string(), Duration::from_secs(10)),
+                ("bulk-export".to_string(), Duration::from_secs(20)),
+            ]))
+            .build();
+
+        // Tags match in kebab-case; the shortest tag timeout wins
+        let slow = tool("slowReport", &["reports", "BulkExport"]);
+        assert_eq!(
+            policy.timeout_for(&slow, None, DEFAULT),
+            Duration::from_secs(10)
+        );
+
+        // A per-tool entry overrides tag timeouts and may exceed the default
+        let fast = tool("fastReport", &["reports"]);
+        assert_eq!(
+            policy.timeout_for(&fast, None, DEFAULT),
+            Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn test_max_is_a_hard_cap() {
+        let policy = TimeoutPolicy::builder()
+            .per_tool(HashMap::from([(
+                "getPet".to_string(),
+                Duration::from_secs(90),
+            )]))
+            .max(Duration::from_secs(45))
+            .build();
+        assert_eq!(
+            policy.timeout_for(&tool("getPet", &[]), None, DEFAULT),
+            Duration::from_secs(45)
+        );
+        assert_eq!(
+            policy.timeout_for(&tool("other", &[]), Some(Duration::from_secs(300)), DEFAULT),
+            Duration::from_secs(45)
+        );
+    }
+}
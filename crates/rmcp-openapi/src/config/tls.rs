@@ -0,0 +1,60 @@
+use bon::Builder;
+use reqwest::{Certificate, Identity};
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+
+/// Client certificate presented to upstream APIs that require mutual TLS
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct ClientCertificate {
+    /// PEM file with the client certificate, optionally followed by its chain
+    pub cert_path: PathBuf,
+    /// PEM file with the private key (PKCS#8, PKCS#1 or SEC1)
+    pub key_path: PathBuf,
+}
+
+impl ClientCertificate {
+    /// Read the certificate and key files into a `reqwest` identity
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be read or does not contain
+    /// a usable PEM certificate or private key
+    pub fn to_identity(&self) -> Result<Identity, Error> {
+        let read = |path: &PathBuf| {
+            std::fs::read(path)
+                .map_err(|e| Error::Tls(format!("Failed to read '{}': {e}", path.display())))
+        };
+        let mut pem = read(&self.cert_path)?;
+        pem.push(b'\n');
+        pem.extend(read(&self.key_path)?);
+
+        Identity::from_pem(&pem).map_err(|e| {
+            Error::Tls(format!(
+                "Invalid client certificate '{}' or key '{}': {e}",
+                self.cert_path.display(),
+                self.key_path.display()
+            ))
+        })
+    }
+}
+
+/// Read a PEM bundle of root certificates to trust for upstream requests,
+/// e.g. the CA of a private PKI
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or contains no PEM certificate
+pub fn load_ca_bundle(path: &Path) -> Result<Vec<Certificate>, Error> {
+    let pem = std::fs::read(path)
+        .map_err(|e| Error::Tls(format!("Failed to read '{}': {e}", path.display())))?;
+    let certificates = Certificate::from_pem_bundle(&pem)
+        .map_err(|e| Error::Tls(format!("Invalid CA bundle '{}': {e}", path.display())))?;
+    if certificates.is_empty() {
+        return Err(Error::Tls(format!(
+            "CA bundle '{}' contains no certificates",
+            path.display()
+        )));
+    }
+    Ok(certificates)
+}
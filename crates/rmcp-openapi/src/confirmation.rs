@@ -0,0 +1,233 @@
+//! Confirmation of destructive tool calls.
+//!
+//! Agents call `DELETE`, `PUT` and `PATCH` operations as readily as `GET`
+//! ones. With a [`ConfirmationConfig`], the first call of such a tool does not
+//! reach the upstream: it fails with
+//! [`ToolCallExecutionError::ConfirmationRequired`](crate::error::ToolCallExecutionError::ConfirmationRequired)
+//! carrying a one-time token. Repeating the call with the same arguments plus
+//! that token in [`CONFIRMATION_TOKEN_PARAMETER`] executes it. This gives the
+//! agent, or a human reviewing its actions, a step to stop before data is
+//! changed.
+
+use bon::Builder;
+use reqwest::Method;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::error::{ToolCallError, ToolCallExecutionError};
+use crate::tool::ToolMetadata;
+
+/// Argument carrying the token that confirms a call
+pub const CONFIRMATION_TOKEN_PARAMETER: &str = "confirmation_token";
+
+/// Which calls need confirmation and how long a confirmation token is valid
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct ConfirmationConfig {
+    /// HTTP methods whose calls need confirmation
+    #[builder(default = vec![Method::DELETE, Method::PUT, Method::PATCH])]
+    pub methods: Vec<Method>,
+    /// How long a token confirms the call it was issued for
+    #[builder(default = Duration::from_secs(300))]
+    pub token_ttl: Duration,
+}
+
+impl Default for ConfirmationConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl ConfirmationConfig {
+    /// Whether calls using `method` need confirmation
+    pub(crate) fn applies_to(&self, method: &str) -> bool {
+        self.methods
+            .iter()
+            .any(|m| m.as_str().eq_ignore_ascii_case(method))
+    }
+
+    /// Declare the confirmation token in the input schema of tools needing
+    /// confirmation, whose schemas reject unknown arguments
+    pub(crate) fn extend_schema(&self, metadata: &mut ToolMetadata) {
+        if !self.applies_to(&metadata.method) {
+            return;
+        }
+        if let Some(properties) = metadata
+            .parameters
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+        {
+            properties.insert(
+                CONFIRMATION_TOKEN_PARAMETER.to_string(),
+                serde_json::json!({
+                    "type": "string",
+                    "description": "Token from a confirmation-required error, given to confirm this call with unchanged arguments"
+                }),
+            );
+        }
+    }
+}
+
+/// Call a token was issued for
+#[derive(Debug)]
+struct PendingCall {
+    tool: String,
+    arguments: Value,
+    expires_at: Instant,
+}
+
+/// Tokens issued and not used yet
+///
+/// Clones share the same tokens, so all tools using clones of one client
+/// accept tokens issued by any of them for their own calls only.
+#[derive(Debug, Clone)]
+pub(crate) struct ConfirmationGate {
+    config: ConfirmationConfig,
+    pending: Arc<Mutex<HashMap<String, PendingCall>>>,
+}
+
+impl ConfirmationGate {
+    pub(crate) fn new(config: ConfirmationConfig) -> Self {
+        Self {
+            config,
+            pending: Arc::default(),
+        }
+    }
+
+    /// Arguments to execute the call with, without the confirmation token
+    ///
+    /// Calls of methods needing confirmation are let through only with a
+    /// token issued for the same tool and arguments, which is used up.
+    /// Otherwise a new token is issued and returned in the error.
+    pub(crate) fn check<'a>(
+        &self,
+        metadata: &ToolMetadata,
+        arguments: &'a Value,
+    ) -> Result<Cow<'a, Value>, ToolCallError> {
+        if !self.config.applies_to(&metadata.method) {
+            return Ok(Cow::Borrowed(arguments));
+        }
+
+        let mut arguments = arguments.clone();
+        let token = arguments
+            .as_object_mut()
+            .and_then(|args| args.remove(CONFIRMATION_TOKEN_PARAMETER));
+
+        let now = Instant::now();
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|_, call| call.expires_at > now);
+
+        // A token only confirms the call it was issued for; it is used up
+        // either way so it cannot be tried against other calls
+        if let Some(call) = token
+            .as_ref()
+            .and_then(Value::as_str)
+            .and_then(|token| pending.remove(token))
+            && call.tool == metadata.name
+            && call.arguments == arguments
+        {
+            return Ok(Cow::Owned(arguments));
+        }
+
+        let token = Uuid::new_v4().to_string();
+        pending.insert(
+            token.clone(),
+            PendingCall {
+                tool: metadata.name.clone(),
+                arguments,
+                expires_at: now + self.config.token_ttl,
+            },
+        );
+
+        Err(ToolCallError::Execution(
+            ToolCallExecutionError::ConfirmationRequired {
+                tool: metadata.name.clone(),
+                method: metadata.method.to_uppercase(),
+                path: metadata.path.clone(),
+                confirmation_token: token,
+                expires_in_seconds: self.config.token_ttl.as_secs(),
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata(name: &str, method: &str) -> ToolMetadata {
+        ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            parameters: json!({"type": "object", "properties": {}}),
+            output_schema: None,
+            method: method.to_string(),
+            path: "/pets/{petId}".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    fn issued_token(result: Result<Cow<'_, Value>, ToolCallError>) -> String {
+        match result {
+            Err(ToolCallError::Execution(ToolCallExecutionError::ConfirmationRequired {
+                confirmation_token,
+                ..
+            })) => confirmation_token,
+            other => panic!("expected a confirmation request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn safe_methods_pass() {
+        let gate = ConfirmationGate::new(ConfirmationConfig::default());
+        let arguments = json!({"petId": 1});
+        let result = gate.check(&metadata("getPet", "get"), &arguments).unwrap();
+        assert_eq!(*result, arguments);
+    }
+
+    #[test]
+    fn token_confirms_the_same_call_once() {
+        let gate = ConfirmationGate::new(ConfirmationConfig::default());
+        let tool = metadata("deletePet", "delete");
+        let token = issued_token(gate.check(&tool, &json!({"petId": 1})));
+
+        let confirmed = json!({"petId": 1, "confirmation_token": token});
+        let result = gate.check(&tool, &confirmed).unwrap();
+        assert_eq!(*result, json!({"petId": 1}));
+
+        // Used up
+        issued_token(gate.check(&tool, &confirmed));
+    }
+
+    #[test]
+    fn token_does_not_confirm_other_calls() {
+        let gate = ConfirmationGate::new(ConfirmationConfig::default());
+        let tool = metadata("deletePet", "delete");
+        let token = issued_token(gate.check(&tool, &json!({"petId": 1})));
+        issued_token(gate.check(&tool, &json!({"petId": 2, "confirmation_token": token})));
+
+        let token = issued_token(gate.check(&tool, &json!({"petId": 1})));
+        issued_token(gate.check(
+            &metadata("updatePet", "put"),
+            &json!({"petId": 1, "confirmation_token": token}),
+        ));
+    }
+
+    #[test]
+    fn expired_tokens_are_refused() {
+        let config = ConfirmationConfig::builder()
+            .token_ttl(Duration::ZERO)
+            .build();
+        let gate = ConfirmationGate::new(config);
+        let tool = metadata("deletePet", "delete");
+        let token = issued_token(gate.check(&tool, &json!({"petId": 1})));
+        issued_token(gate.check(&tool, &json!({"petId": 1, "confirmation_token": token})));
+    }
+}
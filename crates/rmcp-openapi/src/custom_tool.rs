@@ -0,0 +1,157 @@
+//! Hand-written tools served alongside the generated ones.
+//!
+//! A [`CustomTool`] is an MCP tool whose calls are answered by a closure
+//! rather than an upstream operation, so that a deployment can offer helpers
+//! such as `login` or `summarize_logs` next to its `OpenAPI` tools. Custom
+//! tools are listed after the generated ones and called by name. Like
+//! generated tools, they can be disabled, hidden by a tool filter, drained on
+//! shutdown, queued and cancelled; having no tags, they are hidden from
+//! sessions limited to some tags, and they have no aliases.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rmcp::model::{CallToolResult, Content};
+//! use rmcp_openapi::{CustomTool, Server};
+//! use serde_json::json;
+//!
+//! let echo = CustomTool::new(
+//!     "echo",
+//!     "Return the given text",
+//!     json!({
+//!         "type": "object",
+//!         "properties": { "text": { "type": "string" } },
+//!         "required": ["text"]
+//!     }),
+//!     |arguments| async move {
+//!         let text = arguments.get("text").and_then(|t| t.as_str()).unwrap_or_default();
+//!         Ok(CallToolResult::success(vec![Content::text(text)]))
+//!     },
+//! )?;
+//! let server = Server::builder()
+//!     .openapi_spec(json!({}))
+//!     .base_url(url::Url::parse("https://api.example.com").unwrap())
+//!     .custom_tools(vec![echo])
+//!     .build();
+//! # Ok::<(), rmcp_openapi::Error>(())
+//! ```
+
+use rmcp::model::{CallToolResult, ErrorData, Tool as McpTool};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::error::Error;
+
+type Handler = dyn Fn(
+        Map<String, Value>,
+    ) -> Pin<Box<dyn Future<Output = Result<CallToolResult, ErrorData>> + Send>>
+    + Send
+    + Sync;
+
+/// Tool answered by a closure instead of an upstream operation
+#[derive(Clone)]
+pub struct CustomTool {
+    tool: McpTool,
+    handler: Arc<Handler>,
+}
+
+impl CustomTool {
+    /// Tool named `name`, taking arguments matching `input_schema` and
+    /// answered by `handler`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input_schema` is not a JSON object
+    pub fn new<F, Fut>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        input_schema: Value,
+        handler: F,
+    ) -> Result<Self, Error>
+    where
+        F: Fn(Map<String, Value>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<CallToolResult, ErrorData>> + Send + 'static,
+    {
+        let name = name.into();
+        let Value::Object(schema) = input_schema else {
+            return Err(Error::Validation(format!(
+                "Input schema of custom tool '{name}' must be an object"
+            )));
+        };
+        Ok(Self {
+            tool: McpTool::new_with_raw(
+                name,
+                Some(Into::<String>::into(description).into()),
+                Arc::new(schema),
+            ),
+            handler: Arc::new(move |arguments| Box::pin(handler(arguments))),
+        })
+    }
+
+    /// Name the tool is called by
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.tool.name
+    }
+
+    /// The tool, as listed to clients
+    #[must_use]
+    pub fn tool(&self) -> &McpTool {
+        &self.tool
+    }
+
+    /// Answer a call with `arguments`
+    pub(crate) async fn call(
+        &self,
+        arguments: Map<String, Value>,
+    ) -> Result<CallToolResult, ErrorData> {
+        (self.handler)(arguments).await
+    }
+}
+
+impl fmt::Debug for CustomTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomTool")
+            .field("name", &self.tool.name)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::{Content, RawContent};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn calls_are_answered_by_the_handler() {
+        let tool = CustomTool::new(
+            "echo",
+            "Return the given text",
+            json!({ "type": "object", "properties": { "text": { "type": "string" } } }),
+            |arguments| async move {
+                let text = arguments["text"].as_str().unwrap_or_default().to_string();
+                Ok(CallToolResult::success(vec![Content::text(text)]))
+            },
+        )
+        .unwrap();
+        assert_eq!(tool.name(), "echo");
+        assert_eq!(tool.tool().input_schema["type"], "object");
+
+        let mut arguments = Map::new();
+        arguments.insert("text".to_string(), json!("hello"));
+        let result = tool.call(arguments).await.unwrap();
+        assert!(matches!(
+            &result.content[0].raw,
+            RawContent::Text(text) if text.text == "hello"
+        ));
+
+        let schemaless = CustomTool::new("echo", "", json!(true), |_| async {
+            Ok(CallToolResult::success(vec![]))
+        });
+        assert!(schemaless.is_err());
+    }
+}
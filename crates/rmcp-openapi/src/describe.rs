@@ -0,0 +1,165 @@
+//! Compact tool listings, described on demand.
+//!
+//! The parameter and output schemas of hundreds of operations take more of
+//! the agent's context than the work it is asked to do. With compact
+//! listings, `tools/list` only gives the name, title and a one-line
+//! description of each tool, with an input schema accepting any object, and
+//! the server offers a [`DESCRIBE_TOOL_TOOL`] tool returning the full
+//! definition of a tool, as `tools/list` would otherwise list it:
+//!
+//! ```json
+//! { "name": "addPet" }
+//! ```
+//!
+//! Calls are still validated against the full parameter schema.
+
+use rmcp::model::{CallToolResult, Content, ErrorData, Tool as McpTool};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::sync::Arc;
+
+use crate::error::ToolCallValidationError;
+use crate::search::headline;
+use crate::tool::Tool;
+
+/// Name of the tool describing the other tools
+pub const DESCRIBE_TOOL_TOOL: &str = "describe_tool";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DescribeArguments {
+    name: String,
+}
+
+/// The [`DESCRIBE_TOOL_TOOL`] tool, as listed to clients
+pub(crate) fn describe_tool_tool() -> McpTool {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "name": {
+                "type": "string",
+                "description": "Name of the tool to describe"
+            }
+        },
+        "required": ["name"],
+        "additionalProperties": false
+    });
+    let Value::Object(schema) = schema else {
+        unreachable!("schema is an object");
+    };
+    McpTool::new_with_raw(
+        DESCRIBE_TOOL_TOOL,
+        Some(
+            "Get the full description, parameters and output schema of a tool before calling it"
+                .into(),
+        ),
+        Arc::new(schema),
+    )
+}
+
+/// Compact listing of `tool`
+pub(crate) fn compact(tool: &Tool) -> McpTool {
+    let mut listed = McpTool::from(tool);
+    listed.description = headline(&tool.metadata).map(Into::into);
+    listed.input_schema = Arc::new(Map::from_iter([(
+        "type".to_string(),
+        Value::String("object".to_string()),
+    )]));
+    listed.output_schema = None;
+    listed
+}
+
+/// Answer a call to the [`DESCRIBE_TOOL_TOOL`] tool, describing one of `tools`
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid or name no tool of `tools`
+pub(crate) fn call(
+    tools: &[&Tool],
+    arguments: Option<Map<String, Value>>,
+) -> Result<CallToolResult, ErrorData> {
+    let arguments: DescribeArguments =
+        serde_json::from_value(Value::Object(arguments.unwrap_or_default())).map_err(|e| {
+            ErrorData::invalid_params(format!("Invalid describe arguments: {e}"), None)
+        })?;
+    let Some(tool) = tools
+        .iter()
+        .find(|tool| tool.metadata.name == arguments.name)
+    else {
+        let names: Vec<&str> = tools
+            .iter()
+            .map(|tool| tool.metadata.name.as_str())
+            .collect();
+        return Err(ToolCallValidationError::tool_not_found(arguments.name, &names).into());
+    };
+    let described = serde_json::to_value(McpTool::from(*tool))
+        .map_err(|e| ErrorData::internal_error(format!("Failed to describe tool: {e}"), None))?;
+    let mut result = CallToolResult::success(vec![Content::text(described.to_string())]);
+    result.structured_content = Some(described);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HttpClient;
+    use crate::tool::ToolMetadata;
+    use std::collections::HashMap;
+
+    fn tool() -> Tool {
+        let metadata = ToolMetadata {
+            name: "addPet".to_string(),
+            title: None,
+            description: Some("Add a new pet to the store\n\nLonger explanation".to_string()),
+            parameters: json!({
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"]
+            }),
+            output_schema: Some(json!({ "type": "object" })),
+            method: "post".to_string(),
+            path: "/pet".to_string(),
+            tags: vec!["pet".to_string()],
+            security: None,
+            parameter_mappings: HashMap::new(),
+        };
+        Tool::new(metadata, HttpClient::new()).unwrap()
+    }
+
+    #[test]
+    fn test_compact_listing() {
+        let listed = compact(&tool());
+        assert_eq!(listed.name, "addPet");
+        assert_eq!(
+            listed.description.as_deref(),
+            Some("Add a new pet to the store")
+        );
+        assert_eq!(
+            Value::Object((*listed.input_schema).clone()),
+            json!({ "type": "object" })
+        );
+        assert!(listed.output_schema.is_none());
+    }
+
+    #[test]
+    fn test_describe_tool() {
+        let tool = tool();
+        let tools = [&tool];
+        let arguments = Map::from_iter([("name".to_string(), json!("addPet"))]);
+        let described = call(&tools, Some(arguments))
+            .unwrap()
+            .structured_content
+            .unwrap();
+        assert_eq!(described["inputSchema"]["required"], json!(["name"]));
+        assert!(
+            described["description"]
+                .as_str()
+                .unwrap()
+                .contains("Longer explanation")
+        );
+
+        let arguments = Map::from_iter([("name".to_string(), json!("addPets"))]);
+        let error = call(&tools, Some(arguments)).unwrap_err();
+        assert!(error.message.contains("addPets"));
+    }
+}
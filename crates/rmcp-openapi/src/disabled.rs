@@ -0,0 +1,67 @@
+//! Tools disabled at runtime.
+//!
+//! Operators disable a misbehaving tool without restarting the server, for
+//! instance through the admin API of `rmcp-openapi-server`. Disabled tools
+//! are hidden from `tools/list` and refused like unknown tools. The set is
+//! shared by the clones of a server, and by the [`crate::ToolRegistry`] given
+//! it, and kept by name, so a tool disabled before the spec is reloaded stays
+//! disabled afterwards.
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, RwLock};
+
+/// Names of the disabled tools, shared by the clones of a server
+#[derive(Debug, Clone, Default)]
+pub struct DisabledTools {
+    names: Arc<RwLock<BTreeSet<String>>>,
+}
+
+impl DisabledTools {
+    /// Whether the tool `name` is disabled
+    pub fn contains(&self, name: &str) -> bool {
+        self.names
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(name)
+    }
+
+    /// Disable the tool `name`, or enable it again
+    pub fn set(&self, name: &str, disabled: bool) {
+        let mut names = self.names.write().unwrap_or_else(|e| e.into_inner());
+        if disabled {
+            names.insert(name.to_string());
+        } else {
+            names.remove(name);
+        }
+    }
+
+    /// Names of the disabled tools, sorted
+    pub fn names(&self) -> Vec<String> {
+        self.names
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_tools_are_shared_by_clones() {
+        let disabled = DisabledTools::default();
+        let reloaded = disabled.clone();
+
+        disabled.set("addPet", true);
+        disabled.set("deletePet", true);
+        assert!(reloaded.contains("addPet"));
+        assert_eq!(reloaded.names(), ["addPet", "deletePet"]);
+
+        reloaded.set("addPet", false);
+        assert!(!disabled.contains("addPet"));
+        assert!(!disabled.contains("listPets"));
+    }
+}
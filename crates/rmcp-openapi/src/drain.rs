@@ -0,0 +1,104 @@
+//! Tool calls in progress, for graceful shutdown.
+//!
+//! On shutdown, the application [closes](InFlightCalls::close) the tracker so
+//! new tool calls are refused, then waits for the calls already running to
+//! [finish](InFlightCalls::drained) before stopping, bounding the wait with a
+//! timeout of its choice.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tokio::sync::Notify;
+
+/// Counter of tool calls in progress, shared by the clones of a server
+#[derive(Debug, Clone, Default)]
+pub struct InFlightCalls {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    count: AtomicUsize,
+    closed: AtomicBool,
+    idle: Notify,
+}
+
+/// A tool call in progress, counted until dropped
+#[derive(Debug)]
+pub(crate) struct InFlightCall {
+    inner: Arc<Inner>,
+}
+
+impl InFlightCalls {
+    /// Number of tool calls in progress
+    pub fn count(&self) -> usize {
+        self.inner.count.load(Ordering::SeqCst)
+    }
+
+    /// Whether new tool calls are refused
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::SeqCst)
+    }
+
+    /// Refuse new tool calls
+    pub fn close(&self) {
+        self.inner.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Wait until no tool call is in progress
+    pub async fn drained(&self) {
+        loop {
+            // Registered before checking, so a call ending in between wakes us
+            let idle = self.inner.idle.notified();
+            if self.count() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    /// Count a new tool call, unless closed
+    pub(crate) fn start(&self) -> Option<InFlightCall> {
+        self.inner.count.fetch_add(1, Ordering::SeqCst);
+        let call = InFlightCall {
+            inner: self.inner.clone(),
+        };
+        // Checked after counting, so `drained` cannot miss a call let through
+        (!self.is_closed()).then_some(call)
+    }
+}
+
+impl Drop for InFlightCall {
+    fn drop(&mut self) {
+        if self.inner.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.inner.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_drained_waits_for_calls_in_progress() {
+        let calls = InFlightCalls::default();
+        let call = calls.start().unwrap();
+        assert_eq!(calls.count(), 1);
+
+        calls.close();
+        assert!(calls.start().is_none());
+        assert_eq!(calls.count(), 1);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), calls.drained())
+                .await
+                .is_err()
+        );
+
+        drop(call);
+        tokio::time::timeout(Duration::from_secs(1), calls.drained())
+            .await
+            .unwrap();
+        assert_eq!(calls.count(), 0);
+    }
+}
@@ -0,0 +1,212 @@
+//! Dry runs of tool calls.
+//!
+//! With dry runs enabled, every tool takes an optional boolean
+//! [`DRY_RUN_PARAMETER`]. A call setting it builds the upstream request as
+//! usual, headers, signature and body included, and returns it instead of
+//! sending it, so users and agents can check what a call would send:
+//!
+//! ```json
+//! { "request": { "method": "POST", "url": "https://api.example.com/pets", "headers": { ... }, "body": { "name": "Rex" } } }
+//! ```
+//!
+//! Credentials and headers redacted in logs are `<redacted>`. Dry runs change
+//! nothing upstream, so they need no confirmation.
+
+use reqwest::header;
+use serde_json::{Map, Value, json};
+use std::borrow::Cow;
+
+use crate::confirmation::CONFIRMATION_TOKEN_PARAMETER;
+use crate::redaction::REDACTED;
+use crate::tool::ToolMetadata;
+
+/// Tool parameter asking for the request to be returned instead of sent
+pub const DRY_RUN_PARAMETER: &str = "dry_run";
+
+/// Add [`DRY_RUN_PARAMETER`] to the parameters of the tool, and the
+/// `request` field to its output schema
+///
+/// Operations with a parameter of the same name are left unchanged.
+pub(crate) fn extend_schema(metadata: &mut ToolMetadata) {
+    if metadata.parameter_mappings.contains_key(DRY_RUN_PARAMETER) {
+        return;
+    }
+    let Some(properties) = metadata
+        .parameters
+        .get_mut("properties")
+        .and_then(Value::as_object_mut)
+    else {
+        return;
+    };
+    properties.insert(
+        DRY_RUN_PARAMETER.to_string(),
+        json!({
+            "type": "boolean",
+            "description": "Return the request the call would send, without sending it"
+        }),
+    );
+
+    if let Some(schema) = metadata.output_schema.as_mut() {
+        if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+            properties.insert(
+                "request".to_string(),
+                json!({
+                    "type": "object",
+                    "description": "Request a dry run would have sent, instead of the response"
+                }),
+            );
+        }
+        // Dry runs have no response status
+        if let Some(object) = schema.as_object_mut() {
+            object.remove("required");
+        }
+    }
+}
+
+/// Arguments to execute the call with, without the dry run flag, and whether
+/// the call is a dry run
+///
+/// Dry runs also leave out the confirmation token, which they don't need.
+pub(crate) fn take_flag<'a>(
+    metadata: &ToolMetadata,
+    arguments: &'a Value,
+) -> (Cow<'a, Value>, bool) {
+    let declared = !metadata.parameter_mappings.contains_key(DRY_RUN_PARAMETER)
+        && metadata
+            .parameters
+            .pointer(&format!("/properties/{DRY_RUN_PARAMETER}"))
+            .is_some();
+    if !declared || arguments.get(DRY_RUN_PARAMETER).is_none() {
+        return (Cow::Borrowed(arguments), false);
+    }
+
+    let mut arguments = arguments.clone();
+    let Some(args) = arguments.as_object_mut() else {
+        return (Cow::Owned(arguments), false);
+    };
+    let dry_run = args
+        .remove(DRY_RUN_PARAMETER)
+        .and_then(|flag| flag.as_bool())
+        .unwrap_or(false);
+    if dry_run {
+        args.remove(CONFIRMATION_TOKEN_PARAMETER);
+    }
+    (Cow::Owned(arguments), dry_run)
+}
+
+/// Structured content describing `request`, whose body before compression is
+/// `body`
+pub(crate) fn describe(
+    request: &reqwest::Request,
+    body: &str,
+    redacts_header: impl Fn(&str) -> bool,
+) -> Value {
+    let mut headers = Map::new();
+    for (name, value) in request.headers() {
+        let value = if value.is_sensitive()
+            || name == header::AUTHORIZATION
+            || redacts_header(name.as_str())
+        {
+            REDACTED.to_string()
+        } else {
+            String::from_utf8_lossy(value.as_bytes()).into_owned()
+        };
+        headers.insert(name.to_string(), Value::String(value));
+    }
+
+    let body = request
+        .body()
+        .and_then(reqwest::Body::as_bytes)
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .unwrap_or(body);
+    let body = if body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.to_string()))
+    };
+
+    json!({
+        "request": {
+            "method": request.method().as_str(),
+            "url": request.url().as_str(),
+            "headers": headers,
+            "body": body
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn metadata() -> ToolMetadata {
+        ToolMetadata {
+            name: "addPet".to_string(),
+            title: None,
+            description: None,
+            parameters: json!({"type": "object", "properties": {}}),
+            output_schema: Some(json!({
+                "type": "object",
+                "properties": { "status": { "type": "integer" } },
+                "required": ["status"]
+            })),
+            method: "post".to_string(),
+            path: "/pets".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn flag_is_taken_when_declared() {
+        let arguments = json!({"name": "Rex", "dry_run": true, "confirmation_token": "t"});
+        let (taken, dry_run) = take_flag(&metadata(), &arguments);
+        assert!(!dry_run);
+        assert_eq!(*taken, arguments);
+
+        let mut metadata = metadata();
+        extend_schema(&mut metadata);
+        assert!(
+            metadata
+                .output_schema
+                .as_ref()
+                .unwrap()
+                .get("required")
+                .is_none()
+        );
+        let (taken, dry_run) = take_flag(&metadata, &arguments);
+        assert!(dry_run);
+        assert_eq!(*taken, json!({"name": "Rex"}));
+    }
+
+    #[test]
+    fn request_is_described_with_credentials_redacted() {
+        let request = reqwest::Client::new()
+            .post("https://api.example.com/pets?limit=1")
+            .header(header::AUTHORIZATION, "Bearer s3cret")
+            .header("x-api-key", "k3y")
+            .header("x-team", "pets")
+            .body(r#"{"name":"Rex"}"#)
+            .build()
+            .unwrap();
+
+        let description = describe(&request, "", |name| name == "x-api-key");
+        assert_eq!(
+            description,
+            json!({
+                "request": {
+                    "method": "POST",
+                    "url": "https://api.example.com/pets?limit=1",
+                    "headers": {
+                        "authorization": REDACTED,
+                        "x-api-key": REDACTED,
+                        "x-team": "pets"
+                    },
+                    "body": { "name": "Rex" }
+                }
+            })
+        );
+    }
+}
@@ -109,16 +109,25 @@
 //! - Automatically fix certain errors (e.g., typos in parameter names)
 //! - Retry requests with corrected parameters
 
+use reqwest::header::RETRY_AFTER;
 use rmcp::model::{ErrorCode, ErrorData};
 use schemars::JsonSchema;
 use serde::Serialize;
 use serde_json::{Value, json};
+use std::collections::HashMap;
 use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
+use crate::retry::{RetryHint, parse_retry_after};
+use crate::tool::SecurityRequirement;
+
+/// Rate limit resets above this many seconds are Unix times, not delays
+const RESET_DELAY_MAX_SECONDS: u64 = 365 * 24 * 60 * 60;
+
 /// Find similar strings using Jaro distance algorithm
 /// Used for parameter and tool name suggestions in errors
-fn find_similar_strings(unknown: &str, known_strings: &[&str]) -> Vec<String> {
+pub(crate) fn find_similar_strings(unknown: &str, known_strings: &[&str]) -> Vec<String> {
     use strsim::jaro;
 
     let mut candidates = Vec::new();
@@ -355,6 +364,27 @@ fn format_validation_errors(violations: &[ValidationError]) -> String {
     }
 }
 
+/// Describe security requirements as in `oauth (scopes: pets:read) or api_key`
+fn describe_security(requirements: &[SecurityRequirement]) -> String {
+    requirements
+        .iter()
+        .map(|requirement| {
+            requirement
+                .iter()
+                .map(|(scheme, scopes)| {
+                    if scopes.is_empty() {
+                        scheme.clone()
+                    } else {
+                        format!("{scheme} (scopes: {})", scopes.join(", "))
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" and ")
+        })
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
 /// CLI-specific errors for command-line argument parsing and validation
 #[derive(Debug, Error)]
 pub enum CliError {
@@ -374,6 +404,18 @@ pub enum CliError {
         #[source]
         source: http::header::InvalidHeaderValue,
     },
+
+    #[error("Invalid tool rate limit '{value}': {reason}")]
+    InvalidToolRateLimit { value: String, reason: String },
+
+    #[error("Invalid timeout '{value}': {reason}")]
+    InvalidTimeout { value: String, reason: String },
+
+    #[error("Invalid success status '{value}': {reason}")]
+    InvalidSuccessStatus { value: String, reason: String },
+
+    #[error("Invalid tool alias '{value}': {reason}")]
+    InvalidToolAlias { value: String, reason: String },
 }
 
 #[derive(Debug, Error)]
@@ -404,6 +446,14 @@ pub enum Error {
     Http(String),
     #[error("HTTP request error: {0}")]
     HttpRequest(#[from] reqwest::Error),
+    #[error("TLS configuration error: {0}")]
+    Tls(String),
+    #[error("Request signing error: {0}")]
+    Signing(String),
+    #[error("Authentication error: {0}")]
+    Auth(String),
+    #[error("Secret error: {0}")]
+    Secret(String),
     #[error("JSON error at {path}: {source}")]
     JsonAtPath {
         path: String,
@@ -462,10 +512,11 @@ impl From<ToolCallError> for ErrorData {
         match err {
             ToolCallError::Validation(validation_err) => validation_err.into(),
             ToolCallError::Execution(execution_err) => {
+                let hint = execution_err.retry_hint();
                 // Execution errors should not be converted to ErrorData
                 // They should be returned as CallToolResult with is_error: true
                 // But for backward compatibility, we'll convert them
-                match execution_err {
+                let mut error = match execution_err {
                     ToolCallExecutionError::HttpError {
                         status,
                         ref message,
@@ -489,6 +540,156 @@ impl From<ToolCallError> for ErrorData {
                         }));
                         ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
                     }
+                    ToolCallExecutionError::UpstreamUnavailable {
+                        ref host,
+                        retry_after_seconds,
+                    } => {
+                        let data = Some(json!({
+                            "type": "upstream-unavailable",
+                            "host": host,
+                            "retry_after_seconds": retry_after_seconds
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::RateLimitExceeded {
+                        ref scope,
+                        retry_after_ms,
+                    } => {
+                        let data = Some(json!({
+                            "type": "rate-limit-exceeded",
+                            "scope": scope,
+                            "retry_after_ms": retry_after_ms
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::Throttled { ref scope, limit } => {
+                        let data = Some(json!({
+                            "type": "throttled",
+                            "scope": scope,
+                            "limit": limit
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::AuthenticationFailed {
+                        ref scheme,
+                        ref reason,
+                    } => {
+                        let data = Some(json!({
+                            "type": "authentication-failed",
+                            "scheme": scheme,
+                            "reason": reason
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::AuthenticationRequired {
+                        ref tool,
+                        ref requirements,
+                    } => {
+                        let data = Some(json!({
+                            "type": "authentication-required",
+                            "tool": tool,
+                            "requirements": requirements
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::InsufficientScope {
+                        ref tool,
+                        ref missing,
+                    } => {
+                        let data = Some(json!({
+                            "type": "insufficient-scope",
+                            "tool": tool,
+                            "missing": missing
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::ConfirmationRequired {
+                        ref tool,
+                        ref method,
+                        ref path,
+                        ref confirmation_token,
+                        expires_in_seconds,
+                    } => {
+                        let data = Some(json!({
+                            "type": "confirmation-required",
+                            "tool": tool,
+                            "method": method,
+                            "path": path,
+                            "confirmation_token": confirmation_token,
+                            "expires_in_seconds": expires_in_seconds
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::ReadOnly {
+                        ref tool,
+                        ref method,
+                    } => {
+                        let data = Some(json!({
+                            "type": "read-only",
+                            "tool": tool,
+                            "method": method
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::HostNotAllowed { ref host } => {
+                        let data = Some(json!({
+                            "type": "host-not-allowed",
+                            "host": host
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::ShuttingDown { ref tool } => {
+                        let data = Some(json!({
+                            "type": "shutting-down",
+                            "tool": tool
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::Cancelled { ref tool } => {
+                        let data = Some(json!({
+                            "type": "cancelled",
+                            "tool": tool
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::ServerBusy {
+                        ref reason,
+                        retry_after_secs,
+                    } => {
+                        let data = Some(json!({
+                            "type": "server-busy",
+                            "reason": reason,
+                            "retry_after_secs": retry_after_secs
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::ResponseTruncated {
+                        status,
+                        limit_bytes,
+                        ..
+                    } => {
+                        let data = Some(json!({
+                            "type": "response-truncated",
+                            "status": status,
+                            "limit_bytes": limit_bytes
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
+                    ToolCallExecutionError::RateLimited {
+                        retry_after_seconds,
+                        limit,
+                        remaining,
+                        reset_at,
+                    } => {
+                        let data = Some(json!({
+                            "type": "rate-limited",
+                            "retry_after_seconds": retry_after_seconds,
+                            "limit": limit,
+                            "remaining": remaining,
+                            "reset_at": reset_at
+                        }));
+                        ErrorData::new(ErrorCode(-32000), execution_err.to_string(), data)
+                    }
                     ToolCallExecutionError::ResponseParsingError { ref reason, .. } => {
                         let data = Some(json!({
                             "type": "response-parsing-error",
@@ -496,7 +697,15 @@ impl From<ToolCallError> for ErrorData {
                         }));
                         ErrorData::new(ErrorCode(-32700), execution_err.to_string(), data)
                     }
+                };
+                // Tell agents whether repeating the call may help
+                if let Some(Value::Object(data)) = &mut error.data {
+                    data.insert("retryable".to_string(), json!(hint.retryable));
+                    if let Some(retry_after_ms) = hint.retry_after_ms {
+                        data.insert("retry_after_ms".to_string(), json!(retry_after_ms));
+                    }
                 }
+                error
             }
         }
     }
@@ -609,6 +818,172 @@ pub enum ToolCallExecutionError {
         category: NetworkErrorCategory,
     },
 
+    /// The upstream host failed repeatedly and its circuit breaker is open
+    #[error(
+        "Upstream {host} unavailable after repeated failures, retry in {retry_after_seconds} seconds"
+    )]
+    #[serde(rename = "upstream-unavailable")]
+    UpstreamUnavailable {
+        /// Upstream host (and port) whose circuit is open
+        host: String,
+        /// Seconds until a request to the host will be attempted again
+        retry_after_seconds: u64,
+    },
+
+    /// The client-side rate limit was exhausted and the call was rejected
+    #[error("Rate limit exceeded for {scope}, retry in {retry_after_ms} ms")]
+    #[serde(rename = "rate-limit-exceeded")]
+    RateLimitExceeded {
+        /// Which limit was hit: `global` or the tool name
+        scope: String,
+        /// Milliseconds until the call would be allowed
+        retry_after_ms: u64,
+    },
+
+    /// Too many upstream requests were in flight and the call was rejected
+    #[error("Too many concurrent requests for {scope} (limit {limit}), retry shortly")]
+    #[serde(rename = "throttled")]
+    Throttled {
+        /// Which limit was hit: `global` or `session`
+        scope: String,
+        /// Maximum requests in flight for that scope
+        limit: u64,
+    },
+
+    /// Credentials for the upstream API could not be obtained
+    #[error("Authentication with {scheme} failed: {reason}")]
+    #[serde(rename = "authentication-failed")]
+    AuthenticationFailed {
+        /// Security scheme the credentials are for
+        scheme: String,
+        /// Why authentication failed
+        reason: String,
+    },
+
+    /// The operation requires credentials and none are configured or forwarded
+    #[error(
+        "Tool '{tool}' requires authentication with {}",
+        describe_security(requirements)
+    )]
+    #[serde(rename = "authentication-required")]
+    AuthenticationRequired {
+        /// Name of the tool
+        tool: String,
+        /// Accepted security requirements, any one of which suffices, mapping
+        /// each scheme to the scopes it needs
+        requirements: Vec<SecurityRequirement>,
+    },
+
+    /// The forwarded token lacks scopes the operation requires
+    #[error(
+        "Token lacks scopes required by tool '{tool}': {}",
+        describe_security(std::slice::from_ref(missing))
+    )]
+    #[serde(rename = "insufficient-scope")]
+    InsufficientScope {
+        /// Name of the tool
+        tool: String,
+        /// Scopes missing from the token for the closest requirement, by
+        /// scheme
+        missing: SecurityRequirement,
+    },
+
+    /// The tool changes data and the call must be repeated with the token to
+    /// execute
+    #[error(
+        "Tool '{tool}' ({method} {path}) requires confirmation: call it again with the same arguments and confirmation_token '{confirmation_token}' within {expires_in_seconds} seconds"
+    )]
+    #[serde(rename = "confirmation-required")]
+    ConfirmationRequired {
+        /// Name of the tool
+        tool: String,
+        /// HTTP method of the operation
+        method: String,
+        /// Path of the operation
+        path: String,
+        /// One-time token confirming this call
+        confirmation_token: String,
+        /// Seconds the token stays valid
+        expires_in_seconds: u64,
+    },
+
+    /// The server is in read-only mode and the tool's method may change data
+    #[error("Tool '{tool}' uses {method}, which read-only mode does not allow")]
+    #[serde(rename = "read-only")]
+    ReadOnly {
+        /// Name of the tool
+        tool: String,
+        /// HTTP method of the operation
+        method: String,
+    },
+
+    /// The request URL points to a host outside the allowlist
+    #[error("Requests to host '{host}' are not allowed")]
+    #[serde(rename = "host-not-allowed")]
+    HostNotAllowed {
+        /// Host the request would have been sent to
+        host: String,
+    },
+
+    /// The server is shutting down and no longer starts tool calls
+    #[error("Tool '{tool}' was not called: the server is shutting down")]
+    #[serde(rename = "shutting-down")]
+    ShuttingDown {
+        /// Name of the tool
+        tool: String,
+    },
+
+    /// The client cancelled the tool call, whose upstream request was aborted
+    #[error("Tool '{tool}' call was cancelled by the client")]
+    #[serde(rename = "cancelled")]
+    Cancelled {
+        /// Name of the tool
+        tool: String,
+    },
+
+    /// Too many tool calls were queued or waiting, and the call was refused
+    #[error("Server busy ({reason}), retry after {retry_after_secs}s")]
+    #[serde(rename = "server-busy")]
+    ServerBusy {
+        /// Why the call was refused: `queue-full` or `queue-timeout`
+        reason: String,
+        /// Seconds after which the call can be retried
+        retry_after_secs: u64,
+    },
+
+    /// The response body exceeded the configured size limit and was cut
+    #[error("Response body exceeded {limit_bytes} bytes and was truncated")]
+    #[serde(rename = "response-truncated")]
+    ResponseTruncated {
+        /// HTTP status code of the response
+        status: u16,
+        /// Size limit the body was cut at
+        limit_bytes: usize,
+        /// Beginning of the response body, up to the limit
+        partial_body: String,
+    },
+
+    /// The upstream API rate limited the call with a `429` response
+    #[error(
+        "Upstream API rate limit reached{}",
+        retry_after_seconds.map(|seconds| format!(", retry in {seconds}s")).unwrap_or_default()
+    )]
+    #[serde(rename = "rate-limited")]
+    RateLimited {
+        /// Seconds to wait before retrying, from `Retry-After` or the reset time
+        #[serde(skip_serializing_if = "Option::is_none")]
+        retry_after_seconds: Option<u64>,
+        /// Requests allowed in the rate limit window
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<u64>,
+        /// Requests left in the rate limit window
+        #[serde(skip_serializing_if = "Option::is_none")]
+        remaining: Option<u64>,
+        /// Unix time, in seconds, at which the rate limit window resets
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reset_at: Option<u64>,
+    },
+
     /// Response parsing failed
     #[error("Failed to parse response: {reason}")]
     #[serde(rename = "response-parsing-error")]
@@ -632,6 +1007,86 @@ impl ToolCallValidationError {
     }
 }
 
+impl ToolCallExecutionError {
+    /// Whether repeating the call may succeed, and after how long
+    ///
+    /// Timeouts, connection failures, rate limiting, busy or unavailable
+    /// upstreams and server errors are retryable; client errors, missing
+    /// credentials and refused calls are not.
+    #[must_use]
+    pub fn retry_hint(&self) -> RetryHint {
+        let seconds = |seconds: u64| Some(Duration::from_secs(seconds));
+        match self {
+            Self::HttpError { status, .. } => RetryHint::for_response(*status, &HashMap::new()),
+            Self::NetworkError {
+                category:
+                    NetworkErrorCategory::Timeout
+                    | NetworkErrorCategory::Connect
+                    | NetworkErrorCategory::Body,
+                ..
+            } => RetryHint::after(None),
+            Self::UpstreamUnavailable {
+                retry_after_seconds,
+                ..
+            } => RetryHint::after(seconds(*retry_after_seconds)),
+            Self::RateLimitExceeded { retry_after_ms, .. } => {
+                RetryHint::after(Some(Duration::from_millis(*retry_after_ms)))
+            }
+            Self::ServerBusy {
+                retry_after_secs, ..
+            } => RetryHint::after(seconds(*retry_after_secs)),
+            Self::RateLimited {
+                retry_after_seconds,
+                ..
+            } => RetryHint::after(retry_after_seconds.and_then(seconds)),
+            Self::Throttled { .. } | Self::ShuttingDown { .. } => RetryHint::after(None),
+            Self::NetworkError { .. }
+            | Self::AuthenticationFailed { .. }
+            | Self::AuthenticationRequired { .. }
+            | Self::InsufficientScope { .. }
+            | Self::ConfirmationRequired { .. }
+            | Self::ReadOnly { .. }
+            | Self::HostNotAllowed { .. }
+            | Self::Cancelled { .. }
+            | Self::ResponseTruncated { .. }
+            | Self::ResponseParsingError { .. } => RetryHint::permanent(),
+        }
+    }
+
+    /// A [`RateLimited`](Self::RateLimited) error read from the headers of a
+    /// `429` response: `Retry-After`, and `X-RateLimit-*` or `RateLimit-*`
+    #[must_use]
+    pub fn rate_limited(headers: &HashMap<String, String>) -> Self {
+        let rate_limit = |name: &str| {
+            [format!("x-ratelimit-{name}"), format!("ratelimit-{name}")]
+                .iter()
+                .find_map(|header| headers.get(header)?.trim().parse::<u64>().ok())
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs());
+        // Some APIs give the reset as a Unix time, others as seconds from now
+        let reset_at = rate_limit("reset").map(|reset| {
+            if reset > RESET_DELAY_MAX_SECONDS {
+                reset
+            } else {
+                now + reset
+            }
+        });
+        let retry_after_seconds = headers
+            .get(RETRY_AFTER.as_str())
+            .and_then(|value| parse_retry_after(value))
+            .map(|delay| delay.as_secs() + u64::from(delay.subsec_nanos() > 0))
+            .or_else(|| reset_at.map(|reset_at| reset_at.saturating_sub(now)));
+        Self::RateLimited {
+            retry_after_seconds,
+            limit: rate_limit("limit"),
+            remaining: rate_limit("remaining"),
+            reset_at,
+        }
+    }
+}
+
 impl ValidationError {
     /// Create an InvalidParameter error with suggestions based on valid parameters
     pub fn invalid_parameter(parameter: String, valid_parameters: &[String]) -> Self {
@@ -854,6 +1309,65 @@ mod tests {
         assert_json_snapshot!(serialized);
     }
 
+    #[test]
+    fn test_execution_errors_carry_retry_hints() {
+        let error: ErrorData = ToolCallError::Execution(ToolCallExecutionError::ServerBusy {
+            reason: "queue-full".to_string(),
+            retry_after_secs: 2,
+        })
+        .into();
+        let data = error.data.unwrap();
+        assert_eq!(data["retryable"], true);
+        assert_eq!(data["retry_after_ms"], 2000);
+
+        let error: ErrorData = ToolCallError::Execution(ToolCallExecutionError::HttpError {
+            status: 404,
+            message: "Not Found".to_string(),
+            details: None,
+        })
+        .into();
+        let data = error.data.unwrap();
+        assert_eq!(data["retryable"], false);
+        assert!(data.get("retry_after_ms").is_none());
+    }
+
+    #[test]
+    fn test_rate_limited_from_headers() {
+        let headers = HashMap::from([
+            ("retry-after".to_string(), "30".to_string()),
+            ("x-ratelimit-limit".to_string(), "100".to_string()),
+            ("x-ratelimit-remaining".to_string(), "0".to_string()),
+            ("x-ratelimit-reset".to_string(), "1900000000".to_string()),
+        ]);
+        let error = ToolCallExecutionError::rate_limited(&headers);
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            json!({
+                "type": "rate-limited",
+                "retry_after_seconds": 30,
+                "limit": 100,
+                "remaining": 0,
+                "reset_at": 1_900_000_000u64
+            })
+        );
+        assert_eq!(error.retry_hint().retry_after_ms, Some(30_000));
+
+        // Without `Retry-After`, wait until the window resets
+        let headers = HashMap::from([("ratelimit-reset".to_string(), "12".to_string())]);
+        let ToolCallExecutionError::RateLimited {
+            retry_after_seconds,
+            limit,
+            reset_at,
+            ..
+        } = ToolCallExecutionError::rate_limited(&headers)
+        else {
+            panic!("expected a rate-limited error");
+        };
+        assert!(matches!(retry_after_seconds, Some(11..=12)));
+        assert_eq!(limit, None);
+        assert!(reset_at.is_some());
+    }
+
     #[test]
     fn test_error_response_serialization() {
         let error = ToolCallExecutionError::HttpError {
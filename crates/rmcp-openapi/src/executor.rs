@@ -0,0 +1,98 @@
+//! Pluggable transport for upstream requests.
+//!
+//! [`HttpClient`](crate::HttpClient) builds every upstream request of a tool
+//! call, with parameters, credentials, signature and middlewares applied, and
+//! hands it to its `reqwest` client. An [`HttpExecutor`] replaces that last
+//! step, so embedders can send requests through a service mesh client, answer
+//! them in process or record them in tests. Retries, redirects, caching and
+//! response handling stay with the `HttpClient`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use rmcp_openapi::{HttpExecutor, TransportError};
+//!
+//! /// Executor answering every request in process
+//! struct Fixed;
+//!
+//! #[async_trait]
+//! impl HttpExecutor for Fixed {
+//!     async fn execute(
+//!         &self,
+//!         _request: reqwest::Request,
+//!     ) -> Result<reqwest::Response, TransportError> {
+//!         let response = http::Response::builder()
+//!             .status(200)
+//!             .header("content-type", "application/json")
+//!             .body(r#"{"id":1}"#)
+//!             .map_err(|e| TransportError::Other(e.to_string()))?;
+//!         Ok(reqwest::Response::from(response))
+//!     }
+//! }
+//! ```
+//!
+//! Tokens of OAuth2 client credentials and token exchange are still requested
+//! with the `reqwest` client.
+
+use async_trait::async_trait;
+use reqwest::{Request, Response};
+
+/// Transport sending the upstream requests of tool calls
+#[async_trait]
+pub trait HttpExecutor: Send + Sync {
+    /// Send `request` and return the upstream response, whatever its status
+    async fn execute(&self, request: Request) -> Result<Response, TransportError>;
+}
+
+/// Failure to get a response from the upstream
+#[derive(Debug, thiserror::Error)]
+pub enum TransportError {
+    /// Error of the `reqwest` client
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    /// The upstream could not be reached
+    #[error("connection failed: {0}")]
+    Connect(String),
+    /// The upstream did not answer in time
+    #[error("timed out: {0}")]
+    Timeout(String),
+    /// Any other failure
+    #[error("{0}")]
+    Other(String),
+}
+
+impl TransportError {
+    /// Whether the upstream could not be reached
+    pub fn is_connect(&self) -> bool {
+        match self {
+            TransportError::Reqwest(e) => e.is_connect(),
+            TransportError::Connect(_) => true,
+            TransportError::Timeout(_) | TransportError::Other(_) => false,
+        }
+    }
+
+    /// Whether the upstream did not answer in time
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            TransportError::Reqwest(e) => e.is_timeout(),
+            TransportError::Timeout(_) => true,
+            TransportError::Connect(_) | TransportError::Other(_) => false,
+        }
+    }
+
+    /// Whether sending the request failed
+    pub fn is_request(&self) -> bool {
+        matches!(self, TransportError::Reqwest(e) if e.is_request())
+    }
+
+    /// Whether reading or writing a body failed
+    pub fn is_body(&self) -> bool {
+        matches!(self, TransportError::Reqwest(e) if e.is_body())
+    }
+
+    /// Whether decoding the response failed
+    pub fn is_decode(&self) -> bool {
+        matches!(self, TransportError::Reqwest(e) if e.is_decode())
+    }
+}
@@ -25,6 +25,7 @@
 use async_trait::async_trait;
 use rmcp::service::{RequestContext, RoleServer};
 
+use crate::custom_tool::CustomTool;
 use crate::tool::Tool;
 
 /// Trait for dynamically filtering tools based on request context.
@@ -65,6 +66,18 @@ pub trait ToolFilter: Send + Sync {
     ///
     /// `true` if the tool should be accessible, `false` to hide/block it
     async fn allow(&self, tool: &Tool, context: &RequestContext<RoleServer>) -> bool;
+
+    /// Returns true if the custom tool should be accessible in this context.
+    ///
+    /// Called like [`allow`](Self::allow) for tools registered as
+    /// [`CustomTool`]s. Allows every custom tool by default.
+    async fn allow_custom(
+        &self,
+        _tool: &CustomTool,
+        _context: &RequestContext<RoleServer>,
+    ) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,496 @@
+//! Conversion of HTML responses to text or Markdown.
+//!
+//! Agents get little from the markup of an HTML page, error pages of proxies
+//! included, and it fills their context. With a conversion configured,
+//! `text/html` response bodies are rewritten before they reach the client:
+//!
+//! - `text` keeps the text, with line breaks between blocks and `-` before
+//!   list items
+//! - `markdown` also keeps headings, links, images, emphasis, code and
+//!   quotes as Markdown
+//!
+//! Scripts, styles and the document head are dropped, and entities decoded.
+//! This is a lenient converter for readability, not a full HTML parser.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// What `text/html` response bodies are converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtmlConversion {
+    /// Plain text
+    Text,
+    /// Markdown
+    Markdown,
+}
+
+impl FromStr for HtmlConversion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "markdown" => Ok(Self::Markdown),
+            _ => Err(format!(
+                "Invalid HTML conversion: '{s}'. Valid values: text, markdown"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for HtmlConversion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Text => "text",
+            Self::Markdown => "markdown",
+        })
+    }
+}
+
+/// Elements whose content is not text to show
+const SKIPPED_ELEMENTS: [&str; 7] = [
+    "script", "style", "head", "noscript", "template", "svg", "iframe",
+];
+
+/// Elements separated from their surroundings by a blank line
+const BLOCK_ELEMENTS: [&str; 18] = [
+    "p", "div", "section", "article", "header", "footer", "main", "nav", "aside", "table", "form",
+    "dl", "figure", "ul", "ol", "address", "fieldset", "details",
+];
+
+/// Elements starting on a new line
+const LINE_ELEMENTS: [&str; 5] = ["tr", "dt", "dd", "figcaption", "summary"];
+
+impl HtmlConversion {
+    /// `html` converted
+    #[must_use]
+    pub fn convert(self, html: &str) -> String {
+        let mut writer = Writer::new(self == Self::Markdown);
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            writer.text(&rest[..start]);
+            let after = &rest[start + 1..];
+            if let Some(comment) = after.strip_prefix("!--") {
+                rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+                continue;
+            }
+            let Some(tag) = Tag::parse(after) else {
+                writer.text("<");
+                rest = after;
+                continue;
+            };
+            rest = &after[tag.length..];
+            if !tag.closing && !tag.self_closing && SKIPPED_ELEMENTS.contains(&tag.name.as_str()) {
+                rest = skip_element(rest, &tag.name);
+            } else if tag.closing {
+                writer.close(&tag.name);
+            } else {
+                writer.open(&tag);
+            }
+        }
+        writer.text(rest);
+        writer.finish()
+    }
+}
+
+/// A start or end tag
+struct Tag<'a> {
+    /// Lowercase name, empty for doctypes and processing instructions
+    name: String,
+    attributes: &'a str,
+    closing: bool,
+    self_closing: bool,
+    /// Bytes of the tag after its `<`
+    length: usize,
+}
+
+impl<'a> Tag<'a> {
+    /// Tag at the start of `s`, which follows a `<`
+    fn parse(s: &'a str) -> Option<Self> {
+        let closing = s.starts_with('/');
+        let offset = usize::from(closing);
+        let first = s[offset..].chars().next()?;
+        if !first.is_ascii_alphabetic() && first != '!' && first != '?' {
+            return None;
+        }
+
+        // The tag ends at the first `>` outside quotes
+        let mut quote = None;
+        let end = s.char_indices().find_map(|(index, c)| {
+            match (quote, c) {
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(open), c) if c == open => quote = None,
+                (None, '>') => return Some(index),
+                _ => {}
+            }
+            None
+        })?;
+
+        let inner = &s[offset..end];
+        if !first.is_ascii_alphabetic() {
+            return Some(Self {
+                name: String::new(),
+                attributes: "",
+                closing,
+                self_closing: true,
+                length: end + 1,
+            });
+        }
+        let name_end = inner
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(inner.len());
+        Some(Self {
+            name: inner[..name_end].to_ascii_lowercase(),
+            attributes: &inner[name_end..],
+            closing,
+            self_closing: inner.trim_end().ends_with('/'),
+            length: end + 1,
+        })
+    }
+
+    /// Decoded value of attribute `name`
+    fn attribute(&self, name: &str) -> Option<String> {
+        let mut rest = self.attributes;
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+            if rest.is_empty() {
+                return None;
+            }
+            let key_end = rest
+                .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+                .unwrap_or(rest.len());
+            let key = &rest[..key_end];
+            rest = rest[key_end..].trim_start();
+            let mut value = "";
+            if let Some(after) = rest.strip_prefix('=') {
+                let after = after.trim_start();
+                (value, rest) = match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let quoted = &after[1..];
+                        let end = quoted.find(quote).unwrap_or(quoted.len());
+                        (&quoted[..end], quoted.get(end + 1..).unwrap_or(""))
+                    }
+                    _ => {
+                        let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                        after.split_at(end)
+                    }
+                };
+            }
+            if key.eq_ignore_ascii_case(name) {
+                return Some(decode_entities(value));
+            }
+        }
+    }
+}
+
+/// What follows the end tag of element `name` in `rest`
+fn skip_element<'a>(rest: &'a str, name: &str) -> &'a str {
+    // Lowercasing ASCII keeps byte offsets
+    let lowercase = rest.to_ascii_lowercase();
+    lowercase
+        .find(&format!("</{name}"))
+        .and_then(|start| {
+            let end = lowercase[start..].find('>')?;
+            Some(&rest[start + end + 1..])
+        })
+        .unwrap_or("")
+}
+
+/// Output being written, with the state of the open elements
+struct Writer {
+    out: String,
+    markdown: bool,
+    /// Where the text of each open link starts, and its target
+    links: Vec<(usize, Option<String>)>,
+    /// Open lists, with the number of the last item of ordered ones
+    lists: Vec<Option<usize>>,
+    /// Depth of `pre` elements, whose whitespace is kept
+    preformatted: usize,
+}
+
+impl Writer {
+    fn new(markdown: bool) -> Self {
+        Self {
+            out: String::new(),
+            markdown,
+            links: Vec::new(),
+            lists: Vec::new(),
+            preformatted: 0,
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+        if self.preformatted > 0 {
+            self.out.push_str(&text);
+            return;
+        }
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !self.out.is_empty() && !self.out.ends_with(char::is_whitespace) {
+                    self.out.push(' ');
+                }
+            } else {
+                self.out.push(c);
+            }
+        }
+    }
+
+    /// Output `markup` only when writing Markdown
+    fn markup(&mut self, markup: &str) {
+        if self.markdown {
+            self.out.push_str(markup);
+        }
+    }
+
+    /// Start a new line
+    fn line(&mut self) {
+        self.trim_spaces();
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    /// Leave a blank line
+    fn block(&mut self) {
+        self.line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn trim_spaces(&mut self) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+    }
+
+    fn open(&mut self, tag: &Tag) {
+        let name = tag.name.as_str();
+        match name {
+            "br" => {
+                self.trim_spaces();
+                self.out.push('\n');
+            }
+            "hr" => {
+                self.block();
+                self.markup("---");
+                self.block();
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block();
+                let level = usize::from(name.as_bytes()[1] - b'0');
+                self.markup(&format!("{} ", "#".repeat(level)));
+            }
+            "li" => {
+                self.line();
+                let depth = self.lists.len().saturating_sub(1);
+                self.out.push_str(&"  ".repeat(depth));
+                match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        let bullet = format!("{number}. ");
+                        self.out.push_str(&bullet);
+                    }
+                    _ => self.out.push_str("- "),
+                }
+            }
+            "ul" | "ol" => {
+                if self.lists.is_empty() {
+                    self.block();
+                } else {
+                    self.line();
+                }
+                self.lists.push((name == "ol").then_some(0));
+            }
+            "td" | "th" if !self.out.is_empty() && !self.out.ends_with('\n') => {
+                self.trim_spaces();
+                self.out.push_str(" | ");
+            }
+            "pre" => {
+                self.block();
+                self.markup("```\n");
+                self.preformatted += 1;
+            }
+            "code" if self.preformatted == 0 => self.markup("`"),
+            "strong" | "b" => self.markup("**"),
+            "em" | "i" => self.markup("_"),
+            "blockquote" => {
+                self.block();
+                self.markup("> ");
+            }
+            "a" => self.links.push((self.out.len(), tag.attribute("href"))),
+            "img" => {
+                let alt = tag.attribute("alt").unwrap_or_default();
+                match tag.attribute("src") {
+                    Some(src) if self.markdown => self.out.push_str(&format!("![{alt}]({src})")),
+                    _ => self.out.push_str(&alt),
+                }
+            }
+            name if BLOCK_ELEMENTS.contains(&name) => self.block(),
+            name if LINE_ELEMENTS.contains(&name) => self.line(),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote" => self.block(),
+            "ul" | "ol" => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.block();
+                } else {
+                    self.line();
+                }
+            }
+            "pre" => {
+                if self.markdown {
+                    self.line();
+                    self.out.push_str("```");
+                }
+                self.preformatted = self.preformatted.saturating_sub(1);
+                self.block();
+            }
+            "code" if self.preformatted == 0 => self.markup("`"),
+            "strong" | "b" => self.markup("**"),
+            "em" | "i" => self.markup("_"),
+            "a" => self.close_link(),
+            name if BLOCK_ELEMENTS.contains(&name) => self.block(),
+            name if LINE_ELEMENTS.contains(&name) => self.line(),
+            _ => {}
+        }
+    }
+
+    /// Turn the text of the link being closed into a Markdown link
+    fn close_link(&mut self) {
+        let Some((start, Some(href))) = self.links.pop() else {
+            return;
+        };
+        let linkable = self.markdown
+            && !href.is_empty()
+            && !href.starts_with('#')
+            && !href.starts_with("javascript:");
+        if linkable && start <= self.out.len() && !self.out[start..].trim().is_empty() {
+            self.trim_spaces();
+            self.out.insert(start, '[');
+            self.out.push_str(&format!("]({href})"));
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut text = String::with_capacity(self.out.len());
+        let mut blank_lines = 0;
+        for line in self.out.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                blank_lines += 1;
+                if blank_lines > 1 {
+                    continue;
+                }
+            } else {
+                blank_lines = 0;
+            }
+            text.push_str(line);
+            text.push('\n');
+        }
+        text.trim().to_string()
+    }
+}
+
+/// `text` with its character references decoded
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let entity = after
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((decode_entity(&after[..end])?, end)));
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &after[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = after;
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "copy" => '©',
+        "reg" => '®',
+        "hellip" => '…',
+        "mdash" => '—',
+        "ndash" => '–',
+        "laquo" => '«',
+        "raquo" => '»',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><head><title>Status</title><style>p { color: red; }</style></head>
+<body>
+  <h1>Service  unavailable</h1>
+  <!-- maintenance -->
+  <p>Try again <b>later</b> or read the <a href="https://status.example.com">status page</a> &amp; retry.</p>
+  <script>if (a < b) { alert("x"); }</script>
+  <ul><li>One</li><li>Two</li></ul>
+  <pre>let x = 1;
+let y = 2;</pre>
+</body></html>"#;
+
+    #[test]
+    fn test_markdown_keeps_structure() {
+        assert_eq!(
+            HtmlConversion::Markdown.convert(PAGE),
+            "# Service unavailable\n\n\
+             Try again **later** or read the [status page](https://status.example.com) & retry.\n\n\
+             - One\n- Two\n\n\
+             ```\nlet x = 1;\nlet y = 2;\n```"
+        );
+    }
+
+    #[test]
+    fn test_text_drops_markup() {
+        assert_eq!(
+            HtmlConversion::Text.convert(PAGE),
+            "Service unavailable\n\n\
+             Try again later or read the status page & retry.\n\n\
+             - One\n- Two\n\n\
+             let x = 1;\nlet y = 2;"
+        );
+        assert_eq!(
+            HtmlConversion::Text.convert("1 < 2 &copy; &#x41;"),
+            "1 < 2 © A"
+        );
+    }
+}
@@ -1,15 +1,49 @@
 use base64::prelude::*;
+use oas3::spec::ParameterStyle;
 use reqwest::header::{self, HeaderMap, HeaderValue};
-use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use reqwest::{Certificate, Client, Identity, Method, Proxy, RequestBuilder, StatusCode};
 use serde_json::Value;
-use std::collections::HashMap;
-use std::time::Duration;
-use tracing::{debug, error, info, info_span};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tracing::{debug, error, info, info_span, warn};
 use url::Url;
 
+use crate::audit::{AuditLog, AuditRecord, unix_millis};
+use crate::auth::{BasicAuth, ClientCredentials, ResolvedApiKey, ScopeChecker, TokenExchange};
+use crate::blob::BlobStore;
+use crate::budget::BudgetConfig;
+use crate::cache::{
+    CacheConfig, CacheStatus, CachedResponse, InMemoryCache, ResponseCache, cache_key,
+};
+use crate::cassette::Cassette;
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use crate::concurrency::{ConcurrencyLimiter, ConcurrencyLimits};
+use crate::config::{
+    AuthorizationTemplate, ClientCertificate, CompressionConfig, ConnectionConfig, ContentEncoding,
+    HostAllowlist, IdempotencyConfig, NullValuePolicy, ProxyConfig, RedirectPolicy, StatusPolicy,
+    TimeoutPolicy, load_ca_bundle,
+};
+use crate::confirmation::{ConfirmationConfig, ConfirmationGate};
 use crate::error::{
     Error, NetworkErrorCategory, ToolCallError, ToolCallExecutionError, ToolCallValidationError,
 };
+use crate::executor::{HttpExecutor, TransportError};
+use crate::html::HtmlConversion;
+use crate::logging::ClientLog;
+use crate::middleware::Middleware;
+use crate::mock::mock_response;
+use crate::pagination::PaginationConfig;
+use crate::progress::CallProgress;
+use crate::rate_limit::{RateLimitConfig, RateLimiter};
+use crate::redaction::{REDACTED, RedactionRules};
+use crate::retry::RetryPolicy;
+use crate::security::SecurityEventSink;
+use crate::signing::RequestSigner;
+use crate::table::TableConfig;
 use crate::tool::ToolMetadata;
 use crate::tool_generator::{ExtractedParameters, QueryParameter, ToolGenerator};
 
@@ -107,10 +141,124 @@ pub fn parse_data_uri(value: &str, field_name: &str) -> Result<DataUriContent, E
     })
 }
 
+/// Render a scalar JSON value as its bare string form. Strings are used
+/// verbatim; everything else falls back to its JSON rendering.
+fn scalar_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Percent-encode a query value for an `allowReserved` parameter.
+///
+/// RFC 3986 unreserved and reserved characters are kept as-is, as are
+/// existing `%XX` escapes so pre-encoded values are not double-encoded.
+/// Everything else (spaces, `#`, non-ASCII, ...) is percent-encoded.
+fn encode_allow_reserved(value: &str) -> String {
+    const RESERVED: &[u8] = b":/?[]@!$&'()*+,;=";
+
+    let bytes = value.as_bytes();
+    let mut encoded = String::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let is_escape = byte == b'%'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_hexdigit)
+            && bytes.get(i + 2).is_some_and(u8::is_ascii_hexdigit);
+        if byte.is_ascii_alphanumeric()
+            || b"-._~".contains(&byte)
+            || RESERVED.contains(&byte)
+            || is_escape
+        {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+        i += 1;
+    }
+    encoded
+}
+
+/// Percent-encode every byte that RFC 6265 does not allow in a
+/// `cookie-octet` (controls, whitespace, `"`, `,`, `;`, `\` and non-ASCII).
+fn encode_cookie_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if matches!(byte, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Token of a `Bearer` authorization value, or the whole value for other
+/// schemes
+fn bearer_token(auth_value: &str) -> &str {
+    let auth_value = auth_value.trim();
+    auth_value
+        .split_once(' ')
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map_or(auth_value, |(_, token)| token.trim_start())
+}
+
+/// Whether `method` only reads data, the methods read-only mode allows
+pub(crate) fn is_read_only_method(method: &str) -> bool {
+    method.eq_ignore_ascii_case("get") || method.eq_ignore_ascii_case("head")
+}
+
 /// Default request timeout in seconds applied to every `HttpClient`
 /// constructed without an explicit timeout override.
 const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
 
+/// Settings the underlying `reqwest::Client` is built from
+#[derive(Clone, Default)]
+struct TransportOptions {
+    insecure: bool,
+    proxy: Option<Proxy>,
+    identity: Option<Identity>,
+    root_certificates: Vec<Certificate>,
+    connection: ConnectionConfig,
+    redirect: RedirectPolicy,
+    allowed_hosts: Option<HostAllowlist>,
+    unix_socket: Option<PathBuf>,
+}
+
+/// Response cache settings and the store they apply to
+#[derive(Clone)]
+struct ResponseCaching {
+    config: CacheConfig,
+    store: Arc<dyn ResponseCache>,
+}
+
+/// Split a Unix domain socket base URL into the socket path and the HTTP URL
+/// requests are made against; `None` for any other URL
+fn split_unix_socket_url(url: &Url) -> Result<Option<(PathBuf, Url)>, Error> {
+    let (socket, scheme, path) = match url.scheme() {
+        "unix" => (url.path().to_string(), "http", "/".to_string()),
+        "http+unix" | "https+unix" => {
+            let host = url.host_str().unwrap_or_default();
+            let socket = percent_encoding::percent_decode_str(host)
+                .decode_utf8()
+                .map_err(|e| Error::InvalidUrl(format!("Invalid socket path in '{url}': {e}")))?
+                .into_owned();
+            let scheme = url.scheme().trim_end_matches("+unix");
+            (socket, scheme, url.path().to_string())
+        }
+        _ => return Ok(None),
+    };
+    if socket.is_empty() || socket == "/" {
+        return Err(Error::InvalidUrl(format!("Missing socket path in '{url}'")));
+    }
+    let http_url = Url::parse(&format!("{scheme}://localhost{path}"))
+        .map_err(|e| Error::InvalidUrl(format!("Invalid base URL '{url}': {e}")))?;
+    Ok(Some((PathBuf::from(socket), http_url)))
+}
+
 /// HTTP client for executing `OpenAPI` requests
 #[derive(Clone)]
 pub struct HttpClient {
@@ -118,6 +266,48 @@ pub struct HttpClient {
     base_url: Option<Url>,
     default_headers: HeaderMap,
     timeout_seconds: u64,
+    transport: TransportOptions,
+    strip_non_standard_bodies: bool,
+    null_value_policy: NullValuePolicy,
+    retry_policy: Option<RetryPolicy>,
+    circuit_breaker: Option<CircuitBreaker>,
+    rate_limiter: Option<RateLimiter>,
+    concurrency: Option<ConcurrencyLimiter>,
+    timeout_policy: TimeoutPolicy,
+    status_policy: StatusPolicy,
+    cache: Option<ResponseCaching>,
+    compression: Option<CompressionConfig>,
+    max_response_bytes: Option<usize>,
+    signer: Option<Arc<dyn RequestSigner>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    executor: Option<Arc<dyn HttpExecutor>>,
+    idempotency: Option<IdempotencyConfig>,
+    audit_log: Option<Arc<AuditLog>>,
+    security_event_sinks: Vec<Arc<dyn SecurityEventSink>>,
+    session_id: Option<String>,
+    redaction: Option<RedactionRules>,
+    cassette: Option<Arc<Cassette>>,
+    mock_responses: bool,
+    /// Whether requests are described instead of sent
+    dry_run: bool,
+    client_credentials: Option<Arc<ClientCredentials>>,
+    api_keys: Vec<ResolvedApiKey>,
+    /// Headers of the spec's `apiKey` schemes, credentials when set by default
+    api_key_headers: Vec<header::HeaderName>,
+    basic_auth: Option<BasicAuth>,
+    authorization_templates: Vec<AuthorizationTemplate>,
+    token_exchange: Option<Arc<TokenExchange>>,
+    scope_checker: Option<Arc<ScopeChecker>>,
+    confirmation: Option<ConfirmationGate>,
+    read_only: bool,
+    progress: Option<CallProgress>,
+    client_log: Option<ClientLog>,
+    response_headers: bool,
+    pagination: Option<PaginationConfig>,
+    markdown_tables: Option<TableConfig>,
+    html_conversion: Option<HtmlConversion>,
+    blob_store: Option<BlobStore>,
+    response_budget: Option<BudgetConfig>,
 }
 
 impl HttpClient {
@@ -127,23 +317,77 @@ impl HttpClient {
     }
 
     /// Build the underlying `reqwest::Client` with the given timeout and
-    /// optional bypass of TLS certificate verification.
+    /// transport options (TLS verification bypass, extra root certificates,
+    /// proxy, client certificate, connection pool settings, DNS overrides,
+    /// redirect policy, Unix domain socket).
     ///
     /// # Panics
     ///
     /// Panics if the HTTP client cannot be created.
-    fn build_reqwest_client(timeout_seconds: u64, insecure: bool) -> Client {
+    fn build_reqwest_client(timeout_seconds: u64, transport: &TransportOptions) -> Client {
         let user_agent = Self::create_user_agent();
         let mut builder = Client::builder()
             .user_agent(&user_agent)
-            .timeout(Duration::from_secs(timeout_seconds));
+            .timeout(Duration::from_secs(timeout_seconds))
+            .redirect(
+                transport
+                    .redirect
+                    .to_reqwest(transport.allowed_hosts.as_ref()),
+            );
 
-        if insecure {
+        if transport.insecure {
             builder = builder
                 .danger_accept_invalid_certs(true)
                 .danger_accept_invalid_hostnames(true);
         }
 
+        if let Some(proxy) = &transport.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+
+        if let Some(identity) = &transport.identity {
+            builder = builder.identity(identity.clone());
+        }
+
+        for certificate in &transport.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+
+        #[cfg(unix)]
+        if let Some(path) = &transport.unix_socket {
+            builder = builder.unix_socket(path.clone());
+        }
+
+        let connection = &transport.connection;
+        if let Some(max) = connection.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = connection.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(timeout) = connection.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(interval) = connection.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+
+        // Overrides for the same host are combined so each one is tried
+        let mut resolved: Vec<(&str, Vec<SocketAddr>)> = Vec::new();
+        for host_override in &connection.resolve {
+            let addr = host_override.socket_addr();
+            match resolved
+                .iter_mut()
+                .find(|(host, _)| *host == host_override.host)
+            {
+                Some((_, addrs)) => addrs.push(addr),
+                None => resolved.push((host_override.host.as_str(), vec![addr])),
+            }
+        }
+        for (host, addrs) in &resolved {
+            builder = builder.resolve_to_addrs(host, addrs);
+        }
+
         builder.build().expect("Failed to create HTTP client")
     }
 
@@ -155,10 +399,53 @@ impl HttpClient {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            client: Self::build_reqwest_client(DEFAULT_TIMEOUT_SECONDS, false),
+            client: Self::build_reqwest_client(
+                DEFAULT_TIMEOUT_SECONDS,
+                &TransportOptions::default(),
+            ),
             base_url: None,
             default_headers: HeaderMap::new(),
             timeout_seconds: DEFAULT_TIMEOUT_SECONDS,
+            transport: TransportOptions::default(),
+            strip_non_standard_bodies: false,
+            null_value_policy: NullValuePolicy::default(),
+            retry_policy: None,
+            circuit_breaker: None,
+            rate_limiter: None,
+            concurrency: None,
+            timeout_policy: TimeoutPolicy::default(),
+            status_policy: StatusPolicy::default(),
+            cache: None,
+            compression: None,
+            max_response_bytes: None,
+            signer: None,
+            middlewares: Vec::new(),
+            executor: None,
+            idempotency: None,
+            audit_log: None,
+            security_event_sinks: Vec::new(),
+            session_id: None,
+            redaction: None,
+            cassette: None,
+            mock_responses: false,
+            dry_run: false,
+            client_credentials: None,
+            api_keys: Vec::new(),
+            api_key_headers: Vec::new(),
+            basic_auth: None,
+            authorization_templates: Vec::new(),
+            token_exchange: None,
+            scope_checker: None,
+            confirmation: None,
+            read_only: false,
+            progress: None,
+            client_log: None,
+            response_headers: false,
+            pagination: None,
+            markdown_tables: None,
+            html_conversion: None,
+            blob_store: None,
+            response_budget: None,
         }
     }
 
@@ -170,10 +457,9 @@ impl HttpClient {
     #[must_use]
     pub fn with_timeout(timeout_seconds: u64) -> Self {
         Self {
-            client: Self::build_reqwest_client(timeout_seconds, false),
-            base_url: None,
-            default_headers: HeaderMap::new(),
+            client: Self::build_reqwest_client(timeout_seconds, &TransportOptions::default()),
             timeout_seconds,
+            ..Self::new()
         }
     }
 
@@ -198,61 +484,974 @@ impl HttpClient {
     #[must_use]
     pub fn with_insecure(mut self, insecure: bool) -> Self {
         if insecure {
-            self.client = Self::build_reqwest_client(self.timeout_seconds, true);
+            self.transport.insecure = true;
+            self.client = Self::build_reqwest_client(self.timeout_seconds, &self.transport);
         }
         self
     }
 
+    /// Tune the connection pool, connect timeout, TCP keepalive and DNS
+    /// overrides
+    ///
+    /// Rebuilds the underlying `reqwest::Client`, preserving the timeout,
+    /// proxy and TLS settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    pub fn with_connection_config(mut self, connection: ConnectionConfig) -> Self {
+        self.transport.connection = connection;
+        self.client = Self::build_reqwest_client(self.timeout_seconds, &self.transport);
+        self
+    }
+
+    /// Trust the root certificates in a PEM bundle in addition to the
+    /// built-in ones, for upstream APIs served by a private PKI
+    ///
+    /// Rebuilds the underlying `reqwest::Client`, preserving the timeout,
+    /// proxy and TLS settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be read or contains no certificate
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    pub fn with_ca_bundle(mut self, path: &Path) -> Result<Self, Error> {
+        self.transport
+            .root_certificates
+            .extend(load_ca_bundle(path)?);
+        self.client = Self::build_reqwest_client(self.timeout_seconds, &self.transport);
+        Ok(self)
+    }
+
+    /// Send upstream requests through an HTTP(S) proxy
+    ///
+    /// Rebuilds the underlying `reqwest::Client`, preserving the timeout and
+    /// TLS settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proxy URL cannot be used as a proxy
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    pub fn with_proxy(mut self, proxy: &ProxyConfig) -> Result<Self, Error> {
+        let proxy = proxy
+            .to_reqwest()
+            .map_err(|e| Error::InvalidUrl(format!("Invalid proxy '{}': {e}", proxy.url)))?;
+        self.transport.proxy = Some(proxy);
+        self.client = Self::build_reqwest_client(self.timeout_seconds, &self.transport);
+        Ok(self)
+    }
+
+    /// Present a client certificate to upstream APIs that require mutual TLS
+    ///
+    /// Rebuilds the underlying `reqwest::Client`, preserving the timeout,
+    /// proxy and TLS settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the certificate or key cannot be read or parsed
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    pub fn with_client_certificate(
+        mut self,
+        certificate: &ClientCertificate,
+    ) -> Result<Self, Error> {
+        self.transport.identity = Some(certificate.to_identity()?);
+        self.client = Self::build_reqwest_client(self.timeout_seconds, &self.transport);
+        Ok(self)
+    }
+
     /// Set the base URL for all requests
     ///
+    /// Besides `http` and `https` URLs, Unix domain socket targets are
+    /// accepted, e.g. for the Docker Engine API:
+    ///
+    /// - `unix:///var/run/docker.sock` sends requests over the socket at that path
+    /// - `http+unix://%2Fvar%2Frun%2Fdocker.sock/v1.43` names the socket in the
+    ///   percent-encoded host and keeps `/v1.43` as the base path
+    ///   (`https+unix://` uses TLS over the socket)
+    ///
     /// # Errors
     ///
-    /// Returns an error if the base URL is invalid
+    /// Returns an error if the base URL is invalid, or names a Unix domain
+    /// socket on a platform without them
     pub fn with_base_url(mut self, base_url: Url) -> Result<Self, Error> {
-        // Always terminate the path of the base_url with '/'
         let mut base_url = base_url;
+        if let Some((socket, http_url)) = split_unix_socket_url(&base_url)? {
+            self = self.with_unix_socket(socket)?;
+            base_url = http_url;
+        }
+
+        // Always terminate the path of the base_url with '/'
         if !base_url.path().ends_with('/') {
             base_url.set_path(&format!("{}/", base_url.path()));
         }
+        if let Some(allowlist) = self.transport.allowed_hosts.take() {
+            self = self.with_host_allowlist(allowlist.with_hosts(base_url.host_str()));
+        }
         self.base_url = Some(base_url);
         Ok(self)
     }
 
-    /// Set default headers for all requests
+    /// Send every request over a Unix domain socket instead of TCP
+    ///
+    /// The host in request URLs is then only used for the `Host` header.
+    /// Proxy and TCP connection settings do not apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on platforms without Unix domain sockets
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    pub fn with_unix_socket(mut self, path: impl Into<PathBuf>) -> Result<Self, Error> {
+        if !cfg!(unix) {
+            return Err(Error::InvalidUrl(
+                "Unix domain sockets are not supported on this platform".to_string(),
+            ));
+        }
+        self.transport.unix_socket = Some(path.into());
+        self.client = Self::build_reqwest_client(self.timeout_seconds, &self.transport);
+        Ok(self)
+    }
+
+    /// Set default headers for all requests
+    ///
+    /// Values may hold placeholders rendered for each request: `{uuid}`, a new
+    /// UUID, `{session_id}`, the MCP session of the call, and `{tool}`, the name
+    /// of the tool called. Headers naming the session are left out of calls
+    /// made outside any.
+    #[must_use]
+    pub fn with_default_headers(mut self, default_headers: HeaderMap) -> Self {
+        self.default_headers = default_headers;
+        self
+    }
+
+    /// Drop request bodies on methods where HTTP gives them no defined
+    /// semantics (`GET`, `HEAD`, `DELETE`, `OPTIONS`, `TRACE`).
+    ///
+    /// Bodies are sent for every method the spec declares one on by default
+    /// (e.g. Elasticsearch's `GET /_search`). Enable this when a strict proxy
+    /// or gateway in front of the API rejects such requests.
+    #[must_use]
+    pub fn with_strip_non_standard_bodies(mut self, strip: bool) -> Self {
+        self.strip_non_standard_bodies = strip;
+        self
+    }
+
+    /// Set how `null` query parameter values are encoded
+    #[must_use]
+    pub fn with_null_value_policy(mut self, policy: NullValuePolicy) -> Self {
+        self.null_value_policy = policy;
+        self
+    }
+
+    /// Retry failed requests according to `policy`
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Fail fast for upstream hosts that keep failing
+    ///
+    /// Circuit state is shared by all clones of this client, so every tool built
+    /// from it sees the same circuits.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(CircuitBreaker::new(config));
+        self
+    }
+
+    /// Throttle calls to stay within upstream rate limits
+    ///
+    /// Buckets are shared by all clones of this client.
+    #[must_use]
+    pub fn with_rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limiter = Some(RateLimiter::new(config));
+        self
+    }
+
+    /// Reject calls while too many upstream requests are in flight
+    ///
+    /// Counters are shared by all clones of this client.
+    #[must_use]
+    pub fn with_concurrency_limits(mut self, limits: ConcurrencyLimits) -> Self {
+        self.concurrency = Some(ConcurrencyLimiter::new(limits));
+        self
+    }
+
+    /// Cap request timeouts per tool or tag, whatever `timeout_seconds` callers pass
+    #[must_use]
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeout_policy = policy;
+        self
+    }
+
+    /// Return responses with statuses outside `2xx` as successful results,
+    /// for every tool or per tool
+    #[must_use]
+    pub fn with_status_policy(mut self, policy: StatusPolicy) -> Self {
+        self.status_policy = policy;
+        self
+    }
+
+    /// Cache successful `GET` responses in a bounded in-memory store
+    #[must_use]
+    pub fn with_response_cache(self, config: CacheConfig) -> Self {
+        let store = Arc::new(InMemoryCache::new(config.max_entries));
+        self.with_response_cache_store(config, store)
+    }
+
+    /// Cache successful `GET` responses in a custom store
+    ///
+    /// The store is shared by all clones of this client.
+    #[must_use]
+    pub fn with_response_cache_store(
+        mut self,
+        config: CacheConfig,
+        store: Arc<dyn ResponseCache>,
+    ) -> Self {
+        self.cache = Some(ResponseCaching { config, store });
+        self
+    }
+
+    /// Compress request bodies and accept compressed responses
+    ///
+    /// See [`CompressionConfig`] for which bodies are compressed.
+    #[must_use]
+    pub fn with_compression(mut self, config: CompressionConfig) -> Self {
+        self.compression = Some(config);
+        self
+    }
+
+    /// Set how redirects are followed and whether credentials survive them
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    pub fn with_redirect_policy(mut self, policy: RedirectPolicy) -> Self {
+        self.transport.redirect = policy;
+        self.client = Self::build_reqwest_client(self.timeout_seconds, &self.transport);
+        self
+    }
+
+    /// Only send requests to, and follow redirects to, hosts `allowlist`
+    /// allows
+    ///
+    /// The base URL's host is always allowed. Redirects elsewhere are not
+    /// followed; the 3xx response is returned as is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    pub fn with_host_allowlist(mut self, allowlist: HostAllowlist) -> Self {
+        let base_host = self.base_url.as_ref().and_then(Url::host_str);
+        self.transport.allowed_hosts = Some(allowlist.with_hosts(base_host));
+        self.client = Self::build_reqwest_client(self.timeout_seconds, &self.transport);
+        self
+    }
+
+    /// Whether requests to `url` are allowed by the host allowlist, if any
+    pub(crate) fn allows_url(&self, url: &Url) -> bool {
+        self.transport
+            .allowed_hosts
+            .as_ref()
+            .is_none_or(|allowlist| allowlist.allows_url(url))
+    }
+
+    /// Sign every outgoing request, e.g. with an [`HmacSigner`](crate::signing::HmacSigner)
+    #[must_use]
+    pub fn with_request_signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Send upstream requests with `executor` instead of the `reqwest` client
+    #[must_use]
+    pub fn with_executor(mut self, executor: Arc<dyn HttpExecutor>) -> Self {
+        self.executor = Some(executor);
+        self
+    }
+
+    /// Add a [`Middleware`] run after those already added
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Attach an idempotency key to calls of the configured methods
+    #[must_use]
+    pub fn with_idempotency_keys(mut self, config: IdempotencyConfig) -> Self {
+        self.idempotency = Some(config);
+        self
+    }
+
+    /// Record every upstream request in `audit_log`
+    #[must_use]
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Send security events of tool calls made with this client to `sinks`
+    #[must_use]
+    pub fn with_security_event_sinks(mut self, sinks: Vec<Arc<dyn SecurityEventSink>>) -> Self {
+        self.security_event_sinks = sinks;
+        self
+    }
+
+    /// Sinks security events of tool calls made with this client go to
+    pub(crate) fn security_event_sinks(&self) -> &[Arc<dyn SecurityEventSink>] {
+        &self.security_event_sinks
+    }
+
+    /// Attribute upstream requests to an MCP session in the audit log
+    #[must_use]
+    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Hide values matching `rules` in error results and logs
+    #[must_use]
+    pub fn with_redaction(mut self, rules: RedactionRules) -> Self {
+        self.redaction = Some(rules);
+        self
+    }
+
+    /// Record upstream responses to, or replay them from, `cassette`
+    #[must_use]
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Answer tool calls with responses generated from the output schemas
+    /// instead of contacting the upstream
+    #[must_use]
+    pub fn with_mock_responses(mut self, enabled: bool) -> Self {
+        self.mock_responses = enabled;
+        self
+    }
+
+    /// Refuse every tool call whose method is not `GET` or `HEAD`, whatever
+    /// tools were registered
+    #[must_use]
+    pub fn with_read_only(mut self, enabled: bool) -> Self {
+        self.read_only = enabled;
+        self
+    }
+
+    /// Record the retries and response bytes of tool calls in `progress`
+    #[must_use]
+    pub fn with_progress(mut self, progress: CallProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Report the upstream's `5xx` responses to the clients subscribed to `log`
+    #[must_use]
+    pub fn with_client_log(mut self, log: ClientLog) -> Self {
+        self.client_log = Some(log);
+        self
+    }
+
+    pub(crate) fn client_log(&self) -> Option<&ClientLog> {
+        self.client_log.as_ref()
+    }
+
+    /// Authenticate upstream requests with OAuth2 access tokens from `credentials`
+    ///
+    /// Requests already carrying an `Authorization` header keep it.
+    #[must_use]
+    pub fn with_client_credentials(mut self, credentials: Arc<ClientCredentials>) -> Self {
+        self.client_credentials = Some(credentials);
+        self
+    }
+
+    /// Send `api_key` with every upstream request, where its security scheme says
+    ///
+    /// Requests already carrying that header, query parameter or cookie keep it.
+    #[must_use]
+    pub fn with_api_key(mut self, api_key: ResolvedApiKey) -> Self {
+        self.api_keys.push(api_key);
+        self
+    }
+
+    /// Count default headers named `names`, those of the spec's `apiKey`
+    /// schemes, as credentials
+    #[must_use]
+    pub fn with_api_key_headers(mut self, names: Vec<header::HeaderName>) -> Self {
+        self.api_key_headers = names;
+        self
+    }
+
+    /// Authenticate upstream requests with HTTP Basic `credentials`
+    ///
+    /// Requests already carrying an `Authorization` header keep it.
+    #[must_use]
+    pub fn with_basic_auth(mut self, credentials: BasicAuth) -> Self {
+        self.basic_auth = Some(credentials);
+        self
+    }
+
+    /// Whether the value of header `name` must not be shown
+    fn redacts_header(&self, name: &str) -> bool {
+        self.redaction
+            .as_ref()
+            .is_some_and(|rules| rules.redacts_header(name))
+    }
+
+    /// Add the response headers to the structured content of tool results
+    #[must_use]
+    pub fn with_response_headers(mut self, enabled: bool) -> Self {
+        self.response_headers = enabled;
+        self
+    }
+
+    /// Headers of `response` for the structured content of a tool result,
+    /// redacted, when enabled
+    pub(crate) fn structured_headers(
+        &self,
+        response: &HttpResponse,
+    ) -> Option<BTreeMap<String, String>> {
+        self.response_headers.then(|| {
+            response
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = if self.redacts_header(name) {
+                        REDACTED.to_string()
+                    } else {
+                        value.clone()
+                    };
+                    (name.clone(), value)
+                })
+                .collect()
+        })
+    }
+
+    /// Follow the next pages of `GET` listings and merge their items, as
+    /// described in [`crate::pagination`]
+    #[must_use]
+    pub fn with_pagination(mut self, config: PaginationConfig) -> Self {
+        self.pagination = Some(config);
+        self
+    }
+
+    pub(crate) fn pagination(&self) -> Option<&PaginationConfig> {
+        self.pagination.as_ref()
+    }
+
+    /// Render tabular response bodies as Markdown tables in the text content
+    /// of tool results, as described in [`crate::table`]
+    #[must_use]
+    pub fn with_markdown_tables(mut self, config: TableConfig) -> Self {
+        self.markdown_tables = Some(config);
+        self
+    }
+
+    pub(crate) fn markdown_tables(&self) -> Option<&TableConfig> {
+        self.markdown_tables.as_ref()
+    }
+
+    /// Convert `text/html` response bodies to text or Markdown, as described
+    /// in [`crate::html`]
+    #[must_use]
+    pub fn with_html_conversion(mut self, conversion: HtmlConversion) -> Self {
+        self.html_conversion = Some(conversion);
+        self
+    }
+
+    /// Keep binary response bodies other than images in `store`, returning
+    /// resource links to them, as described in [`crate::blob`]
+    #[must_use]
+    pub fn with_blob_store(mut self, store: BlobStore) -> Self {
+        self.blob_store = Some(store);
+        self
+    }
+
+    pub(crate) fn blob_store(&self) -> Option<&BlobStore> {
+        self.blob_store.as_ref()
+    }
+
+    /// Cut JSON bodies larger than the budget returned by tool calls,
+    /// summarizing what was cut, as described in [`crate::budget`]
+    #[must_use]
+    pub fn with_response_budget(mut self, config: BudgetConfig) -> Self {
+        self.response_budget = Some(config);
+        self
+    }
+
+    pub(crate) fn response_budget(&self) -> Option<&BudgetConfig> {
+        self.response_budget.as_ref()
+    }
+
+    /// Stop reading response bodies after `max_bytes`
+    ///
+    /// Larger bodies are streamed up to the limit and the rest is discarded;
+    /// [`HttpResponse::truncated_at`] reports the cut. Compressed bodies are
+    /// limited after decoding.
+    #[must_use]
+    pub fn with_max_response_size(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Key identifying the upstream host for circuit breaking
+    fn circuit_key(url: &Url) -> String {
+        format!(
+            "{}:{}",
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or_default()
+        )
+    }
+
+    /// Send the request, retrying per the configured [`RetryPolicy`]
+    ///
+    /// Requests whose body cannot be cloned (streams) are sent exactly once.
+    async fn send_with_retries(
+        &self,
+        request: RequestBuilder,
+        method: &str,
+        has_idempotency_key: bool,
+    ) -> Result<reqwest::Response, TransportError> {
+        let Some(policy) = self.retry_policy.as_ref().filter(|policy| {
+            policy.max_retries > 0 && (has_idempotency_key || policy.allows_method(method))
+        }) else {
+            return self.execute_request(request).await;
+        };
+
+        let mut retry = 0;
+        loop {
+            let Some(attempt) = request.try_clone() else {
+                return self.execute_request(request).await;
+            };
+            let result = self.execute_request(attempt).await;
+
+            retry += 1;
+            let delay = match &result {
+                Ok(response) => {
+                    policy.delay_for_response(response.status(), response.headers(), retry)
+                }
+                Err(e) => policy.delay_for_error(e, retry),
+            };
+            let Some(delay) = delay else {
+                return result;
+            };
+
+            warn!(
+                method = %method,
+                retry,
+                max_retries = policy.max_retries,
+                delay_ms = delay.as_millis(),
+                outcome = %match &result {
+                    Ok(response) => response.status().to_string(),
+                    Err(e) => e.to_string(),
+                },
+                "Retrying upstream request"
+            );
+            if let Some(progress) = &self.progress {
+                progress.record_retry();
+            }
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Send a `HEAD` request to the base URL the way tool calls are sent,
+    /// waiting at most `timeout`, and return the status it was answered with
+    ///
+    /// Used to check the upstream API can be reached: the proxy, client
+    /// certificate, root certificates, host overrides, Unix socket and
+    /// executor configured for tool calls apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no base URL is configured or the upstream API
+    /// does not answer
+    pub async fn probe_base_url(&self, timeout: Duration) -> Result<StatusCode, Error> {
+        let base_url = self
+            .base_url
+            .as_ref()
+            .ok_or_else(|| Error::InvalidUrl("No base URL to probe".to_string()))?;
+        let request = self.client.head(base_url.clone()).timeout(timeout);
+        let response = self
+            .execute_request(request)
+            .await
+            .map_err(|e| Error::Http(e.to_string()))?;
+        Ok(response.status())
+    }
+
+    /// Send `request` with the executor, if any, or the `reqwest` client
+    async fn execute_request(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<reqwest::Response, TransportError> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        match &self.executor {
+            Some(executor) => executor.execute(request).await,
+            None => Ok(client.execute(request).await?),
+        }
+    }
+
+    /// Send a request, following redirects manually when credentials must survive them
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        method: &str,
+        has_idempotency_key: bool,
+    ) -> Result<reqwest::Response, TransportError> {
+        let redirect_request = self
+            .transport
+            .redirect
+            .follows_manually()
+            .then(|| request.try_clone()?.build().ok())
+            .flatten();
+        match (
+            self.send_with_retries(request, method, has_idempotency_key)
+                .await,
+            redirect_request,
+        ) {
+            (Ok(response), Some(original)) => self.follow_redirects(response, original).await,
+            (result, _) => result,
+        }
+    }
+
+    /// Apply the request signer, if any
+    fn sign(&self, request: RequestBuilder) -> Result<RequestBuilder, ToolCallError> {
+        let Some(signer) = &self.signer else {
+            return Ok(request);
+        };
+        let construction_error = |reason: String| {
+            ToolCallError::Validation(ToolCallValidationError::RequestConstructionError { reason })
+        };
+        let mut built = request
+            .build()
+            .map_err(|e| construction_error(e.to_string()))?;
+        signer
+            .sign(&mut built)
+            .map_err(|e| construction_error(e.to_string()))?;
+        Ok(RequestBuilder::from_parts(self.client.clone(), built))
+    }
+
+    /// Follow redirects by hand, keeping every request header
+    ///
+    /// Used instead of `reqwest`'s redirect handling when credentials must be
+    /// forwarded across origins. `301`, `302` and `303` switch to a bodiless
+    /// `GET` like browsers do; `307` and `308` replay the original request.
+    /// Once the limit is reached the last 3xx response is returned.
+    async fn follow_redirects(
+        &self,
+        mut response: reqwest::Response,
+        mut request: reqwest::Request,
+    ) -> Result<reqwest::Response, TransportError> {
+        for _ in 0..self.transport.redirect.mode.max_redirects() {
+            let status = response.status();
+            if !status.is_redirection() {
+                break;
+            }
+            let Some(location) = response
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|location| response.url().join(location).ok())
+            else {
+                break;
+            };
+            if let Some(allowlist) = &self.transport.allowed_hosts
+                && !allowlist.allows_url(&location)
+            {
+                warn!(location = %location, "Not following redirect to a host outside the allowlist");
+                break;
+            }
+
+            let mut next = reqwest::Request::new(request.method().clone(), location);
+            *next.headers_mut() = request.headers().clone();
+            *next.timeout_mut() = request.timeout().copied();
+            if matches!(
+                status,
+                StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT
+            ) {
+                *next.body_mut() = request
+                    .body()
+                    .and_then(reqwest::Body::as_bytes)
+                    .map(|bytes| reqwest::Body::from(bytes.to_vec()));
+            } else {
+                if next.method() != Method::HEAD {
+                    *next.method_mut() = Method::GET;
+                }
+                for name in [
+                    header::CONTENT_TYPE,
+                    header::CONTENT_LENGTH,
+                    header::CONTENT_ENCODING,
+                ] {
+                    next.headers_mut().remove(name);
+                }
+            }
+
+            debug!(
+                status = status.as_u16(),
+                location = %next.url(),
+                "Following redirect with credentials"
+            );
+            let Some(follow_up) = next.try_clone() else {
+                return self
+                    .execute_request(RequestBuilder::from_parts(self.client.clone(), next))
+                    .await;
+            };
+            response = self
+                .execute_request(RequestBuilder::from_parts(self.client.clone(), next))
+                .await?;
+            request = follow_up;
+        }
+        Ok(response)
+    }
+
+    /// Apply the configured [`NullValuePolicy`] to `null` query parameters
+    fn apply_null_value_policy(&self, query_params: &mut HashMap<String, QueryParameter>) {
+        match self.null_value_policy {
+            NullValuePolicy::Omit => query_params.retain(|_, param| !param.value.is_null()),
+            NullValuePolicy::Empty => {
+                for param in query_params.values_mut() {
+                    if param.value.is_null() {
+                        param.value = Value::String(String::new());
+                    }
+                }
+            }
+            // `null` already renders as the literal string
+            NullValuePolicy::Literal => {}
+        }
+    }
+
+    /// Whether a request body for `method` should be dropped before sending
+    fn should_strip_body(&self, method: &str) -> bool {
+        self.strip_non_standard_bodies
+            && matches!(
+                method.to_uppercase().as_str(),
+                "GET" | "HEAD" | "DELETE" | "OPTIONS" | "TRACE"
+            )
+    }
+
+    /// Create a new HTTP client with authorization header
+    ///
+    /// Clones the current client and adds the Authorization header to default headers.
+    /// This allows passing authorization through to backend APIs.
+    #[must_use]
+    pub fn with_authorization(&self, auth_value: &str) -> Self {
+        let mut headers = self.default_headers.clone();
+        if let Ok(mut header_value) = HeaderValue::from_str(auth_value) {
+            header_value.set_sensitive(true);
+            headers.insert(header::AUTHORIZATION, header_value);
+        }
+
+        Self {
+            default_headers: headers,
+            ..self.clone()
+        }
+    }
+
+    /// Translate forwarded client authorization with `templates` instead of
+    /// sending the `Authorization` header as is
+    #[must_use]
+    pub fn with_authorization_templates(mut self, templates: Vec<AuthorizationTemplate>) -> Self {
+        self.authorization_templates = templates;
+        self
+    }
+
+    /// Create a new HTTP client forwarding the MCP client's authorization
+    ///
+    /// The header is forwarded as is, or rendered into the headers of the
+    /// authorization templates when there are any.
+    #[must_use]
+    pub fn with_client_authorization(&self, auth_value: &str) -> Self {
+        if self.authorization_templates.is_empty() {
+            return self.with_authorization(auth_value);
+        }
+
+        let mut client = self.clone();
+        for template in &self.authorization_templates {
+            if let Some(value) = template.render(auth_value) {
+                client.default_headers.insert(template.name.clone(), value);
+            }
+        }
+        client
+    }
+
+    /// Exchange forwarded client tokens at a security token service before
+    /// they are sent upstream
+    #[must_use]
+    pub fn with_token_exchange(mut self, exchange: Arc<TokenExchange>) -> Self {
+        self.token_exchange = Some(exchange);
+        self
+    }
+
+    /// Create a new HTTP client forwarding the MCP client's authorization,
+    /// exchanged first when token exchange is configured
+    ///
+    /// # Errors
+    ///
+    /// Returns an authentication error if the token exchange fails
+    pub async fn with_exchanged_authorization(
+        &self,
+        auth_value: &str,
+    ) -> Result<Self, ToolCallError> {
+        let Some(exchange) = &self.token_exchange else {
+            return Ok(self.with_client_authorization(auth_value));
+        };
+
+        let token = exchange
+            .exchange(&self.client, bearer_token(auth_value))
+            .await?;
+        Ok(self.with_client_authorization(&format!("Bearer {token}")))
+    }
+
+    /// Check forwarded client tokens for the scopes of the operations they
+    /// are used with
     #[must_use]
-    pub fn with_default_headers(mut self, default_headers: HeaderMap) -> Self {
-        self.default_headers = default_headers;
+    pub fn with_scope_checker(mut self, checker: Arc<ScopeChecker>) -> Self {
+        self.scope_checker = Some(checker);
         self
     }
 
-    /// Create a new HTTP client with authorization header
+    /// Fail unless the MCP client's authorization holds the scopes the tool
+    /// requires, when a scope checker is configured
+    #[cfg(feature = "authorization-token-passthrough")]
+    pub(crate) async fn check_scopes(
+        &self,
+        tool_metadata: &ToolMetadata,
+        auth_value: &str,
+    ) -> Result<(), ToolCallError> {
+        match &self.scope_checker {
+            Some(checker) => {
+                checker
+                    .check(&self.client, tool_metadata, bearer_token(auth_value))
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Require a confirmed second call for tools whose method `config` lists
     ///
-    /// Clones the current client and adds the Authorization header to default headers.
-    /// This allows passing authorization through to backend APIs.
+    /// Tokens are shared by all clones of this client.
     #[must_use]
-    pub fn with_authorization(&self, auth_value: &str) -> Self {
-        let mut headers = self.default_headers.clone();
-        if let Ok(header_value) = HeaderValue::from_str(auth_value) {
-            headers.insert(header::AUTHORIZATION, header_value);
+    pub fn with_confirmation(mut self, config: ConfirmationConfig) -> Self {
+        self.confirmation = Some(ConfirmationGate::new(config));
+        self
+    }
+
+    /// Arguments to execute a tool call with, once confirmed when
+    /// confirmation is configured for its method
+    pub(crate) fn confirm<'a>(
+        &self,
+        tool_metadata: &ToolMetadata,
+        arguments: &'a Value,
+    ) -> Result<Cow<'a, Value>, ToolCallError> {
+        match &self.confirmation {
+            Some(gate) => gate.check(tool_metadata, arguments),
+            None => Ok(Cow::Borrowed(arguments)),
         }
+    }
 
+    /// Same client, answering tool calls with the description of their request
+    pub(crate) fn for_dry_run(&self) -> Self {
         Self {
-            client: self.client.clone(),
-            base_url: self.base_url.clone(),
-            default_headers: headers,
-            timeout_seconds: self.timeout_seconds,
+            dry_run: true,
+            ..self.clone()
         }
     }
 
+    /// Request a tool call would send, described as in [`crate::dry_run`]
+    /// instead of sent
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parameters are invalid, the call would be refused
+    /// or a middleware rejects it
+    pub(crate) async fn dry_run(
+        &self,
+        tool_metadata: &ToolMetadata,
+        arguments: &Value,
+    ) -> Result<Value, ToolCallError> {
+        let response = self
+            .for_dry_run()
+            .send_tool_call(tool_metadata, arguments)
+            .await?;
+        serde_json::from_str(&response.body).map_err(|e| {
+            ToolCallError::Execution(ToolCallExecutionError::ResponseParsingError {
+                reason: e.to_string(),
+                raw_response: None,
+            })
+        })
+    }
+
+    /// Whether upstream requests may carry credentials without the MCP client
+    /// providing any, or never reach the upstream
+    ///
+    /// Only default headers carrying credentials count: `Authorization`,
+    /// `Cookie` and the headers of `apiKey` schemes.
+    pub(crate) fn has_credentials(&self) -> bool {
+        self.default_headers.keys().any(|name| {
+            name == header::AUTHORIZATION
+                || name == header::COOKIE
+                || self.api_key_headers.contains(name)
+        }) || !self.api_keys.is_empty()
+            || self.basic_auth.is_some()
+            || self.client_credentials.is_some()
+            || self.signer.is_some()
+            || self.mock_responses
+            || self.cassette.as_ref().is_some_and(|c| c.is_replaying())
+    }
+
     /// Execute an `OpenAPI` tool call
     ///
     /// # Errors
     ///
-    /// Returns an error if the HTTP request fails or parameters are invalid
+    /// Returns an error if the HTTP request fails, parameters are invalid or
+    /// a middleware rejects the call
     pub async fn execute_tool_call(
         &self,
         tool_metadata: &ToolMetadata,
         arguments: &Value,
+    ) -> Result<HttpResponse, ToolCallError> {
+        let mut response = self.send_tool_call(tool_metadata, arguments).await?;
+        if !response.is_success
+            && self
+                .status_policy
+                .is_success(tool_metadata, response.status_code)
+        {
+            response.is_success = true;
+        }
+        for middleware in &self.middlewares {
+            middleware.on_response(tool_metadata, &mut response).await?;
+        }
+        if let Some(conversion) = self.html_conversion
+            && response.is_html()
+        {
+            response.body = conversion.convert(&response.body);
+        }
+        Ok(response)
+    }
+
+    /// Build, send and read the upstream request of a tool call
+    async fn send_tool_call(
+        &self,
+        tool_metadata: &ToolMetadata,
+        arguments: &Value,
     ) -> Result<HttpResponse, ToolCallError> {
         let span = info_span!(
             "http_request",
@@ -262,22 +1461,43 @@ impl HttpClient {
         );
         let _enter = span.enter();
 
+        if self.read_only && !is_read_only_method(&tool_metadata.method) {
+            warn!("Refusing non-read-only request in read-only mode");
+            return Err(ToolCallError::Execution(ToolCallExecutionError::ReadOnly {
+                tool: tool_metadata.name.clone(),
+                method: tool_metadata.method.to_uppercase(),
+            }));
+        }
+
+        let mut logged_arguments = arguments.clone();
+        if let Some(rules) = &self.redaction {
+            rules.redact_json(&mut logged_arguments);
+        }
         debug!(
             "Executing tool call: {} {} with arguments: {}",
             tool_metadata.method,
             tool_metadata.path,
-            serde_json::to_string_pretty(arguments).unwrap_or_else(|_| "invalid json".to_string())
+            serde_json::to_string_pretty(&logged_arguments)
+                .unwrap_or_else(|_| "invalid json".to_string())
         );
 
         // Extract parameters from arguments
-        let extracted_params = ToolGenerator::extract_parameters(tool_metadata, arguments)?;
+        let mut extracted_params = ToolGenerator::extract_parameters(tool_metadata, arguments)?;
 
+        let logged_headers: HashMap<&String, Value> = extracted_params
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                if self.redacts_header(name) {
+                    (name, Value::String(REDACTED.to_string()))
+                } else {
+                    (name, value.clone())
+                }
+            })
+            .collect();
         debug!(
             "Extracted parameters: path={:?}, query={:?}, headers={:?}, cookies={:?}",
-            extracted_params.path,
-            extracted_params.query,
-            extracted_params.headers,
-            extracted_params.cookies
+            extracted_params.path, extracted_params.query, logged_headers, extracted_params.cookies
         );
 
         // Build the URL with path parameters
@@ -288,8 +1508,17 @@ impl HttpClient {
                     reason: e.to_string(),
                 })
             })?;
+        if !self.allows_url(&url) {
+            warn!(url = %url, "Refusing request to a host outside the allowlist");
+            return Err(ToolCallError::Execution(
+                ToolCallExecutionError::HostNotAllowed {
+                    host: url.host_str().unwrap_or_default().to_string(),
+                },
+            ));
+        }
 
         // Add query parameters with proper URL encoding
+        self.apply_null_value_policy(&mut extracted_params.query);
         if !extracted_params.query.is_empty() {
             Self::add_query_parameters(&mut url, &extracted_params.query);
         }
@@ -305,20 +1534,73 @@ impl HttpClient {
                 })
             })?;
 
+        // Advertise the codings we can decode unless a default header already does
+        if self
+            .compression
+            .as_ref()
+            .is_some_and(|c| c.decompress_responses)
+            && !self.default_headers.contains_key(header::ACCEPT_ENCODING)
+        {
+            request = request.header(header::ACCEPT_ENCODING, "gzip, deflate, br");
+        }
+
         // Add headers: first default headers, then request-specific headers (which take precedence)
         if !self.default_headers.is_empty() {
-            // Use the HeaderMap directly with reqwest
-            request = Self::add_headers_from_map(request, &self.default_headers);
+            request = self.add_default_headers(request, &tool_metadata.name);
         }
 
         // Add request-specific headers (these override default headers)
         if !extracted_params.headers.is_empty() {
-            request = Self::add_headers(request, &extracted_params.headers);
+            request = Self::add_headers(request, tool_metadata, &extracted_params.headers);
+        }
+
+        // Continue the current trace upstream unless the call sets its own context
+        #[cfg(feature = "opentelemetry")]
+        if !self
+            .default_headers
+            .contains_key(crate::telemetry::TRACEPARENT)
+            && !extracted_params
+                .headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case(crate::telemetry::TRACEPARENT))
+        {
+            request = crate::telemetry::inject_current(request);
+        }
+
+        // Generate an idempotency key unless the caller supplied one
+        let mut has_idempotency_key = false;
+        if let Some(idempotency) = self
+            .idempotency
+            .as_ref()
+            .filter(|config| config.applies_to(&tool_metadata.method))
+        {
+            has_idempotency_key = true;
+            let supplied = self
+                .default_headers
+                .contains_key(idempotency.header.as_str())
+                || extracted_params
+                    .headers
+                    .keys()
+                    .any(|name| name.eq_ignore_ascii_case(&idempotency.header));
+            if !supplied {
+                let key = uuid::Uuid::new_v4().to_string();
+                debug!(header = %idempotency.header, key = %key, "Attaching idempotency key");
+                request = request.header(idempotency.header.as_str(), key);
+            }
         }
 
         // Add cookies
         if !extracted_params.cookies.is_empty() {
-            request = Self::add_cookies(request, &extracted_params.cookies);
+            request = Self::add_cookies(request, tool_metadata, &extracted_params.cookies);
+        }
+
+        // Drop bodies on methods a strict proxy would reject them for
+        if !extracted_params.body.is_empty() && self.should_strip_body(&tool_metadata.method) {
+            debug!(
+                "Stripping request body from {} request as configured",
+                tool_metadata.method
+            );
+            extracted_params.body.clear();
         }
 
         // Add request body if present
@@ -333,12 +1615,17 @@ impl HttpClient {
                 })?;
         }
 
-        // Apply custom timeout if specified
-        if extracted_params.config.timeout_seconds != 30 {
-            request = request.timeout(Duration::from_secs(u64::from(
-                extracted_params.config.timeout_seconds,
-            )));
-        }
+        // Apply the caller's timeout within the server-side limits
+        let requested_timeout = extracted_params
+            .config
+            .timeout_seconds
+            .map(|seconds| Duration::from_secs(u64::from(seconds)));
+        let timeout = self.timeout_policy.timeout_for(
+            tool_metadata,
+            requested_timeout,
+            Duration::from_secs(self.timeout_seconds),
+        );
+        request = request.timeout(timeout);
 
         // Capture request details for response formatting
         let request_body_string = if extracted_params.body.is_empty() {
@@ -358,13 +1645,285 @@ impl HttpClient {
             serde_json::to_string(&body_object).unwrap_or_default()
         };
 
+        // Compress large JSON bodies
+        if let Some(encoding) = self
+            .compression
+            .as_ref()
+            .filter(|c| request_body_string.len() >= c.min_request_size)
+            .and_then(|c| c.request_encoding)
+            && extracted_params.config.content_type == mime::APPLICATION_JSON.as_ref()
+        {
+            let compressed = encoding
+                .encode(request_body_string.as_bytes())
+                .map_err(|e| {
+                    ToolCallError::Validation(ToolCallValidationError::RequestConstructionError {
+                        reason: format!("Failed to compress request body: {e}"),
+                    })
+                })?;
+            debug!(
+                encoding = encoding.as_str(),
+                original_bytes = request_body_string.len(),
+                compressed_bytes = compressed.len(),
+                "Compressed request body"
+            );
+            request = request
+                .header(header::CONTENT_ENCODING, encoding.as_str())
+                .body(compressed);
+        }
+
         // Get the final URL for logging
         let final_url = url.to_string();
 
+        // Let middlewares adjust the request before it is looked up or sent
+        if !self.middlewares.is_empty() {
+            let mut built = request.build().map_err(|e| {
+                ToolCallError::Validation(ToolCallValidationError::RequestConstructionError {
+                    reason: e.to_string(),
+                })
+            })?;
+            for middleware in &self.middlewares {
+                middleware.on_request(tool_metadata, &mut built).await?;
+            }
+            request = RequestBuilder::from_parts(self.client.clone(), built);
+        }
+
+        // Describe the request instead of sending it on dry runs
+        if self.dry_run {
+            let built = request.build().map_err(|e| {
+                ToolCallError::Validation(ToolCallValidationError::RequestConstructionError {
+                    reason: e.to_string(),
+                })
+            })?;
+            let description = crate::dry_run::describe(&built, &request_body_string, |name| {
+                self.redacts_header(name)
+            });
+            debug!(tool_name = %tool_metadata.name, url = %final_url, "Describing dry run");
+            return Ok(HttpResponse {
+                status_code: 200,
+                status_text: "OK".to_string(),
+                headers: HashMap::new(),
+                content_type: Some("application/json".to_string()),
+                body: description.to_string(),
+                body_bytes: None,
+                is_success: true,
+                request_method: tool_metadata.method.clone(),
+                request_url: final_url,
+                request_body: request_body_string,
+                cache_status: None,
+                truncated_at: None,
+            });
+        }
+
+        // Fabricate the response in mock mode; nothing is sent
+        if self.mock_responses {
+            debug!(
+                tool_name = %tool_metadata.name,
+                url = %final_url,
+                "Returning mock response"
+            );
+            return Ok(mock_response(
+                tool_metadata,
+                &final_url,
+                &request_body_string,
+            ));
+        }
+
+        // Answer from the cassette instead of the upstream when replaying
+        if let Some(cassette) = self.cassette.as_ref().filter(|c| c.is_replaying()) {
+            return cassette
+                .replay(&tool_metadata.method, &final_url, &request_body_string)
+                .ok_or_else(|| {
+                    ToolCallError::Execution(ToolCallExecutionError::NetworkError {
+                        message: format!(
+                            "No recorded response for {} {} in cassette {}",
+                            tool_metadata.method.to_uppercase(),
+                            final_url,
+                            cassette.config().path.display()
+                        ),
+                        category: NetworkErrorCategory::Other,
+                    })
+                });
+        }
+
+        // Serve fresh cached responses without contacting the upstream
+        let cache_key = self
+            .cache
+            .as_ref()
+            .filter(|_| tool_metadata.method.eq_ignore_ascii_case("get"))
+            .and_then(|_| request.try_clone()?.build().ok())
+            .map(|request| cache_key(&request, &self.per_request_headers()));
+        let mut stale_entry = None;
+        if let (Some(cache), Some(key)) = (&self.cache, &cache_key)
+            && let Some(entry) = cache.store.get(key)
+        {
+            if entry.is_fresh() {
+                debug!(url = %final_url, "Serving response from cache");
+                let mut response = entry.response;
+                response.cache_status = Some(CacheStatus::Hit);
+                return Ok(response);
+            }
+            // Revalidate stale entries that carry a validator
+            if let Some(etag) = entry.response.headers.get("etag") {
+                request = request.header(header::IF_NONE_MATCH, etag);
+                stale_entry = Some(entry);
+            }
+        }
+
+        // Fail fast while the upstream host's circuit is open
+        let circuit_key = Self::circuit_key(&url);
+        if let Some(breaker) = &self.circuit_breaker
+            && let Err(remaining) = breaker.check(&circuit_key)
+        {
+            return Err(ToolCallError::Execution(
+                ToolCallExecutionError::UpstreamUnavailable {
+                    host: circuit_key,
+                    retry_after_seconds: remaining.as_secs_f64().ceil() as u64,
+                },
+            ));
+        }
+
+        // Respect client-side rate limits
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire(&tool_metadata.name).await.map_err(|e| {
+                ToolCallError::Execution(ToolCallExecutionError::RateLimitExceeded {
+                    scope: e.scope,
+                    retry_after_ms: u64::try_from(e.retry_after.as_millis()).unwrap_or(u64::MAX),
+                })
+            })?;
+        }
+
+        // Hold a slot until the response has been read
+        let _in_flight = self
+            .concurrency
+            .as_ref()
+            .map(|limiter| limiter.try_acquire(self.session_id.as_deref()))
+            .transpose()
+            .map_err(|e| {
+                warn!(
+                    scope = e.scope,
+                    limit = e.limit,
+                    "Too many concurrent upstream requests"
+                );
+                ToolCallError::Execution(ToolCallExecutionError::Throttled {
+                    scope: e.scope.to_string(),
+                    limit: e.limit as u64,
+                })
+            })?;
+
+        // Attach API keys and Basic credentials after the URL was logged, so they
+        // stay out of logs and results
+        if !self.api_keys.is_empty() || self.basic_auth.is_some() {
+            let mut built = request.build().map_err(|e| {
+                ToolCallError::Validation(ToolCallValidationError::RequestConstructionError {
+                    reason: e.to_string(),
+                })
+            })?;
+            for api_key in &self.api_keys {
+                api_key.apply(&mut built);
+            }
+            if let Some(basic_auth) = &self.basic_auth {
+                basic_auth.apply(&mut built);
+            }
+            request = RequestBuilder::from_parts(self.client.clone(), built);
+        }
+
+        // Attach an access token unless the request is already authorized, keeping
+        // a copy to resend with a renewed token if the upstream rejects it
+        let mut token_retry = None;
+        if let Some(credentials) = &self.client_credentials {
+            let mut built = request.build().map_err(|e| {
+                ToolCallError::Validation(ToolCallValidationError::RequestConstructionError {
+                    reason: e.to_string(),
+                })
+            })?;
+            if !built.headers().contains_key(header::AUTHORIZATION) {
+                let token = credentials.access_token(&self.client).await?;
+                token_retry = built
+                    .try_clone()
+                    .map(|retry| (credentials, token.clone(), retry));
+                credentials.authorize(&mut built, &token)?;
+            }
+            request = RequestBuilder::from_parts(self.client.clone(), built);
+        }
+
+        // Sign last, so the signature covers the request exactly as sent
+        request = self.sign(request)?;
+
+        // Capture the headers as sent before the request is consumed
+        let audit_headers = self.audit_log.as_ref().map(|log| {
+            request
+                .try_clone()
+                .and_then(|request| request.build().ok())
+                .map(|request| log.redact_headers(request.headers()))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, value)| {
+                    if self.redacts_header(&name) {
+                        (name, REDACTED.to_string())
+                    } else {
+                        (name, value)
+                    }
+                })
+                .collect()
+        });
+
         // Execute the request
         debug!("Sending HTTP request...");
+        let sent_at = SystemTime::now();
         let start_time = std::time::Instant::now();
-        let response = request.send().await.map_err(|e| {
+        let mut result = self
+            .send(request, &tool_metadata.method, has_idempotency_key)
+            .await;
+
+        // Renew an expired or revoked access token and try once more
+        let mut renewed_credentials = None;
+        if let Some((credentials, rejected, mut retry)) = token_retry
+            && result
+                .as_ref()
+                .is_ok_and(|response| response.status() == StatusCode::UNAUTHORIZED)
+        {
+            debug!("Upstream rejected the access token, renewing it");
+            credentials.expire(&rejected);
+            let token = credentials.access_token(&self.client).await?;
+            credentials.authorize(&mut retry, &token)?;
+            let retry = self.sign(RequestBuilder::from_parts(self.client.clone(), retry))?;
+            result = self
+                .send(retry, &tool_metadata.method, has_idempotency_key)
+                .await;
+            renewed_credentials = Some(credentials);
+        }
+
+        if let Some(breaker) = &self.circuit_breaker {
+            let failed = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+            if !failed {
+                breaker.record_success(&circuit_key);
+            } else if breaker.record_failure(&circuit_key) {
+                warn!(
+                    host = %circuit_key,
+                    "Upstream failing repeatedly, opening circuit breaker"
+                );
+            }
+        }
+
+        if let (Some(log), Some(request_headers)) = (&self.audit_log, audit_headers) {
+            log.record(&AuditRecord {
+                timestamp_ms: unix_millis(sent_at),
+                session_id: self.session_id.clone(),
+                tool: tool_metadata.name.clone(),
+                method: tool_metadata.method.to_uppercase(),
+                url: final_url.clone(),
+                status: result.as_ref().ok().map(|r| r.status().as_u16()),
+                error: result.as_ref().err().map(ToString::to_string),
+                duration_ms: u64::try_from(start_time.elapsed().as_millis()).unwrap_or(u64::MAX),
+                request_headers,
+            });
+        }
+
+        let response = result
+            .map_err(|e| {
             error!(
                 operation_id = %tool_metadata.name,
                 method = %tool_metadata.method,
@@ -378,7 +1937,7 @@ impl HttpClient {
                 (
                     format!(
                         "Request timeout after {} seconds while calling {} {}",
-                        extracted_params.config.timeout_seconds,
+                        timeout.as_secs_f64(),
                         tool_metadata.method.to_uppercase(),
                         final_url
                     ),
@@ -439,6 +1998,47 @@ impl HttpClient {
             })
         })?;
 
+        // A freshly obtained token was refused too, retrying will not help
+        if let Some(credentials) = renewed_credentials
+            && response.status() == StatusCode::UNAUTHORIZED
+        {
+            let challenge = response
+                .headers()
+                .get(header::WWW_AUTHENTICATE)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| format!(": {value}"))
+                .unwrap_or_default();
+            return Err(credentials.authentication_failed(format!(
+                "Upstream rejected a renewed access token with 401 Unauthorized{challenge}"
+            )));
+        }
+
+        // The upstream confirmed the cached representation is still current
+        if let (Some(cache), Some(key), Some(entry)) = (&self.cache, &cache_key, stale_entry)
+            && response.status() == StatusCode::NOT_MODIFIED
+        {
+            debug!(url = %final_url, "Cached response revalidated");
+            let mut cached = entry.response;
+            for (name, value) in response.headers() {
+                if matches!(name.as_str(), "cache-control" | "etag" | "expires" | "date")
+                    && let Ok(value) = value.to_str()
+                {
+                    cached.headers.insert(name.to_string(), value.to_string());
+                }
+            }
+            if let Some(ttl) = cache.config.ttl_for(&cached) {
+                cache.store.insert(
+                    key.clone(),
+                    CachedResponse {
+                        response: cached.clone(),
+                        expires_at: SystemTime::now() + ttl,
+                    },
+                );
+            }
+            cached.cache_status = Some(CacheStatus::Revalidated);
+            return Ok(cached);
+        }
+
         let elapsed = start_time.elapsed();
         info!(
             operation_id = %tool_metadata.name,
@@ -451,20 +2051,56 @@ impl HttpClient {
         debug!("Response received with status: {}", response.status());
 
         // Convert response to our format with request details
-        self.process_response_with_request(
-            response,
-            &tool_metadata.method,
-            &final_url,
-            &request_body_string,
-        )
-        .await
-        .map_err(|e| {
-            ToolCallError::Execution(ToolCallExecutionError::HttpError {
-                status: 0,
-                message: e.to_string(),
-                details: None,
-            })
-        })
+        let mut response = self
+            .process_response_with_request(
+                response,
+                &tool_metadata.method,
+                &final_url,
+                &request_body_string,
+            )
+            .await
+            .map_err(|e| {
+                ToolCallError::Execution(ToolCallExecutionError::HttpError {
+                    status: 0,
+                    message: e.to_string(),
+                    details: None,
+                })
+            })?;
+
+        // Failed responses are echoed back in error results
+        if let Some(rules) = &self.redaction
+            && (!response.is_success || response.truncated_at.is_some())
+        {
+            rules.redact_response(&mut response);
+        }
+
+        if let Some(cassette) = &self.cassette {
+            cassette.record(
+                &tool_metadata.method,
+                &final_url,
+                &request_body_string,
+                &response,
+            );
+        }
+
+        if cache_key.is_some() {
+            response.cache_status = Some(CacheStatus::Miss);
+        }
+        if let (Some(cache), Some(key)) = (&self.cache, cache_key)
+            && matches!(response.status_code, 200 | 203)
+            && response.truncated_at.is_none()
+            && let Some(ttl) = cache.config.ttl_for(&response)
+        {
+            cache.store.insert(
+                key,
+                CachedResponse {
+                    response: response.clone(),
+                    expires_at: SystemTime::now() + ttl,
+                },
+            );
+        }
+
+        Ok(response)
     }
 
     /// Build the complete URL with path parameters substituted
@@ -475,15 +2111,16 @@ impl HttpClient {
     ) -> Result<Url, Error> {
         let mut path = tool_metadata.path.clone();
 
-        // Substitute path parameters
+        // Substitute path parameters, honouring the declared serialization style
         for (param_name, param_value) in &extracted_params.path {
             let placeholder = format!("{{{param_name}}}");
-            let value_str = match param_value {
-                Value::String(s) => s.clone(),
-                Value::Number(n) => n.to_string(),
-                Value::Bool(b) => b.to_string(),
-                _ => param_value.to_string(),
-            };
+            let (style, explode) = tool_metadata
+                .parameter_mappings
+                .values()
+                .find(|m| m.location == "path" && m.original_name == *param_name)
+                .map(|m| (m.style, m.explode))
+                .unwrap_or((None, false));
+            let value_str = Self::render_path_parameter(param_name, param_value, style, explode);
             path = path.replace(&placeholder, &value_str);
         }
 
@@ -513,6 +2150,72 @@ impl HttpClient {
         }
     }
 
+    /// Render a path parameter value following its OpenAPI `style`.
+    ///
+    /// Implements the RFC 6570 expansions OpenAPI allows for path parameters:
+    ///
+    /// | style  | explode | primitive | array             | object                |
+    /// |--------|---------|-----------|-------------------|-----------------------|
+    /// | simple | false   | `5`       | `3,4,5`           | `role,admin,id,5`     |
+    /// | simple | true    | `5`       | `3,4,5`           | `role=admin,id=5`     |
+    /// | label  | false   | `.5`      | `.3,4,5`          | `.role,admin,id,5`    |
+    /// | label  | true    | `.5`      | `.3.4.5`          | `.role=admin.id=5`    |
+    /// | matrix | false   | `;id=5`   | `;id=3,4,5`       | `;id=role,admin,id,5` |
+    /// | matrix | true    | `;id=5`   | `;id=3;id=4;id=5` | `;role=admin;id=5`    |
+    ///
+    /// Any style other than `label` or `matrix` is rendered as `simple`.
+    fn render_path_parameter(
+        name: &str,
+        value: &Value,
+        style: Option<ParameterStyle>,
+        explode: bool,
+    ) -> String {
+        // Join array items or object entries with the given separators.
+        let join_items = |items: &[Value], sep: &str| {
+            items
+                .iter()
+                .map(scalar_to_string)
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+        let join_entries = |map: &serde_json::Map<String, Value>, kv_sep: &str, sep: &str| {
+            map.iter()
+                .map(|(k, v)| format!("{k}{kv_sep}{}", scalar_to_string(v)))
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+
+        match style {
+            Some(ParameterStyle::Label) => {
+                let sep = if explode { "." } else { "," };
+                match value {
+                    Value::Array(items) => format!(".{}", join_items(items, sep)),
+                    Value::Object(map) if explode => format!(".{}", join_entries(map, "=", ".")),
+                    Value::Object(map) => format!(".{}", join_entries(map, ",", ",")),
+                    scalar => format!(".{}", scalar_to_string(scalar)),
+                }
+            }
+            Some(ParameterStyle::Matrix) => match value {
+                Value::Array(items) if explode => items
+                    .iter()
+                    .map(|item| format!(";{name}={}", scalar_to_string(item)))
+                    .collect(),
+                Value::Array(items) => format!(";{name}={}", join_items(items, ",")),
+                Value::Object(map) if explode => format!(";{}", join_entries(map, "=", ";")),
+                Value::Object(map) => format!(";{name}={}", join_entries(map, ",", ",")),
+                // RFC 6570 drops the `=` for empty values in matrix expansion
+                Value::String(s) if s.is_empty() => format!(";{name}"),
+                scalar => format!(";{name}={}", scalar_to_string(scalar)),
+            },
+            _ => match value {
+                Value::Array(items) => join_items(items, ","),
+                Value::Object(map) if explode => join_entries(map, "=", ","),
+                Value::Object(map) => join_entries(map, ",", ","),
+                scalar => scalar_to_string(scalar),
+            },
+        }
+    }
+
     /// Create a new HTTP request with the specified method and URL
     fn create_request(&self, method: &str, url: &Url) -> Result<RequestBuilder, Error> {
         let http_method = method.to_uppercase();
@@ -536,115 +2239,249 @@ impl HttpClient {
 
     /// Add query parameters to the request using proper URL encoding
     fn add_query_parameters(url: &mut Url, query_params: &HashMap<String, QueryParameter>) {
-        // Render a scalar JSON value as its bare query-string form. Strings are
-        // used verbatim; everything else falls back to its JSON rendering.
-        fn scalar_to_string(value: &Value) -> String {
-            match value {
-                Value::String(s) => s.clone(),
-                Value::Number(n) => n.to_string(),
-                Value::Bool(b) => b.to_string(),
-                other => other.to_string(),
-            }
-        }
+        // Pairs for `allowReserved` parameters bypass form-urlencoding and are
+        // appended to the raw query string once the encoded pairs are in place.
+        let mut reserved_pairs = Vec::new();
 
         {
             let mut query_pairs = url.query_pairs_mut();
             for (key, query_param) in query_params {
-                match &query_param.value {
-                    Value::Array(arr) => {
-                        if query_param.explode {
-                            // explode=true: emit one query pair per array item.
-                            for item in arr {
-                                query_pairs.append_pair(key, &scalar_to_string(item));
-                            }
-                        } else {
-                            // explode=false: join the array items with commas.
-                            let comma_separated = arr
+                let pairs = Self::query_parameter_pairs(key, query_param);
+                if query_param.allow_reserved {
+                    reserved_pairs.extend(pairs);
+                } else {
+                    for (name, value) in &pairs {
+                        query_pairs.append_pair(name, value);
+                    }
+                }
+            }
+        }
+
+        if !reserved_pairs.is_empty() {
+            let mut query = url.query().unwrap_or_default().to_string();
+            for (name, value) in reserved_pairs {
+                if !query.is_empty() {
+                    query.push('&');
+                }
+                query.extend(url::form_urlencoded::byte_serialize(name.as_bytes()));
+                query.push('=');
+                query.push_str(&encode_allow_reserved(&value));
+            }
+            url.set_query(Some(&query));
+        }
+    }
+
+    /// Serialize a query parameter into its `(name, value)` pairs
+    fn query_parameter_pairs(key: &str, query_param: &QueryParameter) -> Vec<(String, String)> {
+        match &query_param.value {
+            Value::Array(arr) => {
+                if query_param.explode {
+                    // explode=true: emit one query pair per array item.
+                    arr.iter()
+                        .map(|item| (key.to_string(), scalar_to_string(item)))
+                        .collect()
+                } else {
+                    // explode=false: join the array items with commas.
+                    let comma_separated = arr
+                        .iter()
+                        .map(scalar_to_string)
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    vec![(key.to_string(), comma_separated)]
+                }
+            }
+            Value::Object(map) => {
+                // OpenAPI `style: deepObject`: expand the object to one
+                // `key[property]=value` pair per entry, joining
+                // array-valued properties with commas exactly as the
+                // array case above. Without this an object value would be
+                // serialized as an opaque JSON blob the server cannot read.
+                map.iter()
+                    .map(|(property, property_value)| {
+                        let nested_key = format!("{key}[{property}]");
+                        let value_str = match property_value {
+                            Value::Array(items) => items
                                 .iter()
                                 .map(scalar_to_string)
                                 .collect::<Vec<_>>()
-                                .join(",");
-                            query_pairs.append_pair(key, &comma_separated);
-                        }
-                    }
-                    Value::Object(map) => {
-                        // OpenAPI `style: deepObject`: expand the object to one
-                        // `key[property]=value` pair per entry, joining
-                        // array-valued properties with commas exactly as the
-                        // array case above. Without this an object value would be
-                        // serialized as an opaque JSON blob the server cannot read.
-                        for (property, property_value) in map {
-                            let nested_key = format!("{key}[{property}]");
-                            let value_str = match property_value {
-                                Value::Array(items) => items
-                                    .iter()
-                                    .map(scalar_to_string)
-                                    .collect::<Vec<_>>()
-                                    .join(","),
-                                scalar => scalar_to_string(scalar),
-                            };
-                            query_pairs.append_pair(&nested_key, &value_str);
-                        }
-                    }
-                    scalar => {
-                        query_pairs.append_pair(key, &scalar_to_string(scalar));
-                    }
-                }
+                                .join(","),
+                            scalar => scalar_to_string(scalar),
+                        };
+                        (nested_key, value_str)
+                    })
+                    .collect()
             }
+            scalar => vec![(key.to_string(), scalar_to_string(scalar))],
         }
     }
 
-    /// Add headers to the request from HeaderMap
-    fn add_headers_from_map(mut request: RequestBuilder, headers: &HeaderMap) -> RequestBuilder {
-        for (key, value) in headers {
-            // HeaderName and HeaderValue are already validated, pass them directly to reqwest
-            request = request.header(key, value);
+    /// Headers taking a new value on every request, left out of cache keys so
+    /// that they do not turn every lookup into a miss
+    fn per_request_headers(&self) -> Vec<header::HeaderName> {
+        #[cfg_attr(not(feature = "opentelemetry"), allow(unused_mut))]
+        let mut names: Vec<header::HeaderName> = self
+            .default_headers
+            .iter()
+            .filter(|(_, value)| value.to_str().is_ok_and(|value| value.contains("{uuid}")))
+            .map(|(name, _)| name.clone())
+            .collect();
+        // Each call continues the trace with a span of its own
+        #[cfg(feature = "opentelemetry")]
+        names.extend([
+            header::HeaderName::from_static(crate::telemetry::TRACEPARENT),
+            header::HeaderName::from_static(crate::telemetry::TRACESTATE),
+        ]);
+        names
+    }
+
+    /// Add the default headers to a request to `tool_name`, with their
+    /// placeholders rendered
+    fn add_default_headers(&self, mut request: RequestBuilder, tool_name: &str) -> RequestBuilder {
+        for (key, value) in &self.default_headers {
+            let Some(template) = value.to_str().ok().filter(|value| value.contains('{')) else {
+                // HeaderName and HeaderValue are already validated, pass them directly to reqwest
+                request = request.header(key, value);
+                continue;
+            };
+            let session_id = match (&self.session_id, template.contains("{session_id}")) {
+                (Some(session_id), _) => session_id.as_str(),
+                (None, false) => "",
+                (None, true) => continue,
+            };
+            let rendered = template
+                .replace("{uuid}", &uuid::Uuid::new_v4().to_string())
+                .replace("{session_id}", session_id)
+                .replace("{tool}", tool_name);
+            match HeaderValue::from_str(&rendered) {
+                Ok(mut rendered) => {
+                    rendered.set_sensitive(value.is_sensitive());
+                    request = request.header(key, rendered);
+                }
+                Err(e) => warn!(header = %key, error = %e, "Skipping invalid default header"),
+            }
         }
         request
     }
 
     /// Add headers to the request
+    ///
+    /// Values are serialized with the `simple` style, the only style
+    /// `OpenAPI` allows for header parameters.
     fn add_headers(
         mut request: RequestBuilder,
+        tool_metadata: &ToolMetadata,
         headers: &HashMap<String, Value>,
     ) -> RequestBuilder {
         for (key, value) in headers {
-            let value_str = match value {
-                Value::String(s) => s.clone(),
-                Value::Number(n) => n.to_string(),
-                Value::Bool(b) => b.to_string(),
-                _ => value.to_string(),
-            };
-            request = request.header(key, value_str);
+            if value.is_null() {
+                continue;
+            }
+            let explode = tool_metadata
+                .parameter_mappings
+                .values()
+                .find(|m| m.location == "header" && m.original_name == *key)
+                .is_some_and(|m| m.explode);
+            request = request.header(key, Self::header_value(value, explode));
         }
         request
     }
 
-    /// Add cookies to the request
+    /// Serialize a header parameter value using the `simple` style
+    ///
+    /// | value                              | explode = false       | explode = true        |
+    /// |------------------------------------|-----------------------|-----------------------|
+    /// | `5`                                | `5`                   | `5`                   |
+    /// | `[3, 4, 5]`                        | `3,4,5`               | `3,4,5`               |
+    /// | `{"role": "admin", "id": 5}`       | `role,admin,id,5`     | `role=admin,id=5`     |
+    fn header_value(value: &Value, explode: bool) -> String {
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .map(scalar_to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            Value::Object(map) if explode => map
+                .iter()
+                .map(|(k, v)| format!("{k}={}", scalar_to_string(v)))
+                .collect::<Vec<_>>()
+                .join(","),
+            Value::Object(map) => map
+                .iter()
+                .flat_map(|(k, v)| [k.clone(), scalar_to_string(v)])
+                .collect::<Vec<_>>()
+                .join(","),
+            scalar => scalar_to_string(scalar),
+        }
+    }
+
+    /// Add cookies to the request as a single `Cookie` header
+    ///
+    /// Values are serialized with the `form` style declared for the cookie
+    /// parameter (the only style `OpenAPI` allows in cookies). Cookies are
+    /// emitted in name order so the header is deterministic.
     fn add_cookies(
         mut request: RequestBuilder,
+        tool_metadata: &ToolMetadata,
         cookies: &HashMap<String, Value>,
     ) -> RequestBuilder {
-        if !cookies.is_empty() {
-            let cookie_header = cookies
-                .iter()
-                .map(|(key, value)| {
-                    let value_str = match value {
-                        Value::String(s) => s.clone(),
-                        Value::Number(n) => n.to_string(),
-                        Value::Bool(b) => b.to_string(),
-                        _ => value.to_string(),
-                    };
-                    format!("{key}={value_str}")
-                })
-                .collect::<Vec<_>>()
-                .join("; ");
+        let mut names: Vec<&String> = cookies.keys().collect();
+        names.sort();
+
+        let pairs: Vec<String> = names
+            .into_iter()
+            .flat_map(|name| {
+                let explode = tool_metadata
+                    .parameter_mappings
+                    .values()
+                    .find(|m| m.location == "cookie" && m.original_name == *name)
+                    .is_none_or(|m| m.explode);
+                Self::cookie_pairs(name, &cookies[name], explode)
+            })
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
 
-            request = request.header(header::COOKIE, cookie_header);
+        if !pairs.is_empty() {
+            request = request.header(header::COOKIE, pairs.join("; "));
         }
         request
     }
 
+    /// Serialize a single cookie parameter into `name=value` pairs
+    ///
+    /// | value                | explode = false        | explode = true          |
+    /// |----------------------|------------------------|-------------------------|
+    /// | `5`                  | `id=5`                 | `id=5`                  |
+    /// | `[3, 4]`             | `id=3,4`               | `id=3; id=4`            |
+    /// | `{"role": "admin"}`  | `id=role,admin`        | `role=admin`            |
+    ///
+    /// Characters not permitted in a cookie value (whitespace, `"`, `,`,
+    /// `;`, `\`, non-ASCII) are percent-encoded within each item.
+    fn cookie_pairs(name: &str, value: &Value, explode: bool) -> Vec<(String, String)> {
+        let item = |v: &Value| encode_cookie_value(&scalar_to_string(v));
+        match value {
+            Value::Null => vec![],
+            Value::Array(items) if explode => {
+                items.iter().map(|v| (name.to_string(), item(v))).collect()
+            }
+            Value::Array(items) => vec![(
+                name.to_string(),
+                items.iter().map(item).collect::<Vec<_>>().join(","),
+            )],
+            Value::Object(map) if explode => map
+                .iter()
+                .map(|(k, v)| (encode_cookie_value(k), item(v)))
+                .collect(),
+            Value::Object(map) => vec![(
+                name.to_string(),
+                map.iter()
+                    .flat_map(|(k, v)| [encode_cookie_value(k), item(v)])
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )],
+            scalar => vec![(name.to_string(), item(scalar))],
+        }
+    }
+
     /// Add request body to the request
     fn add_request_body(
         mut request: RequestBuilder,
@@ -757,6 +2594,35 @@ impl HttpClient {
         Ok(request)
     }
 
+    /// Read a response body, stopping once it grows past `limit` bytes and
+    /// recording the bytes received in `progress`
+    ///
+    /// Returns the bytes read and whether the whole body was read.
+    async fn read_body_limited(
+        mut response: reqwest::Response,
+        limit: Option<usize>,
+        progress: Option<&CallProgress>,
+    ) -> Result<(Vec<u8>, bool), Error> {
+        if let Some(progress) = progress {
+            progress.record_response(response.content_length());
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| Error::Http(format!("Failed to read response body: {e}")))?
+        {
+            if let Some(progress) = progress {
+                progress.record_bytes(chunk.len());
+            }
+            bytes.extend_from_slice(&chunk);
+            if limit.is_some_and(|limit| bytes.len() > limit) {
+                return Ok((bytes, false));
+            }
+        }
+        Ok((bytes, true))
+    }
+
     /// Process the HTTP response with request details for better formatting
     async fn process_response_with_request(
         &self,
@@ -778,10 +2644,21 @@ impl HttpClient {
         let is_binary_content = content_type
             .as_ref()
             .and_then(|ct| ct.parse::<mime::Mime>().ok())
-            .map(|mime_type| matches!(mime_type.type_(), mime::IMAGE | mime::AUDIO | mime::VIDEO))
-            .unwrap_or(false);
+            .is_some_and(|mime_type| is_binary_mime(&mime_type));
 
-        let headers = response
+        // Bodies in a coding we advertised are decoded before use
+        let content_encoding = response
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .and_then(ContentEncoding::from_header)
+            .filter(|_| {
+                self.compression
+                    .as_ref()
+                    .is_some_and(|c| c.decompress_responses)
+            });
+
+        let mut headers: HashMap<String, String> = response
             .headers()
             .iter()
             .map(|(name, value)| {
@@ -793,7 +2670,60 @@ impl HttpClient {
             .collect();
 
         // Read response body based on content type
-        let (body, body_bytes) = if is_binary_content {
+        let mut truncated_at = None;
+        // Bodies are streamed when they must be bounded, decoded or followed
+        let (body, body_bytes) = if content_encoding.is_some()
+            || self.max_response_bytes.is_some()
+            || self.progress.is_some()
+        {
+            let limit = self.max_response_bytes;
+            let (bytes, complete) =
+                Self::read_body_limited(response, limit, self.progress.as_ref()).await?;
+            let (bytes, truncated) = if let Some(encoding) = content_encoding {
+                let (decoded, truncated) = encoding
+                    .decode_prefix(&bytes, limit, complete)
+                    .map_err(|e| {
+                        Error::Http(format!(
+                            "Failed to decode {} response body: {e}",
+                            encoding.as_str()
+                        ))
+                    })?;
+                debug!(
+                    encoding = encoding.as_str(),
+                    compressed_bytes = bytes.len(),
+                    decoded_bytes = decoded.len(),
+                    "Decoded compressed response body"
+                );
+                // Headers now describe the decoded body
+                headers.remove(header::CONTENT_ENCODING.as_str());
+                headers.remove(header::CONTENT_LENGTH.as_str());
+                (decoded, truncated)
+            } else {
+                let mut bytes = bytes;
+                let truncated = limit.is_some_and(|limit| bytes.len() > limit);
+                bytes.truncate(limit.unwrap_or(usize::MAX));
+                (bytes, truncated)
+            };
+            if truncated {
+                warn!(
+                    url = %url,
+                    limit_bytes = limit,
+                    "Response body exceeds the size limit, truncating"
+                );
+                truncated_at = limit;
+            }
+
+            if is_binary_content {
+                let body_text = format!(
+                    "[Binary content: {} bytes, Content-Type: {}]",
+                    bytes.len(),
+                    content_type.as_ref().unwrap_or(&"unknown".to_string())
+                );
+                (body_text, Some(bytes))
+            } else {
+                (String::from_utf8_lossy(&bytes).into_owned(), None)
+            }
+        } else if is_binary_content {
             // For binary content, read as bytes
             let bytes = response
                 .bytes()
@@ -871,6 +2801,8 @@ impl HttpClient {
             request_method: method.to_string(),
             request_url: url.to_string(),
             request_body: request_body.to_string(),
+            cache_status: None,
+            truncated_at,
         })
     }
 }
@@ -881,6 +2813,34 @@ impl Default for HttpClient {
     }
 }
 
+/// Whether bodies of `mime_type` are binary rather than text
+fn is_binary_mime(mime_type: &mime::Mime) -> bool {
+    match mime_type.type_() {
+        mime::IMAGE | mime::AUDIO | mime::VIDEO | mime::FONT => true,
+        mime::APPLICATION => {
+            // Text formats, and any type declaring a charset
+            let textual = matches!(
+                mime_type.subtype().as_str(),
+                "json"
+                    | "xml"
+                    | "javascript"
+                    | "ecmascript"
+                    | "x-www-form-urlencoded"
+                    | "yaml"
+                    | "x-yaml"
+                    | "graphql"
+                    | "x-ndjson"
+                    | "sql"
+            ) || matches!(
+                mime_type.suffix().map(|suffix| suffix.as_str()),
+                Some("json" | "xml" | "yaml")
+            ) || mime_type.get_param(mime::CHARSET).is_some();
+            !textual
+        }
+        _ => false,
+    }
+}
+
 /// HTTP response from an API call
 #[derive(Debug, Clone)]
 pub struct HttpResponse {
@@ -894,6 +2854,10 @@ pub struct HttpResponse {
     pub request_method: String,
     pub request_url: String,
     pub request_body: String,
+    /// How the response cache was involved, when caching applies to the call
+    pub cache_status: Option<CacheStatus>,
+    /// Byte limit the body was cut at, when it exceeded the configured maximum
+    pub truncated_at: Option<usize>,
 }
 
 impl HttpResponse {
@@ -919,7 +2883,53 @@ impl HttpResponse {
             .unwrap_or(false)
     }
 
-    /// Check if the response contains binary content (image, audio, or video)
+    /// Check if the response contains audio content
+    #[must_use]
+    pub fn is_audio(&self) -> bool {
+        self.content_type
+            .as_ref()
+            .and_then(|ct| ct.parse::<mime::Mime>().ok())
+            .is_some_and(|mime_type| mime_type.type_() == mime::AUDIO)
+    }
+
+    /// Check if the response contains video content
+    #[must_use]
+    pub fn is_video(&self) -> bool {
+        self.content_type
+            .as_ref()
+            .and_then(|ct| ct.parse::<mime::Mime>().ok())
+            .is_some_and(|mime_type| mime_type.type_() == mime::VIDEO)
+    }
+
+    /// Target of a redirect that was not followed, resolved against the
+    /// request URL
+    ///
+    /// `None` unless the response is a `3xx` other than `304 Not Modified`
+    /// with a `Location` header.
+    #[must_use]
+    pub fn redirect_location(&self) -> Option<String> {
+        if !(300..400).contains(&self.status_code) || self.status_code == 304 {
+            return None;
+        }
+        let location = self.headers.get(header::LOCATION.as_str())?;
+        Some(
+            Url::parse(&self.request_url)
+                .and_then(|url| url.join(location))
+                .map_or_else(|_| location.clone(), String::from),
+        )
+    }
+
+    /// Check if the response is an HTML page
+    #[must_use]
+    pub fn is_html(&self) -> bool {
+        self.content_type
+            .as_ref()
+            .and_then(|ct| ct.parse::<mime::Mime>().ok())
+            .is_some_and(|mime_type| mime_type.essence_str() == mime::TEXT_HTML.essence_str())
+    }
+
+    /// Check if the response contains binary content: image, audio, video,
+    /// font, or application types that are not text, such as PDF or zip
     ///
     /// Uses the mime crate to properly parse and validate binary content types.
     #[must_use]
@@ -927,8 +2937,7 @@ impl HttpResponse {
         self.content_type
             .as_ref()
             .and_then(|ct| ct.parse::<mime::Mime>().ok())
-            .map(|mime_type| matches!(mime_type.type_(), mime::IMAGE | mime::AUDIO | mime::VIDEO))
-            .unwrap_or(false)
+            .is_some_and(|mime_type| is_binary_mime(&mime_type))
     }
 
     /// Get a formatted response summary for MCP
@@ -1003,6 +3012,8 @@ impl HttpResponse {
                     header::CONTENT_LENGTH.as_str(),
                     header::LOCATION.as_str(),
                     header::SET_COOKIE.as_str(),
+                    header::RETRY_AFTER.as_str(),
+                    "ratelimit",
                 ]
                 .iter()
                 .any(|&h| key.to_lowercase().contains(h))
@@ -1018,7 +3029,12 @@ impl HttpResponse {
 
         // Add body content
         result.push_str("\nResponse Body:\n");
-        if self.body.is_empty() {
+        if let Some(limit) = self.truncated_at {
+            result.push_str(&self.body);
+            result.push_str("\n... (response truncated at ");
+            result.push_str(&limit.to_string());
+            result.push_str(" bytes)");
+        } else if self.body.is_empty() {
             result.push_str("(empty)");
         } else if let Ok(json_value) = self.json() {
             // Pretty print JSON if possible
@@ -1049,6 +3065,27 @@ mod tests {
     use serde_json::json;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_split_unix_socket_url() {
+        let split = |url: &str| split_unix_socket_url(&Url::parse(url).unwrap());
+
+        let (socket, url) = split("unix:///var/run/docker.sock").unwrap().unwrap();
+        assert_eq!(socket, PathBuf::from("/var/run/docker.sock"));
+        assert_eq!(url.as_str(), "http://localhost/");
+
+        let (socket, url) = split("http+unix://%2Frun%2Fpodman%2Fpodman.sock/v4.0.0/libpod")
+            .unwrap()
+            .unwrap();
+        assert_eq!(socket, PathBuf::from("/run/podman/podman.sock"));
+        assert_eq!(url.as_str(), "http://localhost/v4.0.0/libpod");
+
+        let (_, url) = split("https+unix://%2Ftmp%2Fapi.sock").unwrap().unwrap();
+        assert_eq!(url.scheme(), "https");
+
+        assert!(split("https://api.example.com").unwrap().is_none());
+        assert!(split("unix:").is_err());
+    }
+
     #[test]
     fn test_with_base_url_validation() {
         // Test valid URLs
@@ -1083,6 +3120,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/pets/{id}".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1122,6 +3160,7 @@ mod tests {
                 output_schema: None,
                 method: "GET".to_string(),
                 path: "/pets/{id}".to_string(),
+                tags: vec![],
                 security: None,
                 parameter_mappings: std::collections::HashMap::new(),
             };
@@ -1155,6 +3194,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "https://api.example.com/pets/123".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1180,6 +3220,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/pets/123".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1207,6 +3248,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/search".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1270,6 +3312,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/search".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1305,6 +3348,160 @@ mod tests {
         assert!(url_string.contains("tags=fast%3Dcar")); // "fast=car" encoded
     }
 
+    #[test]
+    fn test_null_value_policy() {
+        let query = || {
+            let mut query_params = HashMap::new();
+            query_params.insert("cursor".to_string(), QueryParameter::new(Value::Null, true));
+            query_params.insert("limit".to_string(), QueryParameter::new(json!(10), true));
+            query_params
+        };
+        let encode = |policy: NullValuePolicy| {
+            let mut query_params = query();
+            HttpClient::new()
+                .with_null_value_policy(policy)
+                .apply_null_value_policy(&mut query_params);
+            let mut url = Url::parse("https://api.example.com/items").unwrap();
+            HttpClient::add_query_parameters(&mut url, &query_params);
+            let mut pairs: Vec<String> =
+                url.query_pairs().map(|(k, v)| format!("{k}={v}")).collect();
+            pairs.sort();
+            pairs
+        };
+
+        assert_eq!(encode(NullValuePolicy::Omit), vec!["limit=10"]);
+        assert_eq!(encode(NullValuePolicy::Empty), vec!["cursor=", "limit=10"]);
+        assert_eq!(
+            encode(NullValuePolicy::Literal),
+            vec!["cursor=null", "limit=10"]
+        );
+    }
+
+    #[test]
+    fn test_header_value_serialization() {
+        assert_eq!(HttpClient::header_value(&json!(5), false), "5");
+        assert_eq!(HttpClient::header_value(&json!("abc"), false), "abc");
+        assert_eq!(HttpClient::header_value(&json!([3, 4, 5]), false), "3,4,5");
+        assert_eq!(HttpClient::header_value(&json!([3, 4, 5]), true), "3,4,5");
+        assert_eq!(
+            HttpClient::header_value(&json!({"role": "admin", "id": 5}), false),
+            "role,admin,id,5"
+        );
+        assert_eq!(
+            HttpClient::header_value(&json!({"role": "admin", "id": 5}), true),
+            "role=admin,id=5"
+        );
+    }
+
+    #[test]
+    fn test_add_headers_uses_mapping_explode() {
+        let mut parameter_mappings = HashMap::new();
+        parameter_mappings.insert(
+            "header_X-Filter".to_string(),
+            crate::tool::ParameterMapping {
+                sanitized_name: "header_X-Filter".to_string(),
+                original_name: "X-Filter".to_string(),
+                location: "header".to_string(),
+                explode: true,
+                style: None,
+                allow_reserved: false,
+            },
+        );
+        let tool_metadata = crate::ToolMetadata {
+            name: "test".to_string(),
+            title: None,
+            description: None,
+            parameters: json!({}),
+            output_schema: None,
+            method: "GET".to_string(),
+            path: "/items".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings,
+        };
+
+        let mut headers = HashMap::new();
+        headers.insert("X-Filter".to_string(), json!({"status": "open"}));
+        headers.insert("X-Ids".to_string(), json!(["a", "b"]));
+        headers.insert("X-Missing".to_string(), Value::Null);
+
+        let client = HttpClient::new();
+        let request = HttpClient::add_headers(
+            client.client.get("http://example.com/items"),
+            &tool_metadata,
+            &headers,
+        )
+        .build()
+        .unwrap();
+
+        assert_eq!(request.headers()["X-Filter"], "status=open");
+        assert_eq!(request.headers()["X-Ids"], "a,b");
+        assert!(!request.headers().contains_key("X-Missing"));
+    }
+
+    #[test]
+    fn test_cookie_pairs_serialization() {
+        let pairs = |value: Value, explode: bool| -> String {
+            HttpClient::cookie_pairs("id", &value, explode)
+                .into_iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+
+        assert_eq!(pairs(json!(5), true), "id=5");
+        assert_eq!(pairs(json!("a b;c"), true), "id=a%20b%3Bc");
+        assert_eq!(pairs(json!([3, 4]), false), "id=3,4");
+        assert_eq!(pairs(json!([3, 4]), true), "id=3; id=4");
+        assert_eq!(
+            pairs(json!({"role": "admin", "team": "x,y"}), false),
+            "id=role,admin,team,x%2Cy"
+        );
+        assert_eq!(
+            pairs(json!({"role": "admin", "team": "ops"}), true),
+            "role=admin; team=ops"
+        );
+        assert_eq!(pairs(Value::Null, true), "");
+    }
+
+    #[test]
+    fn test_allow_reserved_query_parameters() {
+        let mut url = Url::parse("https://api.example.com/search").unwrap();
+
+        let mut query_params = HashMap::new();
+        query_params.insert(
+            "filter".to_string(),
+            QueryParameter::new(json!("status:active,owner=me/team"), true)
+                .with_allow_reserved(true),
+        );
+        query_params.insert(
+            "encoded".to_string(),
+            QueryParameter::new(json!("already%20encoded space"), true).with_allow_reserved(true),
+        );
+        query_params.insert(
+            "strict".to_string(),
+            QueryParameter::new(json!("a/b:c"), true),
+        );
+
+        HttpClient::add_query_parameters(&mut url, &query_params);
+        let query = url.query().unwrap();
+
+        // Reserved characters are preserved verbatim
+        assert!(query.contains("filter=status:active,owner=me/team"));
+        // Existing escapes are not double-encoded; other characters still are
+        assert!(query.contains("encoded=already%20encoded%20space"));
+        // Parameters without allowReserved keep the default form encoding
+        assert!(query.contains("strict=a%2Fb%3Ac"));
+    }
+
+    #[test]
+    fn test_encode_allow_reserved() {
+        assert_eq!(encode_allow_reserved("a+b&c=d"), "a+b&c=d");
+        assert_eq!(encode_allow_reserved("100%"), "100%25");
+        assert_eq!(encode_allow_reserved("%2F%zz"), "%2F%25zz");
+        assert_eq!(encode_allow_reserved("café #1"), "caf%C3%A9%20%231");
+    }
+
     #[test]
     fn test_path_parameter_substitution() {
         let base_url = Url::parse("https://api.example.com").unwrap();
@@ -1318,6 +3515,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/users/{userId}/pets/{petId}".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1342,6 +3540,119 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_matrix_and_label_path_parameters() {
+        let base_url = Url::parse("https://api.example.com").unwrap();
+        let client = HttpClient::new().with_base_url(base_url).unwrap();
+
+        let cases = vec![
+            (Some(ParameterStyle::Label), false, json!(5), "/items/.5"),
+            (
+                Some(ParameterStyle::Label),
+                false,
+                json!([3, 4, 5]),
+                "/items/.3,4,5",
+            ),
+            (
+                Some(ParameterStyle::Label),
+                true,
+                json!([3, 4, 5]),
+                "/items/.3.4.5",
+            ),
+            (
+                Some(ParameterStyle::Label),
+                true,
+                json!({"role": "admin", "firstName": "Alex"}),
+                "/items/.role=admin.firstName=Alex",
+            ),
+            (
+                Some(ParameterStyle::Matrix),
+                false,
+                json!(5),
+                "/items/;id=5",
+            ),
+            (
+                Some(ParameterStyle::Matrix),
+                false,
+                json!([3, 4, 5]),
+                "/items/;id=3,4,5",
+            ),
+            (
+                Some(ParameterStyle::Matrix),
+                true,
+                json!([3, 4, 5]),
+                "/items/;id=3;id=4;id=5",
+            ),
+            (
+                Some(ParameterStyle::Matrix),
+                false,
+                json!({"role": "admin", "firstName": "Alex"}),
+                "/items/;id=role,admin,firstName,Alex",
+            ),
+            (
+                Some(ParameterStyle::Matrix),
+                true,
+                json!({"role": "admin", "firstName": "Alex"}),
+                "/items/;role=admin;firstName=Alex",
+            ),
+            (Some(ParameterStyle::Matrix), false, json!(""), "/items/;id"),
+            (None, false, json!([3, 4, 5]), "/items/3,4,5"),
+            (
+                None,
+                true,
+                json!({"role": "admin", "firstName": "Alex"}),
+                "/items/role=admin,firstName=Alex",
+            ),
+        ];
+
+        for (style, explode, value, expected_path) in cases {
+            let mut parameter_mappings = HashMap::new();
+            parameter_mappings.insert(
+                "id".to_string(),
+                crate::tool::ParameterMapping {
+                    sanitized_name: "id".to_string(),
+                    original_name: "id".to_string(),
+                    location: "path".to_string(),
+                    explode,
+                    style,
+                    allow_reserved: false,
+                },
+            );
+
+            let tool_metadata = crate::ToolMetadata {
+                name: "test".to_string(),
+                title: None,
+                description: Some("test".to_string()),
+                parameters: json!({}),
+                output_schema: None,
+                method: "GET".to_string(),
+                path: "/items/{id}".to_string(),
+                tags: vec![],
+                security: None,
+                parameter_mappings,
+            };
+
+            let mut path_params = HashMap::new();
+            path_params.insert("id".to_string(), value.clone());
+
+            let extracted_params = ExtractedParameters {
+                path: path_params,
+                query: HashMap::new(),
+                headers: HashMap::new(),
+                cookies: HashMap::new(),
+                body: HashMap::new(),
+                config: crate::tool_generator::RequestConfig::default(),
+            };
+
+            let url = client.build_url(&tool_metadata, &extracted_params).unwrap();
+            assert_eq!(
+                url.path(),
+                expected_path,
+                "style={style:?}, explode={explode}, value={value}"
+            );
+        }
+    }
+
     #[test]
     fn test_url_join_edge_cases() {
         // Test trailing slash handling
@@ -1359,6 +3670,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/pets".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1397,6 +3709,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/search".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1475,6 +3788,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/search".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -1519,6 +3833,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_structured_headers_are_opt_in_and_redacted() {
+        let response = HttpResponse {
+            status_code: 201,
+            status_text: "Created".to_string(),
+            headers: HashMap::from([
+                ("location".to_string(), "/pets/1".to_string()),
+                ("x-api-token".to_string(), "s3cret".to_string()),
+            ]),
+            content_type: None,
+            body: String::new(),
+            body_bytes: None,
+            is_success: true,
+            request_method: "POST".to_string(),
+            request_url: "http://example.com/pets".to_string(),
+            request_body: String::new(),
+            cache_status: None,
+            truncated_at: None,
+        };
+        assert!(HttpClient::new().structured_headers(&response).is_none());
+
+        let client = HttpClient::new()
+            .with_response_headers(true)
+            .with_redaction(
+                RedactionRules::builder()
+                    .headers(vec!["X-Api-Token".to_string()])
+                    .build(),
+            );
+        let headers = client.structured_headers(&response).unwrap();
+        assert_eq!(headers["location"], "/pets/1");
+        assert_eq!(headers["x-api-token"], REDACTED);
+    }
+
     #[test]
     fn test_is_image_helper() {
         // Test various image content types
@@ -1533,6 +3880,8 @@ mod tests {
             request_method: "GET".to_string(),
             request_url: "http://example.com".to_string(),
             request_body: String::new(),
+            cache_status: None,
+            truncated_at: None,
         };
         assert!(response_png.is_image());
 
@@ -1570,6 +3919,34 @@ mod tests {
         assert!(!response_no_ct.is_image());
     }
 
+    #[test]
+    fn test_is_audio_and_video_helpers() {
+        let response = HttpResponse {
+            status_code: 200,
+            status_text: "OK".to_string(),
+            headers: HashMap::new(),
+            content_type: Some("audio/mpeg".to_string()),
+            body: String::new(),
+            body_bytes: None,
+            is_success: true,
+            request_method: "GET".to_string(),
+            request_url: "http://example.com".to_string(),
+            request_body: String::new(),
+            cache_status: None,
+            truncated_at: None,
+        };
+        assert!(response.is_audio());
+        assert!(!response.is_video());
+
+        let response = HttpResponse {
+            content_type: Some("video/mp4".to_string()),
+            ..response
+        };
+        assert!(response.is_video());
+        assert!(!response.is_audio());
+        assert!(!response.is_image());
+    }
+
     #[test]
     fn test_is_binary_helper() {
         let base_response = HttpResponse {
@@ -1583,6 +3960,8 @@ mod tests {
             request_method: "GET".to_string(),
             request_url: "http://example.com".to_string(),
             request_body: String::new(),
+            cache_status: None,
+            truncated_at: None,
         };
 
         // Test image types
@@ -1612,6 +3991,26 @@ mod tests {
             ..base_response.clone()
         };
         assert!(!response_json.is_binary());
+        for content_type in ["application/problem+json", "application/xml; charset=utf-8"] {
+            let response = HttpResponse {
+                content_type: Some(content_type.to_string()),
+                ..base_response.clone()
+            };
+            assert!(!response.is_binary(), "{content_type}");
+        }
+
+        // Test documents and archives
+        for content_type in [
+            "application/pdf",
+            "application/zip",
+            "application/octet-stream",
+        ] {
+            let response = HttpResponse {
+                content_type: Some(content_type.to_string()),
+                ..base_response.clone()
+            };
+            assert!(response.is_binary(), "{content_type}");
+        }
 
         // Test with no content type
         assert!(!base_response.is_binary());
@@ -1816,7 +4215,7 @@ mod tests {
         body.insert("description".to_string(), json!("Test file upload"));
 
         let config = crate::tool_generator::RequestConfig {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::MULTIPART_FORM_DATA.to_string(),
         };
 
@@ -1843,7 +4242,7 @@ mod tests {
         );
 
         let config = crate::tool_generator::RequestConfig {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::MULTIPART_FORM_DATA.to_string(),
         };
 
@@ -1872,7 +4271,7 @@ mod tests {
         );
 
         let config = crate::tool_generator::RequestConfig {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::MULTIPART_FORM_DATA.to_string(),
         };
 
@@ -1896,7 +4295,7 @@ mod tests {
         body.insert("field3".to_string(), json!(true));
 
         let config = crate::tool_generator::RequestConfig {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::MULTIPART_FORM_DATA.to_string(),
         };
 
@@ -1926,7 +4325,7 @@ mod tests {
         body.insert("tags".to_string(), json!(["nature", "sunset"]));
 
         let config = crate::tool_generator::RequestConfig {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::MULTIPART_FORM_DATA.to_string(),
         };
 
@@ -1949,7 +4348,7 @@ mod tests {
         );
 
         let config = crate::tool_generator::RequestConfig {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::MULTIPART_FORM_DATA.to_string(),
         };
 
@@ -1970,7 +4369,7 @@ mod tests {
         body.insert("value".to_string(), json!(42));
 
         let config = crate::tool_generator::RequestConfig {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::APPLICATION_JSON.to_string(),
         };
 
@@ -1988,7 +4387,7 @@ mod tests {
         body.insert("password".to_string(), json!("secret"));
 
         let config = crate::tool_generator::RequestConfig {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::APPLICATION_WWW_FORM_URLENCODED.to_string(),
         };
 
@@ -1996,6 +4395,44 @@ mod tests {
         assert!(result.is_ok(), "Should build form-urlencoded body");
     }
 
+    #[test]
+    fn test_default_header_placeholders() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("{uuid}"));
+        headers.insert(
+            "x-caller",
+            HeaderValue::from_static("mcp-{session_id}/{tool}"),
+        );
+        headers.insert("x-filter", HeaderValue::from_static("{\"a\":1}"));
+        let client = HttpClient::new().with_default_headers(headers);
+        let build = |client: &HttpClient| {
+            client
+                .add_default_headers(client.client.get("http://example.com/"), "getPet")
+                .build()
+                .unwrap()
+        };
+
+        let request = build(&client);
+        assert!(request.headers().get("x-caller").is_none());
+        assert_eq!(request.headers()["x-filter"], "{\"a\":1}");
+        let request_id = request.headers()["x-request-id"].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(request_id).is_ok());
+        assert_ne!(build(&client).headers()["x-request-id"], request_id);
+
+        let request = build(&client.with_session_id("s-1"));
+        assert_eq!(request.headers()["x-caller"], "mcp-s-1/getPet");
+    }
+
+    #[test]
+    fn test_forwarded_authorization_is_sensitive() {
+        let client = HttpClient::new().with_authorization("Bearer secret");
+        let request = client
+            .add_default_headers(client.client.get("http://example.com/"), "getPet")
+            .build()
+            .unwrap();
+        assert!(request.headers()[header::AUTHORIZATION].is_sensitive());
+    }
+
     #[tokio::test]
     async fn http_client_with_insecure_still_serves_plain_http() {
         let mut server = mockito::Server::new_async().await;
@@ -2020,6 +4457,37 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn http_client_keeps_connection_config_across_rebuilds() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/ping")
+            .with_status(200)
+            .with_body("pong")
+            .create_async()
+            .await;
+
+        let connection = ConnectionConfig::builder()
+            .pool_max_idle_per_host(1)
+            .pool_idle_timeout(Duration::from_secs(5))
+            .connect_timeout(Duration::from_secs(2))
+            .tcp_keepalive(Duration::from_secs(30))
+            .build();
+        let base_url: Url = server.url().parse().unwrap();
+        let client = HttpClient::new()
+            .with_connection_config(connection.clone())
+            .with_insecure(true)
+            .with_base_url(base_url.clone())
+            .unwrap();
+        assert_eq!(client.transport.connection, connection);
+
+        let url = base_url.join("ping").unwrap();
+        let response = client.client.get(url).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        mock.assert_async().await;
+    }
+
     #[test]
     fn test_add_request_body_empty() {
         let client = HttpClient::new();
@@ -2032,4 +4500,58 @@ mod tests {
         let result = HttpClient::add_request_body(request, &body, &config);
         assert!(result.is_ok(), "Should handle empty body");
     }
+
+    /// Executor answering in process and keeping the URLs it was sent
+    #[derive(Default)]
+    struct RecordingExecutor {
+        urls: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpExecutor for RecordingExecutor {
+        async fn execute(
+            &self,
+            request: reqwest::Request,
+        ) -> Result<reqwest::Response, TransportError> {
+            self.urls.lock().unwrap().push(request.url().to_string());
+            let response = http::Response::builder()
+                .status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"id":1}"#)
+                .map_err(|e| TransportError::Other(e.to_string()))?;
+            Ok(reqwest::Response::from(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_sends_upstream_requests() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let client = HttpClient::new()
+            .with_base_url(Url::parse("http://unreachable.invalid/v1").unwrap())
+            .unwrap()
+            .with_executor(executor.clone());
+        let tool_metadata = crate::ToolMetadata {
+            name: "listPets".to_string(),
+            title: None,
+            description: None,
+            parameters: json!({"type": "object", "properties": {}}),
+            output_schema: None,
+            method: "get".to_string(),
+            path: "/pets".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: HashMap::new(),
+        };
+
+        let response = client
+            .execute_tool_call(&tool_metadata, &json!({}))
+            .await
+            .unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.body, r#"{"id":1}"#);
+        assert_eq!(
+            *executor.urls.lock().unwrap(),
+            ["http://unreachable.invalid/v1/pets"]
+        );
+    }
 }
@@ -1,24 +1,116 @@
+pub mod audit;
+pub mod auth;
+pub mod batch;
+pub mod blob;
+pub mod budget;
+pub mod cache;
+pub mod cassette;
+pub mod circuit_breaker;
+pub mod concurrency;
 pub mod config;
+pub mod confirmation;
+pub mod custom_tool;
+pub mod describe;
+pub mod disabled;
+pub mod drain;
+pub mod dry_run;
 pub mod error;
+pub mod executor;
 pub mod filter;
+pub mod html;
 pub mod http_client;
+pub mod logging;
+pub mod middleware;
+pub mod mock;
+pub mod pagination;
+pub mod pipeline;
+pub mod policy;
+pub mod progress;
+pub mod projection;
+pub mod prompts;
+pub mod queue;
+pub mod rate_limit;
+pub mod redaction;
+pub mod resources;
+pub mod retry;
+pub mod search;
+pub mod secrets;
 pub mod security;
 pub mod server;
+pub mod session;
+pub mod signing;
 pub mod spec;
+pub mod table;
+#[cfg(feature = "opentelemetry")]
+pub mod telemetry;
 pub mod tool;
 pub mod tool_generator;
+pub mod tool_hook;
 pub mod tool_registry;
 pub mod transformer;
+pub mod upstream_error;
 
-pub use config::{Authorization, AuthorizationMode};
+pub use audit::{AuditConfig, AuditLog, AuditRecord, AuditSink, DEFAULT_REDACTED_HEADERS};
+pub use auth::{
+    ApiKey, ApiKeySource, BasicAuth, ClientAuthMethod, ClientCredentials, ClientCredentialsConfig,
+    IntrospectionConfig, JwtValidationConfig, JwtValidator, ResolvedApiKey, ScopeChecker,
+    ScopeSource, TokenExchange, TokenExchangeConfig,
+};
+pub use batch::BATCH_CALL_TOOL;
+pub use blob::{BLOB_RESOURCE_PREFIX, BlobStore, BlobStoreConfig};
+pub use budget::BudgetConfig;
+pub use cache::{CacheConfig, CacheStatus, CachedResponse, InMemoryCache, ResponseCache};
+pub use cassette::{Cassette, CassetteConfig, CassetteMode};
+pub use circuit_breaker::CircuitBreakerConfig;
+pub use concurrency::ConcurrencyLimits;
+pub use config::{
+    Authorization, AuthorizationBehavior, AuthorizationMode, AuthorizationRule,
+    AuthorizationTemplate, BaseUrlRoute, ClientCertificate, CompressionConfig, ConnectionConfig,
+    ContentEncoding, DuplicateNameStrategy, HostAllowlist, HostOverride, IdempotencyConfig,
+    NullValuePolicy, OutputSchemaMode, OutputSchemaPolicy, ProxyConfig, RedirectMode,
+    RedirectPolicy, RouteSelector, ServiceToken, StatusPolicy, TimeoutPolicy, ToolSelector,
+};
+pub use confirmation::{CONFIRMATION_TOKEN_PARAMETER, ConfirmationConfig};
+pub use custom_tool::CustomTool;
+pub use describe::DESCRIBE_TOOL_TOOL;
+pub use disabled::DisabledTools;
+pub use drain::InFlightCalls;
+pub use dry_run::DRY_RUN_PARAMETER;
 pub use error::{CliError, Error, ToolCallError};
+pub use executor::{HttpExecutor, TransportError};
 pub use filter::ToolFilter;
+pub use html::HtmlConversion;
 pub use http_client::{DataUriContent, HttpClient, HttpResponse, parse_data_uri};
-pub use security::SecurityObserver;
-pub use server::Server;
-pub use spec::Spec;
-pub use tool::{Tool, ToolCollection, ToolMetadata};
+pub use logging::ClientLog;
+pub use middleware::Middleware;
+pub use pagination::PaginationConfig;
+pub use pipeline::{PipelineConfig, TransformStep, TransformerPipeline};
+pub use policy::{ParameterConstraint, ParameterPolicy};
+pub use progress::CallProgress;
+pub use projection::RESPONSE_FILTER_PARAMETER;
+pub use queue::{CallQueue, QueueConfig};
+pub use rate_limit::{RateLimit, RateLimitConfig, RateLimitMode};
+pub use redaction::{JsonPath, RedactionRules};
+pub use resources::SPEC_RESOURCE_URI;
+pub use retry::{RetryHint, RetryPolicy};
+pub use search::SEARCH_TOOLS_TOOL;
+pub use secrets::{EnvSecrets, FileSecrets, SecretProvider, SecretResolver};
+pub use security::{
+    FileEventSink, SecurityEvent, SecurityEventKind, SecurityEventSink, SecurityObserver,
+    WebhookEventSink,
+};
+pub use server::{Server, SessionId};
+pub use session::{CONFIGURE_SESSION_TOOL, SESSION_META_KEY, SessionSettings};
+pub use signing::{
+    HmacAlgorithm, HmacSigner, RequestSigner, SignatureComponent, SignatureEncoding,
+};
+pub use spec::{Filter, Filters, Spec};
+pub use table::TableConfig;
+#[cfg(feature = "opentelemetry")]
+pub use telemetry::TraceContext;
+pub use tool::{SecurityRequirement, Tool, ToolCollection, ToolMetadata};
 pub use tool_generator::{ExtractedParameters, RequestConfig, ToolGenerator};
+pub use tool_hook::ToolHook;
 pub use tool_registry::{ToolRegistry, ToolRegistryStats};
 pub use transformer::ResponseTransformer;
 
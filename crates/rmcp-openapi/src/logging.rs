@@ -0,0 +1,148 @@
+//! Operational messages streamed to MCP clients.
+//!
+//! Clients subscribe with a `logging/setLevel` request and then receive, as
+//! `notifications/message`, the messages of that level or more severe:
+//!
+//! - `security`: the [security events](crate::security) of their tool calls,
+//!   denied calls being warnings
+//! - `upstream`: the `5xx` responses of the upstream API, as errors
+//! - any logger the application adds, such as spec reloads
+//!
+//! Messages are sent to every subscribed client, whatever session the call
+//! was made in.
+
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::service::{Peer, RoleServer};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+use crate::security::{SecurityEvent, SecurityEventKind, SecurityEventSink};
+
+/// Clients subscribed to log messages, shared by the clones of a server
+#[derive(Clone, Default)]
+pub struct ClientLog {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+}
+
+struct Subscriber {
+    /// MCP session of the client, when the transport provides one
+    session_id: Option<String>,
+    peer: Peer<RoleServer>,
+    level: LoggingLevel,
+}
+
+impl ClientLog {
+    /// Send the messages of `level` or more severe to `peer`, replacing the
+    /// subscription of its session
+    pub(crate) fn subscribe(
+        &self,
+        peer: Peer<RoleServer>,
+        session_id: Option<&str>,
+        level: LoggingLevel,
+    ) {
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.retain(|subscriber| {
+            !subscriber.peer.is_transport_closed()
+                && (session_id.is_none() || subscriber.session_id.as_deref() != session_id)
+        });
+        subscribers.push(Subscriber {
+            session_id: session_id.map(String::from),
+            peer,
+            level,
+        });
+    }
+
+    /// Number of clients subscribed
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    /// Send `data` from `logger` to the clients subscribed at `level` or below
+    pub fn log(&self, level: LoggingLevel, logger: &str, data: Value) {
+        let peers: Vec<_> = {
+            let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+            subscribers.retain(|subscriber| !subscriber.peer.is_transport_closed());
+            subscribers
+                .iter()
+                .filter(|subscriber| severity(level) >= severity(subscriber.level))
+                .map(|subscriber| subscriber.peer.clone())
+                .collect()
+        };
+        if peers.is_empty() {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!(logger, "No async runtime to send log messages to clients");
+            return;
+        };
+        for peer in peers {
+            let notification = LoggingMessageNotificationParam {
+                level,
+                logger: Some(logger.to_string()),
+                data: data.clone(),
+            };
+            runtime.spawn(async move {
+                // Deprecated by SEP-2577, but still the channel clients read
+                #[allow(deprecated)]
+                let sent = peer.notify_logging_message(notification).await;
+                if let Err(e) = sent {
+                    debug!(error = %e, "Unable to send a log message to the client");
+                }
+            });
+        }
+    }
+}
+
+impl SecurityEventSink for ClientLog {
+    fn send(&self, event: &SecurityEvent) {
+        let data = serde_json::to_value(event).unwrap_or_else(|e| json!(e.to_string()));
+        self.log(security_level(&event.kind), "security", data);
+    }
+}
+
+/// Level of the log messages reporting security events of `kind`
+fn security_level(kind: &SecurityEventKind) -> LoggingLevel {
+    match kind {
+        SecurityEventKind::Passthrough | SecurityEventKind::ServiceToken => LoggingLevel::Debug,
+        SecurityEventKind::CallDenied { .. } | SecurityEventKind::UpstreamDenied { .. } => {
+            LoggingLevel::Warning
+        }
+    }
+}
+
+/// Rank of `level`, from the least to the most severe
+fn severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denied_calls_are_warnings() {
+        assert_eq!(
+            security_level(&SecurityEventKind::ServiceToken),
+            LoggingLevel::Debug
+        );
+        assert_eq!(
+            security_level(&SecurityEventKind::UpstreamDenied { status: 403 }),
+            LoggingLevel::Warning
+        );
+        assert!(severity(LoggingLevel::Warning) >= severity(LoggingLevel::Info));
+        assert!(severity(LoggingLevel::Debug) < severity(LoggingLevel::Info));
+    }
+}
@@ -0,0 +1,73 @@
+//! Request/response middleware for upstream HTTP calls.
+//!
+//! This module provides the [`Middleware`] trait for hooking into every tool
+//! call made by [`HttpClient`](crate::HttpClient): adding custom
+//! authentication, propagating tracing context, rewriting requests or
+//! post-processing responses without forking the client.
+//!
+//! # Example
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use rmcp_openapi::{HttpResponse, Middleware, ToolCallError, ToolMetadata};
+//!
+//! /// Middleware that tags requests with the tool name
+//! struct ToolNameHeader;
+//!
+//! #[async_trait]
+//! impl Middleware for ToolNameHeader {
+//!     async fn on_request(
+//!         &self,
+//!         tool: &ToolMetadata,
+//!         request: &mut reqwest::Request,
+//!     ) -> Result<(), ToolCallError> {
+//!         request
+//!             .headers_mut()
+//!             .insert("x-mcp-tool", tool.name.parse().unwrap());
+//!         Ok(())
+//!     }
+//! }
+//! ```
+
+use async_trait::async_trait;
+use reqwest::Request;
+
+use crate::error::ToolCallError;
+use crate::http_client::HttpResponse;
+use crate::tool::ToolMetadata;
+
+/// Hook run around every upstream request of a tool call.
+///
+/// Middlewares run in the order they were added, for requests and responses
+/// alike. Both methods default to doing nothing, so implementors only
+/// override the side they care about.
+///
+/// # Behavior
+///
+/// - `on_request` sees the request once parameters, headers and body are in
+///   place, before the response cache lookup, rate limiting and request
+///   signing. Changes are therefore part of the cache key and covered by the
+///   signature.
+/// - `on_response` sees every successful outcome, including responses served
+///   from the cache.
+/// - Returning an error from either method fails the tool call with it.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Inspect or modify the outgoing request
+    async fn on_request(
+        &self,
+        _tool: &ToolMetadata,
+        _request: &mut Request,
+    ) -> Result<(), ToolCallError> {
+        Ok(())
+    }
+
+    /// Inspect or modify the response before it is turned into a tool result
+    async fn on_response(
+        &self,
+        _tool: &ToolMetadata,
+        _response: &mut HttpResponse,
+    ) -> Result<(), ToolCallError> {
+        Ok(())
+    }
+}
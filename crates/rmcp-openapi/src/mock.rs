@@ -0,0 +1,239 @@
+//! Fabricated upstream responses for mock mode.
+//!
+//! With mock responses enabled, tool calls are validated and turned into a
+//! request as usual but never sent: the response body is generated from the
+//! tool's output schema instead. Schema `example`, `examples`, `default`,
+//! `const` and `enum` values are used when present, so specs with good examples
+//! produce realistic data; otherwise a placeholder of the right type is built.
+//! This lets agent flows be exercised before the real backend exists.
+//!
+//! Mock responses carry status `200` and an `X-Rmcp-Mock: true` header.
+
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
+use crate::http_client::HttpResponse;
+use crate::tool::ToolMetadata;
+
+/// Header marking fabricated responses
+pub const MOCK_HEADER: &str = "x-rmcp-mock";
+
+/// Nesting depth after which nested values are left empty
+const MAX_DEPTH: usize = 8;
+
+/// Fabricate the response of a tool call from its output schema
+pub(crate) fn mock_response(
+    tool_metadata: &ToolMetadata,
+    url: &str,
+    request_body: &str,
+) -> HttpResponse {
+    // Output schemas wrap the operation's schema as `body.oneOf[0]`, next to the error variant
+    let body = tool_metadata
+        .output_schema
+        .as_ref()
+        .and_then(|schema| schema.pointer("/properties/body"))
+        .map(|body| body.pointer("/oneOf/0").unwrap_or(body))
+        .map_or_else(|| json!({}), mock_value);
+
+    HttpResponse {
+        status_code: 200,
+        status_text: "OK".to_string(),
+        headers: HashMap::from([
+            ("content-type".to_string(), "application/json".to_string()),
+            (MOCK_HEADER.to_string(), "true".to_string()),
+        ]),
+        content_type: Some("application/json".to_string()),
+        body: body.to_string(),
+        body_bytes: None,
+        is_success: true,
+        request_method: tool_metadata.method.clone(),
+        request_url: url.to_string(),
+        request_body: request_body.to_string(),
+        cache_status: None,
+        truncated_at: None,
+    }
+}
+
+/// Generate a value matching a JSON schema
+#[must_use]
+pub fn mock_value(schema: &Value) -> Value {
+    mock_value_at_depth(schema, 0)
+}
+
+fn mock_value_at_depth(schema: &Value, depth: usize) -> Value {
+    let Some(schema) = schema.as_object() else {
+        return Value::Null;
+    };
+
+    if let Some(example) = schema.get("example") {
+        return example.clone();
+    }
+    if let Some(example) = schema
+        .get("examples")
+        .and_then(Value::as_array)
+        .and_then(|examples| examples.first())
+    {
+        return example.clone();
+    }
+    if let Some(value) = schema.get("default").or_else(|| schema.get("const")) {
+        return value.clone();
+    }
+    if let Some(value) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return value.clone();
+    }
+
+    if let Some(variant) = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|key| schema.get(*key)?.as_array()?.first())
+    {
+        return mock_value_at_depth(variant, depth);
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for part in parts {
+            match mock_value_at_depth(part, depth) {
+                Value::Object(object) => merged.extend(object),
+                other if merged.is_empty() => return other,
+                _ => {}
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    let schema_type = match schema.get("type") {
+        Some(Value::String(t)) => Some(t.as_str()),
+        // Nullable types are written as `["string", "null"]`
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null")
+            .or(Some("null")),
+        _ if schema.contains_key("properties") => Some("object"),
+        _ if schema.contains_key("items") => Some("array"),
+        _ => None,
+    };
+
+    match schema_type {
+        Some("object") | None => {
+            let mut object = Map::new();
+            if depth < MAX_DEPTH
+                && let Some(properties) = schema.get("properties").and_then(Value::as_object)
+            {
+                for (name, property) in properties {
+                    object.insert(name.clone(), mock_value_at_depth(property, depth + 1));
+                }
+            }
+            Value::Object(object)
+        }
+        Some("array") => {
+            let count = schema
+                .get("minItems")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .max(1);
+            match schema.get("items").filter(|_| depth < MAX_DEPTH) {
+                Some(items) => Value::Array(
+                    (0..count)
+                        .map(|_| mock_value_at_depth(items, depth + 1))
+                        .collect(),
+                ),
+                None => Value::Array(Vec::new()),
+            }
+        }
+        Some("string") => Value::String(mock_string(schema)),
+        Some("integer") => json!(
+            schema
+                .get("minimum")
+                .and_then(Value::as_i64)
+                .unwrap_or_default()
+        ),
+        Some("number") => json!(
+            schema
+                .get("minimum")
+                .and_then(Value::as_f64)
+                .unwrap_or_default()
+        ),
+        Some("boolean") => Value::Bool(true),
+        Some(_) => Value::Null,
+    }
+}
+
+/// Placeholder string honouring common formats
+fn mock_string(schema: &Map<String, Value>) -> String {
+    match schema.get("format").and_then(Value::as_str) {
+        Some("date-time") => "2024-01-01T00:00:00Z".to_string(),
+        Some("date") => "2024-01-01".to_string(),
+        Some("time") => "00:00:00Z".to_string(),
+        Some("email") => "user@example.com".to_string(),
+        Some("uri" | "url") => "https://example.com".to_string(),
+        Some("hostname") => "example.com".to_string(),
+        Some("ipv4") => "192.0.2.1".to_string(),
+        Some("ipv6") => "2001:db8::1".to_string(),
+        Some("uuid") => "00000000-0000-0000-0000-000000000000".to_string(),
+        _ => "string".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_take_precedence() {
+        assert_eq!(
+            mock_value(&json!({"type": "string", "example": "Rex"})),
+            json!("Rex")
+        );
+        assert_eq!(
+            mock_value(&json!({"type": "integer", "examples": [7, 8]})),
+            json!(7)
+        );
+        assert_eq!(
+            mock_value(&json!({"type": "string", "enum": ["available", "sold"]})),
+            json!("available")
+        );
+    }
+
+    #[test]
+    fn placeholders_follow_the_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "integer", "minimum": 1 },
+                "name": { "type": "string" },
+                "created": { "type": "string", "format": "date-time" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "owner": { "type": ["object", "null"], "properties": { "vip": { "type": "boolean" } } }
+            }
+        });
+        assert_eq!(
+            mock_value(&schema),
+            json!({
+                "id": 1,
+                "name": "string",
+                "created": "2024-01-01T00:00:00Z",
+                "tags": ["string"],
+                "owner": { "vip": true }
+            })
+        );
+    }
+
+    #[test]
+    fn composed_schemas() {
+        assert_eq!(
+            mock_value(&json!({"oneOf": [{"type": "integer"}, {"type": "string"}]})),
+            json!(0)
+        );
+        assert_eq!(
+            mock_value(&json!({"allOf": [
+                {"properties": {"a": {"type": "integer"}}},
+                {"properties": {"b": {"type": "boolean"}}}
+            ]})),
+            json!({"a": 0, "b": true})
+        );
+    }
+}
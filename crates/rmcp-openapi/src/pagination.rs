@@ -0,0 +1,407 @@
+//! Following the pages of paginated listings.
+//!
+//! With pagination enabled, a successful `GET` tool call whose response points
+//! to a next page is repeated for the following pages, up to `max_pages` in
+//! total, and the items of every page are merged into one result. The next
+//! page is found from, in order:
+//!
+//! - a `Link` header with `rel="next"`, whose query parameters the tool accepts
+//! - a cursor field of the body, such as `next_cursor` or `nextPageToken`,
+//!   when the tool has a cursor parameter such as `cursor` or `page_token`
+//! - a `page` parameter, incremented while pages are full
+//!
+//! The items are those of a body that is an array, or of the array field of
+//! an object body (`items`, `data`, `results`, ...); the other fields are
+//! taken from the last page fetched. Following stops at the first page that
+//! fails or cannot be merged. Structured results report the number of pages
+//! merged as `pages_fetched`.
+
+use bon::Builder;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::error::ToolCallError;
+use crate::http_client::HttpClient;
+use crate::tool::ToolMetadata;
+
+/// Body fields holding the items of a page, by preference
+const ITEM_FIELDS: [&str; 7] = [
+    "items", "data", "results", "records", "entries", "values", "content",
+];
+
+/// Body fields holding the cursor of the next page
+const CURSOR_FIELDS: [&str; 8] = [
+    "next_cursor",
+    "nextCursor",
+    "next_page_token",
+    "nextPageToken",
+    "next_token",
+    "nextToken",
+    "cursor",
+    "next",
+];
+
+/// Objects of the body the cursor fields may be nested in
+const CURSOR_CONTAINERS: [&str; 4] = ["meta", "pagination", "paging", "response_metadata"];
+
+/// Query parameters taking the cursor of the next page
+const CURSOR_PARAMETERS: [&str; 7] = [
+    "cursor",
+    "page_token",
+    "pageToken",
+    "next_token",
+    "nextToken",
+    "starting_after",
+    "after",
+];
+
+/// Query parameters giving the size of a page
+const PAGE_SIZE_PARAMETERS: [&str; 5] = ["per_page", "perPage", "page_size", "pageSize", "limit"];
+
+/// How many pages of a listing are followed
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct PaginationConfig {
+    /// Pages fetched at most per tool call, the first one included
+    #[builder(default = 10)]
+    pub max_pages: usize,
+}
+
+/// Fetch the pages following `body`, the first page of the listing returned
+/// for `arguments`, and merge their items into it
+///
+/// Returns the merged body and the number of pages it holds.
+///
+/// # Errors
+///
+/// Returns an error if the request for a following page cannot be made, such
+/// as when a middleware rejects it
+pub(crate) async fn follow(
+    client: &HttpClient,
+    metadata: &ToolMetadata,
+    arguments: &Value,
+    headers: &HashMap<String, String>,
+    body: Value,
+    config: &PaginationConfig,
+) -> Result<(Value, usize), ToolCallError> {
+    let mut merged = body.clone();
+    let mut pages_fetched = 1;
+    let mut page = body;
+    let mut headers = headers.clone();
+    let mut arguments = arguments.clone();
+    while pages_fetched < config.max_pages {
+        let Some(next) = next_arguments(metadata, &arguments, &headers, &page) else {
+            break;
+        };
+        debug!(tool = %metadata.name, page = pages_fetched + 1, "Following next page");
+        let response = client.execute_tool_call(metadata, &next).await?;
+        if !response.is_success || response.truncated_at.is_some() {
+            warn!(
+                tool = %metadata.name,
+                status = response.status_code,
+                "Stopped following pages at a failed page"
+            );
+            break;
+        }
+        let Ok(body) = response.json() else {
+            break;
+        };
+        if !merge(&mut merged, body.clone()) {
+            break;
+        }
+        pages_fetched += 1;
+        page = body;
+        headers = response.headers;
+        arguments = next;
+    }
+    Ok((merged, pages_fetched))
+}
+
+/// Arguments requesting the page after `page`, returned for `arguments`
+fn next_arguments(
+    metadata: &ToolMetadata,
+    arguments: &Value,
+    headers: &HashMap<String, String>,
+    page: &Value,
+) -> Option<Value> {
+    let mut next = arguments.as_object()?.clone();
+    let changed = if let Some(link) = next_link(headers) {
+        follow_link(metadata, &mut next, &link)
+    } else if let Some(cursor) = next_cursor(page) {
+        query_parameter(metadata, &CURSOR_PARAMETERS)
+            .is_some_and(|name| set(&mut next, name, Value::String(cursor)))
+    } else {
+        next_page_number(metadata, &mut next, page)
+    };
+    changed.then_some(Value::Object(next))
+}
+
+/// Target of the `rel="next"` link of a `Link` header
+fn next_link(headers: &HashMap<String, String>) -> Option<String> {
+    let value = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("link"))?
+        .1;
+    value.split(',').find_map(|link| {
+        let (target, params) = link.trim().split_once(';')?;
+        let is_next = params.split(';').any(|param| {
+            param
+                .trim()
+                .strip_prefix("rel=")
+                .is_some_and(|rel| rel.trim_matches('"').split(' ').any(|rel| rel == "next"))
+        });
+        is_next.then(|| {
+            target
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string()
+        })
+    })
+}
+
+/// Set the arguments of the query parameters of `link` the tool accepts
+fn follow_link(metadata: &ToolMetadata, arguments: &mut Map<String, Value>, link: &str) -> bool {
+    // Relative links only need their query
+    let Ok(url) = Url::parse(link).or_else(|_| Url::parse("http://localhost")?.join(link)) else {
+        return false;
+    };
+    let mut changed = false;
+    for (key, value) in url.query_pairs() {
+        if let Some(name) = query_parameter(metadata, &[key.as_ref()]) {
+            changed |= set(arguments, name, typed(metadata, name, &value));
+        }
+    }
+    changed
+}
+
+/// Cursor of the next page in `page`, at the top level or in a container
+fn next_cursor(page: &Value) -> Option<String> {
+    let page = page.as_object()?;
+    std::iter::once(page)
+        .chain(
+            CURSOR_CONTAINERS
+                .iter()
+                .filter_map(|container| page.get(*container)?.as_object()),
+        )
+        .find_map(|object| {
+            CURSOR_FIELDS
+                .iter()
+                .find_map(|field| match object.get(*field)? {
+                    Value::String(cursor) if !cursor.is_empty() && !cursor.contains("://") => {
+                        Some(cursor.clone())
+                    }
+                    _ => None,
+                })
+        })
+}
+
+/// Increment the `page` argument, unless `page` is the last one
+fn next_page_number(
+    metadata: &ToolMetadata,
+    arguments: &mut Map<String, Value>,
+    page: &Value,
+) -> bool {
+    let Some(name) = query_parameter(metadata, &["page"]) else {
+        return false;
+    };
+    let Some(items) = items(page).map(Vec::len).filter(|count| *count > 0) else {
+        return false;
+    };
+    let page_size = query_parameter(metadata, &PAGE_SIZE_PARAMETERS)
+        .and_then(|name| as_u64(arguments.get(name)?));
+    if page_size.is_some_and(|size| (items as u64) < size) {
+        return false;
+    }
+    let current = arguments.get(name).and_then(as_u64).unwrap_or(1);
+    set(arguments, name, json!(current + 1))
+}
+
+/// Name of the first of the query parameters `originals` the tool has
+fn query_parameter<'a>(metadata: &'a ToolMetadata, originals: &[&str]) -> Option<&'a str> {
+    originals.iter().find_map(|original| {
+        metadata
+            .parameter_mappings
+            .values()
+            .find(|mapping| mapping.location == "query" && mapping.original_name == *original)
+            .map(|mapping| mapping.sanitized_name.as_str())
+    })
+}
+
+/// `value` as an argument of parameter `name`, a number if its schema says so
+fn typed(metadata: &ToolMetadata, name: &str, value: &str) -> Value {
+    let kind = metadata
+        .parameters
+        .pointer(&format!("/properties/{name}/type"))
+        .and_then(Value::as_str);
+    match kind {
+        Some("integer") => value
+            .parse::<i64>()
+            .map_or_else(|_| json!(value), Value::from),
+        Some("number") => value
+            .parse::<f64>()
+            .map_or_else(|_| json!(value), Value::from),
+        _ => Value::String(value.to_string()),
+    }
+}
+
+/// Set argument `name` to `value`, returning whether it changed
+fn set(arguments: &mut Map<String, Value>, name: &str, value: Value) -> bool {
+    arguments.insert(name.to_string(), value.clone()) != Some(value)
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|value| value.parse().ok()))
+}
+
+/// Items of a page
+pub(crate) fn items(page: &Value) -> Option<&Vec<Value>> {
+    match page {
+        Value::Array(items) => Some(items),
+        Value::Object(page) => page.get(item_field(page)?)?.as_array(),
+        _ => None,
+    }
+}
+
+/// Field of an object page holding its items: a known field, or its only array
+fn item_field(page: &Map<String, Value>) -> Option<&str> {
+    let known = ITEM_FIELDS
+        .iter()
+        .find(|field| page.get(**field).is_some_and(Value::is_array))
+        .copied();
+    known.or_else(|| {
+        let mut arrays = page.iter().filter(|(_, value)| value.is_array());
+        match (arrays.next(), arrays.next()) {
+            (Some((field, _)), None) => Some(field.as_str()),
+            _ => None,
+        }
+    })
+}
+
+/// Add the items of `page` to `merged`, taking its other fields from `page`
+///
+/// Returns `false`, leaving `merged` unchanged, if the pages have no items
+/// in the same place.
+fn merge(merged: &mut Value, page: Value) -> bool {
+    match (merged, page) {
+        (Value::Array(merged), Value::Array(items)) => {
+            merged.extend(items);
+            true
+        }
+        (Value::Object(merged), Value::Object(mut page)) => {
+            let Some(field) = item_field(merged).map(str::to_string) else {
+                return false;
+            };
+            let Some(Value::Array(items)) = page.remove(&field) else {
+                return false;
+            };
+            let mut all = match merged.remove(&field) {
+                Some(Value::Array(all)) => all,
+                _ => Vec::new(),
+            };
+            all.extend(items);
+            merged.extend(page);
+            merged.insert(field, Value::Array(all));
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool::ParameterMapping;
+
+    fn metadata(parameters: &[(&str, &str)]) -> ToolMetadata {
+        let mappings = parameters
+            .iter()
+            .map(|(name, _)| {
+                (
+                    name.to_string(),
+                    ParameterMapping {
+                        sanitized_name: name.to_string(),
+                        original_name: name.to_string(),
+                        location: "query".to_string(),
+                        explode: true,
+                        style: None,
+                        allow_reserved: false,
+                    },
+                )
+            })
+            .collect();
+        let properties: Map<String, Value> = parameters
+            .iter()
+            .map(|(name, kind)| (name.to_string(), json!({ "type": kind })))
+            .collect();
+        ToolMetadata {
+            name: "listPets".to_string(),
+            title: None,
+            description: None,
+            parameters: json!({ "type": "object", "properties": properties }),
+            output_schema: None,
+            method: "get".to_string(),
+            path: "/pets".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: mappings,
+        }
+    }
+
+    #[test]
+    fn test_next_page_from_link_cursor_or_page_number() {
+        let metadata = metadata(&[("page", "integer"), ("per_page", "integer")]);
+        let headers = HashMap::from([(
+            "link".to_string(),
+            r#"<https://api.example.com/pets?page=3&per_page=2>; rel="next", <https://api.example.com/pets?page=9>; rel="last""#
+                .to_string(),
+        )]);
+        assert_eq!(
+            next_arguments(&metadata, &json!({ "page": 2 }), &headers, &json!([1, 2])),
+            Some(json!({ "page": 3, "per_page": 2 }))
+        );
+
+        // Page numbers go on while pages are full
+        let arguments = json!({ "per_page": 2 });
+        assert_eq!(
+            next_arguments(&metadata, &arguments, &HashMap::new(), &json!([1, 2])),
+            Some(json!({ "page": 2, "per_page": 2 }))
+        );
+        assert_eq!(
+            next_arguments(&metadata, &arguments, &HashMap::new(), &json!([1])),
+            None
+        );
+
+        let metadata = self::metadata(&[("cursor", "string")]);
+        let page = json!({ "data": [1], "meta": { "next_cursor": "abc" } });
+        assert_eq!(
+            next_arguments(&metadata, &json!({}), &HashMap::new(), &page),
+            Some(json!({ "cursor": "abc" }))
+        );
+        let page = json!({ "data": [1], "meta": { "next_cursor": null } });
+        assert_eq!(
+            next_arguments(&metadata, &json!({}), &HashMap::new(), &page),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pages_merge_their_items() {
+        let mut merged = json!([1, 2]);
+        assert!(merge(&mut merged, json!([3])));
+        assert_eq!(merged, json!([1, 2, 3]));
+
+        let mut merged = json!({ "data": [1], "next_cursor": "a" });
+        assert!(merge(
+            &mut merged,
+            json!({ "data": [2], "next_cursor": null })
+        ));
+        assert_eq!(merged, json!({ "data": [1, 2], "next_cursor": null }));
+
+        assert!(!merge(&mut merged, json!([3])));
+        assert_eq!(merged, json!({ "data": [1, 2], "next_cursor": null }));
+    }
+}
@@ -0,0 +1,366 @@
+//! Pipelines of response transformers, configured per tool or tag.
+//!
+//! A [`TransformerPipeline`] chains [`ResponseTransformer`]s, each one
+//! transforming the response body and output schema left by the previous
+//! one. Besides custom transformers, pipelines are built from the
+//! [`TransformStep`]s of a [`PipelineConfig`] file:
+//!
+//! - `truncate`: cut arrays to `max_items` and strings to `max_string_length`
+//!   characters, at any depth
+//! - `redact`: replace the values at JSONPath locations with `<redacted>`
+//! - `project`: keep the values selected by a JSONPath expression, or an
+//!   object of the values selected by each of a list of them, as the
+//!   `response_filter` parameter does (see [`crate::projection`])
+//! - `format`: `compact` drops null and empty values, `flatten` turns nested
+//!   objects into dotted keys
+//!
+//! The first pipeline of the file matching a tool, by name or tag, replaces
+//! the server's global transformer for that tool.
+//!
+//! # Example
+//!
+//! ```json
+//! {
+//!   "pipelines": [
+//!     {
+//!       "tool": "listIssues",
+//!       "steps": [
+//!         { "truncate": { "max_items": 20 } },
+//!         { "project": ["$[*].number", "$[*].title"] }
+//!       ]
+//!     },
+//!     {
+//!       "tag": "users",
+//!       "steps": [
+//!         { "redact": ["$..email"] },
+//!         { "format": "compact" }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::projection::ResponseFilter;
+use crate::redaction::JsonPath;
+use crate::tool::ToolMetadata;
+use crate::transformer::ResponseTransformer;
+
+/// Transformers applied one after the other
+#[derive(Clone, Default)]
+pub struct TransformerPipeline {
+    steps: Vec<Arc<dyn ResponseTransformer>>,
+}
+
+impl TransformerPipeline {
+    /// Pipeline of `steps`, applied in order
+    #[must_use]
+    pub fn new(steps: Vec<Arc<dyn ResponseTransformer>>) -> Self {
+        Self { steps }
+    }
+
+    /// Same pipeline, followed by `step`
+    #[must_use]
+    pub fn then(mut self, step: Arc<dyn ResponseTransformer>) -> Self {
+        self.steps.push(step);
+        self
+    }
+}
+
+impl ResponseTransformer for TransformerPipeline {
+    fn transform_response(&self, response: Value) -> Value {
+        self.steps
+            .iter()
+            .fold(response, |response, step| step.transform_response(response))
+    }
+
+    fn transform_schema(&self, schema: Value) -> Value {
+        self.steps
+            .iter()
+            .fold(schema, |schema, step| step.transform_schema(schema))
+    }
+}
+
+/// Pipelines of the tools, by name or tag
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConfig {
+    pub pipelines: Vec<PipelineRule>,
+}
+
+/// Steps transforming the responses of some tools
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineRule {
+    /// Only transform the responses of the tool with this name
+    #[serde(default)]
+    pub tool: Option<String>,
+    /// Only transform the responses of tools whose operation has this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Steps, applied in order
+    pub steps: Vec<TransformStep>,
+}
+
+/// One step of a configured pipeline
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum TransformStep {
+    /// Cut long arrays and strings
+    Truncate {
+        #[serde(default)]
+        max_items: Option<usize>,
+        #[serde(default)]
+        max_string_length: Option<usize>,
+    },
+    /// Replace the values at these locations with `<redacted>`
+    Redact(Vec<JsonPath>),
+    /// Keep only the values selected
+    Project(Projection),
+    /// Reshape the body
+    Format(Format),
+}
+
+/// Values kept by a `project` step
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Projection {
+    /// The value, or list of values, selected by one path
+    Path(JsonPath),
+    /// An object of the values selected by each path, by path
+    Paths(Vec<JsonPath>),
+}
+
+/// Shapes a `format` step gives the body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// Drop null values, empty strings, arrays and objects
+    Compact,
+    /// Turn nested objects into dotted keys, such as `owner.login`
+    Flatten,
+}
+
+impl PipelineConfig {
+    /// Read pipelines from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid
+    /// configuration, such as one with an invalid JSONPath
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            Error::Validation(format!(
+                "Invalid transformer pipelines '{}': {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Pipeline of the first rule matching `tool`
+    pub(crate) fn pipeline_for(&self, tool: &ToolMetadata) -> Option<TransformerPipeline> {
+        let rule = self.pipelines.iter().find(|rule| rule.matches(tool))?;
+        Some(TransformerPipeline::new(
+            rule.steps
+                .iter()
+                .map(|step| Arc::new(step.clone()) as Arc<dyn ResponseTransformer>)
+                .collect(),
+        ))
+    }
+}
+
+impl PipelineRule {
+    fn matches(&self, tool: &ToolMetadata) -> bool {
+        self.tool.as_ref().is_none_or(|name| *name == tool.name)
+            && self.tag.as_ref().is_none_or(|tag| tool.tags.contains(tag))
+    }
+}
+
+impl ResponseTransformer for TransformStep {
+    fn transform_response(&self, mut response: Value) -> Value {
+        match self {
+            Self::Truncate {
+                max_items,
+                max_string_length,
+            } => {
+                truncate(&mut response, *max_items, *max_string_length);
+                response
+            }
+            Self::Redact(paths) => {
+                for path in paths {
+                    path.redact(&mut response);
+                }
+                response
+            }
+            Self::Project(Projection::Path(path)) => {
+                ResponseFilter::new(vec![path.clone()], false).apply(&response)
+            }
+            Self::Project(Projection::Paths(paths)) => {
+                ResponseFilter::new(paths.clone(), true).apply(&response)
+            }
+            Self::Format(Format::Compact) => compact(response).unwrap_or(Value::Null),
+            Self::Format(Format::Flatten) => flatten(response),
+        }
+    }
+
+    fn transform_schema(&self, schema: Value) -> Value {
+        match self {
+            // Shorter arrays and strings still match the body schema
+            Self::Truncate { .. } => schema,
+            _ => unconstrained_body(schema),
+        }
+    }
+}
+
+/// Output schema accepting any body, which the step reshaped
+fn unconstrained_body(mut schema: Value) -> Value {
+    if let Some(body) = schema.pointer_mut("/properties/body") {
+        *body = json!({ "description": "Response body, reshaped by the response transformers" });
+    }
+    schema
+}
+
+fn truncate(value: &mut Value, max_items: Option<usize>, max_string_length: Option<usize>) {
+    match value {
+        Value::Array(items) => {
+            if let Some(max_items) = max_items {
+                items.truncate(max_items);
+            }
+            items
+                .iter_mut()
+                .for_each(|item| truncate(item, max_items, max_string_length));
+        }
+        Value::Object(object) => object
+            .values_mut()
+            .for_each(|value| truncate(value, max_items, max_string_length)),
+        Value::String(string) => {
+            if let Some(max_length) = max_string_length
+                && let Some((end, _)) = string.char_indices().nth(max_length)
+            {
+                string.truncate(end);
+                string.push('…');
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `value` without null and empty values, `None` if nothing is left
+fn compact(value: Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::String(string) if string.is_empty() => None,
+        Value::Array(items) => {
+            let items: Vec<_> = items.into_iter().filter_map(compact).collect();
+            (!items.is_empty()).then_some(Value::Array(items))
+        }
+        Value::Object(object) => {
+            let object: Map<_, _> = object
+                .into_iter()
+                .filter_map(|(key, value)| Some((key, compact(value)?)))
+                .collect();
+            (!object.is_empty()).then_some(Value::Object(object))
+        }
+        value => Some(value),
+    }
+}
+
+/// `value` with the members of nested objects moved up as dotted keys
+fn flatten(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut flat = Map::new();
+            flatten_into(&mut flat, None, object);
+            Value::Object(flat)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(flatten).collect()),
+        value => value,
+    }
+}
+
+fn flatten_into(flat: &mut Map<String, Value>, prefix: Option<&str>, object: Map<String, Value>) {
+    for (key, value) in object {
+        let key = prefix.map_or_else(|| key.clone(), |prefix| format!("{prefix}.{key}"));
+        match value {
+            Value::Object(object) if !object.is_empty() => flatten_into(flat, Some(&key), object),
+            value => {
+                flat.insert(key, flatten(value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_apply_in_order() {
+        let config: PipelineConfig = serde_json::from_value(json!({
+            "pipelines": [{
+                "tag": "users",
+                "steps": [
+                    { "truncate": { "max_items": 2, "max_string_length": 3 } },
+                    { "redact": ["$..email"] },
+                    { "format": "compact" },
+                    { "format": "flatten" }
+                ]
+            }]
+        }))
+        .unwrap();
+        let tool = ToolMetadata {
+            name: "listUsers".to_string(),
+            title: None,
+            description: None,
+            parameters: json!({}),
+            output_schema: None,
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            tags: vec!["users".to_string()],
+            security: None,
+            parameter_mappings: std::collections::HashMap::new(),
+        };
+        let pipeline = config.pipeline_for(&tool).unwrap();
+
+        let response = json!({
+            "users": [
+                { "name": "Alice", "email": "a@example.com", "team": { "id": 1, "lead": null } },
+                { "name": "Bob", "email": "b@example.com", "team": {} },
+                { "name": "Carol" }
+            ]
+        });
+        assert_eq!(
+            pipeline.transform_response(response),
+            json!({
+                "users": [
+                    { "name": "Ali…", "email": "<redacted>", "team.id": 1 },
+                    { "name": "Bob", "email": "<redacted>" }
+                ]
+            })
+        );
+
+        let schema = json!({ "properties": { "status": {}, "body": { "type": "object" } } });
+        assert!(
+            pipeline.transform_schema(schema)["properties"]["body"]
+                .get("type")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_projection_and_invalid_paths() {
+        let step: TransformStep =
+            serde_json::from_value(json!({ "project": "$.items[*].id" })).unwrap();
+        assert_eq!(
+            step.transform_response(json!({ "items": [{ "id": 1 }, { "id": 2 }] })),
+            json!([1, 2])
+        );
+        assert!(serde_json::from_value::<TransformStep>(json!({ "redact": ["email"] })).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+//! Operator constraints on parameter values, on top of the spec.
+//!
+//! A spec describes what an API accepts, not what an agent should be allowed
+//! to send: a listing may accept any `project_id` while the deployment is
+//! meant for project 42 only. A [`ParameterPolicy`] adds JSON Schema keywords
+//! to tool parameters, so the advertised tool schemas show the constraints
+//! and calls breaking them fail validation before reaching the upstream.
+//!
+//! # Example
+//!
+//! ```json
+//! {
+//!   "constraints": [
+//!     { "parameter": "project_id", "schema": { "const": 42 } },
+//!     { "tool": "listIssues", "parameter": "per_page", "schema": { "maximum": 100 } },
+//!     { "tag": "issues", "parameter": "request_body.labels", "schema": { "maxItems": 5 } }
+//!   ]
+//! }
+//! ```
+
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::path::Path;
+use tracing::warn;
+
+use crate::error::Error;
+use crate::tool::ToolMetadata;
+
+/// Constraints added to tool parameters
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParameterPolicy {
+    pub constraints: Vec<ParameterConstraint>,
+}
+
+/// JSON Schema keywords a parameter of some tools must also satisfy
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParameterConstraint {
+    /// Only constrain the tool with this name
+    #[serde(default)]
+    pub tool: Option<String>,
+    /// Only constrain tools whose operation has this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Parameter as named in the tool schema; nested properties are reached
+    /// with dots, as in `request_body.owner.id`
+    pub parameter: String,
+    /// Keywords added to the parameter's schema, such as `const`, `enum`,
+    /// `maximum` or `pattern`
+    pub schema: Map<String, Value>,
+}
+
+impl ParameterPolicy {
+    /// Read a policy from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a valid policy, or
+    /// holds a constraint that is not a valid JSON Schema
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)?;
+        let policy: Self = serde_json::from_str(&content).map_err(|e| {
+            Error::Validation(format!(
+                "Invalid parameter policy '{}': {e}",
+                path.display()
+            ))
+        })?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Check every constraint is a valid JSON Schema
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first invalid constraint
+    pub fn validate(&self) -> Result<(), Error> {
+        for constraint in &self.constraints {
+            jsonschema::validator_for(&Value::Object(constraint.schema.clone())).map_err(|e| {
+                Error::Validation(format!(
+                    "Invalid constraint on parameter '{}': {e}",
+                    constraint.parameter
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Add the constraints to the input schemas of `tools`
+    pub(crate) fn apply(&self, tools: &mut [ToolMetadata]) {
+        for constraint in &self.constraints {
+            let mut applied = false;
+            for tool in tools.iter_mut().filter(|tool| constraint.matches(tool)) {
+                if let Some(schema) =
+                    property_schema_mut(&mut tool.parameters, &constraint.parameter)
+                {
+                    constrain(schema, &constraint.schema);
+                    applied = true;
+                }
+            }
+            if !applied {
+                warn!(
+                    parameter = %constraint.parameter,
+                    tool = ?constraint.tool,
+                    tag = ?constraint.tag,
+                    "Parameter policy constraint matches no tool parameter"
+                );
+            }
+        }
+    }
+}
+
+impl ParameterConstraint {
+    fn matches(&self, tool: &ToolMetadata) -> bool {
+        self.tool.as_ref().is_none_or(|name| *name == tool.name)
+            && self.tag.as_ref().is_none_or(|tag| tool.tags.contains(tag))
+    }
+}
+
+/// Schema of the property at the dotted `path` of an object schema
+fn property_schema_mut<'a>(schema: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.').try_fold(schema, |schema, name| {
+        schema.get_mut("properties")?.get_mut(name)
+    })
+}
+
+/// Add `keywords` to `schema`
+///
+/// A keyword the schema already has with another value is added through
+/// `allOf`, so both the spec's and the policy's values apply.
+fn constrain(schema: &mut Value, keywords: &Map<String, Value>) {
+    let Some(schema) = schema.as_object_mut() else {
+        return;
+    };
+    for (keyword, value) in keywords {
+        match schema.get(keyword) {
+            None => {
+                schema.insert(keyword.clone(), value.clone());
+            }
+            Some(existing) if existing == value => {}
+            Some(_) => {
+                if let Some(all_of) = schema
+                    .entry("allOf")
+                    .or_insert_with(|| Value::Array(Vec::new()))
+                    .as_array_mut()
+                {
+                    all_of.push(json!({ keyword: value }));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn tool(name: &str, tags: &[&str]) -> ToolMetadata {
+        ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "per_page": { "type": "integer", "maximum": 250 },
+                    "request_body": {
+                        "type": "object",
+                        "properties": { "project_id": { "type": "integer" } }
+                    }
+                }
+            }),
+            output_schema: None,
+            method: "get".to_string(),
+            path: "/issues".to_string(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            security: None,
+            parameter_mappings: HashMap::new(),
+        }
+    }
+
+    fn policy(constraints: Value) -> ParameterPolicy {
+        serde_json::from_value(json!({ "constraints": constraints })).unwrap()
+    }
+
+    #[test]
+    fn constraints_are_added_to_matching_tools() {
+        let mut tools = vec![tool("listIssues", &["issues"]), tool("listPets", &["pets"])];
+        policy(json!([
+            { "tag": "issues", "parameter": "request_body.project_id", "schema": { "const": 42 } },
+            { "tool": "listPets", "parameter": "per_page", "schema": { "minimum": 1 } }
+        ]))
+        .apply(&mut tools);
+
+        assert_eq!(
+            tools[0].parameters["properties"]["request_body"]["properties"]["project_id"],
+            json!({ "type": "integer", "const": 42 })
+        );
+        assert!(
+            tools[1].parameters["properties"]["request_body"]["properties"]["project_id"]
+                .get("const")
+                .is_none()
+        );
+        assert_eq!(
+            tools[1].parameters["properties"]["per_page"],
+            json!({ "type": "integer", "maximum": 250, "minimum": 1 })
+        );
+    }
+
+    #[test]
+    fn conflicting_keywords_both_apply() {
+        let mut tools = vec![tool("listIssues", &[])];
+        policy(json!([{ "parameter": "per_page", "schema": { "maximum": 100 } }]))
+            .apply(&mut tools);
+        assert_eq!(
+            tools[0].parameters["properties"]["per_page"],
+            json!({ "type": "integer", "maximum": 250, "allOf": [{ "maximum": 100 }] })
+        );
+    }
+
+    #[test]
+    fn invalid_constraints_are_rejected() {
+        assert!(
+            policy(json!([{ "parameter": "per_page", "schema": { "maximum": "ten" } }]))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            serde_json::from_value::<ParameterPolicy>(json!({
+                "constraints": [{ "param": "per_page", "schema": {} }]
+            }))
+            .is_err()
+        );
+    }
+}
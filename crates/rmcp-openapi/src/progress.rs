@@ -0,0 +1,149 @@
+//! Progress notifications for slow tool calls.
+//!
+//! When a client asks for progress (with a progress token in the `_meta` of
+//! its `tools/call` request) and the call runs longer than the configured
+//! threshold, the server notifies it of the time elapsed, the retries made
+//! and the bytes of the response received so far, until the call returns.
+
+use rmcp::model::{ProgressNotificationParam, ProgressToken};
+use rmcp::service::{Peer, RoleServer};
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::debug;
+
+/// Time between notifications, once the threshold is reached
+const NOTIFICATION_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Progress of a tool call, recorded by the HTTP client making it
+#[derive(Debug, Clone, Default)]
+pub struct CallProgress {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    retries: AtomicU32,
+    bytes_received: AtomicU64,
+    /// Length of the response body, 0 while unknown
+    content_length: AtomicU64,
+}
+
+impl CallProgress {
+    /// Number of times the upstream request was retried
+    pub fn retries(&self) -> u32 {
+        self.inner.retries.load(Ordering::Relaxed)
+    }
+
+    /// Bytes of the response body received
+    pub fn bytes_received(&self) -> u64 {
+        self.inner.bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Length of the response body, once known
+    pub fn content_length(&self) -> Option<u64> {
+        Some(self.inner.content_length.load(Ordering::Relaxed)).filter(|length| *length > 0)
+    }
+
+    pub(crate) fn record_retry(&self) {
+        self.inner.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the start of a response body of `content_length` bytes
+    pub(crate) fn record_response(&self, content_length: Option<u64>) {
+        self.inner.bytes_received.store(0, Ordering::Relaxed);
+        self.inner
+            .content_length
+            .store(content_length.unwrap_or_default(), Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_bytes(&self, bytes: usize) {
+        self.inner
+            .bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Message describing the progress after `elapsed`
+    fn message(&self, elapsed: Duration) -> String {
+        let mut message = format!("Waiting for the API for {}s", elapsed.as_secs());
+        match self.retries() {
+            0 => {}
+            1 => message.push_str(", retried once"),
+            retries => message.push_str(&format!(", retried {retries} times")),
+        }
+        let received = self.bytes_received();
+        match self.content_length() {
+            Some(length) => message.push_str(&format!(", received {received} of {length} bytes")),
+            None if received > 0 => message.push_str(&format!(", received {received} bytes")),
+            None => {}
+        }
+        message
+    }
+}
+
+/// Run `call`, notifying `peer` of its `progress` under `token` every few
+/// seconds once it runs longer than `threshold`
+///
+/// The progress reported is the number of seconds elapsed, which has no
+/// total.
+pub(crate) async fn report_while<F: Future>(
+    call: F,
+    peer: &Peer<RoleServer>,
+    token: ProgressToken,
+    progress: &CallProgress,
+    threshold: Duration,
+) -> F::Output {
+    let started = Instant::now();
+    let mut call = std::pin::pin!(call);
+    let mut next = started + threshold;
+    loop {
+        match tokio::time::timeout_at(next, &mut call).await {
+            Ok(output) => return output,
+            Err(_) => {
+                let elapsed = started.elapsed();
+                let notification = ProgressNotificationParam {
+                    progress_token: token.clone(),
+                    progress: elapsed.as_secs_f64(),
+                    total: None,
+                    message: Some(progress.message(elapsed)),
+                };
+                if let Err(e) = peer.notify_progress(notification).await {
+                    debug!(error = %e, "Unable to notify the client of the tool call progress");
+                }
+                next += NOTIFICATION_INTERVAL;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_reports_retries_and_download() {
+        let progress = CallProgress::default();
+        assert_eq!(
+            progress.message(Duration::from_secs(5)),
+            "Waiting for the API for 5s"
+        );
+
+        progress.record_retry();
+        progress.record_retry();
+        progress.record_response(Some(1000));
+        progress.record_bytes(250);
+        assert_eq!(
+            progress.message(Duration::from_secs(12)),
+            "Waiting for the API for 12s, retried 2 times, received 250 of 1000 bytes"
+        );
+
+        progress.record_response(None);
+        progress.record_bytes(10);
+        assert_eq!(
+            progress.message(Duration::from_secs(13)),
+            "Waiting for the API for 13s, retried 2 times, received 10 bytes"
+        );
+    }
+}
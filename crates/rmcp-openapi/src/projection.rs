@@ -0,0 +1,235 @@
+//! Projection of response bodies on the values agents ask for.
+//!
+//! With projection enabled, every tool takes an optional
+//! [`RESPONSE_FILTER_PARAMETER`]: a JSONPath expression, or a list of them, in
+//! the subset described in [`crate::redaction`]. The server evaluates it
+//! against the JSON body of the response and returns only the values
+//! selected, as `filtered` instead of `body` in structured results, so large
+//! responses do not fill the agent's context:
+//!
+//! - a single path without wildcard nor descendant step, such as
+//!   `$.user.name`, gives its value, or `null`
+//! - any other single path, such as `$.items[*].id`, gives the list of values
+//!   it selects
+//! - a list of paths gives an object with the selection of each, by path
+//!
+//! Bodies that are not JSON are returned unchanged.
+
+use serde_json::{Map, Value, json};
+use std::borrow::Cow;
+
+use crate::error::{ToolCallError, ToolCallValidationError, ValidationError};
+use crate::redaction::JsonPath;
+use crate::tool::ToolMetadata;
+
+/// Tool parameter selecting the values of the response to return
+pub const RESPONSE_FILTER_PARAMETER: &str = "response_filter";
+
+/// Add [`RESPONSE_FILTER_PARAMETER`] to the parameters of the tool, and the
+/// `filtered` field to its output schema
+///
+/// Operations with a parameter of the same name are left unchanged.
+pub(crate) fn extend_schema(metadata: &mut ToolMetadata) {
+    if metadata
+        .parameter_mappings
+        .contains_key(RESPONSE_FILTER_PARAMETER)
+    {
+        return;
+    }
+    let Some(properties) = metadata
+        .parameters
+        .get_mut("properties")
+        .and_then(Value::as_object_mut)
+    else {
+        return;
+    };
+    properties.insert(
+        RESPONSE_FILTER_PARAMETER.to_string(),
+        json!({
+            "description": "JSONPath expression, or list of them, selecting the values of the response body to return, such as `$.items[*].id`",
+            "oneOf": [
+                { "type": "string" },
+                { "type": "array", "items": { "type": "string" }, "minItems": 1 }
+            ]
+        }),
+    );
+
+    if let Some(schema) = metadata.output_schema.as_mut() {
+        if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+            properties.insert(
+                "filtered".to_string(),
+                json!({ "description": "Values of the response body selected by `response_filter`" }),
+            );
+        }
+        if let Some(object) = schema.as_object_mut() {
+            object.insert("required".to_string(), json!(["status"]));
+        }
+    }
+}
+
+/// Values of the response body requested by a tool call
+#[derive(Debug, Clone)]
+pub(crate) struct ResponseFilter {
+    paths: Vec<JsonPath>,
+    /// Whether the selections are returned by path, for a list of paths
+    by_path: bool,
+}
+
+impl ResponseFilter {
+    /// Filter selecting `paths`, by path when `by_path` or when there are
+    /// several
+    pub(crate) fn new(paths: Vec<JsonPath>, by_path: bool) -> Self {
+        Self { paths, by_path }
+    }
+
+    /// Values of `body` selected by the filter
+    pub(crate) fn apply(&self, body: &Value) -> Value {
+        match self.paths.as_slice() {
+            [path] if !self.by_path => select(body, path),
+            paths => Value::Object(
+                paths
+                    .iter()
+                    .map(|path| (path.to_string(), select(body, path)))
+                    .collect::<Map<_, _>>(),
+            ),
+        }
+    }
+}
+
+/// Arguments to execute the call with, without the response filter, and the
+/// filter
+///
+/// # Errors
+///
+/// Returns a validation error if the filter is not a valid JSONPath or list
+/// of them
+pub(crate) fn take_filter<'a>(
+    metadata: &ToolMetadata,
+    arguments: Cow<'a, Value>,
+) -> Result<(Cow<'a, Value>, Option<ResponseFilter>), ToolCallError> {
+    let declared = !metadata
+        .parameter_mappings
+        .contains_key(RESPONSE_FILTER_PARAMETER)
+        && metadata
+            .parameters
+            .pointer(&format!("/properties/{RESPONSE_FILTER_PARAMETER}"))
+            .is_some();
+    if !declared
+        || arguments
+            .get(RESPONSE_FILTER_PARAMETER)
+            .is_none_or(Value::is_null)
+    {
+        return Ok((arguments, None));
+    }
+
+    let mut arguments = arguments.into_owned();
+    let filter = arguments
+        .as_object_mut()
+        .and_then(|args| args.remove(RESPONSE_FILTER_PARAMETER))
+        .unwrap_or_default();
+    let invalid = |message: String| {
+        ToolCallError::Validation(ToolCallValidationError::InvalidParameters {
+            violations: vec![ValidationError::ConstraintViolation {
+                parameter: RESPONSE_FILTER_PARAMETER.to_string(),
+                message,
+                field_path: None,
+                actual_value: Some(Box::new(filter.clone())),
+                expected_type: Some("JSONPath string or array of them".to_string()),
+                constraints: vec![],
+            }],
+        })
+    };
+    let paths: Vec<&str> = match &filter {
+        Value::String(path) => vec![path.as_str()],
+        Value::Array(paths) => paths.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if paths.is_empty()
+        || filter
+            .as_array()
+            .is_some_and(|all| all.len() != paths.len())
+    {
+        return Err(invalid(
+            "Expected a JSONPath expression or a list of them".to_string(),
+        ));
+    }
+    let paths = paths
+        .into_iter()
+        .map(str::parse)
+        .collect::<Result<Vec<JsonPath>, _>>()
+        .map_err(invalid)?;
+    let filter = ResponseFilter::new(paths, filter.is_array());
+    Ok((Cow::Owned(arguments), Some(filter)))
+}
+
+fn select(body: &Value, path: &JsonPath) -> Value {
+    let selected = path.select(body);
+    if path.is_definite() {
+        selected
+            .first()
+            .map_or(Value::Null, |value| (*value).clone())
+    } else {
+        Value::Array(selected.into_iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> ToolMetadata {
+        let mut metadata = ToolMetadata {
+            name: "listPets".to_string(),
+            title: None,
+            description: None,
+            parameters: json!({ "type": "object", "properties": {} }),
+            output_schema: Some(json!({
+                "type": "object",
+                "required": ["status", "body"],
+                "properties": { "status": {}, "body": {} }
+            })),
+            method: "get".to_string(),
+            path: "/pets".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: std::collections::HashMap::new(),
+        };
+        extend_schema(&mut metadata);
+        metadata
+    }
+
+    #[test]
+    fn test_filters_select_values_of_the_body() {
+        let metadata = metadata();
+        assert!(
+            metadata
+                .output_schema
+                .as_ref()
+                .unwrap()
+                .pointer("/properties/filtered")
+                .is_some()
+        );
+        let body = json!({ "items": [{ "id": 1 }, { "id": 2 }], "total": 2 });
+
+        let arguments = json!({ "limit": 2, "response_filter": "$.items[*].id" });
+        let (arguments, filter) = take_filter(&metadata, Cow::Borrowed(&arguments)).unwrap();
+        assert_eq!(*arguments, json!({ "limit": 2 }));
+        assert_eq!(filter.unwrap().apply(&body), json!([1, 2]));
+
+        let arguments = json!({ "response_filter": ["$.total", "$.missing"] });
+        let (_, filter) = take_filter(&metadata, Cow::Borrowed(&arguments)).unwrap();
+        assert_eq!(
+            filter.unwrap().apply(&body),
+            json!({ "$.total": 2, "$.missing": null })
+        );
+
+        let arguments = json!({ "limit": 2 });
+        let (_, filter) = take_filter(&metadata, Cow::Borrowed(&arguments)).unwrap();
+        assert!(filter.is_none());
+
+        for filter in [json!("items"), json!([]), json!(["$.total", 1])] {
+            let arguments = json!({ "response_filter": filter });
+            assert!(take_filter(&metadata, Cow::Borrowed(&arguments)).is_err());
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! MCP prompts bootstrapping the use of each tool.
+//!
+//! Every tool gets a prompt of the same name, described after the workflow
+//! its operation serves ("Create a new pet", "Delete a pet", ...). Its
+//! arguments are the tool's parameters, described with the type, format and
+//! allowed values of their schema, so clients can ask for them before
+//! calling the tool.
+
+use rmcp::model::{
+    ErrorData, GetPromptResult, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+};
+use serde_json::{Map, Value};
+
+use crate::tool::{Tool, ToolMetadata};
+
+/// Prompts of `tools`
+pub(crate) fn list<'a>(tools: impl IntoIterator<Item = &'a Tool>) -> Vec<Prompt> {
+    tools
+        .into_iter()
+        .map(|tool| {
+            let metadata = &tool.metadata;
+            let arguments: Vec<_> = parameters(metadata)
+                .map(|(name, schema, required)| {
+                    let mut argument = PromptArgument::new(name);
+                    argument.description = Some(hint(schema));
+                    argument.required = Some(required);
+                    argument
+                })
+                .collect();
+            Prompt::new(
+                &metadata.name,
+                Some(workflow(metadata)),
+                (!arguments.is_empty()).then_some(arguments),
+            )
+        })
+        .collect()
+}
+
+/// The prompt of `tool`, filled with `arguments`
+///
+/// # Errors
+///
+/// Returns an error if a required argument is missing
+pub(crate) fn get(
+    tool: &Tool,
+    arguments: Option<&Map<String, Value>>,
+) -> Result<GetPromptResult, ErrorData> {
+    let metadata = &tool.metadata;
+    let workflow = workflow(metadata);
+    let mut text = format!(
+        "{workflow} with the `{}` tool ({} {}).",
+        metadata.name,
+        metadata.method.to_uppercase(),
+        metadata.path
+    );
+    if let Some(description) = &metadata.description {
+        text.push_str(&format!("\n\n{description}"));
+    }
+
+    let mut values = Vec::new();
+    let mut missing = Vec::new();
+    for (name, schema, required) in parameters(metadata) {
+        match arguments.and_then(|arguments| arguments.get(name)) {
+            Some(Value::String(value)) => values.push(format!("- {name}: {value}")),
+            Some(value) => values.push(format!("- {name}: {value}")),
+            None if required => missing.push(format!("- {name}: {}", hint(schema))),
+            None => {}
+        }
+    }
+    if !values.is_empty() {
+        text.push_str(&format!("\n\nUse these values:\n{}", values.join("\n")));
+    }
+    if !missing.is_empty() {
+        return Err(ErrorData::invalid_params(
+            format!(
+                "Missing required arguments for prompt '{}':\n{}",
+                metadata.name,
+                missing.join("\n")
+            ),
+            None,
+        ));
+    }
+
+    let mut result =
+        GetPromptResult::new(vec![PromptMessage::new_text(PromptMessageRole::User, text)]);
+    result.description = Some(workflow);
+    Ok(result)
+}
+
+/// What the operation of `metadata` does, after its method and path
+fn workflow(metadata: &ToolMetadata) -> String {
+    let mut segments = metadata
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .rev();
+    let targets_one = segments
+        .clone()
+        .next()
+        .is_some_and(|segment| segment.starts_with('{'));
+    let collection = segments
+        .find(|segment| !segment.starts_with('{'))
+        .unwrap_or("resource")
+        .replace(['-', '_'], " ");
+    let resource = singular(&collection);
+    let article = article(&resource);
+    match metadata.method.to_uppercase().as_str() {
+        "POST" => format!("Create a new {resource}"),
+        "PUT" | "PATCH" => format!("Update {article} {resource}"),
+        "DELETE" => format!("Delete {article} {resource}"),
+        "GET" if targets_one => format!("Get {article} {resource}"),
+        "GET" => format!("List {collection}"),
+        method => format!("{method} {collection}"),
+    }
+}
+
+/// Indefinite article of `noun`, after the sound it starts with
+fn article(noun: &str) -> &'static str {
+    // Vowels sounding like "you" or "wo"
+    const CONSONANT_SOUNDS: [&str; 8] = ["uni", "use", "usa", "uti", "uri", "url", "eu", "one"];
+    let noun = noun.to_lowercase();
+    let vowel = noun.starts_with(['a', 'e', 'i', 'o', 'u']);
+    if vowel
+        && !CONSONANT_SOUNDS
+            .iter()
+            .any(|prefix| noun.starts_with(prefix))
+    {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+/// Singular of an English plural, enough for resource names
+fn singular(plural: &str) -> String {
+    if let Some(stem) = plural.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if plural.ends_with("ouses") {
+        plural[..plural.len() - 1].to_string()
+    } else if ["sses", "uses", "xes", "ches", "shes", "zzes"]
+        .iter()
+        .any(|suffix| plural.ends_with(suffix))
+    {
+        plural[..plural.len() - 2].to_string()
+    } else if plural.ends_with("ss") || plural.ends_with("us") {
+        plural.to_string()
+    } else {
+        plural.strip_suffix('s').unwrap_or(plural).to_string()
+    }
+}
+
+/// Name, schema and whether it is required of each parameter of the tool
+fn parameters(metadata: &ToolMetadata) -> impl Iterator<Item = (&str, &Value, bool)> {
+    let required: Vec<&str> = metadata
+        .parameters
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    metadata
+        .parameters
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(move |(name, schema)| (name.as_str(), schema, required.contains(&name.as_str())))
+}
+
+/// Description of a parameter with its schema's type, format and allowed
+/// values
+fn hint(schema: &Value) -> String {
+    let mut constraints = Vec::new();
+    if let Some(kind) = schema.get("type").and_then(Value::as_str) {
+        constraints.push(kind.to_string());
+    }
+    if let Some(format) = schema.get("format").and_then(Value::as_str) {
+        constraints.push(format!("format {format}"));
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        let values: Vec<_> = values
+            .iter()
+            .map(|value| match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            })
+            .collect();
+        constraints.push(format!("one of: {}", values.join(", ")));
+    }
+    let description = schema
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    match (constraints.is_empty(), description.is_empty()) {
+        (true, _) => description.to_string(),
+        (false, true) => format!("({})", constraints.join("; ")),
+        (false, false) => format!("{description} ({})", constraints.join("; ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HttpClient;
+    use serde_json::json;
+
+    fn tool(method: &str, path: &str, parameters: Value) -> Tool {
+        let metadata = ToolMetadata {
+            name: "tool".to_string(),
+            title: None,
+            description: Some("Does things".to_string()),
+            parameters,
+            output_schema: None,
+            method: method.to_string(),
+            path: path.to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: std::collections::HashMap::new(),
+        };
+        Tool::new(metadata, HttpClient::new()).unwrap()
+    }
+
+    #[test]
+    fn test_workflows_follow_method_and_path() {
+        let describe = |method, path| workflow(&tool(method, path, json!({})).metadata);
+        assert_eq!(describe("post", "/pets"), "Create a new pet");
+        assert_eq!(describe("get", "/pets"), "List pets");
+        assert_eq!(describe("get", "/pets/{petId}"), "Get a pet");
+        assert_eq!(describe("put", "/categories/{id}"), "Update a category");
+        assert_eq!(describe("delete", "/classes/{id}"), "Delete a class");
+        assert_eq!(describe("patch", "/boxes/{id}"), "Update a box");
+        assert_eq!(describe("delete", "/addresses/{id}"), "Delete an address");
+        assert_eq!(describe("get", "/orders/{orderId}"), "Get an order");
+        assert_eq!(describe("get", "/users/{id}"), "Get a user");
+        assert_eq!(describe("put", "/buses/{id}"), "Update a bus");
+        assert_eq!(describe("get", "/statuses/{id}"), "Get a status");
+        assert_eq!(describe("delete", "/warehouses/{id}"), "Delete a warehouse");
+    }
+
+    #[test]
+    fn test_arguments_are_hinted_and_required() {
+        let tool = tool(
+            "post",
+            "/pets",
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Name of the pet" },
+                    "status": { "type": "string", "enum": ["available", "sold"] }
+                },
+                "required": ["name"]
+            }),
+        );
+
+        let prompts = list([&tool]);
+        let arguments = prompts[0].arguments.as_ref().unwrap();
+        assert_eq!(arguments[0].name, "name");
+        assert_eq!(arguments[0].required, Some(true));
+        assert_eq!(
+            arguments[0].description.as_deref(),
+            Some("Name of the pet (string)")
+        );
+        assert_eq!(
+            arguments[1].description.as_deref(),
+            Some("(string; one of: available, sold)")
+        );
+        assert_eq!(arguments[1].required, Some(false));
+
+        assert!(get(&tool, None).is_err());
+        let arguments = json!({ "name": "Rex" });
+        let result = get(&tool, arguments.as_object()).unwrap();
+        assert_eq!(result.description.as_deref(), Some("Create a new pet"));
+        assert_eq!(result.messages.len(), 1);
+    }
+}
@@ -0,0 +1,137 @@
+//! Bounded queue of tool calls.
+//!
+//! At most `workers` tool calls execute at the same time. The next ones wait
+//! for a worker in a queue of at most `depth` calls, each for at most
+//! `timeout`. A call arriving while the queue is full, or still waiting when
+//! its timeout expires, fails with
+//! [`ToolCallExecutionError::ServerBusy`](crate::error::ToolCallExecutionError::ServerBusy)
+//! telling the client when to retry, instead of piling up.
+//!
+//! The queue is shared by the clones of a server, so it bounds the calls of
+//! every session together.
+
+use bon::Builder;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Size and patience of the tool call queue
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct QueueConfig {
+    /// Tool calls executing at the same time
+    pub workers: usize,
+    /// Tool calls waiting for a worker; more are refused
+    #[builder(default = 100)]
+    pub depth: usize,
+    /// How long a call waits for a worker before being refused
+    #[builder(default = Duration::from_secs(30))]
+    pub timeout: Duration,
+    /// Delay after which refused clients are told to retry
+    #[builder(default = Duration::from_secs(1))]
+    pub retry_after: Duration,
+}
+
+/// Error returned when a call is refused by the queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Busy {
+    /// `"queue-full"` or `"queue-timeout"`
+    pub(crate) reason: &'static str,
+    pub(crate) retry_after: Duration,
+}
+
+/// Queue of tool calls, shared by the clones of a server
+#[derive(Debug, Clone)]
+pub struct CallQueue {
+    config: QueueConfig,
+    workers: Arc<Semaphore>,
+    waiting: Arc<AtomicUsize>,
+}
+
+/// A call waiting for a worker, counted until dropped
+struct Waiting(Arc<AtomicUsize>);
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl CallQueue {
+    pub fn new(config: QueueConfig) -> Self {
+        Self {
+            workers: Arc::new(Semaphore::new(config.workers)),
+            waiting: Arc::default(),
+            config,
+        }
+    }
+
+    /// Number of tool calls waiting for a worker
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::SeqCst)
+    }
+
+    /// Wait for a worker, released when the permit drops
+    pub(crate) async fn enter(&self) -> Result<OwnedSemaphorePermit, Busy> {
+        if let Ok(permit) = self.workers.clone().try_acquire_owned() {
+            return Ok(permit);
+        }
+
+        // Counted before checking, so concurrent calls cannot overfill the queue
+        let _waiting = Waiting(self.waiting.clone());
+        if self.waiting.fetch_add(1, Ordering::SeqCst) >= self.config.depth {
+            return Err(self.busy("queue-full"));
+        }
+        let permit =
+            tokio::time::timeout(self.config.timeout, self.workers.clone().acquire_owned())
+                .await
+                .ok()
+                .and_then(Result::ok)
+                .ok_or_else(|| self.busy("queue-timeout"))?;
+        Ok(permit)
+    }
+
+    fn busy(&self, reason: &'static str) -> Busy {
+        Busy {
+            reason,
+            retry_after: self.config.retry_after,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_calls_wait_then_are_refused() {
+        let queue = CallQueue::new(
+            QueueConfig::builder()
+                .workers(1)
+                .depth(1)
+                .timeout(Duration::from_millis(50))
+                .build(),
+        );
+        let worker = queue.enter().await.unwrap();
+
+        // The queue has room for one call, which times out
+        let waiting = queue.clone();
+        let waiting = tokio::spawn(async move { waiting.enter().await.map(drop) });
+        while queue.waiting() == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(queue.enter().await.unwrap_err().reason, "queue-full");
+        assert_eq!(waiting.await.unwrap().unwrap_err().reason, "queue-timeout");
+        assert_eq!(queue.waiting(), 0);
+
+        // A call waiting when the worker is released gets it
+        let waiting = queue.clone();
+        let waiting = tokio::spawn(async move { waiting.enter().await.map(drop) });
+        while queue.waiting() == 0 {
+            tokio::task::yield_now().await;
+        }
+        drop(worker);
+        assert!(waiting.await.unwrap().is_ok());
+        assert_eq!(queue.waiting(), 0);
+    }
+}
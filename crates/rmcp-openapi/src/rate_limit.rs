@@ -0,0 +1,292 @@
+//! Client-side rate limiting for upstream HTTP requests.
+//!
+//! Token buckets, one global and optionally one per tool, keep the server within
+//! the upstream API's published limits. When a bucket is empty the call either
+//! waits for its turn ([`RateLimitMode::Queue`]) or fails immediately with
+//! [`ToolCallExecutionError::RateLimitExceeded`](crate::error::ToolCallExecutionError::RateLimitExceeded)
+//! ([`RateLimitMode::Reject`]).
+//!
+//! Each tool call takes one token, however many retries it ends up making.
+
+use bon::Builder;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A number of requests allowed per period, e.g. `10/s` or `600/m`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed per period; also the burst size
+    pub requests: u32,
+    /// Length of the period
+    pub per: Duration,
+}
+
+impl RateLimit {
+    fn tokens_per_second(&self) -> f64 {
+        f64::from(self.requests) / self.per.as_secs_f64()
+    }
+}
+
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid rate limit: '{s}'. Expected '<requests>/<period>' with period s, m or h (e.g. '10/s')"
+            )
+        };
+        let (requests, period) = s.trim().split_once('/').ok_or_else(invalid)?;
+        let requests: u32 = requests.trim().parse().map_err(|_| invalid())?;
+        let per = match period.trim() {
+            "s" | "sec" | "second" => Duration::from_secs(1),
+            "m" | "min" | "minute" => Duration::from_secs(60),
+            "h" | "hour" => Duration::from_secs(3600),
+            _ => return Err(invalid()),
+        };
+        if requests == 0 {
+            return Err(invalid());
+        }
+        Ok(Self { requests, per })
+    }
+}
+
+/// What happens to a call when its rate limit is exhausted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Wait until a token is available
+    #[default]
+    Queue,
+    /// Fail the call straight away
+    Reject,
+}
+
+impl FromStr for RateLimitMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queue" => Ok(RateLimitMode::Queue),
+            "reject" => Ok(RateLimitMode::Reject),
+            _ => Err(format!(
+                "Invalid rate limit mode: '{s}'. Valid values: queue, reject"
+            )),
+        }
+    }
+}
+
+/// Global and per-tool rate limits
+#[derive(Debug, Clone, Default, PartialEq, Eq, Builder)]
+pub struct RateLimitConfig {
+    /// Limit shared by every tool
+    pub global: Option<RateLimit>,
+    /// Limits for individual tools, keyed by tool name, applied on top of `global`
+    #[builder(default)]
+    pub per_tool: HashMap<String, RateLimit>,
+    #[builder(default)]
+    pub mode: RateLimitMode,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.requests),
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.tokens_per_second())
+            .min(f64::from(self.limit.requests));
+        self.last_refill = now;
+    }
+
+    /// Time until a whole token is available (zero if one is available now)
+    fn wait_time(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.limit.tokens_per_second())
+        }
+    }
+}
+
+/// Error returned when a call is rejected by the rate limiter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RateLimited {
+    /// `"global"` or the tool name whose limit was hit
+    pub(crate) scope: String,
+    pub(crate) retry_after: Duration,
+}
+
+/// Shared token buckets; clones of one `HttpClient` share the same buckets
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    config: RateLimitConfig,
+    global: Option<Arc<Mutex<TokenBucket>>>,
+    tools: Arc<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: RateLimitConfig) -> Self {
+        Self {
+            global: config
+                .global
+                .map(|limit| Arc::new(Mutex::new(TokenBucket::new(limit)))),
+            tools: Arc::default(),
+            config,
+        }
+    }
+
+    /// Take a token for `tool_name`, waiting or rejecting per the configured mode
+    pub(crate) async fn acquire(&self, tool_name: &str) -> Result<(), RateLimited> {
+        let wait = self.reserve(tool_name, Instant::now())?;
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// Take a token from every bucket that applies to `tool_name`
+    ///
+    /// In queue mode tokens are borrowed ahead of time (a bucket may go negative)
+    /// and the returned duration is how long the caller must wait for its turn.
+    /// In reject mode nothing is taken unless every bucket has a token.
+    fn reserve(&self, tool_name: &str, now: Instant) -> Result<Duration, RateLimited> {
+        let mut tools = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+        let tool_bucket = self.config.per_tool.get(tool_name).map(|limit| {
+            tools
+                .entry(tool_name.to_string())
+                .or_insert_with(|| TokenBucket::new(*limit))
+        });
+        let mut global_bucket = self
+            .global
+            .as_ref()
+            .map(|bucket| bucket.lock().unwrap_or_else(|e| e.into_inner()));
+
+        let mut buckets: Vec<(&str, &mut TokenBucket)> = Vec::with_capacity(2);
+        if let Some(bucket) = tool_bucket {
+            buckets.push((tool_name, bucket));
+        }
+        if let Some(bucket) = global_bucket.as_deref_mut() {
+            buckets.push(("global", bucket));
+        }
+
+        for (_, bucket) in &mut buckets {
+            bucket.refill(now);
+        }
+
+        if self.config.mode == RateLimitMode::Reject
+            && let Some((scope, bucket)) = buckets
+                .iter()
+                .filter(|(_, bucket)| bucket.tokens < 1.0)
+                .max_by_key(|(_, bucket)| bucket.wait_time())
+        {
+            return Err(RateLimited {
+                scope: (*scope).to_string(),
+                retry_after: bucket.wait_time(),
+            });
+        }
+
+        let wait = buckets
+            .iter()
+            .map(|(_, bucket)| bucket.wait_time())
+            .max()
+            .unwrap_or_default();
+        for (_, bucket) in &mut buckets {
+            bucket.tokens -= 1.0;
+        }
+        Ok(wait)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(s: &str) -> RateLimit {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(
+            limit("10/s"),
+            RateLimit {
+                requests: 10,
+                per: Duration::from_secs(1)
+            }
+        );
+        assert_eq!(limit("600/m").per, Duration::from_secs(60));
+        assert_eq!(limit(" 5000 / h ").requests, 5000);
+        assert!("0/s".parse::<RateLimit>().is_err());
+        assert!("10".parse::<RateLimit>().is_err());
+        assert!("10/d".parse::<RateLimit>().is_err());
+    }
+
+    #[test]
+    fn test_reject_mode_enforces_global_burst() {
+        let limiter = RateLimiter::new(
+            RateLimitConfig::builder()
+                .global(limit("2/s"))
+                .mode(RateLimitMode::Reject)
+                .build(),
+        );
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve("a", now), Ok(Duration::ZERO));
+        assert_eq!(limiter.reserve("b", now), Ok(Duration::ZERO));
+        let rejected = limiter.reserve("a", now).unwrap_err();
+        assert_eq!(rejected.scope, "global");
+        assert!(rejected.retry_after <= Duration::from_millis(500));
+
+        // Tokens refill over time
+        assert!(limiter.reserve("a", now + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_per_tool_limits_apply_on_top_of_global() {
+        let limiter = RateLimiter::new(
+            RateLimitConfig::builder()
+                .global(limit("100/s"))
+                .per_tool(HashMap::from([("search".to_string(), limit("1/m"))]))
+                .mode(RateLimitMode::Reject)
+                .build(),
+        );
+        let now = Instant::now();
+
+        assert!(limiter.reserve("search", now).is_ok());
+        let rejected = limiter.reserve("search", now).unwrap_err();
+        assert_eq!(rejected.scope, "search");
+
+        // Other tools only see the global limit
+        assert!(limiter.reserve("getPet", now).is_ok());
+    }
+
+    #[test]
+    fn test_queue_mode_schedules_callers_in_turn() {
+        let limiter = RateLimiter::new(RateLimitConfig::builder().global(limit("10/s")).build());
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert_eq!(limiter.reserve("a", now), Ok(Duration::ZERO));
+        }
+        let first = limiter.reserve("a", now).unwrap();
+        let second = limiter.reserve("a", now).unwrap();
+        assert!(first > Duration::ZERO && first <= Duration::from_millis(100));
+        assert!(second > first && second <= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,384 @@
+//! Redaction of secrets and personal data in upstream traffic.
+//!
+//! Error results echo the request that was sent and the response that came
+//! back, and debug logs print tool arguments and headers. [`RedactionRules`]
+//! list the header names and body locations that must never appear there; the
+//! matching values are replaced with `<redacted>` before a failed response is
+//! turned into an MCP error payload and before anything is logged.
+//!
+//! Body locations are written as JSONPath expressions restricted to the
+//! commonly used subset: `$.user.password`, `$['api-key']`, `$.items[0].token`,
+//! `$.items[*].token` and `$..secret` (any depth). Bodies that are not JSON are
+//! left untouched; only header rules apply to them.
+
+use bon::Builder;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::http_client::HttpResponse;
+
+/// Replacement for redacted values
+pub(crate) const REDACTED: &str = "<redacted>";
+
+/// One step of a [`JsonPath`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Object member, `.name` or `['name']`
+    Key(String),
+    /// Array element, `[0]`
+    Index(usize),
+    /// Every member or element, `.*` or `[*]`
+    Wildcard,
+    /// Object member at any depth, `..name`
+    Descendant(String),
+}
+
+/// Location of values in a JSON body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    /// Replace every value at this path in `value` with `<redacted>`
+    pub fn redact(&self, value: &mut Value) {
+        redact_segments(value, &self.segments);
+    }
+
+    /// Values at this path in `value`, in document order
+    #[must_use]
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut selected = Vec::new();
+        select_segments(value, &self.segments, &mut selected);
+        selected
+    }
+
+    /// Whether this path designates at most one value, having no wildcard
+    /// nor descendant step
+    #[must_use]
+    pub fn is_definite(&self) -> bool {
+        self.segments
+            .iter()
+            .all(|segment| matches!(segment, Segment::Key(_) | Segment::Index(_)))
+    }
+}
+
+fn select_segments<'a>(value: &'a Value, segments: &[Segment], selected: &mut Vec<&'a Value>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        selected.push(value);
+        return;
+    };
+
+    match segment {
+        Segment::Key(key) => {
+            if let Some(child) = value.get(key) {
+                select_segments(child, rest, selected);
+            }
+        }
+        Segment::Index(index) => {
+            if let Some(child) = value.get(*index) {
+                select_segments(child, rest, selected);
+            }
+        }
+        Segment::Wildcard => match value {
+            Value::Object(object) => object
+                .values()
+                .for_each(|child| select_segments(child, rest, selected)),
+            Value::Array(array) => array
+                .iter()
+                .for_each(|child| select_segments(child, rest, selected)),
+            _ => {}
+        },
+        Segment::Descendant(key) => match value {
+            Value::Object(object) => {
+                if let Some(child) = object.get(key) {
+                    select_segments(child, rest, selected);
+                }
+                object
+                    .values()
+                    .for_each(|child| select_segments(child, segments, selected));
+            }
+            Value::Array(array) => array
+                .iter()
+                .for_each(|child| select_segments(child, segments, selected)),
+            _ => {}
+        },
+    }
+}
+
+fn redact_segments(value: &mut Value, segments: &[Segment]) {
+    let Some((segment, rest)) = segments.split_first() else {
+        *value = Value::String(REDACTED.to_string());
+        return;
+    };
+
+    match segment {
+        Segment::Key(key) => {
+            if let Some(child) = value.as_object_mut().and_then(|o| o.get_mut(key)) {
+                redact_segments(child, rest);
+            }
+        }
+        Segment::Index(index) => {
+            if let Some(child) = value.as_array_mut().and_then(|a| a.get_mut(*index)) {
+                redact_segments(child, rest);
+            }
+        }
+        Segment::Wildcard => match value {
+            Value::Object(object) => object
+                .values_mut()
+                .for_each(|child| redact_segments(child, rest)),
+            Value::Array(array) => array
+                .iter_mut()
+                .for_each(|child| redact_segments(child, rest)),
+            _ => {}
+        },
+        Segment::Descendant(key) => match value {
+            Value::Object(object) => {
+                if let Some(child) = object.get_mut(key) {
+                    redact_segments(child, rest);
+                }
+                object
+                    .values_mut()
+                    .for_each(|child| redact_segments(child, segments));
+            }
+            Value::Array(array) => array
+                .iter_mut()
+                .for_each(|child| redact_segments(child, segments)),
+            _ => {}
+        },
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| format!("Invalid JSONPath '{s}': {reason}");
+
+        let mut rest = s
+            .strip_prefix('$')
+            .ok_or_else(|| invalid("must start with '$'"))?;
+        let mut segments = Vec::new();
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("..") {
+                let (name, after) = split_name(after);
+                if name.is_empty() {
+                    return Err(invalid("'..' must be followed by a member name"));
+                }
+                segments.push(Segment::Descendant(name.to_string()));
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix(".*") {
+                segments.push(Segment::Wildcard);
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('.') {
+                let (name, after) = split_name(after);
+                if name.is_empty() {
+                    return Err(invalid("'.' must be followed by a member name"));
+                }
+                segments.push(Segment::Key(name.to_string()));
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let (selector, after) = after
+                    .split_once(']')
+                    .ok_or_else(|| invalid("unclosed '['"))?;
+                let segment = if selector == "*" {
+                    Segment::Wildcard
+                } else if let Some(name) = selector
+                    .strip_prefix('\'')
+                    .and_then(|s| s.strip_suffix('\''))
+                    .or_else(|| selector.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+                {
+                    Segment::Key(name.to_string())
+                } else {
+                    Segment::Index(
+                        selector
+                            .parse()
+                            .map_err(|_| invalid("expected an index, '*' or a quoted name"))?,
+                    )
+                };
+                segments.push(segment);
+                rest = after;
+            } else {
+                return Err(invalid("expected '.', '..' or '['"));
+            }
+        }
+
+        if segments.is_empty() {
+            return Err(invalid("the whole body cannot be redacted"));
+        }
+
+        Ok(Self {
+            source: s.to_string(),
+            segments,
+        })
+    }
+}
+
+/// Split a dotted member name off the front of `s`
+fn split_name(s: &str) -> (&str, &str) {
+    let end = s.find(['.', '[']).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Values hidden from error payloads and logs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Builder)]
+pub struct RedactionRules {
+    /// Header names whose values are redacted (case-insensitive)
+    #[builder(default)]
+    pub headers: Vec<String>,
+    /// Locations redacted in JSON request and response bodies
+    #[builder(default)]
+    pub body_paths: Vec<JsonPath>,
+}
+
+impl RedactionRules {
+    /// Whether the value of header `name` is redacted
+    #[must_use]
+    pub fn redacts_header(&self, name: &str) -> bool {
+        self.headers.iter().any(|h| h.eq_ignore_ascii_case(name))
+    }
+
+    /// Redact every configured body path in `value`
+    pub fn redact_json(&self, value: &mut Value) {
+        for path in &self.body_paths {
+            path.redact(value);
+        }
+    }
+
+    /// Redact a serialized body, leaving it unchanged if it is not JSON
+    #[must_use]
+    pub fn redact_body(&self, body: &str) -> String {
+        if self.body_paths.is_empty() {
+            return body.to_string();
+        }
+        match serde_json::from_str::<Value>(body) {
+            Ok(mut value) => {
+                self.redact_json(&mut value);
+                value.to_string()
+            }
+            Err(_) => body.to_string(),
+        }
+    }
+
+    /// Redact the headers and bodies of a response before it is reported
+    pub(crate) fn redact_response(&self, response: &mut HttpResponse) {
+        for (name, value) in &mut response.headers {
+            if self.redacts_header(name) {
+                *value = REDACTED.to_string();
+            }
+        }
+        response.body = self.redact_body(&response.body);
+        response.request_body = self.redact_body(&response.request_body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn redacted(path: &str, mut value: Value) -> Value {
+        path.parse::<JsonPath>().unwrap().redact(&mut value);
+        value
+    }
+
+    #[test]
+    fn member_and_index_paths() {
+        assert_eq!(
+            redacted(
+                "$.user.password",
+                json!({"user": {"name": "a", "password": "p"}})
+            ),
+            json!({"user": {"name": "a", "password": "<redacted>"}})
+        );
+        assert_eq!(
+            redacted("$['api-key']", json!({"api-key": "k", "other": 1})),
+            json!({"api-key": "<redacted>", "other": 1})
+        );
+        assert_eq!(
+            redacted(
+                "$.items[1].token",
+                json!({"items": [{"token": "a"}, {"token": "b"}]})
+            ),
+            json!({"items": [{"token": "a"}, {"token": "<redacted>"}]})
+        );
+        // Missing members are ignored
+        assert_eq!(
+            redacted("$.missing.token", json!({"a": 1})),
+            json!({"a": 1})
+        );
+    }
+
+    #[test]
+    fn wildcard_and_descendant_paths() {
+        assert_eq!(
+            redacted(
+                "$.items[*].token",
+                json!({"items": [{"token": "a"}, {"token": "b"}]})
+            ),
+            json!({"items": [{"token": "<redacted>"}, {"token": "<redacted>"}]})
+        );
+        assert_eq!(
+            redacted(
+                "$..ssn",
+                json!({"ssn": 1, "people": [{"ssn": 2, "spouse": {"ssn": 3}}]})
+            ),
+            json!({
+                "ssn": "<redacted>",
+                "people": [{"ssn": "<redacted>", "spouse": {"ssn": "<redacted>"}}]
+            })
+        );
+    }
+
+    #[test]
+    fn paths_select_values() {
+        let value = json!({"items": [{"id": 1}, {"id": 2}], "total": 2});
+        let path = "$.items[*].id".parse::<JsonPath>().unwrap();
+        assert_eq!(path.select(&value), [&json!(1), &json!(2)]);
+        assert!(!path.is_definite());
+
+        let path = "$.total".parse::<JsonPath>().unwrap();
+        assert_eq!(path.select(&value), [&json!(2)]);
+        assert!(path.is_definite());
+    }
+
+    #[test]
+    fn invalid_paths_are_rejected() {
+        for path in ["user.password", "$", "$.", "$..", "$[0", "$[x]", "$user"] {
+            assert!(
+                path.parse::<JsonPath>().is_err(),
+                "{path} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn non_json_bodies_are_left_alone() {
+        let rules = RedactionRules::builder()
+            .body_paths(vec!["$.token".parse().unwrap()])
+            .build();
+        assert_eq!(rules.redact_body("token=abc"), "token=abc");
+        assert_eq!(
+            rules.redact_body(r#"{"token":"abc"}"#),
+            r#"{"token":"<redacted>"}"#
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! The OpenAPI spec, exposed as MCP resources.
+//!
+//! Besides the description of each tool, agents can read the documentation of
+//! the API as a whole:
+//!
+//! - [`SPEC_RESOURCE_URI`]: the spec, as loaded
+//! - `openapi://tags/{tag}`: the operations of a tag, with the tools calling
+//!   them; tags are kebab-case, as in tag filters
+//! - `openapi://schemas/{name}`: a schema of the spec's components
+//!
+//! Every resource is JSON.
+
+use rmcp::model::{AnnotateAble, RawResource, Resource};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+use crate::normalize_tag;
+use crate::tool::ToolCollection;
+
+/// URI of the spec resource
+pub const SPEC_RESOURCE_URI: &str = "openapi://spec";
+
+const TAG_RESOURCE_PREFIX: &str = "openapi://tags/";
+const SCHEMA_RESOURCE_PREFIX: &str = "openapi://schemas/";
+const MIME_TYPE: &str = "application/json";
+
+/// Resources of `spec`, whose operations are called by `tools`
+pub(crate) fn list(spec: &Value, tools: &ToolCollection) -> Vec<Resource> {
+    let mut resources = vec![resource(
+        SPEC_RESOURCE_URI.to_string(),
+        "OpenAPI specification".to_string(),
+        spec.pointer("/info/title")
+            .and_then(Value::as_str)
+            .map(|title| format!("OpenAPI specification of {title}")),
+    )];
+    for (tag, operations) in operations_by_tag(tools) {
+        let description =
+            tag_description(spec, &tag).unwrap_or_else(|| format!("Operations tagged '{tag}'"));
+        resources.push(resource(
+            format!("{TAG_RESOURCE_PREFIX}{}", normalize_tag(&tag)),
+            format!("{tag} operations"),
+            Some(format!("{description} ({} tools)", operations.len())),
+        ));
+    }
+    for name in schemas(spec)
+        .map(|schemas| schemas.keys())
+        .into_iter()
+        .flatten()
+    {
+        resources.push(resource(
+            format!("{SCHEMA_RESOURCE_PREFIX}{name}"),
+            format!("{name} schema"),
+            None,
+        ));
+    }
+    resources
+}
+
+/// Content of the resource at `uri`, if any
+pub(crate) fn read(uri: &str, spec: &Value, tools: &ToolCollection) -> Option<String> {
+    let content = if uri == SPEC_RESOURCE_URI {
+        spec.clone()
+    } else if let Some(tag) = uri.strip_prefix(TAG_RESOURCE_PREFIX) {
+        let (tag, operations) = operations_by_tag(tools)
+            .into_iter()
+            .find(|(name, _)| normalize_tag(name) == tag)?;
+        json!({
+            "tag": tag,
+            "description": tag_description(spec, &tag),
+            "operations": operations,
+        })
+    } else if let Some(name) = uri.strip_prefix(SCHEMA_RESOURCE_PREFIX) {
+        schemas(spec)?.get(name)?.clone()
+    } else {
+        return None;
+    };
+    serde_json::to_string_pretty(&content).ok()
+}
+
+fn resource(uri: String, name: String, description: Option<String>) -> Resource {
+    let mut resource = RawResource::new(uri, name);
+    resource.description = description;
+    resource.mime_type = Some(MIME_TYPE.to_string());
+    resource.no_annotation()
+}
+
+/// Summaries of the operations of each tag
+fn operations_by_tag(tools: &ToolCollection) -> BTreeMap<String, Vec<Value>> {
+    let mut tags: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for tool in tools.iter() {
+        let metadata = &tool.metadata;
+        for tag in &metadata.tags {
+            tags.entry(tag.clone()).or_default().push(json!({
+                "tool": metadata.name,
+                "method": metadata.method.to_uppercase(),
+                "path": metadata.path,
+                "title": metadata.title,
+                "description": metadata.description,
+            }));
+        }
+    }
+    tags
+}
+
+/// Description of `tag` in the spec's tag list
+fn tag_description(spec: &Value, tag: &str) -> Option<String> {
+    spec.get("tags")?
+        .as_array()?
+        .iter()
+        .find(|entry| entry.get("name").and_then(Value::as_str) == Some(tag))?
+        .get("description")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn schemas(spec: &Value) -> Option<&serde_json::Map<String, Value>> {
+    spec.pointer("/components/schemas")?.as_object()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Server;
+    use url::Url;
+
+    fn server() -> Server {
+        let mut server = Server::builder()
+            .openapi_spec(json!({
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1.0.0" },
+                "tags": [{ "name": "petStore", "description": "Everything about pets" }],
+                "paths": {
+                    "/pets": {
+                        "get": {
+                            "operationId": "listPets",
+                            "tags": ["petStore"],
+                            "summary": "List pets",
+                            "responses": { "200": { "description": "OK" } }
+                        }
+                    }
+                },
+                "components": {
+                    "schemas": {
+                        "Pet": { "type": "object", "properties": { "name": { "type": "string" } } }
+                    }
+                }
+            }))
+            .base_url(Url::parse("https://api.example.com").unwrap())
+            .build();
+        server.load_openapi_spec().unwrap();
+        server
+    }
+
+    #[test]
+    fn test_spec_tags_and_schemas_are_listed() {
+        let server = server();
+        let uris: Vec<_> = list(&server.openapi_spec, &server.tool_collection)
+            .into_iter()
+            .map(|resource| resource.raw.uri)
+            .collect();
+        assert_eq!(
+            uris,
+            vec![
+                "openapi://spec",
+                "openapi://tags/pet-store",
+                "openapi://schemas/Pet"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resources_are_read() {
+        let server = server();
+        let read_json = |uri| {
+            read(uri, &server.openapi_spec, &server.tool_collection)
+                .map(|content| serde_json::from_str::<Value>(&content).unwrap())
+        };
+
+        assert_eq!(read_json("openapi://spec").unwrap(), server.openapi_spec);
+
+        let tag = read_json("openapi://tags/pet-store").unwrap();
+        assert_eq!(tag["description"], "Everything about pets");
+        assert_eq!(tag["operations"][0]["tool"], "listPets");
+        assert_eq!(tag["operations"][0]["method"], "GET");
+
+        assert_eq!(
+            read_json("openapi://schemas/Pet").unwrap()["type"],
+            "object"
+        );
+        assert!(read_json("openapi://schemas/Owner").is_none());
+        assert!(read_json("openapi://tags/owners").is_none());
+        assert!(read_json("https://example.com").is_none());
+    }
+}
@@ -0,0 +1,315 @@
+//! Retry policy for upstream HTTP requests.
+//!
+//! Flaky upstreams (rate limiting, brief outages, dropped connections) should not
+//! immediately surface as tool errors. A [`RetryPolicy`] attached to the
+//! [`HttpClient`](crate::HttpClient) retries such failures with exponential
+//! backoff and optional jitter, honouring `Retry-After` when the upstream sends it.
+//!
+//! Failures left after retrying carry a [`RetryHint`] telling agents whether
+//! repeating the call may help, and after how long.
+
+use bon::Builder;
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use crate::executor::TransportError;
+
+/// Delay suggested before retrying when nothing tells how long to wait
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// When and how often failed upstream requests are retried
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    #[builder(default = 3)]
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for every further retry
+    #[builder(default = Duration::from_millis(200))]
+    pub initial_backoff: Duration,
+    /// Upper bound for the computed backoff delay
+    #[builder(default = Duration::from_secs(10))]
+    pub max_backoff: Duration,
+    /// Randomize each delay between half and all of the computed backoff
+    #[builder(default = true)]
+    pub jitter: bool,
+    /// Retry `429 Too Many Requests` responses
+    #[builder(default = true)]
+    pub retry_on_rate_limit: bool,
+    /// Retry `5xx` responses
+    #[builder(default = true)]
+    pub retry_on_server_error: bool,
+    /// Retry connection failures and timeouts
+    #[builder(default = true)]
+    pub retry_on_connection_error: bool,
+    /// Wait for the delay given by a `Retry-After` header instead of the backoff.
+    /// A `Retry-After` longer than `max_backoff` is not waited for.
+    #[builder(default = true)]
+    pub honor_retry_after: bool,
+    /// Also retry `POST` and `PATCH` requests, which may not be idempotent
+    #[builder(default)]
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// Whether requests with this HTTP method may be retried at all
+    #[must_use]
+    pub fn allows_method(&self, method: &str) -> bool {
+        self.retry_non_idempotent || !matches!(method.to_uppercase().as_str(), "POST" | "PATCH")
+    }
+
+    /// Exponential backoff before retry number `retry` (starting at 1)
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            delay
+        }
+    }
+
+    /// Delay before retrying a response, or `None` if it should be returned as is
+    #[must_use]
+    pub fn delay_for_response(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+        retry: u32,
+    ) -> Option<Duration> {
+        let retryable = (status == StatusCode::TOO_MANY_REQUESTS && self.retry_on_rate_limit)
+            || (status.is_server_error() && self.retry_on_server_error);
+        if !retryable || retry > self.max_retries {
+            return None;
+        }
+
+        match self
+            .honor_retry_after
+            .then(|| retry_after(headers))
+            .flatten()
+        {
+            Some(delay) if delay > self.max_backoff => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(retry)),
+        }
+    }
+
+    /// Delay before retrying a transport error, or `None` if it should be returned as is
+    #[must_use]
+    pub fn delay_for_error(&self, error: &TransportError, retry: u32) -> Option<Duration> {
+        let retryable =
+            self.retry_on_connection_error && (error.is_connect() || error.is_timeout());
+        (retryable && retry <= self.max_retries).then(|| self.backoff(retry))
+    }
+}
+
+/// Whether repeating a failed call may succeed, and when to try
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RetryHint {
+    /// Whether the same call may succeed if repeated
+    pub retryable: bool,
+    /// Suggested delay before retrying, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u64>,
+}
+
+impl RetryHint {
+    /// The call will fail again unless something changes
+    #[must_use]
+    pub fn permanent() -> Self {
+        Self {
+            retryable: false,
+            retry_after_ms: None,
+        }
+    }
+
+    /// The call may succeed after `delay`, or a default delay
+    #[must_use]
+    pub fn after(delay: Option<Duration>) -> Self {
+        Self {
+            retryable: true,
+            retry_after_ms: Some(
+                u64::try_from(delay.unwrap_or(DEFAULT_RETRY_AFTER).as_millis()).unwrap_or(u64::MAX),
+            ),
+        }
+    }
+
+    /// Hint for an upstream response with `status` and `headers`, retryable
+    /// for timeouts, rate limiting and server errors, after the delay of its
+    /// `Retry-After` header, or rate limit reset, if any
+    #[must_use]
+    pub fn for_response(status: u16, headers: &HashMap<String, String>) -> Self {
+        match status {
+            429 => crate::error::ToolCallExecutionError::rate_limited(headers).retry_hint(),
+            408 | 425 | 500 | 502 | 503 | 504 => Self::after(
+                headers
+                    .get(RETRY_AFTER.as_str())
+                    .and_then(|value| parse_retry_after(value)),
+            ),
+            _ => Self::permanent(),
+        }
+    }
+}
+
+/// Parse a `Retry-After` header given either as delay-seconds or as an HTTP date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    parse_retry_after(headers.get(RETRY_AFTER)?.to_str().ok()?)
+}
+
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn no_jitter() -> RetryPolicy {
+        RetryPolicy::builder().jitter(false).build()
+    }
+
+    #[test]
+    fn test_retry_hints_for_responses() {
+        let headers = HashMap::from([("retry-after".to_string(), "5".to_string())]);
+        assert_eq!(
+            RetryHint::for_response(429, &headers),
+            RetryHint {
+                retryable: true,
+                retry_after_ms: Some(5000)
+            }
+        );
+        assert_eq!(
+            RetryHint::for_response(503, &HashMap::new()).retry_after_ms,
+            Some(1000)
+        );
+        for status in [400, 401, 404, 422] {
+            assert_eq!(
+                RetryHint::for_response(status, &headers),
+                RetryHint::permanent()
+            );
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::builder()
+            .jitter(false)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350))
+            .build();
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_backoff_jitter_stays_within_bounds() {
+        let policy = RetryPolicy::builder()
+            .initial_backoff(Duration::from_millis(100))
+            .build();
+        for _ in 0..100 {
+            let delay = policy.backoff(1);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        let policy = no_jitter();
+        let headers = HeaderMap::new();
+
+        assert!(
+            policy
+                .delay_for_response(StatusCode::TOO_MANY_REQUESTS, &headers, 1)
+                .is_some()
+        );
+        assert!(
+            policy
+                .delay_for_response(StatusCode::SERVICE_UNAVAILABLE, &headers, 1)
+                .is_some()
+        );
+        assert!(
+            policy
+                .delay_for_response(StatusCode::NOT_FOUND, &headers, 1)
+                .is_none()
+        );
+        assert!(
+            policy
+                .delay_for_response(StatusCode::BAD_GATEWAY, &headers, 4)
+                .is_none(),
+            "max_retries exhausted"
+        );
+
+        let policy = RetryPolicy::builder().retry_on_server_error(false).build();
+        assert!(
+            policy
+                .delay_for_response(StatusCode::INTERNAL_SERVER_ERROR, &headers, 1)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        let policy = no_jitter();
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(
+            policy.delay_for_response(StatusCode::TOO_MANY_REQUESTS, &headers, 1),
+            Some(Duration::from_secs(2))
+        );
+
+        // Longer than max_backoff: give up rather than stall the tool call
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+        assert_eq!(
+            policy.delay_for_response(StatusCode::TOO_MANY_REQUESTS, &headers, 1),
+            None
+        );
+
+        // Dates in the past mean "retry now"
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(
+            policy.delay_for_response(StatusCode::SERVICE_UNAVAILABLE, &headers, 1),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_non_idempotent_methods() {
+        let policy = RetryPolicy::default();
+        assert!(policy.allows_method("GET"));
+        assert!(policy.allows_method("delete"));
+        assert!(!policy.allows_method("POST"));
+        assert!(!policy.allows_method("patch"));
+
+        let policy = RetryPolicy::builder().retry_non_idempotent(true).build();
+        assert!(policy.allows_method("POST"));
+    }
+}
@@ -0,0 +1,254 @@
+//! Tool discovery for large APIs.
+//!
+//! Listing thousands of tools fills the agent's context before it has done
+//! anything. With tool search enabled, the server offers a
+//! [`SEARCH_TOOLS_TOOL`] tool taking a free-text `query` and returning the
+//! names of the tools matching it, best first:
+//!
+//! ```json
+//! {
+//!   "tools": [
+//!     { "name": "findPetsByTags", "description": "Finds Pets by tags", "tags": ["pet"] }
+//!   ]
+//! }
+//! ```
+//!
+//! Tools whose name, title, description or tags contain every word of the
+//! query come first, those with more words in their name ahead. Tools whose
+//! name is close to the query, as with the suggestions of unknown tool names,
+//! follow. Only the tools the caller may call are searched.
+
+use rmcp::model::{CallToolResult, Content, ErrorData, Tool as McpTool};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::sync::Arc;
+
+use crate::error::find_similar_strings;
+use crate::tool::{Tool, ToolMetadata};
+
+/// Name of the tool searching the other tools
+pub const SEARCH_TOOLS_TOOL: &str = "search_tools";
+
+/// Most tools returned by default
+const DEFAULT_LIMIT: usize = 10;
+
+/// Most tools a search may return
+const MAX_LIMIT: usize = 50;
+
+/// Longest description returned, in characters
+const DESCRIPTION_CHARS: usize = 200;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SearchArguments {
+    query: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// The [`SEARCH_TOOLS_TOOL`] tool, as listed to clients
+pub(crate) fn search_tools_tool() -> McpTool {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "query": {
+                "type": "string",
+                "description": "Words to look for in tool names, descriptions and tags, or an approximate tool name"
+            },
+            "limit": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": MAX_LIMIT,
+                "description": format!("Most tools returned (default: {DEFAULT_LIMIT})")
+            }
+        },
+        "required": ["query"],
+        "additionalProperties": false
+    });
+    let Value::Object(schema) = schema else {
+        unreachable!("schema is an object");
+    };
+    McpTool::new_with_raw(
+        SEARCH_TOOLS_TOOL,
+        Some("Find the tools matching a query, to call them by name".into()),
+        Arc::new(schema),
+    )
+}
+
+/// Answer a call to the [`SEARCH_TOOLS_TOOL`] tool, searching `tools`
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid
+pub(crate) fn call(
+    tools: &[&Tool],
+    arguments: Option<Map<String, Value>>,
+) -> Result<CallToolResult, ErrorData> {
+    let arguments: SearchArguments =
+        serde_json::from_value(Value::Object(arguments.unwrap_or_default())).map_err(|e| {
+            ErrorData::invalid_params(format!("Invalid search arguments: {e}"), None)
+        })?;
+    let limit = arguments.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let found: Vec<Value> = search(tools, &arguments.query)
+        .into_iter()
+        .take(limit)
+        .map(summary)
+        .collect();
+    let structured = json!({ "tools": found });
+    let mut result = CallToolResult::success(vec![Content::text(structured.to_string())]);
+    result.structured_content = Some(structured);
+    Ok(result)
+}
+
+/// Tools matching `query`, best first
+fn search<'a>(tools: &[&'a Tool], query: &str) -> Vec<&'a Tool> {
+    let query = query.trim().to_lowercase();
+    let words: Vec<&str> = query.split_whitespace().collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    // Tools containing every word, more of them in the name first
+    let mut found: Vec<(usize, &Tool)> = tools
+        .iter()
+        .filter_map(|tool| {
+            let name = tool.metadata.name.to_lowercase();
+            let text = searchable_text(tool);
+            words.iter().all(|word| text.contains(word)).then(|| {
+                (
+                    words.iter().filter(|word| name.contains(**word)).count(),
+                    *tool,
+                )
+            })
+        })
+        .collect();
+    found.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    let mut found: Vec<&Tool> = found.into_iter().map(|(_, tool)| tool).collect();
+
+    // Then tools named close to the query
+    let names: Vec<String> = tools
+        .iter()
+        .map(|tool| tool.metadata.name.to_lowercase())
+        .collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    for similar in find_similar_strings(&query.replace(' ', ""), &names) {
+        if let Some(tool) = tools
+            .iter()
+            .find(|tool| tool.metadata.name.to_lowercase() == similar)
+            && !found
+                .iter()
+                .any(|found| found.metadata.name == tool.metadata.name)
+        {
+            found.push(*tool);
+        }
+    }
+    found
+}
+
+/// Name, title, description and tags of `tool`, in lowercase
+fn searchable_text(tool: &Tool) -> String {
+    let metadata = &tool.metadata;
+    [
+        Some(metadata.name.as_str()),
+        metadata.title.as_deref(),
+        metadata.description.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .chain(metadata.tags.iter().map(String::as_str))
+    .collect::<Vec<_>>()
+    .join(" ")
+    .to_lowercase()
+}
+
+/// What a search returns of `tool`
+fn summary(tool: &Tool) -> Value {
+    let metadata = &tool.metadata;
+    json!({
+        "name": metadata.name,
+        "description": headline(metadata),
+        "tags": metadata.tags,
+    })
+}
+
+/// First line of the title, or else of the description, of a tool
+pub(crate) fn headline(metadata: &ToolMetadata) -> Option<String> {
+    metadata
+        .title
+        .as_deref()
+        .or(metadata.description.as_deref())
+        .and_then(|text| text.lines().find(|line| !line.trim().is_empty()))
+        .map(|line| line.trim().chars().take(DESCRIPTION_CHARS).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HttpClient;
+    use std::collections::HashMap;
+
+    fn tool(name: &str, description: &str, tags: &[&str]) -> Tool {
+        let metadata = ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            parameters: json!({}),
+            output_schema: None,
+            method: "get".to_string(),
+            path: "/".to_string(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            security: None,
+            parameter_mappings: HashMap::new(),
+        };
+        Tool::new(metadata, HttpClient::new()).unwrap()
+    }
+
+    fn names(tools: Vec<&Tool>) -> Vec<&str> {
+        tools
+            .into_iter()
+            .map(|tool| tool.metadata.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_search_by_words_then_similar_names() {
+        let tools = [
+            tool("findPetsByTags", "Finds Pets by tags", &["pet"]),
+            tool("getPetById", "Find pet by ID", &["pet"]),
+            tool(
+                "getInventory",
+                "Returns pet inventories by status",
+                &["store"],
+            ),
+            tool("createUser", "Create user", &["user"]),
+        ];
+        let tools: Vec<&Tool> = tools.iter().collect();
+
+        assert_eq!(
+            names(search(&tools, "find pet")),
+            ["findPetsByTags", "getPetById"]
+        );
+        assert_eq!(names(search(&tools, "STORE")), ["getInventory"]);
+        assert_eq!(names(search(&tools, "createUsr")), ["createUser"]);
+        assert!(search(&tools, "  ").is_empty());
+    }
+
+    #[test]
+    fn test_search_results_are_limited() {
+        let tools: Vec<Tool> = (0..20)
+            .map(|i| tool(&format!("listPets{i}"), "List pets", &[]))
+            .collect();
+        let tools: Vec<&Tool> = tools.iter().collect();
+
+        let arguments = json!({ "query": "pets", "limit": 3 });
+        let Value::Object(arguments) = arguments else {
+            unreachable!()
+        };
+        let result = call(&tools, Some(arguments)).unwrap();
+        let found = &result.structured_content.unwrap()["tools"];
+        assert_eq!(found.as_array().unwrap().len(), 3);
+        assert_eq!(found[0]["description"], "List pets");
+
+        assert!(call(&tools, None).is_err());
+    }
+}
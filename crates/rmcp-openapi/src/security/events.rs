@@ -0,0 +1,192 @@
+//! Structured security events for monitoring agent activity.
+//!
+//! Besides the log lines of [`SecurityObserver`](super::SecurityObserver),
+//! tool calls produce [`SecurityEvent`]s: which credentials a call used, and
+//! why a call was denied, by the server or by the upstream. Events are handed
+//! to [`SecurityEventSink`]s, such as a JSON lines file or an HTTP webhook
+//! feeding a SIEM. They never carry credentials, only where they came from.
+
+use reqwest::Client;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tracing::warn;
+use url::Url;
+
+use crate::audit::unix_millis;
+use crate::error::Error;
+
+/// Time allowed for a webhook delivery
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One security-relevant occurrence during a tool call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SecurityEvent {
+    /// When the event occurred, in milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+    /// MCP session the call was made in, when the transport provides one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Name of the tool called
+    pub tool: String,
+    #[serde(flatten)]
+    pub kind: SecurityEventKind,
+}
+
+impl SecurityEvent {
+    /// Event occurring now
+    pub fn new(tool: &str, session_id: Option<&str>, kind: SecurityEventKind) -> Self {
+        Self {
+            timestamp_ms: unix_millis(SystemTime::now()),
+            session_id: session_id.map(String::from),
+            tool: tool.to_string(),
+            kind,
+        }
+    }
+}
+
+/// What happened, serialized as the `event` field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum SecurityEventKind {
+    /// The MCP client's authorization was forwarded to the upstream
+    Passthrough,
+    /// A service token configured on the server was sent in place of the
+    /// client's authorization
+    ServiceToken,
+    /// The server refused the call before contacting the upstream
+    CallDenied {
+        /// Error type, such as `authentication-required` or `tool-filtered`
+        reason: String,
+        message: String,
+    },
+    /// The upstream answered `401` or `403`
+    UpstreamDenied { status: u16 },
+}
+
+/// Destination of security events
+///
+/// Sending must not block the tool call for long nor fail it: sinks handle
+/// their own errors, typically by logging them.
+pub trait SecurityEventSink: Send + Sync {
+    fn send(&self, event: &SecurityEvent);
+}
+
+/// Appends one JSON object per event and line to a file
+#[derive(Debug)]
+pub struct FileEventSink {
+    file: Mutex<File>,
+}
+
+impl FileEventSink {
+    /// Open `path` for appending, creating it if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened
+    pub fn new(path: &Path) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl SecurityEventSink for FileEventSink {
+    fn send(&self, event: &SecurityEvent) {
+        let line = match serde_json::to_string(event) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize security event");
+                return;
+            }
+        };
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = writeln!(file, "{line}").and_then(|()| file.flush()) {
+            warn!(error = %e, "Failed to write security event");
+        }
+    }
+}
+
+/// POSTs each event as JSON to a URL, in the background
+///
+/// Deliveries are not retried; failures are logged.
+#[derive(Debug)]
+pub struct WebhookEventSink {
+    url: Url,
+    headers: HeaderMap,
+    client: Client,
+}
+
+impl WebhookEventSink {
+    /// Sink posting to `url` with `headers`, such as the receiver's token
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created
+    pub fn new(url: Url, headers: HeaderMap) -> Result<Self, Error> {
+        let client = Client::builder().timeout(WEBHOOK_TIMEOUT).build()?;
+        Ok(Self {
+            url,
+            headers,
+            client,
+        })
+    }
+}
+
+impl SecurityEventSink for WebhookEventSink {
+    fn send(&self, event: &SecurityEvent) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!(url = %self.url, "No async runtime to deliver security event");
+            return;
+        };
+        let request = self
+            .client
+            .post(self.url.clone())
+            .headers(self.headers.clone())
+            .json(event);
+        let url = self.url.clone();
+        runtime.spawn(async move {
+            match request.send().await {
+                Ok(response) if !response.status().is_success() => {
+                    warn!(url = %url, status = %response.status(), "Security event webhook rejected event");
+                }
+                Ok(_) => {}
+                Err(e) => warn!(url = %url, error = %e, "Failed to deliver security event"),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn events_serialize_flat() {
+        let event = SecurityEvent {
+            timestamp_ms: 1,
+            session_id: None,
+            tool: "addPet".to_string(),
+            kind: SecurityEventKind::CallDenied {
+                reason: "insufficient-scope".to_string(),
+                message: "Token lacks scopes".to_string(),
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "timestamp_ms": 1,
+                "tool": "addPet",
+                "event": "call-denied",
+                "reason": "insufficient-scope",
+                "message": "Token lacks scopes"
+            })
+        );
+    }
+}
@@ -1,3 +1,7 @@
+pub mod events;
 pub mod observer;
 
+pub use events::{
+    FileEventSink, SecurityEvent, SecurityEventKind, SecurityEventSink, WebhookEventSink,
+};
 pub use observer::SecurityObserver;
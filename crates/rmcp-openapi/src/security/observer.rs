@@ -1,15 +1,37 @@
-use crate::config::Authorization;
+use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
+use super::events::{SecurityEvent, SecurityEventKind, SecurityEventSink};
+use crate::config::Authorization;
+use crate::error::{ToolCallError, ToolCallExecutionError};
+
 /// Observes and logs security-related decisions
 pub struct SecurityObserver<'a> {
     authorization: &'a Authorization,
+    sinks: &'a [Arc<dyn SecurityEventSink>],
+    session_id: Option<&'a str>,
 }
 
 impl<'a> SecurityObserver<'a> {
     /// Create a new security observer with the given authorization
     pub fn new(authorization: &'a Authorization) -> Self {
-        Self { authorization }
+        Self {
+            authorization,
+            sinks: &[],
+            session_id: None,
+        }
+    }
+
+    /// Also send security events to `sinks`, attributed to `session_id`
+    #[must_use]
+    pub fn with_sinks(
+        mut self,
+        sinks: &'a [Arc<dyn SecurityEventSink>],
+        session_id: Option<&'a str>,
+    ) -> Self {
+        self.sinks = sinks;
+        self.session_id = session_id;
+        self
     }
 
     /// Observe and log an authorization decision for a request
@@ -44,6 +66,64 @@ impl<'a> SecurityObserver<'a> {
                 "OpenAPI spec requires auth but no Authorization header present"
             );
         }
+
+        match self.authorization {
+            Authorization::ServiceToken(_) => {
+                self.emit(operation_id, SecurityEventKind::ServiceToken)
+            }
+            #[cfg(feature = "authorization-token-passthrough")]
+            Authorization::PassthroughWarn(_) | Authorization::PassthroughSilent(_) if has_auth => {
+                self.emit(operation_id, SecurityEventKind::Passthrough);
+            }
+            _ => {}
+        }
+    }
+
+    /// Report a call the server refused for lack of acceptable credentials
+    pub fn observe_error(&self, operation_id: &str, error: &ToolCallError) {
+        let reason = match error {
+            ToolCallError::Execution(ToolCallExecutionError::AuthenticationRequired { .. }) => {
+                "authentication-required"
+            }
+            ToolCallError::Execution(ToolCallExecutionError::InsufficientScope { .. }) => {
+                "insufficient-scope"
+            }
+            ToolCallError::Execution(ToolCallExecutionError::AuthenticationFailed { .. }) => {
+                "authentication-failed"
+            }
+            _ => return,
+        };
+        self.observe_denied(operation_id, reason, &error.to_string());
+    }
+
+    /// Report a call refused before reaching the upstream
+    pub fn observe_denied(&self, operation_id: &str, reason: &str, message: &str) {
+        warn!(operation_id, reason, "Tool call denied");
+        self.emit(
+            operation_id,
+            SecurityEventKind::CallDenied {
+                reason: reason.to_string(),
+                message: message.to_string(),
+            },
+        );
+    }
+
+    /// Report the upstream's verdict on the call's credentials
+    pub fn observe_response(&self, operation_id: &str, status: u16) {
+        if matches!(status, 401 | 403) {
+            debug!(operation_id, status, "Upstream denied the call");
+            self.emit(operation_id, SecurityEventKind::UpstreamDenied { status });
+        }
+    }
+
+    fn emit(&self, operation_id: &str, kind: SecurityEventKind) {
+        if self.sinks.is_empty() {
+            return;
+        }
+        let event = SecurityEvent::new(operation_id, self.session_id, kind);
+        for sink in self.sinks {
+            sink.send(&event);
+        }
     }
 
     /// Log the authorization mode at startup
@@ -63,6 +143,9 @@ impl<'a> SecurityObserver<'a> {
             Authorization::PassthroughSilent(_) => {
                 tracing::info!("Authorization mode: passthrough-silent");
             }
+            Authorization::ServiceToken(_) => {
+                tracing::info!("Authorization mode: service token");
+            }
         }
     }
 }
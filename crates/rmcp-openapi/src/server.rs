@@ -2,39 +2,120 @@ use bon::Builder;
 use rmcp::{
     handler::server::ServerHandler,
     model::{
-        CallToolRequestParams, CallToolResult, ErrorData, Implementation, InitializeResult,
-        ListToolsResult, PaginatedRequestParams, ProtocolVersion, ServerCapabilities,
+        CallToolRequestParams, CallToolResult, Content, ErrorData, GetPromptRequestParams,
+        GetPromptResult, Implementation, InitializeRequestParams, InitializeResult,
+        ListPromptsResult, ListResourcesResult, ListToolsResult, PaginatedRequestParams,
+        PromptsCapability, ProtocolVersion, ReadResourceRequestParams, ReadResourceResult,
+        ResourceContents, ResourcesCapability, ServerCapabilities, SetLevelRequestParams,
         ToolsCapability,
     },
     service::{RequestContext, RoleServer},
 };
 use rmcp_actix_web::transport::AuthorizationHeader;
 use serde_json::Value;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use reqwest::header::HeaderMap;
 use url::Url;
 
-use crate::error::Error;
+use crate::audit::AuditLog;
+use crate::auth::{
+    ApiKey, BasicAuth, ClientCredentials, ClientCredentialsConfig, ScopeChecker, ScopeSource,
+    TokenExchange, TokenExchangeConfig,
+};
+use crate::batch::{self, BATCH_CALL_TOOL, batch_call_tool};
+use crate::blob::BlobStore;
+use crate::budget::BudgetConfig;
+use crate::cache::{CacheConfig, ResponseCache};
+use crate::cassette::Cassette;
+use crate::circuit_breaker::CircuitBreakerConfig;
+use crate::concurrency::ConcurrencyLimits;
+use crate::confirmation::ConfirmationConfig;
+use crate::custom_tool::CustomTool;
+use crate::describe::{self, DESCRIBE_TOOL_TOOL, describe_tool_tool};
+use crate::disabled::DisabledTools;
+use crate::drain::{InFlightCall, InFlightCalls};
+use crate::error::{Error, ToolCallError, ToolCallExecutionError};
+use crate::executor::HttpExecutor;
 use crate::filter::ToolFilter;
+use crate::html::HtmlConversion;
+use crate::http_client::{HttpClient, is_read_only_method};
+use crate::logging::ClientLog;
+use crate::middleware::Middleware;
+use crate::pagination::PaginationConfig;
+use crate::pipeline::PipelineConfig;
+use crate::policy::ParameterPolicy;
+use crate::progress::{self, CallProgress};
+use crate::prompts;
+use crate::queue::CallQueue;
+use crate::rate_limit::RateLimitConfig;
+use crate::redaction::RedactionRules;
+use crate::resources;
+use crate::retry::RetryPolicy;
+use crate::search::{self, SEARCH_TOOLS_TOOL, search_tools_tool};
+use crate::security::{SecurityEventSink, SecurityObserver};
+use crate::session::{
+    CONFIGURE_SESSION_TOOL, SESSION_META_KEY, SessionSettings, SessionState, configure_session_tool,
+};
+use crate::signing::RequestSigner;
+use crate::table::TableConfig;
 use crate::tool::{Tool, ToolCollection, ToolMetadata};
+use crate::tool_hook::ToolHook;
 use crate::transformer::ResponseTransformer;
+use crate::upstream_error::ErrorSchemas;
 use crate::{
-    config::{Authorization, AuthorizationMode},
+    config::{
+        Authorization, AuthorizationMode, AuthorizationRule, AuthorizationTemplate, BaseUrlRoute,
+        ClientCertificate, CompressionConfig, ConnectionConfig, DuplicateNameStrategy,
+        HostAllowlist, IdempotencyConfig, NullValuePolicy, OutputSchemaMode, OutputSchemaPolicy,
+        ProxyConfig, RedirectPolicy, ServiceToken, StatusPolicy, TimeoutPolicy,
+    },
+    normalize_tag,
     spec::Filters,
 };
+use tokio::sync::OwnedSemaphorePermit;
 use tracing::{debug, info, info_span, warn};
 
+/// MCP session a request belongs to
+///
+/// Inserted into the request extensions by the transport (see the
+/// `on_request` hook of `StreamableHttpService`) so upstream calls can be
+/// attributed to the session that made them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionId(pub String);
+
 #[derive(Clone, Builder)]
 pub struct Server {
     pub openapi_spec: serde_json::Value,
     #[builder(default)]
     pub tool_collection: ToolCollection,
     pub base_url: Url,
+    /// Base URLs of the tools of some tags or paths, the first matching route
+    /// overriding `base_url`. Default: none.
+    #[builder(default)]
+    pub base_url_routes: Vec<BaseUrlRoute>,
     pub default_headers: Option<HeaderMap>,
     pub filters: Option<Filters>,
     #[builder(default)]
     pub authorization_mode: AuthorizationMode,
+    /// Per-tool authorization behavior; the first rule matching a tool overrides
+    /// `authorization_mode`. Default: none.
+    #[builder(default)]
+    pub authorization_rules: Vec<AuthorizationRule>,
+    /// Headers rendered from forwarded client authorization in place of the
+    /// `Authorization` header. Default: none.
+    #[builder(default)]
+    pub authorization_templates: Vec<AuthorizationTemplate>,
+    /// Token exchange applied to forwarded client tokens. Default: none.
+    pub token_exchange: Option<TokenExchangeConfig>,
+    /// Where the scopes of forwarded client tokens are read from to check
+    /// them against operation requirements. Default: none.
+    pub scope_check: Option<ScopeSource>,
     pub name: Option<String>,
     pub version: Option<String>,
     pub title: Option<String>,
@@ -50,6 +131,196 @@ pub struct Server {
     pub parameter_examples_in_description: bool,
     #[builder(default)]
     pub insecure: bool,
+    /// Drop request bodies on `GET`/`HEAD`/`DELETE`/`OPTIONS`/`TRACE` operations even when
+    /// the spec declares one, for upstreams behind proxies that reject them.
+    #[builder(default)]
+    pub strip_non_standard_bodies: bool,
+    /// How `null` query parameter values are encoded. Default: the literal string `null`.
+    #[builder(default)]
+    pub null_value_policy: NullValuePolicy,
+    /// Retry policy for failed upstream requests. Default: no retries.
+    pub retry_policy: Option<RetryPolicy>,
+    /// Per-host circuit breaker for upstream requests. Default: disabled.
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Client-side rate limits for upstream requests. Default: unlimited.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Limits on upstream requests in flight. Default: unlimited.
+    pub concurrency: Option<ConcurrencyLimits>,
+    /// Outbound proxy for upstream requests. Default: taken from the environment.
+    pub proxy: Option<ProxyConfig>,
+    /// Client certificate for upstream APIs that require mutual TLS. Default: none.
+    pub client_certificate: Option<ClientCertificate>,
+    /// PEM bundle of extra root certificates trusted for upstream requests. Default: none.
+    pub ca_bundle: Option<PathBuf>,
+    /// Connection pool and socket settings for upstream requests. Default: `reqwest` defaults.
+    pub connection: Option<ConnectionConfig>,
+    /// Per-tool, per-tag and maximum request timeouts. Default: caller-provided timeouts only.
+    pub timeout_policy: Option<TimeoutPolicy>,
+    /// Statuses outside `2xx` returned as successful tool results, for every
+    /// tool or per tool. Default: none, only `2xx` responses succeed.
+    pub status_policy: Option<StatusPolicy>,
+    /// Strictness of tool output schemas, and tools advertising none.
+    /// Default: none, strict output schemas for every tool.
+    pub output_schema_policy: Option<OutputSchemaPolicy>,
+    /// What to do when several operations produce the same tool name.
+    /// Default: none, every tool kept, calls going to the first.
+    pub duplicate_tool_names: Option<DuplicateNameStrategy>,
+    /// Cache for successful `GET` responses. Default: disabled.
+    pub response_cache: Option<CacheConfig>,
+    /// Store used by `response_cache`. Default: a bounded in-memory store.
+    pub response_cache_store: Option<Arc<dyn ResponseCache>>,
+    /// Request body compression and response decompression. Default: disabled.
+    pub compression: Option<CompressionConfig>,
+    /// Largest response body read from the upstream, in bytes. Default: unlimited.
+    pub max_response_bytes: Option<usize>,
+    /// Redirect handling for upstream requests. Default: follow up to 10 redirects,
+    /// dropping credentials across origins.
+    pub redirect_policy: Option<RedirectPolicy>,
+    /// Hosts upstream requests, redirects and OAuth2 token requests may go to,
+    /// besides the base URL's. Default: any host.
+    pub allowed_hosts: Option<HostAllowlist>,
+    /// Hook signing every upstream request. Default: none.
+    pub request_signer: Option<Arc<dyn RequestSigner>>,
+    /// Idempotency keys attached to non-idempotent calls. Default: none.
+    pub idempotency: Option<IdempotencyConfig>,
+    /// Constraints added to tool parameters on top of the spec. Default: none.
+    pub parameter_policy: Option<ParameterPolicy>,
+    /// Register only `GET` and `HEAD` operations and refuse any other request.
+    #[builder(default)]
+    pub read_only: bool,
+    /// Methods whose calls must be confirmed with a one-time token before
+    /// they execute. Default: none.
+    pub confirmation: Option<ConfirmationConfig>,
+    /// Log of every upstream request. Default: none.
+    pub audit_log: Option<Arc<AuditLog>>,
+    /// Destinations of security events: credentials used and calls denied.
+    /// Default: none.
+    #[builder(default)]
+    pub security_event_sinks: Vec<Arc<dyn SecurityEventSink>>,
+    /// Values hidden from error results and logs. Default: none.
+    pub redaction: Option<RedactionRules>,
+    /// Tool calls in progress, shared by the clones of this server; closing
+    /// it refuses new calls.
+    #[builder(default)]
+    pub in_flight: InFlightCalls,
+    /// Tools disabled at runtime, shared by the clones of this server; they
+    /// are hidden and refused like unknown tools.
+    #[builder(default)]
+    pub disabled_tools: DisabledTools,
+    /// Let sessions set their own bearer token, base URL and tool tags (see
+    /// [`crate::session`]). Only meaningful when each session is served by
+    /// its own server, created with [`Server::for_session`].
+    #[builder(default)]
+    pub session_configuration: bool,
+    /// Settings of the session this server serves
+    #[builder(skip)]
+    session: SessionState,
+    /// HTTP client the loaded tools send their requests with
+    #[builder(skip)]
+    upstream_client: Option<HttpClient>,
+    /// Maximum number of tools in a `tools/list` page, clients following the
+    /// returned cursor for the next ones. Default: none, all tools in one page.
+    pub tools_page_size: Option<NonZeroUsize>,
+    /// Expose the spec, the operations of each tag and the component schemas
+    /// as MCP resources (see [`crate::resources`]).
+    #[builder(default)]
+    pub spec_resources: bool,
+    /// Offer a prompt per tool, describing its workflow and asking for its
+    /// parameters (see [`crate::prompts`]).
+    #[builder(default)]
+    pub tool_prompts: bool,
+    /// Offer a tool finding the other tools by name, description or tag, for
+    /// APIs too large to list (see [`crate::search`]).
+    #[builder(default)]
+    pub tool_search: bool,
+    /// List tools by name and one-line description only, offering a tool
+    /// returning the full definition of each (see [`crate::describe`]).
+    #[builder(default)]
+    pub compact_tool_listings: bool,
+    /// Offer a tool calling several tools in order, feeding the results of
+    /// earlier calls into later ones (see [`crate::batch`]).
+    #[builder(default)]
+    pub batch_calls: bool,
+    /// Extra names of tools, as alias to tool name, listed alongside their
+    /// tool and calling it with its settings. Default: none.
+    #[builder(default)]
+    pub tool_aliases: BTreeMap<String, String>,
+    /// Notify clients asking for progress of the tool calls running longer
+    /// than this (see [`crate::progress`]). Default: none.
+    pub progress_threshold: Option<Duration>,
+    /// Queue bounding the tool calls executing at the same time, shared by
+    /// the clones of this server (see [`crate::queue`]). Default: none.
+    pub call_queue: Option<CallQueue>,
+    /// Clients subscribed with `logging/setLevel`, streamed the security
+    /// events and upstream errors of tool calls, shared by the clones of this
+    /// server (see [`crate::logging`]). Default: none, logging disabled.
+    pub client_log: Option<ClientLog>,
+    /// Add the headers of upstream responses, redacted by `redaction`, to the
+    /// structured content of tool results, as `headers`.
+    #[builder(default)]
+    pub response_headers: bool,
+    /// Follow the next pages of `GET` listings and merge their items, adding
+    /// `pages_fetched` to structured results (see [`crate::pagination`]).
+    /// Default: none.
+    pub pagination: Option<PaginationConfig>,
+    /// Let tool calls select the values of the response body to return with
+    /// a `response_filter` parameter (see [`crate::projection`]).
+    #[builder(default)]
+    pub response_filter: bool,
+    /// Let tool calls ask for the request they would send instead of sending
+    /// it with a `dry_run` parameter (see [`crate::dry_run`]).
+    #[builder(default)]
+    pub dry_run: bool,
+    /// Render tabular response bodies as Markdown tables in the text content
+    /// of tool results (see [`crate::table`]). Default: none.
+    pub markdown_tables: Option<TableConfig>,
+    /// Convert `text/html` response bodies to text or Markdown (see
+    /// [`crate::html`]). Default: none, HTML is returned as is.
+    pub html_conversion: Option<HtmlConversion>,
+    /// Store keeping binary response bodies other than images, returned as
+    /// resource links readable with `resources/read` (see [`crate::blob`]),
+    /// shared by the clones of this server. Default: none.
+    pub blob_store: Option<BlobStore>,
+    /// Size of the JSON bodies returned by tool calls, larger ones being cut
+    /// and summarized (see [`crate::budget`]). Default: none.
+    pub response_budget: Option<BudgetConfig>,
+    /// Cassette upstream traffic is recorded to or replayed from. Default: none.
+    pub cassette: Option<Arc<Cassette>>,
+    /// Answer tool calls with responses generated from the spec instead of
+    /// contacting the upstream.
+    #[builder(default)]
+    pub mock_responses: bool,
+    /// OAuth2 client-credentials grant authenticating upstream requests; the
+    /// token URL and scopes default to the spec's security schemes. Default: none.
+    pub client_credentials: Option<ClientCredentialsConfig>,
+    /// API keys for the spec's `apiKey` security schemes. Default: none.
+    #[builder(default)]
+    pub api_keys: Vec<ApiKey>,
+    /// HTTP Basic credentials for upstream requests. Default: none.
+    pub basic_auth: Option<BasicAuth>,
+    /// Middlewares run around every upstream request, in order. Default: none.
+    #[builder(default)]
+    pub middlewares: Vec<Arc<dyn Middleware>>,
+    /// Transport sending upstream requests in place of the `reqwest` client
+    /// (see [`crate::executor`]). Default: none.
+    pub http_executor: Option<Arc<dyn HttpExecutor>>,
+    /// Hooks rewriting the metadata of every generated tool, in order (see
+    /// [`crate::tool_hook`]). Default: none.
+    #[builder(default)]
+    pub tool_hooks: Vec<Arc<dyn ToolHook>>,
+    /// Hand-written tools listed and called alongside the generated ones (see
+    /// [`crate::custom_tool`]). Default: none.
+    #[builder(default)]
+    pub custom_tools: Vec<CustomTool>,
+    /// Pipelines of response transformers of the tools they match, by name or
+    /// tag, replacing `response_transformer` for those tools (see
+    /// [`crate::pipeline`]). Default: none.
+    pub transformer_pipelines: Option<PipelineConfig>,
+    /// Read `4xx` and `5xx` bodies with the operation's error response
+    /// schemas, adding a typed `error` to structured results (see
+    /// [`crate::upstream_error`]).
+    #[builder(default)]
+    pub typed_errors: bool,
     /// Global response transformer applied to all tools.
     ///
     /// Uses dynamic dispatch (`Arc<dyn>`) because:
@@ -57,6 +328,11 @@ pub struct Server {
     /// - Vtable lookup overhead (~1ns) is unmeasurable
     /// - Avoids viral generics throughout Server, Tool, ToolCollection
     pub response_transformer: Option<Arc<dyn ResponseTransformer>>,
+    /// Response transformers of the tools named like, or tagged with, their
+    /// key, replacing pipelines and `response_transformer` for those tools
+    /// (see [`Server::with_transformer`]). Default: none.
+    #[builder(default)]
+    pub tool_transformers: Vec<(String, Arc<dyn ResponseTransformer>)>,
     /// Dynamic tool filter applied to list_tools and call_tool.
     /// Uses dynamic dispatch (`Arc<dyn>`) for same reasons as response_transformer.
     pub tool_filter: Option<Arc<dyn ToolFilter>>,
@@ -64,6 +340,8 @@ pub struct Server {
 
 impl Server {
     /// Create a new Server instance with required parameters
+    ///
+    /// [`Server::builder`] sets any other field in the same expression.
     pub fn new(
         openapi_spec: serde_json::Value,
         base_url: Url,
@@ -77,9 +355,14 @@ impl Server {
             openapi_spec,
             tool_collection: ToolCollection::new(),
             base_url,
+            base_url_routes: Vec::new(),
             default_headers,
             filters,
             authorization_mode: AuthorizationMode::default(),
+            authorization_rules: Vec::new(),
+            authorization_templates: Vec::new(),
+            token_exchange: None,
+            scope_check: None,
             name: None,
             version: None,
             title: None,
@@ -89,7 +372,70 @@ impl Server {
             // Off by default; enable via the builder (`parameter_examples_in_description`).
             parameter_examples_in_description: false,
             insecure,
+            strip_non_standard_bodies: false,
+            null_value_policy: NullValuePolicy::default(),
+            retry_policy: None,
+            circuit_breaker: None,
+            rate_limit: None,
+            concurrency: None,
+            proxy: None,
+            client_certificate: None,
+            ca_bundle: None,
+            connection: None,
+            timeout_policy: None,
+            status_policy: None,
+            output_schema_policy: None,
+            duplicate_tool_names: None,
+            response_cache: None,
+            response_cache_store: None,
+            compression: None,
+            max_response_bytes: None,
+            redirect_policy: None,
+            allowed_hosts: None,
+            request_signer: None,
+            idempotency: None,
+            parameter_policy: None,
+            read_only: false,
+            confirmation: None,
+            audit_log: None,
+            security_event_sinks: Vec::new(),
+            redaction: None,
+            in_flight: InFlightCalls::default(),
+            disabled_tools: DisabledTools::default(),
+            session_configuration: false,
+            session: SessionState::default(),
+            upstream_client: None,
+            tools_page_size: None,
+            spec_resources: false,
+            tool_prompts: false,
+            tool_search: false,
+            compact_tool_listings: false,
+            batch_calls: false,
+            tool_aliases: BTreeMap::new(),
+            progress_threshold: None,
+            call_queue: None,
+            client_log: None,
+            response_headers: false,
+            pagination: None,
+            response_filter: false,
+            dry_run: false,
+            markdown_tables: None,
+            html_conversion: None,
+            blob_store: None,
+            response_budget: None,
+            cassette: None,
+            mock_responses: false,
+            client_credentials: None,
+            api_keys: Vec::new(),
+            basic_auth: None,
+            middlewares: Vec::new(),
+            http_executor: None,
+            tool_hooks: Vec::new(),
+            custom_tools: Vec::new(),
+            transformer_pipelines: None,
+            typed_errors: false,
             response_transformer: None,
+            tool_transformers: Vec::new(),
             tool_filter: None,
         }
     }
@@ -106,24 +452,133 @@ impl Server {
         // Parse the OpenAPI specification
         let spec = crate::spec::Spec::from_value(self.openapi_spec.clone())?;
 
-        // Generate OpenApiTool instances directly
-        let tools = spec.to_openapi_tools(
+        // Generate OpenApiTool instances sharing one configured HTTP client
+        let mut tools_metadata = spec.to_tool_metadata(
             self.filters.as_ref(),
-            Some(self.base_url.clone()),
-            self.default_headers.clone(),
             self.skip_tool_descriptions,
             self.skip_parameter_descriptions,
             self.parameter_examples_in_description,
-            self.insecure,
         )?;
 
-        // Apply global transformer to schemas if present
-        let tools = if let Some(ref transformer) = self.response_transformer {
+        // The parsed spec cannot tell an operation's `security: []`, which makes
+        // it public, from an absent one inheriting the spec's requirements
+        for metadata in &mut tools_metadata {
+            let pointer = format!(
+                "/paths/{}/{}/security",
+                metadata.path.replace('~', "~0").replace('/', "~1"),
+                metadata.method.to_lowercase()
+            );
+            if self
+                .openapi_spec
+                .pointer(&pointer)
+                .and_then(Value::as_array)
+                .is_some_and(Vec::is_empty)
+            {
+                metadata.security = None;
+            }
+        }
+
+        if self.read_only {
+            tools_metadata.retain(|metadata| is_read_only_method(&metadata.method));
+        }
+
+        if let Some(strategy) = self.duplicate_tool_names {
+            tools_metadata = strategy.apply(tools_metadata)?;
+        }
+
+        if let Some(policy) = &self.parameter_policy {
+            policy.apply(&mut tools_metadata);
+        }
+
+        if let Some(config) = &self.confirmation {
+            for metadata in &mut tools_metadata {
+                config.extend_schema(metadata);
+            }
+        }
+
+        if self.response_filter {
+            for metadata in &mut tools_metadata {
+                crate::projection::extend_schema(metadata);
+            }
+        }
+
+        if self.dry_run {
+            for metadata in &mut tools_metadata {
+                crate::dry_run::extend_schema(metadata);
+            }
+        }
+
+        if self.response_headers {
+            for schema in tools_metadata
+                .iter_mut()
+                .filter_map(|metadata| metadata.output_schema.as_mut())
+            {
+                crate::tool_generator::ToolGenerator::add_headers_to_output_schema(schema);
+            }
+        }
+
+        if self.pagination.is_some() {
+            for schema in tools_metadata
+                .iter_mut()
+                .filter(|metadata| metadata.method.eq_ignore_ascii_case("get"))
+                .filter_map(|metadata| metadata.output_schema.as_mut())
+            {
+                crate::tool_generator::ToolGenerator::add_pages_fetched_to_output_schema(schema);
+            }
+        }
+
+        if self.response_budget.is_some() {
+            for schema in tools_metadata
+                .iter_mut()
+                .filter_map(|metadata| metadata.output_schema.as_mut())
+            {
+                crate::budget::extend_output_schema(schema);
+            }
+        }
+
+        if self.redirect_policy.is_some() {
+            for schema in tools_metadata
+                .iter_mut()
+                .filter_map(|metadata| metadata.output_schema.as_mut())
+            {
+                crate::tool_generator::ToolGenerator::add_redirect_to_output_schema(schema);
+            }
+        }
+
+        for metadata in &mut tools_metadata {
+            let error_types = self.error_types(metadata);
+            if let Some(schema) = metadata.output_schema.as_mut() {
+                crate::tool_generator::ToolGenerator::add_error_types_to_output_schema(
+                    schema,
+                    &error_types,
+                );
+            }
+        }
+
+        let http_client = self.http_client()?;
+        let tools = crate::tool_generator::ToolGenerator::generate_tools_with_client(
+            tools_metadata,
+            &http_client,
+        )?;
+
+        // Send the tools of routed tags and paths to their own base URL
+        let tools: Vec<Tool> = tools
+            .into_iter()
+            .map(
+                |tool| match BaseUrlRoute::base_url_for(&self.base_url_routes, &tool.metadata) {
+                    Some(base_url) => tool.with_base_url(base_url.clone()),
+                    None => Ok(tool),
+                },
+            )
+            .collect::<Result<_, _>>()?;
+
+        // Give tools the pipeline matching them, if any
+        let tools: Vec<Tool> = if let Some(config) = &self.transformer_pipelines {
             tools
                 .into_iter()
                 .map(|mut tool| {
-                    if let Some(schema) = tool.metadata.output_schema.take() {
-                        tool.metadata.output_schema = Some(transformer.transform_schema(schema));
+                    if let Some(pipeline) = config.pipeline_for(&tool.metadata) {
+                        tool.response_transformer = Some(Arc::new(pipeline));
                     }
                     tool
                 })
@@ -132,7 +587,110 @@ impl Server {
             tools
         };
 
+        // Registered transformers win over pipelines, a tool's name over its tags
+        let tools: Vec<Tool> = tools
+            .into_iter()
+            .map(|mut tool| {
+                if let Some(transformer) = self.registered_transformer(&tool.metadata) {
+                    tool.response_transformer = Some(transformer.clone());
+                }
+                tool
+            })
+            .collect();
+
+        // Read upstream errors with the error schemas of their operation
+        let tools: Vec<Tool> = if self.typed_errors {
+            tools
+                .into_iter()
+                .map(|mut tool| {
+                    tool.error_schemas = ErrorSchemas::of(&self.openapi_spec, &tool.metadata);
+                    if tool.error_schemas.is_some()
+                        && let Some(schema) = tool.metadata.output_schema.as_mut()
+                    {
+                        crate::upstream_error::extend_output_schema(schema);
+                    }
+                    tool
+                })
+                .collect()
+        } else {
+            tools
+        };
+
+        // Apply the tool's or the global transformer to schemas if present
+        let tools: Vec<Tool> = tools
+            .into_iter()
+            .map(|mut tool| {
+                let transformer = tool
+                    .response_transformer
+                    .clone()
+                    .or_else(|| self.response_transformer.clone());
+                if let Some(transformer) = transformer
+                    && let Some(schema) = tool.metadata.output_schema.take()
+                {
+                    tool.metadata.output_schema = Some(transformer.transform_schema(schema));
+                }
+                tool
+            })
+            .collect();
+
+        // Let hooks rewrite the tools
+        let tools: Vec<Tool> = tools
+            .into_iter()
+            .map(|mut tool| {
+                crate::tool_generator::ToolGenerator::apply_hooks(
+                    &mut tool.metadata,
+                    &self.tool_hooks,
+                );
+                tool
+            })
+            .collect();
+
+        // Relax or drop output schemas as configured, once every field is declared
+        let tools = if let Some(policy) = &self.output_schema_policy {
+            tools
+                .into_iter()
+                .map(|mut tool| {
+                    policy.apply(&mut tool.metadata);
+                    tool.unwrapped_output = policy.mode == OutputSchemaMode::Unwrapped
+                        && tool.metadata.output_schema.is_some();
+                    tool
+                })
+                .collect()
+        } else {
+            tools
+        };
+
+        // Aliases must not hide a tool; those of tools not loaded are ignored
+        for (alias, name) in &self.tool_aliases {
+            if tools.iter().any(|tool| tool.metadata.name == *alias) {
+                return Err(Error::ToolGeneration(format!(
+                    "Tool alias '{alias}' is the name of a tool"
+                )));
+            }
+            if !tools.iter().any(|tool| tool.metadata.name == *name) {
+                warn!(
+                    alias = %alias,
+                    tool_name = %name,
+                    "Ignoring the alias of a tool not loaded"
+                );
+            }
+        }
+
         self.tool_collection = ToolCollection::from_tools(tools);
+        self.upstream_client = Some(http_client);
+
+        for (index, custom) in self.custom_tools.iter().enumerate() {
+            if self.custom_tools[..index]
+                .iter()
+                .any(|other| other.name() == custom.name())
+            {
+                return Err(Error::ToolGeneration(format!(
+                    "Several custom tools are named '{}'",
+                    custom.name()
+                )));
+            }
+            self.check_custom_tool_name(custom.name())?;
+        }
 
         info!(
             tool_count = self.tool_collection.len(),
@@ -142,6 +700,190 @@ impl Server {
         Ok(())
     }
 
+    /// Build the HTTP client shared by all tools from the server configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the base URL, proxy, CA bundle or client certificate is
+    /// invalid, if no OAuth2 token URL can be determined, or if API keys or Basic
+    /// credentials do not match the spec
+    fn http_client(&self) -> Result<HttpClient, Error> {
+        let mut http_client = HttpClient::new()
+            .with_insecure(self.insecure)
+            .with_strip_non_standard_bodies(self.strip_non_standard_bodies)
+            .with_mock_responses(self.mock_responses)
+            .with_read_only(self.read_only)
+            .with_response_headers(self.response_headers)
+            .with_null_value_policy(self.null_value_policy)
+            .with_authorization_templates(self.authorization_templates.clone())
+            .with_base_url(self.base_url.clone())?;
+
+        if let Some(headers) = &self.default_headers {
+            http_client = http_client.with_default_headers(headers.clone());
+        }
+
+        if let Some(policy) = &self.retry_policy {
+            http_client = http_client.with_retry_policy(policy.clone());
+        }
+
+        if let Some(config) = &self.circuit_breaker {
+            http_client = http_client.with_circuit_breaker(config.clone());
+        }
+
+        if let Some(config) = &self.rate_limit {
+            http_client = http_client.with_rate_limit(config.clone());
+        }
+
+        if let Some(limits) = &self.concurrency {
+            http_client = http_client.with_concurrency_limits(limits.clone());
+        }
+
+        if let Some(proxy) = &self.proxy {
+            http_client = http_client.with_proxy(proxy)?;
+        }
+
+        if let Some(policy) = &self.timeout_policy {
+            http_client = http_client.with_timeout_policy(policy.clone());
+        }
+
+        if let Some(policy) = &self.status_policy {
+            http_client = http_client.with_status_policy(policy.clone());
+        }
+
+        if let Some(config) = &self.response_cache {
+            http_client = match &self.response_cache_store {
+                Some(store) => http_client.with_response_cache_store(config.clone(), store.clone()),
+                None => http_client.with_response_cache(config.clone()),
+            };
+        }
+
+        if let Some(config) = &self.compression {
+            http_client = http_client.with_compression(config.clone());
+        }
+
+        if let Some(max_bytes) = self.max_response_bytes {
+            http_client = http_client.with_max_response_size(max_bytes);
+        }
+
+        if let Some(config) = &self.pagination {
+            http_client = http_client.with_pagination(config.clone());
+        }
+
+        if let Some(config) = &self.markdown_tables {
+            http_client = http_client.with_markdown_tables(config.clone());
+        }
+
+        if let Some(conversion) = self.html_conversion {
+            http_client = http_client.with_html_conversion(conversion);
+        }
+
+        if let Some(store) = &self.blob_store {
+            http_client = http_client.with_blob_store(store.clone());
+        }
+
+        if let Some(config) = &self.response_budget {
+            http_client = http_client.with_response_budget(config.clone());
+        }
+
+        if let Some(policy) = self.redirect_policy {
+            http_client = http_client.with_redirect_policy(policy);
+        }
+
+        if let Some(allowlist) = &self.allowed_hosts {
+            http_client = http_client.with_host_allowlist(allowlist.clone());
+        }
+
+        if let Some(config) = &self.idempotency {
+            http_client = http_client.with_idempotency_keys(config.clone());
+        }
+
+        if let Some(config) = &self.confirmation {
+            http_client = http_client.with_confirmation(config.clone());
+        }
+
+        if let Some(audit_log) = &self.audit_log {
+            http_client = http_client.with_audit_log(audit_log.clone());
+        }
+
+        let mut security_event_sinks = self.security_event_sinks.clone();
+        if let Some(log) = &self.client_log {
+            security_event_sinks.push(Arc::new(log.clone()));
+            http_client = http_client.with_client_log(log.clone());
+        }
+        if !security_event_sinks.is_empty() {
+            http_client = http_client.with_security_event_sinks(security_event_sinks);
+        }
+
+        if let Some(rules) = &self.redaction {
+            http_client = http_client.with_redaction(rules.clone());
+        }
+
+        if let Some(cassette) = &self.cassette {
+            http_client = http_client.with_cassette(cassette.clone());
+        }
+
+        http_client = http_client
+            .with_api_key_headers(crate::auth::api_key::header_names(&self.openapi_spec));
+        for api_key in &self.api_keys {
+            http_client = http_client.with_api_key(api_key.resolve(&self.openapi_spec)?);
+        }
+
+        if let Some(basic_auth) = &self.basic_auth {
+            basic_auth.validate(&self.openapi_spec)?;
+            http_client = http_client.with_basic_auth(basic_auth.clone());
+        }
+
+        if let Some(config) = &self.token_exchange {
+            http_client =
+                http_client.with_token_exchange(Arc::new(TokenExchange::new(config.clone())));
+        }
+
+        if let Some(source) = &self.scope_check {
+            http_client =
+                http_client.with_scope_checker(Arc::new(ScopeChecker::new(source.clone())));
+        }
+
+        if let Some(config) = &self.client_credentials {
+            let config = config.clone().resolve(&self.openapi_spec, &self.base_url)?;
+            // The token URL may come from the spec, and the client secret is sent there
+            if let Some(token_url) = &config.token_url
+                && !http_client.allows_url(token_url)
+            {
+                return Err(Error::InvalidUrl(format!(
+                    "OAuth2 token URL '{token_url}' is not on an allowed host"
+                )));
+            }
+            http_client =
+                http_client.with_client_credentials(Arc::new(ClientCredentials::new(config)?));
+        }
+
+        for middleware in &self.middlewares {
+            http_client = http_client.with_middleware(middleware.clone());
+        }
+
+        if let Some(executor) = &self.http_executor {
+            http_client = http_client.with_executor(executor.clone());
+        }
+
+        if let Some(signer) = &self.request_signer {
+            http_client = http_client.with_request_signer(signer.clone());
+        }
+
+        if let Some(connection) = &self.connection {
+            http_client = http_client.with_connection_config(connection.clone());
+        }
+
+        if let Some(path) = &self.ca_bundle {
+            http_client = http_client.with_ca_bundle(path)?;
+        }
+
+        if let Some(certificate) = &self.client_certificate {
+            http_client = http_client.with_client_certificate(certificate)?;
+        }
+
+        Ok(http_client)
+    }
+
     /// Set a response transformer for a specific tool, overriding the global one.
     ///
     /// The transformer's `transform_schema` method is immediately applied to the tool's
@@ -160,11 +902,111 @@ impl Server {
             .set_tool_transformer(tool_name, transformer)
     }
 
+    /// Register a response transformer for the tool named `tag_or_tool`, or
+    /// else for the tools tagged with it, compared in kebab-case
+    ///
+    /// Registered transformers override pipelines and the global transformer.
+    /// A transformer registered for a tool's name wins over those of its
+    /// tags, and among tags the first registered wins. They apply when the
+    /// spec is loaded; see [`Server::set_tool_transformer`] for loaded tools.
+    #[must_use]
+    pub fn with_transformer(
+        mut self,
+        tag_or_tool: impl Into<String>,
+        transformer: Box<dyn ResponseTransformer>,
+    ) -> Self {
+        self.tool_transformers
+            .push((tag_or_tool.into(), Arc::from(transformer)));
+        self
+    }
+
     /// Set the tool filter at runtime.
     pub fn set_tool_filter(&mut self, filter: Arc<dyn ToolFilter>) {
         self.tool_filter = Some(filter);
     }
 
+    /// Server for a new MCP session, with the configuration and tools of
+    /// this one but settings of its own
+    #[must_use]
+    pub fn for_session(&self) -> Self {
+        Self {
+            session: SessionState::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Settings of the session this server serves
+    #[must_use]
+    pub fn session_settings(&self) -> SessionSettings {
+        self.session.settings()
+    }
+
+    /// Update the settings of the session this server serves
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if session configuration is disabled, or if the base
+    /// URL is neither on the configured base URL's host nor allowlisted
+    pub fn configure_session(&self, update: SessionSettings) -> Result<SessionSettings, Error> {
+        if !self.session_configuration {
+            return Err(Error::Validation(
+                "Session configuration is disabled".to_string(),
+            ));
+        }
+        if let Some(base_url) = &update.base_url {
+            let same_host =
+                base_url.host_str().is_some() && base_url.host_str() == self.base_url.host_str();
+            let allowed = self
+                .allowed_hosts
+                .as_ref()
+                .is_some_and(|allowlist| allowlist.allows_url(base_url));
+            if !matches!(base_url.scheme(), "http" | "https") || !(same_host || allowed) {
+                return Err(Error::InvalidUrl(format!(
+                    "Session base URL '{base_url}' is not on an allowed host"
+                )));
+            }
+        }
+        let settings = self.session.settings().merged(update);
+        info!(settings = ?settings, "Session settings updated");
+        self.session.set(settings.clone());
+        Ok(settings)
+    }
+
+    /// Add a hand-written tool, listed and called alongside the generated ones
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a loaded, built-in or custom tool, or an alias,
+    /// already has its name
+    pub fn register_tool(&mut self, tool: CustomTool) -> Result<(), Error> {
+        if self
+            .custom_tools
+            .iter()
+            .any(|custom| custom.name() == tool.name())
+        {
+            return Err(Error::ToolGeneration(format!(
+                "Several custom tools are named '{}'",
+                tool.name()
+            )));
+        }
+        self.check_custom_tool_name(tool.name())?;
+        self.custom_tools.push(tool);
+        Ok(())
+    }
+
+    /// HTTP client upstream requests are sent with: the one of the loaded
+    /// tools, or a new one before the spec is loaded
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created
+    pub fn upstream_client(&self) -> Result<HttpClient, Error> {
+        match &self.upstream_client {
+            Some(client) => Ok(client.clone()),
+            None => self.http_client(),
+        }
+    }
+
     /// Get the number of loaded tools
     #[must_use]
     pub fn tool_count(&self) -> usize {
@@ -200,81 +1042,532 @@ impl Server {
         self.authorization_mode = mode;
     }
 
-    /// Get the current authorization mode
-    pub fn authorization_mode(&self) -> AuthorizationMode {
-        self.authorization_mode
+    /// Get the current authorization mode
+    pub fn authorization_mode(&self) -> AuthorizationMode {
+        self.authorization_mode
+    }
+
+    /// Get basic tool statistics
+    #[must_use]
+    pub fn get_tool_stats(&self) -> String {
+        self.tool_collection.get_stats()
+    }
+
+    /// Simple validation - check that tools are loaded
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no tools are loaded
+    pub fn validate_registry(&self) -> Result<(), Error> {
+        if self.tool_collection.is_empty() {
+            return Err(Error::McpError("No tools loaded".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Extract title from OpenAPI spec info section
+    fn extract_openapi_title(&self) -> Option<String> {
+        self.openapi_spec
+            .get("info")?
+            .get("title")?
+            .as_str()
+            .map(|s| s.to_string())
+    }
+
+    /// Extract version from OpenAPI spec info section
+    fn extract_openapi_version(&self) -> Option<String> {
+        self.openapi_spec
+            .get("info")?
+            .get("version")?
+            .as_str()
+            .map(|s| s.to_string())
+    }
+
+    /// Extract description from OpenAPI spec info section
+    fn extract_openapi_description(&self) -> Option<String> {
+        self.openapi_spec
+            .get("info")?
+            .get("description")?
+            .as_str()
+            .map(|s| s.to_string())
+    }
+
+    /// Build instructions from OpenAPI spec info section: the API and its
+    /// version, its description and its terms of service
+    fn extract_openapi_instructions(&self) -> Option<String> {
+        let info = self.openapi_spec.get("info")?;
+        let field = |name: &str| {
+            info.get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let mut sections = Vec::new();
+        match (field("title"), field("version")) {
+            (Some(title), Some(version)) => {
+                sections.push(format!("Tools calling the {title} API, version {version}."))
+            }
+            (Some(title), None) => sections.push(format!("Tools calling the {title} API.")),
+            _ => {}
+        }
+        if let Some(description) = field("description") {
+            sections.push(description.to_string());
+        }
+        if let Some(terms) = field("termsOfService") {
+            sections.push(format!("Terms of service: {terms}"));
+        }
+        (!sections.is_empty()).then(|| sections.join("\n\n"))
+    }
+
+    /// Extract display title from OpenAPI spec info section
+    /// First checks for x-display-title extension, then derives from title
+    fn extract_openapi_display_title(&self) -> Option<String> {
+        // First check for x-display-title extension
+        if let Some(display_title) = self
+            .openapi_spec
+            .get("info")
+            .and_then(|info| info.get("x-display-title"))
+            .and_then(|t| t.as_str())
+        {
+            return Some(display_title.to_string());
+        }
+
+        // Fallback: enhance the title with "Server" suffix if not already present
+        self.extract_openapi_title().map(|title| {
+            if title.to_lowercase().contains("server") {
+                title
+            } else {
+                format!("{} Server", title)
+            }
+        })
+    }
+}
+
+impl Server {
+    /// [`ServerHandler::call_tool`], boxed so that batches can call it for
+    /// each of their steps
+    fn call_tool_boxed(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Pin<Box<dyn Future<Output = Result<CallToolResult, ErrorData>> + Send + '_>> {
+        Box::pin(self.dispatch_tool_call(request, context))
+    }
+
+    async fn dispatch_tool_call(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        use crate::error::ToolCallValidationError;
+
+        let span = info_span!(
+            "call_tool",
+            tool_name = %request.name
+        );
+        #[cfg(feature = "opentelemetry")]
+        if let Some(trace_context) = context.extensions.get::<crate::telemetry::TraceContext>() {
+            trace_context.set_parent_of(&span);
+        }
+        let _enter = span.enter();
+
+        debug!(
+            tool_name = %request.name,
+            has_arguments = !request.arguments.as_ref().unwrap_or(&serde_json::Map::new()).is_empty(),
+            "Processing MCP call_tool request"
+        );
+
+        if self.session_configuration && request.name == CONFIGURE_SESSION_TOOL {
+            return self.call_configure_session(request.arguments);
+        }
+        // Each step is a call of its own, filtered, authorized and queued
+        if self.batch_calls && request.name == BATCH_CALL_TOOL {
+            return batch::call(request.arguments.clone(), |tool, arguments| {
+                let mut step = request.clone();
+                step.name = tool.into();
+                step.arguments = Some(arguments);
+                self.call_tool_boxed(step, context.clone())
+            })
+            .await;
+        }
+        let session = self.session.settings();
+
+        // Filter all tools once upfront (for both access check and suggestions)
+        let mut allowed_tools: Vec<&Tool> = match &self.tool_filter {
+            None => self.tool_collection.iter().collect(),
+            Some(filter) => {
+                let mut allowed = Vec::new();
+                for tool in self.tool_collection.iter() {
+                    if filter.allow(tool, &context).await {
+                        allowed.push(tool);
+                    }
+                }
+                allowed
+            }
+        };
+        allowed_tools.retain(|tool| {
+            session.exposes(&tool.metadata.tags)
+                && !self.disabled_tools.contains(&tool.metadata.name)
+        });
+
+        if self.tool_search && request.name == SEARCH_TOOLS_TOOL {
+            return search::call(&allowed_tools, request.arguments);
+        }
+        if self.compact_tool_listings && request.name == DESCRIBE_TOOL_TOOL {
+            return describe::call(&allowed_tools, request.arguments);
+        }
+        // Hand-written tools the caller may call; others are refused below
+        if let Some(custom) = self
+            .custom_tools
+            .iter()
+            .find(|custom| custom.name() == request.name)
+            && self.allows_custom_tool(custom, &context).await
+        {
+            return self
+                .call_custom_tool(custom, request.arguments.unwrap_or_default(), &context)
+                .await;
+        }
+
+        // Check if requested tool is in filtered list
+        let tool_name = self.aliased(&request.name);
+        let tool = allowed_tools.iter().find(|t| t.metadata.name == tool_name);
+
+        let tool = match tool {
+            Some(t) => *t,
+            None => {
+                let available_names: Vec<&str> = allowed_tools
+                    .iter()
+                    .map(|t| t.metadata.name.as_str())
+                    .collect();
+
+                // Uses Jaro distance for suggestions internally
+                let error = ToolCallError::Validation(ToolCallValidationError::tool_not_found(
+                    request.name.to_string(),
+                    &available_names,
+                ));
+
+                warn!(
+                    tool_name = %request.name,
+                    success = false,
+                    error = %error,
+                    "MCP call_tool request failed - tool not found or filtered"
+                );
+
+                // An existing tool the filter hides is a denied call, not a typo
+                if self.tool_collection.get_tool(tool_name).is_some() {
+                    let session_id = context
+                        .extensions
+                        .get::<SessionId>()
+                        .map(|id| id.0.as_str());
+                    SecurityObserver::new(&Authorization::None)
+                        .with_sinks(&self.security_event_sinks, session_id)
+                        .observe_denied(&request.name, "tool-filtered", &error.to_string());
+                }
+
+                return Err(error.into());
+            }
+        };
+
+        // Counted until the call returns, so shutdown can wait for it
+        let _in_flight = self.start_call(&request.name)?;
+
+        let arguments = request.arguments.unwrap_or_default();
+        let arguments_value = Value::Object(arguments);
+
+        // Extract authorization header from context extensions
+        let auth_header = context.extensions.get::<AuthorizationHeader>().cloned();
+
+        if auth_header.is_some() {
+            debug!("Authorization header is present");
+        }
+
+        // Create Authorization enum from the tool's rule, or the mode, and header
+        let authorization = Authorization::for_tool(
+            self.authorization_mode,
+            &self.authorization_rules,
+            &tool.metadata.name,
+            &tool.metadata.tags,
+            auth_header,
+        );
+        // A session's own token replaces the configured authorization
+        let authorization = match &session.auth_token {
+            Some(token) => Authorization::ServiceToken(ServiceToken::new(token)),
+            None => authorization,
+        };
+
+        // Send the session's calls to its own base URL
+        let session_tool;
+        let tool = match &session.base_url {
+            Some(base_url) => {
+                session_tool = tool.with_base_url(base_url.clone())?;
+                &session_tool
+            }
+            None => tool,
+        };
+
+        // Follow the progress of calls whose client asked for it
+        let progress_token = context
+            .meta
+            .get_progress_token()
+            .filter(|_| self.progress_threshold.is_some());
+        let progress = CallProgress::default();
+        let progress_tool;
+        let tool = if progress_token.is_some() {
+            progress_tool = tool.with_progress(progress.clone());
+            &progress_tool
+        } else {
+            tool
+        };
+
+        let session_id = context
+            .extensions
+            .get::<SessionId>()
+            .map(|id| id.0.as_str());
+
+        // Get the server-level transformer as a reference for the tool call
+        let server_transformer = self
+            .response_transformer
+            .as_ref()
+            .map(|t| t.as_ref() as &dyn ResponseTransformer);
+
+        // Execute the tool directly (we already have the validated tool reference),
+        // once the queue lets it through
+        let call = async {
+            let _worker = self.enter_queue(&request.name).await?;
+            tool.call_in_session(
+                &arguments_value,
+                authorization,
+                server_transformer,
+                session_id,
+            )
+            .await
+        };
+        // Dropped if the client cancels, aborting the upstream request and
+        // freeing its concurrency slot
+        let call = context.ct.run_until_cancelled(call);
+        let result = match (progress_token, self.progress_threshold) {
+            (Some(token), Some(threshold)) => {
+                progress::report_while(call, &context.peer, token, &progress, threshold).await
+            }
+            _ => call.await,
+        };
+        let result = result.unwrap_or_else(|| Err(cancelled(&request.name)));
+        match result {
+            Ok(result) => {
+                info!(
+                    tool_name = %request.name,
+                    success = true,
+                    "MCP call_tool request completed successfully"
+                );
+                Ok(result)
+            }
+            Err(e) => {
+                warn!(
+                    tool_name = %request.name,
+                    success = false,
+                    error = %e,
+                    "MCP call_tool request failed"
+                );
+                // Convert ToolCallError to ErrorData and return as error
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Transformer registered for the name of `tool`, or else for one of its
+    /// tags
+    fn registered_transformer(&self, tool: &ToolMetadata) -> Option<&Arc<dyn ResponseTransformer>> {
+        let registered = |matches: &dyn Fn(&str) -> bool| {
+            self.tool_transformers
+                .iter()
+                .find(|(key, _)| matches(key.as_str()))
+                .map(|(_, transformer)| transformer)
+        };
+        registered(&|key| key == tool.name).or_else(|| {
+            registered(&|key| {
+                tool.tags
+                    .iter()
+                    .any(|tag| normalize_tag(tag) == normalize_tag(key))
+            })
+        })
     }
 
-    /// Get basic tool statistics
-    #[must_use]
-    pub fn get_tool_stats(&self) -> String {
-        self.tool_collection.get_stats()
+    /// Types of the errors the tool of `metadata` may report besides the
+    /// common ones, following the features enabled
+    ///
+    /// Calls refused while shutting down or cancelled by the client are not
+    /// declared, as no feature of the tool decides them.
+    fn error_types(&self, metadata: &ToolMetadata) -> Vec<&'static str> {
+        let enabled = [
+            (self.circuit_breaker.is_some(), "upstream-unavailable"),
+            (self.rate_limit.is_some(), "rate-limit-exceeded"),
+            (self.concurrency.is_some(), "throttled"),
+            (
+                self.client_credentials.is_some()
+                    || self.token_exchange.is_some()
+                    || self.scope_check.is_some(),
+                "authentication-failed",
+            ),
+            (metadata.requires_auth(), "authentication-required"),
+            (self.scope_check.is_some(), "insufficient-scope"),
+            (
+                self.confirmation
+                    .as_ref()
+                    .is_some_and(|config| config.applies_to(&metadata.method)),
+                "confirmation-required",
+            ),
+            (self.read_only, "read-only"),
+            (self.allowed_hosts.is_some(), "host-not-allowed"),
+            (self.call_queue.is_some(), "server-busy"),
+            (self.max_response_bytes.is_some(), "response-truncated"),
+        ];
+        enabled
+            .into_iter()
+            .filter_map(|(enabled, error_type)| enabled.then_some(error_type))
+            .collect()
     }
 
-    /// Simple validation - check that tools are loaded
-    ///
-    /// # Errors
+    /// Whether the caller may list and call the custom tool `custom`
     ///
-    /// Returns an error if no tools are loaded
-    pub fn validate_registry(&self) -> Result<(), Error> {
-        if self.tool_collection.is_empty() {
-            return Err(Error::McpError("No tools loaded".to_string()));
+    /// Custom tools have no tags, so sessions limited to some tags don't see
+    /// them.
+    async fn allows_custom_tool(
+        &self,
+        custom: &CustomTool,
+        context: &RequestContext<RoleServer>,
+    ) -> bool {
+        if self.disabled_tools.contains(custom.name()) || !self.session.settings().exposes(&[]) {
+            return false;
+        }
+        match &self.tool_filter {
+            Some(filter) => filter.allow_custom(custom, context).await,
+            None => true,
         }
-        Ok(())
     }
 
-    /// Extract title from OpenAPI spec info section
-    fn extract_openapi_title(&self) -> Option<String> {
-        self.openapi_spec
-            .get("info")?
-            .get("title")?
-            .as_str()
-            .map(|s| s.to_string())
+    /// Answer a call to a custom tool, counted in flight, queued and
+    /// cancelled like the calls of generated tools
+    async fn call_custom_tool(
+        &self,
+        custom: &CustomTool,
+        arguments: serde_json::Map<String, Value>,
+        context: &RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let _in_flight = self.start_call(custom.name())?;
+        let call = async {
+            let _worker = self.enter_queue(custom.name()).await?;
+            custom.call(arguments).await
+        };
+        context
+            .ct
+            .run_until_cancelled(call)
+            .await
+            .unwrap_or_else(|| Err(cancelled(custom.name()).into()))
     }
 
-    /// Extract version from OpenAPI spec info section
-    fn extract_openapi_version(&self) -> Option<String> {
-        self.openapi_spec
-            .get("info")?
-            .get("version")?
-            .as_str()
-            .map(|s| s.to_string())
+    /// Count a call to the tool `name` until the returned guard is dropped,
+    /// unless shutting down
+    fn start_call(&self, name: &str) -> Result<InFlightCall, ToolCallError> {
+        self.in_flight.start().ok_or_else(|| {
+            warn!(tool_name = %name, "Refusing tool call while shutting down");
+            ToolCallError::Execution(ToolCallExecutionError::ShuttingDown {
+                tool: name.to_string(),
+            })
+        })
     }
 
-    /// Extract description from OpenAPI spec info section
-    fn extract_openapi_description(&self) -> Option<String> {
-        self.openapi_spec
-            .get("info")?
-            .get("description")?
-            .as_str()
-            .map(|s| s.to_string())
+    /// Wait for a worker of the call queue, if any, to call the tool `name`
+    async fn enter_queue(&self, name: &str) -> Result<Option<OwnedSemaphorePermit>, ToolCallError> {
+        let Some(queue) = &self.call_queue else {
+            return Ok(None);
+        };
+        queue.enter().await.map(Some).map_err(|busy| {
+            warn!(
+                tool_name = %name,
+                reason = busy.reason,
+                "Refusing tool call while the server is busy"
+            );
+            ToolCallError::Execution(ToolCallExecutionError::ServerBusy {
+                reason: busy.reason.to_string(),
+                retry_after_secs: busy.retry_after.as_secs().max(1),
+            })
+        })
     }
 
-    /// Extract display title from OpenAPI spec info section
-    /// First checks for x-display-title extension, then derives from title
-    fn extract_openapi_display_title(&self) -> Option<String> {
-        // First check for x-display-title extension
-        if let Some(display_title) = self
-            .openapi_spec
-            .get("info")
-            .and_then(|info| info.get("x-display-title"))
-            .and_then(|t| t.as_str())
+    /// Name of the tool `name` is an alias of, or `name` itself
+    fn aliased<'a>(&'a self, name: &'a str) -> &'a str {
+        self.tool_aliases.get(name).map_or(name, String::as_str)
+    }
+
+    /// Refuse custom tool names hiding a loaded or built-in tool, or an alias
+    fn check_custom_tool_name(&self, name: &str) -> Result<(), Error> {
+        let built_in = [
+            BATCH_CALL_TOOL,
+            CONFIGURE_SESSION_TOOL,
+            DESCRIBE_TOOL_TOOL,
+            SEARCH_TOOLS_TOOL,
+        ];
+        if self.tool_collection.has_tool(name)
+            || self.tool_aliases.contains_key(name)
+            || built_in.contains(&name)
         {
-            return Some(display_title.to_string());
+            return Err(Error::ToolGeneration(format!(
+                "Custom tool '{name}' is named like another tool"
+            )));
         }
+        Ok(())
+    }
 
-        // Fallback: enhance the title with "Server" suffix if not already present
-        self.extract_openapi_title().map(|title| {
-            if title.to_lowercase().contains("server") {
-                title
-            } else {
-                format!("{} Server", title)
-            }
-        })
+    /// Answer a call to the [`CONFIGURE_SESSION_TOOL`] tool
+    fn call_configure_session(
+        &self,
+        arguments: Option<serde_json::Map<String, Value>>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let update: SessionSettings =
+            serde_json::from_value(Value::Object(arguments.unwrap_or_default())).map_err(|e| {
+                ErrorData::invalid_params(format!("Invalid session settings: {e}"), None)
+            })?;
+        let settings = self
+            .configure_session(update)
+            .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+        let summary = serde_json::json!({
+            "auth_token": settings.auth_token.is_some(),
+            "base_url": settings.base_url.as_ref().map(Url::as_str),
+            "tags": settings.tags,
+        });
+        Ok(CallToolResult::success(vec![Content::text(
+            summary.to_string(),
+        )]))
     }
 }
 
 impl ServerHandler for Server {
+    async fn initialize(
+        &self,
+        request: InitializeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, ErrorData> {
+        // Sessions may pass their settings along with the handshake
+        if self.session_configuration
+            && let Some(settings) = request
+                .meta
+                .as_ref()
+                .and_then(|meta| meta.get(SESSION_META_KEY))
+        {
+            let update: SessionSettings =
+                serde_json::from_value(settings.clone()).map_err(|e| {
+                    ErrorData::invalid_params(format!("Invalid session settings: {e}"), None)
+                })?;
+            self.configure_session(update)
+                .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+        }
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request);
+        }
+        Ok(self.get_info())
+    }
+
     fn get_info(&self) -> InitializeResult {
         // 3-level fallback for server name: custom -> OpenAPI spec -> default
         let server_name = self
@@ -300,7 +1593,7 @@ impl ServerHandler for Server {
         let instructions = self
             .instructions
             .clone()
-            .or_else(|| self.extract_openapi_description())
+            .or_else(|| self.extract_openapi_instructions())
             .or_else(|| Some("Exposes OpenAPI endpoints as MCP tools".to_string()));
 
         let mut server_info = Implementation::new(server_name, server_version);
@@ -311,6 +1604,20 @@ impl ServerHandler for Server {
         capabilities.tools = Some(ToolsCapability {
             list_changed: Some(false),
         });
+        if self.spec_resources || self.blob_store.is_some() {
+            capabilities.resources = Some(ResourcesCapability {
+                subscribe: Some(false),
+                list_changed: Some(false),
+            });
+        }
+        if self.tool_prompts {
+            capabilities.prompts = Some(PromptsCapability {
+                list_changed: Some(false),
+            });
+        }
+        if self.client_log.is_some() {
+            capabilities.logging = Some(serde_json::Map::new());
+        }
 
         let mut result = InitializeResult::new(capabilities)
             .with_protocol_version(ProtocolVersion::V_2024_11_05)
@@ -319,9 +1626,26 @@ impl ServerHandler for Server {
         result
     }
 
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        let Some(log) = &self.client_log else {
+            return Err(ErrorData::invalid_request("Logging is disabled", None));
+        };
+        let session_id = context
+            .extensions
+            .get::<SessionId>()
+            .map(|id| id.0.as_str());
+        info!(level = ?request.level, "Client subscribed to log messages");
+        log.subscribe(context.peer, session_id, request.level);
+        Ok(())
+    }
+
     async fn list_tools(
         &self,
-        _request: Option<PaginatedRequestParams>,
+        request: Option<PaginatedRequestParams>,
         context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, ErrorData> {
         let span = info_span!("list_tools", tool_count = self.tool_collection.len());
@@ -345,125 +1669,226 @@ impl ServerHandler for Server {
             tools = filtered;
         }
 
+        // Apply the session's tags and the disabled tools, and offer the
+        // session to change its settings
+        let session = self.session.settings();
+        tools.retain(|mcp_tool| {
+            !self.disabled_tools.contains(&mcp_tool.name)
+                && self
+                    .tool_collection
+                    .get_tool(&mcp_tool.name)
+                    .is_some_and(|tool| session.exposes(&tool.metadata.tags))
+        });
+        // List aliases alongside their tool
+        let aliases: Vec<_> = self
+            .tool_aliases
+            .iter()
+            .filter_map(|(alias, name)| {
+                let mut listed = tools
+                    .iter()
+                    .find(|mcp_tool| mcp_tool.name == name.as_str())?
+                    .clone();
+                listed.name = alias.clone().into();
+                Some(listed)
+            })
+            .collect();
+        tools.extend(aliases);
+        if self.compact_tool_listings {
+            for mcp_tool in &mut tools {
+                if let Some(tool) = self.tool_collection.get_tool(self.aliased(&mcp_tool.name)) {
+                    let name = mcp_tool.name.clone();
+                    *mcp_tool = describe::compact(tool);
+                    mcp_tool.name = name;
+                }
+            }
+            tools.push(describe_tool_tool());
+        }
+        if self.session_configuration {
+            tools.push(configure_session_tool());
+        }
+        if self.tool_search {
+            tools.push(search_tools_tool());
+        }
+        if self.batch_calls {
+            tools.push(batch_call_tool());
+        }
+        for custom in &self.custom_tools {
+            if self.allows_custom_tool(custom, &context).await {
+                tools.push(custom.tool().clone());
+            }
+        }
+
+        let cursor = request.and_then(|request| request.cursor);
+        let (tools, next_cursor) = paginate(tools, cursor.as_deref(), self.tools_page_size)?;
+
         info!(
             returned_tools = tools.len(),
+            has_next_page = next_cursor.is_some(),
             "MCP list_tools request completed successfully"
         );
 
         Ok(ListToolsResult {
             meta: None,
             tools,
-            next_cursor: None,
+            next_cursor,
         })
     }
 
-    async fn call_tool(
+    async fn list_resources(
         &self,
-        request: CallToolRequestParams,
+        _request: Option<PaginatedRequestParams>,
         context: RequestContext<RoleServer>,
-    ) -> Result<CallToolResult, ErrorData> {
-        use crate::error::{ToolCallError, ToolCallValidationError};
-
-        let span = info_span!(
-            "call_tool",
-            tool_name = %request.name
-        );
-        let _enter = span.enter();
-
+    ) -> Result<ListResourcesResult, ErrorData> {
+        let mut resources = if self.spec_resources {
+            resources::list(&self.openapi_spec, &self.tool_collection)
+        } else {
+            Vec::new()
+        };
+        if let Some(store) = &self.blob_store {
+            let session_id = context
+                .extensions
+                .get::<SessionId>()
+                .map(|id| id.0.as_str());
+            resources.extend(store.list(session_id));
+        }
         debug!(
-            tool_name = %request.name,
-            has_arguments = !request.arguments.as_ref().unwrap_or(&serde_json::Map::new()).is_empty(),
-            "Processing MCP call_tool request"
+            resource_count = resources.len(),
+            "MCP list_resources request completed"
         );
+        Ok(ListResourcesResult {
+            meta: None,
+            resources,
+            next_cursor: None,
+        })
+    }
 
-        // Filter all tools once upfront (for both access check and suggestions)
-        let allowed_tools: Vec<&Tool> = match &self.tool_filter {
-            None => self.tool_collection.iter().collect(),
-            Some(filter) => {
-                let mut allowed = Vec::new();
-                for tool in self.tool_collection.iter() {
-                    if filter.allow(tool, &context).await {
-                        allowed.push(tool);
-                    }
-                }
-                allowed
-            }
-        };
-
-        // Check if requested tool is in filtered list
-        let tool = allowed_tools
-            .iter()
-            .find(|t| t.metadata.name == request.name);
-
-        let tool = match tool {
-            Some(t) => *t,
-            None => {
-                let available_names: Vec<&str> = allowed_tools
-                    .iter()
-                    .map(|t| t.metadata.name.as_str())
-                    .collect();
-
-                // Uses Jaro distance for suggestions internally
-                let error = ToolCallError::Validation(ToolCallValidationError::tool_not_found(
-                    request.name.to_string(),
-                    &available_names,
-                ));
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        let session_id = context
+            .extensions
+            .get::<SessionId>()
+            .map(|id| id.0.as_str());
+        if let Some(blob) = self
+            .blob_store
+            .as_ref()
+            .and_then(|store| store.get(&request.uri, session_id))
+        {
+            use base64::{Engine as _, engine::general_purpose::STANDARD};
+            debug!(uri = %request.uri, "MCP read_resource request completed");
+            return Ok(ReadResourceResult::new(vec![
+                ResourceContents::BlobResourceContents {
+                    uri: request.uri,
+                    mime_type: Some(blob.content_type),
+                    blob: STANDARD.encode(&blob.bytes),
+                    meta: None,
+                },
+            ]));
+        }
 
-                warn!(
-                    tool_name = %request.name,
-                    success = false,
-                    error = %error,
-                    "MCP call_tool request failed - tool not found or filtered"
-                );
+        let content = self
+            .spec_resources
+            .then(|| resources::read(&request.uri, &self.openapi_spec, &self.tool_collection))
+            .flatten()
+            .ok_or_else(|| {
+                ErrorData::resource_not_found(format!("Unknown resource '{}'", request.uri), None)
+            })?;
+        debug!(uri = %request.uri, "MCP read_resource request completed");
+        Ok(ReadResourceResult::new(vec![ResourceContents::text(
+            content,
+            request.uri,
+        )]))
+    }
 
-                return Err(error.into());
-            }
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        let prompts = if self.tool_prompts {
+            let session = self.session.settings();
+            prompts::list(self.tool_collection.iter().filter(|tool| {
+                session.exposes(&tool.metadata.tags)
+                    && !self.disabled_tools.contains(&tool.metadata.name)
+            }))
+        } else {
+            Vec::new()
         };
+        debug!(
+            prompt_count = prompts.len(),
+            "MCP list_prompts request completed"
+        );
+        Ok(ListPromptsResult {
+            meta: None,
+            prompts,
+            next_cursor: None,
+        })
+    }
 
-        let arguments = request.arguments.unwrap_or_default();
-        let arguments_value = Value::Object(arguments);
-
-        // Extract authorization header from context extensions
-        let auth_header = context.extensions.get::<AuthorizationHeader>().cloned();
-
-        if auth_header.is_some() {
-            debug!("Authorization header is present");
-        }
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let session = self.session.settings();
+        let tool = self
+            .tool_collection
+            .get_tool(&request.name)
+            .filter(|tool| {
+                self.tool_prompts
+                    && session.exposes(&tool.metadata.tags)
+                    && !self.disabled_tools.contains(&tool.metadata.name)
+            })
+            .ok_or_else(|| {
+                ErrorData::invalid_params(format!("Unknown prompt '{}'", request.name), None)
+            })?;
+        debug!(prompt = %request.name, "MCP get_prompt request completed");
+        prompts::get(tool, request.arguments.as_ref())
+    }
 
-        // Create Authorization enum from mode and header
-        let authorization = Authorization::from_mode(self.authorization_mode, auth_header);
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        self.call_tool_boxed(request, context).await
+    }
+}
 
-        // Get the server-level transformer as a reference for the tool call
-        let server_transformer = self
-            .response_transformer
-            .as_ref()
-            .map(|t| t.as_ref() as &dyn ResponseTransformer);
+/// Error of a call to the tool `name` cancelled by the client
+fn cancelled(name: &str) -> ToolCallError {
+    info!(tool_name = %name, "MCP call_tool request cancelled by the client");
+    ToolCallError::Execution(ToolCallExecutionError::Cancelled {
+        tool: name.to_string(),
+    })
+}
 
-        // Execute the tool directly (we already have the validated tool reference)
-        match tool
-            .call(&arguments_value, authorization, server_transformer)
-            .await
-        {
-            Ok(result) => {
-                info!(
-                    tool_name = %request.name,
-                    success = true,
-                    "MCP call_tool request completed successfully"
-                );
-                Ok(result)
-            }
-            Err(e) => {
-                warn!(
-                    tool_name = %request.name,
-                    success = false,
-                    error = %e,
-                    "MCP call_tool request failed"
-                );
-                // Convert ToolCallError to ErrorData and return as error
-                Err(e.into())
-            }
-        }
-    }
+/// The page of `items` the `cursor` of a list request points to, and the
+/// cursor of the next page if any
+///
+/// Cursors hold the offset of the page, which clients treat as opaque.
+fn paginate<T>(
+    mut items: Vec<T>,
+    cursor: Option<&str>,
+    page_size: Option<NonZeroUsize>,
+) -> Result<(Vec<T>, Option<String>), ErrorData> {
+    let start = match cursor {
+        None => 0,
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .ok()
+            .filter(|start| *start <= items.len())
+            .ok_or_else(|| ErrorData::invalid_params(format!("Invalid cursor '{cursor}'"), None))?,
+    };
+    let end = page_size.map_or(items.len(), |size| {
+        start.saturating_add(size.get()).min(items.len())
+    });
+    let next_cursor = (end < items.len()).then(|| end.to_string());
+    items.truncate(end);
+    items.drain(..start);
+    Ok((items, next_cursor))
 }
 
 #[cfg(test)]
@@ -520,6 +1945,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/pet/{petId}".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -543,6 +1969,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/pet/findByStatus".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -598,6 +2025,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/pet/{petId}".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -784,8 +2212,40 @@ mod tests {
         assert_eq!(result.server_info.name, "OpenAPI Server");
         assert_eq!(result.server_info.version, "1.5.0");
         assert_eq!(
-            result.instructions,
-            Some("Server from OpenAPI spec".to_string())
+            result.instructions.as_deref(),
+            Some(
+                "Tools calling the OpenAPI Server API, version 1.5.0.\n\nServer from OpenAPI spec"
+            )
+        );
+    }
+
+    #[test]
+    fn test_get_info_instructions_include_terms_of_service() {
+        let openapi_spec = json!({
+            "openapi": "3.0.0",
+            "info": {
+                "title": "Petstore",
+                "version": "1.0.0",
+                "termsOfService": "https://example.com/terms"
+            },
+            "paths": {}
+        });
+
+        let server = Server::new(
+            openapi_spec,
+            url::Url::parse("http://example.com").unwrap(),
+            None,
+            None,
+            false,
+            false,
+            false,
+        );
+
+        assert_eq!(
+            server.get_info().instructions.as_deref(),
+            Some(
+                "Tools calling the Petstore API, version 1.0.0.\n\nTerms of service: https://example.com/terms"
+            )
         );
     }
 
@@ -846,4 +2306,25 @@ mod tests {
         // Custom instructions take precedence
         assert_eq!(result.instructions, Some("Custom instructions".to_string()));
     }
+
+    #[test]
+    fn test_tools_are_listed_a_page_at_a_time() {
+        let page_size = NonZeroUsize::new(2);
+        let items = vec![1, 2, 3, 4, 5];
+
+        let (page, cursor) = paginate(items.clone(), None, page_size).unwrap();
+        assert_eq!(page, vec![1, 2]);
+        let (page, cursor) = paginate(items.clone(), cursor.as_deref(), page_size).unwrap();
+        assert_eq!(page, vec![3, 4]);
+        let (page, cursor) = paginate(items.clone(), cursor.as_deref(), page_size).unwrap();
+        assert_eq!(page, vec![5]);
+        assert_eq!(cursor, None);
+
+        assert_eq!(
+            paginate(items.clone(), None, None).unwrap(),
+            (items.clone(), None)
+        );
+        assert!(paginate(items.clone(), Some("6"), page_size).is_err());
+        assert!(paginate(items, Some("abc"), page_size).is_err());
+    }
 }
@@ -0,0 +1,178 @@
+//! Settings MCP sessions choose for their own tool calls.
+//!
+//! In stateful mode the transport serves each session with its own server,
+//! created with [`Server::for_session`](crate::Server::for_session). When
+//! session configuration is enabled, a session can set a bearer token, a base
+//! URL and the tags of the tools it uses, either in the `_meta` of its
+//! `initialize` request under [`SESSION_META_KEY`] or by calling the
+//! [`CONFIGURE_SESSION_TOOL`] tool:
+//!
+//! ```json
+//! {
+//!   "_meta": {
+//!     "rmcp-openapi/session": {
+//!       "auth_token": "...",
+//!       "base_url": "https://eu.api.example.com/v2",
+//!       "tags": ["issues"]
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! The settings only apply to that session's tool calls.
+
+use rmcp::model::Tool as McpTool;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use url::Url;
+
+use crate::normalize_tag;
+use crate::redaction::REDACTED;
+
+/// Key of the session settings in the `_meta` of an `initialize` request
+pub const SESSION_META_KEY: &str = "rmcp-openapi/session";
+
+/// Name of the tool sessions change their settings with
+pub const CONFIGURE_SESSION_TOOL: &str = "configure_session";
+
+/// Settings of an MCP session
+///
+/// Settings left out keep their current value.
+#[derive(Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SessionSettings {
+    /// Bearer token sent upstream instead of the configured authorization
+    #[serde(default)]
+    pub auth_token: Option<String>,
+    /// Base URL replacing the configured one; its host must be the configured
+    /// base URL's or be in the host allowlist
+    #[serde(default)]
+    pub base_url: Option<Url>,
+    /// Only expose the tools having one of these tags
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+}
+
+impl SessionSettings {
+    /// These settings updated with the ones `update` sets
+    pub(crate) fn merged(&self, update: SessionSettings) -> Self {
+        Self {
+            auth_token: update.auth_token.or_else(|| self.auth_token.clone()),
+            base_url: update.base_url.or_else(|| self.base_url.clone()),
+            tags: update.tags.or_else(|| self.tags.clone()),
+        }
+    }
+
+    /// Whether a tool with `tool_tags` is exposed to the session
+    pub(crate) fn exposes(&self, tool_tags: &[String]) -> bool {
+        self.tags.as_ref().is_none_or(|tags| {
+            tool_tags.iter().any(|tool_tag| {
+                tags.iter()
+                    .any(|tag| normalize_tag(tag) == normalize_tag(tool_tag))
+            })
+        })
+    }
+}
+
+impl fmt::Debug for SessionSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionSettings")
+            .field("auth_token", &self.auth_token.as_ref().map(|_| REDACTED))
+            .field("base_url", &self.base_url)
+            .field("tags", &self.tags)
+            .finish()
+    }
+}
+
+/// Settings of the session a server serves
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SessionState {
+    settings: Arc<RwLock<SessionSettings>>,
+}
+
+impl SessionState {
+    /// Current settings
+    pub(crate) fn settings(&self) -> SessionSettings {
+        self.settings
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the settings
+    pub(crate) fn set(&self, settings: SessionSettings) {
+        *self.settings.write().unwrap_or_else(|e| e.into_inner()) = settings;
+    }
+}
+
+/// The [`CONFIGURE_SESSION_TOOL`] tool, as listed to clients
+pub(crate) fn configure_session_tool() -> McpTool {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "auth_token": {
+                "type": "string",
+                "description": "Bearer token sent to the API on behalf of this session"
+            },
+            "base_url": {
+                "type": "string",
+                "format": "uri",
+                "description": "Base URL of the API for this session"
+            },
+            "tags": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "Only use the tools having one of these tags"
+            }
+        },
+        "additionalProperties": false
+    });
+    let serde_json::Value::Object(schema) = schema else {
+        unreachable!("schema is an object");
+    };
+    McpTool::new_with_raw(
+        CONFIGURE_SESSION_TOOL,
+        Some("Change the settings of this session; settings left out are kept".into()),
+        Arc::new(schema),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_updates_keep_unset_settings() {
+        let settings = SessionSettings {
+            auth_token: Some("s3cret".to_string()),
+            tags: Some(vec!["issues".to_string()]),
+            ..SessionSettings::default()
+        };
+        let update: SessionSettings = serde_json::from_value(json!({
+            "base_url": "https://eu.api.example.com"
+        }))
+        .unwrap();
+        let merged = settings.merged(update);
+        assert_eq!(merged.auth_token.as_deref(), Some("s3cret"));
+        assert_eq!(merged.tags, Some(vec!["issues".to_string()]));
+        assert_eq!(
+            merged.base_url.unwrap().as_str(),
+            "https://eu.api.example.com/"
+        );
+        assert!(!format!("{settings:?}").contains("s3cret"));
+    }
+
+    #[test]
+    fn test_tags_limit_exposed_tools() {
+        let settings = SessionSettings {
+            tags: Some(vec!["Pull Requests".to_string()]),
+            ..SessionSettings::default()
+        };
+        assert!(settings.exposes(&["pullRequests".to_string()]));
+        assert!(!settings.exposes(&["issues".to_string()]));
+        assert!(!settings.exposes(&[]));
+        assert!(SessionSettings::default().exposes(&[]));
+    }
+}
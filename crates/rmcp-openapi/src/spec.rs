@@ -4,6 +4,7 @@ use crate::tool::ToolMetadata;
 use crate::tool_generator::ToolGenerator;
 use bon::Builder;
 use oas3::Spec as Oas3Spec;
+use oas3::spec::{ObjectOrReference, Operation, Parameter};
 use reqwest::Method;
 use serde::de::IntoDeserializer;
 use serde_json::Value;
@@ -115,6 +116,15 @@ impl Spec {
                             }
                         }
 
+                        // Path-level parameters apply to every operation under the path
+                        let merged;
+                        let operation = if path_item.parameters.is_empty() {
+                            operation
+                        } else {
+                            merged = with_path_item_parameters(operation, &path_item.parameters);
+                            &merged
+                        };
+
                         let tool_metadata = ToolGenerator::generate_tool_metadata(
                             operation,
                             method.to_string(),
@@ -151,6 +161,7 @@ impl Spec {
         skip_parameter_descriptions: bool,
         parameter_examples_in_description: bool,
         insecure: bool,
+        strip_non_standard_bodies: bool,
     ) -> Result<Vec<crate::tool::Tool>, Error> {
         // First generate the tool metadata using existing method
         let tools_metadata = self.to_tool_metadata(
@@ -166,6 +177,7 @@ impl Spec {
             base_url,
             default_headers,
             insecure,
+            strip_non_standard_bodies,
         )
     }
 
@@ -206,6 +218,31 @@ impl Spec {
         None
     }
 
+    /// Get the operation at `method` and `path`, told apart even when
+    /// operation IDs are not unique
+    pub fn get_operation_at(
+        &self,
+        method: &str,
+        path: &str,
+    ) -> Option<(&oas3::spec::Operation, String, String)> {
+        let path_item = self.spec.paths.as_ref()?.get(path)?;
+        let operation = match method.to_uppercase().parse::<Method>().ok()? {
+            Method::GET => &path_item.get,
+            Method::POST => &path_item.post,
+            Method::PUT => &path_item.put,
+            Method::DELETE => &path_item.delete,
+            Method::PATCH => &path_item.patch,
+            Method::HEAD => &path_item.head,
+            Method::OPTIONS => &path_item.options,
+            Method::TRACE => &path_item.trace,
+            _ => return None,
+        };
+        let method = method.to_uppercase();
+        operation
+            .as_ref()
+            .map(|operation| (operation, method, path.to_string()))
+    }
+
     /// Get all operation IDs
     pub fn get_operation_ids(&self) -> Vec<String> {
         let mut operation_ids = Vec::new();
@@ -241,6 +278,39 @@ impl Spec {
     }
 }
 
+/// Return a copy of `operation` that also carries the parameters declared on its
+/// path item. Operation-level parameters override path-level ones with the same
+/// name and location, as the `OpenAPI` specification requires.
+fn with_path_item_parameters(
+    operation: &Operation,
+    path_parameters: &[ObjectOrReference<Parameter>],
+) -> Operation {
+    let overridden = |candidate: &ObjectOrReference<Parameter>| {
+        operation
+            .parameters
+            .iter()
+            .any(|existing| match (existing, candidate) {
+                (ObjectOrReference::Object(a), ObjectOrReference::Object(b)) => {
+                    a.name == b.name && a.location == b.location
+                }
+                (
+                    ObjectOrReference::Ref { ref_path: a, .. },
+                    ObjectOrReference::Ref { ref_path: b, .. },
+                ) => a == b,
+                _ => false,
+            })
+    };
+
+    let mut merged = operation.clone();
+    merged.parameters = path_parameters
+        .iter()
+        .filter(|p| !overridden(p))
+        .cloned()
+        .chain(operation.parameters.iter().cloned())
+        .collect();
+    merged
+}
+
 #[derive(Builder, Debug, Clone)]
 pub struct Filters {
     pub tags: Option<Filter<String>>,
@@ -1084,4 +1154,50 @@ mod tests {
         assert!(tool_names.contains(&"deleteUser"));
         assert!(tool_names.contains(&"patchPet"));
     }
+
+    #[test]
+    fn test_path_item_parameters_are_inherited() {
+        let spec = Spec::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [
+                        { "name": "petId", "in": "path", "required": true, "schema": { "type": "string" } },
+                        { "name": "verbose", "in": "query", "schema": { "type": "boolean" } }
+                    ],
+                    "get": {
+                        "operationId": "getPet",
+                        "tags": ["pet"],
+                        "parameters": [
+                            { "name": "verbose", "in": "query", "description": "Operation override", "schema": { "type": "boolean" } }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                },
+                "/broken/{id}": {
+                    "get": {
+                        "operationId": "broken",
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut filters = Filters::builder().build();
+        filters.operations_id = Some(Filter::Include(vec!["getPet".to_string()]));
+        let tools = spec
+            .to_tool_metadata(Some(&filters), false, false, false)
+            .unwrap();
+        let properties = &tools[0].parameters["properties"];
+        assert!(properties.get("petId").is_some());
+        assert_eq!(properties["verbose"]["description"], "Operation override");
+
+        // Placeholders without a matching parameter fail generation
+        let err = spec
+            .to_tool_metadata(None, false, false, false)
+            .unwrap_err();
+        assert!(err.to_string().contains("'broken'"), "{err}");
+    }
 }
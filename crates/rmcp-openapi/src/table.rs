@@ -0,0 +1,112 @@
+//! Markdown rendering of tabular responses.
+//!
+//! Chat clients show the text content of tool results, which for JSON
+//! responses is the serialized JSON. With tables enabled, a successful
+//! response whose body is a list of flat objects (or an object holding such
+//! a list as `items`, `data`, `results`, ...) has a Markdown table as text
+//! content instead, one row per object and one column per member, cut after
+//! `max_rows` rows. Structured content keeps the full JSON.
+
+use bon::Builder;
+use serde_json::{Map, Value};
+
+/// How responses are rendered as Markdown tables
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct TableConfig {
+    /// Rows rendered at most; a note tells how many were left out
+    #[builder(default = 50)]
+    pub max_rows: usize,
+}
+
+/// Markdown table of `body`, if it is a list of flat objects
+pub(crate) fn render(body: &Value, config: &TableConfig) -> Option<String> {
+    let rows: Vec<&Map<String, Value>> = crate::pagination::items(body)?
+        .iter()
+        .map(Value::as_object)
+        .collect::<Option<_>>()?;
+    let flat = |row: &&Map<String, Value>| {
+        row.values()
+            .all(|value| !value.is_array() && !value.is_object())
+    };
+    if rows.is_empty() || !rows.iter().all(flat) {
+        return None;
+    }
+
+    let mut columns: Vec<&str> = Vec::new();
+    for name in rows.iter().flat_map(|row| row.keys()) {
+        if !columns.contains(&name.as_str()) {
+            columns.push(name);
+        }
+    }
+    if columns.is_empty() {
+        return None;
+    }
+
+    let mut table = format!(
+        "| {} |\n|{}\n",
+        columns
+            .iter()
+            .map(|column| cell(column))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        " --- |".repeat(columns.len())
+    );
+    for row in rows.iter().take(config.max_rows) {
+        let cells: Vec<_> = columns
+            .iter()
+            .map(|column| match row.get(*column) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(value)) => cell(value),
+                Some(value) => value.to_string(),
+            })
+            .collect();
+        table.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    if rows.len() > config.max_rows {
+        table.push_str(&format!(
+            "\n_Showing {} of {} rows; the structured content has them all._\n",
+            config.max_rows,
+            rows.len()
+        ));
+    }
+    Some(table)
+}
+
+/// `value` written so it fits in one table cell
+fn cell(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_lists_of_flat_objects_become_tables() {
+        let config = TableConfig::builder().max_rows(2).build();
+        let body = json!({
+            "data": [
+                { "id": 1, "name": "Rex | dog", "tag": null },
+                { "id": 2, "name": "Tom", "sold": true },
+                { "id": 3, "name": "Kit" }
+            ],
+            "total": 3
+        });
+        assert_eq!(
+            render(&body, &config).unwrap(),
+            "| id | name | tag | sold |\n| --- | --- | --- | --- |\n\
+             | 1 | Rex \\| dog |  |  |\n| 2 | Tom |  | true |\n\
+             \n_Showing 2 of 3 rows; the structured content has them all._\n"
+        );
+
+        assert!(render(&json!([{ "id": 1, "owner": { "id": 2 } }]), &config).is_none());
+        assert!(render(&json!([1, 2]), &config).is_none());
+        assert!(render(&json!([]), &config).is_none());
+        assert!(render(&json!({ "id": 1 }), &config).is_none());
+    }
+}
@@ -0,0 +1,152 @@
+//! Distributed tracing across MCP clients, this server and upstream APIs.
+//!
+//! MCP clients send their trace context in W3C `traceparent` and `tracestate`
+//! headers, which the transport stores as a [`TraceContext`] request extension
+//! (see the `on_request` hook of `StreamableHttpService`). The `call_tool` span
+//! then continues the client's trace, and upstream requests carry the context
+//! of their `http_request` span, so a tool call can be followed from the agent
+//! down to the backend.
+//!
+//! Spans only have an OpenTelemetry context once the application installs a
+//! `tracing-opentelemetry` layer; exporting them is up to that layer's tracer.
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use reqwest::RequestBuilder;
+use std::collections::HashMap;
+use tracing::{Span, debug};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Header carrying the trace and parent span IDs
+pub const TRACEPARENT: &str = "traceparent";
+
+/// Header carrying vendor-specific trace data
+pub const TRACESTATE: &str = "tracestate";
+
+/// W3C trace context an MCP request was sent with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub traceparent: String,
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Context of a request with these header values, if `traceparent` is valid
+    pub fn new(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let traceparent = traceparent.trim();
+        if !is_valid_traceparent(traceparent) {
+            debug!(traceparent = %traceparent, "Ignoring invalid traceparent header");
+            return None;
+        }
+        Some(Self {
+            traceparent: traceparent.to_string(),
+            tracestate: tracestate
+                .map(str::trim)
+                .filter(|state| !state.is_empty())
+                .map(str::to_string),
+        })
+    }
+
+    /// Make `span` a child of the remote span this context names
+    ///
+    /// Must be called before `span` is entered.
+    pub fn set_parent_of(&self, span: &Span) {
+        let mut carrier = HashMap::from([(TRACEPARENT.to_string(), self.traceparent.clone())]);
+        if let Some(tracestate) = &self.tracestate {
+            carrier.insert(TRACESTATE.to_string(), tracestate.clone());
+        }
+        let context = TraceContextPropagator::new().extract(&carrier);
+        if let Err(e) = span.set_parent(context) {
+            debug!(error = %e, "Unable to continue the client's trace");
+        }
+    }
+}
+
+/// Add the trace context of the current span to `request`
+///
+/// Leaves `request` untouched when the current span has no OpenTelemetry
+/// context, as when no `tracing-opentelemetry` layer is installed.
+pub(crate) fn inject_current(mut request: RequestBuilder) -> RequestBuilder {
+    let context = Span::current().context();
+    if !context.span().span_context().is_valid() {
+        return request;
+    }
+    let mut carrier: HashMap<String, String> = HashMap::new();
+    TraceContextPropagator::new().inject_context(&context, &mut carrier);
+    for (name, value) in carrier {
+        request = request.header(name, value);
+    }
+    request
+}
+
+/// Whether `value` is a `traceparent` of version 00 or a later, compatible one
+fn is_valid_traceparent(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let is_hex = |part: &str, len: usize| {
+        part.len() == len
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+    let is_zero = |part: &str| part.bytes().all(|b| b == b'0');
+    match parts.as_slice() {
+        [version, trace_id, parent_id, flags, rest @ ..] => {
+            is_hex(version, 2)
+                && *version != "ff"
+                && (rest.is_empty() || *version != "00")
+                && is_hex(trace_id, 32)
+                && !is_zero(trace_id)
+                && is_hex(parent_id, 16)
+                && !is_zero(parent_id)
+                && is_hex(flags, 2)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACEPARENT_VALUE: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_valid_context() {
+        let context = TraceContext::new(&format!(" {TRACEPARENT_VALUE} "), Some("vendor=1"))
+            .expect("valid traceparent");
+        assert_eq!(context.traceparent, TRACEPARENT_VALUE);
+        assert_eq!(context.tracestate.as_deref(), Some("vendor=1"));
+        assert_eq!(
+            TraceContext::new(TRACEPARENT_VALUE, Some(" "))
+                .unwrap()
+                .tracestate,
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_traceparents_are_ignored() {
+        for traceparent in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert!(
+                TraceContext::new(traceparent, None).is_none(),
+                "{traceparent}"
+            );
+        }
+        assert!(
+            TraceContext::new(
+                "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+                None
+            )
+            .is_some()
+        );
+    }
+}
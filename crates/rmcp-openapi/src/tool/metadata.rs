@@ -1,6 +1,10 @@
+use oas3::spec::ParameterStyle;
 use rmcp::model::{Tool, ToolAnnotations};
 use serde_json::Value;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 /// Parameter mapping information for converting between MCP and OpenAPI parameters
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -13,8 +17,18 @@ pub struct ParameterMapping {
     pub location: String,
     /// Whether the parameter should be exploded (for arrays/objects)
     pub explode: bool,
+    /// The serialization style declared in OpenAPI, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<ParameterStyle>,
+    /// Whether reserved characters are sent unencoded (query parameters only)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_reserved: bool,
 }
 
+/// Security schemes that must all be satisfied, by name, with the scopes needed
+/// from each, as in an OpenAPI security requirement object
+pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
+
 /// Internal metadata for tools generated from OpenAPI operations.
 ///
 /// This struct contains all the information needed to execute HTTP requests
@@ -46,9 +60,12 @@ pub struct ToolMetadata {
     pub method: String,
     /// URL path for the API endpoint - internal only, not exposed to MCP
     pub path: String,
-    /// Security requirements from OpenAPI spec - internal only, not exposed to MCP
+    /// Operation tags from the OpenAPI spec - internal only, not exposed to MCP
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Security requirements from OpenAPI spec, any one of which suffices - internal only, not exposed to MCP
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub security: Option<Vec<String>>,
+    pub security: Option<Vec<SecurityRequirement>>,
     /// Parameter mappings for converting between MCP and OpenAPI parameters - internal only, not exposed to MCP
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub parameter_mappings: HashMap<String, ParameterMapping>,
@@ -56,8 +73,12 @@ pub struct ToolMetadata {
 
 impl ToolMetadata {
     /// Check if this tool requires authentication based on OpenAPI security definitions
+    ///
+    /// An empty requirement (`{}`) makes authentication optional.
     pub fn requires_auth(&self) -> bool {
-        self.security.as_ref().is_some_and(|s| !s.is_empty())
+        self.security.as_ref().is_some_and(|requirements| {
+            !requirements.is_empty() && requirements.iter().all(|r| !r.is_empty())
+        })
     }
 
     /// Generate MCP annotations based on HTTP method semantics.
@@ -199,6 +220,7 @@ mod tests {
             output_schema: None,
             method: method.to_string(),
             path: "/test".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: HashMap::new(),
         }
@@ -373,4 +395,21 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_requires_auth() {
+        let mut metadata = create_test_metadata("GET");
+        assert!(!metadata.requires_auth());
+
+        let oauth = SecurityRequirement::from([("oauth".to_string(), vec!["read".to_string()])]);
+        metadata.security = Some(vec![oauth.clone()]);
+        assert!(metadata.requires_auth());
+
+        // An empty requirement makes authentication optional
+        metadata.security = Some(vec![oauth, SecurityRequirement::new()]);
+        assert!(!metadata.requires_auth());
+
+        metadata.security = Some(vec![]);
+        assert!(!metadata.requires_auth());
+    }
 }
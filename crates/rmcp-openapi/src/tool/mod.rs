@@ -1,15 +1,18 @@
 pub mod metadata;
 pub mod tool_collection;
 
-pub use metadata::{ParameterMapping, ToolMetadata};
+pub use metadata::{ParameterMapping, SecurityRequirement, ToolMetadata};
 pub use tool_collection::ToolCollection;
 
 use crate::config::Authorization;
 use crate::error::Error;
 use crate::http_client::HttpClient;
+use crate::progress::CallProgress;
+use crate::retry::RetryHint;
 use crate::security::SecurityObserver;
 use crate::transformer::ResponseTransformer;
-use rmcp::model::{CallToolResult, Tool as McpTool};
+use crate::upstream_error::ErrorSchemas;
+use rmcp::model::{CallToolResult, LoggingLevel, Meta, Tool as McpTool};
 use serde_json::Value;
 use std::sync::Arc;
 
@@ -20,6 +23,11 @@ pub struct Tool {
     http_client: HttpClient,
     /// Per-tool response transformer, overrides the global server transformer
     pub(crate) response_transformer: Option<Arc<dyn ResponseTransformer>>,
+    /// Error response schemas of the operation, typing upstream errors
+    pub(crate) error_schemas: Option<ErrorSchemas>,
+    /// Whether the output schema is the body's, structured content being the
+    /// body of successful responses
+    pub(crate) unwrapped_output: bool,
 }
 
 impl Tool {
@@ -29,9 +37,27 @@ impl Tool {
             metadata,
             http_client,
             response_transformer: None,
+            error_schemas: None,
+            unwrapped_output: false,
         })
     }
 
+    /// Same tool, sending its requests to `base_url`
+    pub(crate) fn with_base_url(&self, base_url: url::Url) -> Result<Self, Error> {
+        Ok(Self {
+            http_client: self.http_client.clone().with_base_url(base_url)?,
+            ..self.clone()
+        })
+    }
+
+    /// Same tool, recording the progress of its calls in `progress`
+    pub(crate) fn with_progress(&self, progress: CallProgress) -> Self {
+        Self {
+            http_client: self.http_client.clone().with_progress(progress),
+            ..self.clone()
+        }
+    }
+
     /// Execute tool and return MCP-compliant result
     ///
     /// # Arguments
@@ -44,39 +70,49 @@ impl Tool {
         arguments: &Value,
         authorization: Authorization,
         server_transformer: Option<&dyn ResponseTransformer>,
+    ) -> Result<CallToolResult, crate::error::ToolCallError> {
+        self.call_in_session(arguments, authorization, server_transformer, None)
+            .await
+    }
+
+    /// Execute tool on behalf of an MCP session
+    ///
+    /// Same as [`Tool::call`], with upstream requests attributed to `session_id`
+    /// in the audit log.
+    pub async fn call_in_session(
+        &self,
+        arguments: &Value,
+        authorization: Authorization,
+        server_transformer: Option<&dyn ResponseTransformer>,
+        session_id: Option<&str>,
     ) -> Result<CallToolResult, crate::error::ToolCallError> {
         use rmcp::model::Content;
         use serde_json::json;
 
-        // Create security observer for logging
-        let observer = SecurityObserver::new(&authorization);
+        // Create security observer for logging and security events
+        let observer = SecurityObserver::new(&authorization)
+            .with_sinks(self.http_client.security_event_sinks(), session_id);
 
         // Log the authorization decision
-        let has_auth = match &authorization {
-            Authorization::None => false,
-            #[cfg(feature = "authorization-token-passthrough")]
-            Authorization::PassthroughWarn(header) | Authorization::PassthroughSilent(header) => {
-                header.is_some()
-            }
-        };
-
+        let has_auth = has_auth(&authorization);
         observer.observe_request(&self.metadata.name, has_auth, self.metadata.requires_auth());
 
-        // Extract authorization header if present
-        let auth_header: Option<&rmcp_actix_web::transport::AuthorizationHeader> =
-            match &authorization {
-                Authorization::None => None,
-                #[cfg(feature = "authorization-token-passthrough")]
-                Authorization::PassthroughWarn(header)
-                | Authorization::PassthroughSilent(header) => header.as_ref(),
-            };
-
-        // Create HTTP client with authorization if provided
-        let client = if let Some(auth) = auth_header {
-            self.http_client.with_authorization(&auth.0)
-        } else {
-            self.http_client.clone()
+        let authorized = match self.check_credentials(has_auth) {
+            Ok(()) => self.authorized_client(&authorization).await,
+            Err(e) => Err(e),
         };
+        let mut client =
+            authorized.inspect_err(|e| observer.observe_error(&self.metadata.name, e))?;
+        if let Some(session_id) = session_id {
+            client = client.with_session_id(session_id);
+        }
+        let (arguments, dry_run) = crate::dry_run::take_flag(&self.metadata, arguments);
+        if dry_run {
+            let request = client.dry_run(&self.metadata, &arguments).await?;
+            return Ok(CallToolResult::structured(request));
+        }
+        let arguments = client.confirm(&self.metadata, &arguments)?;
+        let (arguments, filter) = crate::projection::take_filter(&self.metadata, arguments)?;
 
         // Determine which transformer to use: per-tool takes precedence over server-level
         let transformer = self
@@ -86,39 +122,160 @@ impl Tool {
             .or(server_transformer);
 
         // Execute the HTTP request using the (potentially auth-enhanced) HTTP client
-        match client.execute_tool_call(&self.metadata, arguments).await {
+        match client.execute_tool_call(&self.metadata, &arguments).await {
             Ok(response) => {
-                // Check if response is an image and return image content
-                if response.is_image()
+                observer.observe_response(&self.metadata.name, response.status_code);
+                if response.status_code >= 500
+                    && let Some(log) = client.client_log()
+                {
+                    log.log(
+                        LoggingLevel::Error,
+                        "upstream",
+                        json!({
+                            "message": format!(
+                                "Upstream answered {} to {} {}",
+                                response.status_code,
+                                self.metadata.method.to_uppercase(),
+                                self.metadata.path
+                            ),
+                            "tool": self.metadata.name,
+                            "status": response.status_code,
+                            "session_id": session_id,
+                        }),
+                    );
+                }
+
+                // Tell clients whether the response came from the cache
+                let meta = response.cache_status.map(|status| {
+                    let mut meta = Meta::new();
+                    meta.insert("cache".to_string(), json!(status.as_str()));
+                    meta
+                });
+
+                // Check if response is an image or audio and return it as such
+                if (response.is_image() || response.is_audio())
+                    && response.truncated_at.is_none()
                     && let Some(bytes) = &response.body_bytes
                 {
-                    // Base64 encode the image data
+                    // Base64 encode the media data
                     use base64::{Engine as _, engine::general_purpose::STANDARD};
+                    use rmcp::model::{AnnotateAble, RawAudioContent, RawContent};
                     let base64_data = STANDARD.encode(bytes);
 
-                    // Get the MIME type - it must be present for image responses
+                    // Get the MIME type - it must be present for media responses
                     let mime_type = response.content_type.as_deref().ok_or_else(|| {
                         crate::error::ToolCallError::Execution(
                             crate::error::ToolCallExecutionError::ResponseParsingError {
-                                reason: "Image response missing Content-Type header".to_string(),
+                                reason: "Media response missing Content-Type header".to_string(),
                                 raw_response: None,
                             },
                         )
                     })?;
+                    let content = if response.is_audio() {
+                        RawContent::Audio(RawAudioContent {
+                            data: base64_data,
+                            mime_type: mime_type.to_string(),
+                        })
+                        .no_annotation()
+                    } else {
+                        Content::image(base64_data, mime_type)
+                    };
 
-                    // Return image content (transformers don't apply to binary responses)
-                    return Ok(if response.is_success {
-                        CallToolResult::success(vec![Content::image(base64_data, mime_type)])
+                    // Return media content (transformers don't apply to binary responses)
+                    let mut result = if response.is_success {
+                        CallToolResult::success(vec![content])
                     } else {
-                        CallToolResult::error(vec![Content::image(base64_data, mime_type)])
-                    });
+                        CallToolResult::error(vec![content])
+                    };
+                    result.meta = meta;
+                    return Ok(result);
+                }
+
+                // Keep video and other binary bodies, such as PDF or zip, as resources
+                // rather than flooding the context with them
+                if response.is_binary()
+                    && response.is_success
+                    && response.truncated_at.is_none()
+                    && let Some(bytes) = &response.body_bytes
+                    && let Some(store) = client.blob_store()
+                    && let Some(resource) = store.insert(
+                        bytes,
+                        response
+                            .content_type
+                            .as_deref()
+                            .unwrap_or("application/octet-stream"),
+                        session_id,
+                    )
+                {
+                    use rmcp::model::{AnnotateAble, RawContent};
+                    let summary = format!(
+                        "Binary response stored as resource {} ({} bytes, Content-Type: {}); read it with resources/read",
+                        resource.uri,
+                        bytes.len(),
+                        resource.mime_type.as_deref().unwrap_or_default()
+                    );
+                    let mut result = CallToolResult::success(vec![
+                        RawContent::ResourceLink(resource.raw).no_annotation(),
+                        Content::text(summary),
+                    ]);
+                    result.meta = meta;
+                    return Ok(result);
                 }
 
                 // Check if the tool has an output schema
-                let structured_content = if self.metadata.output_schema.is_some() {
+                let mut structured_content = if let Some(limit_bytes) = response.truncated_at
+                    && self.metadata.output_schema.is_some()
+                {
+                    // A cut body is not valid against the output schema; report the cut instead
+                    let error = crate::error::ErrorResponse {
+                        error: crate::error::ToolCallExecutionError::ResponseTruncated {
+                            status: response.status_code,
+                            limit_bytes,
+                            partial_body: response.body.clone(),
+                        },
+                    };
+                    Some(json!({
+                        "status": response.status_code,
+                        "body": error
+                    }))
+                } else if response.status_code == 429
+                    && !response.is_success
+                    && self.metadata.output_schema.is_some()
+                {
+                    // Tell the agent how long to wait rather than relaying the upstream body
+                    let error = crate::error::ErrorResponse {
+                        error: crate::error::ToolCallExecutionError::rate_limited(
+                            &response.headers,
+                        ),
+                    };
+                    Some(json!({
+                        "status": response.status_code,
+                        "body": error
+                    }))
+                } else if self.metadata.output_schema.is_some() {
                     // Try to parse the response body as JSON
                     match response.json() {
                         Ok(json_value) => {
+                            // Merge the following pages of listings when enabled
+                            let (json_value, pages_fetched) = match client.pagination() {
+                                Some(config)
+                                    if response.is_success
+                                        && self.metadata.method.eq_ignore_ascii_case("get") =>
+                                {
+                                    let (body, pages) = crate::pagination::follow(
+                                        &client,
+                                        &self.metadata,
+                                        &arguments,
+                                        &response.headers,
+                                        json_value,
+                                        config,
+                                    )
+                                    .await?;
+                                    (body, Some(pages))
+                                }
+                                _ => (json_value, None),
+                            };
+
                             // Apply transformer to the response body if present
                             let transformed_body = if let Some(t) = transformer {
                                 t.transform_response(json_value)
@@ -126,11 +283,33 @@ impl Tool {
                                 json_value
                             };
 
-                            // Wrap the response in our standard HTTP response structure
-                            Some(json!({
-                                "status": response.status_code,
-                                "body": transformed_body
-                            }))
+                            // Wrap the response in our standard HTTP response structure,
+                            // with only the values asked for if the call filters them
+                            let mut structured = json!({ "status": response.status_code });
+                            match &filter {
+                                Some(filter) if response.is_success => {
+                                    structured["filtered"] = filter.apply(&transformed_body);
+                                }
+                                _ => structured["body"] = transformed_body,
+                            }
+                            // Cut bodies over the response budget, telling what was cut
+                            if let Some(config) = client.response_budget() {
+                                let key = if structured.get("filtered").is_some() {
+                                    "filtered"
+                                } else {
+                                    "body"
+                                };
+                                let (body, summary) =
+                                    crate::budget::fit(structured[key].take(), config);
+                                structured[key] = body;
+                                if let Some(summary) = summary {
+                                    structured["summary"] = summary;
+                                }
+                            }
+                            if let Some(pages) = pages_fetched {
+                                structured["pages_fetched"] = json!(pages);
+                            }
+                            Some(structured)
                         }
                         Err(_) => None, // If parsing fails, fall back to text content
                     }
@@ -138,8 +317,84 @@ impl Tool {
                     None
                 };
 
+                // Give upstream errors described by the spec a typed `error`
+                if !response.is_success
+                    && response.truncated_at.is_none()
+                    && let Some(schemas) = &self.error_schemas
+                    && let Ok(body) = response.json()
+                    && let Some(error) = schemas.parse(response.status_code, &body)
+                {
+                    let structured = structured_content.get_or_insert_with(
+                        || json!({ "status": response.status_code, "body": body }),
+                    );
+                    structured["error"] = error;
+                }
+
+                // Let agents decide whether to follow redirects that were not followed
+                if let Some(location) = response.redirect_location() {
+                    let structured = structured_content.get_or_insert_with(|| {
+                        json!({
+                            "status": response.status_code,
+                            "body": response.json().unwrap_or(Value::Null)
+                        })
+                    });
+                    structured["redirect"] = json!({
+                        "status": response.status_code,
+                        "location": location
+                    });
+                }
+
+                // Tell agents whether repeating a failed call may help
+                if !response.is_success
+                    && let Some(Value::Object(structured)) = &mut structured_content
+                {
+                    structured.insert(
+                        "retry".to_string(),
+                        json!(RetryHint::for_response(
+                            response.status_code,
+                            &response.headers
+                        )),
+                    );
+                }
+
+                // Let agents follow pagination links, rate limits and `Location`
+                if let Some(Value::Object(structured)) = &mut structured_content
+                    && let Some(headers) = client.structured_headers(&response)
+                {
+                    structured.insert("headers".to_string(), json!(headers));
+                }
+
+                // Return the body itself when the output schema is the body's
+                if self.unwrapped_output {
+                    structured_content = structured_content
+                        .filter(|_| response.is_success && response.truncated_at.is_none())
+                        .and_then(|mut structured| structured.get_mut("body").map(Value::take))
+                        .filter(Value::is_object);
+                }
+
                 // For structured content, serialize to JSON for backwards compatibility
-                let content = if let Some(ref structured) = structured_content {
+                // Show lists of flat objects as a Markdown table, the JSON staying in
+                // the structured content
+                let table = client
+                    .markdown_tables()
+                    .filter(|_| response.is_success && response.truncated_at.is_none())
+                    .and_then(|config| {
+                        let body = match &structured_content {
+                            Some(structured) => structured
+                                .get("filtered")
+                                .or_else(|| structured.get("body"))
+                                .cloned(),
+                            None => response.json().ok().map(|body| match &filter {
+                                Some(filter) => filter.apply(&body),
+                                None => body,
+                            }),
+                        };
+                        crate::table::render(&body?, config)
+                    });
+
+                let content = if let Some(table) = table {
+                    vec![Content::text(table)]
+                } else if let Some(ref structured) = structured_content {
                     // MCP Specification: https://modelcontextprotocol.io/specification/2025-06-18/server/tools#structured-content
                     // "For backwards compatibility, a tool that returns structured content SHOULD also
                     // return the serialized JSON in a TextContent block."
@@ -156,17 +411,43 @@ impl Tool {
                             return Err(error);
                         }
                     }
+                } else if let Some(filter) = &filter
+                    && response.is_success
+                    && response.truncated_at.is_none()
+                    && let Ok(body) = response.json()
+                {
+                    let filtered = filter.apply(&body);
+                    let text = match client
+                        .response_budget()
+                        .map(|config| crate::budget::fit(filtered.clone(), config))
+                    {
+                        Some((filtered, Some(summary))) => {
+                            json!({ "filtered": filtered, "summary": summary }).to_string()
+                        }
+                        _ => filtered.to_string(),
+                    };
+                    vec![Content::text(text)]
+                } else if let Some(config) = client.response_budget()
+                    && response.truncated_at.is_none()
+                    && let Ok(body) = response.json()
+                    && let (body, Some(summary)) = crate::budget::fit(body, config)
+                {
+                    vec![Content::text(
+                        json!({ "status": response.status_code, "body": body, "summary": summary })
+                            .to_string(),
+                    )]
                 } else {
                     vec![Content::text(response.to_mcp_content())]
                 };
 
                 // Return successful response
-                let mut result = if response.is_success {
+                let mut result = if response.is_success && response.truncated_at.is_none() {
                     CallToolResult::success(content)
                 } else {
                     CallToolResult::error(content)
                 };
                 result.structured_content = structured_content;
+                result.meta = meta;
                 Ok(result)
             }
             Err(e) => {
@@ -182,25 +463,75 @@ impl Tool {
         arguments: &Value,
         authorization: Authorization,
     ) -> Result<crate::http_client::HttpResponse, crate::error::ToolCallError> {
-        // Extract authorization header if present
-        let auth_header: Option<&rmcp_actix_web::transport::AuthorizationHeader> =
-            match &authorization {
-                Authorization::None => None,
-                #[cfg(feature = "authorization-token-passthrough")]
-                Authorization::PassthroughWarn(header)
-                | Authorization::PassthroughSilent(header) => header.as_ref(),
-            };
-
-        // Create HTTP client with authorization if provided
-        let client = if let Some(auth) = auth_header {
-            self.http_client.with_authorization(&auth.0)
-        } else {
-            self.http_client.clone()
-        };
+        self.check_credentials(has_auth(&authorization))?;
+        let client = self.authorized_client(&authorization).await?;
+        let (arguments, dry_run) = crate::dry_run::take_flag(&self.metadata, arguments);
+        if dry_run {
+            return client
+                .for_dry_run()
+                .execute_tool_call(&self.metadata, &arguments)
+                .await;
+        }
+        let arguments = client.confirm(&self.metadata, &arguments)?;
+        let (arguments, _) = crate::projection::take_filter(&self.metadata, arguments)?;
 
         // Execute the HTTP request using the (potentially auth-enhanced) HTTP client
         // Return the raw HttpResponse without MCP formatting
-        client.execute_tool_call(&self.metadata, arguments).await
+        client.execute_tool_call(&self.metadata, &arguments).await
+    }
+
+    /// Fail calls to operations requiring authentication when neither the
+    /// client nor the server provides credentials, rather than sending a
+    /// request the upstream will reject
+    fn check_credentials(&self, has_auth: bool) -> Result<(), crate::error::ToolCallError> {
+        if !self.metadata.requires_auth() || has_auth || self.http_client.has_credentials() {
+            return Ok(());
+        }
+        Err(crate::error::ToolCallError::Execution(
+            crate::error::ToolCallExecutionError::AuthenticationRequired {
+                tool: self.metadata.name.clone(),
+                requirements: self.metadata.security.clone().unwrap_or_default(),
+            },
+        ))
+    }
+
+    /// HTTP client carrying the credentials `authorization` calls for
+    async fn authorized_client(
+        &self,
+        authorization: &Authorization,
+    ) -> Result<HttpClient, crate::error::ToolCallError> {
+        match authorization {
+            Authorization::None => Ok(self.http_client.clone()),
+            #[cfg(feature = "authorization-token-passthrough")]
+            Authorization::PassthroughWarn(header) | Authorization::PassthroughSilent(header) => {
+                match header {
+                    Some(header) => {
+                        self.http_client
+                            .check_scopes(&self.metadata, &header.0)
+                            .await?;
+                        self.http_client
+                            .with_exchanged_authorization(&header.0)
+                            .await
+                    }
+                    None => Ok(self.http_client.clone()),
+                }
+            }
+            Authorization::ServiceToken(token) => {
+                Ok(self.http_client.with_authorization(token.header_value()))
+            }
+        }
+    }
+}
+
+/// Whether `authorization` forwards credentials to the upstream
+fn has_auth(authorization: &Authorization) -> bool {
+    match authorization {
+        Authorization::None => false,
+        #[cfg(feature = "authorization-token-passthrough")]
+        Authorization::PassthroughWarn(header) | Authorization::PassthroughSilent(header) => {
+            header.is_some()
+        }
+        Authorization::ServiceToken(_) => true,
     }
 }
 
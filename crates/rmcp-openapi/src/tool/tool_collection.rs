@@ -196,6 +196,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: format!("/{}", name),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -109,12 +109,14 @@ use schemars::schema_for;
 use serde::{Serialize, Serializer};
 use serde_json::{Value, json};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
 
 use crate::HttpClient;
 use crate::error::{
     Error, ErrorResponse, ToolCallValidationError, ValidationConstraint, ValidationError,
 };
 use crate::tool::ToolMetadata;
+use crate::tool_hook::ToolHook;
 use oas3::spec::{
     BooleanSchema, ObjectOrReference, ObjectSchema, Operation, Parameter, ParameterIn,
     ParameterStyle, RequestBody, Response, Schema, SchemaType, SchemaTypeSet, Spec,
@@ -128,6 +130,8 @@ const X_PARAMETER_REQUIRED: &str = "x-parameter-required";
 const X_CONTENT_TYPE: &str = "x-content-type";
 const X_ORIGINAL_NAME: &str = "x-original-name";
 const X_PARAMETER_EXPLODE: &str = "x-parameter-explode";
+const X_PARAMETER_STYLE: &str = "x-parameter-style";
+const X_PARAMETER_ALLOW_RESERVED: &str = "x-parameter-allow-reserved";
 const X_FILE_FIELDS: &str = "x-file-fields";
 
 /// Location type that extends ParameterIn with Body variant
@@ -170,6 +174,10 @@ pub enum Annotation {
     OriginalName(String),
     /// Parameter explode setting for arrays/objects
     Explode(bool),
+    /// Parameter serialization style (matrix, label, form, simple, ...)
+    Style(ParameterStyle),
+    /// Whether reserved characters in query values are sent unencoded
+    AllowReserved(bool),
     /// File fields in a multipart/form-data request body
     FileFields(Vec<String>),
 }
@@ -219,6 +227,19 @@ impl Annotations {
         self
     }
 
+    /// Add a style annotation
+    pub fn with_style(mut self, style: ParameterStyle) -> Self {
+        self.annotations.push(Annotation::Style(style));
+        self
+    }
+
+    /// Add an allowReserved annotation
+    pub fn with_allow_reserved(mut self, allow_reserved: bool) -> Self {
+        self.annotations
+            .push(Annotation::AllowReserved(allow_reserved));
+        self
+    }
+
     /// Add file fields annotation for multipart/form-data requests
     pub fn with_file_fields(mut self, file_fields: Vec<String>) -> Self {
         self.annotations.push(Annotation::FileFields(file_fields));
@@ -265,6 +286,12 @@ impl Serialize for Annotations {
                 Annotation::Explode(explode) => {
                     map.serialize_entry(X_PARAMETER_EXPLODE, explode)?;
                 }
+                Annotation::Style(style) => {
+                    map.serialize_entry(X_PARAMETER_STYLE, style)?;
+                }
+                Annotation::AllowReserved(allow_reserved) => {
+                    map.serialize_entry(X_PARAMETER_ALLOW_RESERVED, allow_reserved)?;
+                }
                 Annotation::FileFields(file_fields) => {
                     map.serialize_entry(X_FILE_FIELDS, file_fields)?;
                 }
@@ -764,8 +791,11 @@ impl ToolGenerator {
             )
         });
 
+        // Catch spec mistakes here rather than as a 404 from the upstream API
+        Self::validate_path_template(&name, &path, &operation.parameters)?;
+
         // Generate parameter schema first so we can include it in description
-        let (parameters, parameter_mappings) = Self::generate_parameter_schema(
+        let (mut parameters, parameter_mappings) = Self::generate_parameter_schema(
             &operation.parameters,
             &method,
             &operation.request_body,
@@ -774,6 +804,9 @@ impl ToolGenerator {
             parameter_examples_in_description,
         )?;
 
+        // Let callers pick among the declared response media types
+        Self::add_accept_parameter(&mut parameters, &operation.responses);
+
         // Build description from summary, description, and parameters
         let description =
             (!skip_tool_description).then(|| Self::build_description(operation, &method, &path));
@@ -781,6 +814,19 @@ impl ToolGenerator {
         // Extract output schema from responses (already returns wrapped Value)
         let output_schema = Self::extract_output_schema(&operation.responses, spec)?;
 
+        // The operation's requirements replace the spec's
+        let security = if operation.security.is_empty() {
+            &spec.security
+        } else {
+            &operation.security
+        };
+        let security = (!security.is_empty()).then(|| {
+            security
+                .iter()
+                .map(|requirement| requirement.0.clone())
+                .collect()
+        });
+
         Ok(ToolMetadata {
             name,
             title: operation.summary.clone(),
@@ -789,11 +835,121 @@ impl ToolGenerator {
             output_schema,
             method,
             path,
-            security: None, // TODO: Extract security requirements from OpenAPI spec
+            tags: operation.tags.clone(),
+            security,
             parameter_mappings,
         })
     }
 
+    /// Check that the `{placeholders}` in `path` and the declared path parameters agree
+    ///
+    /// A declared path parameter without a placeholder is always an error. A
+    /// placeholder without a declared parameter is an error unless some parameters
+    /// are unresolved references, in which case it is only logged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Validation`] describing the first mismatch found
+    fn validate_path_template(
+        tool_name: &str,
+        path: &str,
+        parameters: &[ObjectOrReference<Parameter>],
+    ) -> Result<(), Error> {
+        let placeholders = path_placeholders(path);
+
+        let mut declared = Vec::new();
+        let mut has_references = false;
+        for param in parameters {
+            match param {
+                ObjectOrReference::Object(param) if param.location == ParameterIn::Path => {
+                    declared.push(param.name.as_str());
+                }
+                ObjectOrReference::Object(_) => {}
+                ObjectOrReference::Ref { .. } => has_references = true,
+            }
+        }
+
+        if let Some(name) = declared.iter().find(|name| !placeholders.contains(name)) {
+            return Err(Error::Validation(format!(
+                "Tool '{tool_name}' declares path parameter '{name}' but path '{path}' has no '{{{name}}}' placeholder"
+            )));
+        }
+
+        let undeclared: Vec<&str> = placeholders
+            .into_iter()
+            .filter(|placeholder| !declared.contains(placeholder))
+            .collect();
+        if !undeclared.is_empty() {
+            let list = undeclared.join("', '");
+            if has_references {
+                warn!(
+                    tool_name = %tool_name,
+                    path = %path,
+                    "Path placeholders '{list}' have no inline path parameter; assuming they are declared by reference"
+                );
+            } else {
+                return Err(Error::Validation(format!(
+                    "Tool '{tool_name}' path '{path}' has placeholders with no matching path parameter: '{list}'"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collect the media types declared by the operation's success responses
+    ///
+    /// Only inline `2xx`/`2XX` responses are considered; error responses are not
+    /// something a caller negotiates for.
+    fn success_media_types(
+        responses: &Option<BTreeMap<String, ObjectOrReference<Response>>>,
+    ) -> Vec<String> {
+        let mut media_types = Vec::new();
+        for (status_code, response) in responses.iter().flatten() {
+            if !status_code.starts_with('2') {
+                continue;
+            }
+            if let ObjectOrReference::Object(response) = response {
+                for media_type in response.content.keys() {
+                    if !media_types.contains(media_type) {
+                        media_types.push(media_type.clone());
+                    }
+                }
+            }
+        }
+        media_types
+    }
+
+    /// Add an optional `accept` parameter when the operation can respond with more
+    /// than one media type
+    ///
+    /// The value is constrained to the declared media types and sent as the
+    /// `Accept` header. Nothing is added if an `accept` property already exists.
+    fn add_accept_parameter(
+        parameters: &mut Value,
+        responses: &Option<BTreeMap<String, ObjectOrReference<Response>>>,
+    ) {
+        let media_types = Self::success_media_types(responses);
+        if media_types.len() < 2 {
+            return;
+        }
+
+        if let Some(properties) = parameters
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+            && !properties.contains_key("accept")
+        {
+            properties.insert(
+                "accept".to_string(),
+                json!({
+                    "type": "string",
+                    "description": "Response media type to request (sent as the Accept header)",
+                    "enum": media_types,
+                }),
+            );
+        }
+    }
+
     /// Generate OpenApiTool instances from tool metadata with HTTP configuration
     ///
     /// # Errors
@@ -804,10 +960,11 @@ impl ToolGenerator {
         base_url: Option<url::Url>,
         default_headers: Option<reqwest::header::HeaderMap>,
         insecure: bool,
+        strip_non_standard_bodies: bool,
     ) -> Result<Vec<crate::tool::Tool>, Error> {
-        let mut openapi_tools = Vec::with_capacity(tools_metadata.len());
-
-        let mut http_client = HttpClient::new().with_insecure(insecure);
+        let mut http_client = HttpClient::new()
+            .with_insecure(insecure)
+            .with_strip_non_standard_bodies(strip_non_standard_bodies);
 
         if let Some(url) = base_url {
             http_client = http_client.with_base_url(url)?;
@@ -817,12 +974,29 @@ impl ToolGenerator {
             http_client = http_client.with_default_headers(headers);
         }
 
-        for metadata in tools_metadata {
-            let tool = crate::tool::Tool::new(metadata, http_client.clone())?;
-            openapi_tools.push(tool);
-        }
+        Self::generate_tools_with_client(tools_metadata, &http_client)
+    }
+
+    /// Generate OpenApiTool instances sharing an already configured HTTP client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any OpenApiTool cannot be created
+    pub fn generate_tools_with_client(
+        tools_metadata: Vec<ToolMetadata>,
+        http_client: &HttpClient,
+    ) -> Result<Vec<crate::tool::Tool>, Error> {
+        tools_metadata
+            .into_iter()
+            .map(|metadata| crate::tool::Tool::new(metadata, http_client.clone()))
+            .collect()
+    }
 
-        Ok(openapi_tools)
+    /// Run `hooks` on the metadata of a generated tool, in order
+    pub fn apply_hooks(metadata: &mut ToolMetadata, hooks: &[Arc<dyn ToolHook>]) {
+        for hook in hooks {
+            hook.process(metadata);
+        }
     }
 
     /// Build a comprehensive description for the tool
@@ -1480,6 +1654,15 @@ impl ToolGenerator {
                 })
                 .unwrap_or(true);
 
+            // Extract style setting from annotations
+            let style = annotations.annotations.iter().find_map(|a| {
+                if let Annotation::Style(s) = a {
+                    Some(*s)
+                } else {
+                    None
+                }
+            });
+
             // Store parameter mapping
             parameter_mappings.insert(
                 sanitized_name.clone(),
@@ -1488,6 +1671,8 @@ impl ToolGenerator {
                     original_name: param.name.clone(),
                     location: "path".to_string(),
                     explode,
+                    style,
+                    allow_reserved: false,
                 },
             );
 
@@ -1525,6 +1710,21 @@ impl ToolGenerator {
                 })
                 .unwrap_or(true);
 
+            // Extract style setting from annotations
+            let style = annotations.annotations.iter().find_map(|a| {
+                if let Annotation::Style(s) = a {
+                    Some(*s)
+                } else {
+                    None
+                }
+            });
+
+            // Extract allowReserved setting from annotations
+            let allow_reserved = annotations
+                .annotations
+                .iter()
+                .any(|a| matches!(a, Annotation::AllowReserved(true)));
+
             // Store parameter mapping
             parameter_mappings.insert(
                 sanitized_name.clone(),
@@ -1533,6 +1733,8 @@ impl ToolGenerator {
                     original_name: param.name.clone(),
                     location: "query".to_string(),
                     explode,
+                    style,
+                    allow_reserved,
                 },
             );
 
@@ -1573,6 +1775,15 @@ impl ToolGenerator {
                 })
                 .unwrap_or(true);
 
+            // Extract style setting from annotations
+            let style = annotations.annotations.iter().find_map(|a| {
+                if let Annotation::Style(s) = a {
+                    Some(*s)
+                } else {
+                    None
+                }
+            });
+
             // Store parameter mapping
             parameter_mappings.insert(
                 sanitized_name.clone(),
@@ -1581,6 +1792,8 @@ impl ToolGenerator {
                     original_name: param.name.clone(),
                     location: "header".to_string(),
                     explode,
+                    style,
+                    allow_reserved: false,
                 },
             );
 
@@ -1621,6 +1834,15 @@ impl ToolGenerator {
                 })
                 .unwrap_or(true);
 
+            // Extract style setting from annotations
+            let style = annotations.annotations.iter().find_map(|a| {
+                if let Annotation::Style(s) = a {
+                    Some(*s)
+                } else {
+                    None
+                }
+            });
+
             // Store parameter mapping
             parameter_mappings.insert(
                 sanitized_name.clone(),
@@ -1629,6 +1851,8 @@ impl ToolGenerator {
                     original_name: param.name.clone(),
                     location: "cookie".to_string(),
                     explode,
+                    style,
+                    allow_reserved: false,
                 },
             );
 
@@ -1652,6 +1876,8 @@ impl ToolGenerator {
                     original_name: "request_body".to_string(),
                     location: "body".to_string(),
                     explode: false,
+                    style: None,
+                    allow_reserved: false,
                 },
             );
 
@@ -1864,6 +2090,16 @@ impl ToolGenerator {
             .with_location(Location::Parameter(location))
             .with_required(param.required.unwrap_or(false));
 
+        // Add style annotation if present
+        if let Some(style) = param.style {
+            annotations = annotations.with_style(style);
+        }
+
+        // allowReserved only applies to query parameters
+        if location == ParameterIn::Query && param.allow_reserved == Some(true) {
+            annotations = annotations.with_allow_reserved(true);
+        }
+
         // Add explode annotation if present
         if let Some(explode) = param.explode {
             annotations = annotations.with_explode(explode);
@@ -1871,10 +2107,12 @@ impl ToolGenerator {
             // Default explode behavior based on OpenAPI spec:
             // - form style defaults to true
             // - other styles default to false
-            let default_explode = match &param.style {
-                Some(ParameterStyle::Form) | None => true, // form is default style
-                _ => false,
-            };
+            // When no style is given, query and cookie parameters default to
+            // `form` while path and header parameters default to `simple`.
+            let default_explode = matches!(
+                (&param.style, location),
+                (Some(ParameterStyle::Form), _) | (None, ParameterIn::Query | ParameterIn::Cookie)
+            );
             annotations = annotations.with_explode(default_explode);
         }
 
@@ -2245,7 +2483,7 @@ impl ToolGenerator {
 
         // Extract timeout if provided
         if let Some(timeout) = args.get("timeout_seconds").and_then(Value::as_u64) {
-            config.timeout_seconds = u32::try_from(timeout).unwrap_or(u32::MAX);
+            config.timeout_seconds = Some(u32::try_from(timeout).unwrap_or(u32::MAX));
         }
 
         // Process each argument
@@ -2254,6 +2492,12 @@ impl ToolGenerator {
                 continue; // Already processed
             }
 
+            // Handle the generated accept parameter, unless the operation declares its own
+            if key == "accept" && !tool_metadata.parameter_mappings.contains_key(key) {
+                header_params.insert("Accept".to_string(), value.clone());
+                continue;
+            }
+
             // Handle special request_body parameter
             if key == "request_body" {
                 body_params.insert("request_body".to_string(), value.clone());
@@ -2272,7 +2516,8 @@ impl ToolGenerator {
                     "query" => {
                         query_params.insert(
                             mapping.original_name.clone(),
-                            QueryParameter::new(value.clone(), mapping.explode),
+                            QueryParameter::new(value.clone(), mapping.explode)
+                                .with_allow_reserved(mapping.allow_reserved),
                         );
                     }
                     "header" => {
@@ -2884,7 +3129,8 @@ impl ToolGenerator {
             }
         };
 
-        let error_schema = create_error_response_schema();
+        let error_schema =
+            create_error_response_schema(|error_type| COMMON_ERROR_TYPES.contains(&error_type));
 
         Ok(json!({
             "type": "object",
@@ -2904,11 +3150,108 @@ impl ToolGenerator {
                         body_schema_json,
                         error_schema
                     ]
+                },
+                "retry": {
+                    "type": "object",
+                    "description": "Whether repeating the call may succeed, on error responses",
+                    "properties": {
+                        "retryable": {
+                            "type": "boolean",
+                            "description": "Whether the same call may succeed if repeated"
+                        },
+                        "retry_after_ms": {
+                            "type": "integer",
+                            "description": "Suggested delay before retrying, in milliseconds",
+                            "minimum": 0
+                        }
+                    },
+                    "required": ["retryable"]
                 }
             }
         }))
     }
 
+    /// Declare the error types `error_types` in an output schema built by
+    /// [`Self::wrap_output_schema`], next to the ones it already declares
+    pub(crate) fn add_error_types_to_output_schema(schema: &mut Value, error_types: &[&str]) {
+        let Some(error_schema) = schema
+            .pointer_mut("/properties/body/oneOf")
+            .and_then(Value::as_array_mut)
+            .and_then(|variants| variants.last_mut())
+        else {
+            return;
+        };
+        let Some(declared) = error_schema
+            .pointer("/properties/error/oneOf")
+            .and_then(Value::as_array)
+        else {
+            return;
+        };
+        let declared: Vec<String> = declared
+            .iter()
+            .filter_map(error_type)
+            .map(str::to_string)
+            .collect();
+        *error_schema = create_error_response_schema(|error_type| {
+            error_types.contains(&error_type) || declared.iter().any(|d| d == error_type)
+        });
+    }
+
+    /// Declare the `redirect` field of structured results in an output
+    /// schema built by [`Self::wrap_output_schema`]
+    pub(crate) fn add_redirect_to_output_schema(schema: &mut Value) {
+        if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+            properties.insert(
+                "redirect".to_string(),
+                json!({
+                    "type": "object",
+                    "description": "Target of a redirect that was not followed, for the agent to follow or not",
+                    "properties": {
+                        "status": {
+                            "type": "integer",
+                            "description": "HTTP status code of the redirect"
+                        },
+                        "location": {
+                            "type": "string",
+                            "description": "URL the response redirects to"
+                        }
+                    },
+                    "required": ["status", "location"]
+                }),
+            );
+        }
+    }
+
+    /// Declare the `headers` field of structured results in an output schema
+    /// built by [`Self::wrap_output_schema`]
+    pub(crate) fn add_headers_to_output_schema(schema: &mut Value) {
+        if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+            properties.insert(
+                "headers".to_string(),
+                json!({
+                    "type": "object",
+                    "description": "Response headers, by lowercase name",
+                    "additionalProperties": { "type": "string" }
+                }),
+            );
+        }
+    }
+
+    /// Declare the `pages_fetched` field of structured results in an output
+    /// schema built by [`Self::wrap_output_schema`]
+    pub(crate) fn add_pages_fetched_to_output_schema(schema: &mut Value) {
+        if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+            properties.insert(
+                "pages_fetched".to_string(),
+                json!({
+                    "type": "integer",
+                    "description": "Number of pages of the listing merged into the body",
+                    "minimum": 1
+                }),
+            );
+        }
+    }
+
     /// Check if a schema represents a file field based on its format.
     ///
     /// Returns `true` if the schema has `format: binary` or `format: byte`,
@@ -2992,8 +3335,26 @@ impl ToolGenerator {
     }
 }
 
-/// Create the error schema structure that all tool errors conform to
-fn create_error_response_schema() -> Value {
+/// Types of the errors any tool may report in its structured results
+///
+/// The others are only declared in the output schema of tools whose server
+/// enables the feature reporting them, with
+/// [`ToolGenerator::add_error_types_to_output_schema`].
+const COMMON_ERROR_TYPES: &[&str] = &[
+    "http-error",
+    "network-error",
+    "rate-limited",
+    "response-parsing-error",
+];
+
+/// Type of a variant of the error schema, such as `http-error`
+fn error_type(variant: &Value) -> Option<&str> {
+    variant.pointer("/properties/type/const")?.as_str()
+}
+
+/// Create the error schema structure that tool errors conform to, with the
+/// error types `declared` keeps
+fn create_error_response_schema(declared: impl Fn(&str) -> bool) -> Value {
     let root_schema = schema_for!(ErrorResponse);
     let schema_json = serde_json::to_value(root_schema).expect("Valid error schema");
 
@@ -3016,6 +3377,13 @@ fn create_error_response_schema() -> Value {
     // Inline all references
     inline_refs(&mut result, &definitions);
 
+    if let Some(variants) = result
+        .pointer_mut("/properties/error/oneOf")
+        .and_then(Value::as_array_mut)
+    {
+        variants.retain(|variant| error_type(variant).is_some_and(&declared));
+    }
+
     result
 }
 
@@ -3063,11 +3431,23 @@ fn inline_refs(schema: &mut Value, definitions: &Value) {
 pub struct QueryParameter {
     pub value: Value,
     pub explode: bool,
+    /// Send RFC 3986 reserved characters unencoded (OpenAPI `allowReserved`)
+    pub allow_reserved: bool,
 }
 
 impl QueryParameter {
     pub fn new(value: Value, explode: bool) -> Self {
-        Self { value, explode }
+        Self {
+            value,
+            explode,
+            allow_reserved: false,
+        }
+    }
+
+    /// Set whether reserved characters are sent unencoded
+    pub fn with_allow_reserved(mut self, allow_reserved: bool) -> Self {
+        self.allow_reserved = allow_reserved;
+        self
     }
 }
 
@@ -3082,17 +3462,26 @@ pub struct ExtractedParameters {
     pub config: RequestConfig,
 }
 
+/// Extract the `{placeholder}` names from a path template, in order of appearance
+fn path_placeholders(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|segment| segment.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
 /// Request configuration options
 #[derive(Debug, Clone)]
 pub struct RequestConfig {
-    pub timeout_seconds: u32,
+    /// Timeout the caller asked for, if any
+    pub timeout_seconds: Option<u32>,
     pub content_type: String,
 }
 
 impl Default for RequestConfig {
     fn default() -> Self {
         Self {
-            timeout_seconds: 30,
+            timeout_seconds: None,
             content_type: mime::APPLICATION_JSON.to_string(),
         }
     }
@@ -3290,6 +3679,29 @@ mod tests {
         assert_json_snapshot!("array_parameter_examples_lift", result);
     }
 
+    /// Create a required string path parameter
+    fn path_parameter(name: &str) -> ObjectOrReference<Parameter> {
+        ObjectOrReference::Object(Parameter {
+            name: name.to_string(),
+            location: ParameterIn::Path,
+            description: None,
+            required: Some(true),
+            deprecated: None,
+            allow_empty_value: None,
+            style: None,
+            explode: None,
+            allow_reserved: None,
+            schema: Some(ObjectOrReference::Object(ObjectSchema {
+                schema_type: Some(SchemaTypeSet::Single(SchemaType::String)),
+                ..Default::default()
+            })),
+            example: None,
+            examples: Default::default(),
+            content: None,
+            extensions: Default::default(),
+        })
+    }
+
     /// Create a minimal test OpenAPI spec for testing purposes
     fn create_test_spec() -> Spec {
         Spec {
@@ -3362,7 +3774,8 @@ mod tests {
 
     #[test]
     fn test_error_schema_structure() {
-        let error_schema = create_error_response_schema();
+        let error_schema =
+            create_error_response_schema(|error_type| COMMON_ERROR_TYPES.contains(&error_type));
 
         // Should not contain $schema or definitions at top level
         assert!(error_schema.get("$schema").is_none());
@@ -3618,7 +4031,7 @@ mod tests {
             description: Some("Returns a single pet".to_string()),
             tags: vec![],
             external_docs: None,
-            parameters: vec![],
+            parameters: vec![path_parameter("petId")],
             request_body: None,
             responses: Default::default(),
             callbacks: Default::default(),
@@ -3661,7 +4074,7 @@ mod tests {
             description: description.clone(),
             tags: vec![],
             external_docs: None,
-            parameters: vec![],
+            parameters: vec![path_parameter("petId")],
             request_body: None,
             responses: Default::default(),
             callbacks: Default::default(),
@@ -3997,7 +4410,7 @@ mod tests {
             description: None,
             tags: vec![],
             external_docs: None,
-            parameters: vec![],
+            parameters: vec![path_parameter("petId")],
             request_body: Some(ObjectOrReference::Object(RequestBody {
                 description: None,
                 content: {
@@ -4063,7 +4476,7 @@ mod tests {
             description: None,
             tags: vec![],
             external_docs: None,
-            parameters: vec![],
+            parameters: vec![path_parameter("petId")],
             request_body: Some(ObjectOrReference::Ref {
                 ref_path: "#/components/requestBodies/PetBody".to_string(),
                 summary: None,
@@ -4156,7 +4569,7 @@ mod tests {
             description: None,
             tags: vec![],
             external_docs: None,
-            parameters: vec![],
+            parameters: vec![path_parameter("petId")],
             request_body: Some(ObjectOrReference::Object(RequestBody {
                 description: Some("Pet status update".to_string()),
                 content: {
@@ -4639,7 +5052,7 @@ mod tests {
             description: None,
             tags: vec![],
             external_docs: None,
-            parameters: vec![],
+            parameters: vec![path_parameter("id")],
             request_body: None,
             responses: Default::default(),
             callbacks: Default::default(),
@@ -4988,6 +5401,253 @@ mod tests {
         assert_eq!(extracted.headers.get("auth-token!"), Some(&json!("secret")));
     }
 
+    #[test]
+    fn test_path_parameter_style_recorded_in_mapping() {
+        let spec = create_test_spec();
+
+        let path_param = |name: &str, style: Option<ParameterStyle>, explode: Option<bool>| {
+            ObjectOrReference::Object(Parameter {
+                name: name.to_string(),
+                location: ParameterIn::Path,
+                description: None,
+                required: Some(true),
+                deprecated: None,
+                allow_empty_value: None,
+                style,
+                explode,
+                allow_reserved: None,
+                schema: Some(ObjectOrReference::Object(ObjectSchema {
+                    schema_type: Some(SchemaTypeSet::Single(SchemaType::String)),
+                    ..Default::default()
+                })),
+                example: None,
+                examples: Default::default(),
+                content: None,
+                extensions: Default::default(),
+            })
+        };
+
+        let operation = Operation {
+            operation_id: Some("styledPath".to_string()),
+            parameters: vec![
+                path_param("plain", None, None),
+                path_param("matrixed", Some(ParameterStyle::Matrix), None),
+                path_param("labelled", Some(ParameterStyle::Label), Some(true)),
+            ],
+            ..Default::default()
+        };
+
+        let tool_metadata = ToolGenerator::generate_tool_metadata(
+            &operation,
+            "get".to_string(),
+            "/{plain}/{matrixed}/{labelled}".to_string(),
+            &spec,
+            false,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let plain = &tool_metadata.parameter_mappings["plain"];
+        assert_eq!(plain.style, None);
+        // Path parameters default to `simple`, which does not explode
+        assert!(!plain.explode);
+
+        let matrixed = &tool_metadata.parameter_mappings["matrixed"];
+        assert_eq!(matrixed.style, Some(ParameterStyle::Matrix));
+        assert!(!matrixed.explode);
+
+        let labelled = &tool_metadata.parameter_mappings["labelled"];
+        assert_eq!(labelled.style, Some(ParameterStyle::Label));
+        assert!(labelled.explode);
+    }
+
+    #[test]
+    fn test_allow_reserved_query_parameter() {
+        let spec = create_test_spec();
+
+        let operation = Operation {
+            operation_id: Some("search".to_string()),
+            parameters: vec![ObjectOrReference::Object(Parameter {
+                name: "filter".to_string(),
+                location: ParameterIn::Query,
+                description: None,
+                required: Some(false),
+                deprecated: None,
+                allow_empty_value: None,
+                style: None,
+                explode: None,
+                allow_reserved: Some(true),
+                schema: Some(ObjectOrReference::Object(ObjectSchema {
+                    schema_type: Some(SchemaTypeSet::Single(SchemaType::String)),
+                    ..Default::default()
+                })),
+                example: None,
+                examples: Default::default(),
+                content: None,
+                extensions: Default::default(),
+            })],
+            ..Default::default()
+        };
+
+        let tool_metadata = ToolGenerator::generate_tool_metadata(
+            &operation,
+            "get".to_string(),
+            "/search".to_string(),
+            &spec,
+            false,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert!(tool_metadata.parameter_mappings["filter"].allow_reserved);
+
+        let extracted =
+            ToolGenerator::extract_parameters(&tool_metadata, &json!({"filter": "status:active"}))
+                .unwrap();
+        assert!(extracted.query["filter"].allow_reserved);
+    }
+
+    #[test]
+    fn test_accept_parameter_for_multiple_response_media_types() {
+        use oas3::spec::Response;
+
+        let spec = create_test_spec();
+        let response = |media_types: &[&str]| {
+            ObjectOrReference::Object(Response {
+                description: Some("OK".to_string()),
+                headers: Default::default(),
+                content: media_types
+                    .iter()
+                    .map(|m| (m.to_string(), MediaType::default()))
+                    .collect(),
+                links: Default::default(),
+                extensions: Default::default(),
+            })
+        };
+
+        let mut responses = BTreeMap::new();
+        responses.insert(
+            "200".to_string(),
+            response(&["application/json", "text/csv"]),
+        );
+        responses.insert("404".to_string(), response(&["application/problem+json"]));
+        let operation = Operation {
+            operation_id: Some("exportReport".to_string()),
+            responses: Some(responses),
+            ..Default::default()
+        };
+
+        let tool_metadata = ToolGenerator::generate_tool_metadata(
+            &operation,
+            "get".to_string(),
+            "/report".to_string(),
+            &spec,
+            false,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            tool_metadata.parameters["properties"]["accept"]["enum"],
+            json!(["application/json", "text/csv"])
+        );
+        assert!(
+            !tool_metadata.parameters["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("accept"))
+        );
+
+        let extracted =
+            ToolGenerator::extract_parameters(&tool_metadata, &json!({"accept": "text/csv"}))
+                .unwrap();
+        assert_eq!(extracted.headers.get("Accept"), Some(&json!("text/csv")));
+
+        let invalid =
+            ToolGenerator::extract_parameters(&tool_metadata, &json!({"accept": "text/html"}));
+        assert!(invalid.is_err());
+
+        // A single declared media type leaves nothing to negotiate
+        let mut responses = BTreeMap::new();
+        responses.insert("200".to_string(), response(&["application/json"]));
+        let operation = Operation {
+            operation_id: Some("getReport".to_string()),
+            responses: Some(responses),
+            ..Default::default()
+        };
+        let tool_metadata = ToolGenerator::generate_tool_metadata(
+            &operation,
+            "get".to_string(),
+            "/report".to_string(),
+            &spec,
+            false,
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(
+            tool_metadata.parameters["properties"]
+                .get("accept")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_path_template_validation() {
+        let spec = create_test_spec();
+        let generate = |path: &str, parameters: Vec<ObjectOrReference<Parameter>>| {
+            let operation = Operation {
+                operation_id: Some("getItem".to_string()),
+                parameters,
+                ..Default::default()
+            };
+            ToolGenerator::generate_tool_metadata(
+                &operation,
+                "get".to_string(),
+                path.to_string(),
+                &spec,
+                false,
+                false,
+                false,
+            )
+        };
+
+        assert!(generate("/items/{itemId}", vec![path_parameter("itemId")]).is_ok());
+
+        let err = generate("/items/{itemId}", vec![]).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        assert!(err.to_string().contains("'itemId'"), "{err}");
+
+        let err = generate("/items/{id}", vec![path_parameter("itemId")]).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        assert!(
+            err.to_string()
+                .contains("declares path parameter 'itemId' but path '/items/{id}'"),
+            "{err}"
+        );
+
+        // Placeholders may be covered by parameters that are not resolved inline
+        let reference = ObjectOrReference::Ref {
+            ref_path: "#/components/parameters/ItemId".to_string(),
+            summary: None,
+            description: None,
+        };
+        assert!(generate("/items/{itemId}", vec![reference]).is_ok());
+    }
+
+    #[test]
+    fn test_path_placeholders() {
+        assert_eq!(
+            path_placeholders("/orgs/{org}/repos/{repo}"),
+            vec!["org", "repo"]
+        );
+        assert_eq!(path_placeholders("/report.{format}"), vec!["format"]);
+        assert!(path_placeholders("/health").is_empty());
+    }
+
     #[test]
     fn test_check_unknown_parameters() {
         // Test with unknown parameter that has a suggestion
@@ -5231,6 +5891,7 @@ mod tests {
             output_schema: None,
             method: "GET".to_string(),
             path: "/items".to_string(),
+            tags: vec![],
             security: None,
             parameter_mappings: std::collections::HashMap::new(),
         };
@@ -6661,4 +7322,53 @@ mod tests {
         // Validate using snapshot
         insta::assert_json_snapshot!("test_multipart_non_file_fields_unchanged", schema);
     }
+
+    #[test]
+    fn test_explicit_timeout_is_kept_even_when_it_is_the_default() {
+        // The timeout parameter is offered to operations with query parameters
+        let limit = Parameter {
+            name: "limit".to_string(),
+            location: ParameterIn::Query,
+            description: None,
+            required: Some(false),
+            deprecated: None,
+            allow_empty_value: None,
+            style: None,
+            explode: None,
+            allow_reserved: None,
+            schema: Some(ObjectOrReference::Object(ObjectSchema {
+                schema_type: Some(SchemaTypeSet::Single(SchemaType::Integer)),
+                ..Default::default()
+            })),
+            example: None,
+            examples: Default::default(),
+            content: None,
+            extensions: Default::default(),
+        };
+        let operation = Operation {
+            operation_id: Some("listPets".to_string()),
+            parameters: vec![ObjectOrReference::Object(limit)],
+            ..Default::default()
+        };
+        let tool_metadata = ToolGenerator::generate_tool_metadata(
+            &operation,
+            "get".to_string(),
+            "/pets".to_string(),
+            &create_test_spec(),
+            false,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let timeout = |arguments: Value| {
+            ToolGenerator::extract_parameters(&tool_metadata, &arguments)
+                .unwrap()
+                .config
+                .timeout_seconds
+        };
+        assert_eq!(timeout(json!({})), None);
+        assert_eq!(timeout(json!({"timeout_seconds": 30})), Some(30));
+        assert_eq!(timeout(json!({"timeout_seconds": 5})), Some(5));
+    }
 }
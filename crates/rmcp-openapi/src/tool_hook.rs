@@ -0,0 +1,47 @@
+//! Hooks rewriting generated tools before they are registered.
+//!
+//! A [`ToolHook`] sees the [`ToolMetadata`] of every tool once the server has
+//! generated it and applied its own options, response transformer schemas
+//! included. Hooks can rewrite descriptions, trim parameter or output schemas,
+//! or rename tools. Closures taking `&mut ToolMetadata` are hooks too.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rmcp_openapi::{Server, ToolHook, ToolMetadata};
+//! use std::sync::Arc;
+//!
+//! let admin_note: Arc<dyn ToolHook> = Arc::new(|tool: &mut ToolMetadata| {
+//!     if tool.tags.iter().any(|tag| tag == "admin") {
+//!         let description = tool.description.take().unwrap_or_default();
+//!         tool.description = Some(format!("[admin only] {description}"));
+//!     }
+//! });
+//! let server = Server::builder()
+//!     .openapi_spec(serde_json::json!({}))
+//!     .base_url(url::Url::parse("https://api.example.com")?)
+//!     .tool_hooks(vec![admin_note])
+//!     .build();
+//! # Ok::<(), url::ParseError>(())
+//! ```
+
+use crate::tool::ToolMetadata;
+
+/// Hook run on the metadata of every generated tool
+///
+/// Hooks run in the order they were given. Calls to a name shared by several
+/// tools go to the first, and calls are still sent to the tool's `method` and
+/// `path`, so those are best left unchanged.
+pub trait ToolHook: Send + Sync {
+    /// Rewrite `tool` before it is registered
+    fn process(&self, tool: &mut ToolMetadata);
+}
+
+impl<F> ToolHook for F
+where
+    F: Fn(&mut ToolMetadata) + Send + Sync,
+{
+    fn process(&self, tool: &mut ToolMetadata) {
+        self(tool);
+    }
+}
@@ -1,7 +1,11 @@
+use crate::config::DuplicateNameStrategy;
+use crate::config::duplicate_names::{duplicate_name_error, suffixed_name};
+use crate::disabled::DisabledTools;
 use crate::error::Error;
 use crate::spec::{Filters, Spec};
 use crate::tool::ToolMetadata;
-use std::collections::HashMap;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
 
 /// Registry for managing dynamically generated MCP tools from `OpenAPI` operations
 #[derive(Debug, Clone)]
@@ -12,6 +16,10 @@ pub struct ToolRegistry {
     operations: HashMap<String, (oas3::spec::Operation, String, String)>,
     /// Source spec for reference
     spec: Option<Spec>,
+    /// Tools disabled at runtime, kept across reloads
+    disabled: DisabledTools,
+    /// What to do with a tool named like a registered one
+    duplicate_names: DuplicateNameStrategy,
 }
 
 impl ToolRegistry {
@@ -22,9 +30,27 @@ impl ToolRegistry {
             tools: HashMap::new(),
             operations: HashMap::new(),
             spec: None,
+            disabled: DisabledTools::default(),
+            duplicate_names: DuplicateNameStrategy::default(),
         }
     }
 
+    /// Resolve tools named like registered ones with `strategy` instead of
+    /// refusing them
+    #[must_use]
+    pub fn with_duplicate_name_strategy(mut self, strategy: DuplicateNameStrategy) -> Self {
+        self.duplicate_names = strategy;
+        self
+    }
+
+    /// Share the disabled tools of `disabled`, such as those of a server
+    /// ([`crate::Server::disabled_tools`])
+    #[must_use]
+    pub fn with_disabled_tools(mut self, disabled: DisabledTools) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
     /// Register tools from a specification
     ///
     /// # Errors
@@ -52,8 +78,9 @@ impl ToolRegistry {
 
         // Register each tool
         for tool in tools_metadata {
-            // Find corresponding operation
-            if let Some((operation, method, path)) = spec.get_operation(&tool.name) {
+            // Find corresponding operation, by location as names may repeat
+            if let Some((operation, method, path)) = spec.get_operation_at(&tool.method, &tool.path)
+            {
                 self.register_tool(tool, (operation.clone(), method, path))?;
                 registered_count += 1;
             }
@@ -69,17 +96,26 @@ impl ToolRegistry {
     ///
     /// # Errors
     ///
-    /// Returns an error if the tool metadata is invalid or the tool name already exists
+    /// Returns an error if the tool metadata is invalid, or if the tool name already
+    /// exists and the duplicate name strategy is [`DuplicateNameStrategy::Error`]
     pub fn register_tool(
         &mut self,
-        tool: ToolMetadata,
+        mut tool: ToolMetadata,
         operation: (oas3::spec::Operation, String, String),
     ) -> Result<(), Error> {
-        let tool_name = tool.name.clone();
-
         // Validate tool metadata
         self.validate_tool(&tool)?;
 
+        // Resolve a name already taken; last-wins replaces the registered tool
+        if let Some(existing) = self.tools.get(&tool.name) {
+            match self.duplicate_names {
+                DuplicateNameStrategy::Error => return Err(duplicate_name_error(existing, &tool)),
+                DuplicateNameStrategy::Suffix => tool.name = suffixed_name(&tool),
+                DuplicateNameStrategy::LastWins => {}
+            }
+        }
+        let tool_name = tool.name.clone();
+
         // Store tool metadata and operation
         self.tools.insert(tool_name.clone(), tool);
         self.operations.insert(tool_name, operation);
@@ -107,14 +143,6 @@ impl ToolRegistry {
             ));
         }
 
-        // Validate that the tool name is unique
-        if self.tools.contains_key(&tool.name) {
-            return Err(Error::ToolGeneration(format!(
-                "Tool '{}' already exists",
-                tool.name
-            )));
-        }
-
         Ok(())
     }
 
@@ -163,7 +191,37 @@ impl ToolRegistry {
         self.tools.remove(name)
     }
 
-    /// Clear all tools
+    /// Enable or disable the tool `name`
+    ///
+    /// Disabled tools stay registered but are left out of
+    /// [`Self::get_enabled_tools`]. The disabled set outlives the tools: a tool
+    /// disabled before a reload, or before it is registered, stays disabled.
+    pub fn set_enabled(&self, name: &str, enabled: bool) {
+        self.disabled.set(name, !enabled);
+    }
+
+    /// Check if a tool is enabled, unknown tools included
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        !self.disabled.contains(name)
+    }
+
+    /// Get the names of the disabled tools, sorted
+    #[must_use]
+    pub fn get_disabled_tool_names(&self) -> Vec<String> {
+        self.disabled.names()
+    }
+
+    /// Get the tools that are not disabled
+    #[must_use]
+    pub fn get_enabled_tools(&self) -> Vec<&ToolMetadata> {
+        self.tools
+            .values()
+            .filter(|tool| self.is_enabled(&tool.name))
+            .collect()
+    }
+
+    /// Clear all tools, keeping the disabled set
     pub fn clear(&mut self) {
         self.tools.clear();
         self.operations.clear();
@@ -179,18 +237,37 @@ impl ToolRegistry {
     /// Get registry statistics
     #[must_use]
     pub fn get_stats(&self) -> ToolRegistryStats {
-        let mut method_counts = HashMap::new();
+        let mut method_counts = BTreeMap::new();
+        let mut tag_counts = BTreeMap::new();
         let mut path_counts = HashMap::new();
+        let mut deprecated_tools = 0;
+        let mut auth_required_tools = 0;
 
         for tool in self.tools.values() {
-            *method_counts.entry(tool.method.clone()).or_insert(0) += 1;
+            *method_counts.entry(tool.method.to_lowercase()).or_insert(0) += 1;
+            for tag in &tool.tags {
+                *tag_counts.entry(tag.clone()).or_insert(0) += 1;
+            }
             *path_counts.entry(tool.path.clone()).or_insert(0) += 1;
+            if self
+                .operations
+                .get(&tool.name)
+                .is_some_and(|(operation, _, _)| operation.deprecated == Some(true))
+            {
+                deprecated_tools += 1;
+            }
+            if tool.requires_auth() {
+                auth_required_tools += 1;
+            }
         }
 
         ToolRegistryStats {
             total_tools: self.tools.len(),
             method_distribution: method_counts,
+            tag_distribution: tag_counts,
             unique_paths: path_counts.len(),
+            deprecated_tools,
+            auth_required_tools,
             has_spec: self.spec.is_some(),
         }
     }
@@ -289,8 +366,15 @@ impl Default for ToolRegistry {
 #[derive(Debug, Clone)]
 pub struct ToolRegistryStats {
     pub total_tools: usize,
-    pub method_distribution: HashMap<String, usize>,
+    /// Number of tools per HTTP method, in lowercase
+    pub method_distribution: BTreeMap<String, usize>,
+    /// Number of tools per tag, untagged tools counting in none
+    pub tag_distribution: BTreeMap<String, usize>,
     pub unique_paths: usize,
+    /// Number of tools whose operation is deprecated
+    pub deprecated_tools: usize,
+    /// Number of tools whose operation can't be called without credentials
+    pub auth_required_tools: usize,
     pub has_spec: bool,
 }
 
@@ -304,12 +388,131 @@ impl ToolRegistryStats {
             .map(|(method, count)| format!("{}: {}", method.to_uppercase(), count))
             .collect();
 
+        let tags: Vec<String> = self
+            .tag_distribution
+            .iter()
+            .map(|(tag, count)| format!("{tag}: {count}"))
+            .collect();
+
         format!(
-            "Tools: {}, Methods: [{}], Paths: {}, Spec: {}",
+            "Tools: {}, Methods: [{}], Tags: [{}], Paths: {}, Deprecated: {}, Auth required: {}, Spec: {}",
             self.total_tools,
             methods.join(", "),
+            tags.join(", "),
             self.unique_paths,
+            self.deprecated_tools,
+            self.auth_required_tools,
             if self.has_spec { "loaded" } else { "none" }
         )
     }
+
+    /// Get the registry stats as JSON, for dashboards
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        json!({
+            "total_tools": self.total_tools,
+            "methods": self.method_distribution,
+            "tags": self.tag_distribution,
+            "unique_paths": self.unique_paths,
+            "deprecated_tools": self.deprecated_tools,
+            "auth_required_tools": self.auth_required_tools,
+            "has_spec": self.has_spec,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool::SecurityRequirement;
+    use oas3::spec::Operation;
+
+    fn register(registry: &mut ToolRegistry, name: &str, method: &str, tags: &[&str], auth: bool) {
+        let tool = ToolMetadata {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            parameters: json!({ "type": "object", "properties": {} }),
+            output_schema: None,
+            method: method.to_string(),
+            path: format!("/{name}"),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            security: auth
+                .then(|| vec![SecurityRequirement::from([("api_key".to_string(), vec![])])]),
+            parameter_mappings: HashMap::new(),
+        };
+        let operation = Operation {
+            operation_id: Some(name.to_string()),
+            summary: None,
+            description: None,
+            tags: vec![],
+            external_docs: None,
+            parameters: vec![],
+            request_body: None,
+            responses: Default::default(),
+            callbacks: Default::default(),
+            deprecated: Some(name.starts_with("old")),
+            security: vec![],
+            servers: vec![],
+            extensions: Default::default(),
+        };
+        registry
+            .register_tool(tool, (operation, method.to_string(), format!("/{name}")))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_disabled_tools_survive_reloads() {
+        let disabled = DisabledTools::default();
+        let mut registry = ToolRegistry::new().with_disabled_tools(disabled.clone());
+        register(&mut registry, "listPets", "get", &[], false);
+        register(&mut registry, "addPet", "post", &[], false);
+
+        registry.set_enabled("addPet", false);
+        assert!(!registry.is_enabled("addPet"));
+        assert!(disabled.contains("addPet"));
+        assert!(registry.has_tool("addPet"));
+        let enabled: Vec<&str> = registry
+            .get_enabled_tools()
+            .into_iter()
+            .map(|tool| tool.name.as_str())
+            .collect();
+        assert_eq!(enabled, ["listPets"]);
+
+        registry.clear();
+        register(&mut registry, "addPet", "post", &[], false);
+        assert!(registry.get_enabled_tools().is_empty());
+        assert_eq!(registry.get_disabled_tool_names(), ["addPet"]);
+
+        disabled.set("addPet", false);
+        assert_eq!(registry.get_enabled_tools().len(), 1);
+    }
+
+    #[test]
+    fn test_stats_by_tag_and_method() {
+        let mut registry = ToolRegistry::new();
+        register(&mut registry, "listPets", "get", &["pet"], false);
+        register(&mut registry, "addPet", "post", &["pet"], true);
+        register(&mut registry, "oldOrder", "get", &["store", "pet"], true);
+        register(&mut registry, "ping", "get", &[], false);
+
+        let stats = registry.get_stats();
+        assert_eq!(
+            stats.summary(),
+            "Tools: 4, Methods: [GET: 3, POST: 1], Tags: [pet: 3, store: 1], Paths: 4, \
+             Deprecated: 1, Auth required: 2, Spec: none"
+        );
+        assert_eq!(
+            stats.to_json(),
+            json!({
+                "total_tools": 4,
+                "methods": { "get": 3, "post": 1 },
+                "tags": { "pet": 3, "store": 1 },
+                "unique_paths": 4,
+                "deprecated_tools": 1,
+                "auth_required_tools": 2,
+                "has_spec": false
+            })
+        );
+    }
 }
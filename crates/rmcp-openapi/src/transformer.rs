@@ -60,7 +60,8 @@ use serde_json::Value;
 ///
 /// # Usage
 ///
-/// Response transformers can be applied globally to all tools or per-tool:
+/// Response transformers can be applied globally, to the tools of a tag, or
+/// per-tool:
 ///
 /// ```rust,ignore
 /// use std::sync::Arc;
@@ -71,7 +72,8 @@ use serde_json::Value;
 ///     .openapi_spec(spec)
 ///     .base_url(url)
 ///     .response_transformer(Arc::new(RemoveNulls))
-///     .build();
+///     .build()
+///     .with_transformer("billing", Box::new(MaskAmounts));
 ///
 /// server.load_openapi_spec()?;
 ///
@@ -82,8 +84,10 @@ use serde_json::Value;
 /// # Resolution Order
 ///
 /// 1. Per-tool transformer (if set) takes precedence
-/// 2. Else global server transformer
-/// 3. Else no transformation
+/// 2. Else the transformer registered for the tool's name, then its tags
+/// 3. Else the pipeline matching the tool
+/// 4. Else global server transformer
+/// 5. Else no transformation
 pub trait ResponseTransformer: Send + Sync {
     /// Transform the response body before returning to MCP client.
     ///
@@ -0,0 +1,357 @@
+//! Typed upstream errors, read with the spec's error response schemas.
+//!
+//! Upstream errors otherwise reach agents as the raw response body. With
+//! typed errors enabled, a `4xx` or `5xx` JSON body answered by an operation
+//! declaring a schema for that status (`404`, `4XX` or `default`) gives the
+//! structured result an `error` object, found at the same place whatever the
+//! API:
+//!
+//! ```json
+//! {
+//!   "status": 404,
+//!   "body": { "error": { "code": "pet_not_found", "message": "No pet 42" } },
+//!   "error": {
+//!     "response": "404",
+//!     "description": "Pet not found",
+//!     "code": "pet_not_found",
+//!     "message": "No pet 42"
+//!   }
+//! }
+//! ```
+//!
+//! `code` and `message` are read from the members the schema declares with
+//! the usual names (`code`, `error_code`, `type`, ... and `message`,
+//! `detail`, `error_description`, ...), at the top level of the body or in its
+//! `error` object or first `errors` item, and are `null` when absent.
+
+use serde_json::{Map, Value, json};
+
+use crate::tool::ToolMetadata;
+
+/// Members holding the error code, by preference
+const CODE_FIELDS: &[&str] = &["code", "error_code", "errorCode", "type", "error"];
+
+/// Members holding the error message, by preference
+const MESSAGE_FIELDS: &[&str] = &[
+    "message",
+    "error_message",
+    "errorMessage",
+    "detail",
+    "error_description",
+    "title",
+    "description",
+];
+
+/// Most `$ref`s followed to resolve one schema
+const MAX_REFERENCES: usize = 16;
+
+/// Levels of error schemas resolved, enough for `errors[0].code`
+const INLINE_DEPTH: usize = 3;
+
+/// Error response schemas of an operation, by response key
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ErrorSchemas {
+    responses: Vec<ErrorResponse>,
+}
+
+#[derive(Debug, Clone)]
+struct ErrorResponse {
+    /// `404`, `4XX` or `default`
+    key: String,
+    description: Option<String>,
+    schema: Value,
+}
+
+impl ErrorSchemas {
+    /// Error schemas of the operation of `metadata` in `spec`, if it declares
+    /// any with JSON content
+    pub(crate) fn of(spec: &Value, metadata: &ToolMetadata) -> Option<Self> {
+        let operation = spec
+            .get("paths")?
+            .get(&metadata.path)?
+            .get(metadata.method.to_lowercase())?;
+        let responses = resolve(spec, operation.get("responses")?).as_object()?;
+        let responses: Vec<ErrorResponse> = responses
+            .iter()
+            .filter(|(key, _)| is_error_key(key))
+            .filter_map(|(key, response)| {
+                let response = resolve(spec, response);
+                let schema = response
+                    .get("content")?
+                    .as_object()?
+                    .iter()
+                    .find(|(media_type, _)| is_json(media_type))?
+                    .1
+                    .get("schema")?;
+                Some(ErrorResponse {
+                    key: key.clone(),
+                    description: response
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    schema: inline(spec, schema, INLINE_DEPTH),
+                })
+            })
+            .collect();
+        (!responses.is_empty()).then_some(Self { responses })
+    }
+
+    /// Typed error of a `status` response with `body`, if the operation
+    /// declares a schema for it
+    pub(crate) fn parse(&self, status: u16, body: &Value) -> Option<Value> {
+        let code = status.to_string();
+        let class = format!("{}XX", status / 100);
+        let response = [code.as_str(), class.as_str(), "default"]
+            .into_iter()
+            .find_map(|key| {
+                self.responses
+                    .iter()
+                    .find(|response| response.key.eq_ignore_ascii_case(key))
+            })?;
+        let object = body.as_object()?;
+
+        // The error may be the body itself, or nested in it
+        let mut candidates = vec![(object, response.schema.get("properties"))];
+        if let Some(nested) = object.get("error").and_then(Value::as_object) {
+            candidates.insert(
+                0,
+                (
+                    nested,
+                    response.schema.pointer("/properties/error/properties"),
+                ),
+            );
+        }
+        if let Some(nested) = object
+            .get("errors")
+            .and_then(Value::as_array)
+            .and_then(|errors| errors.first())
+            .and_then(Value::as_object)
+        {
+            candidates.push((
+                nested,
+                response
+                    .schema
+                    .pointer("/properties/errors/items/properties"),
+            ));
+        }
+
+        Some(json!({
+            "response": response.key,
+            "description": response.description,
+            "code": find(&candidates, CODE_FIELDS, |value| value.is_string() || value.is_number()),
+            "message": find(&candidates, MESSAGE_FIELDS, Value::is_string),
+        }))
+    }
+}
+
+/// Output schema of the `error` field of structured results
+pub(crate) fn extend_output_schema(schema: &mut Value) {
+    if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+        properties.insert(
+            "error".to_string(),
+            json!({
+                "type": "object",
+                "description": "Upstream error, read with the error response schema of the operation",
+                "properties": {
+                    "response": { "type": "string", "description": "Response of the spec matched, such as `404` or `4XX`" },
+                    "description": { "type": ["string", "null"] },
+                    "code": { "type": ["string", "number", "null"] },
+                    "message": { "type": ["string", "null"] }
+                }
+            }),
+        );
+    }
+}
+
+/// First value of `fields`, preferring members the schema declares
+fn find(
+    candidates: &[(&Map<String, Value>, Option<&Value>)],
+    fields: &[&str],
+    accepts: impl Fn(&Value) -> bool,
+) -> Value {
+    let declared = candidates.iter().find_map(|&(object, properties)| {
+        fields.iter().find_map(|field| {
+            properties?.get(*field)?;
+            object.get(*field).filter(|value| accepts(value))
+        })
+    });
+    let present = || {
+        candidates.iter().find_map(|&(object, _)| {
+            fields
+                .iter()
+                .find_map(|field| object.get(*field).filter(|value| accepts(value)))
+        })
+    };
+    declared.or_else(present).cloned().unwrap_or(Value::Null)
+}
+
+fn is_error_key(key: &str) -> bool {
+    key == "default" || key.starts_with('4') || key.starts_with('5')
+}
+
+fn is_json(media_type: &str) -> bool {
+    media_type.parse::<mime::Mime>().is_ok_and(|mime_type| {
+        mime_type.subtype() == mime::JSON || mime_type.suffix() == Some(mime::JSON)
+    })
+}
+
+/// `value`, or the value its `$ref` points to in `spec`
+fn resolve<'a>(spec: &'a Value, mut value: &'a Value) -> &'a Value {
+    for _ in 0..MAX_REFERENCES {
+        let Some(target) = value
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| spec.pointer(pointer))
+        else {
+            break;
+        };
+        value = target;
+    }
+    value
+}
+
+/// `schema` with its `properties`, `items` and `allOf` references resolved,
+/// `depth` levels deep
+fn inline(spec: &Value, schema: &Value, depth: usize) -> Value {
+    let schema = resolve(spec, schema);
+    let Some(object) = schema.as_object() else {
+        return schema.clone();
+    };
+    if depth == 0 {
+        return schema.clone();
+    }
+    let mut inlined = object.clone();
+    if let Some(Value::Object(properties)) = inlined.get_mut("properties") {
+        for property in properties.values_mut() {
+            *property = inline(spec, property, depth - 1);
+        }
+    }
+    if let Some(items) = inlined.get_mut("items") {
+        *items = inline(spec, items, depth - 1);
+    }
+    // Members of composed schemas are declared by the whole
+    if let Some(Value::Array(parts)) = inlined.remove("allOf") {
+        let properties = inlined.entry("properties").or_insert_with(|| json!({}));
+        if let Some(properties) = properties.as_object_mut() {
+            for part in parts {
+                if let Some(Value::Object(part)) = inline(spec, &part, depth - 1).get("properties")
+                {
+                    properties.extend(part.clone());
+                }
+            }
+        }
+    }
+    Value::Object(inlined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> Value {
+        json!({
+            "paths": {
+                "/pets/{id}": {
+                    "get": {
+                        "responses": {
+                            "200": { "description": "A pet" },
+                            "404": {
+                                "description": "Pet not found",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Error" }
+                                    }
+                                }
+                            },
+                            "5XX": { "$ref": "#/components/responses/Problem" }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Error": {
+                        "type": "object",
+                        "properties": {
+                            "error": {
+                                "type": "object",
+                                "properties": {
+                                    "reason": { "type": "string" },
+                                    "message": { "type": "string" }
+                                }
+                            }
+                        }
+                    }
+                },
+                "responses": {
+                    "Problem": {
+                        "description": "Server error",
+                        "content": {
+                            "application/problem+json": {
+                                "schema": {
+                                    "properties": {
+                                        "type": { "type": "string" },
+                                        "title": { "type": "string" },
+                                        "detail": { "type": "string" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    fn metadata() -> ToolMetadata {
+        ToolMetadata {
+            name: "getPet".to_string(),
+            title: None,
+            description: None,
+            parameters: json!({}),
+            output_schema: None,
+            method: "GET".to_string(),
+            path: "/pets/{id}".to_string(),
+            tags: vec![],
+            security: None,
+            parameter_mappings: std::collections::HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_errors_are_read_with_their_schema() {
+        let schemas = ErrorSchemas::of(&spec(), &metadata()).unwrap();
+
+        let body = json!({ "error": { "code": "pet_not_found", "message": "No pet 42" } });
+        assert_eq!(
+            schemas.parse(404, &body).unwrap(),
+            json!({
+                "response": "404",
+                "description": "Pet not found",
+                "code": "pet_not_found",
+                "message": "No pet 42"
+            })
+        );
+
+        let body = json!({
+            "type": "https://example.com/overloaded",
+            "title": "Overloaded",
+            "detail": "Try again later"
+        });
+        let error = schemas.parse(503, &body).unwrap();
+        assert_eq!(error["response"], "5XX");
+        assert_eq!(error["code"], "https://example.com/overloaded");
+        assert_eq!(error["message"], "Try again later");
+
+        assert!(schemas.parse(400, &body).is_none());
+        assert!(schemas.parse(404, &json!("Not found")).is_none());
+    }
+
+    #[test]
+    fn test_operations_without_error_schemas() {
+        let mut metadata = metadata();
+        metadata.path = "/owners".to_string();
+        assert!(ErrorSchemas::of(&spec(), &metadata).is_none());
+    }
+}
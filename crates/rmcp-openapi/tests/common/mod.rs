@@ -1 +1,3 @@
 pub mod mock_server;
+pub mod pets;
+pub mod tls_server;
@@ -0,0 +1,86 @@
+//! The Pets API most integration tests call upstream.
+
+use rmcp_openapi::{Server, ToolMetadata};
+use serde_json::{Value, json};
+use url::Url;
+
+/// Spec of the Pets API with the operations of `paths`
+#[allow(dead_code)]
+pub fn spec(paths: Value) -> Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Pets", "version": "1.0.0" },
+        "paths": paths
+    })
+}
+
+/// Spec of the Pets API with only `GET /pets` (`listPets`)
+#[allow(dead_code)]
+pub fn list_pets_spec() -> Value {
+    spec(json!({
+        "/pets": {
+            "get": {
+                "operationId": "listPets",
+                "responses": { "200": { "description": "OK" } }
+            }
+        }
+    }))
+}
+
+/// Spec of the Pets API with only `GET /pets/{petId}` (`getPet`)
+#[allow(dead_code)]
+pub fn get_pet_spec() -> Value {
+    spec(json!({
+        "/pets/{petId}": {
+            "get": {
+                "operationId": "getPet",
+                "parameters": [{
+                    "name": "petId",
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "integer" }
+                }],
+                "responses": { "200": { "description": "OK" } }
+            }
+        }
+    }))
+}
+
+/// `server` with the tools of its spec loaded
+#[allow(dead_code)]
+pub fn load(mut server: Server) -> anyhow::Result<Server> {
+    server.load_openapi_spec()?;
+    Ok(server)
+}
+
+/// Server for the Pets API at `base_url` with only `listPets`
+#[allow(dead_code)]
+pub fn server(base_url: &Url) -> anyhow::Result<Server> {
+    load(
+        Server::builder()
+            .openapi_spec(list_pets_spec())
+            .base_url(base_url.clone())
+            .build(),
+    )
+}
+
+/// Metadata of the `listPets` tool for the Pets API at `base_url`
+#[allow(dead_code)]
+pub fn list_pets_tool(base_url: &Url) -> anyhow::Result<ToolMetadata> {
+    Ok(server(base_url)?
+        .get_tool_metadata("listPets")
+        .unwrap()
+        .clone())
+}
+
+/// Metadata of the `getPet` tool for the Pets API at `base_url`
+#[allow(dead_code)]
+pub fn get_pet_tool(base_url: &Url) -> anyhow::Result<ToolMetadata> {
+    let server = load(
+        Server::builder()
+            .openapi_spec(get_pet_spec())
+            .base_url(base_url.clone())
+            .build(),
+    )?;
+    Ok(server.get_tool_metadata("getPet").unwrap().clone())
+}
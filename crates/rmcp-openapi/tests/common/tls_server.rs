@@ -0,0 +1,76 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::crypto::aws_lc_rs;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+use url::Url;
+
+/// Path of a file in the test PKI (`tests/assets/tls`)
+///
+/// `ca.pem` signs both `server.pem` (valid for `localhost` and `127.0.0.1`)
+/// and `client.pem`.
+#[allow(dead_code)]
+pub fn tls_asset(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/assets/tls")
+        .join(name)
+}
+
+/// Start an HTTPS server answering every request with `200 []`
+///
+/// When `require_client_cert` is set, the handshake fails unless the client
+/// presents a certificate signed by the test CA.
+#[allow(dead_code)]
+pub async fn start_tls_server(require_client_cert: bool) -> Url {
+    let provider = Arc::new(aws_lc_rs::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .unwrap();
+    let builder = if require_client_cert {
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_file_iter(tls_asset("ca.pem")).unwrap() {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        builder.with_client_cert_verifier(
+            WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .unwrap(),
+        )
+    } else {
+        builder.with_no_client_auth()
+    };
+    let certs = CertificateDer::pem_file_iter(tls_asset("server.pem"))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let key = PrivateKeyDer::from_pem_file(tls_asset("server.key")).unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(builder.with_single_cert(certs, key).unwrap()));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                let Ok(mut stream) = acceptor.accept(stream).await else {
+                    return;
+                };
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 2\r\nconnection: close\r\n\r\n[]",
+                    )
+                    .await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+
+    Url::parse(&format!("https://localhost:{port}")).unwrap()
+}
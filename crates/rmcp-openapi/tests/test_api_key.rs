@@ -0,0 +1,68 @@
+//! Integration tests for API keys bound to `apiKey` security schemes.
+
+mod common;
+
+use common::pets;
+use mockito::Matcher;
+use rmcp_openapi::{ApiKey, ApiKeySource, Authorization, Server};
+use serde_json::json;
+use url::Url;
+
+fn server(base_url: &Url, api_keys: Vec<ApiKey>) -> anyhow::Result<Server> {
+    let mut spec = pets::list_pets_spec();
+    spec["components"] = json!({
+        "securitySchemes": {
+            "header_key": { "type": "apiKey", "name": "X-API-Key", "in": "header" },
+            "query_key": { "type": "apiKey", "name": "api_key", "in": "query" }
+        }
+    });
+    pets::load(
+        Server::builder()
+            .openapi_spec(spec)
+            .base_url(base_url.clone())
+            .api_keys(api_keys)
+            .build(),
+    )
+}
+
+#[actix_web::test]
+async fn test_api_keys_are_sent_where_the_scheme_says() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let server = server(
+        &base_url,
+        vec![
+            ApiKey {
+                scheme: "header_key".to_string(),
+                source: ApiKeySource::Value("from-config".to_string()),
+            },
+            "query_key=literal".parse().map_err(anyhow::Error::msg)?,
+        ],
+    )?;
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .match_header("x-api-key", "from-config")
+        .match_query(Matcher::UrlEncoded("api_key".into(), "literal".into()))
+        .with_header("content-type", "application/json")
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let result = server
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .await?;
+    assert_ne!(result.is_error, Some(true));
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+#[test]
+fn test_unknown_scheme_fails_loading() {
+    let base_url = Url::parse("http://localhost").unwrap();
+    assert!(server(&base_url, vec!["missing=abc".parse().unwrap()]).is_err());
+}
@@ -0,0 +1,122 @@
+//! Integration tests for the outbound request audit log.
+
+mod common;
+
+use common::pets;
+use reqwest::header::{HeaderMap, HeaderValue};
+use rmcp_openapi::{AuditConfig, AuditLog, AuditSink, HttpClient};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use std::sync::Arc;
+use url::Url;
+
+fn audit_file(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("rmcp-openapi-{}-{name}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn read_records(path: &PathBuf) -> Vec<Value> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[actix_web::test]
+async fn test_upstream_calls_are_recorded() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::get_pet_tool(&base_url)?;
+
+    let found = mock_server
+        .mock("GET", "/pets/1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+    let missing = mock_server
+        .mock("GET", "/pets/2")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let path = audit_file("recorded");
+    let audit_log = AuditLog::new(
+        AuditConfig::builder()
+            .sink(AuditSink::File(path.clone()))
+            .build(),
+    )?;
+    let mut headers = HeaderMap::new();
+    headers.insert("x-trace", HeaderValue::from_static("abc"));
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_default_headers(headers)
+        .with_audit_log(Arc::new(audit_log))
+        .with_session_id("session-1")
+        .with_authorization("Bearer secret-token");
+
+    client
+        .execute_tool_call(&tool, &json!({ "petId": 1 }))
+        .await?;
+    client
+        .execute_tool_call(&tool, &json!({ "petId": 2 }))
+        .await?;
+
+    let records = read_records(&path);
+    assert_eq!(records.len(), 2);
+
+    let first = &records[0];
+    assert_eq!(first["tool"], "getPet");
+    assert_eq!(first["method"], "GET");
+    assert_eq!(first["url"], format!("{}pets/1", base_url));
+    assert_eq!(first["status"], 200);
+    assert_eq!(first["session_id"], "session-1");
+    assert!(first["duration_ms"].is_u64());
+    assert!(first["timestamp_ms"].as_u64().unwrap() > 0);
+    assert_eq!(first["request_headers"]["authorization"], "<redacted>");
+    assert_eq!(first["request_headers"]["x-trace"], "abc");
+    assert!(!std::fs::read_to_string(&path)?.contains("secret-token"));
+
+    assert_eq!(records[1]["status"], 404);
+
+    found.assert_async().await;
+    missing.assert_async().await;
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_transport_errors_are_recorded() -> anyhow::Result<()> {
+    // Nothing listens on port 1
+    let base_url = Url::parse("http://127.0.0.1:1")?;
+    let tool = pets::get_pet_tool(&base_url)?;
+
+    let path = audit_file("errors");
+    let audit_log = AuditLog::new(
+        AuditConfig::builder()
+            .sink(AuditSink::File(path.clone()))
+            .build(),
+    )?;
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_audit_log(Arc::new(audit_log));
+
+    assert!(
+        client
+            .execute_tool_call(&tool, &json!({ "petId": 1 }))
+            .await
+            .is_err()
+    );
+
+    let records = read_records(&path);
+    assert_eq!(records.len(), 1);
+    assert!(records[0].get("status").is_none());
+    assert!(records[0]["error"].is_string());
+    assert!(records[0].get("session_id").is_none());
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
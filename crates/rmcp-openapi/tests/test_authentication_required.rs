@@ -0,0 +1,190 @@
+//! Integration tests for rejecting calls that need credentials nobody provides.
+
+mod common;
+
+use common::pets;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{Authorization, Server, ToolCallError};
+use serde_json::json;
+use std::collections::BTreeMap;
+use url::Url;
+
+fn server(base_url: &Url, default_headers: Option<HeaderMap>) -> anyhow::Result<Server> {
+    let mut spec = pets::spec(json!({
+        "/pets": {
+            "get": {
+                "operationId": "listPets",
+                "responses": { "200": { "description": "OK" } }
+            }
+        },
+        "/health": {
+            "get": {
+                "operationId": "health",
+                "security": [],
+                "responses": { "200": { "description": "OK" } }
+            }
+        },
+        "/pets/featured": {
+            "get": {
+                "operationId": "featuredPets",
+                "security": [{ "api_key": [] }, {}],
+                "responses": { "200": { "description": "OK" } }
+            }
+        }
+    }));
+    spec["security"] = json!([{ "petstore_auth": ["pets:read"] }]);
+    spec["components"] = json!({
+        "securitySchemes": {
+            "petstore_auth": {
+                "type": "oauth2",
+                "flows": {
+                    "clientCredentials": {
+                        "tokenUrl": "https://idp.example.com/token",
+                        "scopes": { "pets:read": "Read pets" }
+                    }
+                }
+            },
+            "api_key": { "type": "apiKey", "name": "X-API-Key", "in": "header" }
+        }
+    });
+    pets::load(
+        Server::builder()
+            .openapi_spec(spec)
+            .base_url(base_url.clone())
+            .maybe_default_headers(default_headers)
+            .build(),
+    )
+}
+
+#[actix_web::test]
+async fn test_missing_credentials_fail_before_the_upstream_call() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let server = server(&base_url, None)?;
+
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let result = server
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .await;
+
+    match result {
+        Err(ToolCallError::Execution(ToolCallExecutionError::AuthenticationRequired {
+            tool,
+            requirements,
+        })) => {
+            assert_eq!(tool, "listPets");
+            assert_eq!(
+                requirements,
+                vec![BTreeMap::from([(
+                    "petstore_auth".to_string(),
+                    vec!["pets:read".to_string()]
+                )])]
+            );
+        }
+        other => panic!("expected an authentication error, got {other:?}"),
+    }
+
+    pets.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_public_and_optional_operations_are_called() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let server = server(&base_url, None)?;
+
+    let health = mock_server
+        .mock("GET", "/health")
+        .with_body("ok")
+        .create_async()
+        .await;
+    let featured = mock_server
+        .mock("GET", "/pets/featured")
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    for tool in ["health", "featuredPets"] {
+        server
+            .get_tool(tool)
+            .unwrap()
+            .call(&json!({}), Authorization::None, None)
+            .await?;
+    }
+
+    health.assert_async().await;
+    featured.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_configured_credentials_are_sent() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer t"));
+    let server = server(&base_url, Some(headers))?;
+
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .match_header("authorization", "Bearer t")
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    server
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .await?;
+
+    pets.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_only_credential_headers_count_as_credentials() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .match_header("x-api-key", "k3y")
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+    headers.insert(header::USER_AGENT, HeaderValue::from_static("pets-agent"));
+    let result = server(&base_url, Some(headers.clone()))?
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ToolCallError::Execution(
+            ToolCallExecutionError::AuthenticationRequired { .. }
+        ))
+    ));
+
+    headers.insert("x-api-key", HeaderValue::from_static("k3y"));
+    server(&base_url, Some(headers))?
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .await?;
+
+    pets.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Integration tests for translating forwarded client authorization into
+//! upstream headers.
+
+mod common;
+
+use common::pets;
+use mockito::Matcher;
+use rmcp_openapi::HttpClient;
+use serde_json::json;
+use url::Url;
+
+#[actix_web::test]
+async fn test_client_token_is_sent_as_templated_headers() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .match_header("x-api-key", "abc")
+        .match_header("x-auth-scheme", "Bearer")
+        .match_header("authorization", Matcher::Missing)
+        .with_header("content-type", "application/json")
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_authorization_templates(vec![
+            "X-Api-Key: {token}".parse().map_err(anyhow::Error::msg)?,
+            "X-Auth-Scheme: {scheme}"
+                .parse()
+                .map_err(anyhow::Error::msg)?,
+        ])
+        .with_client_authorization("Bearer abc");
+    let response = client.execute_tool_call(&tool, &json!({})).await?;
+    assert!(response.is_success);
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_client_authorization_is_forwarded_as_is_without_templates() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .match_header("authorization", "Bearer abc")
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_client_authorization("Bearer abc");
+    client.execute_tool_call(&tool, &json!({})).await?;
+
+    mock.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! Integration tests for trusting a custom CA bundle for upstream APIs.
+
+mod common;
+
+use common::pets;
+use common::tls_server::{start_tls_server, tls_asset};
+use rmcp_openapi::{ClientCertificate, Error, HttpClient};
+use serde_json::json;
+
+#[actix_web::test]
+async fn test_private_ca_is_trusted_with_bundle() -> anyhow::Result<()> {
+    let base_url = start_tls_server(false).await;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    // Default roots do not know the private CA
+    let client = HttpClient::new().with_base_url(base_url.clone())?;
+    assert!(client.execute_tool_call(&tool, &json!({})).await.is_err());
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_ca_bundle(&tls_asset("ca.pem"))?;
+    let response = client.execute_tool_call(&tool, &json!({})).await?;
+    assert_eq!(response.status_code, 200);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_ca_bundle_combines_with_client_certificate() -> anyhow::Result<()> {
+    let base_url = start_tls_server(true).await;
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_ca_bundle(&tls_asset("ca.pem"))?
+        .with_client_certificate(
+            &ClientCertificate::builder()
+                .cert_path(tls_asset("client.pem"))
+                .key_path(tls_asset("client.key"))
+                .build(),
+        )?;
+
+    let response = client
+        .execute_tool_call(&pets::list_pets_tool(&base_url)?, &json!({}))
+        .await?;
+    assert_eq!(response.status_code, 200);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_ca_bundle_is_rejected() {
+    assert!(matches!(
+        HttpClient::new().with_ca_bundle(&tls_asset("missing.pem")),
+        Err(Error::Tls(_))
+    ));
+    // A private key is not a certificate
+    assert!(matches!(
+        HttpClient::new().with_ca_bundle(&tls_asset("client.key")),
+        Err(Error::Tls(_))
+    ));
+}
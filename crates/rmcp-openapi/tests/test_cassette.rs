@@ -0,0 +1,71 @@
+//! Integration tests for record-and-replay of upstream traffic.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{Cassette, CassetteConfig, CassetteMode, HttpClient, ToolCallError};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use url::Url;
+
+fn cassette(path: &Path, mode: CassetteMode) -> anyhow::Result<Arc<Cassette>> {
+    Ok(Arc::new(Cassette::open(
+        CassetteConfig::builder()
+            .path(path.to_path_buf())
+            .mode(mode)
+            .build(),
+    )?))
+}
+
+#[actix_web::test]
+async fn test_recorded_responses_are_replayed_offline() -> anyhow::Result<()> {
+    let path =
+        std::env::temp_dir().join(format!("rmcp-openapi-{}-cassette.json", std::process::id()));
+
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::get_pet_tool(&base_url)?;
+    let mock = mock_server
+        .mock("GET", "/pets/1")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id":1,"name":"Rex"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let recorder = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_cassette(cassette(&path, CassetteMode::Record)?);
+    let recorded = recorder
+        .execute_tool_call(&tool, &json!({ "petId": 1 }))
+        .await?;
+    mock.assert_async().await;
+
+    // The upstream is gone; the cassette answers instead
+    drop(mock_server);
+    let player = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_cassette(cassette(&path, CassetteMode::Replay)?);
+    let replayed = player
+        .execute_tool_call(&tool, &json!({ "petId": 1 }))
+        .await?;
+    assert_eq!(replayed.status_code, 200);
+    assert_eq!(replayed.body, recorded.body);
+    assert_eq!(replayed.json()?["name"], "Rex");
+
+    match player
+        .execute_tool_call(&tool, &json!({ "petId": 2 }))
+        .await
+    {
+        Err(ToolCallError::Execution(ToolCallExecutionError::NetworkError { message, .. })) => {
+            assert!(message.contains("No recorded response"), "{message}");
+        }
+        other => panic!("expected network error, got {other:?}"),
+    }
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
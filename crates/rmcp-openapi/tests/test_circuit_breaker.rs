@@ -0,0 +1,62 @@
+//! Integration tests for the per-host circuit breaker.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{CircuitBreakerConfig, HttpClient, ToolCallError};
+use serde_json::json;
+use std::time::Duration;
+use url::Url;
+
+#[actix_web::test]
+async fn test_circuit_opens_after_consecutive_failures() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    // Only the failures that reach the upstream are served
+    let failing = mock_server
+        .mock("GET", "/pets")
+        .with_status(502)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_circuit_breaker(
+            CircuitBreakerConfig::builder()
+                .failure_threshold(2)
+                .open_duration(Duration::from_secs(60))
+                .build(),
+        );
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    for _ in 0..2 {
+        let response = client.execute_tool_call(&tool, &json!({})).await?;
+        assert_eq!(response.status_code, 502);
+    }
+
+    // Clones (one per tool) share the circuit
+    let result = client.clone().execute_tool_call(&tool, &json!({})).await;
+    match result {
+        Err(ToolCallError::Execution(ToolCallExecutionError::UpstreamUnavailable {
+            host,
+            retry_after_seconds,
+        })) => {
+            assert_eq!(
+                host,
+                format!(
+                    "{}:{}",
+                    base_url.host_str().unwrap(),
+                    base_url.port().unwrap()
+                )
+            );
+            assert!(retry_after_seconds > 0 && retry_after_seconds <= 60);
+        }
+        other => panic!("expected upstream-unavailable error, got {other:?}"),
+    }
+
+    failing.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Integration tests for upstream request and response compression.
+
+mod common;
+
+use common::pets;
+use mockito::Matcher;
+use rmcp_openapi::{CompressionConfig, ContentEncoding, HttpClient, Server, ToolMetadata};
+use serde_json::json;
+use url::Url;
+
+fn tools(base_url: &Url) -> anyhow::Result<(ToolMetadata, ToolMetadata)> {
+    let mut server = Server::builder()
+        .openapi_spec(pets::spec(json!({
+            "/pets": {
+                "get": {
+                    "operationId": "listPets",
+                    "responses": { "200": { "description": "OK" } }
+                },
+                "post": {
+                    "operationId": "addPets",
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/json": {
+                                "schema": { "type": "array", "items": { "type": "object" } }
+                            }
+                        }
+                    },
+                    "responses": { "201": { "description": "Created" } }
+                }
+            }
+        })))
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+    Ok((
+        server.get_tool_metadata("listPets").unwrap().clone(),
+        server.get_tool_metadata("addPets").unwrap().clone(),
+    ))
+}
+
+#[actix_web::test]
+async fn test_compressed_responses_are_decoded() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (list_pets, _) = tools(&base_url)?;
+
+    let body = r#"[{"id":1,"name":"doggie"},{"id":2,"name":"kitty"}]"#;
+    for encoding in [
+        ContentEncoding::Gzip,
+        ContentEncoding::Deflate,
+        ContentEncoding::Brotli,
+    ] {
+        let client = HttpClient::new()
+            .with_base_url(base_url.clone())?
+            .with_compression(CompressionConfig::default());
+        let mock = mock_server
+            .mock("GET", "/pets")
+            .match_header("accept-encoding", "gzip, deflate, br")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("content-encoding", encoding.as_str())
+            .with_body(encoding.encode(body.as_bytes())?)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let response = client.execute_tool_call(&list_pets, &json!({})).await?;
+        assert_eq!(response.body, body, "{encoding:?}");
+        assert!(!response.headers.contains_key("content-encoding"));
+        assert_eq!(response.json()?[1]["name"], "kitty");
+
+        mock.assert_async().await;
+        mock.remove_async().await;
+    }
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_without_compression_no_encoding_is_advertised() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (list_pets, _) = tools(&base_url)?;
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .match_header("accept-encoding", Matcher::Missing)
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new().with_base_url(base_url)?;
+    let response = client.execute_tool_call(&list_pets, &json!({})).await?;
+    assert_eq!(response.body, "[]");
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_large_json_request_bodies_are_compressed() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (_, add_pets) = tools(&base_url)?;
+
+    let pets: Vec<_> = (0..100)
+        .map(|id| json!({ "id": id, "name": format!("pet-{id}") }))
+        .collect();
+    let expected = serde_json::to_vec(&pets)?;
+
+    let compressed = mock_server
+        .mock("POST", "/pets")
+        .match_header("content-encoding", "gzip")
+        .match_request(move |request| {
+            request
+                .body()
+                .ok()
+                .and_then(|body| ContentEncoding::Gzip.decode(body).ok())
+                .is_some_and(|body| body == expected)
+        })
+        .with_status(201)
+        .expect(1)
+        .create_async()
+        .await;
+    let small = mock_server
+        .mock("POST", "/pets")
+        .match_header("content-encoding", Matcher::Missing)
+        .match_body(Matcher::Json(json!([{ "id": 1 }])))
+        .with_status(201)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new().with_base_url(base_url)?.with_compression(
+        CompressionConfig::builder()
+            .request_encoding(ContentEncoding::Gzip)
+            .min_request_size(256)
+            .build(),
+    );
+
+    let response = client
+        .execute_tool_call(&add_pets, &json!({ "request_body": pets }))
+        .await?;
+    assert_eq!(response.status_code, 201);
+    // Below the size threshold the body is sent as is
+    let response = client
+        .execute_tool_call(&add_pets, &json!({ "request_body": [{ "id": 1 }] }))
+        .await?;
+    assert_eq!(response.status_code, 201);
+
+    compressed.assert_async().await;
+    small.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! Integration tests for limits on concurrent upstream requests.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{ConcurrencyLimits, HttpClient, ToolCallError};
+use serde_json::json;
+use std::net::TcpListener;
+use std::time::Duration;
+use url::Url;
+
+#[actix_web::test]
+async fn test_calls_over_the_session_limit_are_throttled() -> anyhow::Result<()> {
+    // Connections queue in the backlog and never get a response
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let base_url = Url::parse(&format!("http://{}", listener.local_addr()?))?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    let client = HttpClient::with_timeout(2)
+        .with_base_url(base_url)?
+        .with_concurrency_limits(ConcurrencyLimits::builder().per_session(1).build());
+    let session_a = client.clone().with_session_id("a");
+
+    let hanging = {
+        let (client, tool) = (session_a.clone(), tool.clone());
+        actix_web::rt::spawn(async move { client.execute_tool_call(&tool, &json!({})).await })
+    };
+    actix_web::rt::time::sleep(Duration::from_millis(200)).await;
+
+    match session_a.execute_tool_call(&tool, &json!({})).await {
+        Err(ToolCallError::Execution(ToolCallExecutionError::Throttled { scope, limit })) => {
+            assert_eq!(scope, "session");
+            assert_eq!(limit, 1);
+        }
+        other => panic!("expected throttled error, got {other:?}"),
+    }
+
+    // Another session still has its own slot; it times out against the silent upstream
+    let other_session = client.with_session_id("b");
+    assert!(matches!(
+        other_session.execute_tool_call(&tool, &json!({})).await,
+        Err(ToolCallError::Execution(
+            ToolCallExecutionError::NetworkError { .. }
+        ))
+    ));
+
+    assert!(hanging.await?.is_err());
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Integration tests for confirmation of destructive tool calls.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{Authorization, ConfirmationConfig, Server, ToolCallError};
+use serde_json::json;
+use url::Url;
+
+fn server(base_url: &Url) -> anyhow::Result<Server> {
+    let mut spec = pets::get_pet_spec();
+    spec["paths"]["/pets/{petId}"]["delete"] = json!({
+        "operationId": "deletePet",
+        "parameters": [{
+            "name": "petId",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "integer" }
+        }],
+        "responses": { "204": { "description": "Deleted" } }
+    });
+    pets::load(
+        Server::builder()
+            .openapi_spec(spec)
+            .base_url(base_url.clone())
+            .confirmation(ConfirmationConfig::default())
+            .build(),
+    )
+}
+
+#[actix_web::test]
+async fn test_delete_runs_only_once_confirmed() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let server = server(&Url::parse(&mock_server.url())?)?;
+    let delete = mock_server
+        .mock("DELETE", "/pets/1")
+        .with_status(204)
+        .expect(1)
+        .create_async()
+        .await;
+    let tool = server.get_tool("deletePet").unwrap();
+
+    let token = match tool
+        .call(&json!({ "petId": 1 }), Authorization::None, None)
+        .await
+    {
+        Err(ToolCallError::Execution(ToolCallExecutionError::ConfirmationRequired {
+            method,
+            confirmation_token,
+            ..
+        })) => {
+            assert_eq!(method, "DELETE");
+            confirmation_token
+        }
+        other => panic!("expected a confirmation request, got {other:?}"),
+    };
+
+    let result = tool
+        .call(
+            &json!({ "petId": 1, "confirmation_token": token }),
+            Authorization::None,
+            None,
+        )
+        .await?;
+    assert_ne!(result.is_error, Some(true));
+
+    // The token is used up
+    let repeated = tool
+        .call(
+            &json!({ "petId": 1, "confirmation_token": token }),
+            Authorization::None,
+            None,
+        )
+        .await;
+    assert!(matches!(
+        repeated,
+        Err(ToolCallError::Execution(
+            ToolCallExecutionError::ConfirmationRequired { .. }
+        ))
+    ));
+
+    delete.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_safe_methods_need_no_confirmation() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let server = server(&Url::parse(&mock_server.url())?)?;
+    let get = mock_server
+        .mock("GET", "/pets/1")
+        .with_status(200)
+        .with_body("{}")
+        .expect(1)
+        .create_async()
+        .await;
+
+    server
+        .get_tool("getPet")
+        .unwrap()
+        .call(&json!({ "petId": 1 }), Authorization::None, None)
+        .await?;
+
+    get.assert_async().await;
+    Ok(())
+}
+
+#[test]
+fn test_token_parameter_is_declared_for_destructive_tools() -> anyhow::Result<()> {
+    let server = server(&Url::parse("http://127.0.0.1:9")?)?;
+    let properties =
+        |name: &str| server.get_tool_metadata(name).unwrap().parameters["properties"].clone();
+    assert!(properties("deletePet").get("confirmation_token").is_some());
+    assert!(properties("getPet").get("confirmation_token").is_none());
+    Ok(())
+}
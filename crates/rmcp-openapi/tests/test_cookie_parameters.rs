@@ -0,0 +1,121 @@
+//! Integration tests verifying that cookie parameters reach the upstream
+//! API as a properly serialized `Cookie` header.
+
+use mockito::Matcher;
+use rmcp_openapi::{HttpClient, Server};
+use serde_json::json;
+use url::Url;
+
+fn cookie_spec() -> serde_json::Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Cookie API", "version": "1.0.0" },
+        "paths": {
+            "/session": {
+                "get": {
+                    "operationId": "getSession",
+                    "parameters": [
+                        {
+                            "name": "session_id",
+                            "in": "cookie",
+                            "required": true,
+                            "schema": { "type": "string" }
+                        },
+                        {
+                            "name": "prefs",
+                            "in": "cookie",
+                            "explode": false,
+                            "schema": { "type": "array", "items": { "type": "string" } }
+                        },
+                        {
+                            "name": "tags",
+                            "in": "cookie",
+                            "schema": { "type": "array", "items": { "type": "string" } }
+                        }
+                    ],
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        }
+    })
+}
+
+fn create_server(base_url: Url) -> anyhow::Result<Server> {
+    let mut server = Server::builder()
+        .openapi_spec(cookie_spec())
+        .base_url(base_url)
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server)
+}
+
+#[actix_web::test]
+async fn test_cookie_parameters_sent_as_cookie_header() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let mock = mock_server
+        .mock("GET", "/session")
+        .match_header(
+            "cookie",
+            "prefs=dark,compact; session_id=abc%20123; tags=a; tags=b",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"ok": true}"#)
+        .create_async()
+        .await;
+
+    let server = create_server(base_url.clone())?;
+    let client = HttpClient::new().with_base_url(base_url)?;
+    let tool_metadata = server
+        .get_tool_metadata("getSession")
+        .expect("getSession tool should be registered");
+
+    let arguments = json!({
+        "cookie_session_id": "abc 123",
+        "cookie_prefs": ["dark", "compact"],
+        "cookie_tags": ["a", "b"]
+    });
+
+    let response = client.execute_tool_call(tool_metadata, &arguments).await?;
+
+    assert!(response.is_success);
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_no_cookie_header_without_cookie_arguments() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let mock = mock_server
+        .mock("GET", "/session")
+        .match_header("cookie", Matcher::Missing)
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let mut spec = cookie_spec();
+    spec["paths"]["/session"]["get"]["parameters"][0]["required"] = json!(false);
+    let mut server = Server::builder()
+        .openapi_spec(spec)
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+
+    let client = HttpClient::new().with_base_url(base_url)?;
+    let tool_metadata = server
+        .get_tool_metadata("getSession")
+        .expect("getSession tool should be registered");
+
+    let response = client.execute_tool_call(tool_metadata, &json!({})).await?;
+
+    assert!(response.is_success);
+    mock.assert_async().await;
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! Integration tests for hand-written tools served alongside generated ones.
+
+mod common;
+
+use common::pets;
+use rmcp::model::CallToolResult;
+use rmcp_openapi::{CustomTool, Server};
+use serde_json::json;
+use url::Url;
+
+fn custom_tool(name: &str) -> anyhow::Result<CustomTool> {
+    Ok(CustomTool::new(
+        name,
+        "Hand-written helper",
+        json!({ "type": "object", "properties": {} }),
+        |_| async { Ok(CallToolResult::success(vec![])) },
+    )?)
+}
+
+fn build_server(custom_tools: Vec<CustomTool>) -> anyhow::Result<Server> {
+    Ok(Server::builder()
+        .openapi_spec(pets::list_pets_spec())
+        .base_url(Url::parse("http://127.0.0.1:9")?)
+        .custom_tools(custom_tools)
+        .build())
+}
+
+#[test]
+fn test_custom_tools_are_registered_alongside_generated_ones() -> anyhow::Result<()> {
+    let mut server = build_server(vec![custom_tool("login")?])?;
+    server.load_openapi_spec()?;
+    server.register_tool(custom_tool("summarize_logs")?)?;
+
+    let names: Vec<&str> = server.custom_tools.iter().map(CustomTool::name).collect();
+    assert_eq!(names, ["login", "summarize_logs"]);
+    assert!(server.has_tool("listPets"));
+    Ok(())
+}
+
+#[test]
+fn test_custom_tools_cannot_hide_other_tools() -> anyhow::Result<()> {
+    let mut server = build_server(vec![custom_tool("listPets")?])?;
+    assert!(server.load_openapi_spec().is_err());
+
+    let mut server = build_server(vec![custom_tool("login")?, custom_tool("login")?])?;
+    assert!(server.load_openapi_spec().is_err());
+
+    let mut server = build_server(vec![])?;
+    server.load_openapi_spec()?;
+    assert!(server.register_tool(custom_tool("listPets")?).is_err());
+    assert!(server.register_tool(custom_tool("search_tools")?).is_err());
+    Ok(())
+}
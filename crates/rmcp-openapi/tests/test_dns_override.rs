@@ -0,0 +1,43 @@
+//! Integration tests for DNS overrides of upstream hosts.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::{ConnectionConfig, HttpClient};
+use serde_json::json;
+use url::Url;
+
+#[actix_web::test]
+async fn test_overridden_host_reaches_pinned_address() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let port = Url::parse(&mock_server.url())?.port().unwrap();
+
+    // The name does not resolve; the override points it at the mock server
+    let base_url = Url::parse(&format!("http://pets.staging.invalid:{port}"))?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .match_header("host", format!("pets.staging.invalid:{port}").as_str())
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let connection = ConnectionConfig::builder()
+        .resolve(vec![
+            format!("pets.staging.invalid:{port}:127.0.0.1")
+                .parse()
+                .unwrap(),
+        ])
+        .build();
+    let client = HttpClient::new()
+        .with_connection_config(connection)
+        .with_base_url(base_url)?;
+
+    let response = client.execute_tool_call(&tool, &json!({})).await?;
+    assert_eq!(response.status_code, 200);
+
+    mock.assert_async().await;
+    Ok(())
+}
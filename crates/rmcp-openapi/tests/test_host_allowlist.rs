@@ -0,0 +1,163 @@
+//! Integration tests for the upstream host allowlist.
+//!
+//! Mock servers listen on 127.0.0.1; `localhost` URLs reach them under a
+//! host name the allowlists below do not contain.
+
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{
+    ClientCredentialsConfig, HostAllowlist, HttpClient, RedirectPolicy, Server, ToolCallError,
+    ToolMetadata,
+};
+use serde_json::{Value, json};
+use url::Url;
+
+fn spec() -> Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Reports", "version": "1.0.0" },
+        "paths": {
+            "/reports/latest": {
+                "get": {
+                    "operationId": "getLatestReport",
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        }
+    })
+}
+
+fn get_latest_report(base_url: &Url) -> anyhow::Result<ToolMetadata> {
+    let mut server = Server::builder()
+        .openapi_spec(spec())
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server.get_tool_metadata("getLatestReport").unwrap().clone())
+}
+
+fn localhost(server: &mockito::ServerGuard) -> String {
+    format!("http://localhost:{}", server.socket_address().port())
+}
+
+#[actix_web::test]
+async fn test_base_url_host_is_allowed() -> anyhow::Result<()> {
+    let mut api = mockito::Server::new_async().await;
+    let base_url = Url::parse(&api.url())?;
+    let latest = api
+        .mock("GET", "/reports/latest")
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_host_allowlist(HostAllowlist::new(["api.example.com"]))
+        .with_base_url(base_url.clone())?;
+    let response = client
+        .execute_tool_call(&get_latest_report(&base_url)?, &json!({}))
+        .await?;
+    assert_eq!(response.status_code, 200);
+
+    latest.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_requests_to_other_hosts_are_refused() -> anyhow::Result<()> {
+    let mut api = mockito::Server::new_async().await;
+    let base_url = Url::parse(&api.url())?;
+    let never = api
+        .mock("GET", "/reports/latest")
+        .expect(0)
+        .create_async()
+        .await;
+
+    // An absolute path stands for a URL taken from the spec
+    let mut tool = get_latest_report(&base_url)?;
+    tool.path = format!("{}/reports/latest", localhost(&api));
+    let client = HttpClient::new().with_host_allowlist(HostAllowlist::new(["127.0.0.1"]));
+    match client.execute_tool_call(&tool, &json!({})).await {
+        Err(ToolCallError::Execution(ToolCallExecutionError::HostNotAllowed { host })) => {
+            assert_eq!(host, "localhost");
+        }
+        other => panic!("expected a host-not-allowed error, got {other:?}"),
+    }
+
+    never.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_redirects_to_other_hosts_are_not_followed() -> anyhow::Result<()> {
+    let mut api = mockito::Server::new_async().await;
+    let mut other = mockito::Server::new_async().await;
+    let base_url = Url::parse(&api.url())?;
+    let tool = get_latest_report(&base_url)?;
+
+    let redirect = api
+        .mock("GET", "/reports/latest")
+        .with_status(302)
+        .with_header("location", &format!("{}/exfiltrate", localhost(&other)))
+        .expect(2)
+        .create_async()
+        .await;
+    let never = other
+        .mock("GET", "/exfiltrate")
+        .expect(0)
+        .create_async()
+        .await;
+
+    for policy in [
+        RedirectPolicy::default(),
+        RedirectPolicy::builder()
+            .forward_credentials_cross_origin(true)
+            .build(),
+    ] {
+        let client = HttpClient::new()
+            .with_base_url(base_url.clone())?
+            .with_redirect_policy(policy)
+            .with_host_allowlist(HostAllowlist::default());
+        let response = client.execute_tool_call(&tool, &json!({})).await?;
+        assert_eq!(response.status_code, 302);
+    }
+
+    redirect.assert_async().await;
+    never.assert_async().await;
+    Ok(())
+}
+
+#[test]
+fn test_token_url_must_be_allowed() -> anyhow::Result<()> {
+    let mut spec = spec();
+    spec["components"] = json!({
+        "securitySchemes": {
+            "oauth": {
+                "type": "oauth2",
+                "flows": {
+                    "clientCredentials": {
+                        "tokenUrl": "https://attacker.example.net/token",
+                        "scopes": {}
+                    }
+                }
+            }
+        }
+    });
+    let mut server = Server::builder()
+        .openapi_spec(spec)
+        .base_url(Url::parse("https://api.example.com")?)
+        .allowed_hosts(HostAllowlist::default())
+        .client_credentials(
+            ClientCredentialsConfig::builder()
+                .client_id("agent".to_string())
+                .client_secret("s3cret".to_string())
+                .build(),
+        )
+        .build();
+
+    let error = server.load_openapi_spec().unwrap_err();
+    assert!(
+        error.to_string().contains("attacker.example.net"),
+        "{error}"
+    );
+    Ok(())
+}
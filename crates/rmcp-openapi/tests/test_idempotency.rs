@@ -0,0 +1,186 @@
+//! Integration tests for idempotency key injection.
+
+use mockito::Matcher;
+use rmcp_openapi::{HttpClient, IdempotencyConfig, RetryPolicy, Server, ToolMetadata};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+const UUID_PATTERN: &str = "^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$";
+
+fn tools(base_url: &Url) -> anyhow::Result<(ToolMetadata, ToolMetadata, ToolMetadata)> {
+    let mut server = Server::builder()
+        .openapi_spec(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Payments", "version": "1.0.0" },
+            "paths": {
+                "/payments": {
+                    "get": {
+                        "operationId": "listPayments",
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "post": {
+                        "operationId": "createPayment",
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": { "schema": { "type": "object" } }
+                            }
+                        },
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                },
+                "/refunds": {
+                    "post": {
+                        "operationId": "createRefund",
+                        "parameters": [{
+                            "name": "Idempotency-Key",
+                            "in": "header",
+                            "schema": { "type": "string" }
+                        }],
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                }
+            }
+        }))
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+    Ok((
+        server.get_tool_metadata("listPayments").unwrap().clone(),
+        server.get_tool_metadata("createPayment").unwrap().clone(),
+        server.get_tool_metadata("createRefund").unwrap().clone(),
+    ))
+}
+
+#[actix_web::test]
+async fn test_post_calls_get_a_fresh_key() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (list_payments, create_payment, _) = tools(&base_url)?;
+
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let recorded = keys.clone();
+    let create = mock_server
+        .mock("POST", "/payments")
+        .match_header("idempotency-key", Matcher::Regex(UUID_PATTERN.to_string()))
+        .match_request(move |request| {
+            let key = request.header("idempotency-key")[0]
+                .to_str()
+                .unwrap()
+                .to_string();
+            recorded.lock().unwrap().push(key);
+            true
+        })
+        .with_status(201)
+        .expect(2)
+        .create_async()
+        .await;
+    let list = mock_server
+        .mock("GET", "/payments")
+        .match_header("idempotency-key", Matcher::Missing)
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_idempotency_keys(IdempotencyConfig::default());
+    for _ in 0..2 {
+        client
+            .execute_tool_call(
+                &create_payment,
+                &json!({ "request_body": { "amount": 10 } }),
+            )
+            .await?;
+    }
+    client.execute_tool_call(&list_payments, &json!({})).await?;
+
+    let keys = keys.lock().unwrap().clone();
+    assert_eq!(keys.len(), 2);
+    assert_ne!(keys[0], keys[1]);
+
+    create.assert_async().await;
+    list.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_retries_reuse_the_key() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (_, create_payment, _) = tools(&base_url)?;
+
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let recorded = keys.clone();
+    let unavailable = mock_server
+        .mock("POST", "/payments")
+        .match_request(move |request| {
+            let key = request.header("idempotency-key")[0]
+                .to_str()
+                .unwrap()
+                .to_string();
+            recorded.lock().unwrap().push(key);
+            true
+        })
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+
+    // POST is not retried by default, but keyed calls are safe to repeat
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_retry_policy(
+            RetryPolicy::builder()
+                .max_retries(1)
+                .initial_backoff(Duration::from_millis(1))
+                .jitter(false)
+                .build(),
+        )
+        .with_idempotency_keys(IdempotencyConfig::default());
+    let response = client
+        .execute_tool_call(
+            &create_payment,
+            &json!({ "request_body": { "amount": 10 } }),
+        )
+        .await?;
+    assert_eq!(response.status_code, 503);
+
+    let keys = keys.lock().unwrap().clone();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0], keys[1]);
+
+    unavailable.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_caller_supplied_key_is_kept() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (_, _, create_refund) = tools(&base_url)?;
+
+    let refund = mock_server
+        .mock("POST", "/refunds")
+        .match_header("idempotency-key", "refund-42")
+        .with_status(201)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_idempotency_keys(IdempotencyConfig::default());
+    client
+        .execute_tool_call(
+            &create_refund,
+            &json!({ "header_Idempotency-Key": "refund-42" }),
+        )
+        .await?;
+
+    refund.assert_async().await;
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use rmcp_openapi::{HttpClient, Tool, ToolMetadata, config::Authorization};
+use rmcp_openapi::{
+    BLOB_RESOURCE_PREFIX, BlobStore, BlobStoreConfig, HttpClient, Tool, ToolMetadata,
+    config::Authorization,
+};
 use serde_json::json;
 
 mod common;
@@ -88,6 +91,7 @@ fn create_image_tool(mock_server: &MockImageServer, path: &str) -> anyhow::Resul
         output_schema: None,
         method: "GET".to_string(),
         path: path.to_string(),
+        tags: vec![],
         security: None,
         parameter_mappings: std::collections::HashMap::new(),
     };
@@ -106,6 +110,7 @@ fn create_text_tool(mock_server: &MockImageServer, path: &str) -> anyhow::Result
         output_schema: None,
         method: "GET".to_string(),
         path: path.to_string(),
+        tags: vec![],
         security: None,
         parameter_mappings: std::collections::HashMap::new(),
     };
@@ -124,6 +129,7 @@ fn create_json_tool(mock_server: &MockImageServer, path: &str) -> anyhow::Result
         output_schema: None,
         method: "GET".to_string(),
         path: path.to_string(),
+        tags: vec![],
         security: None,
         parameter_mappings: std::collections::HashMap::new(),
     };
@@ -646,3 +652,74 @@ async fn test_empty_image_response() {
     assert!(call_result.structured_content.is_none());
     assert_eq!(call_result.is_error, Some(false));
 }
+
+// ============================================================================
+// Audio and Video Tests
+// ============================================================================
+
+/// Test audio responses are returned as audio content
+#[actix_web::test]
+async fn test_audio_response() {
+    let mut mock_server = MockImageServer::new_with_port(9213).await;
+
+    let audio_bytes = b"ID3\x04\x00\x00\x00\x00\x00\x00".to_vec();
+    let _mock = mock_server.mock_image_endpoint("/sound.mp3", "audio/mpeg", &audio_bytes);
+
+    let tool = create_image_tool(&mock_server, "/sound.mp3").expect("Failed to create tool");
+    let call_result = tool
+        .call(&json!({}), Authorization::None, None)
+        .await
+        .expect("Tool call should succeed");
+
+    assert_eq!(call_result.content.len(), 1);
+    use rmcp::model::RawContent;
+    match &call_result.content[0].raw {
+        RawContent::Audio(audio) => {
+            use base64::{Engine as _, engine::general_purpose::STANDARD};
+            let decoded = STANDARD
+                .decode(&audio.data)
+                .expect("Should be valid base64");
+            assert_eq!(decoded, audio_bytes);
+            assert_eq!(&audio.mime_type, "audio/mpeg");
+        }
+        _ => panic!("Expected Audio content, got: {:?}", call_result.content[0]),
+    }
+    assert!(call_result.structured_content.is_none());
+    assert_eq!(call_result.is_error, Some(false));
+}
+
+/// Test video responses are returned as resource links to the stored payload
+#[actix_web::test]
+async fn test_video_response_as_resource_link() {
+    let mut mock_server = MockImageServer::new_with_port(9214).await;
+
+    let video_bytes = b"\x00\x00\x00\x18ftypmp42".to_vec();
+    let _mock = mock_server.mock_image_endpoint("/clip.mp4", "video/mp4", &video_bytes);
+
+    let metadata = create_image_tool(&mock_server, "/clip.mp4")
+        .expect("Failed to create tool")
+        .metadata;
+    let http_client = HttpClient::new()
+        .with_base_url(mock_server.base_url())
+        .unwrap()
+        .with_blob_store(BlobStore::new(BlobStoreConfig::builder().build()));
+    let tool = Tool::new(metadata, http_client).unwrap();
+    let call_result = tool
+        .call(&json!({}), Authorization::None, None)
+        .await
+        .expect("Tool call should succeed");
+
+    use rmcp::model::RawContent;
+    match &call_result.content[0].raw {
+        RawContent::ResourceLink(resource) => {
+            assert!(resource.uri.starts_with(BLOB_RESOURCE_PREFIX));
+            assert_eq!(resource.mime_type.as_deref(), Some("video/mp4"));
+            assert_eq!(resource.size, Some(video_bytes.len() as u32));
+        }
+        _ => panic!(
+            "Expected ResourceLink content, got: {:?}",
+            call_result.content[0]
+        ),
+    }
+    assert_eq!(call_result.is_error, Some(false));
+}
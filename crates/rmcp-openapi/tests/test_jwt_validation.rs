@@ -0,0 +1,134 @@
+//! Integration tests for validating JWTs presented to the MCP endpoint.
+
+use base64::prelude::*;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use rmcp_openapi::{Error, JwtValidationConfig, JwtValidator};
+use serde_json::{Value, json};
+use std::time::{SystemTime, UNIX_EPOCH};
+use url::Url;
+
+struct SigningKey {
+    kid: String,
+    pkcs8: Vec<u8>,
+    public: Vec<u8>,
+}
+
+impl SigningKey {
+    fn generate(kid: &str) -> Self {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let public = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())
+            .unwrap()
+            .public_key()
+            .as_ref()
+            .to_vec();
+        Self {
+            kid: kid.to_string(),
+            pkcs8: pkcs8.as_ref().to_vec(),
+            public,
+        }
+    }
+
+    fn jwk(&self) -> Value {
+        json!({
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "alg": "EdDSA",
+            "kid": self.kid,
+            "x": BASE64_URL_SAFE_NO_PAD.encode(&self.public)
+        })
+    }
+
+    fn sign(&self, claims: Value) -> String {
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = Some(self.kid.clone());
+        jsonwebtoken::encode(&header, &claims, &EncodingKey::from_ed_der(&self.pkcs8)).unwrap()
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn validator(base_url: &Url) -> anyhow::Result<JwtValidator> {
+    Ok(JwtValidator::new(
+        JwtValidationConfig::builder()
+            .jwks_url(base_url.join("/.well-known/jwks.json")?)
+            .issuer("https://idp.example.com".to_string())
+            .audiences(vec!["mcp-server".to_string()])
+            .build(),
+    )?)
+}
+
+#[actix_web::test]
+async fn test_valid_token_returns_claims_and_keys_are_cached() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let key = SigningKey::generate("key-1");
+    let jwks = mock_server
+        .mock("GET", "/.well-known/jwks.json")
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "keys": [key.jwk()] }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let validator = validator(&base_url)?;
+    let token = key.sign(json!({
+        "iss": "https://idp.example.com",
+        "aud": "mcp-server",
+        "sub": "agent",
+        "exp": now() + 300
+    }));
+    for _ in 0..2 {
+        let claims = validator.validate(&token).await?;
+        assert_eq!(claims["sub"], "agent");
+    }
+
+    jwks.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_invalid_tokens_are_rejected() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let key = SigningKey::generate("key-1");
+    mock_server
+        .mock("GET", "/.well-known/jwks.json")
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "keys": [key.jwk()] }).to_string())
+        .create_async()
+        .await;
+
+    let validator = validator(&base_url)?;
+    let valid = json!({
+        "iss": "https://idp.example.com",
+        "aud": "mcp-server",
+        "exp": now() + 300
+    });
+    let mut expired = valid.clone();
+    expired["exp"] = json!(now() - 600);
+    let mut other_audience = valid.clone();
+    other_audience["aud"] = json!("another-server");
+    let mut other_issuer = valid.clone();
+    other_issuer["iss"] = json!("https://evil.example.com");
+
+    let tokens = [
+        key.sign(expired),
+        key.sign(other_audience),
+        key.sign(other_issuer),
+        SigningKey::generate("key-1").sign(valid.clone()),
+        SigningKey::generate("unknown").sign(valid),
+        "not-a-jwt".to_string(),
+    ];
+    for token in tokens {
+        let result = validator.validate(&token).await;
+        assert!(matches!(result, Err(Error::Auth(_))), "{result:?}");
+    }
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! Integration tests for request/response middleware.
+
+mod common;
+
+use async_trait::async_trait;
+use common::pets;
+use rmcp_openapi::error::ToolCallValidationError;
+use rmcp_openapi::{
+    CacheConfig, HttpClient, HttpResponse, Middleware, Server, ToolCallError, ToolMetadata,
+};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+fn get_pet(base_url: &Url) -> anyhow::Result<ToolMetadata> {
+    let mut server = Server::builder()
+        .openapi_spec(pets::spec(json!({
+            "/pets/{id}": {
+                "get": {
+                    "operationId": "getPet",
+                    "parameters": [{
+                        "name": "id",
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "integer" }
+                    }],
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        })))
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server.get_tool_metadata("getPet").unwrap().clone())
+}
+
+/// Records every hook call and stamps requests and responses with its name
+struct Recorder {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Middleware for Recorder {
+    async fn on_request(
+        &self,
+        tool: &ToolMetadata,
+        request: &mut reqwest::Request,
+    ) -> Result<(), ToolCallError> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} request {}", self.name, tool.name));
+        let header = format!("x-{}", self.name);
+        request.headers_mut().insert(
+            reqwest::header::HeaderName::from_bytes(header.as_bytes()).unwrap(),
+            "yes".parse().unwrap(),
+        );
+        Ok(())
+    }
+
+    async fn on_response(
+        &self,
+        tool: &ToolMetadata,
+        response: &mut HttpResponse,
+    ) -> Result<(), ToolCallError> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} response {}", self.name, tool.name));
+        response
+            .headers
+            .insert(format!("x-seen-by-{}", self.name), "yes".to_string());
+        Ok(())
+    }
+}
+
+#[actix_web::test]
+async fn test_middlewares_run_in_order() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = get_pet(&base_url)?;
+
+    let mock = mock_server
+        .mock("GET", "/pets/1")
+        .match_header("x-first", "yes")
+        .match_header("x-second", "yes")
+        .with_status(200)
+        .with_body(r#"{"id":1}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_response_cache(CacheConfig::default())
+        .with_middleware(Arc::new(Recorder {
+            name: "first",
+            log: log.clone(),
+        }))
+        .with_middleware(Arc::new(Recorder {
+            name: "second",
+            log: log.clone(),
+        }));
+
+    for _ in 0..2 {
+        let response = client.execute_tool_call(&tool, &json!({ "id": 1 })).await?;
+        assert_eq!(response.headers["x-seen-by-first"], "yes");
+        assert_eq!(response.headers["x-seen-by-second"], "yes");
+    }
+
+    // The second call is a cache hit, but still goes through every hook
+    let expected: Vec<String> = [
+        "first request getPet",
+        "second request getPet",
+        "first response getPet",
+        "second response getPet",
+    ]
+    .repeat(2)
+    .into_iter()
+    .map(String::from)
+    .collect();
+    assert_eq!(*log.lock().unwrap(), expected);
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+struct DenyAll;
+
+#[async_trait]
+impl Middleware for DenyAll {
+    async fn on_request(
+        &self,
+        tool: &ToolMetadata,
+        _request: &mut reqwest::Request,
+    ) -> Result<(), ToolCallError> {
+        Err(ToolCallError::Validation(
+            ToolCallValidationError::RequestConstructionError {
+                reason: format!("{} is disabled", tool.name),
+            },
+        ))
+    }
+}
+
+#[actix_web::test]
+async fn test_middleware_errors_fail_the_call() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = get_pet(&base_url)?;
+
+    let mock = mock_server
+        .mock("GET", "/pets/1")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_middleware(Arc::new(DenyAll));
+    let error = client
+        .execute_tool_call(&tool, &json!({ "id": 1 }))
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("getPet is disabled"));
+
+    mock.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! Integration tests for mock mode.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::mock::MOCK_HEADER;
+use rmcp_openapi::{HttpClient, Server, ToolMetadata};
+use serde_json::json;
+use url::Url;
+
+fn pet_tools(base_url: &Url) -> anyhow::Result<(ToolMetadata, ToolMetadata)> {
+    let mut spec = pets::spec(json!({
+        "/pets/{petId}": {
+            "get": {
+                "operationId": "getPet",
+                "parameters": [{
+                    "name": "petId",
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "integer" }
+                }],
+                "responses": {
+                    "200": {
+                        "description": "OK",
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": "#/components/schemas/Pet" }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "/pets": {
+            "get": {
+                "operationId": "listPets",
+                "responses": { "200": { "description": "OK" } }
+            }
+        }
+    }));
+    spec["components"] = json!({
+        "schemas": {
+            "Pet": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer", "format": "int64" },
+                    "name": { "type": "string", "example": "Rex" },
+                    "status": { "type": "string", "enum": ["available", "sold"] },
+                    "tags": { "type": "array", "items": { "type": "string" } }
+                }
+            }
+        }
+    });
+    let server = pets::load(
+        Server::builder()
+            .openapi_spec(spec)
+            .base_url(base_url.clone())
+            .build(),
+    )?;
+    Ok((
+        server.get_tool_metadata("getPet").unwrap().clone(),
+        server.get_tool_metadata("listPets").unwrap().clone(),
+    ))
+}
+
+#[actix_web::test]
+async fn test_mock_responses_follow_the_response_schema() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (get_pet, _) = pet_tools(&base_url)?;
+
+    let upstream = mock_server
+        .mock("GET", mockito::Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_mock_responses(true);
+    let response = client
+        .execute_tool_call(&get_pet, &json!({ "petId": 7 }))
+        .await?;
+
+    assert!(response.is_success);
+    assert_eq!(response.status_code, 200);
+    assert_eq!(response.headers[MOCK_HEADER], "true");
+    assert!(response.request_url.ends_with("/pets/7"));
+    assert_eq!(
+        response.json()?,
+        json!({ "id": 0, "name": "Rex", "status": "available", "tags": ["string"] })
+    );
+
+    upstream.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_mock_responses_without_schema_are_empty_objects() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (_, list_pets) = pet_tools(&base_url)?;
+
+    let upstream = mock_server
+        .mock("GET", mockito::Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_mock_responses(true);
+    let response = client.execute_tool_call(&list_pets, &json!({})).await?;
+
+    assert!(response.is_success);
+    assert_eq!(response.json()?, json!({}));
+
+    upstream.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! Integration tests for presenting a client certificate to upstream APIs.
+
+mod common;
+
+use common::pets;
+use common::tls_server::{start_tls_server, tls_asset};
+use rmcp_openapi::{ClientCertificate, Error, HttpClient};
+use serde_json::json;
+
+fn client_certificate() -> ClientCertificate {
+    ClientCertificate::builder()
+        .cert_path(tls_asset("client.pem"))
+        .key_path(tls_asset("client.key"))
+        .build()
+}
+
+#[actix_web::test]
+async fn test_client_certificate_is_presented() -> anyhow::Result<()> {
+    let base_url = start_tls_server(true).await;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    // The test server's certificate comes from a private CA
+    let client = HttpClient::new()
+        .with_insecure(true)
+        .with_base_url(base_url.clone())?
+        .with_client_certificate(&client_certificate())?;
+    let response = client.execute_tool_call(&tool, &json!({})).await?;
+    assert_eq!(response.status_code, 200);
+
+    // Without a certificate the handshake is refused
+    let client = HttpClient::new()
+        .with_insecure(true)
+        .with_base_url(base_url.clone())?;
+    assert!(client.execute_tool_call(&tool, &json!({})).await.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_client_certificate_is_rejected() {
+    let missing = ClientCertificate::builder()
+        .cert_path(tls_asset("missing.pem"))
+        .key_path(tls_asset("client.key"))
+        .build();
+    assert!(matches!(
+        HttpClient::new().with_client_certificate(&missing),
+        Err(Error::Tls(_))
+    ));
+
+    // A CA certificate without a key is not an identity
+    let no_key = ClientCertificate::builder()
+        .cert_path(tls_asset("client.pem"))
+        .key_path(tls_asset("ca.pem"))
+        .build();
+    assert!(matches!(
+        HttpClient::new().with_client_certificate(&no_key),
+        Err(Error::Tls(_))
+    ));
+}
@@ -0,0 +1,228 @@
+//! Integration tests for the OAuth2 client-credentials grant.
+
+mod common;
+
+use common::pets;
+use mockito::Matcher;
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{ClientCredentials, ClientCredentialsConfig, HttpClient, ToolCallError};
+use serde_json::json;
+use std::sync::Arc;
+use url::Url;
+
+fn credentials(base_url: &Url) -> anyhow::Result<Arc<ClientCredentials>> {
+    let config = ClientCredentialsConfig::builder()
+        .token_url(base_url.join("/oauth/token")?)
+        .client_id("id".to_string())
+        .client_secret("secret".to_string())
+        .scopes(vec!["pets:read".to_string()])
+        .build();
+    Ok(Arc::new(ClientCredentials::new(config)?))
+}
+
+#[actix_web::test]
+async fn test_access_token_is_requested_once_and_attached() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    let token = mock_server
+        .mock("POST", "/oauth/token")
+        // base64("id:secret")
+        .match_header("authorization", "Basic aWQ6c2VjcmV0")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("grant_type".into(), "client_credentials".into()),
+            Matcher::UrlEncoded("scope".into(), "pets:read".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"access_token":"tok-1","token_type":"Bearer","expires_in":3600}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .match_header("authorization", "Bearer tok-1")
+        .with_header("content-type", "application/json")
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_client_credentials(credentials(&base_url)?);
+    for _ in 0..2 {
+        let response = client.execute_tool_call(&tool, &json!({})).await?;
+        assert!(response.is_success);
+    }
+
+    token.assert_async().await;
+    pets.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_existing_authorization_is_kept() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    let token = mock_server
+        .mock("POST", "/oauth/token")
+        .expect(0)
+        .create_async()
+        .await;
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .match_header("authorization", "Bearer user-token")
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_client_credentials(credentials(&base_url)?)
+        .with_authorization("Bearer user-token");
+    client.execute_tool_call(&tool, &json!({})).await?;
+
+    token.assert_async().await;
+    pets.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_token_endpoint_errors_fail_the_call() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    mock_server
+        .mock("POST", "/oauth/token")
+        .with_status(401)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"error":"invalid_client","error_description":"Unknown client"}"#)
+        .create_async()
+        .await;
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_client_credentials(credentials(&base_url)?);
+    let result = client.execute_tool_call(&tool, &json!({})).await;
+
+    match result {
+        Err(ToolCallError::Execution(ToolCallExecutionError::AuthenticationFailed {
+            scheme,
+            reason,
+        })) => {
+            assert_eq!(scheme, "oauth2");
+            assert!(reason.contains("invalid_client"), "{reason}");
+            assert!(reason.contains("Unknown client"), "{reason}");
+        }
+        other => panic!("expected an authentication error, got {other:?}"),
+    }
+
+    pets.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_rejected_token_is_refreshed_and_the_call_retried() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    let grant = mock_server
+        .mock("POST", "/oauth/token")
+        .match_body(Matcher::UrlEncoded(
+            "grant_type".into(),
+            "client_credentials".into(),
+        ))
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"access_token":"tok-1","expires_in":3600,"refresh_token":"ref-1"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let refresh = mock_server
+        .mock("POST", "/oauth/token")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("grant_type".into(), "refresh_token".into()),
+            Matcher::UrlEncoded("refresh_token".into(), "ref-1".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"access_token":"tok-2","expires_in":3600}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let revoked = mock_server
+        .mock("GET", "/pets")
+        .match_header("authorization", "Bearer tok-1")
+        .with_status(401)
+        .with_header("www-authenticate", r#"Bearer error="invalid_token""#)
+        .expect(1)
+        .create_async()
+        .await;
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .match_header("authorization", "Bearer tok-2")
+        .with_header("content-type", "application/json")
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_client_credentials(credentials(&base_url)?);
+    let response = client.execute_tool_call(&tool, &json!({})).await?;
+    assert!(response.is_success);
+
+    grant.assert_async().await;
+    refresh.assert_async().await;
+    revoked.assert_async().await;
+    pets.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_renewed_token_rejected_fails_the_call() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    let token = mock_server
+        .mock("POST", "/oauth/token")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"access_token":"tok","expires_in":3600}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .with_status(401)
+        .with_header("www-authenticate", r#"Bearer error="invalid_token""#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_client_credentials(credentials(&base_url)?);
+    let result = client.execute_tool_call(&tool, &json!({})).await;
+
+    match result {
+        Err(ToolCallError::Execution(ToolCallExecutionError::AuthenticationFailed {
+            reason,
+            ..
+        })) => assert!(reason.contains("invalid_token"), "{reason}"),
+        other => panic!("expected an authentication error, got {other:?}"),
+    }
+
+    token.assert_async().await;
+    pets.assert_async().await;
+    Ok(())
+}
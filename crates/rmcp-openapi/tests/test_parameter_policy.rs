@@ -0,0 +1,95 @@
+//! Integration tests for operator constraints on parameter values.
+
+use rmcp_openapi::error::ToolCallValidationError;
+use rmcp_openapi::{Authorization, ParameterPolicy, Server, ToolCallError};
+use serde_json::json;
+use url::Url;
+
+fn server(base_url: &Url) -> anyhow::Result<Server> {
+    let policy: ParameterPolicy = serde_json::from_value(json!({
+        "constraints": [
+            { "parameter": "project_id", "schema": { "const": 42 } },
+            { "tool": "listIssues", "parameter": "per_page", "schema": { "maximum": 100 } }
+        ]
+    }))?;
+    let mut server = Server::builder()
+        .openapi_spec(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Issues", "version": "1.0.0" },
+            "paths": {
+                "/projects/{project_id}/issues": {
+                    "get": {
+                        "operationId": "listIssues",
+                        "parameters": [
+                            {
+                                "name": "project_id",
+                                "in": "path",
+                                "required": true,
+                                "schema": { "type": "integer" }
+                            },
+                            {
+                                "name": "per_page",
+                                "in": "query",
+                                "schema": { "type": "integer", "maximum": 250 }
+                            }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        }))
+        .base_url(base_url.clone())
+        .parameter_policy(policy)
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server)
+}
+
+#[test]
+fn test_constraints_are_advertised() -> anyhow::Result<()> {
+    let server = server(&Url::parse("http://127.0.0.1:9")?)?;
+    let properties = &server.get_tool_metadata("listIssues").unwrap().parameters["properties"];
+    assert_eq!(properties["project_id"]["const"], json!(42));
+    assert_eq!(properties["per_page"]["allOf"], json!([{ "maximum": 100 }]));
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_calls_breaking_constraints_are_refused() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let server = server(&Url::parse(&mock_server.url())?)?;
+    let allowed = mock_server
+        .mock("GET", "/projects/42/issues")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "per_page".into(),
+            "100".into(),
+        ))
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+    let tool = server.get_tool("listIssues").unwrap();
+
+    for arguments in [
+        json!({ "project_id": 7 }),
+        json!({ "project_id": 42, "per_page": 200 }),
+    ] {
+        match tool.call(&arguments, Authorization::None, None).await {
+            Err(ToolCallError::Validation(ToolCallValidationError::InvalidParameters {
+                violations,
+            })) => assert_eq!(violations.len(), 1, "{arguments}"),
+            other => panic!("expected a validation error for {arguments}, got {other:?}"),
+        }
+    }
+
+    tool.call(
+        &json!({ "project_id": 42, "per_page": 100 }),
+        Authorization::None,
+        None,
+    )
+    .await?;
+
+    allowed.assert_async().await;
+    Ok(())
+}
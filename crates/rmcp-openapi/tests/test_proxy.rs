@@ -0,0 +1,75 @@
+//! Integration tests for sending upstream requests through an HTTP proxy.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::{HttpClient, ProxyConfig};
+use serde_json::json;
+use url::Url;
+
+#[actix_web::test]
+async fn test_requests_are_sent_through_proxy() -> anyhow::Result<()> {
+    let mut proxy_server = mockito::Server::new_async().await;
+    // A proxied plain-HTTP request carries the absolute target URL
+    let mock = proxy_server
+        .mock(
+            "GET",
+            mockito::Matcher::Regex(r"^(http://api\.example\.invalid)?/pets$".to_string()),
+        )
+        .match_header("host", "api.example.invalid")
+        .match_header("proxy-authorization", "Basic dXNlcjpzZWNyZXQ=")
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let base_url = Url::parse("http://api.example.invalid")?;
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_proxy(
+            &ProxyConfig::builder()
+                .url(Url::parse(&proxy_server.url())?)
+                .no_proxy(String::new())
+                .username("user".to_string())
+                .password("secret".to_string())
+                .build(),
+        )?;
+
+    let response = client
+        .execute_tool_call(&pets::list_pets_tool(&base_url)?, &json!({}))
+        .await?;
+    assert_eq!(response.status_code, 200);
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_no_proxy_hosts_are_reached_directly() -> anyhow::Result<()> {
+    let mut upstream = mockito::Server::new_async().await;
+    let mock = upstream
+        .mock("GET", "/pets")
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let base_url = Url::parse(&upstream.url())?;
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_proxy(
+            &ProxyConfig::builder()
+                // Nothing listens here; the request must not go through it
+                .url(Url::parse("http://127.0.0.1:9")?)
+                .no_proxy(base_url.host_str().unwrap().to_string())
+                .build(),
+        )?;
+
+    let response = client
+        .execute_tool_call(&pets::list_pets_tool(&base_url)?, &json!({}))
+        .await?;
+    assert_eq!(response.status_code, 200);
+
+    mock.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,92 @@
+//! Integration tests for client-side rate limiting.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{HttpClient, RateLimitConfig, RateLimitMode, ToolCallError};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use url::Url;
+
+#[actix_web::test]
+async fn test_reject_mode_fails_calls_over_the_limit() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .with_status(200)
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_rate_limit(
+            RateLimitConfig::builder()
+                .per_tool(HashMap::from([(
+                    "listPets".to_string(),
+                    "2/m".parse().unwrap(),
+                )]))
+                .mode(RateLimitMode::Reject)
+                .build(),
+        );
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    for _ in 0..2 {
+        let response = client.execute_tool_call(&tool, &json!({})).await?;
+        assert_eq!(response.status_code, 200);
+    }
+
+    // Clones (one per tool) share the buckets
+    match client.clone().execute_tool_call(&tool, &json!({})).await {
+        Err(ToolCallError::Execution(ToolCallExecutionError::RateLimitExceeded {
+            scope,
+            retry_after_ms,
+        })) => {
+            assert_eq!(scope, "listPets");
+            assert!(retry_after_ms > 0 && retry_after_ms <= 30_000);
+        }
+        other => panic!("expected rate-limit-exceeded error, got {other:?}"),
+    }
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_queue_mode_delays_calls_over_the_limit() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .with_status(200)
+        .with_body("[]")
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_rate_limit(
+            RateLimitConfig::builder()
+                .global("2/s".parse().unwrap())
+                .build(),
+        );
+    let tool = pets::list_pets_tool(&base_url)?;
+
+    // The first two calls use the burst, the third waits for a refill
+    let start = Instant::now();
+    for _ in 0..3 {
+        let response = client.execute_tool_call(&tool, &json!({})).await?;
+        assert_eq!(response.status_code, 200);
+    }
+    assert!(start.elapsed() >= Duration::from_millis(400));
+
+    mock.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Integration tests for read-only mode.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{HttpClient, Server, ToolCallError, ToolMetadata};
+use serde_json::json;
+use url::Url;
+
+fn server(base_url: &Url, read_only: bool) -> anyhow::Result<Server> {
+    pets::load(
+        Server::builder()
+            .openapi_spec(pets::spec(json!({
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "head": {
+                        "operationId": "checkPets",
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "post": {
+                        "operationId": "addPet",
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                },
+                "/pets/{petId}": {
+                    "delete": {
+                        "operationId": "deletePet",
+                        "parameters": [{
+                            "name": "petId",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "integer" }
+                        }],
+                        "responses": { "204": { "description": "Deleted" } }
+                    }
+                }
+            })))
+            .base_url(base_url.clone())
+            .read_only(read_only)
+            .build(),
+    )
+}
+
+#[test]
+fn test_only_read_operations_are_registered() -> anyhow::Result<()> {
+    let base_url = Url::parse("http://127.0.0.1:9")?;
+
+    let mut names = server(&base_url, true)?.get_tool_names();
+    names.sort();
+    assert_eq!(names, vec!["checkPets", "listPets"]);
+
+    assert_eq!(server(&base_url, false)?.tool_count(), 4);
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_client_refuses_writes() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let add_pet: ToolMetadata = server(&base_url, false)?
+        .get_tool_metadata("addPet")
+        .unwrap()
+        .clone();
+    let post = mock_server
+        .mock("POST", "/pets")
+        .expect(0)
+        .create_async()
+        .await;
+    let list = mock_server
+        .mock("GET", "/pets")
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_read_only(true);
+    let result = client.execute_tool_call(&add_pet, &json!({})).await;
+    match result {
+        Err(ToolCallError::Execution(ToolCallExecutionError::ReadOnly { tool, method })) => {
+            assert_eq!(tool, "addPet");
+            assert_eq!(method, "POST");
+        }
+        other => panic!("expected a read-only error, got {other:?}"),
+    }
+
+    let list_pets = server(&base_url, true)?
+        .get_tool_metadata("listPets")
+        .unwrap()
+        .clone();
+    client.execute_tool_call(&list_pets, &json!({})).await?;
+
+    post.assert_async().await;
+    list.assert_async().await;
+    Ok(())
+}
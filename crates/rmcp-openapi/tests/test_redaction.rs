@@ -0,0 +1,112 @@
+//! Integration tests for redaction of error results.
+
+use rmcp_openapi::{HttpClient, RedactionRules, Server, ToolMetadata};
+use serde_json::json;
+use url::Url;
+
+fn create_user_tool(base_url: &Url) -> anyhow::Result<ToolMetadata> {
+    let mut server = Server::builder()
+        .openapi_spec(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Users", "version": "1.0.0" },
+            "paths": {
+                "/users": {
+                    "post": {
+                        "operationId": "createUser",
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "object",
+                                        "properties": {
+                                            "name": { "type": "string" },
+                                            "password": { "type": "string" }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                }
+            }
+        }))
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server.get_tool_metadata("createUser").unwrap().clone())
+}
+
+fn rules() -> RedactionRules {
+    RedactionRules::builder()
+        .headers(vec!["Set-Cookie".to_string()])
+        .body_paths(vec!["$..password".parse().unwrap()])
+        .build()
+}
+
+#[actix_web::test]
+async fn test_error_results_are_redacted() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = create_user_tool(&base_url)?;
+
+    let mock = mock_server
+        .mock("POST", "/users")
+        .with_status(422)
+        .with_header("content-type", "application/json")
+        .with_header("set-cookie", "session=s3cr3t-cookie")
+        .with_body(r#"{"error":"weak password","rejected":{"password":"hunter2"}}"#)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_redaction(rules());
+    let response = client
+        .execute_tool_call(
+            &tool,
+            &json!({ "request_body": { "name": "alice", "password": "hunter2" } }),
+        )
+        .await?;
+
+    let content = response.to_mcp_content();
+    assert!(!content.contains("hunter2"), "{content}");
+    assert!(!content.contains("s3cr3t-cookie"), "{content}");
+    assert!(content.contains("weak password"));
+    assert!(content.contains("alice"));
+    assert_eq!(response.json()?["rejected"]["password"], "<redacted>");
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_successful_results_are_not_redacted() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = create_user_tool(&base_url)?;
+
+    let mock = mock_server
+        .mock("POST", "/users")
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"name":"alice","password":"hunter2"}"#)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_redaction(rules());
+    let response = client
+        .execute_tool_call(
+            &tool,
+            &json!({ "request_body": { "name": "alice", "password": "hunter2" } }),
+        )
+        .await?;
+
+    assert_eq!(response.json()?["password"], "hunter2");
+
+    mock.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,220 @@
+//! Integration tests for the upstream redirect policy.
+
+use mockito::Matcher;
+use rmcp_openapi::{HttpClient, RedirectMode, RedirectPolicy, Server, ToolMetadata};
+use serde_json::json;
+use url::Url;
+
+fn tools(base_url: &Url) -> anyhow::Result<(ToolMetadata, ToolMetadata)> {
+    let mut server = Server::builder()
+        .openapi_spec(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Reports", "version": "1.0.0" },
+            "paths": {
+                "/reports/latest": {
+                    "get": {
+                        "operationId": "getLatestReport",
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                },
+                "/reports": {
+                    "post": {
+                        "operationId": "createReport",
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": { "schema": { "type": "object" } }
+                            }
+                        },
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                }
+            }
+        }))
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+    Ok((
+        server.get_tool_metadata("getLatestReport").unwrap().clone(),
+        server.get_tool_metadata("createReport").unwrap().clone(),
+    ))
+}
+
+#[actix_web::test]
+async fn test_credentials_are_dropped_across_origins_by_default() -> anyhow::Result<()> {
+    let mut api = mockito::Server::new_async().await;
+    let mut storage = mockito::Server::new_async().await;
+    let base_url = Url::parse(&api.url())?;
+    let (get_latest_report, _) = tools(&base_url)?;
+
+    let redirect = api
+        .mock("GET", "/reports/latest")
+        .with_status(302)
+        .with_header("location", &format!("{}/signed/report-1", storage.url()))
+        .expect(1)
+        .create_async()
+        .await;
+    let signed = storage
+        .mock("GET", "/signed/report-1")
+        .match_header("authorization", Matcher::Missing)
+        .with_status(200)
+        .with_body("report")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_authorization("Bearer secret");
+    let response = client
+        .execute_tool_call(&get_latest_report, &json!({}))
+        .await?;
+    assert_eq!(response.status_code, 200);
+    assert_eq!(response.body, "report");
+
+    redirect.assert_async().await;
+    signed.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_redirects_can_be_disabled() -> anyhow::Result<()> {
+    let mut api = mockito::Server::new_async().await;
+    let base_url = Url::parse(&api.url())?;
+    let (get_latest_report, _) = tools(&base_url)?;
+
+    let redirect = api
+        .mock("GET", "/reports/latest")
+        .with_status(302)
+        .with_header("location", "/reports/1")
+        .expect(1)
+        .create_async()
+        .await;
+    let target = api.mock("GET", "/reports/1").expect(0).create_async().await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_redirect_policy(RedirectPolicy::builder().mode(RedirectMode::None).build());
+    let response = client
+        .execute_tool_call(&get_latest_report, &json!({}))
+        .await?;
+    assert_eq!(response.status_code, 302);
+    assert_eq!(response.headers["location"], "/reports/1");
+    assert_eq!(
+        response.redirect_location(),
+        Some(format!("{}/reports/1", api.url()))
+    );
+
+    redirect.assert_async().await;
+    target.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_credentials_can_be_forwarded_across_origins() -> anyhow::Result<()> {
+    let mut api = mockito::Server::new_async().await;
+    let mut storage = mockito::Server::new_async().await;
+    let base_url = Url::parse(&api.url())?;
+    let (get_latest_report, create_report) = tools(&base_url)?;
+
+    let redirect = api
+        .mock("GET", "/reports/latest")
+        .with_status(302)
+        .with_header("location", &format!("{}/signed/report-1", storage.url()))
+        .expect(1)
+        .create_async()
+        .await;
+    let signed = storage
+        .mock("GET", "/signed/report-1")
+        .match_header("authorization", "Bearer secret")
+        .with_status(200)
+        .with_body("report")
+        .expect(1)
+        .create_async()
+        .await;
+    // 307 replays the request, body included
+    let temporary = api
+        .mock("POST", "/reports")
+        .with_status(307)
+        .with_header("location", &format!("{}/reports", storage.url()))
+        .expect(1)
+        .create_async()
+        .await;
+    let replayed = storage
+        .mock("POST", "/reports")
+        .match_header("authorization", "Bearer secret")
+        .match_body(Matcher::Json(json!({ "title": "Q3" })))
+        .with_status(201)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_redirect_policy(
+            RedirectPolicy::builder()
+                .forward_credentials_cross_origin(true)
+                .build(),
+        )
+        .with_authorization("Bearer secret");
+
+    let response = client
+        .execute_tool_call(&get_latest_report, &json!({}))
+        .await?;
+    assert_eq!(response.body, "report");
+    let response = client
+        .execute_tool_call(
+            &create_report,
+            &json!({ "request_body": { "title": "Q3" } }),
+        )
+        .await?;
+    assert_eq!(response.status_code, 201);
+
+    redirect.assert_async().await;
+    signed.assert_async().await;
+    temporary.assert_async().await;
+    replayed.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_forwarding_respects_the_redirect_limit() -> anyhow::Result<()> {
+    let mut api = mockito::Server::new_async().await;
+    let base_url = Url::parse(&api.url())?;
+    let (get_latest_report, _) = tools(&base_url)?;
+
+    let first = api
+        .mock("GET", "/reports/latest")
+        .with_status(302)
+        .with_header("location", "/reports/1")
+        .expect(1)
+        .create_async()
+        .await;
+    let second = api
+        .mock("GET", "/reports/1")
+        .with_status(302)
+        .with_header("location", "/reports/2")
+        .expect(1)
+        .create_async()
+        .await;
+    let final_target = api.mock("GET", "/reports/2").expect(0).create_async().await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_redirect_policy(
+            RedirectPolicy::builder()
+                .mode(RedirectMode::Limited(1))
+                .forward_credentials_cross_origin(true)
+                .build(),
+        );
+    let response = client
+        .execute_tool_call(&get_latest_report, &json!({}))
+        .await?;
+    assert_eq!(response.status_code, 302);
+    assert_eq!(response.headers["location"], "/reports/2");
+
+    first.assert_async().await;
+    second.assert_async().await;
+    final_target.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Integration tests for response transformers registered by tool or tag.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::{ResponseTransformer, Server};
+use serde_json::{Value, json};
+use url::Url;
+
+/// Transformer marking the output schemas it transforms
+struct Mark(&'static str);
+
+impl ResponseTransformer for Mark {
+    fn transform_response(&self, response: Value) -> Value {
+        response
+    }
+
+    fn transform_schema(&self, mut schema: Value) -> Value {
+        schema["x-transformer"] = json!(self.0);
+        schema
+    }
+}
+
+fn operation(operation_id: &str, tag: &str) -> Value {
+    json!({
+        "operationId": operation_id,
+        "tags": [tag],
+        "responses": {
+            "200": {
+                "description": "OK",
+                "content": { "application/json": { "schema": { "type": "object" } } }
+            }
+        }
+    })
+}
+
+#[test]
+fn test_tool_name_wins_over_tags() -> anyhow::Result<()> {
+    let mut server = Server::builder()
+        .openapi_spec(pets::spec(json!({
+            "/pets": { "get": operation("listPets", "PetStore") },
+            "/pets/mine": { "get": operation("getPet", "PetStore") },
+            "/orders": { "get": operation("listOrders", "store") }
+        })))
+        .base_url(Url::parse("http://127.0.0.1:9")?)
+        .build()
+        .with_transformer("pet-store", Box::new(Mark("tag")))
+        .with_transformer("getPet", Box::new(Mark("tool")));
+    server.load_openapi_spec()?;
+
+    let mark = |name: &str| {
+        server
+            .get_tool_metadata(name)
+            .unwrap()
+            .output_schema
+            .as_ref()
+            .unwrap()["x-transformer"]
+            .clone()
+    };
+    assert_eq!(mark("listPets"), json!("tag"));
+    assert_eq!(mark("getPet"), json!("tool"));
+    assert_eq!(mark("listOrders"), Value::Null);
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Integration tests for request bodies declared on methods that do not
+//! conventionally carry one (e.g. Elasticsearch's `GET /_search`).
+
+use mockito::Matcher;
+use rmcp_openapi::{HttpClient, Server};
+use serde_json::json;
+use url::Url;
+
+fn search_spec() -> serde_json::Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Search API", "version": "1.0.0" },
+        "paths": {
+            "/_search": {
+                "get": {
+                    "operationId": "search",
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": { "query": { "type": "object" } }
+                                }
+                            }
+                        }
+                    },
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        }
+    })
+}
+
+fn create_server(base_url: Url, strip_non_standard_bodies: bool) -> anyhow::Result<Server> {
+    let mut server = Server::builder()
+        .openapi_spec(search_spec())
+        .base_url(base_url)
+        .strip_non_standard_bodies(strip_non_standard_bodies)
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server)
+}
+
+#[actix_web::test]
+async fn test_get_request_sends_declared_body() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let mock = mock_server
+        .mock("GET", "/_search")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::Json(json!({"query": {"match_all": {}}})))
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let server = create_server(base_url.clone(), false)?;
+    let client = HttpClient::new().with_base_url(base_url)?;
+    let tool_metadata = server
+        .get_tool_metadata("search")
+        .expect("search tool should be registered");
+
+    let arguments = json!({ "request_body": { "query": { "match_all": {} } } });
+    let response = client.execute_tool_call(tool_metadata, &arguments).await?;
+
+    assert!(response.is_success);
+    assert_eq!(response.request_method, "GET");
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_get_request_body_stripped_when_configured() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let mock = mock_server
+        .mock("GET", "/_search")
+        .match_header("content-type", Matcher::Missing)
+        .match_body("")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let server = create_server(base_url.clone(), true)?;
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_strip_non_standard_bodies(true);
+    let tool_metadata = server
+        .get_tool_metadata("search")
+        .expect("search tool should be registered");
+
+    let arguments = json!({ "request_body": { "query": { "match_all": {} } } });
+    let response = client.execute_tool_call(tool_metadata, &arguments).await?;
+
+    assert!(response.is_success);
+    assert!(response.request_body.is_empty());
+    mock.assert_async().await;
+
+    Ok(())
+}
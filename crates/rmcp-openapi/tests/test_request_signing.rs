@@ -0,0 +1,131 @@
+//! Integration tests for signing upstream requests.
+
+use rmcp_openapi::{
+    Error, HmacSigner, HttpClient, RequestSigner, Server, SignatureComponent, ToolCallError,
+    ToolMetadata,
+};
+use serde_json::json;
+use std::sync::Arc;
+use url::Url;
+
+fn create_order(base_url: &Url) -> anyhow::Result<ToolMetadata> {
+    let mut server = Server::builder()
+        .openapi_spec(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Orders", "version": "1.0.0" },
+            "paths": {
+                "/orders": {
+                    "post": {
+                        "operationId": "createOrder",
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": { "schema": { "type": "object" } }
+                            }
+                        },
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                }
+            }
+        }))
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server.get_tool_metadata("createOrder").unwrap().clone())
+}
+
+#[actix_web::test]
+async fn test_hmac_signature_covers_the_sent_request() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = create_order(&base_url)?;
+
+    let signer = HmacSigner::builder()
+        .secret(b"s3cret".to_vec())
+        .header("X-Hub-Signature-256")
+        .prefix("sha256=")
+        .components(vec![
+            SignatureComponent::Method,
+            SignatureComponent::Path,
+            SignatureComponent::Body,
+        ])
+        .build();
+
+    // Sign an identical request independently to know what to expect
+    let mut expected = reqwest::Client::new()
+        .post(format!("{}/orders", mock_server.url()))
+        .body(r#"{"sku":"A-1"}"#)
+        .build()?;
+    signer.sign(&mut expected)?;
+    let expected = expected.headers()["x-hub-signature-256"]
+        .to_str()?
+        .to_string();
+    assert!(expected.starts_with("sha256="));
+
+    let mock = mock_server
+        .mock("POST", "/orders")
+        .match_header("x-hub-signature-256", expected.as_str())
+        .match_header("x-timestamp", mockito::Matcher::Missing)
+        .with_status(201)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_request_signer(Arc::new(signer));
+    let response = client
+        .execute_tool_call(&tool, &json!({ "request_body": { "sku": "A-1" } }))
+        .await?;
+    assert_eq!(response.status_code, 201);
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+struct StaticSigner(Result<&'static str, &'static str>);
+
+impl RequestSigner for StaticSigner {
+    fn sign(&self, request: &mut reqwest::Request) -> Result<(), Error> {
+        let signature = self.0.map_err(|e| Error::Signing(e.to_string()))?;
+        request
+            .headers_mut()
+            .insert("x-signature", signature.parse().unwrap());
+        Ok(())
+    }
+}
+
+#[actix_web::test]
+async fn test_custom_signers_are_called() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = create_order(&base_url)?;
+
+    let mock = mock_server
+        .mock("POST", "/orders")
+        .match_header("x-signature", "custom")
+        .with_status(201)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new().with_base_url(base_url.clone())?;
+    client
+        .clone()
+        .with_request_signer(Arc::new(StaticSigner(Ok("custom"))))
+        .execute_tool_call(&tool, &json!({ "request_body": {} }))
+        .await?;
+
+    // A signing failure stops the call before it reaches the upstream
+    let result = client
+        .with_request_signer(Arc::new(StaticSigner(Err("key unavailable"))))
+        .execute_tool_call(&tool, &json!({ "request_body": {} }))
+        .await;
+    let Err(ToolCallError::Validation(error)) = result else {
+        panic!("expected a validation error, got {result:?}");
+    };
+    assert!(error.to_string().contains("key unavailable"));
+
+    mock.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,278 @@
+//! Integration tests for caching `GET` responses.
+
+mod common;
+
+use common::pets;
+use mockito::Matcher;
+use reqwest::header::{HeaderMap, HeaderValue};
+use rmcp_openapi::{Authorization, CacheConfig, CacheStatus, HttpClient, Server, ToolMetadata};
+use serde_json::json;
+use std::time::Duration;
+use url::Url;
+
+fn tools(base_url: &Url) -> anyhow::Result<(ToolMetadata, ToolMetadata)> {
+    let mut server = Server::builder()
+        .openapi_spec(pets::spec(json!({
+            "/pets/{id}": {
+                "parameters": [{
+                    "name": "id",
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "integer" }
+                }],
+                "get": {
+                    "operationId": "getPet",
+                    "responses": { "200": { "description": "OK" } }
+                },
+                "put": {
+                    "operationId": "updatePet",
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        })))
+        .base_url(base_url.clone())
+        .build();
+    server.load_openapi_spec()?;
+    Ok((
+        server.get_tool_metadata("getPet").unwrap().clone(),
+        server.get_tool_metadata("updatePet").unwrap().clone(),
+    ))
+}
+
+#[actix_web::test]
+async fn test_get_responses_are_served_from_cache() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (get_pet, update_pet) = tools(&base_url)?;
+
+    let pet_1 = mock_server
+        .mock("GET", "/pets/1")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id":1}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let pet_2 = mock_server
+        .mock("GET", "/pets/2")
+        .with_status(200)
+        .with_body(r#"{"id":2}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let update = mock_server
+        .mock("PUT", "/pets/1")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_response_cache(CacheConfig::default());
+
+    for _ in 0..3 {
+        // Clones (one per tool) share the cache
+        let response = client
+            .clone()
+            .execute_tool_call(&get_pet, &json!({ "id": 1 }))
+            .await?;
+        assert_eq!(response.body, r#"{"id":1}"#);
+    }
+    client
+        .execute_tool_call(&get_pet, &json!({ "id": 2 }))
+        .await?;
+
+    // Non-GET calls always reach the upstream
+    for _ in 0..2 {
+        client
+            .execute_tool_call(&update_pet, &json!({ "id": 1 }))
+            .await?;
+    }
+
+    pet_1.assert_async().await;
+    pet_2.assert_async().await;
+    update.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_request_id_headers_do_not_defeat_the_cache() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (get_pet, _) = tools(&base_url)?;
+
+    let pet = mock_server
+        .mock("GET", "/pets/1")
+        .match_header(
+            "x-request-id",
+            Matcher::Regex("^[0-9a-f-]{36}$".to_string()),
+        )
+        .with_status(200)
+        .with_body(r#"{"id":1}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-request-id", HeaderValue::from_static("{uuid}"));
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_default_headers(headers)
+        .with_response_cache(CacheConfig::default());
+
+    client
+        .execute_tool_call(&get_pet, &json!({ "id": 1 }))
+        .await?;
+    let response = client
+        .execute_tool_call(&get_pet, &json!({ "id": 1 }))
+        .await?;
+    assert_eq!(response.cache_status, Some(CacheStatus::Hit));
+
+    pet.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_cache_control_is_honoured() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (get_pet, _) = tools(&base_url)?;
+
+    let no_store = mock_server
+        .mock("GET", "/pets/1")
+        .with_status(200)
+        .with_header("cache-control", "no-store")
+        .with_body(r#"{"id":1}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let short_lived = mock_server
+        .mock("GET", "/pets/2")
+        .with_status(200)
+        .with_header("cache-control", "max-age=1")
+        .with_body(r#"{"id":2}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let not_found = mock_server
+        .mock("GET", "/pets/3")
+        .with_status(404)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_response_cache(CacheConfig::default());
+
+    for id in [1, 1, 2, 2, 3, 3] {
+        client
+            .execute_tool_call(&get_pet, &json!({ "id": id }))
+            .await?;
+    }
+    // max-age=1 expires
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    client
+        .execute_tool_call(&get_pet, &json!({ "id": 2 }))
+        .await?;
+
+    no_store.assert_async().await;
+    short_lived.assert_async().await;
+    not_found.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_stale_entries_are_revalidated_with_etag() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let (get_pet, _) = tools(&base_url)?;
+
+    let initial = mock_server
+        .mock("GET", "/pets/1")
+        .match_header("if-none-match", Matcher::Missing)
+        .with_status(200)
+        .with_header("cache-control", "no-cache")
+        .with_header("etag", "\"v1\"")
+        .with_body(r#"{"id":1}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let not_modified = mock_server
+        .mock("GET", "/pets/1")
+        .match_header("if-none-match", "\"v1\"")
+        .with_status(304)
+        .with_header("cache-control", "max-age=60")
+        .with_header("etag", "\"v1\"")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_response_cache(CacheConfig::default());
+
+    let response = client
+        .execute_tool_call(&get_pet, &json!({ "id": 1 }))
+        .await?;
+    assert_eq!(response.cache_status, Some(CacheStatus::Miss));
+
+    // no-cache: the entry is stored but must be revalidated
+    let response = client
+        .execute_tool_call(&get_pet, &json!({ "id": 1 }))
+        .await?;
+    assert_eq!(response.cache_status, Some(CacheStatus::Revalidated));
+    assert_eq!(response.status_code, 200);
+    assert_eq!(response.body, r#"{"id":1}"#);
+
+    // The 304 renewed the lifetime with max-age=60
+    let response = client
+        .execute_tool_call(&get_pet, &json!({ "id": 1 }))
+        .await?;
+    assert_eq!(response.cache_status, Some(CacheStatus::Hit));
+    assert_eq!(response.body, r#"{"id":1}"#);
+
+    initial.assert_async().await;
+    not_modified.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_cache_status_is_reported_in_tool_result_meta() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let mut server = Server::builder()
+        .openapi_spec(pets::spec(json!({
+            "/pets": {
+                "get": {
+                    "operationId": "listPets",
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        })))
+        .base_url(base_url)
+        .build();
+    server.response_cache = Some(CacheConfig::default());
+    server.load_openapi_spec()?;
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let tool = server.get_tool("listPets").unwrap();
+    for expected in ["miss", "hit"] {
+        let result = tool.call(&json!({}), Authorization::None, None).await?;
+        let meta = result.meta.expect("cache status in _meta");
+        assert_eq!(meta.get("cache"), Some(&json!(expected)));
+    }
+
+    mock.assert_async().await;
+    Ok(())
+}
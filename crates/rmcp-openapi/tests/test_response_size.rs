@@ -0,0 +1,165 @@
+//! Integration tests for the response body size limit.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::{
+    CompressionConfig, ContentEncoding, HttpClient, Server, ToolMetadata, config::Authorization,
+};
+use serde_json::json;
+use url::Url;
+
+fn server(base_url: &Url) -> anyhow::Result<Server> {
+    let mut spec = pets::list_pets_spec();
+    spec["paths"]["/pets"]["get"]["responses"]["200"]["content"] = json!({
+        "application/json": {
+            "schema": { "type": "array", "items": { "type": "object" } }
+        }
+    });
+    pets::load(
+        Server::builder()
+            .openapi_spec(spec)
+            .base_url(base_url.clone())
+            .max_response_bytes(100)
+            .build(),
+    )
+}
+
+fn list_pets(server: &Server) -> ToolMetadata {
+    server.get_tool_metadata("listPets").unwrap().clone()
+}
+
+fn large_body() -> String {
+    let pets: Vec<_> = (0..50)
+        .map(|id| json!({ "id": id, "name": format!("pet-{id}") }))
+        .collect();
+    serde_json::to_string(&pets).unwrap()
+}
+
+#[actix_web::test]
+async fn test_large_responses_are_truncated() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = list_pets(&server(&base_url)?);
+    let body = large_body();
+
+    let large = mock_server
+        .mock("GET", "/pets")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(&body)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_max_response_size(100);
+    let response = client.execute_tool_call(&tool, &json!({})).await?;
+    assert_eq!(response.truncated_at, Some(100));
+    assert_eq!(response.body, &body[..100]);
+    assert!(
+        response
+            .to_mcp_content()
+            .contains("response truncated at 100 bytes")
+    );
+
+    // Bodies within the limit are untouched
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_max_response_size(body.len());
+    let response = client.execute_tool_call(&tool, &json!({})).await?;
+    assert_eq!(response.truncated_at, None);
+    assert_eq!(response.body, body);
+
+    large.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_limit_applies_to_decoded_bodies() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = list_pets(&server(&base_url)?);
+    let body = large_body();
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("content-encoding", "gzip")
+        .with_body(ContentEncoding::Gzip.encode(body.as_bytes())?)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_compression(CompressionConfig::default())
+        .with_max_response_size(100);
+    let response = client.execute_tool_call(&tool, &json!({})).await?;
+    assert_eq!(response.truncated_at, Some(100));
+    assert_eq!(response.body, &body[..100]);
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_truncation_is_reported_in_structured_content() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let server = server(&base_url)?;
+    let body = large_body();
+
+    let mock = mock_server
+        .mock("GET", "/pets")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(&body)
+        .create_async()
+        .await;
+
+    let result = server
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .await?;
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(
+        result.structured_content,
+        Some(json!({
+            "status": 200,
+            "body": {
+                "error": {
+                    "type": "response-truncated",
+                    "status": 200,
+                    "limit_bytes": 100,
+                    "partial_body": &body[..100]
+                }
+            }
+        }))
+    );
+
+    mock.assert_async().await;
+    Ok(())
+}
+
+#[test]
+fn test_truncation_is_declared_in_the_output_schema() -> anyhow::Result<()> {
+    let server = server(&Url::parse("https://api.example.com")?)?;
+    let schema = list_pets(&server).output_schema.unwrap();
+
+    let error_types: Vec<_> =
+        schema["properties"]["body"]["oneOf"][1]["properties"]["error"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|variant| variant["properties"]["type"]["const"].clone())
+            .collect();
+    assert!(error_types.contains(&json!("http-error")));
+    assert!(error_types.contains(&json!("response-truncated")));
+    // Only the errors of the features enabled are declared
+    assert!(!error_types.contains(&json!("upstream-unavailable")));
+    assert!(schema["properties"].get("redirect").is_none());
+    Ok(())
+}
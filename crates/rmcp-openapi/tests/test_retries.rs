@@ -0,0 +1,142 @@
+//! Integration tests for the `HttpClient` retry policy.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::{HttpClient, RetryPolicy, Server, ToolMetadata};
+use serde_json::json;
+use std::time::Duration;
+use url::Url;
+
+fn spec() -> serde_json::Value {
+    pets::spec(json!({
+        "/status": {
+            "get": {
+                "operationId": "getStatus",
+                "responses": { "200": { "description": "OK" } }
+            },
+            "post": {
+                "operationId": "postStatus",
+                "responses": { "200": { "description": "OK" } }
+            }
+        }
+    }))
+}
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::builder()
+        .max_retries(2)
+        .initial_backoff(Duration::from_millis(1))
+        .jitter(false)
+        .build()
+}
+
+fn tool(base_url: &Url, name: &str) -> anyhow::Result<ToolMetadata> {
+    let server = pets::load(
+        Server::builder()
+            .openapi_spec(spec())
+            .base_url(base_url.clone())
+            .build(),
+    )?;
+    Ok(server.get_tool_metadata(name).unwrap().clone())
+}
+
+#[actix_web::test]
+async fn test_retries_server_errors_until_success() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let failing = mock_server
+        .mock("GET", "/status")
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+    let healthy = mock_server
+        .mock("GET", "/status")
+        .with_status(200)
+        .with_body(r#"{"ok": true}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_retry_policy(fast_policy());
+    let response = client
+        .execute_tool_call(&tool(&base_url, "getStatus")?, &json!({}))
+        .await?;
+
+    assert_eq!(response.status_code, 200);
+    failing.assert_async().await;
+    healthy.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_gives_up_after_max_retries() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let failing = mock_server
+        .mock("GET", "/status")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_retry_policy(fast_policy());
+    let response = client
+        .execute_tool_call(&tool(&base_url, "getStatus")?, &json!({}))
+        .await?;
+
+    // The last upstream response is surfaced once retries are exhausted
+    assert_eq!(response.status_code, 429);
+    failing.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_post_not_retried_by_default() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+
+    let failing = mock_server
+        .mock("POST", "/status")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_retry_policy(fast_policy());
+    let response = client
+        .execute_tool_call(&tool(&base_url, "postStatus")?, &json!({}))
+        .await?;
+
+    assert_eq!(response.status_code, 503);
+    failing.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_retries_connection_errors() -> anyhow::Result<()> {
+    // Nothing listens on this port once the listener is dropped
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let base_url = Url::parse(&format!("http://{}", listener.local_addr()?))?;
+    drop(listener);
+
+    let client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_retry_policy(fast_policy());
+    let result = client
+        .execute_tool_call(&tool(&base_url, "getStatus")?, &json!({}))
+        .await;
+
+    assert!(result.is_err());
+    Ok(())
+}
@@ -0,0 +1,164 @@
+//! Integration tests for checking forwarded token scopes against operation
+//! security requirements.
+#![cfg(feature = "authorization-token-passthrough")]
+
+mod common;
+
+use base64::prelude::*;
+use common::pets;
+use mockito::Matcher;
+use rmcp_actix_web::transport::AuthorizationHeader;
+use rmcp_openapi::error::ToolCallExecutionError;
+use rmcp_openapi::{
+    Authorization, HttpClient, IntrospectionConfig, ScopeChecker, ScopeSource, SecurityRequirement,
+    Server, Tool, ToolCallError,
+};
+use serde_json::json;
+use std::sync::Arc;
+use url::Url;
+
+fn add_pet_tool(base_url: &Url, scope_check: ScopeSource) -> anyhow::Result<Tool> {
+    let mut spec = pets::spec(json!({
+        "/pets": {
+            "post": {
+                "operationId": "addPet",
+                "security": [{ "petstore_auth": ["pets:read", "pets:write"] }],
+                "responses": { "201": { "description": "Created" } }
+            }
+        }
+    }));
+    spec["components"] = json!({
+        "securitySchemes": {
+            "petstore_auth": {
+                "type": "oauth2",
+                "flows": {
+                    "clientCredentials": {
+                        "tokenUrl": "https://idp.example.com/token",
+                        "scopes": {
+                            "pets:read": "Read pets",
+                            "pets:write": "Modify pets"
+                        }
+                    }
+                }
+            }
+        }
+    });
+    let server = pets::load(
+        Server::builder()
+            .openapi_spec(spec)
+            .base_url(base_url.clone())
+            .build(),
+    )?;
+    let metadata = server.get_tool_metadata("addPet").unwrap().clone();
+    let http_client = HttpClient::new()
+        .with_base_url(base_url.clone())?
+        .with_scope_checker(Arc::new(ScopeChecker::new(scope_check)));
+    Ok(Tool::new(metadata, http_client)?)
+}
+
+fn forwarded(token: &str) -> Authorization {
+    Authorization::PassthroughSilent(Some(AuthorizationHeader(format!("Bearer {token}"))))
+}
+
+#[actix_web::test]
+async fn test_missing_jwt_scope_is_reported() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let upstream = mock_server
+        .mock("POST", "/pets")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let token = format!(
+        "{}.{}.signature",
+        BASE64_URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256"}"#),
+        BASE64_URL_SAFE_NO_PAD.encode(r#"{"sub":"agent","scope":"pets:read"}"#)
+    );
+    let result = add_pet_tool(&base_url, ScopeSource::JwtClaims)?
+        .call(&json!({}), forwarded(&token), None)
+        .await;
+
+    match result {
+        Err(ToolCallError::Execution(ToolCallExecutionError::InsufficientScope {
+            tool,
+            missing,
+        })) => {
+            assert_eq!(tool, "addPet");
+            assert_eq!(
+                missing,
+                SecurityRequirement::from([(
+                    "petstore_auth".to_string(),
+                    vec!["pets:write".to_string()]
+                )])
+            );
+        }
+        other => panic!("expected an insufficient scope error, got {other:?}"),
+    }
+    upstream.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_introspected_scopes_are_cached() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let introspection = mock_server
+        .mock("POST", "/introspect")
+        .match_body(Matcher::UrlEncoded("token".into(), "opaque-token".into()))
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"active":true,"scope":"pets:read pets:write"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let upstream = mock_server
+        .mock("POST", "/pets")
+        .match_header("authorization", "Bearer opaque-token")
+        .with_status(201)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let config = IntrospectionConfig::builder()
+        .url(base_url.join("/introspect")?)
+        .build();
+    let tool = add_pet_tool(&base_url, ScopeSource::Introspection(config))?;
+    for _ in 0..2 {
+        tool.call(&json!({}), forwarded("opaque-token"), None)
+            .await?;
+    }
+
+    introspection.assert_async().await;
+    upstream.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_inactive_token_fails_the_call() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    mock_server
+        .mock("POST", "/introspect")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"active":false}"#)
+        .create_async()
+        .await;
+
+    let config = IntrospectionConfig::builder()
+        .url(base_url.join("/introspect")?)
+        .build();
+    let result = add_pet_tool(&base_url, ScopeSource::Introspection(config))?
+        .call(&json!({}), forwarded("revoked-token"), None)
+        .await;
+
+    assert!(
+        matches!(
+            result,
+            Err(ToolCallError::Execution(
+                ToolCallExecutionError::AuthenticationFailed { .. }
+            ))
+        ),
+        "{result:?}"
+    );
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Integration tests for structured security events.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::{
+    Authorization, FileEventSink, SecurityEvent, SecurityEventKind, SecurityEventSink, Server,
+    ServiceToken,
+};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<SecurityEvent>>);
+
+impl SecurityEventSink for Recorder {
+    fn send(&self, event: &SecurityEvent) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
+
+impl Recorder {
+    fn kinds(&self) -> Vec<SecurityEventKind> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| event.kind.clone())
+            .collect()
+    }
+}
+
+fn server(base_url: &Url, sinks: Vec<Arc<dyn SecurityEventSink>>) -> anyhow::Result<Server> {
+    let mut spec = pets::list_pets_spec();
+    spec["security"] = json!([{ "api_key": [] }]);
+    spec["components"] = json!({
+        "securitySchemes": {
+            "api_key": { "type": "apiKey", "name": "X-API-Key", "in": "header" }
+        }
+    });
+    pets::load(
+        Server::builder()
+            .openapi_spec(spec)
+            .base_url(base_url.clone())
+            .security_event_sinks(sinks)
+            .build(),
+    )
+}
+
+#[actix_web::test]
+async fn test_missing_credentials_are_reported_as_denied() -> anyhow::Result<()> {
+    let mock_server = mockito::Server::new_async().await;
+    let recorder = Arc::new(Recorder::default());
+    let server = server(&Url::parse(&mock_server.url())?, vec![recorder.clone()])?;
+
+    let result = server
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .await;
+    assert!(result.is_err());
+
+    match recorder.kinds().as_slice() {
+        [SecurityEventKind::CallDenied { reason, message }] => {
+            assert_eq!(reason, "authentication-required");
+            assert!(message.contains("api_key"), "{message}");
+        }
+        other => panic!("unexpected events: {other:?}"),
+    }
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_service_token_and_upstream_rejection_are_reported() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    mock_server
+        .mock("GET", "/pets")
+        .with_status(403)
+        .create_async()
+        .await;
+    let path = std::env::temp_dir().join(format!(
+        "rmcp-openapi-security-events-{}.jsonl",
+        std::process::id()
+    ));
+    let recorder = Arc::new(Recorder::default());
+    let file = Arc::new(FileEventSink::new(&path)?);
+    let server = server(
+        &Url::parse(&mock_server.url())?,
+        vec![recorder.clone(), file],
+    )?;
+
+    server
+        .get_tool("listPets")
+        .unwrap()
+        .call_in_session(
+            &json!({}),
+            Authorization::ServiceToken(ServiceToken::new("s3cret")),
+            None,
+            Some("session-1"),
+        )
+        .await?;
+
+    assert_eq!(
+        recorder.kinds(),
+        vec![
+            SecurityEventKind::ServiceToken,
+            SecurityEventKind::UpstreamDenied { status: 403 }
+        ]
+    );
+
+    let lines = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+    let events: Vec<Value> = lines
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["event"], "service-token");
+    assert_eq!(events[0]["session_id"], "session-1");
+    assert_eq!(events[1]["event"], "upstream-denied");
+    assert!(!lines.contains("s3cret"));
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Integration tests for per-session settings.
+
+use rmcp_openapi::{HostAllowlist, Server, SessionSettings};
+use serde_json::json;
+use url::Url;
+
+fn server(session_configuration: bool) -> anyhow::Result<Server> {
+    let mut server = Server::builder()
+        .openapi_spec(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Issues", "version": "1.0.0" },
+            "paths": {}
+        }))
+        .base_url(Url::parse("https://api.example.com/v1")?)
+        .allowed_hosts(HostAllowlist::new(["*.api.example.com"]))
+        .session_configuration(session_configuration)
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server)
+}
+
+fn settings(value: serde_json::Value) -> SessionSettings {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_sessions_have_their_own_settings() -> anyhow::Result<()> {
+    let server = server(true)?;
+    let first = server.for_session();
+    let second = server.for_session();
+
+    first.configure_session(settings(json!({
+        "auth_token": "alice-token",
+        "tags": ["issues"]
+    })))?;
+    let updated = first.configure_session(settings(json!({
+        "base_url": "https://eu.api.example.com/v1"
+    })))?;
+
+    assert_eq!(updated.auth_token.as_deref(), Some("alice-token"));
+    assert_eq!(updated.tags, Some(vec!["issues".to_string()]));
+    assert_eq!(first.session_settings(), updated);
+    assert_eq!(second.session_settings(), SessionSettings::default());
+    assert_eq!(server.session_settings(), SessionSettings::default());
+    Ok(())
+}
+
+#[test]
+fn test_base_url_must_stay_on_allowed_hosts() -> anyhow::Result<()> {
+    let session = server(true)?.for_session();
+    for base_url in ["https://api.example.com/v2", "https://eu.api.example.com"] {
+        session.configure_session(settings(json!({ "base_url": base_url })))?;
+    }
+    for base_url in ["https://attacker.example.net", "file:///etc/passwd"] {
+        assert!(
+            session
+                .configure_session(settings(json!({ "base_url": base_url })))
+                .is_err(),
+            "{base_url}"
+        );
+    }
+    assert_eq!(
+        session.session_settings().base_url.unwrap().as_str(),
+        "https://eu.api.example.com/"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_configuration_must_be_enabled() -> anyhow::Result<()> {
+    let session = server(false)?.for_session();
+    assert!(
+        session
+            .configure_session(settings(json!({ "tags": ["issues"] })))
+            .is_err()
+    );
+    assert_eq!(session.session_settings(), SessionSettings::default());
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! Integration tests for server-side request timeout limits.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::error::{NetworkErrorCategory, ToolCallExecutionError};
+use rmcp_openapi::{HttpClient, Server, TimeoutPolicy, ToolCallError, ToolMetadata};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use url::Url;
+
+/// Accept connections but never answer them
+async fn start_unresponsive_server() -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+    tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+    url
+}
+
+fn tool(base_url: &Url) -> anyhow::Result<ToolMetadata> {
+    let server = pets::load(
+        Server::builder()
+            .openapi_spec(pets::spec(json!({
+                "/reports": {
+                    "get": {
+                        "operationId": "exportReport",
+                        "tags": ["Reports"],
+                        "parameters": [{
+                            "name": "format",
+                            "in": "query",
+                            "schema": { "type": "string" }
+                        }],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            })))
+            .base_url(base_url.clone())
+            .build(),
+    )?;
+    Ok(server.get_tool_metadata("exportReport").unwrap().clone())
+}
+
+#[actix_web::test]
+async fn test_tag_timeout_caps_caller_timeout() -> anyhow::Result<()> {
+    let base_url = start_unresponsive_server().await;
+    let tool = tool(&base_url)?;
+    assert_eq!(tool.tags, vec!["Reports".to_string()]);
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_timeout_policy(
+            TimeoutPolicy::builder()
+                .per_tag(HashMap::from([(
+                    "reports".to_string(),
+                    Duration::from_millis(200),
+                )]))
+                .build(),
+        );
+
+    let start = Instant::now();
+    let result = client
+        .execute_tool_call(&tool, &json!({ "timeout_seconds": 120 }))
+        .await;
+    assert!(start.elapsed() < Duration::from_secs(5));
+    match result {
+        Err(ToolCallError::Execution(ToolCallExecutionError::NetworkError {
+            message,
+            category: NetworkErrorCategory::Timeout,
+        })) => assert!(message.contains("0.2 seconds"), "{message}"),
+        other => panic!("expected timeout error, got {other:?}"),
+    }
+    Ok(())
+}
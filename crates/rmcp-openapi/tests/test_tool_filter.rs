@@ -76,6 +76,7 @@ fn create_test_server() -> Server {
         output_schema: None,
         method: "GET".to_string(),
         path: "/pet/{petId}".to_string(),
+        tags: vec![],
         security: None,
         parameter_mappings: std::collections::HashMap::new(),
     };
@@ -96,6 +97,7 @@ fn create_test_server() -> Server {
         output_schema: None,
         method: "POST".to_string(),
         path: "/pet".to_string(),
+        tags: vec![],
         security: None,
         parameter_mappings: std::collections::HashMap::new(),
     };
@@ -111,6 +113,7 @@ fn create_test_server() -> Server {
         output_schema: None,
         method: "GET".to_string(),
         path: "/store/inventory".to_string(),
+        tags: vec![],
         security: None,
         parameter_mappings: std::collections::HashMap::new(),
     };
@@ -131,6 +134,7 @@ fn create_test_server() -> Server {
         output_schema: None,
         method: "DELETE".to_string(),
         path: "/pet/{petId}".to_string(),
+        tags: vec![],
         security: None,
         parameter_mappings: std::collections::HashMap::new(),
     };
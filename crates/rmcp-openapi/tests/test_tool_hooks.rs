@@ -0,0 +1,59 @@
+//! Integration tests for hooks rewriting generated tools.
+
+mod common;
+
+use common::pets;
+use rmcp_openapi::{Server, ToolHook, ToolMetadata};
+use serde_json::json;
+use std::sync::Arc;
+use url::Url;
+
+/// Hook hiding a parameter the upstream defaults well
+struct HideVerbose;
+
+impl ToolHook for HideVerbose {
+    fn process(&self, tool: &mut ToolMetadata) {
+        if let Some(properties) = tool.parameters["properties"].as_object_mut() {
+            properties.remove("verbose");
+        }
+    }
+}
+
+#[test]
+fn test_hooks_rewrite_tools_in_order() -> anyhow::Result<()> {
+    let prefix: Arc<dyn ToolHook> = Arc::new(|tool: &mut ToolMetadata| {
+        tool.description = Some(format!("Pets: {}", tool.name));
+    });
+    let suffix: Arc<dyn ToolHook> = Arc::new(|tool: &mut ToolMetadata| {
+        if let Some(description) = tool.description.as_mut() {
+            description.push_str(" (beta)");
+        }
+    });
+    let mut server = Server::builder()
+        .openapi_spec(pets::spec(json!({
+            "/pets": {
+                "get": {
+                    "operationId": "listPets",
+                    "parameters": [
+                        { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+                        { "name": "verbose", "in": "query", "schema": { "type": "boolean" } }
+                    ],
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        })))
+        .base_url(Url::parse("http://127.0.0.1:9")?)
+        .tool_hooks(vec![prefix, Arc::new(HideVerbose), suffix])
+        .build();
+    server.load_openapi_spec()?;
+
+    let metadata = server.get_tool_metadata("listPets").unwrap();
+    assert_eq!(
+        metadata.description.as_deref(),
+        Some("Pets: listPets (beta)")
+    );
+    let properties = metadata.parameters["properties"].as_object().unwrap();
+    assert!(properties.contains_key("limit"));
+    assert!(!properties.contains_key("verbose"));
+    Ok(())
+}
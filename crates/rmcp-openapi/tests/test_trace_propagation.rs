@@ -0,0 +1,113 @@
+//! Integration tests for W3C trace context propagation to upstream APIs.
+#![cfg(feature = "opentelemetry")]
+
+mod common;
+
+use common::pets;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use rmcp_openapi::{Authorization, CacheConfig, CacheStatus, HttpClient, TraceContext};
+use serde_json::json;
+use tracing::{Instrument, info_span};
+use tracing_subscriber::layer::SubscriberExt;
+use url::Url;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+#[actix_web::test]
+async fn test_upstream_requests_continue_the_trace() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let server = pets::server(&Url::parse(&mock_server.url())?)?;
+    let traced = mock_server
+        .mock("GET", "/pets")
+        .match_header(
+            "traceparent",
+            mockito::Matcher::Regex(format!("^00-{TRACE_ID}-[0-9a-f]{{16}}-01$")),
+        )
+        .match_header("tracestate", "vendor=1")
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let provider = SdkTracerProvider::builder().build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let incoming = TraceContext::new(
+        &format!("00-{TRACE_ID}-00f067aa0ba902b7-01"),
+        Some("vendor=1"),
+    )
+    .unwrap();
+    let span = info_span!("call_tool");
+    incoming.set_parent_of(&span);
+    server
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .instrument(span)
+        .await?;
+
+    traced.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_no_context_is_sent_without_a_trace() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let server = pets::server(&Url::parse(&mock_server.url())?)?;
+    let untraced = mock_server
+        .mock("GET", "/pets")
+        .match_header("traceparent", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    server
+        .get_tool("listPets")
+        .unwrap()
+        .call(&json!({}), Authorization::None, None)
+        .await?;
+
+    untraced.assert_async().await;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_traced_calls_are_served_from_cache() -> anyhow::Result<()> {
+    let mut mock_server = mockito::Server::new_async().await;
+    let base_url = Url::parse(&mock_server.url())?;
+    let tool = pets::list_pets_tool(&base_url)?;
+    let pets = mock_server
+        .mock("GET", "/pets")
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let provider = SdkTracerProvider::builder().build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let client = HttpClient::new()
+        .with_base_url(base_url)?
+        .with_response_cache(CacheConfig::default());
+    let mut statuses = Vec::new();
+    for _ in 0..2 {
+        let response = client
+            .execute_tool_call(&tool, &json!({}))
+            .instrument(info_span!("call_tool"))
+            .await?;
+        statuses.push(response.cache_status);
+    }
+    assert_eq!(statuses, [Some(CacheStatus::Miss), Some(CacheStatus::Hit)]);
+
+    pets.assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Integration tests for upstream APIs served on a Unix domain socket.
+#![cfg(unix)]
+
+use rmcp_openapi::{Server, config::Authorization};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixListener;
+use url::Url;
+
+/// Serve HTTP on a fresh socket, answering with the request line as JSON
+fn start_unix_server(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("rmcp-openapi-{}-{name}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let request_line = request.lines().next().unwrap_or_default();
+                let body = json!({ "request": request_line }).to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    path
+}
+
+fn docker_server(base_url: Url) -> anyhow::Result<Server> {
+    let mut server = Server::builder()
+        .openapi_spec(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Docker Engine API", "version": "1.43" },
+            "paths": {
+                "/containers/json": {
+                    "get": {
+                        "operationId": "ContainerList",
+                        "parameters": [{
+                            "name": "all",
+                            "in": "query",
+                            "schema": { "type": "boolean" }
+                        }],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        }))
+        .base_url(base_url)
+        .build();
+    server.load_openapi_spec()?;
+    Ok(server)
+}
+
+async fn request_line(server: &Server) -> anyhow::Result<String> {
+    let response = server
+        .get_tool("ContainerList")
+        .unwrap()
+        .execute(&json!({ "all": true }), Authorization::None)
+        .await?;
+    assert_eq!(response.status_code, 200);
+    Ok(response.json()?["request"].as_str().unwrap().to_string())
+}
+
+fn encoded(path: &Path) -> String {
+    path.to_str().unwrap().replace('/', "%2F")
+}
+
+#[actix_web::test]
+async fn test_unix_base_url() -> anyhow::Result<()> {
+    let socket = start_unix_server("plain");
+    let server = docker_server(Url::parse(&format!("unix://{}", socket.display()))?)?;
+
+    assert_eq!(
+        request_line(&server).await?,
+        "GET /containers/json?all=true HTTP/1.1"
+    );
+
+    std::fs::remove_file(socket)?;
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test_http_unix_base_url_keeps_base_path() -> anyhow::Result<()> {
+    let socket = start_unix_server("versioned");
+    let server = docker_server(Url::parse(&format!(
+        "http+unix://{}/v1.43",
+        encoded(&socket)
+    ))?)?;
+
+    assert_eq!(
+        request_line(&server).await?,
+        "GET /v1.43/containers/json?all=true HTTP/1.1"
+    );
+
+    std::fs::remove_file(socket)?;
+    Ok(())
+}
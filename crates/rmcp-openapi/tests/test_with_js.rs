@@ -33,6 +33,7 @@ fn create_petstore_mcp_server_with_spec(base_url: Url, spec_path: &str) -> anyho
         false, // skip_parameter_descriptions
         false, // parameter_examples_in_description
         false, // insecure
+        false, // strip_non_standard_bodies
     )?;
 
     let mut server = Server::builder()